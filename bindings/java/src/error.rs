@@ -51,6 +51,7 @@ impl Error {
             ErrorKind::ConfigInvalid => "ConfigInvalid",
             ErrorKind::NotFound => "NotFound",
             ErrorKind::PermissionDenied => "PermissionDenied",
+            ErrorKind::Unauthenticated => "Unauthenticated",
             ErrorKind::IsADirectory => "IsADirectory",
             ErrorKind::NotADirectory => "NotADirectory",
             ErrorKind::AlreadyExists => "AlreadyExists",
@@ -65,6 +65,7 @@ public class OpenDALException extends RuntimeException {
         ConfigInvalid,
         NotFound,
         PermissionDenied,
+        Unauthenticated,
         IsADirectory,
         NotADirectory,
         AlreadyExists,
@@ -0,0 +1,796 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use bytes::BytesMut;
+use fuser::BackgroundSession;
+use fuser::FileAttr;
+use fuser::FileType;
+use fuser::Filesystem;
+use fuser::MountOption;
+use fuser::ReplyAttr;
+use fuser::ReplyCreate;
+use fuser::ReplyData;
+use fuser::ReplyDirectory;
+use fuser::ReplyEmpty;
+use fuser::ReplyEntry;
+use fuser::ReplyOpen;
+use fuser::ReplyWrite;
+use fuser::Request;
+use fuser::TimeOrNow;
+use tokio::runtime::Handle;
+
+use super::inode::*;
+use crate::*;
+
+const BLOCK_SIZE: u32 = 4096;
+const DEFAULT_ATTR_TTL: Duration = Duration::from_secs(1);
+const DEFAULT_WRITE_BUFFER: usize = 8 * 1024 * 1024;
+
+/// Consistency controls how [`FuseGateway`] caches attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Consistency {
+    /// Attributes will be cached for `attr_ttl` both in gateway and kernel.
+    ///
+    /// Changes made by others may be invisible until the cache expired.
+    #[default]
+    Relaxed,
+    /// Attributes will never be cached, every lookup will stat the service.
+    Strict,
+}
+
+/// FuseGateway mounts an [`Operator`] as a read-write FUSE filesystem, so
+/// that all services supported by OpenDAL become mountable.
+///
+/// # Notes
+///
+/// OpenDAL doesn't support random write, so:
+///
+/// - Files must be opened for writing with `O_TRUNC`, content is written
+///   sequentially into a [`Writer`] and committed while the file is closed.
+///   Opening existing files with `O_APPEND` or without `O_TRUNC` returns
+///   `EOPNOTSUPP` instead of destroying their content.
+/// - Writes will be buffered in memory until the buffer reaches
+///   `write_buffer` bytes, see [`FuseGateway::with_write_buffer`].
+/// - Writes that are not appended to the end return `ENOTSUP`.
+/// - Only truncating to size `0` is supported.
+///
+/// Renaming dirs is not supported.
+///
+/// # Examples
+///
+/// ```no_run
+/// use opendal::gateways::FuseGateway;
+/// use opendal::services;
+/// use opendal::Operator;
+/// use tokio::runtime::Handle;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let op = Operator::new(services::Memory::default())?.finish();
+///     let fs = FuseGateway::new(op, Handle::current());
+///
+///     // Keep the session alive to keep the filesystem mounted.
+///     let _session = fs.spawn_mount("/tmp/opendal")?;
+///     tokio::signal::ctrl_c().await?;
+///     Ok(())
+/// }
+/// ```
+pub struct FuseGateway {
+    op: Operator,
+    rt: Handle,
+    attr_ttl: Duration,
+    consistency: Consistency,
+    write_buffer: usize,
+    uid: u32,
+    gid: u32,
+
+    inodes: Inodes,
+    attrs: HashMap<u64, (FileAttr, Instant)>,
+    handles: HashMap<u64, FileHandle>,
+    next_fh: u64,
+}
+
+impl Debug for FuseGateway {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FuseGateway")
+            .field("attr_ttl", &self.attr_ttl)
+            .field("consistency", &self.consistency)
+            .field("write_buffer", &self.write_buffer)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FuseGateway {
+    /// Create a new gateway for given operator.
+    ///
+    /// FUSE requests are handled in blocking threads, futures of operator
+    /// will be executed on given tokio runtime.
+    pub fn new(op: Operator, rt: Handle) -> Self {
+        Self {
+            op,
+            rt,
+            attr_ttl: DEFAULT_ATTR_TTL,
+            consistency: Consistency::default(),
+            write_buffer: DEFAULT_WRITE_BUFFER,
+            uid: 0,
+            gid: 0,
+
+            inodes: Inodes::default(),
+            attrs: HashMap::new(),
+            handles: HashMap::new(),
+            next_fh: 1,
+        }
+    }
+
+    /// Set the ttl of cached attributes.
+    ///
+    /// Default to 1s, not used under [`Consistency::Strict`].
+    pub fn with_attr_ttl(mut self, ttl: Duration) -> Self {
+        self.attr_ttl = ttl;
+        self
+    }
+
+    /// Set the consistency of this filesystem.
+    ///
+    /// Default to [`Consistency::Relaxed`].
+    pub fn with_consistency(mut self, consistency: Consistency) -> Self {
+        self.consistency = consistency;
+        self
+    }
+
+    /// Set the size of write buffer, writes will be sent to service once
+    /// buffered content reaches this size.
+    ///
+    /// Default to 8 MiB.
+    pub fn with_write_buffer(mut self, size: usize) -> Self {
+        self.write_buffer = size;
+        self
+    }
+
+    /// Set the owner of all files.
+    ///
+    /// Default to `0` (root).
+    pub fn with_owner(mut self, uid: u32, gid: u32) -> Self {
+        self.uid = uid;
+        self.gid = gid;
+        self
+    }
+
+    /// Mount at given mountpoint, blocks until the filesystem is unmounted.
+    pub fn mount(self, mountpoint: impl AsRef<Path>) -> io::Result<()> {
+        fuser::mount2(self, mountpoint, &mount_options())
+    }
+
+    /// Mount at given mountpoint in background, the filesystem will be
+    /// unmounted once returned session dropped.
+    pub fn spawn_mount(self, mountpoint: impl AsRef<Path>) -> io::Result<BackgroundSession> {
+        fuser::spawn_mount2(self, mountpoint, &mount_options())
+    }
+
+    fn ttl(&self) -> Duration {
+        match self.consistency {
+            Consistency::Relaxed => self.attr_ttl,
+            Consistency::Strict => Duration::ZERO,
+        }
+    }
+
+    fn cache_attr(&mut self, attr: FileAttr) {
+        if self.consistency == Consistency::Relaxed {
+            self.attrs.insert(attr.ino, (attr, Instant::now()));
+        }
+    }
+
+    fn build_attr(&self, ino: u64, meta: &Metadata) -> FileAttr {
+        let mtime = meta
+            .last_modified()
+            .map(SystemTime::from)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let (kind, perm, size) = if meta.is_dir() {
+            (FileType::Directory, 0o755, 0)
+        } else {
+            (FileType::RegularFile, 0o644, meta.content_length())
+        };
+
+        FileAttr {
+            ino,
+            size,
+            blocks: (size + BLOCK_SIZE as u64 - 1) / BLOCK_SIZE as u64,
+            atime: mtime,
+            mtime,
+            ctime: mtime,
+            crtime: mtime,
+            kind,
+            perm,
+            nlink: 1,
+            uid: self.uid,
+            gid: self.gid,
+            rdev: 0,
+            blksize: BLOCK_SIZE,
+            flags: 0,
+        }
+    }
+
+    /// Stat given path and cache its attribute.
+    fn stat(&mut self, path: &str) -> Result<FileAttr> {
+        let meta = self.rt.block_on(self.op.stat(path))?;
+        let ino = self.inodes.get_or_insert(path);
+        let attr = self.build_attr(ino, &meta);
+        self.cache_attr(attr);
+        Ok(attr)
+    }
+
+    /// Lookup child of given dir, the child could be a file or a dir.
+    fn lookup_child(&mut self, parent: u64, name: &OsStr) -> Result<FileAttr> {
+        let path = self.child_path(parent, name, false)?;
+        match self.stat(&path) {
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                let path = self.child_path(parent, name, true)?;
+                self.stat(&path)
+            }
+            res => res,
+        }
+    }
+
+    fn get_attr(&mut self, ino: u64) -> Result<FileAttr> {
+        if let Some((attr, cached_at)) = self.attrs.get(&ino) {
+            if cached_at.elapsed() < self.attr_ttl {
+                return Ok(*attr);
+            }
+        }
+
+        let path = self.path(ino)?.to_string();
+        self.stat(&path)
+    }
+
+    fn path(&self, ino: u64) -> Result<&str> {
+        self.inodes
+            .path(ino)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "inode doesn't exist"))
+    }
+
+    fn child_path(&self, parent: u64, name: &OsStr, is_dir: bool) -> Result<String> {
+        let name = name
+            .to_str()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "file name is not valid utf-8"))?;
+        Ok(build_child_path(self.path(parent)?, name, is_dir))
+    }
+
+    fn forget_path(&mut self, path: &str) {
+        if let Some(ino) = self.inodes.remove(path) {
+            self.attrs.remove(&ino);
+        }
+    }
+
+    fn new_fh(&mut self, handle: FileHandle) -> u64 {
+        let fh = self.next_fh;
+        self.next_fh += 1;
+        self.handles.insert(fh, handle);
+        fh
+    }
+
+    /// Open a writer for given file, the file will be truncated.
+    fn open_writer(&mut self, ino: u64) -> Result<u64> {
+        let path = self.path(ino)?.to_string();
+        let writer = self.rt.block_on(self.op.writer(&path))?;
+        self.attrs.remove(&ino);
+
+        Ok(self.new_fh(FileHandle::Writer {
+            writer,
+            buf: BytesMut::new(),
+            written: 0,
+        }))
+    }
+
+    fn write_handle(&mut self, fh: u64, offset: i64, data: &[u8]) -> Result<()> {
+        let (writer, buf, written) = match self.handles.get_mut(&fh) {
+            Some(FileHandle::Writer {
+                writer,
+                buf,
+                written,
+            }) => (writer, buf, written),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::PermissionDenied,
+                    "file is not opened for writing",
+                ))
+            }
+        };
+        if offset as u64 != *written {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "only sequential write is supported",
+            ));
+        }
+
+        buf.extend_from_slice(data);
+        *written += data.len() as u64;
+        if buf.len() >= self.write_buffer {
+            let bs = buf.split().freeze();
+            self.rt.block_on(writer.write(bs))?;
+        }
+        Ok(())
+    }
+
+    fn release_handle(&mut self, ino: u64, fh: u64) -> Result<()> {
+        let handle = self.handles.remove(&fh);
+        self.attrs.remove(&ino);
+
+        match handle {
+            Some(FileHandle::Writer {
+                mut writer, buf, ..
+            }) => self.rt.block_on(async move {
+                if !buf.is_empty() {
+                    writer.write(buf.freeze()).await?;
+                }
+                writer.close().await
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn list_dir(&mut self, ino: u64) -> Result<Vec<(u64, FileType, String)>> {
+        let path = self.path(ino)?.to_string();
+        let entries = self.rt.block_on(
+            self.op
+                .list_with(&path)
+                .metakey(Metakey::Mode | Metakey::ContentLength | Metakey::LastModified),
+        )?;
+
+        let mut children = vec![
+            (ino, FileType::Directory, ".".to_string()),
+            (
+                self.inodes.get_or_insert(&parent_path(&path)),
+                FileType::Directory,
+                "..".to_string(),
+            ),
+        ];
+        for entry in entries {
+            // Skip the dir itself which could be returned by some services.
+            if entry.path() == path {
+                continue;
+            }
+            let child = self.inodes.get_or_insert(entry.path());
+            let attr = self.build_attr(child, entry.metadata());
+            self.cache_attr(attr);
+            children.push((
+                child,
+                attr.kind,
+                entry.name().trim_end_matches('/').to_string(),
+            ));
+        }
+        Ok(children)
+    }
+
+    fn remove_dir(&mut self, parent: u64, name: &OsStr) -> Result<()> {
+        let path = self.child_path(parent, name, true)?;
+        let entries = self.rt.block_on(self.op.list(&path))?;
+        if entries.iter().any(|e| e.path() != path) {
+            return Err(Error::new(ErrorKind::DirectoryNotEmpty, "dir is not empty"));
+        }
+        self.rt.block_on(self.op.delete(&path))?;
+        self.forget_path(&path);
+        Ok(())
+    }
+}
+
+impl Filesystem for FuseGateway {
+    fn lookup(&mut self, _: &Request<'_>, parent: u64, name: &OsStr, reply: ReplyEntry) {
+        match self.lookup_child(parent, name) {
+            Ok(attr) => reply.entry(&self.ttl(), &attr, 0),
+            Err(err) => reply.error(format_errno(&err)),
+        }
+    }
+
+    fn getattr(&mut self, _: &Request<'_>, ino: u64, reply: ReplyAttr) {
+        match self.get_attr(ino) {
+            Ok(attr) => reply.attr(&self.ttl(), &attr),
+            Err(err) => reply.error(format_errno(&err)),
+        }
+    }
+
+    fn setattr(
+        &mut self,
+        _: &Request<'_>,
+        ino: u64,
+        _mode: Option<u32>,
+        _uid: Option<u32>,
+        _gid: Option<u32>,
+        size: Option<u64>,
+        _atime: Option<TimeOrNow>,
+        _mtime: Option<TimeOrNow>,
+        _ctime: Option<SystemTime>,
+        fh: Option<u64>,
+        _crtime: Option<SystemTime>,
+        _chgtime: Option<SystemTime>,
+        _bkuptime: Option<SystemTime>,
+        _flags: Option<u32>,
+        reply: ReplyAttr,
+    ) {
+        let res = match size {
+            None => self.get_attr(ino),
+            // The file has been truncated while opening the writer.
+            Some(0) if fh.is_some() => self.get_attr(ino).map(|attr| FileAttr {
+                size: 0,
+                blocks: 0,
+                ..attr
+            }),
+            Some(0) => self.path(ino).map(|v| v.to_string()).and_then(|path| {
+                self.rt.block_on(self.op.write(&path, vec![]))?;
+                self.stat(&path)
+            }),
+            Some(_) => Err(Error::new(
+                ErrorKind::Unsupported,
+                "only truncating to size 0 is supported",
+            )),
+        };
+
+        match res {
+            Ok(attr) => reply.attr(&self.ttl(), &attr),
+            Err(err) => reply.error(format_errno(&err)),
+        }
+    }
+
+    fn mkdir(
+        &mut self,
+        _: &Request<'_>,
+        parent: u64,
+        name: &OsStr,
+        _mode: u32,
+        _umask: u32,
+        reply: ReplyEntry,
+    ) {
+        let res = self.child_path(parent, name, true).and_then(|path| {
+            self.rt.block_on(self.op.create_dir(&path))?;
+            self.stat(&path)
+        });
+
+        match res {
+            Ok(attr) => reply.entry(&self.ttl(), &attr, 0),
+            Err(err) => reply.error(format_errno(&err)),
+        }
+    }
+
+    fn unlink(&mut self, _: &Request<'_>, parent: u64, name: &OsStr, reply: ReplyEmpty) {
+        let res = self.child_path(parent, name, false).and_then(|path| {
+            self.rt.block_on(self.op.delete(&path))?;
+            self.forget_path(&path);
+            Ok(())
+        });
+
+        match res {
+            Ok(()) => reply.ok(),
+            Err(err) => reply.error(format_errno(&err)),
+        }
+    }
+
+    fn rmdir(&mut self, _: &Request<'_>, parent: u64, name: &OsStr, reply: ReplyEmpty) {
+        match self.remove_dir(parent, name) {
+            Ok(()) => reply.ok(),
+            Err(err) => reply.error(format_errno(&err)),
+        }
+    }
+
+    fn rename(
+        &mut self,
+        _: &Request<'_>,
+        parent: u64,
+        name: &OsStr,
+        newparent: u64,
+        newname: &OsStr,
+        _flags: u32,
+        reply: ReplyEmpty,
+    ) {
+        let res = self.lookup_child(parent, name).and_then(|attr| {
+            if attr.kind == FileType::Directory {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "rename dir is not supported",
+                ));
+            }
+            let from = self.child_path(parent, name, false)?;
+            let to = self.child_path(newparent, newname, false)?;
+            self.rt.block_on(self.op.rename(&from, &to))?;
+            self.forget_path(&from);
+            self.forget_path(&to);
+            Ok(())
+        });
+
+        match res {
+            Ok(()) => reply.ok(),
+            Err(err) => reply.error(format_errno(&err)),
+        }
+    }
+
+    fn open(&mut self, _: &Request<'_>, ino: u64, flags: i32, reply: ReplyOpen) {
+        let res = open_mode(flags).and_then(|mode| match mode {
+            OpenMode::Read => Ok(self.new_fh(FileHandle::Reader)),
+            OpenMode::Truncate => self.open_writer(ino),
+        });
+
+        match res {
+            Ok(fh) => reply.opened(fh, 0),
+            Err(err) => reply.error(format_errno(&err)),
+        }
+    }
+
+    fn read(
+        &mut self,
+        _: &Request<'_>,
+        ino: u64,
+        _fh: u64,
+        offset: i64,
+        size: u32,
+        _flags: i32,
+        _lock_owner: Option<u64>,
+        reply: ReplyData,
+    ) {
+        let res = self.path(ino).map(|v| v.to_string()).and_then(|path| {
+            let offset = offset as u64;
+            self.rt
+                .block_on(self.op.read_with(&path).range(offset..offset + size as u64))
+        });
+
+        match res {
+            Ok(bs) => reply.data(&bs),
+            Err(err) => reply.error(format_errno(&err)),
+        }
+    }
+
+    fn write(
+        &mut self,
+        _: &Request<'_>,
+        _ino: u64,
+        fh: u64,
+        offset: i64,
+        data: &[u8],
+        _write_flags: u32,
+        _flags: i32,
+        _lock_owner: Option<u64>,
+        reply: ReplyWrite,
+    ) {
+        match self.write_handle(fh, offset, data) {
+            Ok(()) => reply.written(data.len() as u32),
+            Err(err) => reply.error(format_errno(&err)),
+        }
+    }
+
+    fn release(
+        &mut self,
+        _: &Request<'_>,
+        ino: u64,
+        fh: u64,
+        _flags: i32,
+        _lock_owner: Option<u64>,
+        _flush: bool,
+        reply: ReplyEmpty,
+    ) {
+        match self.release_handle(ino, fh) {
+            Ok(()) => reply.ok(),
+            Err(err) => reply.error(format_errno(&err)),
+        }
+    }
+
+    fn opendir(&mut self, _: &Request<'_>, ino: u64, _flags: i32, reply: ReplyOpen) {
+        match self.list_dir(ino) {
+            Ok(entries) => reply.opened(self.new_fh(FileHandle::Dir(entries)), 0),
+            Err(err) => reply.error(format_errno(&err)),
+        }
+    }
+
+    fn readdir(
+        &mut self,
+        _: &Request<'_>,
+        _ino: u64,
+        fh: u64,
+        offset: i64,
+        mut reply: ReplyDirectory,
+    ) {
+        let entries = match self.handles.get(&fh) {
+            Some(FileHandle::Dir(entries)) => entries,
+            _ => return reply.error(libc::EBADF),
+        };
+
+        for (idx, (ino, kind, name)) in entries.iter().enumerate().skip(offset as usize) {
+            // Offset is the position of the next entry.
+            if reply.add(*ino, idx as i64 + 1, *kind, name) {
+                break;
+            }
+        }
+        reply.ok()
+    }
+
+    fn releasedir(&mut self, _: &Request<'_>, _ino: u64, fh: u64, _flags: i32, reply: ReplyEmpty) {
+        self.handles.remove(&fh);
+        reply.ok()
+    }
+
+    fn create(
+        &mut self,
+        _: &Request<'_>,
+        parent: u64,
+        name: &OsStr,
+        _mode: u32,
+        _umask: u32,
+        _flags: i32,
+        reply: ReplyCreate,
+    ) {
+        let res = self.child_path(parent, name, false).and_then(|path| {
+            // Write an empty file so that it's visible before closed.
+            self.rt.block_on(self.op.write(&path, vec![]))?;
+            let attr = self.stat(&path)?;
+            let fh = self.open_writer(attr.ino)?;
+            Ok((attr, fh))
+        });
+
+        match res {
+            Ok((attr, fh)) => reply.created(&self.ttl(), &attr, 0, fh, 0),
+            Err(err) => reply.error(format_errno(&err)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OpenMode {
+    Read,
+    /// Truncate the file and write it from the beginning.
+    Truncate,
+}
+
+/// Decide how to open an existing file by given flags.
+///
+/// OpenDAL can only write a file from scratch, flags that require keeping
+/// the existing content will be rejected.
+fn open_mode(flags: i32) -> Result<OpenMode> {
+    let truncate = flags & libc::O_TRUNC != 0;
+    if flags & libc::O_APPEND != 0 {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "open file with O_APPEND is not supported",
+        ));
+    }
+
+    match flags & libc::O_ACCMODE {
+        libc::O_RDONLY if !truncate => Ok(OpenMode::Read),
+        _ if truncate => Ok(OpenMode::Truncate),
+        _ => Err(Error::new(
+            ErrorKind::Unsupported,
+            "open file for writing without O_TRUNC is not supported",
+        )),
+    }
+}
+
+enum FileHandle {
+    Reader,
+    Writer {
+        writer: Writer,
+        buf: BytesMut,
+        written: u64,
+    },
+    Dir(Vec<(u64, FileType, String)>),
+}
+
+fn mount_options() -> Vec<MountOption> {
+    vec![
+        MountOption::FSName("opendal".to_string()),
+        MountOption::DefaultPermissions,
+    ]
+}
+
+fn format_errno(err: &Error) -> libc::c_int {
+    match err.kind() {
+        ErrorKind::NotFound => libc::ENOENT,
+        ErrorKind::PermissionDenied | ErrorKind::Unauthenticated => libc::EACCES,
+        ErrorKind::AlreadyExists => libc::EEXIST,
+        ErrorKind::IsADirectory => libc::EISDIR,
+        ErrorKind::NotADirectory => libc::ENOTDIR,
+        ErrorKind::DirectoryNotEmpty => libc::ENOTEMPTY,
+        ErrorKind::InvalidInput => libc::EINVAL,
+        ErrorKind::Unsupported => libc::ENOTSUP,
+        _ => libc::EIO,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::runtime::Runtime;
+
+    use super::*;
+    use crate::services::Memory;
+
+    #[test]
+    fn test_open_mode() {
+        let cases = vec![
+            ("read", libc::O_RDONLY, Some(OpenMode::Read)),
+            (
+                "write with trunc",
+                libc::O_WRONLY | libc::O_TRUNC,
+                Some(OpenMode::Truncate),
+            ),
+            (
+                "read write with trunc",
+                libc::O_RDWR | libc::O_TRUNC,
+                Some(OpenMode::Truncate),
+            ),
+            ("write without trunc", libc::O_WRONLY, None),
+            ("read write without trunc", libc::O_RDWR, None),
+            ("append", libc::O_WRONLY | libc::O_APPEND, None),
+            (
+                "append with trunc",
+                libc::O_RDWR | libc::O_APPEND | libc::O_TRUNC,
+                None,
+            ),
+        ];
+
+        for (name, flags, expect) in cases {
+            match (open_mode(flags), expect) {
+                (Ok(mode), Some(expect)) => assert_eq!(mode, expect, "{name}"),
+                (Err(err), None) => {
+                    assert_eq!(err.kind(), ErrorKind::Unsupported, "{name}");
+                    assert_eq!(format_errno(&err), libc::EOPNOTSUPP, "{name}");
+                }
+                (res, _) => panic!("{name}: unexpected result {res:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_write_and_release() -> Result<()> {
+        let rt = Runtime::new().expect("runtime must be created");
+        let op = Operator::new(Memory::default())?.finish();
+        rt.block_on(op.write("file", "old content"))?;
+
+        let mut fs = FuseGateway::new(op.clone(), rt.handle().clone()).with_write_buffer(4);
+        let ino = fs.stat("file")?.ino;
+
+        let fh = fs.open_writer(ino)?;
+        fs.write_handle(fh, 0, b"Hello, ")?;
+        let err = fs
+            .write_handle(fh, 0, b"World!")
+            .expect_err("random write must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        fs.write_handle(fh, 7, b"World!")?;
+        fs.release_handle(ino, fh)?;
+
+        assert_eq!(rt.block_on(op.read("file"))?, b"Hello, World!");
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_to_reader() -> Result<()> {
+        let rt = Runtime::new().expect("runtime must be created");
+        let op = Operator::new(Memory::default())?.finish();
+        rt.block_on(op.write("file", "content"))?;
+
+        let mut fs = FuseGateway::new(op.clone(), rt.handle().clone());
+        let fh = fs.new_fh(FileHandle::Reader);
+        let err = fs.write_handle(fh, 0, b"x").expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        assert_eq!(rt.block_on(op.read("file"))?, b"content");
+        Ok(())
+    }
+}
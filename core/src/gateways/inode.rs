@@ -0,0 +1,107 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+
+/// The inode of root dir.
+pub(super) const ROOT_INO: u64 = 1;
+
+/// Inodes maintains the mapping between inodes and paths.
+///
+/// Dir paths always end with `/`, and the root is `/`.
+pub(super) struct Inodes {
+    paths: HashMap<u64, String>,
+    inodes: HashMap<String, u64>,
+    next: u64,
+}
+
+impl Default for Inodes {
+    fn default() -> Self {
+        let mut inodes = Self {
+            paths: HashMap::new(),
+            inodes: HashMap::new(),
+            next: ROOT_INO,
+        };
+        inodes.get_or_insert("/");
+        inodes
+    }
+}
+
+impl Inodes {
+    pub(super) fn path(&self, ino: u64) -> Option<&str> {
+        self.paths.get(&ino).map(|v| v.as_str())
+    }
+
+    pub(super) fn get_or_insert(&mut self, path: &str) -> u64 {
+        if let Some(ino) = self.inodes.get(path) {
+            return *ino;
+        }
+
+        let ino = self.next;
+        self.next += 1;
+        self.paths.insert(ino, path.to_string());
+        self.inodes.insert(path.to_string(), ino);
+        ino
+    }
+
+    pub(super) fn remove(&mut self, path: &str) -> Option<u64> {
+        let ino = self.inodes.remove(path)?;
+        self.paths.remove(&ino);
+        Some(ino)
+    }
+}
+
+/// Build the path of child `name` under dir `parent`.
+pub(super) fn build_child_path(parent: &str, name: &str, is_dir: bool) -> String {
+    let parent = parent.trim_start_matches('/');
+    if is_dir {
+        format!("{parent}{name}/")
+    } else {
+        format!("{parent}{name}")
+    }
+}
+
+/// Get the parent dir of given path.
+pub(super) fn parent_path(path: &str) -> String {
+    match path.trim_end_matches('/').rfind('/') {
+        Some(idx) => path[..=idx].to_string(),
+        None => "/".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inodes() {
+        let mut inodes = Inodes::default();
+        assert_eq!(inodes.path(ROOT_INO), Some("/"));
+
+        let dir = build_child_path("/", "dir", true);
+        assert_eq!(dir, "dir/");
+        let file = build_child_path(&dir, "file", false);
+        assert_eq!(file, "dir/file");
+        assert_eq!(parent_path(&file), "dir/");
+        assert_eq!(parent_path(&dir), "/");
+
+        let ino = inodes.get_or_insert(&file);
+        assert_eq!(inodes.get_or_insert(&file), ino);
+        assert_eq!(inodes.remove(&file), Some(ino));
+        assert_eq!(inodes.path(ino), None);
+    }
+}
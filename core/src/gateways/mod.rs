@@ -0,0 +1,48 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Gateways serve an [`Operator`](crate::Operator) over well-known protocols.
+//!
+//! Gateways are disabled by default, please enable them via features like
+//! `gateways-s3`, `gateways-webdav`, `gateways-fuse` and `gateways-nfs`.
+
+#[cfg(feature = "gateways-s3")]
+mod s3;
+#[cfg(feature = "gateways-s3")]
+pub use s3::S3Gateway;
+
+#[cfg(feature = "gateways-webdav")]
+mod webdav;
+#[cfg(feature = "gateways-webdav")]
+pub use webdav::WebdavGateway;
+
+#[cfg(all(unix, feature = "gateways-fuse"))]
+mod fuse;
+#[cfg(all(unix, feature = "gateways-fuse"))]
+pub use fuse::Consistency;
+#[cfg(all(unix, feature = "gateways-fuse"))]
+pub use fuse::FuseGateway;
+
+#[cfg(feature = "gateways-nfs")]
+mod nfs;
+#[cfg(feature = "gateways-nfs")]
+pub use nfs::NfsGateway;
+
+#[cfg(any(all(unix, feature = "gateways-fuse"), feature = "gateways-nfs"))]
+mod inode;
+#[cfg(any(feature = "gateways-s3", feature = "gateways-webdav"))]
+mod util;
@@ -0,0 +1,727 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::lock::Mutex;
+use nfsserve::nfs::fattr3;
+use nfsserve::nfs::fileid3;
+use nfsserve::nfs::filename3;
+use nfsserve::nfs::ftype3;
+use nfsserve::nfs::nfspath3;
+use nfsserve::nfs::nfsstat3;
+use nfsserve::nfs::nfstime3;
+use nfsserve::nfs::sattr3;
+use nfsserve::nfs::set_size3;
+use nfsserve::nfs::specdata3;
+use nfsserve::tcp::NFSTcp;
+use nfsserve::tcp::NFSTcpListener;
+use nfsserve::vfs::DirEntry;
+use nfsserve::vfs::NFSFileSystem;
+use nfsserve::vfs::ReadDirResult;
+use nfsserve::vfs::VFSCapabilities;
+
+use super::inode::*;
+use crate::*;
+
+const DEFAULT_WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+const DEFAULT_MAX_BUFFER_SIZE: usize = 64 * 1024 * 1024;
+
+/// NfsGateway serves an [`Operator`] over NFSv3 in user-space, so that all
+/// services supported by OpenDAL become network-mountable, even in
+/// containers where FUSE is unavailable.
+///
+/// # Notes
+///
+/// NFS doesn't have `open` and `close`, so:
+///
+/// - The first write of a file starts a new [`Writer`] that replaces the
+///   file, truncating is done by `setattr` with size `0`.
+/// - Writes can arrive out of order, they will be buffered in memory until
+///   they can be appended sequentially. `NFS3ERR_JUKEBOX` will be returned
+///   to let clients retry later once the buffer of a file is full.
+/// - Writes that have been written already are taken as retransmissions
+///   and ignored.
+/// - Content is committed after no more writes arrive in `write_timeout`, or
+///   once the file is read, renamed or removed.
+///
+/// Symlinks and renaming dirs are not supported.
+///
+/// # Examples
+///
+/// ```no_run
+/// use opendal::gateways::NfsGateway;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let op = Operator::new(services::Memory::default())?.finish();
+///
+///     // Mount via `mount -t nfs -o nolocks,vers=3,tcp,port=11111,mountport=11111,soft 127.0.0.1:/ /mnt`
+///     NfsGateway::new(op).serve("127.0.0.1:11111").await?;
+///     Ok(())
+/// }
+/// ```
+pub struct NfsGateway {
+    op: Operator,
+    write_timeout: Duration,
+    max_buffer_size: usize,
+
+    inodes: Mutex<Inodes>,
+    /// The map lock is never held across IO, while the lock of each file
+    /// serializes writes to it.
+    pending: Arc<Mutex<HashMap<fileid3, Arc<Mutex<PendingWrite>>>>>,
+}
+
+impl Debug for NfsGateway {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NfsGateway")
+            .field("write_timeout", &self.write_timeout)
+            .field("max_buffer_size", &self.max_buffer_size)
+            .finish_non_exhaustive()
+    }
+}
+
+/// PendingWrite is a file that is being written but not committed yet.
+struct PendingWrite {
+    path: String,
+    writer: Writer,
+    written: u64,
+    parts: BTreeMap<u64, Bytes>,
+    /// The size of out of order parts.
+    buffered: usize,
+    updated_at: Instant,
+}
+
+impl PendingWrite {
+    async fn write(
+        &mut self,
+        offset: u64,
+        data: &[u8],
+        max_buffer_size: usize,
+    ) -> std::result::Result<(), nfsstat3> {
+        self.updated_at = Instant::now();
+
+        let end = offset + data.len() as u64;
+        if end <= self.written {
+            // Retransmission of data that has been written.
+            return Ok(());
+        }
+        if offset < self.written {
+            return Err(nfsstat3::NFS3ERR_NOTSUPP);
+        }
+        if offset > self.written {
+            let replaced = self.parts.get(&offset).map(|v| v.len()).unwrap_or_default();
+            if self.buffered - replaced + data.len() > max_buffer_size {
+                return Err(nfsstat3::NFS3ERR_JUKEBOX);
+            }
+            self.buffered = self.buffered - replaced + data.len();
+            self.parts.insert(offset, Bytes::copy_from_slice(data));
+            return Ok(());
+        }
+
+        self.written = end;
+        self.writer
+            .write(Bytes::copy_from_slice(data))
+            .await
+            .map_err(format_nfsstat)?;
+        while let Some(bs) = self.parts.remove(&self.written) {
+            self.buffered -= bs.len();
+            self.written += bs.len() as u64;
+            self.writer.write(bs).await.map_err(format_nfsstat)?;
+        }
+        Ok(())
+    }
+
+    fn size(&self) -> u64 {
+        self.parts
+            .iter()
+            .next_back()
+            .map(|(offset, bs)| offset + bs.len() as u64)
+            .unwrap_or(self.written)
+            .max(self.written)
+    }
+}
+
+impl NfsGateway {
+    /// Create a new gateway for given operator.
+    pub fn new(op: Operator) -> Self {
+        Self {
+            op,
+            write_timeout: DEFAULT_WRITE_TIMEOUT,
+            max_buffer_size: DEFAULT_MAX_BUFFER_SIZE,
+
+            inodes: Mutex::new(Inodes::default()),
+            pending: Arc::default(),
+        }
+    }
+
+    /// Set the timeout after which pending writes will be committed.
+    ///
+    /// Default to 5s.
+    pub fn with_write_timeout(mut self, timeout: Duration) -> Self {
+        self.write_timeout = timeout;
+        self
+    }
+
+    /// Set the max size of out of order writes buffered for each file.
+    ///
+    /// Default to 64MiB.
+    pub fn with_max_buffer_size(mut self, size: usize) -> Self {
+        self.max_buffer_size = size;
+        self
+    }
+
+    /// Serve at given address like `127.0.0.1:11111`, runs until error
+    /// happens.
+    pub async fn serve(self, addr: &str) -> io::Result<()> {
+        let pending = self.pending.clone();
+        let timeout = self.write_timeout;
+        let committer = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(timeout / 2).await;
+                commit_expired(&pending, timeout).await;
+            }
+        });
+
+        let listener = NFSTcpListener::bind(addr, self).await?;
+        let res = listener.handle_forever().await;
+        committer.abort();
+        res
+    }
+
+    async fn path(&self, id: fileid3) -> std::result::Result<String, nfsstat3> {
+        self.inodes
+            .lock()
+            .await
+            .path(id)
+            .map(|v| v.to_string())
+            .ok_or(nfsstat3::NFS3ERR_STALE)
+    }
+
+    async fn child_path(
+        &self,
+        dirid: fileid3,
+        name: &filename3,
+        is_dir: bool,
+    ) -> std::result::Result<String, nfsstat3> {
+        let name = std::str::from_utf8(name).map_err(|_| nfsstat3::NFS3ERR_INVAL)?;
+        Ok(build_child_path(&self.path(dirid).await?, name, is_dir))
+    }
+
+    async fn stat(&self, path: &str) -> std::result::Result<(fileid3, fattr3), nfsstat3> {
+        let meta = self.op.stat(path).await.map_err(format_nfsstat)?;
+        let id = self.inodes.lock().await.get_or_insert(path);
+        let mut attr = build_attr(id, &meta);
+
+        let pending = self.pending.lock().await.get(&id).cloned();
+        if let Some(p) = pending {
+            attr.size = p.lock().await.size();
+            attr.used = attr.size;
+        }
+        Ok((id, attr))
+    }
+
+    /// Get the pending write of given file, a new one will be started if
+    /// not exists.
+    async fn pending_write(
+        &self,
+        id: fileid3,
+        path: &str,
+        offset: u64,
+    ) -> std::result::Result<Arc<Mutex<PendingWrite>>, nfsstat3> {
+        if let Some(p) = self.pending.lock().await.get(&id) {
+            return Ok(p.clone());
+        }
+
+        // Writes could arrive out of order, but we can't modify existing
+        // content in place.
+        if offset != 0 {
+            let meta = self.op.stat(path).await.map_err(format_nfsstat)?;
+            if meta.content_length() != 0 {
+                return Err(nfsstat3::NFS3ERR_NOTSUPP);
+            }
+        }
+
+        let writer = self.op.writer(path).await.map_err(format_nfsstat)?;
+        let p = Arc::new(Mutex::new(PendingWrite {
+            path: path.to_string(),
+            writer,
+            written: 0,
+            parts: BTreeMap::new(),
+            buffered: 0,
+            updated_at: Instant::now(),
+        }));
+        // Another write could have started the file while we are creating
+        // the writer, use that one instead.
+        Ok(self.pending.lock().await.entry(id).or_insert(p).clone())
+    }
+
+    /// Lookup child of given dir, the child could be a file or a dir.
+    async fn lookup_child(
+        &self,
+        dirid: fileid3,
+        name: &filename3,
+    ) -> std::result::Result<(fileid3, fattr3), nfsstat3> {
+        let path = self.child_path(dirid, name, false).await?;
+        match self.stat(&path).await {
+            Err(nfsstat3::NFS3ERR_NOENT) => {
+                let path = self.child_path(dirid, name, true).await?;
+                self.stat(&path).await
+            }
+            res => res,
+        }
+    }
+
+    /// Commit pending write of given file if exists.
+    async fn commit(&self, id: fileid3) -> std::result::Result<(), nfsstat3> {
+        let pending = self.pending.lock().await.remove(&id);
+        match pending {
+            Some(p) => commit_pending(&mut *p.lock().await)
+                .await
+                .map_err(format_nfsstat),
+            None => Ok(()),
+        }
+    }
+
+    async fn forget(&self, path: &str) {
+        if let Some(id) = self.inodes.lock().await.remove(path) {
+            self.pending.lock().await.remove(&id);
+        }
+    }
+}
+
+#[async_trait]
+impl NFSFileSystem for NfsGateway {
+    fn capabilities(&self) -> VFSCapabilities {
+        VFSCapabilities::ReadWrite
+    }
+
+    fn root_dir(&self) -> fileid3 {
+        ROOT_INO
+    }
+
+    async fn lookup(
+        &self,
+        dirid: fileid3,
+        filename: &filename3,
+    ) -> std::result::Result<fileid3, nfsstat3> {
+        match filename.as_ref() {
+            b"." => Ok(dirid),
+            b".." => {
+                let path = parent_path(&self.path(dirid).await?);
+                Ok(self.inodes.lock().await.get_or_insert(&path))
+            }
+            _ => self.lookup_child(dirid, filename).await.map(|(id, _)| id),
+        }
+    }
+
+    async fn getattr(&self, id: fileid3) -> std::result::Result<fattr3, nfsstat3> {
+        let path = self.path(id).await?;
+        self.stat(&path).await.map(|(_, attr)| attr)
+    }
+
+    async fn setattr(&self, id: fileid3, setattr: sattr3) -> std::result::Result<fattr3, nfsstat3> {
+        let path = self.path(id).await?;
+        match setattr.size {
+            set_size3::Void => {}
+            set_size3::size(0) => {
+                self.pending.lock().await.remove(&id);
+                self.op.write(&path, vec![]).await.map_err(format_nfsstat)?;
+            }
+            set_size3::size(_) => return Err(nfsstat3::NFS3ERR_NOTSUPP),
+        }
+
+        self.stat(&path).await.map(|(_, attr)| attr)
+    }
+
+    async fn read(
+        &self,
+        id: fileid3,
+        offset: u64,
+        count: u32,
+    ) -> std::result::Result<(Vec<u8>, bool), nfsstat3> {
+        self.commit(id).await?;
+
+        let path = self.path(id).await?;
+        let (_, attr) = self.stat(&path).await?;
+        if offset >= attr.size {
+            return Ok((vec![], true));
+        }
+
+        let end = (offset + count as u64).min(attr.size);
+        let bs = self
+            .op
+            .read_with(&path)
+            .range(offset..end)
+            .await
+            .map_err(format_nfsstat)?;
+        Ok((bs, end >= attr.size))
+    }
+
+    async fn write(
+        &self,
+        id: fileid3,
+        offset: u64,
+        data: &[u8],
+    ) -> std::result::Result<fattr3, nfsstat3> {
+        let path = self.path(id).await?;
+
+        let p = self.pending_write(id, &path, offset).await?;
+        p.lock()
+            .await
+            .write(offset, data, self.max_buffer_size)
+            .await?;
+
+        self.stat(&path).await.map(|(_, attr)| attr)
+    }
+
+    async fn create(
+        &self,
+        dirid: fileid3,
+        filename: &filename3,
+        _attr: sattr3,
+    ) -> std::result::Result<(fileid3, fattr3), nfsstat3> {
+        let path = self.child_path(dirid, filename, false).await?;
+        self.op.write(&path, vec![]).await.map_err(format_nfsstat)?;
+        self.stat(&path).await
+    }
+
+    async fn create_exclusive(
+        &self,
+        dirid: fileid3,
+        filename: &filename3,
+    ) -> std::result::Result<fileid3, nfsstat3> {
+        match self.lookup_child(dirid, filename).await {
+            Ok(_) => Err(nfsstat3::NFS3ERR_EXIST),
+            Err(nfsstat3::NFS3ERR_NOENT) => self
+                .create(dirid, filename, sattr3::default())
+                .await
+                .map(|(id, _)| id),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn mkdir(
+        &self,
+        dirid: fileid3,
+        dirname: &filename3,
+    ) -> std::result::Result<(fileid3, fattr3), nfsstat3> {
+        let path = self.child_path(dirid, dirname, true).await?;
+        self.op.create_dir(&path).await.map_err(format_nfsstat)?;
+        self.stat(&path).await
+    }
+
+    async fn remove(
+        &self,
+        dirid: fileid3,
+        filename: &filename3,
+    ) -> std::result::Result<(), nfsstat3> {
+        let (id, attr) = self.lookup_child(dirid, filename).await?;
+        let path = self.path(id).await?;
+
+        if attr.ftype == ftype3::NF3DIR {
+            let entries = self.op.list(&path).await.map_err(format_nfsstat)?;
+            if entries.iter().any(|e| e.path() != path) {
+                return Err(nfsstat3::NFS3ERR_NOTEMPTY);
+            }
+        } else {
+            self.commit(id).await?;
+        }
+
+        self.op.delete(&path).await.map_err(format_nfsstat)?;
+        self.forget(&path).await;
+        Ok(())
+    }
+
+    async fn rename(
+        &self,
+        from_dirid: fileid3,
+        from_filename: &filename3,
+        to_dirid: fileid3,
+        to_filename: &filename3,
+    ) -> std::result::Result<(), nfsstat3> {
+        let (id, attr) = self.lookup_child(from_dirid, from_filename).await?;
+        if attr.ftype == ftype3::NF3DIR {
+            return Err(nfsstat3::NFS3ERR_NOTSUPP);
+        }
+        self.commit(id).await?;
+
+        let from = self.path(id).await?;
+        let to = self.child_path(to_dirid, to_filename, false).await?;
+        self.op.rename(&from, &to).await.map_err(format_nfsstat)?;
+        self.forget(&from).await;
+        self.forget(&to).await;
+        Ok(())
+    }
+
+    async fn readdir(
+        &self,
+        dirid: fileid3,
+        start_after: fileid3,
+        max_entries: usize,
+    ) -> std::result::Result<ReadDirResult, nfsstat3> {
+        let path = self.path(dirid).await?;
+        let entries = self
+            .op
+            .list_with(&path)
+            .metakey(Metakey::Mode | Metakey::ContentLength | Metakey::LastModified)
+            .await
+            .map_err(format_nfsstat)?;
+
+        let mut result = ReadDirResult {
+            entries: vec![],
+            end: true,
+        };
+        let mut started = start_after == 0;
+        for entry in entries {
+            // Skip the dir itself which could be returned by some services.
+            if entry.path() == path {
+                continue;
+            }
+            let id = self.inodes.lock().await.get_or_insert(entry.path());
+            if !started {
+                started = id == start_after;
+                continue;
+            }
+            if result.entries.len() >= max_entries {
+                result.end = false;
+                break;
+            }
+            result.entries.push(DirEntry {
+                fileid: id,
+                name: entry
+                    .name()
+                    .trim_end_matches('/')
+                    .as_bytes()
+                    .to_vec()
+                    .into(),
+                attr: build_attr(id, entry.metadata()),
+            });
+        }
+        // The entry of cookie has been removed, clients should restart
+        // reading the dir instead of taking it as the end.
+        if !started {
+            return Err(nfsstat3::NFS3ERR_BAD_COOKIE);
+        }
+        Ok(result)
+    }
+
+    async fn symlink(
+        &self,
+        _dirid: fileid3,
+        _linkname: &filename3,
+        _symlink: &nfspath3,
+        _attr: &sattr3,
+    ) -> std::result::Result<(fileid3, fattr3), nfsstat3> {
+        Err(nfsstat3::NFS3ERR_NOTSUPP)
+    }
+
+    async fn readlink(&self, _id: fileid3) -> std::result::Result<nfspath3, nfsstat3> {
+        Err(nfsstat3::NFS3ERR_NOTSUPP)
+    }
+}
+
+async fn commit_pending(p: &mut PendingWrite) -> Result<()> {
+    if !p.parts.is_empty() {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "file has holes that never been written",
+        )
+        .with_context("path", &p.path));
+    }
+    p.writer.close().await
+}
+
+/// Commit all pending writes that haven't been updated in `timeout`.
+async fn commit_expired(
+    pending: &Mutex<HashMap<fileid3, Arc<Mutex<PendingWrite>>>>,
+    timeout: Duration,
+) {
+    let expired: Vec<_> = {
+        let mut pending = pending.lock().await;
+        // Files that are locked are being written, they are not expired.
+        let ids: Vec<_> = pending
+            .iter()
+            .filter(|(_, p)| {
+                p.try_lock()
+                    .map(|p| p.updated_at.elapsed() >= timeout)
+                    .unwrap_or_default()
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        ids.into_iter()
+            .filter_map(|id| pending.remove(&id))
+            .collect()
+    };
+
+    for p in expired {
+        let mut p = p.lock().await;
+        if let Err(err) = commit_pending(&mut p).await {
+            log::warn!("nfs gateway commit {} failed: {err}", p.path);
+        }
+    }
+}
+
+fn build_attr(id: fileid3, meta: &Metadata) -> fattr3 {
+    let mtime = meta
+        .last_modified()
+        .map(|v| nfstime3 {
+            seconds: v.timestamp() as u32,
+            nseconds: v.timestamp_subsec_nanos(),
+        })
+        .unwrap_or_default();
+    let (ftype, mode, size) = if meta.is_dir() {
+        (ftype3::NF3DIR, 0o755, 0)
+    } else {
+        (ftype3::NF3REG, 0o644, meta.content_length())
+    };
+
+    fattr3 {
+        ftype,
+        mode,
+        nlink: 1,
+        uid: 0,
+        gid: 0,
+        size,
+        used: size,
+        rdev: specdata3::default(),
+        fsid: 0,
+        fileid: id,
+        atime: mtime,
+        mtime,
+        ctime: mtime,
+    }
+}
+
+fn format_nfsstat(err: Error) -> nfsstat3 {
+    match err.kind() {
+        ErrorKind::NotFound => nfsstat3::NFS3ERR_NOENT,
+        ErrorKind::PermissionDenied | ErrorKind::Unauthenticated => nfsstat3::NFS3ERR_ACCES,
+        ErrorKind::AlreadyExists => nfsstat3::NFS3ERR_EXIST,
+        ErrorKind::IsADirectory => nfsstat3::NFS3ERR_ISDIR,
+        ErrorKind::NotADirectory => nfsstat3::NFS3ERR_NOTDIR,
+        ErrorKind::DirectoryNotEmpty => nfsstat3::NFS3ERR_NOTEMPTY,
+        ErrorKind::InvalidInput => nfsstat3::NFS3ERR_INVAL,
+        ErrorKind::Unsupported => nfsstat3::NFS3ERR_NOTSUPP,
+        _ => nfsstat3::NFS3ERR_IO,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_out_of_order_write() -> std::result::Result<(), nfsstat3> {
+        let op = Operator::new(Memory::default())
+            .expect("must init")
+            .finish();
+        let fs = NfsGateway::new(op.clone());
+
+        let (id, _) = fs
+            .create(ROOT_INO, &b"file".to_vec().into(), sattr3::default())
+            .await?;
+        fs.write(id, 0, b"Hello").await?;
+        let attr = fs.write(id, 7, b"World!").await?;
+        assert_eq!(attr.size, 13);
+        fs.write(id, 5, b", ").await?;
+
+        let (bs, eof) = fs.read(id, 0, 1024).await?;
+        assert_eq!(bs, b"Hello, World!");
+        assert!(eof);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retransmitted_write() -> std::result::Result<(), nfsstat3> {
+        let op = Operator::new(Memory::default())
+            .expect("must init")
+            .finish();
+        let fs = NfsGateway::new(op.clone());
+
+        let (id, _) = fs
+            .create(ROOT_INO, &b"file".to_vec().into(), sattr3::default())
+            .await?;
+        fs.write(id, 0, b"Hello, ").await?;
+        fs.write(id, 7, b"World!").await?;
+        // Retransmitted write at offset 0 must not drop written data.
+        fs.write(id, 0, b"Hello, ").await?;
+
+        let (bs, _) = fs.read(id, 0, 1024).await?;
+        assert_eq!(bs, b"Hello, World!");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_buffer_limit() -> std::result::Result<(), nfsstat3> {
+        let op = Operator::new(Memory::default())
+            .expect("must init")
+            .finish();
+        let fs = NfsGateway::new(op.clone()).with_max_buffer_size(8);
+
+        let (id, _) = fs
+            .create(ROOT_INO, &b"file".to_vec().into(), sattr3::default())
+            .await?;
+        fs.write(id, 4, b"4567").await?;
+        assert!(matches!(
+            fs.write(id, 8, b"89abc").await,
+            Err(nfsstat3::NFS3ERR_JUKEBOX)
+        ));
+        // Writes that can be appended are never limited.
+        fs.write(id, 0, b"0123").await?;
+        fs.write(id, 8, b"89abc").await?;
+
+        let (bs, _) = fs.read(id, 0, 1024).await?;
+        assert_eq!(bs, b"0123456789abc");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_readdir_with_vanished_cookie() -> std::result::Result<(), nfsstat3> {
+        let op = Operator::new(Memory::default())
+            .expect("must init")
+            .finish();
+        let fs = NfsGateway::new(op.clone());
+        for name in ["a", "b", "c"] {
+            op.write(name, "x").await.map_err(format_nfsstat)?;
+        }
+
+        let rd = fs.readdir(ROOT_INO, 0, 1).await?;
+        assert!(!rd.end);
+        let cookie = rd.entries[0].fileid;
+
+        let name = fs.path(cookie).await?;
+        op.delete(&name).await.map_err(format_nfsstat)?;
+        assert!(matches!(
+            fs.readdir(ROOT_INO, cookie, 1).await,
+            Err(nfsstat3::NFS3ERR_BAD_COOKIE)
+        ));
+        Ok(())
+    }
+}
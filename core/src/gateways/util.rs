@@ -0,0 +1,104 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use chrono::DateTime;
+use chrono::Utc;
+use http::header;
+use http::HeaderMap;
+
+use crate::*;
+
+/// Parse the `Range` header into inclusive `(start, end)` for content of
+/// `total` size.
+///
+/// Returns `None` if request doesn't have a range.
+pub(super) fn parse_range_header(headers: &HeaderMap, total: u64) -> Result<Option<(u64, u64)>> {
+    let v = match headers.get(header::RANGE) {
+        None => return Ok(None),
+        Some(v) => v.to_str().map_err(|err| {
+            Error::new(ErrorKind::InvalidInput, "header range is invalid")
+                .with_context("code", "InvalidRange")
+                .set_source(err)
+        })?,
+    };
+
+    parse_range(v, total).map(Some)
+}
+
+/// Parse range like `bytes=0-9` into inclusive `(start, end)` for content of
+/// `total` size.
+pub(super) fn parse_range(v: &str, total: u64) -> Result<(u64, u64)> {
+    let invalid_range = || {
+        Error::new(ErrorKind::InvalidInput, "range is not satisfiable")
+            .with_context("code", "InvalidRange")
+            .with_context("range", v)
+    };
+
+    let (start, end) = v
+        .strip_prefix("bytes=")
+        .and_then(|v| v.split_once('-'))
+        .ok_or_else(invalid_range)?;
+
+    let (start, end) = match (start, end) {
+        ("", suffix) => {
+            let suffix: u64 = suffix.parse().map_err(|_| invalid_range())?;
+            (total.saturating_sub(suffix), total.saturating_sub(1))
+        }
+        (start, "") => (
+            start.parse().map_err(|_| invalid_range())?,
+            total.saturating_sub(1),
+        ),
+        (start, end) => (
+            start.parse().map_err(|_| invalid_range())?,
+            end.parse::<u64>()
+                .map_err(|_| invalid_range())?
+                .min(total.saturating_sub(1)),
+        ),
+    };
+
+    if total == 0 || start > end || start >= total {
+        return Err(invalid_range());
+    }
+    Ok((start, end))
+}
+
+/// Format time in the format used by http headers like `Last-Modified`.
+pub(super) fn format_http_date(t: DateTime<Utc>) -> String {
+    t.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+pub(super) fn new_xml_serialize_error(err: quick_xml::DeError) -> Error {
+    Error::new(ErrorKind::Unexpected, "serialize xml").set_source(err)
+}
+
+pub(super) fn new_http_build_error(err: http::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, "build http response").set_source(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-9", 100).unwrap(), (0, 9));
+        assert_eq!(parse_range("bytes=90-", 100).unwrap(), (90, 99));
+        assert_eq!(parse_range("bytes=-10", 100).unwrap(), (90, 99));
+        assert_eq!(parse_range("bytes=90-200", 100).unwrap(), (90, 99));
+        assert!(parse_range("bytes=100-", 100).is_err());
+    }
+}
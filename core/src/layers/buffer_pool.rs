@@ -0,0 +1,248 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io::SeekFrom;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+
+use crate::raw::*;
+use crate::*;
+
+/// Account and bound memory used by readers and writers via [`BufferPool`].
+///
+/// # Notes
+///
+/// - Writers with `buffer` set hold the buffer size from the pool until
+///   they are dropped, other writers hold the size of every write until
+///   it's accepted by the service.
+/// - Readers hold the size of every read until it's returned.
+/// - Blocking operations are not accounted.
+/// - Buffers are not recycled between streams, see [`BufferPool`] for
+///   details.
+///
+/// Share the same pool between operators to bound their memory usage
+/// together.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::BufferPoolLayer;
+/// use opendal::services;
+/// use opendal::BufferPool;
+/// use opendal::Operator;
+///
+/// let pool = BufferPool::with_capacity(512 * 1024 * 1024);
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(BufferPoolLayer::new(pool.clone()))
+///     .finish();
+/// ```
+#[derive(Clone)]
+pub struct BufferPoolLayer {
+    pool: BufferPool,
+}
+
+impl BufferPoolLayer {
+    /// Create a new BufferPoolLayer with given pool.
+    pub fn new(pool: BufferPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl<A: Accessor> Layer<A> for BufferPoolLayer {
+    type LayeredAccessor = BufferPoolAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        BufferPoolAccessor {
+            inner,
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BufferPoolAccessor<A: Accessor> {
+    inner: A,
+    pool: BufferPool,
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for BufferPoolAccessor<A> {
+    type Inner = A;
+    type Reader = BufferPoolWrapper<A::Reader>;
+    type BlockingReader = A::BlockingReader;
+    type Writer = BufferPoolWrapper<A::Writer>;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner
+            .read(path, args)
+            .await
+            .map(|(rp, r)| (rp, BufferPoolWrapper::new(r, self.pool.clone(), None)))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let reserved = match args.buffer() {
+            Some(size) => Some(self.pool.acquire(size).await),
+            None => None,
+        };
+
+        self.inner
+            .write(path, args)
+            .await
+            .map(|(rp, w)| (rp, BufferPoolWrapper::new(w, self.pool.clone(), reserved)))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+pub struct BufferPoolWrapper<R> {
+    inner: R,
+    pool: BufferPool,
+
+    /// Hold on this permit until this wrapper has been dropped.
+    reserved: Option<BufferPermit>,
+    /// The permit for the ongoing read or write.
+    permit: Option<BufferPermit>,
+    acquiring: Option<BoxFuture<'static, BufferPermit>>,
+}
+
+/// # Safety
+///
+/// We will only take `&mut Self` reference for BufferPoolWrapper.
+unsafe impl<R: Sync> Sync for BufferPoolWrapper<R> {}
+
+impl<R> BufferPoolWrapper<R> {
+    fn new(inner: R, pool: BufferPool, reserved: Option<BufferPermit>) -> Self {
+        Self {
+            inner,
+            pool,
+            reserved,
+            permit: None,
+            acquiring: None,
+        }
+    }
+
+    /// Acquire bytes for the ongoing operation if not reserved.
+    fn poll_acquire(&mut self, cx: &mut Context<'_>, size: usize) -> Poll<()> {
+        if self.reserved.is_some() || self.permit.is_some() {
+            return Poll::Ready(());
+        }
+
+        let pool = self.pool.clone();
+        let fut = self
+            .acquiring
+            .get_or_insert_with(|| async move { pool.acquire(size).await }.boxed());
+        let permit = ready!(fut.poll_unpin(cx));
+        self.acquiring = None;
+        self.permit = Some(permit);
+        Poll::Ready(())
+    }
+}
+
+impl<R: oio::Read> oio::Read for BufferPoolWrapper<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        ready!(self.poll_acquire(cx, buf.len()));
+
+        let res = ready!(self.inner.poll_read(cx, buf));
+        self.permit = None;
+        Poll::Ready(res)
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        self.inner.poll_seek(cx, pos)
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        self.inner.poll_next(cx)
+    }
+}
+
+impl<R: oio::Write> oio::Write for BufferPoolWrapper<R> {
+    fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        ready!(self.poll_acquire(cx, bs.remaining()));
+
+        let res = ready!(self.inner.poll_write(cx, bs));
+        self.permit = None;
+        Poll::Ready(res)
+    }
+
+    fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_close(cx)
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_abort(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_buffer_pool_layer() -> Result<()> {
+        let pool = BufferPool::with_capacity(1024 * 1024);
+        let op = Operator::new(Memory::default())?
+            .layer(BufferPoolLayer::new(pool.clone()))
+            .finish();
+
+        let mut w = op.writer_with("test").buffer(4096).await?;
+        assert_eq!(pool.used(), 4096);
+        w.write("Hello, World!").await?;
+        w.close().await?;
+        drop(w);
+        assert_eq!(pool.used(), 0);
+
+        assert_eq!(op.read("test").await?, b"Hello, World!");
+        assert!(pool.peak() >= 4096);
+        assert_eq!(pool.used(), 0);
+        Ok(())
+    }
+}
@@ -152,6 +152,63 @@ impl<A: Accessor> CompleteAccessor<A> {
         .with_operation(op)
     }
 
+    /// `must_exist` checks the path via the delete response or `stat`,
+    /// neither of them can tell if any entry matches the prefix.
+    fn check_delete_must_exist(&self, args: &OpDelete) -> Result<()> {
+        if args.prefix() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "must_exist can't be used with prefix",
+            )
+            .with_operation(Operation::Delete));
+        }
+        Ok(())
+    }
+
+    fn check_copy_args(&self, args: &OpCopy) -> Result<()> {
+        let capability = self.meta.full_capability();
+        let checks = [
+            (
+                args.storage_class().is_some(),
+                capability.copy_with_storage_class,
+                "storage class",
+            ),
+            (
+                args.content_type().is_some(),
+                capability.copy_with_content_type,
+                "content type",
+            ),
+            (
+                args.cache_control().is_some(),
+                capability.copy_with_cache_control,
+                "cache control",
+            ),
+            (
+                args.user_metadata().is_some(),
+                capability.copy_with_user_metadata,
+                "user metadata",
+            ),
+            (args.tags().is_some(), capability.copy_with_tags, "tags"),
+            (
+                args.source_url().is_some(),
+                capability.copy_from_url,
+                "source url",
+            ),
+        ];
+        for (set, supported, feature) in checks {
+            if set && !supported {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    &format!(
+                        "service {} doesn't support operation copy with {feature}",
+                        self.info().scheme()
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
     async fn complete_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         let capability = self.meta.full_capability();
         if capability.create_dir {
@@ -185,9 +242,39 @@ impl<A: Accessor> CompleteAccessor<A> {
         if !capability.stat {
             return Err(self.new_unsupported_error(Operation::Stat));
         }
+        if args.signer().is_some() && !capability.stat_with_signer {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation stat with signer",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.snapshot().is_some() && !capability.stat_with_snapshot {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation stat with snapshot",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.version().is_some() && !capability.stat_with_version {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation stat with version",
+                    self.info().scheme()
+                ),
+            ));
+        }
 
         if path == "/" {
-            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+            if !capability.stat_root {
+                return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+            }
+            return complete_stat_root(self.inner.stat(path, args).await);
         }
 
         // Forward to inner if create_dir is supported.
@@ -240,7 +327,10 @@ impl<A: Accessor> CompleteAccessor<A> {
         }
 
         if path == "/" {
-            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+            if !capability.stat_root {
+                return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+            }
+            return complete_stat_root(self.inner.blocking_stat(path, args));
         }
 
         // Forward to inner if create dir is supported.
@@ -292,6 +382,42 @@ impl<A: Accessor> CompleteAccessor<A> {
         if !capability.read {
             return Err(self.new_unsupported_error(Operation::Read));
         }
+        if args.signer().is_some() && !capability.read_with_signer {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation read with signer",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.snapshot().is_some() && !capability.read_with_snapshot {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation read with snapshot",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.version().is_some() && !capability.read_with_version {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation read with version",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.lease_id().is_some() && !capability.read_with_lease_id {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation read with lease id",
+                    self.info().scheme()
+                ),
+            ));
+        }
 
         let seekable = capability.read_can_seek;
         let streamable = capability.read_can_next;
@@ -492,6 +618,114 @@ impl<A: Accessor> LayeredAccessor for CompleteAccessor<A> {
                 ),
             ));
         }
+        if args.sparse() && !capability.write_with_sparse {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with sparse",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.signer().is_some() && !capability.write_with_signer {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with signer",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.retention().is_some() && !capability.write_with_retention {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with retention",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.legal_hold().is_some() && !capability.write_with_legal_hold {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with legal hold",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if !args.compose_sources().is_empty() && !capability.write_with_compose_sources {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with compose sources",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.expire_at().is_some() && !capability.write_with_expire_at {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with expire at",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.user_metadata().is_some() && !capability.write_with_user_metadata {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with user metadata",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.tags().is_some() && !capability.write_with_tags {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with tags",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.storage_class().is_some() && !capability.write_with_storage_class {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with storage class",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.lease_id().is_some() && !capability.write_with_lease_id {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with lease id",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.if_match().is_some() && !capability.write_with_if_match {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with if match",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.if_none_match().is_some() && !capability.write_with_if_none_match {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with if none match",
+                    self.info().scheme()
+                ),
+            ));
+        }
 
         // Calculate buffer size.
         let buffer_size = args.buffer().map(|mut size| {
@@ -526,6 +760,7 @@ impl<A: Accessor> LayeredAccessor for CompleteAccessor<A> {
         if !capability.copy {
             return Err(self.new_unsupported_error(Operation::Copy));
         }
+        self.check_copy_args(&args)?;
 
         self.inner().copy(from, to, args).await
     }
@@ -548,6 +783,31 @@ impl<A: Accessor> LayeredAccessor for CompleteAccessor<A> {
         if !capability.delete {
             return Err(self.new_unsupported_error(Operation::Delete));
         }
+        if args.trash() && !capability.delete_with_trash {
+            return Err(self.new_unsupported_error(Operation::Delete));
+        }
+        if args.recursive() && !capability.delete_with_recursive {
+            return Err(self.new_unsupported_error(Operation::Delete));
+        }
+        if args.prefix() && !capability.delete_with_prefix {
+            return Err(self.new_unsupported_error(Operation::Delete));
+        }
+        if args.lease_id().is_some() && !capability.delete_with_lease_id {
+            return Err(self.new_unsupported_error(Operation::Delete));
+        }
+        if args.snapshots().is_some() && !capability.delete_with_snapshots {
+            return Err(self.new_unsupported_error(Operation::Delete));
+        }
+        if args.version().is_some() && !capability.delete_with_version {
+            return Err(self.new_unsupported_error(Operation::Delete));
+        }
+        if args.must_exist() {
+            self.check_delete_must_exist(&args)?;
+            // Services that delete idempotently can only be checked via stat.
+            if !capability.delete_with_must_exist {
+                self.complete_stat(path, args.stat_args()).await?;
+            }
+        }
 
         self.inner().delete(path, args).await
     }
@@ -557,6 +817,15 @@ impl<A: Accessor> LayeredAccessor for CompleteAccessor<A> {
         if !capability.list {
             return Err(self.new_unsupported_error(Operation::List));
         }
+        if args.delimiter().is_some() && !capability.list_with_delimiter {
+            return Err(self.new_unsupported_error(Operation::List));
+        }
+        if args.snapshots() && !capability.list_with_snapshots {
+            return Err(self.new_unsupported_error(Operation::List));
+        }
+        if args.versions() && !capability.list_with_versions {
+            return Err(self.new_unsupported_error(Operation::List));
+        }
 
         self.complete_list(path, args).await
     }
@@ -566,6 +835,16 @@ impl<A: Accessor> LayeredAccessor for CompleteAccessor<A> {
         if !capability.batch {
             return Err(self.new_unsupported_error(Operation::Batch));
         }
+        // Batch delete of services reports missing paths as deleted.
+        if args.operation().iter().any(|(_, op)| match op {
+            BatchOperation::Delete(op) => op.must_exist(),
+        }) {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "batch delete doesn't support must_exist",
+            )
+            .with_operation(Operation::Batch));
+        }
 
         self.inner().batch(args).await
     }
@@ -579,6 +858,73 @@ impl<A: Accessor> LayeredAccessor for CompleteAccessor<A> {
         self.inner.presign(path, args).await
     }
 
+    async fn create_symlink(
+        &self,
+        target: &str,
+        path: &str,
+        args: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        let capability = self.meta.full_capability();
+        if !capability.create_symlink {
+            return Err(self.new_unsupported_error(Operation::CreateSymlink));
+        }
+
+        self.inner().create_symlink(target, path, args).await
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        let capability = self.meta.full_capability();
+        if !capability.set_permissions {
+            return Err(self.new_unsupported_error(Operation::SetPermissions));
+        }
+
+        self.inner().set_permissions(path, args).await
+    }
+
+    async fn create_snapshot(
+        &self,
+        path: &str,
+        args: OpCreateSnapshot,
+    ) -> Result<RpCreateSnapshot> {
+        let capability = self.meta.full_capability();
+        if !capability.create_snapshot {
+            return Err(self.new_unsupported_error(Operation::CreateSnapshot));
+        }
+
+        self.inner().create_snapshot(path, args).await
+    }
+
+    async fn lease(&self, path: &str, args: OpLease) -> Result<RpLease> {
+        let capability = self.meta.full_capability();
+        if !capability.lease {
+            return Err(self.new_unsupported_error(Operation::Lease));
+        }
+
+        self.inner().lease(path, args).await
+    }
+
+    async fn list_roots(&self, args: OpListRoots) -> Result<RpListRoots> {
+        let capability = self.meta.full_capability();
+        if !capability.list_roots {
+            return Err(self.new_unsupported_error(Operation::ListRoots));
+        }
+
+        self.inner().list_roots(args).await
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        let capability = self.meta.full_capability();
+        if !capability.concat {
+            return Err(self.new_unsupported_error(Operation::Concat));
+        }
+
+        self.inner().concat(path, args).await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.complete_blocking_create_dir(path, args)
     }
@@ -602,6 +948,114 @@ impl<A: Accessor> LayeredAccessor for CompleteAccessor<A> {
                 ),
             ));
         }
+        if args.sparse() && !capability.write_with_sparse {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with sparse",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.signer().is_some() && !capability.write_with_signer {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with signer",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.retention().is_some() && !capability.write_with_retention {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with retention",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.legal_hold().is_some() && !capability.write_with_legal_hold {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with legal hold",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if !args.compose_sources().is_empty() && !capability.write_with_compose_sources {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with compose sources",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.expire_at().is_some() && !capability.write_with_expire_at {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with expire at",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.user_metadata().is_some() && !capability.write_with_user_metadata {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with user metadata",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.tags().is_some() && !capability.write_with_tags {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with tags",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.storage_class().is_some() && !capability.write_with_storage_class {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with storage class",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.lease_id().is_some() && !capability.write_with_lease_id {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with lease id",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.if_match().is_some() && !capability.write_with_if_match {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with if match",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.if_none_match().is_some() && !capability.write_with_if_none_match {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with if none match",
+                    self.info().scheme()
+                ),
+            ));
+        }
 
         self.inner
             .blocking_write(path, args)
@@ -613,6 +1067,7 @@ impl<A: Accessor> LayeredAccessor for CompleteAccessor<A> {
         if !capability.copy || !capability.blocking {
             return Err(self.new_unsupported_error(Operation::BlockingCopy));
         }
+        self.check_copy_args(&args)?;
 
         self.inner().blocking_copy(from, to, args)
     }
@@ -635,6 +1090,30 @@ impl<A: Accessor> LayeredAccessor for CompleteAccessor<A> {
         if !capability.delete || !capability.blocking {
             return Err(self.new_unsupported_error(Operation::BlockingDelete));
         }
+        if args.trash() && !capability.delete_with_trash {
+            return Err(self.new_unsupported_error(Operation::BlockingDelete));
+        }
+        if args.recursive() && !capability.delete_with_recursive {
+            return Err(self.new_unsupported_error(Operation::BlockingDelete));
+        }
+        if args.prefix() && !capability.delete_with_prefix {
+            return Err(self.new_unsupported_error(Operation::BlockingDelete));
+        }
+        if args.lease_id().is_some() && !capability.delete_with_lease_id {
+            return Err(self.new_unsupported_error(Operation::BlockingDelete));
+        }
+        if args.snapshots().is_some() && !capability.delete_with_snapshots {
+            return Err(self.new_unsupported_error(Operation::BlockingDelete));
+        }
+        if args.version().is_some() && !capability.delete_with_version {
+            return Err(self.new_unsupported_error(Operation::BlockingDelete));
+        }
+        if args.must_exist() {
+            self.check_delete_must_exist(&args)?;
+            if !capability.delete_with_must_exist {
+                self.complete_blocking_stat(path, args.stat_args())?;
+            }
+        }
 
         self.inner().blocking_delete(path, args)
     }
@@ -644,9 +1123,58 @@ impl<A: Accessor> LayeredAccessor for CompleteAccessor<A> {
         if !capability.list || !capability.blocking {
             return Err(self.new_unsupported_error(Operation::BlockingList));
         }
+        if args.delimiter().is_some() && !capability.list_with_delimiter {
+            return Err(self.new_unsupported_error(Operation::BlockingList));
+        }
 
         self.complete_blocking_list(path, args)
     }
+
+    fn blocking_create_symlink(
+        &self,
+        target: &str,
+        path: &str,
+        args: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        let capability = self.meta.full_capability();
+        if !capability.create_symlink || !capability.blocking {
+            return Err(self.new_unsupported_error(Operation::BlockingCreateSymlink));
+        }
+
+        self.inner().blocking_create_symlink(target, path, args)
+    }
+
+    fn blocking_set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        let capability = self.meta.full_capability();
+        if !capability.set_permissions || !capability.blocking {
+            return Err(self.new_unsupported_error(Operation::BlockingSetPermissions));
+        }
+
+        self.inner().blocking_set_permissions(path, args)
+    }
+}
+
+/// Stat on root always returns a dir, with backend-level metadata returned
+/// by service.
+///
+/// Users that only have permissions on objects are not able to fetch
+/// metadata of bucket, we return a plain dir for them.
+fn complete_stat_root(res: Result<RpStat>) -> Result<RpStat> {
+    match res {
+        Ok(rp) => Ok(rp.map_metadata(|m| {
+            let bit = m.metakey();
+            m.with_mode(EntryMode::DIR)
+                .with_metakey(bit | Metakey::Complete)
+        })),
+        Err(err) if err.kind() == ErrorKind::PermissionDenied => {
+            Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
+        }
+        Err(err) => Err(err),
+    }
 }
 
 pub enum CompleteReader<A: Accessor, R> {
@@ -810,6 +1338,14 @@ where
         Poll::Ready(Ok(n))
     }
 
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let w = self.inner.as_mut().ok_or_else(|| {
+            Error::new(ErrorKind::Unexpected, "writer has been closed or aborted")
+        })?;
+
+        w.poll_ready(cx)
+    }
+
     fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         let w = self.inner.as_mut().ok_or_else(|| {
             Error::new(ErrorKind::Unexpected, "writer has been closed or aborted")
@@ -909,7 +1445,9 @@ mod tests {
         }
 
         async fn stat(&self, _: &str, _: OpStat) -> Result<RpStat> {
-            Ok(RpStat::new(Metadata::new(EntryMode::Unknown)))
+            Ok(RpStat::new(
+                Metadata::new(EntryMode::Unknown).with_region("us-east-1".to_string()),
+            ))
         }
 
         async fn delete(&self, _: &str, _: OpDelete) -> Result<RpDelete> {
@@ -966,6 +1504,78 @@ mod tests {
         assert!(res.is_ok())
     }
 
+    #[derive(Debug)]
+    struct NoopSigner;
+
+    #[async_trait]
+    impl RequestSign for NoopSigner {
+        async fn sign(&self, _: &mut http::Request<AsyncBody>) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stat_with_signer() {
+        let signer: RequestSigner = Arc::new(NoopSigner);
+
+        let op = new_test_operator(Capability {
+            stat: true,
+            ..Default::default()
+        });
+        let res = op.stat_with("path").signer(signer.clone()).await;
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
+        let op = new_test_operator(Capability {
+            stat: true,
+            stat_with_signer: true,
+            ..Default::default()
+        });
+        let res = op.stat_with("path").signer(signer).await;
+        assert!(res.is_ok())
+    }
+
+    #[tokio::test]
+    async fn test_copy_with_metadata() -> Result<()> {
+        let op = new_test_operator(Capability {
+            copy: true,
+            copy_with_content_type: true,
+            ..Default::default()
+        });
+        op.copy_with("from", "to")
+            .content_type("text/plain")
+            .await?;
+
+        let err = op
+            .copy_with("from", "to")
+            .tags([("k".to_string(), "v".to_string())])
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stat_root() -> Result<()> {
+        let op = new_test_operator(Capability {
+            stat: true,
+            ..Default::default()
+        });
+        let meta = op.stat("/").await?;
+        assert!(meta.is_dir());
+        assert_eq!(meta.region(), None);
+
+        let op = new_test_operator(Capability {
+            stat: true,
+            stat_root: true,
+            ..Default::default()
+        });
+        let meta = op.stat("/").await?;
+        assert!(meta.is_dir());
+        assert_eq!(meta.region(), Some("us-east-1"));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_writer() {
         let op = new_test_operator(Capability::default());
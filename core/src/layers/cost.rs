@@ -0,0 +1,467 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::io::SeekFrom;
+use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use crate::raw::*;
+use crate::*;
+
+const GIB: f64 = (1024 * 1024 * 1024) as f64;
+
+/// CostModel describes how a service charges requests and egress.
+///
+/// Prices are in whatever currency users choose, the estimated cost will
+/// be in the same currency.
+#[derive(Debug, Clone, Default)]
+pub struct CostModel {
+    class_a_price: f64,
+    class_b_price: f64,
+    egress_price: f64,
+    classes: HashMap<Operation, RequestClass>,
+}
+
+impl CostModel {
+    /// Create a new cost model that charges nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the price of every 1000 class A requests.
+    pub fn with_class_a_price(mut self, price: f64) -> Self {
+        self.class_a_price = price;
+        self
+    }
+
+    /// Set the price of every 1000 class B requests.
+    pub fn with_class_b_price(mut self, price: f64) -> Self {
+        self.class_b_price = price;
+        self
+    }
+
+    /// Set the price of every GiB read out of the service.
+    pub fn with_egress_price(mut self, price: f64) -> Self {
+        self.egress_price = price;
+        self
+    }
+
+    /// Override the class of given operation.
+    pub fn with_request_class(mut self, op: Operation, class: RequestClass) -> Self {
+        self.classes.insert(op, class);
+        self
+    }
+
+    /// Get the class of given operation.
+    ///
+    /// By default, `read` and `stat` are class B, `delete`, `batch` and
+    /// `presign` are free, and all others are class A.
+    pub fn request_class(&self, op: Operation) -> RequestClass {
+        if let Some(class) = self.classes.get(&op) {
+            return *class;
+        }
+
+        match op {
+            Operation::Read
+            | Operation::BlockingRead
+            | Operation::Stat
+            | Operation::BlockingStat => RequestClass::B,
+            Operation::Delete
+            | Operation::BlockingDelete
+            | Operation::Batch
+            | Operation::Presign
+            | Operation::Info => RequestClass::Free,
+            _ => RequestClass::A,
+        }
+    }
+
+    fn request_cost(&self, class: RequestClass) -> f64 {
+        match class {
+            RequestClass::A => self.class_a_price / 1000.0,
+            RequestClass::B => self.class_b_price / 1000.0,
+            RequestClass::Free => 0.0,
+        }
+    }
+
+    fn egress_cost(&self, n: usize) -> f64 {
+        n as f64 / GIB * self.egress_price
+    }
+}
+
+/// Estimate the cloud cost of operations by given [`CostModel`].
+///
+/// The estimated cost is accumulated per operator and can be fetched by
+/// [`Operator::stats`] via [`OperatorStats::cost`], so batch jobs can
+/// report their estimated spend at the end.
+///
+/// # Notes
+///
+/// The cost is an estimation at operation level:
+///
+/// - Every call of an operation is counted as one request, extra requests
+///   like multipart parts and list pages are not counted.
+/// - All bytes read from readers are counted as egress.
+/// - Add this layer before [`RetryLayer`](crate::layers::RetryLayer) so
+///   that retried requests are counted too.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::CostLayer;
+/// use opendal::layers::CostModel;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let op = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(CostLayer::new(
+///         CostModel::new()
+///             .with_class_a_price(0.005)
+///             .with_class_b_price(0.0004)
+///             .with_egress_price(0.09),
+///     ))
+///     .finish();
+///
+/// println!("estimated cost: {}", op.stats().cost().estimated_cost());
+/// ```
+#[derive(Debug, Clone)]
+pub struct CostLayer {
+    model: Arc<CostModel>,
+}
+
+impl CostLayer {
+    /// Create a new cost layer with given model.
+    pub fn new(model: CostModel) -> Self {
+        Self {
+            model: Arc::new(model),
+        }
+    }
+}
+
+impl<A: Accessor> Layer<A> for CostLayer {
+    type LayeredAccessor = CostAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        let stats = inner.info().stats();
+        CostAccessor {
+            inner,
+            cost: CostRecorder {
+                model: self.model.clone(),
+                stats,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CostRecorder {
+    model: Arc<CostModel>,
+    stats: Option<Arc<StatsCollector>>,
+}
+
+impl CostRecorder {
+    fn request(&self, op: Operation) {
+        if let Some(stats) = &self.stats {
+            let class = self.model.request_class(op);
+            stats.add_request_cost(class, self.model.request_cost(class));
+        }
+    }
+
+    fn egress(&self, n: usize) {
+        if let Some(stats) = &self.stats {
+            stats.add_egress_cost(n, self.model.egress_cost(n));
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CostAccessor<A: Accessor> {
+    inner: A,
+    cost: CostRecorder,
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for CostAccessor<A> {
+    type Inner = A;
+    type Reader = CostReader<A::Reader>;
+    type BlockingReader = CostReader<A::BlockingReader>;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.cost.request(Operation::CreateDir);
+        self.inner.create_dir(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.cost.request(Operation::Read);
+        self.inner
+            .read(path, args)
+            .await
+            .map(|(rp, r)| (rp, CostReader::new(r, self.cost.clone())))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.cost.request(Operation::Write);
+        self.inner.write(path, args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.cost.request(Operation::Copy);
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.cost.request(Operation::Rename);
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.cost.request(Operation::Stat);
+        self.inner.stat(path, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.cost.request(Operation::Delete);
+        self.inner.delete(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.cost.request(Operation::List);
+        self.inner.list(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.cost.request(Operation::Batch);
+        self.inner.batch(args).await
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.cost.request(Operation::Presign);
+        self.inner.presign(path, args).await
+    }
+
+    async fn create_symlink(
+        &self,
+        target: &str,
+        path: &str,
+        args: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        self.cost.request(Operation::CreateSymlink);
+        self.inner.create_symlink(target, path, args).await
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        self.cost.request(Operation::SetPermissions);
+        self.inner.set_permissions(path, args).await
+    }
+
+    async fn create_snapshot(
+        &self,
+        path: &str,
+        args: OpCreateSnapshot,
+    ) -> Result<RpCreateSnapshot> {
+        self.cost.request(Operation::CreateSnapshot);
+        self.inner.create_snapshot(path, args).await
+    }
+
+    async fn lease(&self, path: &str, args: OpLease) -> Result<RpLease> {
+        self.cost.request(Operation::Lease);
+        self.inner.lease(path, args).await
+    }
+
+    async fn list_roots(&self, args: OpListRoots) -> Result<RpListRoots> {
+        self.cost.request(Operation::ListRoots);
+        self.inner.list_roots(args).await
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        self.cost.request(Operation::Concat);
+        self.inner.concat(path, args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.cost.request(Operation::BlockingCreateDir);
+        self.inner.blocking_create_dir(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.cost.request(Operation::BlockingRead);
+        self.inner
+            .blocking_read(path, args)
+            .map(|(rp, r)| (rp, CostReader::new(r, self.cost.clone())))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.cost.request(Operation::BlockingWrite);
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.cost.request(Operation::BlockingCopy);
+        self.inner.blocking_copy(from, to, args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.cost.request(Operation::BlockingRename);
+        self.inner.blocking_rename(from, to, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.cost.request(Operation::BlockingStat);
+        self.inner.blocking_stat(path, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.cost.request(Operation::BlockingDelete);
+        self.inner.blocking_delete(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.cost.request(Operation::BlockingList);
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_create_symlink(
+        &self,
+        target: &str,
+        path: &str,
+        args: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        self.cost.request(Operation::BlockingCreateSymlink);
+        self.inner.blocking_create_symlink(target, path, args)
+    }
+
+    fn blocking_set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        self.cost.request(Operation::BlockingSetPermissions);
+        self.inner.blocking_set_permissions(path, args)
+    }
+}
+
+/// CostReader counts bytes read out of the service as egress.
+pub struct CostReader<R> {
+    inner: R,
+    cost: CostRecorder,
+}
+
+impl<R> CostReader<R> {
+    fn new(inner: R, cost: CostRecorder) -> Self {
+        Self { inner, cost }
+    }
+}
+
+impl<R: oio::Read> oio::Read for CostReader<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        let n = ready!(self.inner.poll_read(cx, buf))?;
+        self.cost.egress(n);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        self.inner.poll_seek(cx, pos)
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        let res = ready!(self.inner.poll_next(cx));
+        if let Some(Ok(bs)) = &res {
+            self.cost.egress(bs.len());
+        }
+        Poll::Ready(res)
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for CostReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.cost.egress(n);
+        Ok(n)
+    }
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        let res = self.inner.next();
+        if let Some(Ok(bs)) = &res {
+            self.cost.egress(bs.len());
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_cost_layer() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .layer(CostLayer::new(
+                CostModel::new()
+                    .with_class_a_price(5.0)
+                    .with_class_b_price(0.4)
+                    .with_egress_price(GIB)
+                    .with_request_class(Operation::Delete, RequestClass::A),
+            ))
+            .finish();
+
+        op.write("a.txt", "hello").await?;
+        op.stat("a.txt").await?;
+        assert_eq!(op.read("a.txt").await?, b"hello");
+        op.delete("a.txt").await?;
+
+        let cost = op.stats().cost();
+        assert_eq!(cost.class_a_requests(), 2);
+        // `read` without range will `stat` first.
+        assert_eq!(cost.class_b_requests(), 3);
+        assert_eq!(cost.egress_bytes(), 5);
+        // 2 * 5.0 / 1000 + 3 * 0.4 / 1000 + 5 bytes * 1 per byte
+        assert!((cost.estimated_cost() - 5.0112).abs() < 1e-9);
+
+        op.reset_stats();
+        assert_eq!(op.stats().cost(), CostStats::default());
+
+        // Operations are counted even if the service doesn't support them.
+        let _ = op.list_roots().await;
+        let _ = op.break_lease("a.txt").await;
+        assert_eq!(op.stats().cost().class_a_requests(), 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,539 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+
+use crate::raw::oio::ListExt;
+use crate::raw::*;
+use crate::*;
+
+/// Enforce uniform directory semantics on flat object storage services.
+///
+/// Object storage services like s3 don't have real directories, and
+/// behaviors around `a/` diverge between services. This layer makes them
+/// behave the same:
+///
+/// - `create_dir` writes an empty marker object at `a/` if the service
+///   doesn't support `create_dir` natively.
+/// - `stat` on `a/` returns a dir as long as the marker exists or there
+///   are any entries under `a/`.
+/// - `list` synthesizes the parent dir entries that don't have a marker,
+///   and every dir will be returned only once.
+/// - `delete` on `a/` requires the dir to be empty, or returns
+///   [`ErrorKind::DirectoryNotEmpty`]. Use `delete_with(path).recursive(true)`
+///   or `remove_all` to delete a dir with all its entries.
+///
+/// # Notes
+///
+/// `list` needs to remember all returned dirs to avoid duplicates, so the
+/// memory usage grows with the number of dirs being listed.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::layers::DirectoryLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(DirectoryLayer)
+///     .finish();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DirectoryLayer;
+
+impl<A: Accessor> Layer<A> for DirectoryLayer {
+    type LayeredAccessor = DirectoryAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        let mut info = inner.info();
+        let native_create_dir = info.full_capability().create_dir;
+
+        let cap = info.full_capability_mut();
+        if cap.list && cap.delete {
+            cap.delete_with_recursive = true;
+        }
+        if cap.write_can_empty {
+            cap.create_dir = true;
+        }
+
+        DirectoryAccessor {
+            inner,
+            info,
+            native_create_dir,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DirectoryAccessor<A: Accessor> {
+    inner: A,
+    info: AccessorInfo,
+    native_create_dir: bool,
+}
+
+impl<A: Accessor> DirectoryAccessor<A> {
+    fn new_not_empty_error(&self, op: Operation, path: &str) -> Error {
+        Error::new(ErrorKind::DirectoryNotEmpty, "directory is not empty")
+            .with_operation(op)
+            .with_context("service", self.info.scheme())
+            .with_context("path", path)
+    }
+
+    /// Check if there are any entries under given dir path.
+    async fn has_children(&self, path: &str) -> Result<bool> {
+        let (_, mut l) = self
+            .inner
+            .list(path, OpList::new().with_recursive(true).with_limit(2))
+            .await?;
+
+        while let Some(e) = l.next().await? {
+            if e.path() != path {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Check if there are any entries under given dir path.
+    fn blocking_has_children(&self, path: &str) -> Result<bool> {
+        let (_, mut l) = self
+            .inner
+            .blocking_list(path, OpList::new().with_recursive(true).with_limit(2))?;
+
+        while let Some(e) = oio::BlockingList::next(&mut l)? {
+            if e.path() != path {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Remove all entries under given dir path, the dir itself is kept.
+    ///
+    /// Files are removed first, and dirs are removed from the deepest one.
+    async fn remove_children(&self, path: &str) -> Result<()> {
+        let (_, mut l) = self
+            .inner
+            .list(path, OpList::new().with_recursive(true))
+            .await?;
+
+        let mut dirs = vec![];
+        while let Some(e) = l.next().await? {
+            if e.path() == path {
+                continue;
+            }
+            if e.mode().is_dir() {
+                dirs.push(e.path().to_string());
+                continue;
+            }
+            self.inner.delete(e.path(), OpDelete::new()).await?;
+        }
+
+        dirs.sort_by_key(|v| std::cmp::Reverse(v.len()));
+        for dir in dirs {
+            self.inner.delete(&dir, OpDelete::new()).await?;
+        }
+        Ok(())
+    }
+
+    /// Remove all entries under given dir path, the dir itself is kept.
+    ///
+    /// Files are removed first, and dirs are removed from the deepest one.
+    fn blocking_remove_children(&self, path: &str) -> Result<()> {
+        let (_, mut l) = self
+            .inner
+            .blocking_list(path, OpList::new().with_recursive(true))?;
+
+        let mut dirs = vec![];
+        while let Some(e) = oio::BlockingList::next(&mut l)? {
+            if e.path() == path {
+                continue;
+            }
+            if e.mode().is_dir() {
+                dirs.push(e.path().to_string());
+                continue;
+            }
+            self.inner.blocking_delete(e.path(), OpDelete::new())?;
+        }
+
+        dirs.sort_by_key(|v| std::cmp::Reverse(v.len()));
+        for dir in dirs {
+            self.inner.blocking_delete(&dir, OpDelete::new())?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for DirectoryAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = DirectoryLister<A::Lister>;
+    type BlockingLister = DirectoryLister<A::BlockingLister>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn metadata(&self) -> AccessorInfo {
+        self.info.clone()
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        if self.native_create_dir {
+            return self.inner.create_dir(path, args).await;
+        }
+
+        let (_, mut w) = self.inner.write(path, OpWrite::new()).await?;
+        oio::WriteExt::close(&mut w).await?;
+        Ok(RpCreateDir::default())
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if !path.ends_with('/') || path == "/" {
+            return self.inner.stat(path, args).await;
+        }
+
+        match self.inner.stat(path, args).await {
+            // Markers written by `create_dir` could be stored as files.
+            Ok(rp) => return Ok(RpStat::new(rp.into_metadata().with_mode(EntryMode::DIR))),
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+
+        if self.has_children(path).await? {
+            Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
+        } else {
+            Err(
+                Error::new(ErrorKind::NotFound, "the directory is not found")
+                    .with_context("path", path),
+            )
+        }
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        if !path.ends_with('/') {
+            return self.inner.delete(path, args).await;
+        }
+
+        if args.recursive() {
+            self.remove_children(path).await?;
+        } else if self.has_children(path).await? {
+            return Err(self.new_not_empty_error(Operation::Delete, path));
+        }
+
+        self.inner.delete(path, args.with_recursive(false)).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let recursive = args.recursive();
+        let (rp, l) = self.inner.list(path, args).await?;
+        Ok((rp, DirectoryLister::new(l, path, recursive)))
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        if self.native_create_dir {
+            return self.inner.blocking_create_dir(path, args);
+        }
+
+        let (_, mut w) = self.inner.blocking_write(path, OpWrite::new())?;
+        oio::BlockingWrite::close(&mut w)?;
+        Ok(RpCreateDir::default())
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if !path.ends_with('/') || path == "/" {
+            return self.inner.blocking_stat(path, args);
+        }
+
+        match self.inner.blocking_stat(path, args) {
+            // Markers written by `create_dir` could be stored as files.
+            Ok(rp) => return Ok(RpStat::new(rp.into_metadata().with_mode(EntryMode::DIR))),
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+
+        if self.blocking_has_children(path)? {
+            Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
+        } else {
+            Err(
+                Error::new(ErrorKind::NotFound, "the directory is not found")
+                    .with_context("path", path),
+            )
+        }
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        if !path.ends_with('/') {
+            return self.inner.blocking_delete(path, args);
+        }
+
+        if args.recursive() {
+            self.blocking_remove_children(path)?;
+        } else if self.blocking_has_children(path)? {
+            return Err(self.new_not_empty_error(Operation::BlockingDelete, path));
+        }
+
+        self.inner.blocking_delete(path, args.with_recursive(false))
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let recursive = args.recursive();
+        let (rp, l) = self.inner.blocking_list(path, args)?;
+        Ok((rp, DirectoryLister::new(l, path, recursive)))
+    }
+}
+
+/// DirectoryLister makes sure every dir will be returned exactly once.
+///
+/// For recursive listing, the parent dirs of an entry will be synthesized
+/// and returned before the entry itself if they are not returned yet.
+pub struct DirectoryLister<P> {
+    lister: P,
+    path: String,
+    recursive: bool,
+
+    visited: HashSet<String>,
+    pending: VecDeque<oio::Entry>,
+}
+
+impl<P> DirectoryLister<P> {
+    fn new(lister: P, path: &str, recursive: bool) -> Self {
+        let path = if path == "/" {
+            "".to_string()
+        } else {
+            path.to_string()
+        };
+
+        Self {
+            lister,
+            path,
+            recursive,
+            visited: HashSet::default(),
+            pending: VecDeque::default(),
+        }
+    }
+
+    fn push_entry(&mut self, e: oio::Entry) {
+        // Tolerate entries that outside of the listing path.
+        if !e.path().starts_with(&self.path) {
+            self.pending.push_back(e);
+            return;
+        }
+
+        if self.recursive {
+            let start = self.path.len();
+            let end = e.path().trim_end_matches('/').len();
+            for (idx, _) in e.path()[start..end].match_indices('/') {
+                let dir = &e.path()[..start + idx + 1];
+                if self.visited.insert(dir.to_string()) {
+                    self.pending
+                        .push_back(oio::Entry::new(dir, Metadata::new(EntryMode::DIR)));
+                }
+            }
+        }
+
+        if e.mode().is_dir() && !self.visited.insert(e.path().to_string()) {
+            return;
+        }
+        self.pending.push_back(e);
+    }
+}
+
+impl<P: oio::List> oio::List for DirectoryLister<P> {
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<oio::Entry>>> {
+        loop {
+            if let Some(e) = self.pending.pop_front() {
+                return Poll::Ready(Ok(Some(e)));
+            }
+
+            match ready!(self.lister.poll_next(cx))? {
+                Some(e) => self.push_entry(e),
+                None => return Poll::Ready(Ok(None)),
+            }
+        }
+    }
+}
+
+impl<P: oio::BlockingList> oio::BlockingList for DirectoryLister<P> {
+    fn next(&mut self) -> Result<Option<oio::Entry>> {
+        loop {
+            if let Some(e) = self.pending.pop_front() {
+                return Ok(Some(e));
+            }
+
+            match self.lister.next()? {
+                Some(e) => self.push_entry(e),
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::IntoIter;
+
+    use oio::BlockingList;
+
+    use super::*;
+
+    struct MockLister {
+        inner: IntoIter<&'static str>,
+    }
+
+    impl BlockingList for MockLister {
+        fn next(&mut self) -> Result<Option<oio::Entry>> {
+            let entry = self.inner.next().map(|path| {
+                if path.ends_with('/') {
+                    oio::Entry::new(path, Metadata::new(EntryMode::DIR))
+                } else {
+                    oio::Entry::new(path, Metadata::new(EntryMode::FILE))
+                }
+            });
+
+            Ok(entry)
+        }
+    }
+
+    #[test]
+    fn test_recursive_list_synthesize_dirs() -> Result<()> {
+        let lister = MockLister {
+            inner: vec!["x/a/", "x/a/b/c", "x/a/d", "x/e"].into_iter(),
+        };
+        let mut lister = DirectoryLister::new(lister, "x/", true);
+
+        let mut paths = vec![];
+        while let Some(e) = lister.next()? {
+            paths.push(e.path().to_string());
+        }
+
+        assert_eq!(paths, vec!["x/a/", "x/a/b/", "x/a/b/c", "x/a/d", "x/e"]);
+        Ok(())
+    }
+
+    fn new_operator() -> Operator {
+        Operator::new(crate::services::Memory::default())
+            .expect("must init")
+            .layer(DirectoryLayer)
+            .finish()
+    }
+
+    #[tokio::test]
+    async fn test_create_dir_writes_marker() -> Result<()> {
+        let op = new_operator();
+        assert!(op.info().full_capability().create_dir);
+
+        op.create_dir("a/").await?;
+        assert!(op.stat("a/").await?.is_dir());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stat_dir_without_marker() -> Result<()> {
+        let op = new_operator();
+
+        op.write("a/b/c", "hello").await?;
+        assert!(op.stat("a/").await?.is_dir());
+        assert!(op.stat("a/b/").await?.is_dir());
+
+        let err = op.stat("x/").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_synthesizes_dirs() -> Result<()> {
+        let op = new_operator();
+
+        op.create_dir("a/").await?;
+        op.write("a/b/c", "hello").await?;
+        op.write("a/d", "world").await?;
+
+        let mut paths: Vec<_> = op
+            .list_with("a/")
+            .recursive(true)
+            .await?
+            .into_iter()
+            .map(|e| e.path().to_string())
+            .filter(|path| path != "a/")
+            .collect();
+        paths.sort();
+        // `a/b/` is synthesized since it doesn't have a marker.
+        assert_eq!(paths, vec!["a/b/", "a/b/c", "a/d"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_dir_requires_empty() -> Result<()> {
+        let op = new_operator();
+
+        op.create_dir("a/").await?;
+        op.write("a/b", "hello").await?;
+
+        let err = op.delete("a/").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DirectoryNotEmpty);
+        assert!(op.is_exist("a/b").await?);
+
+        op.delete_with("a/").recursive(true).await?;
+        assert!(!op.is_exist("a/b").await?);
+        assert!(!op.is_exist("a/").await?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_blocking_delete_dir_requires_empty() -> Result<()> {
+        let op = new_operator().blocking();
+
+        op.write("a/b", "hello")?;
+        assert!(op.stat("a/")?.is_dir());
+
+        let err = op.delete("a/").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DirectoryNotEmpty);
+
+        op.delete_with("a/").recursive(true).call()?;
+        assert!(!op.is_exist("a/b")?);
+        Ok(())
+    }
+}
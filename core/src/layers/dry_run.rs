@@ -0,0 +1,503 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use log::info;
+
+use crate::raw::*;
+use crate::*;
+
+static DRY_RUN_TARGET: &str = "opendal::layers::dry_run";
+
+/// Validate mutating operations but never execute them.
+///
+/// `create_dir`, `write`, `copy`, `rename`, `delete` and `batch` will be
+/// checked against the capability of the service and logged under target
+/// `opendal::layers::dry_run`, then return success without touching the
+/// service. Data written into writers will be discarded. Read operations
+/// like `read`, `stat` and `list` are passed to the service as is.
+///
+/// Every skipped operation will be recorded as a [`DryRunRecord`], which
+/// can be fetched via [`DryRunLayer::records`] to build a report. This makes
+/// it cheap for sync and migration tools to offer `--dry-run`.
+///
+/// # Notes
+///
+/// Operations are not executed, so a following `stat` or `read` will not
+/// see the effect of them.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::DryRunLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// # async fn test() -> Result<()> {
+/// let layer = DryRunLayer::default();
+/// let op = Operator::new(services::Memory::default())?
+///     .layer(layer.clone())
+///     .finish();
+///
+/// op.write("test", "Hello, World!").await?;
+/// for record in layer.records() {
+///     println!("would {} {}", record.operation(), record.path());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DryRunLayer {
+    records: Arc<Mutex<Vec<DryRunRecord>>>,
+}
+
+impl DryRunLayer {
+    /// Get all operations that have been skipped so far.
+    pub fn records(&self) -> Vec<DryRunRecord> {
+        self.records.lock().expect("lock must be valid").clone()
+    }
+
+    /// Take all operations that have been skipped so far and clear them.
+    pub fn take_records(&self) -> Vec<DryRunRecord> {
+        std::mem::take(&mut *self.records.lock().expect("lock must be valid"))
+    }
+}
+
+impl<A: Accessor> Layer<A> for DryRunLayer {
+    type LayeredAccessor = DryRunAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        let info = Arc::new(inner.info());
+        DryRunAccessor {
+            inner,
+            info,
+            records: self.records.clone(),
+        }
+    }
+}
+
+/// DryRunRecord is an operation skipped by [`DryRunLayer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DryRunRecord {
+    operation: Operation,
+    path: String,
+    target: Option<String>,
+    size: Option<u64>,
+}
+
+impl DryRunRecord {
+    /// The operation that has been skipped.
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    /// The path of the operation.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The target path of `copy` and `rename`.
+    pub fn target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    /// The size of data that would have been written by `write`.
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Recorder {
+    info: Arc<AccessorInfo>,
+    records: Arc<Mutex<Vec<DryRunRecord>>>,
+}
+
+impl Recorder {
+    fn record(&self, operation: Operation, path: &str, target: Option<&str>, size: Option<u64>) {
+        info!(
+            target: DRY_RUN_TARGET,
+            "service={} operation={} path={} target={} size={:?} -> skipped by dry run",
+            self.info.scheme(),
+            operation,
+            path,
+            target.unwrap_or_default(),
+            size,
+        );
+
+        self.records
+            .lock()
+            .expect("lock must be valid")
+            .push(DryRunRecord {
+                operation,
+                path: path.to_string(),
+                target: target.map(|v| v.to_string()),
+                size,
+            });
+    }
+}
+
+pub struct DryRunAccessor<A: Accessor> {
+    inner: A,
+    info: Arc<AccessorInfo>,
+    records: Arc<Mutex<Vec<DryRunRecord>>>,
+}
+
+impl<A: Accessor> Debug for DryRunAccessor<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DryRunAccessor")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Accessor> DryRunAccessor<A> {
+    fn recorder(&self) -> Recorder {
+        Recorder {
+            info: self.info.clone(),
+            records: self.records.clone(),
+        }
+    }
+
+    /// Make sure the operation could be executed by the service.
+    fn check(&self, supported: bool, op: Operation, feature: Option<&str>) -> Result<()> {
+        if supported {
+            return Ok(());
+        }
+
+        let scheme = self.info.scheme();
+        let message = match feature {
+            Some(feature) => {
+                format!("service {scheme} doesn't support operation {op} with {feature}")
+            }
+            None => format!("service {scheme} doesn't support operation {op}"),
+        };
+        Err(Error::new(ErrorKind::Unsupported, &message)
+            .with_operation(op)
+            .with_context("dry_run", "true"))
+    }
+
+    fn check_create_dir(&self, op: Operation) -> Result<()> {
+        let cap = self.info.full_capability();
+        self.check(cap.create_dir || cap.write_can_empty, op, None)
+    }
+
+    fn check_write(&self, op: Operation, args: &OpWrite) -> Result<()> {
+        let cap = self.info.full_capability();
+        self.check(cap.write, op, None)?;
+        self.check(!args.append() || cap.write_can_append, op, Some("append"))?;
+        self.check(
+            args.content_type().is_none() || cap.write_with_content_type,
+            op,
+            Some("content type"),
+        )?;
+        self.check(
+            args.content_disposition().is_none() || cap.write_with_content_disposition,
+            op,
+            Some("content disposition"),
+        )?;
+        self.check(
+            args.cache_control().is_none() || cap.write_with_cache_control,
+            op,
+            Some("cache control"),
+        )?;
+        self.check(
+            args.user_metadata().is_none() || cap.write_with_user_metadata,
+            op,
+            Some("user metadata"),
+        )?;
+        self.check(
+            args.if_match().is_none() || cap.write_with_if_match,
+            op,
+            Some("if match"),
+        )?;
+        self.check(
+            args.if_none_match().is_none() || cap.write_with_if_none_match,
+            op,
+            Some("if none match"),
+        )
+    }
+
+    fn check_delete(&self, op: Operation, args: &OpDelete) -> Result<()> {
+        let cap = self.info.full_capability();
+        self.check(cap.delete, op, None)?;
+        self.check(!args.trash() || cap.delete_with_trash, op, Some("trash"))?;
+        self.check(
+            !args.recursive() || cap.delete_with_recursive,
+            op,
+            Some("recursive"),
+        )
+    }
+
+    fn check_batch(&self, args: &OpBatch) -> Result<()> {
+        let cap = self.info.full_capability();
+        self.check(cap.batch, Operation::Batch, None)?;
+        if let Some(max) = cap.batch_max_operations {
+            if args.operation().len() > max {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "batch operations exceed the limit",
+                )
+                .with_operation(Operation::Batch)
+                .with_context("max_operations", max.to_string())
+                .with_context("dry_run", "true"));
+            }
+        }
+        for (_, op) in args.operation() {
+            match op {
+                BatchOperation::Delete(args) => self.check_delete(Operation::Batch, args)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for DryRunAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = DryRunWriter;
+    type BlockingWriter = DryRunWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        self.check_create_dir(Operation::CreateDir)?;
+        self.recorder()
+            .record(Operation::CreateDir, path, None, None);
+        Ok(RpCreateDir::default())
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.check_write(Operation::Write, &args)?;
+        let w = DryRunWriter::new(self.recorder(), Operation::Write, path);
+        Ok((RpWrite::default(), w))
+    }
+
+    async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        self.check(self.info.full_capability().copy, Operation::Copy, None)?;
+        // The source must exist, otherwise the copy will fail for real.
+        self.inner.stat(from, OpStat::new()).await?;
+        self.recorder()
+            .record(Operation::Copy, from, Some(to), None);
+        Ok(RpCopy::default())
+    }
+
+    async fn rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        self.check(self.info.full_capability().rename, Operation::Rename, None)?;
+        // The source must exist, otherwise the rename will fail for real.
+        self.inner.stat(from, OpStat::new()).await?;
+        self.recorder()
+            .record(Operation::Rename, from, Some(to), None);
+        Ok(RpRename::default())
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.check_delete(Operation::Delete, &args)?;
+        self.recorder().record(Operation::Delete, path, None, None);
+        Ok(RpDelete::default())
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.check_batch(&args)?;
+
+        let recorder = self.recorder();
+        let results = args
+            .into_operation()
+            .into_iter()
+            .map(|(path, op)| match op {
+                BatchOperation::Delete(_) => {
+                    recorder.record(Operation::Delete, &path, None, None);
+                    (path, Ok(BatchedReply::Delete(RpDelete::default())))
+                }
+            })
+            .collect();
+        Ok(RpBatch::new(results))
+    }
+
+    fn blocking_create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        self.check_create_dir(Operation::BlockingCreateDir)?;
+        self.recorder()
+            .record(Operation::BlockingCreateDir, path, None, None);
+        Ok(RpCreateDir::default())
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.check_write(Operation::BlockingWrite, &args)?;
+        let w = DryRunWriter::new(self.recorder(), Operation::BlockingWrite, path);
+        Ok((RpWrite::default(), w))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        self.check(
+            self.info.full_capability().copy,
+            Operation::BlockingCopy,
+            None,
+        )?;
+        self.inner.blocking_stat(from, OpStat::new())?;
+        self.recorder()
+            .record(Operation::BlockingCopy, from, Some(to), None);
+        Ok(RpCopy::default())
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        self.check(
+            self.info.full_capability().rename,
+            Operation::BlockingRename,
+            None,
+        )?;
+        self.inner.blocking_stat(from, OpStat::new())?;
+        self.recorder()
+            .record(Operation::BlockingRename, from, Some(to), None);
+        Ok(RpRename::default())
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.check_delete(Operation::BlockingDelete, &args)?;
+        self.recorder()
+            .record(Operation::BlockingDelete, path, None, None);
+        Ok(RpDelete::default())
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+/// DryRunWriter discards all data written into it and records the write
+/// while closing.
+pub struct DryRunWriter {
+    recorder: Recorder,
+    op: Operation,
+    path: String,
+    size: u64,
+}
+
+impl DryRunWriter {
+    fn new(recorder: Recorder, op: Operation, path: &str) -> Self {
+        Self {
+            recorder,
+            op,
+            path: path.to_string(),
+            size: 0,
+        }
+    }
+}
+
+impl oio::Write for DryRunWriter {
+    fn poll_write(&mut self, _: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        self.size += bs.remaining() as u64;
+        Poll::Ready(Ok(bs.remaining()))
+    }
+
+    /// Nothing is sent to the service, so the writer is always ready.
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_abort(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+        self.recorder
+            .record(self.op, &self.path, None, Some(self.size));
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl oio::BlockingWrite for DryRunWriter {
+    fn write(&mut self, bs: &dyn oio::WriteBuf) -> Result<usize> {
+        self.size += bs.remaining() as u64;
+        Ok(bs.remaining())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.recorder
+            .record(self.op, &self.path, None, Some(self.size));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_dry_run() -> Result<()> {
+        let layer = DryRunLayer::default();
+        let op = Operator::new(Memory::default())?.finish();
+        op.write("exist", "Hello").await?;
+        let dry = op.clone().layer(layer.clone());
+
+        dry.write("test", "Hello, World!").await?;
+        dry.copy("exist", "copied").await?;
+        dry.delete("exist").await?;
+        assert!(!op.is_exist("test").await?);
+        assert!(!op.is_exist("copied").await?);
+        assert!(op.is_exist("exist").await?);
+
+        // Copy from a not exist path must fail.
+        let err = dry
+            .copy("not_exist", "copied")
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        // Memory doesn't support append.
+        let err = dry
+            .write_with("test", "Hello")
+            .append(true)
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        let records = layer.take_records();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].operation(), Operation::Write);
+        assert_eq!(records[0].size(), Some(13));
+        assert_eq!(records[1].target(), Some("copied"));
+        assert_eq!(records[2].operation(), Operation::Delete);
+        assert!(layer.records().is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,811 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use chrono::DateTime;
+use chrono::Utc;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use futures::StreamExt;
+
+use crate::raw::oio::ReadExt;
+use crate::raw::oio::WriteExt;
+use crate::raw::*;
+use crate::*;
+
+/// The buffer size used while emulating copy via read and write.
+const COPY_BUFFER_SIZE: usize = 256 * 1024;
+
+/// Emulate operations that not supported by underlying services natively.
+///
+/// # Notes
+///
+/// All emulations are disabled by default, users should enable them
+/// explicitly after understanding the cost:
+///
+/// - `copy`: emulated via `read` + `write`, all data will be transferred
+///   through the client.
+/// - `rename`: emulated via `copy` + `delete`, the operation is not atomic.
+/// - `append`: emulated via reading the whole existing content and
+///   rewriting it with new data, which is **very** expensive for large
+///   files and not safe under concurrent writers.
+/// - `batch`: emulated via concurrent single `delete` calls.
+/// - `path_misuse`: emulated via stating the path again after `read` or
+///   `list` failed (or listed nothing), so that misused paths return
+///   `IsADirectory` or `NotADirectory` instead of `NotFound`.
+/// - `expire`: emulated via writing a record into the manifest dir for every
+///   object written with `expire_at` after the write succeeded, expired
+///   objects will only be removed while [`ExpiryManifest::gc`] is called.
+///
+/// Emulations will only take effect when the service doesn't support the
+/// operation natively and the required operations are supported.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::layers::EmulateLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(
+///         EmulateLayer::default()
+///             .with_copy(true)
+///             .with_rename(true)
+///             .with_batch(true),
+///     )
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct EmulateLayer {
+    copy: bool,
+    rename: bool,
+    append: bool,
+    batch: bool,
+    batch_concurrency: usize,
+    path_misuse: bool,
+    expire: Option<String>,
+}
+
+impl Default for EmulateLayer {
+    fn default() -> Self {
+        Self {
+            copy: false,
+            rename: false,
+            append: false,
+            batch: false,
+            batch_concurrency: 8,
+            path_misuse: false,
+            expire: None,
+        }
+    }
+}
+
+impl EmulateLayer {
+    /// Emulate `copy` via `read` + `write`.
+    pub fn with_copy(mut self, enabled: bool) -> Self {
+        self.copy = enabled;
+        self
+    }
+
+    /// Emulate `rename` via `copy` + `delete`.
+    ///
+    /// `copy` could be native or emulated.
+    pub fn with_rename(mut self, enabled: bool) -> Self {
+        self.rename = enabled;
+        self
+    }
+
+    /// Emulate `write` with append via reading existing content and
+    /// rewriting it.
+    pub fn with_append(mut self, enabled: bool) -> Self {
+        self.append = enabled;
+        self
+    }
+
+    /// Emulate `batch` delete via concurrent single `delete`.
+    pub fn with_batch(mut self, enabled: bool) -> Self {
+        self.batch = enabled;
+        self
+    }
+
+    /// Set the concurrency of emulated batch, default to `8`.
+    pub fn with_batch_concurrency(mut self, concurrency: usize) -> Self {
+        self.batch_concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Emulate `IsADirectory` and `NotADirectory` errors via `stat` for
+    /// services that report misused paths as `NotFound`.
+    pub fn with_path_misuse(mut self, enabled: bool) -> Self {
+        self.path_misuse = enabled;
+        self
+    }
+
+    /// Emulate `write` with `expire_at` via recording objects in the
+    /// manifest dir, which should be cleaned by [`ExpiryManifest::gc`]
+    /// periodically.
+    pub fn with_expire(mut self, dir: &str) -> Self {
+        self.expire = Some(ExpiryManifest::normalize_dir(dir));
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for EmulateLayer {
+    type LayeredAccessor = EmulateAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        let mut info = inner.info();
+        let cap = info.full_capability();
+
+        let copy = self.copy && !cap.copy && cap.read && cap.write;
+        let rename = self.rename && !cap.rename && (cap.copy || copy) && cap.delete;
+        let append = self.append && !cap.write_can_append && cap.read && cap.write && cap.stat;
+        let batch = self.batch && !cap.batch && cap.delete;
+        let path_misuse = self.path_misuse && !cap.path_misuse_check && cap.stat;
+        let expire = if !cap.write_with_expire_at && cap.write {
+            self.expire.clone()
+        } else {
+            None
+        };
+
+        let full = info.full_capability_mut();
+        if copy {
+            full.copy = true;
+        }
+        if rename {
+            full.rename = true;
+        }
+        if append {
+            full.write_can_append = true;
+        }
+        if batch {
+            full.batch = true;
+            full.batch_delete = true;
+        }
+        if path_misuse {
+            full.path_misuse_check = true;
+        }
+        if expire.is_some() {
+            full.write_with_expire_at = true;
+        }
+
+        EmulateAccessor {
+            inner: Arc::new(inner),
+            info,
+            copy,
+            rename,
+            append,
+            batch,
+            batch_concurrency: self.batch_concurrency,
+            expire,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct EmulateAccessor<A: Accessor> {
+    inner: Arc<A>,
+    info: AccessorInfo,
+
+    copy: bool,
+    rename: bool,
+    append: bool,
+    batch: bool,
+    batch_concurrency: usize,
+    expire: Option<String>,
+}
+
+impl<A: Accessor> EmulateAccessor<A> {
+    async fn emulated_copy(&self, from: &str, to: &str) -> Result<RpCopy> {
+        let (_, mut r) = self.inner.read(from, OpRead::new()).await?;
+        let (_, mut w) = self.inner.write(to, OpWrite::new()).await?;
+
+        let res = async {
+            let mut buf = vec![0; COPY_BUFFER_SIZE];
+            loop {
+                let n = r.read(&mut buf).await?;
+                if n == 0 {
+                    break;
+                }
+
+                let mut bs = &buf[..n];
+                while !bs.is_empty() {
+                    let written = w.write(&bs).await?;
+                    bs = &bs[written..];
+                }
+            }
+            w.close().await
+        }
+        .await;
+
+        if let Err(err) = res {
+            let _ = w.abort().await;
+            return Err(err);
+        }
+
+        Ok(RpCopy::default())
+    }
+
+    fn blocking_emulated_copy(&self, from: &str, to: &str) -> Result<RpCopy> {
+        let (_, mut r) = self.inner.blocking_read(from, OpRead::new())?;
+        let (_, mut w) = self.inner.blocking_write(to, OpWrite::new())?;
+
+        let mut buf = vec![0; COPY_BUFFER_SIZE];
+        loop {
+            let n = oio::BlockingRead::read(&mut r, &mut buf)?;
+            if n == 0 {
+                break;
+            }
+
+            let mut bs = &buf[..n];
+            while !bs.is_empty() {
+                let written = oio::BlockingWrite::write(&mut w, &bs)?;
+                bs = &bs[written..];
+            }
+        }
+        oio::BlockingWrite::close(&mut w)?;
+
+        Ok(RpCopy::default())
+    }
+
+    /// Take the expiry to record if `expire_at` is emulated.
+    fn take_expire(&self, args: &mut OpWrite) -> Option<(String, DateTime<Utc>)> {
+        // Keep `expire_at` in args if the service supports it natively.
+        let dir = self.expire.as_ref()?;
+        Some((dir.clone(), args.take_expire_at()?))
+    }
+
+    /// Read the existing content of path, returns empty if not found.
+    async fn read_existing(&self, path: &str) -> Result<Vec<u8>> {
+        match self.inner.stat(path, OpStat::new()).await {
+            Ok(_) => {}
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err),
+        }
+
+        let (_, mut r) = self.inner.read(path, OpRead::new()).await?;
+        let mut buf = vec![];
+        r.read_to_end(&mut buf).await?;
+        Ok(buf)
+    }
+
+    /// Read the existing content of path, returns empty if not found.
+    fn blocking_read_existing(&self, path: &str) -> Result<Vec<u8>> {
+        match self.inner.blocking_stat(path, OpStat::new()) {
+            Ok(_) => {}
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err),
+        }
+
+        let (_, mut r) = self.inner.blocking_read(path, OpRead::new())?;
+        let mut buf = vec![];
+        oio::BlockingRead::read_to_end(&mut r, &mut buf)?;
+        Ok(buf)
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for EmulateAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = EmulateWriter<A, A::Writer>;
+    type BlockingWriter = EmulateWriter<A, A::BlockingWriter>;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn metadata(&self) -> AccessorInfo {
+        self.info.clone()
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, mut args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let expire = self.take_expire(&mut args);
+
+        if !(self.append && args.append()) {
+            let (rp, w) = self.inner.write(path, args).await?;
+            return Ok((rp, EmulateWriter::new(w, self.inner.clone(), path, expire)));
+        }
+
+        let existing = self.read_existing(path).await?;
+        let (rp, mut w) = self.inner.write(path, args.with_append(false)).await?;
+
+        let mut bs = existing.as_slice();
+        while !bs.is_empty() {
+            let written = w.write(&bs).await?;
+            bs = &bs[written..];
+        }
+
+        Ok((rp, EmulateWriter::new(w, self.inner.clone(), path, expire)))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        if !self.copy {
+            return self.inner.copy(from, to, args).await;
+        }
+
+        self.emulated_copy(from, to).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        if !self.rename {
+            return self.inner.rename(from, to, args).await;
+        }
+
+        if self.copy {
+            self.emulated_copy(from, to).await?;
+        } else {
+            self.inner.copy(from, to, OpCopy::new()).await?;
+        }
+        self.inner.delete(from, OpDelete::new()).await?;
+
+        Ok(RpRename::default())
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        if !self.batch {
+            return self.inner.batch(args).await;
+        }
+
+        let results = futures::stream::iter(args.into_operation())
+            .map(|(path, op)| async move {
+                let res = match op {
+                    BatchOperation::Delete(op) => {
+                        self.inner.delete(&path, op).await.map(BatchedReply::from)
+                    }
+                };
+                (path, res)
+            })
+            .buffered(self.batch_concurrency)
+            .collect()
+            .await;
+
+        Ok(RpBatch::new(results))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(
+        &self,
+        path: &str,
+        mut args: OpWrite,
+    ) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let expire = self.take_expire(&mut args);
+
+        if !(self.append && args.append()) {
+            let (rp, w) = self.inner.blocking_write(path, args)?;
+            return Ok((rp, EmulateWriter::new(w, self.inner.clone(), path, expire)));
+        }
+
+        let existing = self.blocking_read_existing(path)?;
+        let (rp, mut w) = self.inner.blocking_write(path, args.with_append(false))?;
+
+        let mut bs = existing.as_slice();
+        while !bs.is_empty() {
+            let written = oio::BlockingWrite::write(&mut w, &bs)?;
+            bs = &bs[written..];
+        }
+
+        Ok((rp, EmulateWriter::new(w, self.inner.clone(), path, expire)))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        if !self.copy {
+            return self.inner.blocking_copy(from, to, args);
+        }
+
+        self.blocking_emulated_copy(from, to)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        if !self.rename {
+            return self.inner.blocking_rename(from, to, args);
+        }
+
+        if self.copy {
+            self.blocking_emulated_copy(from, to)?;
+        } else {
+            self.inner.blocking_copy(from, to, OpCopy::new())?;
+        }
+        self.inner.blocking_delete(from, OpDelete::new())?;
+
+        Ok(RpRename::default())
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+/// EmulateWriter records the expiry of object in manifest after the write
+/// succeeded.
+pub struct EmulateWriter<A: Accessor, W> {
+    inner: W,
+    acc: Arc<A>,
+    path: String,
+    expire: Option<(String, DateTime<Utc>)>,
+
+    fut: Option<BoxFuture<'static, Result<()>>>,
+}
+
+/// # Safety
+///
+/// We will only take `&mut Self` reference for EmulateWriter.
+unsafe impl<A: Accessor, W: Sync> Sync for EmulateWriter<A, W> {}
+
+impl<A: Accessor, W> EmulateWriter<A, W> {
+    fn new(inner: W, acc: Arc<A>, path: &str, expire: Option<(String, DateTime<Utc>)>) -> Self {
+        Self {
+            inner,
+            acc,
+            path: path.to_string(),
+            expire,
+            fut: None,
+        }
+    }
+}
+
+impl<A: Accessor, W: oio::Write> oio::Write for EmulateWriter<A, W> {
+    fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        self.inner.poll_write(cx, bs)
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_abort(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        if self.fut.is_none() {
+            ready!(self.inner.poll_close(cx))?;
+
+            let Some((dir, expire_at)) = self.expire.take() else {
+                return Poll::Ready(Ok(()));
+            };
+            let acc = self.acc.clone();
+            let path = self.path.clone();
+            self.fut = Some(Box::pin(async move {
+                // Stat after close so that the record only matches the
+                // version we have written.
+                let meta = acc.stat(&path, OpStat::new()).await?.into_metadata();
+                let record = ExpiryManifest::record_path(&dir, expire_at);
+                let content = ExpiryManifest::record_content(&path, &meta);
+
+                let (_, mut w) = acc.write(&record, OpWrite::new()).await?;
+                w.write(&content.as_bytes()).await?;
+                w.close().await
+            }));
+        }
+
+        let fut = self.fut.as_mut().expect("future must be valid");
+        let res = ready!(fut.poll_unpin(cx));
+        self.fut = None;
+        Poll::Ready(res)
+    }
+}
+
+impl<A: Accessor, W: oio::BlockingWrite> oio::BlockingWrite for EmulateWriter<A, W> {
+    fn write(&mut self, bs: &dyn oio::WriteBuf) -> Result<usize> {
+        self.inner.write(bs)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.inner.close()?;
+
+        let Some((dir, expire_at)) = self.expire.take() else {
+            return Ok(());
+        };
+        let meta = self
+            .acc
+            .blocking_stat(&self.path, OpStat::new())?
+            .into_metadata();
+        let record = ExpiryManifest::record_path(&dir, expire_at);
+        let content = ExpiryManifest::record_content(&self.path, &meta);
+
+        let (_, mut w) = self.acc.blocking_write(&record, OpWrite::new())?;
+        oio::BlockingWrite::write(&mut w, &content.as_bytes())?;
+        oio::BlockingWrite::close(&mut w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    /// Hide native copy and rename of the inner service so that emulations
+    /// could take effect.
+    struct NoCopyLayer;
+
+    impl<A: Accessor> Layer<A> for NoCopyLayer {
+        type LayeredAccessor = NoCopyAccessor<A>;
+
+        fn layer(&self, inner: A) -> Self::LayeredAccessor {
+            NoCopyAccessor { inner }
+        }
+    }
+
+    #[derive(Debug)]
+    struct NoCopyAccessor<A: Accessor> {
+        inner: A,
+    }
+
+    #[async_trait]
+    impl<A: Accessor> LayeredAccessor for NoCopyAccessor<A> {
+        type Inner = A;
+        type Reader = A::Reader;
+        type BlockingReader = A::BlockingReader;
+        type Writer = A::Writer;
+        type BlockingWriter = A::BlockingWriter;
+        type Lister = A::Lister;
+        type BlockingLister = A::BlockingLister;
+
+        fn inner(&self) -> &Self::Inner {
+            &self.inner
+        }
+
+        fn metadata(&self) -> AccessorInfo {
+            let mut info = self.inner.info();
+            let cap = info.full_capability_mut();
+            cap.copy = false;
+            cap.rename = false;
+            info
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            self.inner.read(path, args).await
+        }
+
+        async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            self.inner.write(path, args).await
+        }
+
+        async fn copy(&self, _: &str, _: &str, _: OpCopy) -> Result<RpCopy> {
+            Err(Error::new(ErrorKind::Unsupported, "copy is hidden"))
+        }
+
+        async fn rename(&self, _: &str, _: &str, _: OpRename) -> Result<RpRename> {
+            Err(Error::new(ErrorKind::Unsupported, "rename is hidden"))
+        }
+
+        async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+            self.inner.list(path, args).await
+        }
+
+        fn blocking_read(
+            &self,
+            path: &str,
+            args: OpRead,
+        ) -> Result<(RpRead, Self::BlockingReader)> {
+            self.inner.blocking_read(path, args)
+        }
+
+        fn blocking_write(
+            &self,
+            path: &str,
+            args: OpWrite,
+        ) -> Result<(RpWrite, Self::BlockingWriter)> {
+            self.inner.blocking_write(path, args)
+        }
+
+        fn blocking_copy(&self, _: &str, _: &str, _: OpCopy) -> Result<RpCopy> {
+            Err(Error::new(ErrorKind::Unsupported, "copy is hidden"))
+        }
+
+        fn blocking_rename(&self, _: &str, _: &str, _: OpRename) -> Result<RpRename> {
+            Err(Error::new(ErrorKind::Unsupported, "rename is hidden"))
+        }
+
+        fn blocking_list(
+            &self,
+            path: &str,
+            args: OpList,
+        ) -> Result<(RpList, Self::BlockingLister)> {
+            self.inner.blocking_list(path, args)
+        }
+    }
+
+    fn new_emulated_operator(layer: EmulateLayer) -> Operator {
+        Operator::new(Memory::default())
+            .expect("must init")
+            .layer(NoCopyLayer)
+            .layer(layer)
+            .finish()
+    }
+
+    #[tokio::test]
+    async fn test_emulate_capability() {
+        let op = Operator::new(Memory::default())
+            .expect("must init")
+            .layer(EmulateLayer::default().with_rename(true).with_batch(true))
+            .finish();
+
+        let cap = op.info().full_capability();
+        assert!(cap.batch);
+        assert!(cap.batch_delete);
+    }
+
+    #[tokio::test]
+    async fn test_emulate_disabled_by_default() {
+        let op = new_emulated_operator(EmulateLayer::default());
+
+        let cap = op.info().full_capability();
+        assert!(!cap.copy);
+        assert!(!cap.rename);
+        assert!(!cap.write_can_append);
+        assert!(!cap.path_misuse_check);
+    }
+
+    #[tokio::test]
+    async fn test_emulate_copy_and_rename() -> Result<()> {
+        let op = new_emulated_operator(EmulateLayer::default().with_copy(true).with_rename(true));
+        let cap = op.info().full_capability();
+        assert!(cap.copy);
+        assert!(cap.rename);
+
+        // Larger than the copy buffer to cover multiple rounds.
+        let content = vec![7; COPY_BUFFER_SIZE * 2 + 1];
+        op.write("a", content.clone()).await?;
+
+        op.copy("a", "b").await?;
+        assert_eq!(op.read("a").await?, content);
+        assert_eq!(op.read("b").await?, content);
+
+        op.rename("b", "c").await?;
+        assert!(!op.is_exist("b").await?);
+        assert_eq!(op.read("c").await?, content);
+        Ok(())
+    }
+
+    #[test]
+    fn test_emulate_blocking_copy_and_rename() -> Result<()> {
+        let op = new_emulated_operator(EmulateLayer::default().with_copy(true).with_rename(true))
+            .blocking();
+
+        op.write("a", "hello")?;
+        op.copy("a", "b")?;
+        assert_eq!(op.read("b")?, b"hello");
+
+        op.rename("b", "c")?;
+        assert!(!op.is_exist("b")?);
+        assert_eq!(op.read("c")?, b"hello");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_emulate_copy_not_exist() {
+        let op = new_emulated_operator(EmulateLayer::default().with_copy(true));
+
+        let err = op.copy("not_exist", "b").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_emulate_append() -> Result<()> {
+        let op = new_emulated_operator(EmulateLayer::default().with_append(true));
+        assert!(op.info().full_capability().write_can_append);
+
+        // Append to a not exist file creates it.
+        op.write_with("a", "hello").append(true).await?;
+        op.write_with("a", " world").append(true).await?;
+        assert_eq!(op.read("a").await?, b"hello world");
+
+        let op = op.blocking();
+        op.write_with("a", "!").append(true).call()?;
+        assert_eq!(op.read("a")?, b"hello world!");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_emulate_batch_delete() -> Result<()> {
+        let op = Operator::new(Memory::default())
+            .expect("must init")
+            .layer(EmulateLayer::default().with_batch(true))
+            .finish();
+
+        op.write("a", "hello").await?;
+        op.write("b", "world").await?;
+        op.remove(vec!["a".to_string(), "b".to_string()]).await?;
+
+        assert!(!op.is_exist("a").await?);
+        assert!(!op.is_exist("b").await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_emulate_expire() -> Result<()> {
+        let op = Operator::new(Memory::default())
+            .expect("must init")
+            .layer(EmulateLayer::default().with_expire(".expiry"))
+            .finish();
+        assert!(op.info().full_capability().write_with_expire_at);
+
+        let now = chrono::Utc::now();
+        op.write_with("a", "hello")
+            .expire_at(now - chrono::Duration::seconds(1))
+            .await?;
+        op.write_with("b", "world")
+            .expire_at(now + chrono::Duration::hours(1))
+            .await?;
+
+        let manifest = ExpiryManifest::new(op.clone(), ".expiry");
+        assert_eq!(manifest.gc().await?, 1);
+        assert!(!op.is_exist("a").await?);
+        assert!(op.is_exist("b").await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_emulate_expire_keeps_overwritten() -> Result<()> {
+        let op = Operator::new(Memory::default())
+            .expect("must init")
+            .layer(EmulateLayer::default().with_expire(".expiry"))
+            .finish();
+
+        op.write_with("a", "hello")
+            .expire_at(chrono::Utc::now() - chrono::Duration::seconds(1))
+            .await?;
+        op.write("a", "world!").await?;
+
+        let manifest = ExpiryManifest::new(op.clone(), ".expiry");
+        assert_eq!(manifest.gc().await?, 1);
+        assert_eq!(op.read("a").await?, b"world!");
+        assert!(op.list(".expiry/").await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_emulate_expire_after_close() -> Result<()> {
+        let op = Operator::new(Memory::default())
+            .expect("must init")
+            .layer(EmulateLayer::default().with_expire(".expiry"))
+            .finish();
+
+        let mut w = op
+            .writer_with("a")
+            .expire_at(chrono::Utc::now() - chrono::Duration::seconds(1))
+            .await?;
+        w.write("hello").await?;
+        assert!(op.list(".expiry/").await?.is_empty());
+        w.abort().await?;
+        assert!(op.list(".expiry/").await?.is_empty());
+        Ok(())
+    }
+}
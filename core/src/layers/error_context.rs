@@ -238,6 +238,87 @@ impl<A: Accessor> LayeredAccessor for ErrorContextAccessor<A> {
         })
     }
 
+    async fn create_symlink(
+        &self,
+        target: &str,
+        path: &str,
+        args: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        self.inner
+            .create_symlink(target, path, args)
+            .map_err(|err| {
+                err.with_operation(Operation::CreateSymlink)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("target", target)
+                    .with_context("path", path)
+            })
+            .await
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        self.inner
+            .set_permissions(path, args)
+            .map_err(|err| {
+                err.with_operation(Operation::SetPermissions)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path)
+            })
+            .await
+    }
+
+    async fn create_snapshot(
+        &self,
+        path: &str,
+        args: OpCreateSnapshot,
+    ) -> Result<RpCreateSnapshot> {
+        self.inner
+            .create_snapshot(path, args)
+            .map_err(|err| {
+                err.with_operation(Operation::CreateSnapshot)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path)
+            })
+            .await
+    }
+
+    async fn lease(&self, path: &str, args: OpLease) -> Result<RpLease> {
+        self.inner
+            .lease(path, args)
+            .map_err(|err| {
+                err.with_operation(Operation::Lease)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path)
+            })
+            .await
+    }
+
+    async fn list_roots(&self, args: OpListRoots) -> Result<RpListRoots> {
+        self.inner
+            .list_roots(args)
+            .map_err(|err| {
+                err.with_operation(Operation::ListRoots)
+                    .with_context("service", self.meta.scheme())
+            })
+            .await
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        let sources = args.sources().len();
+        self.inner
+            .concat(path, args)
+            .map_err(|err| {
+                err.with_operation(Operation::Concat)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path)
+                    .with_context("sources", sources.to_string())
+            })
+            .await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.inner.blocking_create_dir(path, args).map_err(|err| {
             err.with_operation(Operation::BlockingCreateDir)
@@ -339,6 +420,36 @@ impl<A: Accessor> LayeredAccessor for ErrorContextAccessor<A> {
                     .with_context("path", path)
             })
     }
+
+    fn blocking_create_symlink(
+        &self,
+        target: &str,
+        path: &str,
+        args: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        self.inner
+            .blocking_create_symlink(target, path, args)
+            .map_err(|err| {
+                err.with_operation(Operation::BlockingCreateSymlink)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("target", target)
+                    .with_context("path", path)
+            })
+    }
+
+    fn blocking_set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        self.inner
+            .blocking_set_permissions(path, args)
+            .map_err(|err| {
+                err.with_operation(Operation::BlockingSetPermissions)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path)
+            })
+    }
 }
 
 pub struct ErrorContextWrapper<T> {
@@ -415,6 +526,14 @@ impl<T: oio::Write> oio::Write for ErrorContextWrapper<T> {
         })
     }
 
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx).map_err(|err| {
+            err.with_operation(WriteOperation::Ready)
+                .with_context("service", self.scheme)
+                .with_context("path", &self.path)
+        })
+    }
+
     fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         self.inner.poll_close(cx).map_err(|err| {
             err.with_operation(WriteOperation::Close)
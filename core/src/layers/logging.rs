@@ -1301,6 +1301,10 @@ impl<W: oio::Write> oio::Write for LoggingWriter<W> {
         }
     }
 
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
     fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         match ready!(self.inner.poll_abort(cx)) {
             Ok(_) => {
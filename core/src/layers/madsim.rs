@@ -317,6 +317,10 @@ impl oio::Write for MadsimWriter {
         }
     }
 
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<crate::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
     fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<crate::Result<()>> {
         Poll::Ready(Err(Error::new(
             ErrorKind::Unsupported,
@@ -0,0 +1,658 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use flagset::FlagSet;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use log::warn;
+
+use crate::raw::*;
+use crate::*;
+
+/// Serve metadata from a secondary index to avoid extra `stat` calls.
+///
+/// Listing with metakeys that the service doesn't return (like
+/// `content_type` on most object storages) will issue one `stat` per entry,
+/// which could be very slow for large dirs. With this layer, lister will
+/// consult the [`MetadataIndex`] first and only fall back to `stat` if the
+/// index doesn't contain the entry. `stat` will also be served from the
+/// index.
+///
+/// The index is maintained by this layer:
+///
+/// - Metadata will be fetched via `stat` and stored after every successful
+///   write, and after `stat` misses.
+/// - Entries will be removed after `delete` (with all children for dirs),
+///   `copy` (the target), `rename` and `batch`.
+///
+/// # Notes
+///
+/// - Changes that bypass this layer (like other processes writing to the
+///   same service) will not be seen, users should only use this layer on
+///   data that is written via it.
+/// - Failures of [`MetadataIndex::get`] will be treated as misses, but
+///   failures of updating the index will be returned to users since the
+///   index could be stale.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::sync::Mutex;
+///
+/// use async_trait::async_trait;
+/// use opendal::layers::MetadataIndex;
+/// use opendal::layers::MetadataIndexLayer;
+/// use opendal::services;
+/// use opendal::Metadata;
+/// use opendal::Operator;
+/// use opendal::Result;
+///
+/// #[derive(Debug, Default)]
+/// struct MemoryIndex(Mutex<HashMap<String, Metadata>>);
+///
+/// #[async_trait]
+/// impl MetadataIndex for MemoryIndex {
+///     async fn get(&self, path: &str) -> Result<Option<Metadata>> {
+///         Ok(self.0.lock().unwrap().get(path).cloned())
+///     }
+///
+///     async fn set(&self, path: &str, meta: Metadata) -> Result<()> {
+///         self.0.lock().unwrap().insert(path.to_string(), meta);
+///         Ok(())
+///     }
+///
+///     async fn remove(&self, path: &str) -> Result<()> {
+///         self.0.lock().unwrap().remove(path);
+///         Ok(())
+///     }
+///
+///     async fn remove_prefix(&self, prefix: &str) -> Result<()> {
+///         self.0.lock().unwrap().retain(|k, _| !k.starts_with(prefix));
+///         Ok(())
+///     }
+/// }
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(MetadataIndexLayer::new(MemoryIndex::default()))
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct MetadataIndexLayer {
+    index: Arc<dyn MetadataIndex>,
+}
+
+impl MetadataIndexLayer {
+    /// Create a new MetadataIndexLayer with given index.
+    pub fn new(index: impl MetadataIndex) -> Self {
+        Self {
+            index: Arc::new(index),
+        }
+    }
+}
+
+impl<A: Accessor> Layer<A> for MetadataIndexLayer {
+    type LayeredAccessor = MetadataIndexAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        MetadataIndexAccessor {
+            inner: Arc::new(inner),
+            index: self.index.clone(),
+        }
+    }
+}
+
+/// MetadataIndex is a secondary index of metadata used by
+/// [`MetadataIndexLayer`], like a local sled database or a redis server.
+///
+/// Paths are relative to the root of operator, dirs end with `/`.
+///
+/// Blocking methods are used by blocking operations. They will ignore the
+/// index or return [`ErrorKind::Unsupported`] by default, implementations
+/// backed by a blocking store should implement them.
+#[async_trait]
+pub trait MetadataIndex: Debug + Send + Sync + 'static {
+    /// Get the metadata of given path, returns `None` if not indexed.
+    async fn get(&self, path: &str) -> Result<Option<Metadata>>;
+
+    /// Store the metadata of given path.
+    async fn set(&self, path: &str, meta: Metadata) -> Result<()>;
+
+    /// Remove the metadata of given path.
+    async fn remove(&self, path: &str) -> Result<()>;
+
+    /// Remove the metadata of all paths start with given prefix, including
+    /// the prefix itself.
+    async fn remove_prefix(&self, prefix: &str) -> Result<()>;
+
+    /// Get the metadata of given path in blocking way.
+    fn blocking_get(&self, path: &str) -> Result<Option<Metadata>> {
+        let _ = path;
+        Ok(None)
+    }
+
+    /// Store the metadata of given path in blocking way.
+    fn blocking_set(&self, path: &str, meta: Metadata) -> Result<()> {
+        let _ = (path, meta);
+        Err(new_blocking_unsupported_error())
+    }
+
+    /// Remove the metadata of given path in blocking way.
+    fn blocking_remove(&self, path: &str) -> Result<()> {
+        let _ = path;
+        Err(new_blocking_unsupported_error())
+    }
+
+    /// Remove the metadata of all paths start with given prefix in
+    /// blocking way.
+    fn blocking_remove_prefix(&self, prefix: &str) -> Result<()> {
+        let _ = prefix;
+        Err(new_blocking_unsupported_error())
+    }
+}
+
+fn new_blocking_unsupported_error() -> Error {
+    Error::new(
+        ErrorKind::Unsupported,
+        "metadata index doesn't support blocking operations",
+    )
+}
+
+/// Metadata returned by `stat` is all we could know about the path.
+fn into_complete(meta: Metadata) -> Metadata {
+    let metakey = meta.metakey() | Metakey::Complete;
+    meta.with_metakey(metakey)
+}
+
+/// Only plain stat could be served from the index.
+///
+/// Stat with restricted fields is not plain either, otherwise the partial
+/// metadata will be cached as complete.
+fn is_plain_stat(args: &OpStat) -> bool {
+    args.if_match().is_none()
+        && args.if_none_match().is_none()
+        && args.version().is_none()
+        && args.snapshot().is_none()
+        && args.fields().is_none()
+}
+
+#[derive(Debug)]
+pub struct MetadataIndexAccessor<A: Accessor> {
+    inner: Arc<A>,
+    index: Arc<dyn MetadataIndex>,
+}
+
+impl<A: Accessor> MetadataIndexAccessor<A> {
+    async fn index_get(&self, path: &str) -> Option<Metadata> {
+        match self.index.get(path).await {
+            Ok(meta) => meta,
+            Err(err) => {
+                warn!("metadata index get {path} failed, fallback to stat: {err}");
+                None
+            }
+        }
+    }
+
+    fn blocking_index_get(&self, path: &str) -> Option<Metadata> {
+        match self.index.blocking_get(path) {
+            Ok(meta) => meta,
+            Err(err) => {
+                warn!("metadata index get {path} failed, fallback to stat: {err}");
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for MetadataIndexAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = MetadataIndexWriter<A, A::Writer>;
+    type BlockingWriter = MetadataIndexWriter<A, A::BlockingWriter>;
+    type Lister = MetadataIndexLister<A::Lister>;
+    type BlockingLister = MetadataIndexLister<A::BlockingLister>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let (rp, w) = self.inner.write(path, args).await?;
+        let w = MetadataIndexWriter::new(w, self.inner.clone(), self.index.clone(), path);
+        Ok((rp, w))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let rp = self.inner.copy(from, to, args).await?;
+        self.index.remove(to).await?;
+        Ok(rp)
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let rp = self.inner.rename(from, to, args).await?;
+        self.index.remove(from).await?;
+        self.index.remove(to).await?;
+        Ok(rp)
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if !is_plain_stat(&args) {
+            return self.inner.stat(path, args).await;
+        }
+        if let Some(meta) = self.index_get(path).await {
+            return Ok(RpStat::new(meta));
+        }
+
+        let meta = into_complete(self.inner.stat(path, args).await?.into_metadata());
+        if let Err(err) = self.index.set(path, meta.clone()).await {
+            warn!("metadata index set {path} failed: {err}");
+        }
+        Ok(RpStat::new(meta))
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let rp = self.inner.delete(path, args).await?;
+        // Children of dir could be deleted too by some services.
+        if path.ends_with('/') {
+            self.index.remove_prefix(path).await?;
+        } else {
+            self.index.remove(path).await?;
+        }
+        Ok(rp)
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let required = args.metakey();
+        let (rp, l) = self.inner.list(path, args).await?;
+        Ok((
+            rp,
+            MetadataIndexLister::new(l, self.index.clone(), required),
+        ))
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let paths: Vec<String> = args.operation().iter().map(|(p, _)| p.clone()).collect();
+        let res = self.inner.batch(args).await;
+        // Remove all paths even if batch failed since some of them may
+        // have been deleted.
+        for path in paths {
+            self.index.remove(&path).await?;
+        }
+        res
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let (rp, w) = self.inner.blocking_write(path, args)?;
+        let w = MetadataIndexWriter::new(w, self.inner.clone(), self.index.clone(), path);
+        Ok((rp, w))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let rp = self.inner.blocking_copy(from, to, args)?;
+        self.index.blocking_remove(to)?;
+        Ok(rp)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let rp = self.inner.blocking_rename(from, to, args)?;
+        self.index.blocking_remove(from)?;
+        self.index.blocking_remove(to)?;
+        Ok(rp)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if !is_plain_stat(&args) {
+            return self.inner.blocking_stat(path, args);
+        }
+        if let Some(meta) = self.blocking_index_get(path) {
+            return Ok(RpStat::new(meta));
+        }
+
+        let meta = into_complete(self.inner.blocking_stat(path, args)?.into_metadata());
+        if let Err(err) = self.index.blocking_set(path, meta.clone()) {
+            warn!("metadata index set {path} failed: {err}");
+        }
+        Ok(RpStat::new(meta))
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let rp = self.inner.blocking_delete(path, args)?;
+        if path.ends_with('/') {
+            self.index.blocking_remove_prefix(path)?;
+        } else {
+            self.index.blocking_remove(path)?;
+        }
+        Ok(rp)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let required = args.metakey();
+        let (rp, l) = self.inner.blocking_list(path, args)?;
+        Ok((
+            rp,
+            MetadataIndexLister::new(l, self.index.clone(), required),
+        ))
+    }
+}
+
+/// MetadataIndexWriter updates the index after the write succeeded.
+pub struct MetadataIndexWriter<A: Accessor, W> {
+    inner: W,
+    acc: Arc<A>,
+    index: Arc<dyn MetadataIndex>,
+    path: String,
+
+    fut: Option<BoxFuture<'static, Result<()>>>,
+}
+
+/// # Safety
+///
+/// We will only take `&mut Self` reference for MetadataIndexWriter.
+unsafe impl<A: Accessor, W: Sync> Sync for MetadataIndexWriter<A, W> {}
+
+impl<A: Accessor, W> MetadataIndexWriter<A, W> {
+    fn new(inner: W, acc: Arc<A>, index: Arc<dyn MetadataIndex>, path: &str) -> Self {
+        Self {
+            inner,
+            acc,
+            index,
+            path: path.to_string(),
+            fut: None,
+        }
+    }
+}
+
+impl<A: Accessor, W: oio::Write> oio::Write for MetadataIndexWriter<A, W> {
+    fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        self.inner.poll_write(cx, bs)
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_abort(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        if self.fut.is_none() {
+            ready!(self.inner.poll_close(cx))?;
+
+            let acc = self.acc.clone();
+            let index = self.index.clone();
+            let path = self.path.clone();
+            self.fut = Some(Box::pin(async move {
+                match acc.stat(&path, OpStat::new()).await {
+                    Ok(rp) => index.set(&path, into_complete(rp.into_metadata())).await,
+                    // Make sure the index will not be stale.
+                    Err(_) => index.remove(&path).await,
+                }
+            }));
+        }
+
+        let fut = self.fut.as_mut().expect("future must be valid");
+        let res = ready!(fut.poll_unpin(cx));
+        self.fut = None;
+        Poll::Ready(res)
+    }
+}
+
+impl<A: Accessor, W: oio::BlockingWrite> oio::BlockingWrite for MetadataIndexWriter<A, W> {
+    fn write(&mut self, bs: &dyn oio::WriteBuf) -> Result<usize> {
+        self.inner.write(bs)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.inner.close()?;
+
+        match self.acc.blocking_stat(&self.path, OpStat::new()) {
+            Ok(rp) => self
+                .index
+                .blocking_set(&self.path, into_complete(rp.into_metadata())),
+            Err(_) => self.index.blocking_remove(&self.path),
+        }
+    }
+}
+
+/// MetadataIndexLister fills metadata of entries from the index.
+pub struct MetadataIndexLister<L> {
+    inner: L,
+    index: Arc<dyn MetadataIndex>,
+    required: FlagSet<Metakey>,
+
+    fut: Option<BoxFuture<'static, (oio::Entry, Result<Option<Metadata>>)>>,
+}
+
+/// # Safety
+///
+/// We will only take `&mut Self` reference for MetadataIndexLister.
+unsafe impl<L: Sync> Sync for MetadataIndexLister<L> {}
+
+impl<L> MetadataIndexLister<L> {
+    fn new(inner: L, index: Arc<dyn MetadataIndex>, required: FlagSet<Metakey>) -> Self {
+        Self {
+            inner,
+            index,
+            required,
+            fut: None,
+        }
+    }
+
+    /// Replace the metadata of entry if the indexed one is good enough.
+    fn hydrate(&self, entry: oio::Entry, res: Result<Option<Metadata>>) -> oio::Entry {
+        match res {
+            Ok(Some(meta)) if meta.contains_metakey(self.required) => {
+                oio::Entry::with(entry.path().to_string(), meta)
+            }
+            Ok(_) => entry,
+            Err(err) => {
+                warn!(
+                    "metadata index get {} failed, fallback to stat: {err}",
+                    entry.path()
+                );
+                entry
+            }
+        }
+    }
+}
+
+impl<L: oio::List> oio::List for MetadataIndexLister<L> {
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<oio::Entry>>> {
+        if let Some(fut) = self.fut.as_mut() {
+            let (entry, res) = ready!(fut.poll_unpin(cx));
+            self.fut = None;
+            return Poll::Ready(Ok(Some(self.hydrate(entry, res))));
+        }
+
+        let Some(entry) = ready!(self.inner.poll_next(cx))? else {
+            return Poll::Ready(Ok(None));
+        };
+        if entry.metadata().contains_metakey(self.required) {
+            return Poll::Ready(Ok(Some(entry)));
+        }
+
+        let index = self.index.clone();
+        self.fut = Some(Box::pin(async move {
+            let res = index.get(entry.path()).await;
+            (entry, res)
+        }));
+        self.poll_next(cx)
+    }
+}
+
+impl<L: oio::BlockingList> oio::BlockingList for MetadataIndexLister<L> {
+    fn next(&mut self) -> Result<Option<oio::Entry>> {
+        let Some(entry) = self.inner.next()? else {
+            return Ok(None);
+        };
+        if entry.metadata().contains_metakey(self.required) {
+            return Ok(Some(entry));
+        }
+
+        let res = self.index.blocking_get(entry.path());
+        Ok(Some(self.hydrate(entry, res)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Mutex;
+
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services::Memory;
+
+    #[derive(Debug, Default)]
+    struct MockIndex {
+        data: Mutex<HashMap<String, Metadata>>,
+        hits: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl MetadataIndex for MockIndex {
+        async fn get(&self, path: &str) -> Result<Option<Metadata>> {
+            let meta = self.data.lock().unwrap().get(path).cloned();
+            if meta.is_some() {
+                self.hits.fetch_add(1, Ordering::SeqCst);
+            }
+            Ok(meta)
+        }
+
+        async fn set(&self, path: &str, meta: Metadata) -> Result<()> {
+            self.data.lock().unwrap().insert(path.to_string(), meta);
+            Ok(())
+        }
+
+        async fn remove(&self, path: &str) -> Result<()> {
+            self.data.lock().unwrap().remove(path);
+            Ok(())
+        }
+
+        async fn remove_prefix(&self, prefix: &str) -> Result<()> {
+            self.data
+                .lock()
+                .unwrap()
+                .retain(|k, _| !k.starts_with(prefix));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metadata_index() -> Result<()> {
+        let index = Arc::new(MockIndex::default());
+        let op = Operator::new(Memory::default())?
+            .layer(MetadataIndexLayer {
+                index: index.clone(),
+            })
+            .finish();
+
+        op.write("dir/a", "Hello").await?;
+        op.write("dir/b", "World!").await?;
+        op.delete("dir/b").await?;
+        assert_eq!(index.data.lock().unwrap().len(), 1);
+
+        let entries: Vec<_> = op
+            .lister_with("dir/")
+            .metakey(Metakey::ContentLength)
+            .await?
+            .try_collect()
+            .await?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].metadata().content_length(), 5);
+        assert_eq!(index.hits.load(Ordering::SeqCst), 1);
+
+        let meta = op.stat("dir/a").await?;
+        assert_eq!(meta.content_length(), 5);
+        assert_eq!(index.hits.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_metadata_index_delete_dir() -> Result<()> {
+        let index = Arc::new(MockIndex::default());
+        let op = Operator::new(Memory::default())?
+            .layer(MetadataIndexLayer {
+                index: index.clone(),
+            })
+            .finish();
+
+        op.write("dir/a", "Hello").await?;
+        op.write("dir/sub/b", "World!").await?;
+        op.write("dirx", "!").await?;
+        index
+            .data
+            .lock()
+            .unwrap()
+            .insert("dir/".to_string(), Metadata::new(EntryMode::DIR));
+
+        op.delete("dir/").await?;
+        let data = index.data.lock().unwrap();
+        assert_eq!(data.keys().collect::<Vec<_>>(), vec!["dirx"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_plain_stat() {
+        assert!(is_plain_stat(&OpStat::new()));
+        assert!(!is_plain_stat(&OpStat::new().with_version("v1")));
+        assert!(!is_plain_stat(&OpStat::new().with_snapshot("s1")));
+        assert!(!is_plain_stat(
+            &OpStat::new().with_fields(Metakey::ContentLength)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_metadata_index_bypass_restricted_fields() -> Result<()> {
+        let index = Arc::new(MockIndex::default());
+        let op = Operator::new(Memory::default())?
+            .layer(MetadataIndexLayer {
+                index: index.clone(),
+            })
+            .finish();
+
+        op.write("a", "Hello").await?;
+        index.data.lock().unwrap().clear();
+
+        op.stat_with("a").fields(Metakey::ContentLength).await?;
+        assert!(index.data.lock().unwrap().is_empty());
+        assert_eq!(index.hits.load(Ordering::SeqCst), 0);
+        Ok(())
+    }
+}
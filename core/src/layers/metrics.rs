@@ -25,6 +25,7 @@ use std::time::Instant;
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use flagset::FlagSet;
 use futures::FutureExt;
 use futures::TryFutureExt;
 use metrics::increment_counter;
@@ -53,6 +54,9 @@ static METRIC_REQUESTS_DURATION_SECONDS: &str = "opendal_requests_duration_secon
 static METRICS_ERRORS_TOTAL: &str = "opendal_errors_total";
 /// bytes_total records all bytes processed by operator.
 static METRIC_BYTES_TOTAL: &str = "opendal_bytes_total";
+/// list_entries_total records all entries returned by list, labeled by
+/// whether the entry is served from listing or needs a fallback stat.
+static METRIC_LIST_ENTRIES_TOTAL: &str = "opendal_list_entries_total";
 
 /// The scheme of the service.
 static LABEL_SERVICE: &str = "service";
@@ -60,6 +64,8 @@ static LABEL_SERVICE: &str = "service";
 static LABEL_OPERATION: &str = "operation";
 /// The error kind of this failed request.
 static LABEL_ERROR: &str = "error";
+/// The source of list entries, `list` or `fallback_stat`.
+static LABEL_SOURCE: &str = "source";
 
 /// Add [metrics](https://docs.rs/metrics/) for every operations.
 ///
@@ -69,6 +75,8 @@ static LABEL_ERROR: &str = "error";
 /// - `opendal_requests_duration_seconds`: Request duration seconds.
 /// - `opendal_errors_total`: Total error numbers.
 /// - `opendal_bytes_total`: bytes read/write from/to underlying storage.
+/// - `opendal_list_entries_total`: Total list entries, entries that don't
+///   contain the required metakey need an extra stat call.
 ///
 /// # Labels
 ///
@@ -77,6 +85,7 @@ static LABEL_ERROR: &str = "error";
 /// - `service`: Service name from [`Scheme`]
 /// - `operation`: Operation name from [`Operation`]
 /// - `error`: [`ErrorKind`] received by requests
+/// - `source`: Where list entries served from, `list` or `fallback_stat`
 ///
 /// # Notes
 ///
@@ -185,6 +194,9 @@ struct MetricsHandler {
 
     requests_total_blocking_list: Counter,
     requests_duration_seconds_blocking_list: Histogram,
+
+    list_entries_total_from_list: Counter,
+    list_entries_total_need_stat: Counter,
 }
 
 impl MetricsHandler {
@@ -376,6 +388,17 @@ impl MetricsHandler {
                 LABEL_SERVICE => service,
                 LABEL_OPERATION => Operation::BlockingList.into_static(),
             ),
+
+            list_entries_total_from_list: register_counter!(
+                METRIC_LIST_ENTRIES_TOTAL,
+                LABEL_SERVICE => service,
+                LABEL_SOURCE => "list",
+            ),
+            list_entries_total_need_stat: register_counter!(
+                METRIC_LIST_ENTRIES_TOTAL,
+                LABEL_SERVICE => service,
+                LABEL_SOURCE => "fallback_stat",
+            ),
         }
     }
 
@@ -412,8 +435,8 @@ impl<A: Accessor> LayeredAccessor for MetricsAccessor<A> {
     type BlockingReader = MetricWrapper<A::BlockingReader>;
     type Writer = MetricWrapper<A::Writer>;
     type BlockingWriter = MetricWrapper<A::BlockingWriter>;
-    type Lister = A::Lister;
-    type BlockingLister = A::BlockingLister;
+    type Lister = MetricsLister<A::Lister>;
+    type BlockingLister = MetricsLister<A::BlockingLister>;
 
     fn inner(&self) -> &Self::Inner {
         &self.inner
@@ -551,6 +574,7 @@ impl<A: Accessor> LayeredAccessor for MetricsAccessor<A> {
         self.handle.requests_total_list.increment(1);
 
         let start = Instant::now();
+        let metakey = args.metakey();
 
         self.inner
             .list(path, args)
@@ -563,6 +587,7 @@ impl<A: Accessor> LayeredAccessor for MetricsAccessor<A> {
                 self.handle
                     .increment_errors_total(Operation::List, e.kind());
             })
+            .map_ok(|(rp, l)| (rp, MetricsLister::new(l, metakey, self.handle.clone())))
             .await
     }
 
@@ -713,6 +738,7 @@ impl<A: Accessor> LayeredAccessor for MetricsAccessor<A> {
         self.handle.requests_total_blocking_list.increment(1);
 
         let start = Instant::now();
+        let metakey = args.metakey();
         let result = self.inner.blocking_list(path, args);
         let dur = start.elapsed().as_secs_f64();
 
@@ -720,11 +746,13 @@ impl<A: Accessor> LayeredAccessor for MetricsAccessor<A> {
             .requests_duration_seconds_blocking_list
             .record(dur);
 
-        result.map_err(|e| {
-            self.handle
-                .increment_errors_total(Operation::BlockingList, e.kind());
-            e
-        })
+        result
+            .map(|(rp, l)| (rp, MetricsLister::new(l, metakey, self.handle.clone())))
+            .map_err(|e| {
+                self.handle
+                    .increment_errors_total(Operation::BlockingList, e.kind());
+                e
+            })
     }
 }
 
@@ -860,6 +888,10 @@ impl<R: oio::Write> oio::Write for MetricWrapper<R> {
             })
     }
 
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
     fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         self.inner.poll_abort(cx).map_err(|err| {
             self.handle.increment_errors_total(self.op, err.kind());
@@ -896,3 +928,66 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for MetricWrapper<R> {
         })
     }
 }
+
+/// MetricsLister counts entries returned by the underlying lister.
+///
+/// Entries that don't contain the required metakey are counted as
+/// `fallback_stat` since [`Lister`] will issue an extra `stat` for each of
+/// them. The stat calls themselves are recorded as `stat` requests.
+pub struct MetricsLister<P> {
+    inner: P,
+    required_metakey: FlagSet<Metakey>,
+    handle: Arc<MetricsHandler>,
+}
+
+impl<P> MetricsLister<P> {
+    fn new(inner: P, required_metakey: FlagSet<Metakey>, handle: Arc<MetricsHandler>) -> Self {
+        Self {
+            inner,
+            required_metakey,
+            handle,
+        }
+    }
+
+    fn record(&self, entry: &oio::Entry) {
+        if entry.metadata().contains_metakey(self.required_metakey) {
+            self.handle.list_entries_total_from_list.increment(1);
+        } else {
+            self.handle.list_entries_total_need_stat.increment(1);
+        }
+    }
+}
+
+impl<P: oio::List> oio::List for MetricsLister<P> {
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<oio::Entry>>> {
+        self.inner.poll_next(cx).map(|res| match res {
+            Ok(Some(entry)) => {
+                self.record(&entry);
+                Ok(Some(entry))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => {
+                self.handle
+                    .increment_errors_total(Operation::List, e.kind());
+                Err(e)
+            }
+        })
+    }
+}
+
+impl<P: oio::BlockingList> oio::BlockingList for MetricsLister<P> {
+    fn next(&mut self) -> Result<Option<oio::Entry>> {
+        match self.inner.next() {
+            Ok(Some(entry)) => {
+                self.record(&entry);
+                Ok(Some(entry))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => {
+                self.handle
+                    .increment_errors_total(Operation::BlockingList, e.kind());
+                Err(e)
+            }
+        }
+    }
+}
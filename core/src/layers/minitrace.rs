@@ -343,6 +343,10 @@ impl<R: oio::Write> oio::Write for MinitraceWrapper<R> {
         self.inner.poll_write(cx, bs)
     }
 
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
     fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         let _g = self.span.set_local_parent();
         let _span = LocalSpan::enter_with_local_parent(WriteOperation::Abort.into_static());
@@ -26,9 +26,15 @@ pub(crate) use error_context::ErrorContextLayer;
 mod complete;
 pub(crate) use complete::CompleteLayer;
 
+mod stats;
+pub(crate) use stats::StatsLayer;
+
 mod concurrent_limit;
 pub use concurrent_limit::ConcurrentLimitLayer;
 
+mod buffer_pool;
+pub use buffer_pool::BufferPoolLayer;
+
 mod immutable_index;
 pub use immutable_index::ImmutableIndexLayer;
 
@@ -41,6 +47,38 @@ pub use timeout::TimeoutLayer;
 mod blocking;
 pub use blocking::BlockingLayer;
 
+mod dry_run;
+pub use dry_run::DryRunLayer;
+pub use dry_run::DryRunRecord;
+
+mod presign_only;
+pub use presign_only::PresignOnlyLayer;
+
+mod emulate;
+pub use emulate::EmulateLayer;
+
+mod directory;
+pub use directory::DirectoryLayer;
+
+mod metadata_index;
+pub use metadata_index::MetadataIndex;
+pub use metadata_index::MetadataIndexLayer;
+
+mod policy;
+pub use policy::PolicyLayer;
+
+mod cost;
+pub use cost::CostLayer;
+pub use cost::CostModel;
+
+mod request_id;
+pub use request_id::RequestIdLayer;
+
+mod scan;
+pub use scan::ContentScanner;
+pub use scan::ScanLayer;
+pub use scan::ScanSession;
+
 #[cfg(feature = "layers-chaos")]
 mod chaos;
 #[cfg(feature = "layers-chaos")]
@@ -65,6 +103,9 @@ mod retry;
 pub use self::retry::RetryInterceptor;
 pub use self::retry::RetryLayer;
 
+mod spill;
+pub use self::spill::SpillLayer;
+
 #[cfg(feature = "layers-tracing")]
 mod tracing;
 #[cfg(feature = "layers-tracing")]
@@ -310,6 +310,10 @@ impl<R: oio::Write> oio::Write for OtelTraceWrapper<R> {
         self.inner.poll_write(cx, bs)
     }
 
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
     fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         self.inner.poll_abort(cx)
     }
@@ -0,0 +1,594 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io::SeekFrom;
+use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use crate::raw::*;
+use crate::*;
+
+/// Enforce size, content type and extension constraints on reads and writes.
+///
+/// This layer is designed for services that expose uploads and downloads
+/// to end users directly. Operations violating the policy will fail with
+/// [`ErrorKind::PermissionDenied`] and `policy` in context.
+///
+/// # Write constraints
+///
+/// - `max_write_size`: writes will fail once more bytes than given are
+///   written, the object will not be committed since `close` fails.
+/// - `allowed_content_types`: writes must set a content type that matches
+///   one of given types, `type/*` matches all subtypes.
+/// - `forbidden_extensions`: writes to paths with given extensions (case
+///   insensitive) will be rejected.
+///
+/// `copy` and `rename` are checked against the write constraints with the
+/// source object's size and content type. Presigned writes are rejected
+/// while `max_write_size` is set since the upload can't be metered.
+///
+/// # Read constraints
+///
+/// - `max_read_size`: reads will be rejected if the reader is known to be
+///   larger than given size, or fail once more bytes than given are read.
+///   Presigned reads are rejected if the object is larger than given size.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::PolicyLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(
+///         PolicyLayer::default()
+///             .with_max_write_size(10 * 1024 * 1024)
+///             .with_allowed_content_types(["image/*", "application/pdf"])
+///             .with_forbidden_extensions(["exe", "sh"]),
+///     )
+///     .finish();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PolicyLayer {
+    policy: Arc<Policy>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Policy {
+    max_write_size: Option<u64>,
+    max_read_size: Option<u64>,
+    allowed_content_types: Vec<String>,
+    forbidden_extensions: Vec<String>,
+}
+
+impl PolicyLayer {
+    /// Set the max size of objects that can be written.
+    pub fn with_max_write_size(mut self, size: u64) -> Self {
+        Arc::make_mut(&mut self.policy).max_write_size = Some(size);
+        self
+    }
+
+    /// Set the max size of objects that can be read.
+    pub fn with_max_read_size(mut self, size: u64) -> Self {
+        Arc::make_mut(&mut self.policy).max_read_size = Some(size);
+        self
+    }
+
+    /// Set the content types that writes are allowed to use.
+    ///
+    /// Types like `image/*` will match all subtypes, parameters like
+    /// `charset` will be ignored while matching.
+    pub fn with_allowed_content_types(
+        mut self,
+        types: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Arc::make_mut(&mut self.policy).allowed_content_types = types
+            .into_iter()
+            .map(|v| v.into().to_ascii_lowercase())
+            .collect();
+        self
+    }
+
+    /// Set the extensions that writes are not allowed to use, like `exe`.
+    pub fn with_forbidden_extensions(
+        mut self,
+        exts: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Arc::make_mut(&mut self.policy).forbidden_extensions = exts
+            .into_iter()
+            .map(|v| v.into().trim_start_matches('.').to_ascii_lowercase())
+            .collect();
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for PolicyLayer {
+    type LayeredAccessor = PolicyAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        PolicyAccessor {
+            inner,
+            policy: self.policy.clone(),
+        }
+    }
+}
+
+impl Policy {
+    fn check_write(&self, path: &str, args: &OpWrite) -> Result<()> {
+        self.check_extension(path)?;
+        self.check_content_type(path, args.content_type())
+    }
+
+    /// Check the destination of copy and rename against the write
+    /// constraints with the metadata of the source object.
+    fn check_copy(&self, to: &str, meta: Option<&Metadata>) -> Result<()> {
+        self.check_extension(to)?;
+        let Some(meta) = meta else {
+            return Ok(());
+        };
+        if let Some(max) = self.max_write_size {
+            let size = meta.content_length();
+            if size > max {
+                return Err(new_policy_error("write size exceeds limit", to)
+                    .with_context("size", size.to_string())
+                    .with_context("max_write_size", max.to_string()));
+            }
+        }
+        self.check_content_type(to, meta.content_type())
+    }
+
+    /// Copy and rename only need the source metadata if size or content
+    /// type are constrained.
+    fn need_source_meta(&self) -> bool {
+        self.max_write_size.is_some() || !self.allowed_content_types.is_empty()
+    }
+
+    fn check_extension(&self, path: &str) -> Result<()> {
+        if let Some((_, ext)) = get_basename(path).rsplit_once('.') {
+            let ext = ext.to_ascii_lowercase();
+            if self.forbidden_extensions.contains(&ext) {
+                return Err(
+                    new_policy_error("extension is forbidden", path).with_context("extension", ext)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn check_content_type(&self, path: &str, content_type: Option<&str>) -> Result<()> {
+        if !self.allowed_content_types.is_empty() {
+            let Some(content_type) = content_type else {
+                return Err(new_policy_error("content type is required", path));
+            };
+            let essence = content_type
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase();
+            let allowed = self
+                .allowed_content_types
+                .iter()
+                .any(|v| match v.strip_suffix("/*") {
+                    Some(prefix) => essence.split('/').next() == Some(prefix),
+                    None => v == &essence,
+                });
+            if !allowed {
+                return Err(new_policy_error("content type is not allowed", path)
+                    .with_context("content_type", content_type));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_read(&self, path: &str, rp: &RpRead) -> Result<()> {
+        self.check_read_size(path, rp.size())
+    }
+
+    fn check_read_size(&self, path: &str, size: Option<u64>) -> Result<()> {
+        match (self.max_read_size, size) {
+            (Some(max), Some(size)) if size > max => {
+                Err(new_policy_error("read size exceeds limit", path)
+                    .with_context("size", size.to_string())
+                    .with_context("max_read_size", max.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn new_policy_error(message: &str, path: &str) -> Error {
+    Error::new(ErrorKind::PermissionDenied, message)
+        .with_context("policy", "PolicyLayer")
+        .with_context("path", path)
+}
+
+#[derive(Debug)]
+pub struct PolicyAccessor<A: Accessor> {
+    inner: A,
+    policy: Arc<Policy>,
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for PolicyAccessor<A> {
+    type Inner = A;
+    type Reader = PolicyWrapper<A::Reader>;
+    type BlockingReader = PolicyWrapper<A::BlockingReader>;
+    type Writer = PolicyWrapper<A::Writer>;
+    type BlockingWriter = PolicyWrapper<A::BlockingWriter>;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let (rp, r) = self.inner.read(path, args).await?;
+        self.policy.check_read(path, &rp)?;
+
+        let r = PolicyWrapper::new(r, path, self.policy.max_read_size);
+        Ok((rp, r))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.policy.check_write(path, &args)?;
+
+        let (rp, w) = self.inner.write(path, args).await?;
+        Ok((rp, PolicyWrapper::new(w, path, self.policy.max_write_size)))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let meta = if self.policy.need_source_meta() {
+            Some(self.inner.stat(from, OpStat::new()).await?.into_metadata())
+        } else {
+            None
+        };
+        self.policy.check_copy(to, meta.as_ref())?;
+
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let meta = if self.policy.need_source_meta() {
+            Some(self.inner.stat(from, OpStat::new()).await?.into_metadata())
+        } else {
+            None
+        };
+        self.policy.check_copy(to, meta.as_ref())?;
+
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        match args.operation() {
+            PresignOperation::Stat(_) => {}
+            PresignOperation::Read(_) => {
+                if self.policy.max_read_size.is_some() {
+                    let meta = self.inner.stat(path, OpStat::new()).await?.into_metadata();
+                    self.policy
+                        .check_read_size(path, Some(meta.content_length()))?;
+                }
+            }
+            PresignOperation::Write(op) => {
+                self.policy.check_write(path, op)?;
+                if self.policy.max_write_size.is_some() {
+                    return Err(new_policy_error(
+                        "presigned write can't be limited in size",
+                        path,
+                    ));
+                }
+            }
+        }
+
+        self.inner.presign(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let (rp, r) = self.inner.blocking_read(path, args)?;
+        self.policy.check_read(path, &rp)?;
+
+        let r = PolicyWrapper::new(r, path, self.policy.max_read_size);
+        Ok((rp, r))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.policy.check_write(path, &args)?;
+
+        let (rp, w) = self.inner.blocking_write(path, args)?;
+        Ok((rp, PolicyWrapper::new(w, path, self.policy.max_write_size)))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let meta = if self.policy.need_source_meta() {
+            Some(
+                self.inner
+                    .blocking_stat(from, OpStat::new())?
+                    .into_metadata(),
+            )
+        } else {
+            None
+        };
+        self.policy.check_copy(to, meta.as_ref())?;
+
+        self.inner.blocking_copy(from, to, args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let meta = if self.policy.need_source_meta() {
+            Some(
+                self.inner
+                    .blocking_stat(from, OpStat::new())?
+                    .into_metadata(),
+            )
+        } else {
+            None
+        };
+        self.policy.check_copy(to, meta.as_ref())?;
+
+        self.inner.blocking_rename(from, to, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+/// PolicyWrapper counts bytes passing through and fails once they exceed
+/// the limit.
+///
+/// Writers that exceeded the limit will be aborted on close so that the
+/// object is never committed.
+pub struct PolicyWrapper<R> {
+    inner: R,
+    path: String,
+    limit: Option<u64>,
+    size: u64,
+    exceeded: bool,
+}
+
+impl<R> PolicyWrapper<R> {
+    fn new(inner: R, path: &str, limit: Option<u64>) -> Self {
+        Self {
+            inner,
+            path: path.to_string(),
+            limit,
+            size: 0,
+            exceeded: false,
+        }
+    }
+
+    /// Check if `n` more bytes are allowed.
+    fn check(&self, n: usize) -> Result<()> {
+        match self.limit {
+            Some(max) if self.size + n as u64 > max => {
+                Err(new_policy_error("size exceeds limit", &self.path)
+                    .with_context("limit", max.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<R: oio::Read> oio::Read for PolicyWrapper<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        let n = ready!(self.inner.poll_read(cx, buf))?;
+        self.check(n)?;
+        self.size += n as u64;
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        self.inner.poll_seek(cx, pos)
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        match ready!(self.inner.poll_next(cx)) {
+            Some(Ok(bs)) => {
+                self.check(bs.len())?;
+                self.size += bs.len() as u64;
+                Poll::Ready(Some(Ok(bs)))
+            }
+            v => Poll::Ready(v),
+        }
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for PolicyWrapper<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.check(n)?;
+        self.size += n as u64;
+        Ok(n)
+    }
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        match self.inner.next() {
+            Some(Ok(bs)) => {
+                if let Err(err) = self.check(bs.len()) {
+                    return Some(Err(err));
+                }
+                self.size += bs.len() as u64;
+                Some(Ok(bs))
+            }
+            v => v,
+        }
+    }
+}
+
+#[async_trait]
+impl<R: oio::Write> oio::Write for PolicyWrapper<R> {
+    fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        if let Err(err) = self.check(bs.remaining()) {
+            self.exceeded = true;
+            return Poll::Ready(Err(err));
+        }
+        let n = ready!(self.inner.poll_write(cx, bs))?;
+        self.size += n as u64;
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_abort(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        if self.exceeded {
+            ready!(self.inner.poll_abort(cx))?;
+            return Poll::Ready(Err(new_policy_error(
+                "size exceeds limit, writer has been aborted",
+                &self.path,
+            )));
+        }
+        self.inner.poll_close(cx)
+    }
+}
+
+impl<R: oio::BlockingWrite> oio::BlockingWrite for PolicyWrapper<R> {
+    fn write(&mut self, bs: &dyn oio::WriteBuf) -> Result<usize> {
+        if let Err(err) = self.check(bs.remaining()) {
+            self.exceeded = true;
+            return Err(err);
+        }
+        let n = self.inner.write(bs)?;
+        self.size += n as u64;
+        Ok(n)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        // Blocking writers can't be aborted, refuse to commit instead.
+        if self.exceeded {
+            return Err(new_policy_error("size exceeds limit", &self.path));
+        }
+        self.inner.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_policy_layer() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .layer(
+                PolicyLayer::default()
+                    .with_max_write_size(8)
+                    .with_max_read_size(4)
+                    .with_allowed_content_types(["text/*"])
+                    .with_forbidden_extensions([".exe"]),
+            )
+            .finish();
+
+        op.write_with("a.txt", "hello")
+            .content_type("text/plain; charset=utf-8")
+            .await?;
+
+        let cases = vec![
+            ("too large", "b.txt", "text/plain", "hello, world"),
+            ("content type", "b.txt", "image/png", "hello"),
+            ("extension", "b.EXE", "text/plain", "hello"),
+        ];
+        for (name, path, content_type, content) in cases {
+            let err = op
+                .write_with(path, content)
+                .content_type(content_type)
+                .await
+                .expect_err(name);
+            assert_eq!(err.kind(), ErrorKind::PermissionDenied, "{name}");
+        }
+        assert!(!op.is_exist("b.txt").await?);
+
+        let err = op.read("a.txt").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert_eq!(op.read_with("a.txt").range(0..4).await?, b"hell");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_policy_layer_writer_close_after_exceeded() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .layer(PolicyLayer::default().with_max_write_size(8))
+            .finish();
+
+        let mut w = op.writer("big.txt").await?;
+        w.write("hello").await?;
+        let err = w.write("world").await.expect_err("must exceed");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = w.close().await.expect_err("close must not commit");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert!(!op.is_exist("big.txt").await?);
+        Ok(())
+    }
+
+    #[cfg(feature = "services-fs")]
+    #[tokio::test]
+    async fn test_policy_layer_copy_and_rename() -> Result<()> {
+        let root = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let mut builder = crate::services::Fs::default();
+        builder.root(&root.to_string_lossy());
+        let inner = Operator::new(builder)?.finish();
+        inner.write("big.txt", "hello, world").await?;
+        inner.write("small.txt", "hello").await?;
+
+        let op = inner
+            .clone()
+            .layer(
+                PolicyLayer::default()
+                    .with_max_write_size(8)
+                    .with_forbidden_extensions(["exe"]),
+            )
+            .finish();
+
+        let cases = vec![
+            ("too large", "big.txt", "copied.txt"),
+            ("extension", "small.txt", "copied.exe"),
+        ];
+        for (name, from, to) in cases {
+            let err = op.copy(from, to).await.expect_err(name);
+            assert_eq!(err.kind(), ErrorKind::PermissionDenied, "{name}");
+            let err = op.rename(from, to).await.expect_err(name);
+            assert_eq!(err.kind(), ErrorKind::PermissionDenied, "{name}");
+            assert!(!inner.is_exist(to).await?, "{name}");
+        }
+
+        op.copy("small.txt", "copied.txt").await?;
+        assert_eq!(inner.read("copied.txt").await?, b"hello");
+        Ok(())
+    }
+}
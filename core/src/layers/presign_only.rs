@@ -0,0 +1,318 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::raw::*;
+use crate::*;
+
+/// Turn the operator into a presign-only operator.
+///
+/// Only `presign` will be passed to the service, all other operations will
+/// fail with [`ErrorKind::Unsupported`] without touching the service. The
+/// capability of the operator will only contain presign related ones.
+///
+/// This is useful for signers running in serverless environments with
+/// strict egress rules: they hold the credentials and hand out presigned
+/// requests, while the data never goes through them.
+///
+/// # Notes
+///
+/// Services may still load credentials from network (like EC2 metadata or
+/// STS) while signing. Please configure static credentials and pass
+/// [`HttpClient::offline`] to the service to make sure no request will be
+/// sent.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use anyhow::Result;
+/// use opendal::layers::PresignOnlyLayer;
+/// use opendal::raw::HttpClient;
+/// use opendal::services::S3;
+/// use opendal::Operator;
+///
+/// # async fn test() -> Result<()> {
+/// let mut builder = S3::default();
+/// builder
+///     .bucket("example")
+///     .region("us-east-1")
+///     .access_key_id("access_key_id")
+///     .secret_access_key("secret_access_key")
+///     .http_client(HttpClient::offline()?);
+///
+/// let op = Operator::new(builder)?.layer(PresignOnlyLayer).finish();
+/// let req = op.presign_read("test", Duration::from_secs(3600)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PresignOnlyLayer;
+
+impl<A: Accessor> Layer<A> for PresignOnlyLayer {
+    type LayeredAccessor = PresignOnlyAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        let mut info = inner.info();
+        let cap = info.full_capability();
+        *info.full_capability_mut() = Capability {
+            presign: cap.presign,
+            presign_stat: cap.presign_stat,
+            presign_read: cap.presign_read,
+            presign_write: cap.presign_write,
+            ..Default::default()
+        };
+
+        PresignOnlyAccessor {
+            inner,
+            info: Arc::new(info),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PresignOnlyAccessor<A: Accessor> {
+    inner: A,
+    info: Arc<AccessorInfo>,
+}
+
+impl<A: Accessor> PresignOnlyAccessor<A> {
+    fn new_unsupported_error(&self, op: Operation) -> Error {
+        Error::new(
+            ErrorKind::Unsupported,
+            &format!("operator is presign only, operation {op} is not allowed"),
+        )
+        .with_operation(op)
+        .with_context("service", self.info.scheme())
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for PresignOnlyAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn metadata(&self) -> AccessorInfo {
+        (*self.info).clone()
+    }
+
+    async fn create_dir(&self, _: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        Err(self.new_unsupported_error(Operation::CreateDir))
+    }
+
+    async fn read(&self, _: &str, _: OpRead) -> Result<(RpRead, Self::Reader)> {
+        Err(self.new_unsupported_error(Operation::Read))
+    }
+
+    async fn write(&self, _: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        Err(self.new_unsupported_error(Operation::Write))
+    }
+
+    async fn copy(&self, _: &str, _: &str, _: OpCopy) -> Result<RpCopy> {
+        Err(self.new_unsupported_error(Operation::Copy))
+    }
+
+    async fn rename(&self, _: &str, _: &str, _: OpRename) -> Result<RpRename> {
+        Err(self.new_unsupported_error(Operation::Rename))
+    }
+
+    async fn stat(&self, _: &str, _: OpStat) -> Result<RpStat> {
+        Err(self.new_unsupported_error(Operation::Stat))
+    }
+
+    async fn delete(&self, _: &str, _: OpDelete) -> Result<RpDelete> {
+        Err(self.new_unsupported_error(Operation::Delete))
+    }
+
+    async fn list(&self, _: &str, _: OpList) -> Result<(RpList, Self::Lister)> {
+        Err(self.new_unsupported_error(Operation::List))
+    }
+
+    async fn batch(&self, _: OpBatch) -> Result<RpBatch> {
+        Err(self.new_unsupported_error(Operation::Batch))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.inner.presign(path, args).await
+    }
+
+    async fn create_symlink(
+        &self,
+        _: &str,
+        _: &str,
+        _: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        Err(self.new_unsupported_error(Operation::CreateSymlink))
+    }
+
+    async fn set_permissions(&self, _: &str, _: OpSetPermissions) -> Result<RpSetPermissions> {
+        Err(self.new_unsupported_error(Operation::SetPermissions))
+    }
+
+    async fn create_snapshot(&self, _: &str, _: OpCreateSnapshot) -> Result<RpCreateSnapshot> {
+        Err(self.new_unsupported_error(Operation::CreateSnapshot))
+    }
+
+    async fn lease(&self, _: &str, _: OpLease) -> Result<RpLease> {
+        Err(self.new_unsupported_error(Operation::Lease))
+    }
+
+    async fn list_roots(&self, _: OpListRoots) -> Result<RpListRoots> {
+        Err(self.new_unsupported_error(Operation::ListRoots))
+    }
+
+    async fn concat(&self, _: &str, _: OpConcat) -> Result<RpConcat> {
+        Err(self.new_unsupported_error(Operation::Concat))
+    }
+
+    fn blocking_create_dir(&self, _: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        Err(self.new_unsupported_error(Operation::BlockingCreateDir))
+    }
+
+    fn blocking_read(&self, _: &str, _: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        Err(self.new_unsupported_error(Operation::BlockingRead))
+    }
+
+    fn blocking_write(&self, _: &str, _: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        Err(self.new_unsupported_error(Operation::BlockingWrite))
+    }
+
+    fn blocking_copy(&self, _: &str, _: &str, _: OpCopy) -> Result<RpCopy> {
+        Err(self.new_unsupported_error(Operation::BlockingCopy))
+    }
+
+    fn blocking_rename(&self, _: &str, _: &str, _: OpRename) -> Result<RpRename> {
+        Err(self.new_unsupported_error(Operation::BlockingRename))
+    }
+
+    fn blocking_stat(&self, _: &str, _: OpStat) -> Result<RpStat> {
+        Err(self.new_unsupported_error(Operation::BlockingStat))
+    }
+
+    fn blocking_delete(&self, _: &str, _: OpDelete) -> Result<RpDelete> {
+        Err(self.new_unsupported_error(Operation::BlockingDelete))
+    }
+
+    fn blocking_list(&self, _: &str, _: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        Err(self.new_unsupported_error(Operation::BlockingList))
+    }
+
+    fn blocking_create_symlink(
+        &self,
+        _: &str,
+        _: &str,
+        _: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        Err(self.new_unsupported_error(Operation::BlockingCreateSymlink))
+    }
+
+    fn blocking_set_permissions(&self, _: &str, _: OpSetPermissions) -> Result<RpSetPermissions> {
+        Err(self.new_unsupported_error(Operation::BlockingSetPermissions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_presign_only() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .layer(PresignOnlyLayer)
+            .finish();
+
+        let cap = op.info().full_capability();
+        assert!(!cap.read);
+        assert!(!cap.write);
+        assert!(!cap.presign);
+
+        let err = op
+            .write("test", "Hello, World!")
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = op.stat("test").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        Ok(())
+    }
+
+    #[cfg(feature = "services-s3")]
+    #[tokio::test]
+    async fn test_presign_only_with_offline_client() -> Result<()> {
+        use std::time::Duration;
+
+        use crate::services::S3;
+
+        let mut builder = S3::default();
+        builder
+            .endpoint("http://127.0.0.1:9000")
+            .bucket("bucket")
+            .region("us-east-1")
+            .access_key_id("access_key")
+            .secret_access_key("secret_key")
+            .disable_config_load()
+            .disable_ec2_metadata()
+            .http_client(HttpClient::offline()?);
+        let op = Operator::new(builder)?.layer(PresignOnlyLayer).finish();
+
+        let cap = op.info().full_capability();
+        assert!(cap.presign_read && cap.presign_write && cap.presign_stat);
+        assert!(!cap.read && !cap.write && !cap.list && !cap.delete);
+
+        let req = op.presign_read("file", Duration::from_secs(3600)).await?;
+        assert_eq!(req.method(), &http::Method::GET);
+        assert_eq!(req.uri().path(), "/bucket/file");
+        let query = req.uri().query().expect("query must exist");
+        assert!(query.contains("X-Amz-Signature="), "query: {query}");
+        let req = op.presign_write("file", Duration::from_secs(3600)).await?;
+        assert_eq!(req.method(), &http::Method::PUT);
+        let req = op.presign_stat("file", Duration::from_secs(3600)).await?;
+        assert_eq!(req.method(), &http::Method::HEAD);
+
+        let err = op.stat("file").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        // Nothing will be sent even without the layer.
+        let mut builder = S3::default();
+        builder
+            .endpoint("http://127.0.0.1:9000")
+            .bucket("bucket")
+            .region("us-east-1")
+            .allow_anonymous()
+            .disable_config_load()
+            .disable_ec2_metadata()
+            .http_client(HttpClient::offline()?);
+        let op = Operator::new(builder)?.finish();
+        let err = op.stat("file").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        Ok(())
+    }
+}
@@ -0,0 +1,332 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io::SeekFrom;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::header::HeaderName;
+
+use crate::raw::*;
+use crate::*;
+
+/// Attach a client request id to all HTTP requests sent by an operation.
+///
+/// A new id will be generated for every operation, and all requests sent
+/// by it (including requests sent by its reader, writer and lister) will
+/// carry the id in given header. The id will also be added into the
+/// context of errors as `client_request_id`, so errors in logs can be
+/// correlated with server side logs.
+///
+/// Place this layer after [`RetryLayer`][crate::layers::RetryLayer] so
+/// that all retries of an operation share the same id.
+///
+/// # Examples
+///
+/// ```
+/// use http::header::HeaderName;
+/// use opendal::layers::RequestIdLayer;
+/// use opendal::layers::RetryLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(RetryLayer::new())
+///     .layer(
+///         RequestIdLayer::default()
+///             .with_header(HeaderName::from_static("x-ms-client-request-id")),
+///     )
+///     .finish();
+/// ```
+///
+/// Ids can be derived from the current tracing span via
+/// `RequestIdLayer::with_tracing_span` (requires `layers-tracing` feature),
+/// or any other source via [`RequestIdLayer::with_generator`].
+///
+/// Services that sign headers (like azblob and s3) sign the id too, so
+/// `x-ms-*` and `x-amz-*` headers can be used safely.
+#[derive(Clone)]
+pub struct RequestIdLayer {
+    header: HeaderName,
+    generator: Arc<dyn Fn() -> String + Send + Sync>,
+}
+
+impl Default for RequestIdLayer {
+    fn default() -> Self {
+        Self {
+            header: HeaderName::from_static("x-client-request-id"),
+            generator: Arc::new(|| uuid::Uuid::new_v4().to_string()),
+        }
+    }
+}
+
+impl Debug for RequestIdLayer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestIdLayer")
+            .field("header", &self.header)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RequestIdLayer {
+    /// Set the header that request id will be sent in.
+    ///
+    /// Default to `x-client-request-id`. Use `x-ms-client-request-id` for
+    /// azure services so that the id shows up in storage analytics logs.
+    pub fn with_header(mut self, header: HeaderName) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Set the generator of request ids.
+    ///
+    /// Default to generate uuid v4.
+    pub fn with_generator(mut self, f: impl Fn() -> String + Send + Sync + 'static) -> Self {
+        self.generator = Arc::new(f);
+        self
+    }
+
+    /// Derive request ids from the current tracing span.
+    ///
+    /// The id of current span will be used in hex, so requests could be
+    /// correlated with the logs of span. Span ids are only unique within
+    /// the process and can be reused after the span closed. A uuid v4 will
+    /// be generated instead if there is no current span.
+    #[cfg(feature = "layers-tracing")]
+    pub fn with_tracing_span(self) -> Self {
+        self.with_generator(|| match tracing::Span::current().id() {
+            Some(id) => format!("{:016x}", id.into_u64()),
+            None => uuid::Uuid::new_v4().to_string(),
+        })
+    }
+}
+
+impl<A: Accessor> Layer<A> for RequestIdLayer {
+    type LayeredAccessor = RequestIdAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        RequestIdAccessor {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+pub struct RequestIdAccessor<A: Accessor> {
+    inner: A,
+    layer: RequestIdLayer,
+}
+
+impl<A: Accessor> Debug for RequestIdAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestIdAccessor")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Accessor> RequestIdAccessor<A> {
+    fn new_id(&self) -> ClientRequestId {
+        ClientRequestId::new(self.layer.header.clone(), (self.layer.generator)())
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for RequestIdAccessor<A> {
+    type Inner = A;
+    type Reader = RequestIdWrapper<A::Reader>;
+    type BlockingReader = RequestIdWrapper<A::BlockingReader>;
+    type Writer = RequestIdWrapper<A::Writer>;
+    type BlockingWriter = RequestIdWrapper<A::BlockingWriter>;
+    type Lister = RequestIdWrapper<A::Lister>;
+    type BlockingLister = RequestIdWrapper<A::BlockingLister>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.new_id().scope(self.inner.create_dir(path, args)).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let id = self.new_id();
+        let (rp, r) = id.clone().scope(self.inner.read(path, args)).await?;
+        Ok((rp, RequestIdWrapper::new(r, id)))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let id = self.new_id();
+        let (rp, w) = id.clone().scope(self.inner.write(path, args)).await?;
+        Ok((rp, RequestIdWrapper::new(w, id)))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.new_id().scope(self.inner.copy(from, to, args)).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.new_id().scope(self.inner.rename(from, to, args)).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.new_id().scope(self.inner.stat(path, args)).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.new_id().scope(self.inner.delete(path, args)).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let id = self.new_id();
+        let (rp, l) = id.clone().scope(self.inner.list(path, args)).await?;
+        Ok((rp, RequestIdWrapper::new(l, id)))
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.new_id().scope(self.inner.batch(args)).await
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.new_id().scope(self.inner.presign(path, args)).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let id = self.new_id();
+        let (rp, r) = id
+            .clone()
+            .sync_scope(|| self.inner.blocking_read(path, args))?;
+        Ok((rp, RequestIdWrapper::new(r, id)))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let id = self.new_id();
+        let (rp, w) = id
+            .clone()
+            .sync_scope(|| self.inner.blocking_write(path, args))?;
+        Ok((rp, RequestIdWrapper::new(w, id)))
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let id = self.new_id();
+        let (rp, l) = id
+            .clone()
+            .sync_scope(|| self.inner.blocking_list(path, args))?;
+        Ok((rp, RequestIdWrapper::new(l, id)))
+    }
+}
+
+/// RequestIdWrapper polls inner reader, writer and lister within the scope
+/// of the request id of its operation.
+pub struct RequestIdWrapper<R> {
+    inner: R,
+    id: ClientRequestId,
+}
+
+impl<R> RequestIdWrapper<R> {
+    fn new(inner: R, id: ClientRequestId) -> Self {
+        Self { inner, id }
+    }
+}
+
+impl<R: oio::Read> oio::Read for RequestIdWrapper<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        let inner = &mut self.inner;
+        self.id.clone().sync_scope(|| inner.poll_read(cx, buf))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        let inner = &mut self.inner;
+        self.id.clone().sync_scope(|| inner.poll_seek(cx, pos))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        let inner = &mut self.inner;
+        self.id.clone().sync_scope(|| inner.poll_next(cx))
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for RequestIdWrapper<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let inner = &mut self.inner;
+        self.id.clone().sync_scope(|| inner.read(buf))
+    }
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let inner = &mut self.inner;
+        self.id.clone().sync_scope(|| inner.seek(pos))
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        let inner = &mut self.inner;
+        self.id.clone().sync_scope(|| inner.next())
+    }
+}
+
+impl<R: oio::Write> oio::Write for RequestIdWrapper<R> {
+    fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        let inner = &mut self.inner;
+        self.id.clone().sync_scope(|| inner.poll_write(cx, bs))
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let inner = &mut self.inner;
+        self.id.clone().sync_scope(|| inner.poll_ready(cx))
+    }
+
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let inner = &mut self.inner;
+        self.id.clone().sync_scope(|| inner.poll_abort(cx))
+    }
+
+    fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let inner = &mut self.inner;
+        self.id.clone().sync_scope(|| inner.poll_close(cx))
+    }
+}
+
+impl<R: oio::BlockingWrite> oio::BlockingWrite for RequestIdWrapper<R> {
+    fn write(&mut self, bs: &dyn oio::WriteBuf) -> Result<usize> {
+        let inner = &mut self.inner;
+        self.id.clone().sync_scope(|| inner.write(bs))
+    }
+
+    fn close(&mut self) -> Result<()> {
+        let inner = &mut self.inner;
+        self.id.clone().sync_scope(|| inner.close())
+    }
+}
+
+impl<R: oio::List> oio::List for RequestIdWrapper<R> {
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<Entry>>> {
+        let inner = &mut self.inner;
+        self.id.clone().sync_scope(|| inner.poll_next(cx))
+    }
+}
+
+impl<R: oio::BlockingList> oio::BlockingList for RequestIdWrapper<R> {
+    fn next(&mut self) -> Result<Option<Entry>> {
+        let inner = &mut self.inner;
+        self.id.clone().sync_scope(|| inner.next())
+    }
+}
@@ -20,6 +20,7 @@ use std::fmt::Formatter;
 use std::io;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
@@ -49,6 +50,14 @@ use crate::*;
 /// returns true. If operation still failed, this layer will set error to
 /// `Persistent` which means error has been retried.
 ///
+/// [`ErrorKind::ConditionNotMatch`] will never be retried, since the
+/// condition is not going to match without callers re-reading the state.
+///
+/// Retried range reads will carry the etag returned by the first response
+/// as `If-Match` if the service supports it, so a retried read will fail
+/// with [`ErrorKind::ConditionNotMatch`] instead of returning bytes from
+/// an object that has been overwritten concurrently.
+///
 /// `write` and `blocking_write` don't support retry so far, visit [this issue](https://github.com/apache/incubator-opendal/issues/1223) for more details.
 ///
 /// # Examples
@@ -97,6 +106,8 @@ use crate::*;
 /// ```
 pub struct RetryLayer<I = DefaultRetryInterceptor> {
     builder: ExponentialBuilder,
+    /// Also used to cap the delay asked by services via retry after.
+    max_delay: Duration,
     notify: Arc<I>,
 }
 
@@ -104,15 +115,20 @@ impl<I> Clone for RetryLayer<I> {
     fn clone(&self) -> Self {
         Self {
             builder: self.builder.clone(),
+            max_delay: self.max_delay,
             notify: self.notify.clone(),
         }
     }
 }
 
+/// The default max delay of [`ExponentialBuilder`].
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(60);
+
 impl Default for RetryLayer {
     fn default() -> Self {
         Self {
             builder: ExponentialBuilder::default(),
+            max_delay: DEFAULT_MAX_DELAY,
             notify: Arc::new(DefaultRetryInterceptor),
         }
     }
@@ -166,6 +182,7 @@ impl RetryLayer {
     pub fn with_notify<I: RetryInterceptor>(self, notify: I) -> RetryLayer<I> {
         RetryLayer {
             builder: self.builder,
+            max_delay: self.max_delay,
             notify: Arc::new(notify),
         }
     }
@@ -198,8 +215,10 @@ impl RetryLayer {
     /// Set max_delay of current backoff.
     ///
     /// Delay will not increasing if current delay is larger than max_delay.
+    /// Retry after asked by services will also be capped by max_delay.
     pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
         self.builder = self.builder.with_max_delay(max_delay);
+        self.max_delay = max_delay;
         self
     }
 
@@ -219,6 +238,7 @@ impl<A: Accessor, I: RetryInterceptor> Layer<A> for RetryLayer<I> {
         RetryAccessor {
             inner,
             builder: self.builder.clone(),
+            max_delay: self.max_delay,
             notify: self.notify.clone(),
         }
     }
@@ -266,6 +286,7 @@ impl RetryInterceptor for DefaultRetryInterceptor {
 pub struct RetryAccessor<A: Accessor, I: RetryInterceptor> {
     inner: A,
     builder: ExponentialBuilder,
+    max_delay: Duration,
     notify: Arc<I>,
 }
 
@@ -292,9 +313,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.create_dir(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur: Duration| {
                 self.notify.intercept(
                     err,
@@ -310,9 +332,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.read(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -324,7 +347,13 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
                 v.map(|(rp, r)| {
                     (
                         rp,
-                        RetryWrapper::new(r, self.notify.clone(), path, self.builder.clone()),
+                        RetryWrapper::new(
+                            r,
+                            self.notify.clone(),
+                            path,
+                            self.builder.clone(),
+                            self.max_delay,
+                        ),
                     )
                 })
                 .map_err(|e| e.set_persistent())
@@ -336,9 +365,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     ///
     /// Allowing users to retry the write request from upper logic.
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.write(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -353,7 +383,13 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
                 v.map(|(rp, r)| {
                     (
                         rp,
-                        RetryWrapper::new(r, self.notify.clone(), path, self.builder.clone()),
+                        RetryWrapper::new(
+                            r,
+                            self.notify.clone(),
+                            path,
+                            self.builder.clone(),
+                            self.max_delay,
+                        ),
                     )
                 })
                 .map_err(|e| e.set_persistent())
@@ -362,9 +398,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.stat(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -377,9 +414,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.delete(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -395,9 +433,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.copy(from, to, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -414,9 +453,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.rename(from, to, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -433,9 +473,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.list(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -445,8 +486,13 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
             })
             .map(|v| {
                 v.map(|(l, p)| {
-                    let lister =
-                        RetryWrapper::new(p, self.notify.clone(), path, self.builder.clone());
+                    let lister = RetryWrapper::new(
+                        p,
+                        self.notify.clone(),
+                        path,
+                        self.builder.clone(),
+                        self.max_delay,
+                    );
                     (l, lister)
                 })
                 .map_err(|e| e.set_persistent())
@@ -455,6 +501,7 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         {
             || async {
                 let rp = self.inner.batch(args.clone()).await?;
@@ -466,8 +513,8 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
                 Ok(RpBatch::new(nrp))
             }
         }
-        .retry(&self.builder)
-        .when(|e: &Error| e.is_temporary())
+        .retry(&backoff)
+        .when(|e| backoff.when(e))
         .notify(|err, dur| {
             self.notify.intercept(
                 err,
@@ -483,9 +530,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.blocking_create_dir(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -501,9 +549,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.blocking_read(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -518,16 +567,23 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
             .map(|(rp, r)| {
                 (
                     rp,
-                    RetryWrapper::new(r, self.notify.clone(), path, self.builder.clone()),
+                    RetryWrapper::new(
+                        r,
+                        self.notify.clone(),
+                        path,
+                        self.builder.clone(),
+                        self.max_delay,
+                    ),
                 )
             })
             .map_err(|e| e.set_persistent())
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.blocking_write(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -542,16 +598,23 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
             .map(|(rp, r)| {
                 (
                     rp,
-                    RetryWrapper::new(r, self.notify.clone(), path, self.builder.clone()),
+                    RetryWrapper::new(
+                        r,
+                        self.notify.clone(),
+                        path,
+                        self.builder.clone(),
+                        self.max_delay,
+                    ),
                 )
             })
             .map_err(|e| e.set_persistent())
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.blocking_stat(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -567,9 +630,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.blocking_delete(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -585,9 +649,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.blocking_copy(from, to, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -604,9 +669,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.blocking_rename(from, to, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -623,9 +689,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.blocking_list(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -638,7 +705,13 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
             })
             .call()
             .map(|(rp, p)| {
-                let p = RetryWrapper::new(p, self.notify.clone(), path, self.builder.clone());
+                let p = RetryWrapper::new(
+                    p,
+                    self.notify.clone(),
+                    path,
+                    self.builder.clone(),
+                    self.max_delay,
+                );
                 (rp, p)
             })
             .map_err(|e| e.set_persistent())
@@ -651,18 +724,26 @@ pub struct RetryWrapper<R, I> {
 
     path: String,
     builder: ExponentialBuilder,
+    max_delay: Duration,
     current_backoff: Option<ExponentialBackoff>,
     sleep: Option<Pin<Box<tokio::time::Sleep>>>,
 }
 
 impl<R, I> RetryWrapper<R, I> {
-    fn new(inner: R, notify: Arc<I>, path: &str, backoff: ExponentialBuilder) -> Self {
+    fn new(
+        inner: R,
+        notify: Arc<I>,
+        path: &str,
+        backoff: ExponentialBuilder,
+        max_delay: Duration,
+    ) -> Self {
         Self {
             inner,
             notify,
 
             path: path.to_string(),
             builder: backoff,
+            max_delay,
             current_backoff: None,
             sleep: None,
         }
@@ -681,7 +762,7 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
                 self.current_backoff = None;
                 Poll::Ready(Ok(v))
             }
-            Err(err) if !err.is_temporary() => {
+            Err(err) if !is_retryable(&err) => {
                 self.current_backoff = None;
                 Poll::Ready(Err(err))
             }
@@ -700,6 +781,7 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
                         Poll::Ready(Err(err))
                     }
                     Some(dur) => {
+                        let dur = backoff_with_hint(&err, dur, self.max_delay);
                         self.notify.intercept(
                             &err,
                             dur,
@@ -727,7 +809,7 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
                 self.current_backoff = None;
                 Poll::Ready(Ok(v))
             }
-            Err(err) if !err.is_temporary() => {
+            Err(err) if !is_retryable(&err) => {
                 self.current_backoff = None;
                 Poll::Ready(Err(err))
             }
@@ -746,6 +828,7 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
                         Poll::Ready(Err(err))
                     }
                     Some(dur) => {
+                        let dur = backoff_with_hint(&err, dur, self.max_delay);
                         self.notify.intercept(
                             &err,
                             dur,
@@ -777,7 +860,7 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
                 self.current_backoff = None;
                 Poll::Ready(Some(Ok(v)))
             }
-            Some(Err(err)) if !err.is_temporary() => {
+            Some(Err(err)) if !is_retryable(&err) => {
                 self.current_backoff = None;
                 Poll::Ready(Some(Err(err)))
             }
@@ -796,6 +879,7 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
                         Poll::Ready(Some(Err(err)))
                     }
                     Some(dur) => {
+                        let dur = backoff_with_hint(&err, dur, self.max_delay);
                         self.notify.intercept(
                             &err,
                             dur,
@@ -815,9 +899,10 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
 
 impl<R: oio::BlockingRead, I: RetryInterceptor> oio::BlockingRead for RetryWrapper<R, I> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.read(buf) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -833,9 +918,10 @@ impl<R: oio::BlockingRead, I: RetryInterceptor> oio::BlockingRead for RetryWrapp
     }
 
     fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.seek(pos) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -851,9 +937,10 @@ impl<R: oio::BlockingRead, I: RetryInterceptor> oio::BlockingRead for RetryWrapp
     }
 
     fn next(&mut self) -> Option<Result<Bytes>> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.next().transpose() }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -883,7 +970,7 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
                 self.current_backoff = None;
                 Poll::Ready(Ok(v))
             }
-            Err(err) if !err.is_temporary() => {
+            Err(err) if !is_retryable(&err) => {
                 self.current_backoff = None;
                 Poll::Ready(Err(err))
             }
@@ -902,6 +989,7 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
                         Poll::Ready(Err(err))
                     }
                     Some(dur) => {
+                        let dur = backoff_with_hint(&err, dur, self.max_delay);
                         self.notify.intercept(
                             &err,
                             dur,
@@ -918,6 +1006,53 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
         }
     }
 
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        if let Some(sleep) = self.sleep.as_mut() {
+            ready!(sleep.poll_unpin(cx));
+            self.sleep = None;
+        }
+
+        match ready!(self.inner.poll_ready(cx)) {
+            Ok(v) => {
+                self.current_backoff = None;
+                Poll::Ready(Ok(v))
+            }
+            Err(err) if !is_retryable(&err) => {
+                self.current_backoff = None;
+                Poll::Ready(Err(err))
+            }
+            Err(err) => {
+                let backoff = match self.current_backoff.as_mut() {
+                    Some(backoff) => backoff,
+                    None => {
+                        self.current_backoff = Some(self.builder.build());
+                        self.current_backoff.as_mut().unwrap()
+                    }
+                };
+
+                match backoff.next() {
+                    None => {
+                        self.current_backoff = None;
+                        Poll::Ready(Err(err))
+                    }
+                    Some(dur) => {
+                        let dur = backoff_with_hint(&err, dur, self.max_delay);
+                        self.notify.intercept(
+                            &err,
+                            dur,
+                            &[
+                                ("operation", WriteOperation::Ready.into_static()),
+                                ("path", &self.path),
+                            ],
+                        );
+                        self.sleep = Some(Box::pin(tokio::time::sleep(dur)));
+                        self.poll_ready(cx)
+                    }
+                }
+            }
+        }
+    }
+
     fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         if let Some(sleep) = self.sleep.as_mut() {
             ready!(sleep.poll_unpin(cx));
@@ -929,7 +1064,7 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
                 self.current_backoff = None;
                 Poll::Ready(Ok(v))
             }
-            Err(err) if !err.is_temporary() => {
+            Err(err) if !is_retryable(&err) => {
                 self.current_backoff = None;
                 Poll::Ready(Err(err))
             }
@@ -948,6 +1083,7 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
                         Poll::Ready(Err(err))
                     }
                     Some(dur) => {
+                        let dur = backoff_with_hint(&err, dur, self.max_delay);
                         self.notify.intercept(
                             &err,
                             dur,
@@ -975,7 +1111,7 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
                 self.current_backoff = None;
                 Poll::Ready(Ok(v))
             }
-            Err(err) if !err.is_temporary() => {
+            Err(err) if !is_retryable(&err) => {
                 self.current_backoff = None;
                 Poll::Ready(Err(err))
             }
@@ -994,6 +1130,7 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
                         Poll::Ready(Err(err))
                     }
                     Some(dur) => {
+                        let dur = backoff_with_hint(&err, dur, self.max_delay);
                         self.notify.intercept(
                             &err,
                             dur,
@@ -1013,9 +1150,10 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
 
 impl<R: oio::BlockingWrite, I: RetryInterceptor> oio::BlockingWrite for RetryWrapper<R, I> {
     fn write(&mut self, bs: &dyn oio::WriteBuf) -> Result<usize> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.write(bs) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -1031,9 +1169,10 @@ impl<R: oio::BlockingWrite, I: RetryInterceptor> oio::BlockingWrite for RetryWra
     }
 
     fn close(&mut self) -> Result<()> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.close() }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -1062,7 +1201,7 @@ impl<P: oio::List, I: RetryInterceptor> oio::List for RetryWrapper<P, I> {
                 self.current_backoff = None;
                 Poll::Ready(Ok(v))
             }
-            Err(err) if !err.is_temporary() => {
+            Err(err) if !is_retryable(&err) => {
                 self.current_backoff = None;
                 Poll::Ready(Err(err))
             }
@@ -1081,6 +1220,7 @@ impl<P: oio::List, I: RetryInterceptor> oio::List for RetryWrapper<P, I> {
                         Poll::Ready(Err(err))
                     }
                     Some(dur) => {
+                        let dur = backoff_with_hint(&err, dur, self.max_delay);
                         self.notify.intercept(
                             &err,
                             dur,
@@ -1100,9 +1240,10 @@ impl<P: oio::List, I: RetryInterceptor> oio::List for RetryWrapper<P, I> {
 
 impl<P: oio::BlockingList, I: RetryInterceptor> oio::BlockingList for RetryWrapper<P, I> {
     fn next(&mut self) -> Result<Option<oio::Entry>> {
+        let backoff = RetryAfterBuilder::new(&self.builder, self.max_delay);
         { || self.inner.next() }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.when(e))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -1118,6 +1259,72 @@ impl<P: oio::BlockingList, I: RetryInterceptor> oio::BlockingList for RetryWrapp
     }
 }
 
+/// Wait at least the time that service asks before retrying.
+fn backoff_with_hint(err: &Error, dur: Duration, max_delay: Duration) -> Duration {
+    err.retry_after().map_or(dur, |v| v.min(max_delay).max(dur))
+}
+
+/// RetryAfterBuilder builds backoffs that honor [`Error::retry_after`].
+///
+/// The hint of the last error is recorded by [`RetryAfterBuilder::when`]
+/// which will be called before asking backoff for the next delay.
+#[derive(Debug, Clone)]
+struct RetryAfterBuilder {
+    builder: ExponentialBuilder,
+    max_delay: Duration,
+    hint: Arc<Mutex<Option<Duration>>>,
+}
+
+impl RetryAfterBuilder {
+    fn new(builder: &ExponentialBuilder, max_delay: Duration) -> Self {
+        Self {
+            builder: builder.clone(),
+            max_delay,
+            hint: Arc::default(),
+        }
+    }
+
+    /// Check if the error should be retried and record its hint.
+    fn when(&self, err: &Error) -> bool {
+        *self.hint.lock().expect("lock must be valid") = err.retry_after();
+        is_retryable(err)
+    }
+}
+
+impl BackoffBuilder for RetryAfterBuilder {
+    type Backoff = RetryAfterBackoff;
+
+    fn build(&self) -> Self::Backoff {
+        RetryAfterBackoff {
+            backoff: self.builder.build(),
+            max_delay: self.max_delay,
+            hint: self.hint.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RetryAfterBackoff {
+    backoff: ExponentialBackoff,
+    max_delay: Duration,
+    hint: Arc<Mutex<Option<Duration>>>,
+}
+
+impl Iterator for RetryAfterBackoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        let dur = self.backoff.next()?;
+        let hint = self.hint.lock().expect("lock must be valid").take();
+        Some(hint.map_or(dur, |v| v.min(self.max_delay).max(dur)))
+    }
+}
+
+/// Check if the error should be retried.
+fn is_retryable(err: &Error) -> bool {
+    err.is_temporary() && err.kind() != ErrorKind::ConditionNotMatch
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -1163,7 +1370,7 @@ mod tests {
     impl Accessor for MockService {
         type Reader = MockReader;
         type BlockingReader = ();
-        type Writer = ();
+        type Writer = MockWriter;
         type BlockingWriter = ();
         type Lister = MockLister;
         type BlockingLister = ();
@@ -1172,6 +1379,7 @@ mod tests {
             let mut am = AccessorInfo::default();
             am.set_native_capability(Capability {
                 read: true,
+                write: true,
                 list: true,
                 list_without_recursive: true,
                 list_with_recursive: true,
@@ -1192,6 +1400,15 @@ mod tests {
             ))
         }
 
+        async fn write(&self, _: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            Ok((
+                RpWrite::new(),
+                MockWriter {
+                    attempt: self.attempt.clone(),
+                },
+            ))
+        }
+
         async fn list(&self, _: &str, _: OpList) -> Result<(RpList, Self::Lister)> {
             let lister = MockLister::default();
             Ok((RpList::default(), lister))
@@ -1310,6 +1527,43 @@ mod tests {
         }
     }
 
+    #[derive(Debug, Clone, Default)]
+    struct MockWriter {
+        attempt: Arc<Mutex<usize>>,
+    }
+
+    impl oio::Write for MockWriter {
+        fn poll_write(
+            &mut self,
+            _: &mut Context<'_>,
+            bs: &dyn oio::WriteBuf,
+        ) -> Poll<Result<usize>> {
+            Poll::Ready(Ok(bs.remaining()))
+        }
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+            let mut attempt = self.attempt.lock().unwrap();
+            *attempt += 1;
+
+            Poll::Ready(match *attempt {
+                1 | 2 => Err(
+                    Error::new(ErrorKind::Unexpected, "retryable_error from writer")
+                        .set_temporary(),
+                ),
+                3 => Ok(()),
+                _ => unreachable!(),
+            })
+        }
+
+        fn poll_close(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_abort(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
     #[derive(Debug, Clone, Default)]
     struct MockLister {
         attempt: usize,
@@ -1377,6 +1631,37 @@ mod tests {
         assert_eq!(*builder.attempt.lock().unwrap(), 5);
     }
 
+    #[tokio::test]
+    async fn test_retry_ready() {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(RetryLayer::new().with_min_delay(Duration::from_millis(10)))
+            .finish();
+
+        let mut w = op.writer("retryable_error").await.unwrap();
+        w.ready().await.expect("ready must succeed");
+        // Two retryable errors followed by a ready writer.
+        assert_eq!(*builder.attempt.lock().unwrap(), 3);
+
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(
+                RetryLayer::new()
+                    .with_min_delay(Duration::from_millis(10))
+                    .with_max_times(1),
+            )
+            .finish();
+
+        let mut w = op.writer("retryable_error").await.unwrap();
+        let err = w.ready().await.expect_err("ready must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert_eq!(*builder.attempt.lock().unwrap(), 2);
+    }
+
     #[tokio::test]
     async fn test_retry_list() {
         let _ = tracing_subscriber::fmt().with_test_writer().try_init();
@@ -1425,4 +1710,35 @@ mod tests {
         op.remove(paths).await.expect("batch must succeed");
         assert_eq!(*builder.attempt.lock().unwrap(), 5);
     }
+
+    #[test]
+    fn test_condition_not_match_not_retryable() {
+        let err = Error::new(ErrorKind::ConditionNotMatch, "conflict").set_temporary();
+        assert!(!is_retryable(&err));
+        let err = Error::new(ErrorKind::Unexpected, "timeout").set_temporary();
+        assert!(is_retryable(&err));
+    }
+
+    #[test]
+    fn test_retry_after_backoff() {
+        let builder = RetryAfterBuilder::new(&ExponentialBuilder::default(), DEFAULT_MAX_DELAY);
+        let mut backoff = builder.build();
+
+        let err = Error::new(ErrorKind::RateLimited, "slow down")
+            .set_temporary()
+            .with_retry_after(Duration::from_secs(30));
+        assert!(builder.when(&err));
+        assert_eq!(backoff.next(), Some(Duration::from_secs(30)));
+
+        // Retry after from services is capped by max delay.
+        let err = Error::new(ErrorKind::RateLimited, "slow down")
+            .set_temporary()
+            .with_retry_after(Duration::from_secs(3600));
+        assert!(builder.when(&err));
+        assert_eq!(backoff.next(), Some(DEFAULT_MAX_DELAY));
+        assert_eq!(
+            backoff_with_hint(&err, Duration::from_secs(1), Duration::from_secs(10)),
+            Duration::from_secs(10)
+        );
+    }
 }
@@ -0,0 +1,433 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+
+use crate::raw::*;
+use crate::*;
+
+/// Scan content of writes before they are committed, like virus or malware
+/// scanning.
+///
+/// Content will be teed into the [`ScanSession`] created by given
+/// [`ContentScanner`] while it's being written, so uploads can be scanned
+/// without reading them back from the service. The write will only be
+/// committed after [`ScanSession::finish`] returns `Ok`, otherwise it will
+/// be aborted and the error will be returned.
+///
+/// # Notes
+///
+/// - Only the content written by this writer will be scanned, data that
+///   already exists before an append write is not scanned.
+/// - Blocking writes will return [`ErrorKind::Unsupported`] since scanners
+///   are async.
+///
+/// # Examples
+///
+/// ```
+/// use async_trait::async_trait;
+/// use bytes::Bytes;
+/// use opendal::layers::ContentScanner;
+/// use opendal::layers::ScanLayer;
+/// use opendal::layers::ScanSession;
+/// use opendal::services;
+/// use opendal::Error;
+/// use opendal::ErrorKind;
+/// use opendal::Operator;
+/// use opendal::Result;
+///
+/// #[derive(Debug)]
+/// struct EicarScanner;
+///
+/// #[async_trait]
+/// impl ContentScanner for EicarScanner {
+///     async fn start(&self, _: &str) -> Result<Box<dyn ScanSession>> {
+///         Ok(Box::new(EicarSession(Vec::new())))
+///     }
+/// }
+///
+/// struct EicarSession(Vec<u8>);
+///
+/// #[async_trait]
+/// impl ScanSession for EicarSession {
+///     async fn update(&mut self, bs: Bytes) -> Result<()> {
+///         self.0.extend_from_slice(&bs);
+///         Ok(())
+///     }
+///
+///     async fn finish(&mut self) -> Result<()> {
+///         if self.0.starts_with(b"X5O!P%@AP") {
+///             return Err(Error::new(ErrorKind::PermissionDenied, "virus found"));
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(ScanLayer::new(EicarScanner))
+///     .finish();
+/// ```
+#[derive(Clone)]
+pub struct ScanLayer {
+    scanner: Arc<dyn ContentScanner>,
+}
+
+impl ScanLayer {
+    /// Create a new `ScanLayer` with given scanner.
+    pub fn new(scanner: impl ContentScanner) -> Self {
+        Self {
+            scanner: Arc::new(scanner),
+        }
+    }
+}
+
+/// ContentScanner creates a [`ScanSession`] for every write.
+#[async_trait]
+pub trait ContentScanner: Debug + Send + Sync + 'static {
+    /// Start a scan session for the write to given path.
+    ///
+    /// Returning an error will reject the write before any content is sent.
+    async fn start(&self, path: &str) -> Result<Box<dyn ScanSession>>;
+}
+
+/// ScanSession receives content of a write in order and decides whether
+/// the write could be committed.
+#[async_trait]
+pub trait ScanSession: Send + Sync {
+    /// Feed the next chunk of content.
+    ///
+    /// Returning an error will fail the write immediately.
+    async fn update(&mut self, bs: Bytes) -> Result<()>;
+
+    /// All content has been fed, returns an error to veto the write.
+    async fn finish(&mut self) -> Result<()>;
+}
+
+impl<A: Accessor> Layer<A> for ScanLayer {
+    type LayeredAccessor = ScanAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        ScanAccessor {
+            inner,
+            scanner: self.scanner.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ScanAccessor<A: Accessor> {
+    inner: A,
+    scanner: Arc<dyn ContentScanner>,
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for ScanAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = ScanWriter<A::Writer>;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let session = self.scanner.start(path).await?;
+        let (rp, w) = self.inner.write(path, args).await?;
+        Ok((rp, ScanWriter::new(w, session)))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "content scanning is not supported in blocking write",
+        )
+        .with_operation(Operation::BlockingWrite)
+        .with_context("path", path))
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+type ScanFuture = BoxFuture<'static, (Box<dyn ScanSession>, Result<()>)>;
+
+enum State {
+    Idle(Option<Box<dyn ScanSession>>),
+    /// Feeding the chunk of given size that has been written.
+    Update(usize, ScanFuture),
+    Finish(ScanFuture),
+    /// The scanner accepted the write, closing inner writer.
+    Close,
+    /// The scanner vetoed the write, aborting inner writer.
+    Abort(Option<Error>),
+}
+
+/// # Safety
+///
+/// We will only take `&mut Self` reference for State.
+unsafe impl Sync for State {}
+
+pub struct ScanWriter<W> {
+    inner: W,
+    state: State,
+}
+
+impl<W> ScanWriter<W> {
+    fn new(inner: W, session: Box<dyn ScanSession>) -> Self {
+        Self {
+            inner,
+            state: State::Idle(Some(session)),
+        }
+    }
+}
+
+#[async_trait]
+impl<W: oio::Write> oio::Write for ScanWriter<W> {
+    fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        loop {
+            match &mut self.state {
+                State::Idle(session) => {
+                    // Take session only after inner write is ready, so that
+                    // it's still there if we are polled again.
+                    let n = ready!(self.inner.poll_write(cx, bs))?;
+                    let mut session = session.take().expect("session must be valid");
+                    let chunk = bs.bytes(n);
+                    let fut = async move {
+                        let res = session.update(chunk).await;
+                        (session, res)
+                    };
+                    self.state = State::Update(n, fut.boxed());
+                }
+                State::Update(n, fut) => {
+                    let n = *n;
+                    let (session, res) = ready!(fut.poll_unpin(cx));
+                    return match res {
+                        Ok(()) => {
+                            self.state = State::Idle(Some(session));
+                            Poll::Ready(Ok(n))
+                        }
+                        Err(err) => {
+                            // Latch the veto so that the content written
+                            // will never be committed by close.
+                            self.state = State::Abort(Some(
+                                Error::new(err.kind(), "scanner vetoed a previous write")
+                                    .with_context("reason", err.to_string()),
+                            ));
+                            Poll::Ready(Err(err))
+                        }
+                    };
+                }
+                State::Abort(_) => {
+                    return Poll::Ready(Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "writer has been vetoed by scanner, write is not allowed",
+                    )));
+                }
+                State::Finish(_) | State::Close => {
+                    return Poll::Ready(Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "writer is closing, write is not allowed",
+                    )));
+                }
+            }
+        }
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_abort(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        loop {
+            match &mut self.state {
+                State::Idle(session) => {
+                    let mut session = session.take().expect("session must be valid");
+                    let fut = async move {
+                        let res = session.finish().await;
+                        (session, res)
+                    };
+                    self.state = State::Finish(fut.boxed());
+                }
+                State::Finish(fut) => {
+                    let (_, res) = ready!(fut.poll_unpin(cx));
+                    self.state = match res {
+                        Ok(()) => State::Close,
+                        Err(err) => State::Abort(Some(err)),
+                    };
+                }
+                State::Close => return self.inner.poll_close(cx),
+                State::Abort(err) => {
+                    // Abort is best effort, the veto is what users care about.
+                    let _ = ready!(self.inner.poll_abort(cx));
+                    let err = err.take().expect("error must be valid");
+                    return Poll::Ready(Err(err));
+                }
+                State::Update(..) => {
+                    return Poll::Ready(Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "writer is writing, close is not allowed",
+                    )));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[derive(Debug)]
+    struct MockScanner;
+
+    #[async_trait]
+    impl ContentScanner for MockScanner {
+        async fn start(&self, _: &str) -> Result<Box<dyn ScanSession>> {
+            Ok(Box::<MockSession>::default())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockSession(Vec<u8>);
+
+    #[async_trait]
+    impl ScanSession for MockSession {
+        async fn update(&mut self, bs: Bytes) -> Result<()> {
+            if bs.as_ref() == b"blocked" {
+                return Err(Error::new(ErrorKind::PermissionDenied, "content blocked"));
+            }
+            self.0.extend_from_slice(&bs);
+            Ok(())
+        }
+
+        async fn finish(&mut self) -> Result<()> {
+            if self.0.windows(5).any(|v| v == b"virus") {
+                return Err(Error::new(ErrorKind::PermissionDenied, "virus found"));
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_layer() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .layer(ScanLayer::new(MockScanner))
+            .finish();
+
+        op.write("clean", "hello").await?;
+        assert_eq!(op.read("clean").await?, b"hello");
+
+        let mut w = op.writer("infected").await?;
+        w.write("hello, vi").await?;
+        w.write("rus").await?;
+        let err = w.close().await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert!(!op.is_exist("infected").await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_scan_layer_update_veto() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .layer(ScanLayer::new(MockScanner))
+            .finish();
+
+        let mut w = op.writer("blocked").await?;
+        w.write("hello").await?;
+        let err = w.write("blocked").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = w.close().await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert!(!op.is_exist("blocked").await?);
+        Ok(())
+    }
+
+    /// PendingWriter returns `Pending` for every first poll of write.
+    #[derive(Default)]
+    struct PendingWriter {
+        pending: bool,
+        buf: Vec<u8>,
+    }
+
+    impl oio::Write for PendingWriter {
+        fn poll_write(
+            &mut self,
+            cx: &mut Context<'_>,
+            bs: &dyn oio::WriteBuf,
+        ) -> Poll<Result<usize>> {
+            self.pending = !self.pending;
+            if self.pending {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            self.buf.extend_from_slice(bs.chunk());
+            Poll::Ready(Ok(bs.chunk().len()))
+        }
+
+        fn poll_abort(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_writer_pending_write() -> Result<()> {
+        use oio::WriteExt;
+
+        let mut w = ScanWriter::new(PendingWriter::default(), Box::<MockSession>::default());
+        assert_eq!(w.write(&Bytes::from("hello")).await?, 5);
+        assert_eq!(w.write(&Bytes::from("world")).await?, 5);
+        w.close().await?;
+        assert_eq!(w.inner.buf, b"helloworld");
+        Ok(())
+    }
+}
@@ -0,0 +1,497 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Buf;
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+
+use crate::raw::oio::WriteExt;
+use crate::raw::*;
+use crate::*;
+
+/// Record writes into a spill buffer so that the upload can be replayed.
+///
+/// # Notes
+///
+/// Writes are passed to the service as is, so native multipart uploads
+/// and errors are not delayed. Meanwhile data is kept in memory until
+/// `threshold` is reached, the rest will be spilled into a file under the
+/// temp dir, or into the operator set by [`SpillLayer::with_operator`].
+/// Spilled data will be removed once the writer is dropped.
+///
+/// Once `write` or `close` failed, the failed writer will be aborted and
+/// the next call will replay the recorded data into a new writer from the
+/// beginning, so a `RetryLayer` added after this layer can retry the whole
+/// upload without holding it in memory.
+///
+/// Writes with `append` are passed to the service directly.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::RetryLayer;
+/// use opendal::layers::SpillLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(SpillLayer::new(64 * 1024 * 1024))
+///     .layer(RetryLayer::new())
+///     .finish();
+/// ```
+#[derive(Clone)]
+pub struct SpillLayer {
+    threshold: usize,
+    op: Option<BlockingOperator>,
+}
+
+impl SpillLayer {
+    /// Create a new spill layer that keeps at most `threshold` bytes in
+    /// memory for every writer.
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            op: None,
+        }
+    }
+
+    /// Spill data into given operator instead of local temp dir.
+    ///
+    /// The operator must support append and blocking, like `fs`.
+    pub fn with_operator(mut self, op: BlockingOperator) -> Self {
+        self.op = Some(op);
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for SpillLayer {
+    type LayeredAccessor = SpillAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        SpillAccessor {
+            inner: Arc::new(inner),
+            threshold: self.threshold,
+            op: self.op.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SpillAccessor<A: Accessor> {
+    inner: Arc<A>,
+    threshold: usize,
+    op: Option<BlockingOperator>,
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for SpillAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = oio::TwoWaysWriter<SpillWriter<A>, A::Writer>;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        if args.append() {
+            return self
+                .inner
+                .write(path, args)
+                .await
+                .map(|(rp, w)| (rp, oio::TwoWaysWriter::Two(w)));
+        }
+
+        let (rp, w) = self.inner.write(path, args.clone()).await?;
+
+        let mut buf = oio::SpillBuf::new(self.threshold);
+        if let Some(op) = &self.op {
+            buf.set_operator(op.clone());
+        }
+        let w = SpillWriter {
+            inner: self.inner.clone(),
+            path: path.to_string(),
+            args,
+            buf: Arc::new(Mutex::new(buf)),
+            w: Some(w),
+            failed: None,
+            state: State::Idle,
+        };
+        Ok((rp, oio::TwoWaysWriter::One(w)))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+pub struct SpillWriter<A: Accessor> {
+    inner: Arc<A>,
+    path: String,
+    args: OpWrite,
+
+    buf: Arc<Mutex<oio::SpillBuf>>,
+    /// The writer of service, `None` means it has failed and must be
+    /// replayed.
+    w: Option<A::Writer>,
+    /// The failed writer that must be aborted before replaying, otherwise
+    /// uploads like multipart will be left unfinished.
+    failed: Option<A::Writer>,
+    state: State<A::Writer>,
+}
+
+enum State<W> {
+    Idle,
+    /// Replaying recorded data into a new writer.
+    Replay(BoxFuture<'static, Result<W>>),
+    /// Recording data that has been written into spilled buffer.
+    Record(BoxFuture<'static, Result<()>>, usize),
+}
+
+/// # Safety
+///
+/// We will only take `&mut Self` reference for SpillWriter.
+unsafe impl<A: Accessor> Sync for SpillWriter<A> {}
+
+impl<A: Accessor> SpillWriter<A> {
+    fn replay(&self) -> BoxFuture<'static, Result<A::Writer>> {
+        let (inner, path, args, buf) = (
+            self.inner.clone(),
+            self.path.clone(),
+            self.args.clone(),
+            self.buf.clone(),
+        );
+
+        async move {
+            let (_, mut w) = inner.write(&path, args).await?;
+
+            let mut pos = 0;
+            loop {
+                let mut bs = read_at(buf.clone(), pos).await?;
+                if bs.is_empty() {
+                    return Ok(w);
+                }
+                pos += bs.len() as u64;
+                while bs.has_remaining() {
+                    let n = w.write(&bs).await?;
+                    bs.advance(n);
+                }
+            }
+        }
+        .boxed()
+    }
+
+    /// Record data that has been written, returns `None` if it has been
+    /// recorded in memory.
+    fn record(&self, bs: Bytes) -> Result<Option<BoxFuture<'static, Result<()>>>> {
+        let mut buf = self.buf.lock().expect("lock must be valid");
+        if !buf.will_spill(bs.len()) {
+            buf.push(bs)?;
+            return Ok(None);
+        }
+
+        let buf = self.buf.clone();
+        Ok(Some(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    buf.lock().expect("lock must be valid").push(bs)
+                })
+                .await
+                .map_err(new_task_join_error)?
+            }
+            .boxed(),
+        ))
+    }
+
+    /// Mark current writer as failed so that it will be aborted.
+    fn fail(&mut self) {
+        if let Some(w) = self.w.take() {
+            self.failed = Some(w);
+        }
+    }
+
+    /// Abort the failed writer, errors are ignored since the upload will be
+    /// replayed into a new writer anyway.
+    fn poll_abort_failed(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if let Some(w) = self.failed.as_mut() {
+            if let Err(err) = ready!(w.poll_abort(cx)) {
+                log::warn!("spill writer abort failed upload of {}: {err}", self.path);
+            }
+            self.failed = None;
+        }
+        Poll::Ready(())
+    }
+
+    /// Drive the replay or record in progress.
+    fn poll_state(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<usize>>> {
+        ready!(self.poll_abort_failed(cx));
+
+        match &mut self.state {
+            State::Idle => Poll::Ready(Ok(None)),
+            State::Replay(fut) => {
+                let res = ready!(fut.poll_unpin(cx));
+                self.state = State::Idle;
+                self.w = Some(res?);
+                Poll::Ready(Ok(None))
+            }
+            State::Record(fut, n) => {
+                let n = *n;
+                let res = ready!(fut.poll_unpin(cx));
+                self.state = State::Idle;
+                if let Err(err) = res {
+                    // Written data is not recorded, replay is required.
+                    self.fail();
+                    return Poll::Ready(Err(err));
+                }
+                Poll::Ready(Ok(Some(n)))
+            }
+        }
+    }
+}
+
+/// Read recorded data, spilled data will be read in blocking threads.
+async fn read_at(buf: Arc<Mutex<oio::SpillBuf>>, pos: u64) -> Result<Bytes> {
+    {
+        let mut b = buf.lock().expect("lock must be valid");
+        if pos < b.memory_size() {
+            return b.read_at(pos);
+        }
+    }
+
+    tokio::task::spawn_blocking(move || buf.lock().expect("lock must be valid").read_at(pos))
+        .await
+        .map_err(new_task_join_error)?
+}
+
+impl<A: Accessor> oio::Write for SpillWriter<A> {
+    fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        loop {
+            if let Some(n) = ready!(self.poll_state(cx))? {
+                return Poll::Ready(Ok(n));
+            }
+
+            let Some(w) = self.w.as_mut() else {
+                self.state = State::Replay(self.replay());
+                continue;
+            };
+            let n = match ready!(w.poll_write(cx, bs)) {
+                Ok(n) => n,
+                Err(err) => {
+                    self.fail();
+                    return Poll::Ready(Err(err));
+                }
+            };
+
+            match self.record(bs.bytes(n)) {
+                Ok(None) => return Poll::Ready(Ok(n)),
+                Ok(Some(fut)) => self.state = State::Record(fut, n),
+                Err(err) => {
+                    self.fail();
+                    return Poll::Ready(Err(err));
+                }
+            }
+        }
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        match (&self.state, self.w.as_mut()) {
+            (State::Idle, Some(w)) => w.poll_ready(cx),
+            _ => Poll::Ready(Ok(())),
+        }
+    }
+
+    fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        loop {
+            // Ignore the size of record that cancelled by caller.
+            ready!(self.poll_state(cx))?;
+
+            let Some(w) = self.w.as_mut() else {
+                self.state = State::Replay(self.replay());
+                continue;
+            };
+            let res = ready!(w.poll_close(cx));
+            // The next close will upload again from the beginning.
+            if res.is_err() {
+                self.fail();
+            } else {
+                self.w = None;
+            }
+            return Poll::Ready(res);
+        }
+    }
+
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.state = State::Idle;
+        ready!(self.poll_abort_failed(cx));
+        if let Some(w) = self.w.as_mut() {
+            ready!(w.poll_abort(cx))?;
+            self.w = None;
+        }
+        self.buf.lock().expect("lock must be valid").clear();
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_spill_layer() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .layer(SpillLayer::new(4))
+            .finish();
+
+        let mut w = op.writer("test").await?;
+        w.write("Hello, ").await?;
+        w.write("World!").await?;
+        w.close().await?;
+
+        assert_eq!(op.read("test").await?, b"Hello, World!");
+        Ok(())
+    }
+
+    #[derive(Debug, Default)]
+    struct MockService {
+        closed: Arc<Mutex<usize>>,
+        aborted: Arc<Mutex<usize>>,
+        content: Arc<Mutex<Vec<u8>>>,
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        type Reader = ();
+        type BlockingReader = ();
+        type Writer = MockWriter;
+        type BlockingWriter = ();
+        type Lister = ();
+        type BlockingLister = ();
+
+        fn info(&self) -> AccessorInfo {
+            let mut am = AccessorInfo::default();
+            am.set_native_capability(Capability {
+                write: true,
+                ..Default::default()
+            });
+            am
+        }
+
+        async fn write(&self, _: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            Ok((
+                RpWrite::new(),
+                MockWriter {
+                    closed: self.closed.clone(),
+                    aborted: self.aborted.clone(),
+                    content: self.content.clone(),
+                    buf: vec![],
+                },
+            ))
+        }
+    }
+
+    struct MockWriter {
+        closed: Arc<Mutex<usize>>,
+        aborted: Arc<Mutex<usize>>,
+        content: Arc<Mutex<Vec<u8>>>,
+        buf: Vec<u8>,
+    }
+
+    impl oio::Write for MockWriter {
+        fn poll_write(
+            &mut self,
+            _: &mut Context<'_>,
+            bs: &dyn oio::WriteBuf,
+        ) -> Poll<Result<usize>> {
+            self.buf.extend_from_slice(bs.chunk());
+            Poll::Ready(Ok(bs.chunk().len()))
+        }
+
+        fn poll_close(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+            let mut closed = self.closed.lock().unwrap();
+            *closed += 1;
+            // The first upload fails while committing.
+            if *closed == 1 {
+                return Poll::Ready(Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "retryable error from writer",
+                )
+                .set_temporary()));
+            }
+            *self.content.lock().unwrap() = std::mem::take(&mut self.buf);
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_abort(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+            *self.aborted.lock().unwrap() += 1;
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spill_layer_replay() -> Result<()> {
+        let srv = MockService::default();
+        let (closed, aborted, content) =
+            (srv.closed.clone(), srv.aborted.clone(), srv.content.clone());
+        let op = OperatorBuilder::new(srv)
+            .layer(SpillLayer::new(4))
+            .layer(crate::layers::RetryLayer::new().with_min_delay(std::time::Duration::ZERO))
+            .finish();
+
+        let mut w = op.writer("test").await?;
+        w.write("Hello, ").await?;
+        w.write("World!").await?;
+        w.close().await?;
+
+        // The second upload replays spilled data from the beginning.
+        assert_eq!(*closed.lock().unwrap(), 2);
+        assert_eq!(*content.lock().unwrap(), b"Hello, World!");
+        // The failed upload must be aborted before replaying.
+        assert_eq!(*aborted.lock().unwrap(), 1);
+        Ok(())
+    }
+}
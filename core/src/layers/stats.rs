@@ -0,0 +1,288 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io::SeekFrom;
+use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use crate::raw::*;
+use crate::*;
+
+/// StatsLayer collects statistics for [`Operator::stats`].
+///
+/// This layer is added by OpenDAL itself, users don't need to add it.
+pub(crate) struct StatsLayer;
+
+impl<A: Accessor> Layer<A> for StatsLayer {
+    type LayeredAccessor = StatsAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        StatsAccessor {
+            inner,
+            stats: Arc::default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct StatsAccessor<A: Accessor> {
+    inner: A,
+    stats: Arc<StatsCollector>,
+}
+
+impl<A: Accessor> StatsAccessor<A> {
+    fn record<T>(&self, op: Operation, start: Instant, res: &Result<T>) {
+        self.stats.record(op, start, res.is_ok());
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for StatsAccessor<A> {
+    type Inner = A;
+    type Reader = StatsWrapper<A::Reader>;
+    type BlockingReader = StatsWrapper<A::BlockingReader>;
+    type Writer = StatsWrapper<A::Writer>;
+    type BlockingWriter = StatsWrapper<A::BlockingWriter>;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn metadata(&self) -> AccessorInfo {
+        let mut info = self.inner.info();
+        info.set_stats(self.stats.clone());
+        info
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let start = Instant::now();
+        let res = self.inner.create_dir(path, args).await;
+        self.record(Operation::CreateDir, start, &res);
+        res
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let start = Instant::now();
+        let res = self.inner.read(path, args).await;
+        self.record(Operation::Read, start, &res);
+        res.map(|(rp, r)| (rp, StatsWrapper::new(r, self.stats.clone())))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let start = Instant::now();
+        let res = self.inner.write(path, args).await;
+        self.record(Operation::Write, start, &res);
+        res.map(|(rp, w)| (rp, StatsWrapper::new(w, self.stats.clone())))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let start = Instant::now();
+        let res = self.inner.copy(from, to, args).await;
+        self.record(Operation::Copy, start, &res);
+        res
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let start = Instant::now();
+        let res = self.inner.rename(from, to, args).await;
+        self.record(Operation::Rename, start, &res);
+        res
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let start = Instant::now();
+        let res = self.inner.stat(path, args).await;
+        self.record(Operation::Stat, start, &res);
+        res
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let start = Instant::now();
+        let res = self.inner.delete(path, args).await;
+        self.record(Operation::Delete, start, &res);
+        res
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let start = Instant::now();
+        let res = self.inner.list(path, args).await;
+        self.record(Operation::List, start, &res);
+        res
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let start = Instant::now();
+        let res = self.inner.batch(args).await;
+        self.record(Operation::Batch, start, &res);
+        res
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        let start = Instant::now();
+        let res = self.inner.presign(path, args).await;
+        self.record(Operation::Presign, start, &res);
+        res
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let start = Instant::now();
+        let res = self.inner.blocking_create_dir(path, args);
+        self.record(Operation::BlockingCreateDir, start, &res);
+        res
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let start = Instant::now();
+        let res = self.inner.blocking_read(path, args);
+        self.record(Operation::BlockingRead, start, &res);
+        res.map(|(rp, r)| (rp, StatsWrapper::new(r, self.stats.clone())))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let start = Instant::now();
+        let res = self.inner.blocking_write(path, args);
+        self.record(Operation::BlockingWrite, start, &res);
+        res.map(|(rp, w)| (rp, StatsWrapper::new(w, self.stats.clone())))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let start = Instant::now();
+        let res = self.inner.blocking_copy(from, to, args);
+        self.record(Operation::BlockingCopy, start, &res);
+        res
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let start = Instant::now();
+        let res = self.inner.blocking_rename(from, to, args);
+        self.record(Operation::BlockingRename, start, &res);
+        res
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let start = Instant::now();
+        let res = self.inner.blocking_stat(path, args);
+        self.record(Operation::BlockingStat, start, &res);
+        res
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let start = Instant::now();
+        let res = self.inner.blocking_delete(path, args);
+        self.record(Operation::BlockingDelete, start, &res);
+        res
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let start = Instant::now();
+        let res = self.inner.blocking_list(path, args);
+        self.record(Operation::BlockingList, start, &res);
+        res
+    }
+}
+
+/// StatsWrapper counts bytes passing through readers and writers.
+pub(crate) struct StatsWrapper<R> {
+    inner: R,
+    stats: Arc<StatsCollector>,
+}
+
+impl<R> StatsWrapper<R> {
+    fn new(inner: R, stats: Arc<StatsCollector>) -> Self {
+        Self { inner, stats }
+    }
+}
+
+impl<R: oio::Read> oio::Read for StatsWrapper<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        let n = ready!(self.inner.poll_read(cx, buf))?;
+        self.stats.add_read_bytes(n);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        self.inner.poll_seek(cx, pos)
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        let res = ready!(self.inner.poll_next(cx));
+        if let Some(Ok(bs)) = &res {
+            self.stats.add_read_bytes(bs.len());
+        }
+        Poll::Ready(res)
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for StatsWrapper<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.stats.add_read_bytes(n);
+        Ok(n)
+    }
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        let res = self.inner.next();
+        if let Some(Ok(bs)) = &res {
+            self.stats.add_read_bytes(bs.len());
+        }
+        res
+    }
+}
+
+impl<R: oio::Write> oio::Write for StatsWrapper<R> {
+    fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        let n = ready!(self.inner.poll_write(cx, bs))?;
+        self.stats.add_written_bytes(n);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_abort(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_close(cx)
+    }
+}
+
+impl<R: oio::BlockingWrite> oio::BlockingWrite for StatsWrapper<R> {
+    fn write(&mut self, bs: &dyn oio::WriteBuf) -> Result<usize> {
+        let n = self.inner.write(bs)?;
+        self.stats.add_written_bytes(n);
+        Ok(n)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.inner.close()
+    }
+}
@@ -354,6 +354,10 @@ impl<R: oio::Write> oio::Write for TimeoutWrapper<R> {
         }
     }
 
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
     fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         match self.start {
             Some(start) => {
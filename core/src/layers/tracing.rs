@@ -328,6 +328,10 @@ impl<R: oio::Write> oio::Write for TracingWrapper<R> {
         self.inner.poll_write(cx, bs)
     }
 
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
     #[tracing::instrument(
         parent = &self.span,
         level = "trace",
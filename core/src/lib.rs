@@ -72,9 +72,14 @@ pub use types::*;
 // Public modules, they will be accessed like `opendal::layers::Xxxx`
 #[cfg(feature = "docs")]
 pub mod docs;
+pub mod gateways;
 pub mod layers;
+pub mod lock;
 pub mod raw;
 pub mod services;
+#[cfg(feature = "tests")]
+pub mod testkit;
+pub mod transfer;
 
 #[cfg(test)]
 mod tests {
@@ -88,9 +93,9 @@ mod tests {
     /// unexpected struct/enum size change.
     #[test]
     fn assert_size() {
-        assert_eq!(24, size_of::<Operator>());
-        assert_eq!(264, size_of::<Entry>());
-        assert_eq!(240, size_of::<Metadata>());
+        assert_eq!(32, size_of::<Operator>());
+        assert_eq!(360, size_of::<Entry>());
+        assert_eq!(336, size_of::<Metadata>());
         assert_eq!(1, size_of::<EntryMode>());
         assert_eq!(24, size_of::<Scheme>());
     }
@@ -0,0 +1,57 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Distributed lock built on conditional writes.
+//!
+//! Locks are plain objects written with `If-None-Match: *` and `If-Match`,
+//! so they work on any service that supports
+//! [`Capability::write_with_if_none_match`](crate::Capability::write_with_if_none_match)
+//! and [`Capability::write_with_if_match`](crate::Capability::write_with_if_match),
+//! like `s3` and `azblob`.
+//!
+//! Every successful acquisition returns a [`LockLease`] carrying a fencing
+//! token that increases monotonically per lock. Pass the token to the
+//! resources protected by the lock so that they can reject writes from
+//! holders whose lease has expired.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use opendal::lock::Lock;
+//! use opendal::Operator;
+//! use opendal::Result;
+//!
+//! async fn test(op: Operator) -> Result<()> {
+//!     let lock = Lock::new(op, "locks/leader")
+//!         .with_owner("node-1")
+//!         .with_ttl(Duration::from_secs(30));
+//!
+//!     if let Some(mut lease) = lock.try_lock().await? {
+//!         println!("became leader with token {}", lease.token());
+//!
+//!         lock.renew(&mut lease).await?;
+//!         lock.unlock(lease).await?;
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+mod object_lock;
+pub use object_lock::Lock;
+pub use object_lock::LockLease;
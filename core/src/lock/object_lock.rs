@@ -0,0 +1,492 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::*;
+
+/// LockRecord is the content of lock object.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct LockRecord {
+    owner: String,
+    token: u64,
+    /// Unix timestamp in milliseconds, `0` means released.
+    expires_at: u64,
+}
+
+impl LockRecord {
+    fn is_expired(&self, now: u64) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// LockLease is returned by [`Lock::try_lock`] while the lock is held.
+#[derive(Debug, Clone)]
+pub struct LockLease {
+    owner: String,
+    token: u64,
+    expires_at: u64,
+    etag: String,
+}
+
+impl LockLease {
+    /// The owner of this lease.
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    /// The fencing token of this lease.
+    ///
+    /// Tokens increase monotonically every time the lock is acquired, so
+    /// resources can reject requests carrying an older token.
+    pub fn token(&self) -> u64 {
+        self.token
+    }
+
+    /// The time that this lease will expire at.
+    pub fn expires_at(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.expires_at)
+    }
+
+    /// Check if this lease has been expired by local clock.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= now_millis()
+    }
+}
+
+/// Lock is a distributed lock stored at given path.
+///
+/// Expiry is decided by wall clock of clients, please make sure the `ttl`
+/// is much larger than the clock skew between them.
+#[derive(Debug, Clone)]
+pub struct Lock {
+    op: Operator,
+    path: String,
+    owner: String,
+    ttl: Duration,
+}
+
+impl Lock {
+    /// Create a lock stored at given path.
+    pub fn new(op: Operator, path: &str) -> Self {
+        Self {
+            op,
+            path: path.to_string(),
+            owner: uuid::Uuid::new_v4().to_string(),
+            ttl: Duration::from_secs(30),
+        }
+    }
+
+    /// Set the owner of this lock.
+    ///
+    /// Default to a random uuid.
+    pub fn with_owner(mut self, owner: &str) -> Self {
+        self.owner = owner.to_string();
+        self
+    }
+
+    /// Set the ttl of leases.
+    ///
+    /// Default to 30 seconds.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Try to acquire the lock.
+    ///
+    /// Returns `None` if the lock is held by others or we lost the race.
+    pub async fn try_lock(&self) -> Result<Option<LockLease>> {
+        self.check_capability()?;
+
+        let now = now_millis();
+        let (record, res) = match self.load().await {
+            Ok(None) => {
+                let record = self.new_record(1, now);
+                let res = self.write(&record).if_none_match("*").await;
+                (record, res)
+            }
+            Ok(Some((etag, prev))) if prev.is_expired(now) => {
+                let record = self.new_record(prev.token + 1, now);
+                let res = self.write(&record).if_match(&etag).await;
+                (record, res)
+            }
+            Ok(Some(_)) => return Ok(None),
+            Err(err) if err.kind() == ErrorKind::ConditionNotMatch => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        match res {
+            Ok(()) => {}
+            Err(err) if err.kind() == ErrorKind::ConditionNotMatch => return Ok(None),
+            Err(err) => return Err(err),
+        }
+
+        match self.confirm(&record).await {
+            Ok(lease) => Ok(Some(lease)),
+            Err(err) if err.kind() == ErrorKind::ConditionNotMatch => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Extend the lease by another `ttl`.
+    ///
+    /// Returns [`ErrorKind::ConditionNotMatch`] if the lock has been taken
+    /// over by others.
+    pub async fn renew(&self, lease: &mut LockLease) -> Result<()> {
+        self.check_capability()?;
+
+        let record = LockRecord {
+            owner: lease.owner.clone(),
+            token: lease.token,
+            expires_at: now_millis() + self.ttl.as_millis() as u64,
+        };
+        self.write(&record)
+            .if_match(&lease.etag)
+            .await
+            .map_err(|err| self.with_renew_context(err))?;
+
+        *lease = self
+            .confirm(&record)
+            .await
+            .map_err(|err| self.with_renew_context(err))?;
+        Ok(())
+    }
+
+    /// Release the lease.
+    ///
+    /// Releasing a lease that has been taken over by others is a no-op.
+    pub async fn unlock(&self, lease: LockLease) -> Result<()> {
+        self.check_capability()?;
+
+        // Keep the record instead of deleting it so that tokens keep
+        // increasing across acquisitions.
+        let record = LockRecord {
+            owner: lease.owner,
+            token: lease.token,
+            expires_at: 0,
+        };
+        match self.write(&record).if_match(&lease.etag).await {
+            Err(err) if err.kind() != ErrorKind::ConditionNotMatch => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_capability(&self) -> Result<()> {
+        let cap = self.op.info().full_capability();
+        if !cap.write_with_if_none_match || !cap.write_with_if_match {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "lock requires the service to support conditional writes",
+            )
+            .with_context("service", self.op.info().scheme().into_static()));
+        }
+        Ok(())
+    }
+
+    fn new_record(&self, token: u64, now: u64) -> LockRecord {
+        LockRecord {
+            owner: self.owner.clone(),
+            token,
+            expires_at: now + self.ttl.as_millis() as u64,
+        }
+    }
+
+    fn write(&self, record: &LockRecord) -> operator_futures::FutureWrite {
+        let bs = serde_json::to_vec(record).expect("lock record must be valid json");
+        self.op
+            .write_with(&self.path, bs)
+            .content_type("application/json")
+    }
+
+    /// Load the lock record along with its etag.
+    async fn load(&self) -> Result<Option<(String, LockRecord)>> {
+        let meta = match self.op.stat(&self.path).await {
+            Ok(meta) => meta,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let etag = meta.etag().ok_or_else(|| {
+            Error::new(ErrorKind::Unsupported, "lock requires etag of lock object")
+                .with_context("path", &self.path)
+        })?;
+
+        // Make sure the content we read is the version of the etag.
+        let bs = self.op.read_with(&self.path).if_match(etag).await?;
+        let record = serde_json::from_slice(&bs).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "lock record is invalid")
+                .with_context("path", &self.path)
+                .set_source(err)
+        })?;
+        Ok(Some((etag.to_string(), record)))
+    }
+
+    /// Make sure the lock record is written by us and build the lease.
+    async fn confirm(&self, record: &LockRecord) -> Result<LockLease> {
+        match self.load().await? {
+            Some((etag, current)) if &current == record => Ok(LockLease {
+                owner: current.owner,
+                token: current.token,
+                expires_at: current.expires_at,
+                etag,
+            }),
+            _ => Err(Error::new(
+                ErrorKind::ConditionNotMatch,
+                "lock has been taken over by others",
+            )),
+        }
+    }
+
+    fn with_renew_context(&self, err: Error) -> Error {
+        err.with_operation("Lock::renew")
+            .with_context("path", &self.path)
+            .with_context("owner", &self.owner)
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time must be later than unix epoch")
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::raw::*;
+    use crate::services::Memory;
+
+    /// Emulate conditional writes with etag on top of memory.
+    ///
+    /// Conditions are checked while the writer is created, which is good
+    /// enough for tests that don't write concurrently.
+    struct ConditionalLayer;
+
+    impl<A: Accessor> Layer<A> for ConditionalLayer {
+        type LayeredAccessor = ConditionalAccessor<A>;
+
+        fn layer(&self, inner: A) -> Self::LayeredAccessor {
+            ConditionalAccessor {
+                inner,
+                versions: Mutex::default(),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct ConditionalAccessor<A: Accessor> {
+        inner: A,
+        versions: Mutex<HashMap<String, u64>>,
+    }
+
+    impl<A: Accessor> ConditionalAccessor<A> {
+        fn etag(&self, path: &str) -> Option<String> {
+            let versions = self.versions.lock().unwrap();
+            versions.get(path).map(|v| format!("\"{v}\""))
+        }
+    }
+
+    fn condition_not_match() -> Error {
+        Error::new(ErrorKind::ConditionNotMatch, "condition not match")
+    }
+
+    #[async_trait]
+    impl<A: Accessor> LayeredAccessor for ConditionalAccessor<A> {
+        type Inner = A;
+        type Reader = A::Reader;
+        type BlockingReader = A::BlockingReader;
+        type Writer = A::Writer;
+        type BlockingWriter = A::BlockingWriter;
+        type Lister = A::Lister;
+        type BlockingLister = A::BlockingLister;
+
+        fn inner(&self) -> &Self::Inner {
+            &self.inner
+        }
+
+        fn metadata(&self) -> AccessorInfo {
+            let mut info = self.inner.info();
+            let cap = info.full_capability_mut();
+            cap.read_with_if_match = true;
+            cap.write_with_if_match = true;
+            cap.write_with_if_none_match = true;
+            info
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            if let Some(if_match) = args.if_match() {
+                if self.etag(path).as_deref() != Some(if_match) {
+                    return Err(condition_not_match());
+                }
+            }
+            self.inner.read(path, OpRead::new()).await
+        }
+
+        async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            {
+                let mut versions = self.versions.lock().unwrap();
+                let current = versions.get(path).map(|v| format!("\"{v}\""));
+                if args.if_none_match() == Some("*") && current.is_some() {
+                    return Err(condition_not_match());
+                }
+                if let Some(if_match) = args.if_match() {
+                    if current.as_deref() != Some(if_match) {
+                        return Err(condition_not_match());
+                    }
+                }
+                *versions.entry(path.to_string()).or_default() += 1;
+            }
+            self.inner.write(path, OpWrite::new()).await
+        }
+
+        async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+            let meta = self.inner.stat(path, args).await?.into_metadata();
+            match self.etag(path) {
+                Some(etag) => Ok(RpStat::new(meta.with_etag(etag))),
+                None => Ok(RpStat::new(meta)),
+            }
+        }
+
+        async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+            self.inner.list(path, args).await
+        }
+
+        fn blocking_read(
+            &self,
+            path: &str,
+            args: OpRead,
+        ) -> Result<(RpRead, Self::BlockingReader)> {
+            self.inner.blocking_read(path, args)
+        }
+
+        fn blocking_write(
+            &self,
+            path: &str,
+            args: OpWrite,
+        ) -> Result<(RpWrite, Self::BlockingWriter)> {
+            self.inner.blocking_write(path, args)
+        }
+
+        fn blocking_list(
+            &self,
+            path: &str,
+            args: OpList,
+        ) -> Result<(RpList, Self::BlockingLister)> {
+            self.inner.blocking_list(path, args)
+        }
+    }
+
+    fn new_operator() -> Operator {
+        Operator::new(Memory::default())
+            .expect("must init")
+            .layer(ConditionalLayer)
+            .finish()
+    }
+
+    #[test]
+    fn test_lock_record_expired() {
+        let record = LockRecord {
+            owner: "node-1".to_string(),
+            token: 3,
+            expires_at: 1000,
+        };
+        assert!(!record.is_expired(999));
+        assert!(record.is_expired(1000));
+
+        let bs = serde_json::to_vec(&record).expect("must be valid");
+        let decoded: LockRecord = serde_json::from_slice(&bs).expect("must be valid");
+        assert_eq!(decoded, record);
+    }
+
+    #[tokio::test]
+    async fn test_lock_requires_conditional_write() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        let lock = Lock::new(op, "locks/leader");
+
+        let err = lock.try_lock().await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_lock_is_exclusive() -> Result<()> {
+        let op = new_operator();
+        let a = Lock::new(op.clone(), "locks/leader").with_owner("a");
+        let b = Lock::new(op, "locks/leader").with_owner("b");
+
+        let lease = a.try_lock().await?.expect("must be acquired");
+        assert_eq!(lease.owner(), "a");
+        assert_eq!(lease.token(), 1);
+        assert!(!lease.is_expired());
+        assert!(b.try_lock().await?.is_none());
+
+        a.unlock(lease).await?;
+        let lease = b.try_lock().await?.expect("must be acquired");
+        assert_eq!(lease.owner(), "b");
+        // Tokens keep increasing across acquisitions.
+        assert_eq!(lease.token(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_lock_renew() -> Result<()> {
+        let op = new_operator();
+        let lock = Lock::new(op, "locks/leader").with_ttl(Duration::from_secs(3600));
+
+        let mut lease = lock.try_lock().await?.expect("must be acquired");
+        let expires_at = lease.expires_at();
+        std::thread::sleep(Duration::from_millis(10));
+
+        lock.renew(&mut lease).await?;
+        assert_eq!(lease.token(), 1);
+        assert!(lease.expires_at() > expires_at);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_lock_takeover_after_expired() -> Result<()> {
+        let op = new_operator();
+        let a = Lock::new(op.clone(), "locks/leader")
+            .with_owner("a")
+            .with_ttl(Duration::ZERO);
+        let b = Lock::new(op, "locks/leader").with_owner("b");
+
+        let mut stale = a.try_lock().await?.expect("must be acquired");
+        let lease = b
+            .try_lock()
+            .await?
+            .expect("expired lock must be taken over");
+        assert_eq!(lease.token(), 2);
+
+        // The stale holder can't renew nor release the lock of others.
+        let err = a.renew(&mut stale).await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        a.unlock(stale).await?;
+        assert!(a.try_lock().await?.is_none());
+        Ok(())
+    }
+}
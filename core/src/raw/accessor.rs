@@ -53,6 +53,26 @@ use crate::*;
 /// - Operations with capability requirement like `presign` are optional operations.
 ///   - Services can implement them based on services capabilities.
 ///   - The default implementation should return [`ErrorKind::Unsupported`].
+///
+/// # Stability
+///
+/// [`Accessor`] can be implemented by services outside of OpenDAL and
+/// turned into an [`Operator`] via [`Operator::from_accessor`]. The
+/// following surface is covered by semver: breaking changes to it only
+/// happen in minor releases (before 1.0) and will be noted in the upgrade
+/// guide.
+///
+/// - [`Accessor::info`] and the required associated types.
+/// - [`Accessor::read`], [`Accessor::write`], [`Accessor::stat`],
+///   [`Accessor::delete`], [`Accessor::list`] and [`Accessor::create_dir`].
+/// - [`AccessorInfo`], [`Capability`] and all `Op*` / `Rp*` types used
+///   by the methods above.
+///
+/// All other methods have default implementations, new methods will always
+/// be added with defaults that return [`ErrorKind::Unsupported`], so
+/// existing implementations keep compiling. Use
+/// [`run_conformance_tests`][crate::raw::tests::run_conformance_tests] to
+/// verify an implementation.
 #[async_trait]
 pub trait Accessor: Send + Sync + Debug + Unpin + 'static {
     /// Reader is the associated reader the could return in `read` operation.
@@ -246,6 +266,104 @@ pub trait Accessor: Send + Sync + Debug + Unpin + 'static {
         ))
     }
 
+    /// Invoke the `create_symlink` operation, creating a symbolic link at
+    /// `path` which points to `target`.
+    ///
+    /// Require [`Capability::create_symlink`]
+    ///
+    /// # Behavior
+    ///
+    /// - `path` MUST be file path, DON'T NEED to check mode.
+    /// - `target` is stored as is, services SHOULD NOT resolve or normalize it.
+    /// - Create symlink on existing path SHOULD return `AlreadyExists`.
+    async fn create_symlink(
+        &self,
+        target: &str,
+        path: &str,
+        args: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        let (_, _, _) = (target, path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `set_permissions` operation on the specified path.
+    ///
+    /// Require [`Capability::set_permissions`]
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `create_snapshot` operation on the specified path.
+    ///
+    /// Require [`Capability::create_snapshot`]
+    async fn create_snapshot(
+        &self,
+        path: &str,
+        args: OpCreateSnapshot,
+    ) -> Result<RpCreateSnapshot> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `lease` operation on the specified path.
+    ///
+    /// Require [`Capability::lease`]
+    async fn lease(&self, path: &str, args: OpLease) -> Result<RpLease> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `list_roots` operation to list all buckets or containers
+    /// in the account.
+    ///
+    /// Require [`Capability::list_roots`]
+    async fn list_roots(&self, args: OpListRoots) -> Result<RpListRoots> {
+        let _ = args;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `concat` operation on the specified path.
+    ///
+    /// Require [`Capability::concat`]
+    ///
+    /// # Behavior
+    ///
+    /// - `path` will be overwritten by the content of `sources` in order.
+    /// - `sources` will be kept untouched.
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
     /// Invoke the `blocking_create` operation on the specified path.
     ///
     /// This operation is the blocking version of [`Accessor::create_dir`]
@@ -361,6 +479,43 @@ pub trait Accessor: Send + Sync + Debug + Unpin + 'static {
             "operation is not supported",
         ))
     }
+
+    /// Invoke the `blocking_create_symlink` operation.
+    ///
+    /// This operation is the blocking version of [`Accessor::create_symlink`]
+    ///
+    /// Require [`Capability::create_symlink`] and [`Capability::blocking`]
+    fn blocking_create_symlink(
+        &self,
+        target: &str,
+        path: &str,
+        args: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        let (_, _, _) = (target, path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `blocking_set_permissions` operation on the specified path.
+    ///
+    /// This operation is the blocking version of [`Accessor::set_permissions`]
+    ///
+    /// Require [`Capability::set_permissions`] and [`Capability::blocking`]
+    fn blocking_set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
 }
 
 /// Dummy implementation of accessor.
@@ -436,6 +591,43 @@ impl<T: Accessor + ?Sized> Accessor for Arc<T> {
         self.as_ref().presign(path, args).await
     }
 
+    async fn create_symlink(
+        &self,
+        target: &str,
+        path: &str,
+        args: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        self.as_ref().create_symlink(target, path, args).await
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        self.as_ref().set_permissions(path, args).await
+    }
+
+    async fn create_snapshot(
+        &self,
+        path: &str,
+        args: OpCreateSnapshot,
+    ) -> Result<RpCreateSnapshot> {
+        self.as_ref().create_snapshot(path, args).await
+    }
+
+    async fn lease(&self, path: &str, args: OpLease) -> Result<RpLease> {
+        self.as_ref().lease(path, args).await
+    }
+
+    async fn list_roots(&self, args: OpListRoots) -> Result<RpListRoots> {
+        self.as_ref().list_roots(args).await
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        self.as_ref().concat(path, args).await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.as_ref().blocking_create_dir(path, args)
     }
@@ -463,6 +655,23 @@ impl<T: Accessor + ?Sized> Accessor for Arc<T> {
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
         self.as_ref().blocking_list(path, args)
     }
+
+    fn blocking_create_symlink(
+        &self,
+        target: &str,
+        path: &str,
+        args: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        self.as_ref().blocking_create_symlink(target, path, args)
+    }
+
+    fn blocking_set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        self.as_ref().blocking_set_permissions(path, args)
+    }
 }
 
 /// FusedAccessor is the type erased accessor with `Arc<dyn Accessor>`.
@@ -483,9 +692,14 @@ pub struct AccessorInfo {
     scheme: Scheme,
     root: String,
     name: String,
+    endpoint: Option<String>,
+    location: Option<String>,
 
     native_capability: Capability,
     full_capability: Capability,
+
+    signer: Option<RequestSigner>,
+    stats: Option<Arc<StatsCollector>>,
 }
 
 impl AccessorInfo {
@@ -529,6 +743,42 @@ impl AccessorInfo {
         self
     }
 
+    /// Endpoint that requests are sent to. Returns `None` if the backend
+    /// doesn't send requests to an endpoint.
+    ///
+    /// The endpoint identifies the account of services like `azblob`, so
+    /// backends with the same name but different endpoints are different
+    /// namespaces.
+    pub fn endpoint(&self) -> Option<&str> {
+        self.endpoint.as_deref()
+    }
+
+    /// Set endpoint of this backend.
+    pub fn set_endpoint(&mut self, endpoint: &str) -> &mut Self {
+        self.endpoint = Some(endpoint.to_string());
+        self
+    }
+
+    /// Location of the data at rest, like the region of bucket. Returns
+    /// `None` if the backend doesn't have location concept or it's unknown.
+    ///
+    /// For example:
+    ///
+    /// - location for `s3` => region like `us-east-1`
+    /// - location for `oss` => region like `oss-cn-hangzhou`
+    /// - location for `gcs` and `azblob` => `None`, gcs can only query it
+    ///   from bucket, and azblob storage accounts don't expose it via data
+    ///   APIs.
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+
+    /// Set location of this backend.
+    pub fn set_location(&mut self, location: &str) -> &mut Self {
+        self.location = Some(location.to_string());
+        self
+    }
+
     /// Get backend's native capabilities.
     pub fn native_capability(&self) -> Capability {
         self.native_capability
@@ -555,4 +805,26 @@ impl AccessorInfo {
     pub fn full_capability_mut(&mut self) -> &mut Capability {
         &mut self.full_capability
     }
+
+    /// Get the request signer of service.
+    pub fn signer(&self) -> Option<RequestSigner> {
+        self.signer.clone()
+    }
+
+    /// Set the request signer of service.
+    pub fn set_signer(&mut self, signer: RequestSigner) -> &mut Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Get the stats collector of operator.
+    pub(crate) fn stats(&self) -> Option<Arc<StatsCollector>> {
+        self.stats.clone()
+    }
+
+    /// Set the stats collector of operator.
+    pub(crate) fn set_stats(&mut self, stats: Arc<StatsCollector>) -> &mut Self {
+        self.stats = Some(stats);
+        self
+    }
 }
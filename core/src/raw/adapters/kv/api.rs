@@ -16,6 +16,7 @@
 // under the License.
 
 use std::fmt::Debug;
+use std::time::Duration;
 
 use async_trait::async_trait;
 
@@ -50,6 +51,32 @@ pub trait Adapter: Send + Sync + Debug + Unpin + 'static {
         .with_operation("kv::Adapter::blocking_get"))
     }
 
+    /// Get the size of value of a key without fetching it.
+    ///
+    /// - return `Ok(None)` if this key is not exist.
+    ///
+    /// The default implementation fetches the whole value, adapters should
+    /// override it if the size can be fetched alone, like `length(value)`
+    /// in databases.
+    async fn size(&self, path: &str) -> Result<Option<u64>> {
+        Ok(self.get(path).await?.map(|v| v.len() as u64))
+    }
+
+    /// The blocking version of size.
+    fn blocking_size(&self, path: &str) -> Result<Option<u64>> {
+        Ok(self.blocking_get(path)?.map(|v| v.len() as u64))
+    }
+
+    /// Check if a key exists without fetching its value.
+    async fn exists(&self, path: &str) -> Result<bool> {
+        Ok(self.size(path).await?.is_some())
+    }
+
+    /// The blocking version of exists.
+    fn blocking_exists(&self, path: &str) -> Result<bool> {
+        Ok(self.blocking_size(path)?.is_some())
+    }
+
     /// Set a key into service.
     async fn set(&self, path: &str, value: &[u8]) -> Result<()>;
 
@@ -64,6 +91,31 @@ pub trait Adapter: Send + Sync + Debug + Unpin + 'static {
         .with_operation("kv::Adapter::blocking_set"))
     }
 
+    /// Set a key into service which will be expired after given ttl.
+    ///
+    /// Adapters that implement this should declare
+    /// [`Capability::write_with_expire_at`] in their metadata.
+    async fn set_with_ttl(&self, path: &str, value: &[u8], ttl: Duration) -> Result<()> {
+        let _ = (path, value, ttl);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "kv adapter doesn't support this operation",
+        )
+        .with_operation("kv::Adapter::set_with_ttl"))
+    }
+
+    /// The blocking version of set_with_ttl.
+    fn blocking_set_with_ttl(&self, path: &str, value: &[u8], ttl: Duration) -> Result<()> {
+        let _ = (path, value, ttl);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "kv adapter doesn't support this operation",
+        )
+        .with_operation("kv::Adapter::blocking_set_with_ttl"))
+    }
+
     /// Delete a key from service.
     ///
     /// - return `Ok(())` even if this key is not exist.
@@ -82,6 +134,31 @@ pub trait Adapter: Send + Sync + Debug + Unpin + 'static {
         .with_operation("kv::Adapter::blocking_delete"))
     }
 
+    /// Delete all keys that start with given prefix.
+    ///
+    /// Adapters that implement this should set
+    /// [`Capability::delete_with_prefix`] in their metadata.
+    async fn delete_prefix(&self, path: &str) -> Result<()> {
+        let _ = path;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "kv adapter doesn't support this operation",
+        )
+        .with_operation("kv::Adapter::delete_prefix"))
+    }
+
+    /// Delete all keys that start with given prefix in blocking way.
+    fn blocking_delete_prefix(&self, path: &str) -> Result<()> {
+        let _ = path;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "kv adapter doesn't support this operation",
+        )
+        .with_operation("kv::Adapter::blocking_delete_prefix"))
+    }
+
     /// Scan a key prefix to get all keys that start with this key.
     async fn scan(&self, path: &str) -> Result<Vec<String>> {
         let _ = path;
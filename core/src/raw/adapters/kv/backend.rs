@@ -19,11 +19,13 @@ use std::sync::Arc;
 use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Duration;
 use std::vec::IntoIter;
 
 use async_trait::async_trait;
 use bytes::Bytes;
 use bytes::BytesMut;
+use chrono::Utc;
 use futures::future::BoxFuture;
 use futures::FutureExt;
 
@@ -131,61 +133,85 @@ impl<S: Adapter> Accessor for Backend<S> {
         Ok((RpRead::new(), oio::Cursor::from(bs)))
     }
 
-    async fn write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
         let p = build_abs_path(&self.root, path);
+        let ttl = parse_ttl(&args)?;
 
-        Ok((RpWrite::new(), KvWriter::new(self.kv.clone(), p)))
+        Ok((RpWrite::new(), KvWriter::new(self.kv.clone(), p, ttl)))
     }
 
-    fn blocking_write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
         let p = build_abs_path(&self.root, path);
+        let ttl = parse_ttl(&args)?;
 
-        Ok((RpWrite::new(), KvWriter::new(self.kv.clone(), p)))
+        Ok((RpWrite::new(), KvWriter::new(self.kv.clone(), p, ttl)))
     }
 
-    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         let p = build_abs_path(&self.root, path);
 
         if p == build_abs_path(&self.root, "") {
-            Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
-        } else {
-            let bs = self.kv.get(&p).await?;
-            match bs {
-                Some(bs) => Ok(RpStat::new(
-                    Metadata::new(EntryMode::FILE).with_content_length(bs.len() as u64),
-                )),
-                None => Err(Error::new(ErrorKind::NotFound, "kv doesn't have this path")),
-            }
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        // Only check the existence if content length is not required.
+        if !args.need_field(Metakey::ContentLength) {
+            return match self.kv.exists(&p).await? {
+                true => Ok(RpStat::new(Metadata::new(EntryMode::FILE))),
+                false => Err(Error::new(ErrorKind::NotFound, "kv doesn't have this path")),
+            };
+        }
+
+        match self.kv.size(&p).await? {
+            Some(size) => Ok(RpStat::new(
+                Metadata::new(EntryMode::FILE).with_content_length(size),
+            )),
+            None => Err(Error::new(ErrorKind::NotFound, "kv doesn't have this path")),
         }
     }
 
-    fn blocking_stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         let p = build_abs_path(&self.root, path);
 
         if p == build_abs_path(&self.root, "") {
-            Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
-        } else {
-            let bs = self.kv.blocking_get(&p)?;
-            match bs {
-                Some(bs) => Ok(RpStat::new(
-                    Metadata::new(EntryMode::FILE).with_content_length(bs.len() as u64),
-                )),
-                None => Err(Error::new(ErrorKind::NotFound, "kv doesn't have this path")),
-            }
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        // Only check the existence if content length is not required.
+        if !args.need_field(Metakey::ContentLength) {
+            return match self.kv.blocking_exists(&p)? {
+                true => Ok(RpStat::new(Metadata::new(EntryMode::FILE))),
+                false => Err(Error::new(ErrorKind::NotFound, "kv doesn't have this path")),
+            };
+        }
+
+        match self.kv.blocking_size(&p)? {
+            Some(size) => Ok(RpStat::new(
+                Metadata::new(EntryMode::FILE).with_content_length(size),
+            )),
+            None => Err(Error::new(ErrorKind::NotFound, "kv doesn't have this path")),
         }
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         let p = build_abs_path(&self.root, path);
 
-        self.kv.delete(&p).await?;
+        if args.prefix() {
+            self.kv.delete_prefix(&p).await?;
+        } else {
+            self.kv.delete(&p).await?;
+        }
         Ok(RpDelete::default())
     }
 
-    fn blocking_delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         let p = build_abs_path(&self.root, path);
 
-        self.kv.blocking_delete(&p)?;
+        if args.prefix() {
+            self.kv.blocking_delete_prefix(&p)?;
+        } else {
+            self.kv.blocking_delete(&p)?;
+        }
         Ok(RpDelete::default())
     }
 
@@ -327,19 +353,33 @@ impl oio::BlockingList for KvLister {
     }
 }
 
+/// Convert the expire time of op into ttl.
+fn parse_ttl(args: &OpWrite) -> Result<Option<Duration>> {
+    let Some(expire_at) = args.expire_at() else {
+        return Ok(None);
+    };
+
+    (expire_at - Utc::now()).to_std().map(Some).map_err(|_| {
+        Error::new(ErrorKind::InvalidInput, "expire_at must be in the future")
+            .with_context("expire_at", expire_at.to_rfc3339())
+    })
+}
+
 pub struct KvWriter<S> {
     kv: Arc<S>,
     path: String,
+    ttl: Option<Duration>,
 
     buffer: Buffer,
     future: Option<BoxFuture<'static, Result<()>>>,
 }
 
 impl<S> KvWriter<S> {
-    fn new(kv: Arc<S>, path: String) -> Self {
+    fn new(kv: Arc<S>, path: String, ttl: Option<Duration>) -> Self {
         KvWriter {
             kv,
             path,
+            ttl,
             buffer: Buffer::Active(BytesMut::new()),
             future: None,
         }
@@ -396,7 +436,13 @@ impl<S: Adapter> oio::Write for KvWriter<S> {
                         Buffer::Frozen(buf) => buf.clone(),
                     };
 
-                    let fut = async move { kv.set(&path, &buf).await };
+                    let ttl = self.ttl;
+                    let fut = async move {
+                        match ttl {
+                            Some(ttl) => kv.set_with_ttl(&path, &buf, ttl).await,
+                            None => kv.set(&path, &buf).await,
+                        }
+                    };
                     self.future = Some(Box::pin(fut));
                 }
             }
@@ -438,7 +484,10 @@ impl<S: Adapter> oio::BlockingWrite for KvWriter<S> {
             Buffer::Frozen(buf) => buf.clone(),
         };
 
-        self.kv.blocking_set(&self.path, &buf)?;
+        match self.ttl {
+            Some(ttl) => self.kv.blocking_set_with_ttl(&self.path, &buf, ttl)?,
+            None => self.kv.blocking_set(&self.path, &buf)?,
+        }
         Ok(())
     }
 }
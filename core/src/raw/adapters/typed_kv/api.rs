@@ -87,6 +87,28 @@ pub trait Adapter: Send + Sync + Debug + Unpin + 'static {
         )
         .with_operation("typed_kv::Adapter::blocking_scan"))
     }
+
+    /// Delete all values whose key starts with given prefix.
+    async fn delete_prefix(&self, path: &str) -> Result<()> {
+        let _ = path;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "typed_kv adapter doesn't support this operation",
+        )
+        .with_operation("typed_kv::Adapter::delete_prefix"))
+    }
+
+    /// Delete all values whose key starts with given prefix in blocking way.
+    fn blocking_delete_prefix(&self, path: &str) -> Result<()> {
+        let _ = path;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "typed_kv adapter doesn't support this operation",
+        )
+        .with_operation("typed_kv::Adapter::blocking_delete_prefix"))
+    }
 }
 
 /// Value is the typed value stored in adapter.
@@ -129,6 +151,8 @@ pub struct Capability {
     pub delete: bool,
     /// If typed_kv operator supports scan natively.
     pub scan: bool,
+    /// If typed_kv operator supports delete by prefix natively.
+    pub delete_prefix: bool,
 }
 
 impl Debug for Capability {
@@ -147,6 +171,9 @@ impl Debug for Capability {
         if self.scan {
             s.push("Scan");
         }
+        if self.delete_prefix {
+            s.push("DeletePrefix");
+        }
 
         write!(f, "{{ {} }}", s.join(" | "))
     }
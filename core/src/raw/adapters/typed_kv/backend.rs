@@ -23,6 +23,7 @@ use std::vec::IntoIter;
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use chrono::Utc;
 use futures::future::BoxFuture;
 use futures::FutureExt;
 
@@ -92,6 +93,10 @@ impl<S: Adapter> Accessor for Backend<S> {
             cap.delete = true;
         }
 
+        if kv_cap.delete_prefix {
+            cap.delete_with_prefix = true;
+        }
+
         if kv_cap.scan {
             cap.list = true;
             cap.list_with_recursive = true;
@@ -178,17 +183,25 @@ impl<S: Adapter> Accessor for Backend<S> {
         }
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         let p = build_abs_path(&self.root, path);
 
-        self.kv.delete(&p).await?;
+        if args.prefix() {
+            self.kv.delete_prefix(&p).await?;
+        } else {
+            self.kv.delete(&p).await?;
+        }
         Ok(RpDelete::default())
     }
 
-    fn blocking_delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         let p = build_abs_path(&self.root, path);
 
-        self.kv.blocking_delete(&p)?;
+        if args.prefix() {
+            self.kv.blocking_delete_prefix(&p)?;
+        } else {
+            self.kv.blocking_delete(&p)?;
+        }
         Ok(RpDelete::default())
     }
 
@@ -360,6 +373,7 @@ impl<S> KvWriter<S> {
 
         let mut metadata = Metadata::new(EntryMode::FILE);
         metadata.set_content_length(value.len() as u64);
+        metadata.set_last_modified(Utc::now());
 
         if let Some(v) = self.op.cache_control() {
             metadata.set_cache_control(v);
@@ -53,3 +53,14 @@ pub fn parse_datetime_from_from_timestamp_millis(s: i64) -> Result<DateTime<Utc>
 
     Ok(st.into())
 }
+
+/// Get the number of days from now to given time, rounded up and at least
+/// `1`.
+///
+/// Services like OSS and COS can only expire objects by lifecycle rules in
+/// days, rounding up makes sure objects will never be removed before
+/// the expected time.
+pub fn expire_days_from_now(expire_at: DateTime<Utc>) -> u64 {
+    let secs = (expire_at - Utc::now()).num_seconds().max(1) as u64;
+    (secs + 86399) / 86400
+}
@@ -0,0 +1,208 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::DateTime;
+use chrono::Utc;
+
+use crate::*;
+
+/// The credential will be refreshed before it's actually expired.
+const EXPIRY_SKEW: Duration = Duration::from_secs(120);
+/// The default ttl of cached credential.
+const DEFAULT_TTL: Duration = Duration::from_secs(600);
+
+/// CredentialCache caches the credential loaded by services so that hot
+/// paths don't need to run the whole loader chain for every request.
+///
+/// A cached credential will be reloaded if:
+///
+/// - It has been cached longer than `ttl`.
+/// - It's going to expire, returned by the expiry function set by
+///   [`CredentialCache::with_expiry`].
+/// - It has been invalidated via [`CredentialCache::invalidate`], usually
+///   after service rejects it.
+///
+/// The lock is never held while loading, so concurrent callers could load
+/// at the same time. A credential loaded before the latest invalidation
+/// will be returned but not cached.
+pub struct CredentialCache<T> {
+    ttl: Duration,
+    expiry: Option<fn(&T) -> Option<DateTime<Utc>>>,
+
+    state: Mutex<CacheState<T>>,
+}
+
+struct CacheState<T> {
+    cred: Option<(T, Instant)>,
+    /// Increased by every invalidation.
+    generation: u64,
+}
+
+impl<T> Debug for CredentialCache<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CredentialCache")
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Clone> Default for CredentialCache<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_TTL)
+    }
+}
+
+impl<T: Clone> CredentialCache<T> {
+    /// Create a new credential cache that caches credential for at most `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            expiry: None,
+            state: Mutex::new(CacheState {
+                cred: None,
+                generation: 0,
+            }),
+        }
+    }
+
+    /// Set the function to extract the expiry time from credential.
+    pub fn with_expiry(mut self, expiry: fn(&T) -> Option<DateTime<Utc>>) -> Self {
+        self.expiry = Some(expiry);
+        self
+    }
+
+    fn is_fresh(&self, cred: &T, loaded_at: Instant) -> bool {
+        if loaded_at.elapsed() >= self.ttl {
+            return false;
+        }
+
+        match self.expiry.and_then(|f| f(cred)) {
+            Some(expires_at) => {
+                let skew = chrono::Duration::from_std(EXPIRY_SKEW)
+                    .expect("skew must be valid chrono duration");
+                expires_at - skew > Utc::now()
+            }
+            None => true,
+        }
+    }
+
+    /// Get the cached credential, or load a new one via given loader.
+    pub async fn get_or_load<F, Fut>(&self, load: F) -> Result<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let generation = {
+            let state = self.state.lock().expect("lock must be valid");
+            if let Some((cred, loaded_at)) = state.cred.as_ref() {
+                if self.is_fresh(cred, *loaded_at) {
+                    return Ok(cred.clone());
+                }
+            }
+            state.generation
+        };
+
+        let cred = load().await?;
+
+        let mut state = self.state.lock().expect("lock must be valid");
+        if state.generation == generation {
+            state.cred = Some((cred.clone(), Instant::now()));
+        }
+        Ok(cred)
+    }
+
+    /// Invalidate the cached credential so that next call will reload it.
+    pub fn invalidate(&self) {
+        let mut state = self.state.lock().expect("lock must be valid");
+        state.cred = None;
+        state.generation += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_credential_cache() -> Result<()> {
+        let loaded = AtomicUsize::new(0);
+        let cache = CredentialCache::new(Duration::from_secs(3600));
+        let load = || async {
+            loaded.fetch_add(1, Ordering::SeqCst);
+            Ok("token".to_string())
+        };
+
+        assert_eq!(cache.get_or_load(load).await?, "token");
+        assert_eq!(cache.get_or_load(load).await?, "token");
+        assert_eq!(loaded.load(Ordering::SeqCst), 1);
+
+        cache.invalidate();
+        assert_eq!(cache.get_or_load(load).await?, "token");
+        assert_eq!(loaded.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_credential_cache_expired() -> Result<()> {
+        let loaded = AtomicUsize::new(0);
+        let cache = CredentialCache::new(Duration::from_secs(3600))
+            .with_expiry(|_: &String| Some(Utc::now()));
+        let load = || async {
+            loaded.fetch_add(1, Ordering::SeqCst);
+            Ok("token".to_string())
+        };
+
+        cache.get_or_load(load).await?;
+        cache.get_or_load(load).await?;
+        assert_eq!(loaded.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_credential_cache_invalidate_while_loading() -> Result<()> {
+        let loaded = AtomicUsize::new(0);
+        let cache = CredentialCache::new(Duration::from_secs(3600));
+
+        // Credential loaded before invalidation must not be cached.
+        let cred = cache
+            .get_or_load(|| async {
+                cache.invalidate();
+                Ok("stale".to_string())
+            })
+            .await?;
+        assert_eq!(cred, "stale");
+
+        let load = || async {
+            loaded.fetch_add(1, Ordering::SeqCst);
+            Ok("token".to_string())
+        };
+        assert_eq!(cache.get_or_load(load).await?, "token");
+        assert_eq!(cache.get_or_load(load).await?, "token");
+        assert_eq!(loaded.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+}
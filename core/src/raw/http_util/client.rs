@@ -18,7 +18,12 @@
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::mem;
+use std::net::SocketAddr;
 use std::str::FromStr;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::time::Duration;
 
 use futures::TryStreamExt;
@@ -39,6 +44,234 @@ const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(60);
 #[derive(Clone)]
 pub struct HttpClient {
     client: reqwest::Client,
+    stats: Arc<HttpClientStatsInner>,
+    /// Reject all requests instead of sending them.
+    offline: bool,
+    /// The tower service built by [`HttpClient::with_layer`], all requests
+    /// will be sent through it if set.
+    #[cfg(feature = "http-tower")]
+    pub(super) service: Option<super::service::SharedService>,
+}
+
+/// HttpClientBuilder is used to build a [`HttpClient`] with connection pool
+/// options.
+///
+/// The built client can be passed to services via their `http_client`
+/// builder method.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use opendal::raw::HttpClient;
+///
+/// let client = HttpClient::builder()
+///     .pool_max_idle_per_host(16)
+///     .pool_idle_timeout(Duration::from_secs(30))
+///     .tcp_keepalive(Duration::from_secs(60))
+///     .build()
+///     .expect("must build");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HttpClientBuilder {
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    http1_only: bool,
+    http2_prior_knowledge: bool,
+    http2_initial_stream_window_size: Option<u32>,
+    http2_initial_connection_window_size: Option<u32>,
+    http2_adaptive_window: bool,
+    tcp_keepalive: Option<Duration>,
+    resolve: Vec<(String, Vec<SocketAddr>)>,
+}
+
+impl HttpClientBuilder {
+    /// Set the max idle connections per host in pool.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Set the timeout for idle connections in pool.
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Only use HTTP/2 for all connections.
+    ///
+    /// Please make sure the service supports HTTP/2, otherwise all requests
+    /// will fail.
+    ///
+    /// Conflicts with [`Self::http1_only`].
+    pub fn http2_prior_knowledge(mut self, enabled: bool) -> Self {
+        self.http2_prior_knowledge = enabled;
+        self
+    }
+
+    /// Only use HTTP/1 for all connections.
+    ///
+    /// Conflicts with [`Self::http2_prior_knowledge`].
+    pub fn http1_only(mut self, enabled: bool) -> Self {
+        self.http1_only = enabled;
+        self
+    }
+
+    /// Set the initial window size of HTTP/2 stream-level flow control.
+    pub fn http2_initial_stream_window_size(mut self, size: u32) -> Self {
+        self.http2_initial_stream_window_size = Some(size);
+        self
+    }
+
+    /// Set the initial window size of HTTP/2 connection-level flow control.
+    pub fn http2_initial_connection_window_size(mut self, size: u32) -> Self {
+        self.http2_initial_connection_window_size = Some(size);
+        self
+    }
+
+    /// Enable adaptive flow control of HTTP/2, which overrides the initial
+    /// window sizes.
+    pub fn http2_adaptive_window(mut self, enabled: bool) -> Self {
+        self.http2_adaptive_window = enabled;
+        self
+    }
+
+    /// Set the interval of TCP keepalive.
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Override DNS resolution of given domain to given addresses.
+    ///
+    /// Addresses will be tried in order, so the secondary addresses can be
+    /// used as failover while the first one is unreachable. The port of
+    /// address will be ignored, the port of request URL is used instead.
+    ///
+    /// Can be called multiple times for different domains.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::SocketAddr;
+    ///
+    /// use opendal::raw::HttpClient;
+    ///
+    /// let addr: SocketAddr = "10.0.0.1:443".parse().unwrap();
+    /// let client = HttpClient::builder()
+    ///     .resolve("account.blob.core.windows.net", &[addr])
+    ///     .build()
+    ///     .expect("must build");
+    /// ```
+    pub fn resolve(mut self, domain: &str, addrs: &[SocketAddr]) -> Self {
+        self.resolve.push((domain.to_string(), addrs.to_vec()));
+        self
+    }
+
+    /// Build the http client.
+    pub fn build(self) -> Result<HttpClient> {
+        if self.http1_only && self.http2_prior_knowledge {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "http1_only and http2_prior_knowledge can't be enabled at the same time",
+            ));
+        }
+
+        let mut builder = reqwest::ClientBuilder::new();
+
+        if let Some(v) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(v);
+        }
+        if let Some(v) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(v);
+        }
+        if self.http1_only {
+            builder = builder.http1_only();
+        }
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if let Some(v) = self.http2_initial_stream_window_size {
+            builder = builder.http2_initial_stream_window_size(v);
+        }
+        if let Some(v) = self.http2_initial_connection_window_size {
+            builder = builder.http2_initial_connection_window_size(v);
+        }
+        if self.http2_adaptive_window {
+            builder = builder.http2_adaptive_window(true);
+        }
+        if let Some(v) = self.tcp_keepalive {
+            builder = builder.tcp_keepalive(v);
+        }
+        for (domain, addrs) in &self.resolve {
+            if addrs.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "resolve requires at least one address",
+                )
+                .with_context("domain", domain));
+            }
+            builder = builder.resolve_to_addrs(domain, addrs);
+        }
+
+        HttpClient::build(builder)
+    }
+}
+
+/// HttpClientStats is the statistics of requests sent by [`HttpClient`].
+///
+/// # Notes
+///
+/// reqwest doesn't expose the state of its connection pool, so the stats
+/// are collected at request level. A request is in flight until its
+/// response headers have been received.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HttpClientStats {
+    requests_total: u64,
+    requests_in_flight: usize,
+    errors_total: u64,
+}
+
+impl HttpClientStats {
+    /// The number of requests that have been sent.
+    pub fn requests_total(&self) -> u64 {
+        self.requests_total
+    }
+
+    /// The number of requests that are still waiting for response.
+    pub fn requests_in_flight(&self) -> usize {
+        self.requests_in_flight
+    }
+
+    /// The number of requests that failed to get a response.
+    pub fn errors_total(&self) -> u64 {
+        self.errors_total
+    }
+}
+
+#[derive(Default)]
+struct HttpClientStatsInner {
+    requests_total: AtomicU64,
+    requests_in_flight: AtomicUsize,
+    errors_total: AtomicU64,
+}
+
+/// InFlightGuard decreases the in flight requests while dropped, so
+/// cancelled requests will be counted correctly.
+struct InFlightGuard<'a>(&'a AtomicUsize);
+
+impl<'a> InFlightGuard<'a> {
+    fn new(v: &'a AtomicUsize) -> Self {
+        v.fetch_add(1, Ordering::Relaxed);
+        Self(v)
+    }
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
 }
 
 /// We don't want users to know details about our clients.
@@ -54,6 +287,11 @@ impl HttpClient {
         Self::build(reqwest::ClientBuilder::new())
     }
 
+    /// Create a new [`HttpClientBuilder`] to configure connection pool.
+    pub fn builder() -> HttpClientBuilder {
+        HttpClientBuilder::default()
+    }
+
     /// Build a new http client in async context.
     pub fn build(mut builder: reqwest::ClientBuilder) -> Result<Self> {
         // Make sure we don't enable auto gzip decompress.
@@ -72,21 +310,99 @@ impl HttpClient {
             client: builder.build().map_err(|err| {
                 Error::new(ErrorKind::Unexpected, "async client build failed").set_source(err)
             })?,
+            stats: Arc::default(),
+            offline: false,
+            #[cfg(feature = "http-tower")]
+            service: None,
         })
     }
 
+    /// Create a http client that never sends requests.
+    ///
+    /// All requests sent by this client will fail with
+    /// [`ErrorKind::Unsupported`]. It's useful for services that only used
+    /// to presign requests in environments with strict egress rules, see
+    /// [`crate::layers::PresignOnlyLayer`].
+    ///
+    /// Note: the reqwest client returned by [`HttpClient::client`] is still
+    /// able to send requests.
+    pub fn offline() -> Result<Self> {
+        let mut client = Self::new()?;
+        client.offline = true;
+        Ok(client)
+    }
+
+    /// Check if this client is offline.
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
     /// Get the async client from http client.
     pub fn client(&self) -> reqwest::Client {
         self.client.clone()
     }
 
+    /// Get the stats of requests sent by this client.
+    ///
+    /// Stats are shared by all clones of this client.
+    pub fn stats(&self) -> HttpClientStats {
+        HttpClientStats {
+            requests_total: self.stats.requests_total.load(Ordering::Relaxed),
+            requests_in_flight: self.stats.requests_in_flight.load(Ordering::Relaxed),
+            errors_total: self.stats.errors_total.load(Ordering::Relaxed),
+        }
+    }
+
     /// Send a request in async way.
-    pub async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+    pub async fn send(&self, mut req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+        let Some(request_id) = ClientRequestId::current() else {
+            return self.send_inner(req).await;
+        };
+
+        request_id.insert_into(req.headers_mut());
+        match self.send_inner(req).await {
+            Ok(mut resp) => {
+                // Insert request id into response extension so that we can
+                // fetch it while building errors.
+                resp.extensions_mut().insert(request_id);
+                Ok(resp)
+            }
+            Err(err) => Err(err.with_context("client_request_id", request_id.id())),
+        }
+    }
+
+    async fn send_inner(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+        if self.offline {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "http client is offline, no request could be sent",
+            )
+            .with_operation("http_util::Client::send_async")
+            .with_context("url", req.uri().to_string()));
+        }
+
+        #[cfg(feature = "http-tower")]
+        if let Some(service) = &self.service {
+            return service.call(req).await;
+        }
+
+        self.send_direct(req).await
+    }
+
+    /// Send a request via reqwest directly without tower service.
+    pub(super) async fn send_direct(
+        &self,
+        req: Request<AsyncBody>,
+    ) -> Result<Response<IncomingAsyncBody>> {
         // Uri stores all string alike data in `Bytes` which means
         // the clone here is cheap.
         let uri = req.uri().clone();
         let is_head = req.method() == http::Method::HEAD;
 
+        // Only decompress the response while we asked for it.
+        #[cfg(feature = "http-compression")]
+        let accept_encoding = req.headers().contains_key(http::header::ACCEPT_ENCODING);
+
         let (parts, body) = req.into_parts();
 
         let mut req_builder = self
@@ -105,7 +421,14 @@ impl HttpClient {
             AsyncBody::Stream(s) => req_builder.body(reqwest::Body::wrap_stream(s)),
         };
 
-        let mut resp = req_builder.send().await.map_err(|err| {
+        self.stats.requests_total.fetch_add(1, Ordering::Relaxed);
+        let guard = InFlightGuard::new(&self.stats.requests_in_flight);
+        let resp = req_builder.send().await;
+        drop(guard);
+
+        let mut resp = resp.map_err(|err| {
+            self.stats.errors_total.fetch_add(1, Ordering::Relaxed);
+
             let is_temporary = !(
                 // Builder related error should not be retried.
                 err.is_builder() ||
@@ -132,12 +455,28 @@ impl HttpClient {
 
         // Get content length from header so that we can check it.
         // If the request method is HEAD, we will ignore this.
-        let content_length = if is_head {
+        #[cfg_attr(not(feature = "http-compression"), allow(unused_mut))]
+        let mut content_length = if is_head {
             None
         } else {
             parse_content_length(resp.headers()).expect("response content length must be valid")
         };
 
+        #[cfg(feature = "http-compression")]
+        let decoder = if accept_encoding {
+            resp.headers()
+                .get(http::header::CONTENT_ENCODING)
+                .and_then(|v| v.to_str().ok())
+                .and_then(super::compression::Decoder::new)
+        } else {
+            None
+        };
+        // Content length is the size of compressed content, we can't check it.
+        #[cfg(feature = "http-compression")]
+        if decoder.is_some() {
+            content_length = None;
+        }
+
         let mut hr = Response::builder()
             .version(resp.version())
             .status(resp.status())
@@ -156,10 +495,100 @@ impl HttpClient {
                 .set_source(err)
         });
 
-        let body = IncomingAsyncBody::new(Box::new(oio::into_stream(stream)), content_length);
+        let stream: oio::Streamer = Box::new(oio::into_stream(stream));
+        #[cfg(feature = "http-compression")]
+        let stream: oio::Streamer = match decoder {
+            Some(decoder) => Box::new(super::compression::DecompressStream::new(stream, decoder)),
+            None => stream,
+        };
+
+        let body = IncomingAsyncBody::new(stream, content_length);
 
         let resp = hr.body(body).expect("response must build succeed");
 
         Ok(resp)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use http::Version;
+    use wiremock::matchers::method;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+
+    use super::*;
+
+    async fn send_get(client: &HttpClient, server: &MockServer) -> Result<Version> {
+        let req = Request::get(format!("{}/", server.uri()))
+            .body(AsyncBody::Empty)
+            .expect("request must be valid");
+        let resp = client.send(req).await?;
+        assert!(resp.status().is_success());
+        Ok(resp.version())
+    }
+
+    #[test]
+    fn test_build_with_conflict_http_versions() {
+        let err = HttpClient::builder()
+            .http1_only(true)
+            .http2_prior_knowledge(true)
+            .build()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[tokio::test]
+    async fn test_http1_only() -> Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let client = HttpClient::builder().http1_only(true).build()?;
+        assert_eq!(send_get(&client, &server).await?, Version::HTTP_11);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_http2_prior_knowledge_with_windows() -> Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let client = HttpClient::builder()
+            .http2_prior_knowledge(true)
+            .http2_initial_stream_window_size(1024 * 1024)
+            .http2_initial_connection_window_size(4 * 1024 * 1024)
+            .build()?;
+        assert_eq!(send_get(&client, &server).await?, Version::HTTP_2);
+
+        let client = HttpClient::builder()
+            .http2_prior_knowledge(true)
+            .http2_adaptive_window(true)
+            .build()?;
+        assert_eq!(send_get(&client, &server).await?, Version::HTTP_2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_offline() -> Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let client = HttpClient::offline()?;
+        assert!(client.is_offline());
+        let err = send_get(&client, &server).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert!(!HttpClient::new()?.is_offline());
+        Ok(())
+    }
+}
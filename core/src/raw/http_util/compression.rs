@@ -0,0 +1,222 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+#[cfg(feature = "http-compression")]
+pub(crate) use decompress::*;
+
+/// Ask server to compress the response.
+///
+/// Services should only use this for metadata requests like `list` whose
+/// responses are generated by server. The compressed response will be
+/// decompressed by [`HttpClient`](super::HttpClient) transparently.
+///
+/// This function does nothing unless feature `http-compression` is enabled.
+pub fn with_accept_encoding(req: http::request::Builder) -> http::request::Builder {
+    #[cfg(feature = "http-compression")]
+    let req = req.header(http::header::ACCEPT_ENCODING, decompress::ACCEPT_ENCODING);
+
+    req
+}
+
+#[cfg(feature = "http-compression")]
+mod decompress {
+    use std::io;
+    use std::io::Write;
+    use std::mem;
+    use std::task::ready;
+    use std::task::Context;
+    use std::task::Poll;
+
+    use bytes::Bytes;
+    use flate2::write::GzDecoder;
+    use flate2::write::ZlibDecoder;
+
+    use crate::raw::*;
+    use crate::*;
+
+    /// The encodings that we can decompress.
+    pub(crate) const ACCEPT_ENCODING: &str = "gzip, deflate";
+
+    pub(crate) enum Decoder {
+        Gzip(GzDecoder<Vec<u8>>),
+        Deflate(ZlibDecoder<Vec<u8>>),
+    }
+
+    impl Decoder {
+        /// Create a decoder for given content encoding, returns `None` if
+        /// it's not supported.
+        ///
+        /// Content codings are case-insensitive as described in
+        /// [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#section-8.4.1).
+        pub(crate) fn new(encoding: &str) -> Option<Self> {
+            match encoding.trim().to_ascii_lowercase().as_str() {
+                "gzip" | "x-gzip" => Some(Self::Gzip(GzDecoder::new(Vec::new()))),
+                "deflate" => Some(Self::Deflate(ZlibDecoder::new(Vec::new()))),
+                _ => None,
+            }
+        }
+
+        fn write(&mut self, bs: &[u8]) -> io::Result<Bytes> {
+            let buf = match self {
+                Self::Gzip(d) => {
+                    d.write_all(bs)?;
+                    d.get_mut()
+                }
+                Self::Deflate(d) => {
+                    d.write_all(bs)?;
+                    d.get_mut()
+                }
+            };
+            Ok(mem::take(buf).into())
+        }
+
+        fn finish(&mut self) -> io::Result<Bytes> {
+            let buf = match self {
+                Self::Gzip(d) => {
+                    d.try_finish()?;
+                    d.get_mut()
+                }
+                Self::Deflate(d) => {
+                    d.try_finish()?;
+                    d.get_mut()
+                }
+            };
+            Ok(mem::take(buf).into())
+        }
+    }
+
+    /// DecompressStream decompresses the content of given stream.
+    pub(crate) struct DecompressStream {
+        inner: oio::Streamer,
+        decoder: Decoder,
+        done: bool,
+    }
+
+    impl DecompressStream {
+        pub(crate) fn new(inner: oio::Streamer, decoder: Decoder) -> Self {
+            Self {
+                inner,
+                decoder,
+                done: false,
+            }
+        }
+    }
+
+    fn new_decompress_error(err: io::Error) -> Error {
+        Error::new(ErrorKind::Unexpected, "decompress http response").set_source(err)
+    }
+
+    impl oio::Stream for DecompressStream {
+        fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+            loop {
+                if self.done {
+                    return Poll::Ready(None);
+                }
+
+                let bs = match ready!(self.inner.poll_next(cx)) {
+                    Some(Ok(bs)) => self.decoder.write(&bs),
+                    Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                    None => {
+                        self.done = true;
+                        self.decoder.finish()
+                    }
+                };
+
+                match bs {
+                    Ok(bs) if bs.is_empty() => continue,
+                    Ok(bs) => return Poll::Ready(Some(Ok(bs))),
+                    Err(err) => return Poll::Ready(Some(Err(new_decompress_error(err)))),
+                }
+            }
+        }
+
+        fn poll_reset(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+            Poll::Ready(Err(Error::new(
+                ErrorKind::Unsupported,
+                "DecompressStream doesn't support reset",
+            )))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use flate2::write::GzEncoder;
+        use flate2::write::ZlibEncoder;
+        use flate2::Compression;
+
+        use super::*;
+
+        #[test]
+        fn test_gzip_decoder() {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(b"Hello, World!").unwrap();
+            let compressed = encoder.finish().unwrap();
+
+            let mut decoder = Decoder::new("gzip").expect("gzip must be supported");
+            let mut content = vec![];
+            for chunk in compressed.chunks(3) {
+                content.extend_from_slice(&decoder.write(chunk).unwrap());
+            }
+            content.extend_from_slice(&decoder.finish().unwrap());
+
+            assert_eq!(content, b"Hello, World!");
+        }
+
+        #[test]
+        fn test_decoder_encoding() {
+            assert!(Decoder::new("GZIP").is_some());
+            assert!(Decoder::new(" x-gzip ").is_some());
+            assert!(Decoder::new("Deflate").is_some());
+            assert!(Decoder::new("br").is_none());
+        }
+
+        #[tokio::test]
+        async fn test_decompress_stream() {
+            let content = "Hello, World!".repeat(1024);
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(content.as_bytes()).unwrap();
+            let compressed = encoder.finish().unwrap();
+
+            let chunks = compressed
+                .chunks(7)
+                .map(Bytes::copy_from_slice)
+                .collect::<Vec<_>>();
+            let stream = DecompressStream::new(
+                Box::new(oio::ChunkedBytes::from_vec(chunks)),
+                Decoder::new("deflate").expect("deflate must be supported"),
+            );
+            let bs = oio::StreamExt::collect(stream)
+                .await
+                .expect("decompress must succeed");
+            assert_eq!(bs, content.as_bytes());
+        }
+
+        #[tokio::test]
+        async fn test_decompress_stream_invalid_content() {
+            let stream = DecompressStream::new(
+                Box::new(oio::ChunkedBytes::from_vec(vec![Bytes::from(
+                    "not compressed at all",
+                )])),
+                Decoder::new("gzip").expect("gzip must be supported"),
+            );
+            let err = oio::StreamExt::collect(stream)
+                .await
+                .expect_err("decompress must fail");
+            assert_eq!(err.kind(), ErrorKind::Unexpected);
+        }
+    }
+}
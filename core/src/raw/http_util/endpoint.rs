@@ -0,0 +1,384 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::net::IpAddr;
+use std::net::Ipv6Addr;
+
+use crate::*;
+
+/// EndpointStyle decides where the bucket (or container) name will be put
+/// in the endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointStyle {
+    /// Put the bucket name in path like `https://s3.amazonaws.com/bucket`.
+    Path,
+    /// Put the bucket name in host like `https://bucket.s3.amazonaws.com`.
+    ///
+    /// Only works with domain endpoints, IP literals are not allowed.
+    VirtualHost,
+}
+
+/// Endpoint is the parsed endpoint of HTTP services.
+///
+/// Endpoint accepts:
+///
+/// - host with or without scheme: `s3.amazonaws.com`, `https://s3.amazonaws.com`
+/// - host with port: `127.0.0.1:9000`, `minio.local:9000`
+/// - IPv6 literals with optional zone: `[::1]:9000`, `[fe80::1%25eth0]`, `::1`
+/// - private-link endpoints: `bucket.vpce-1a2b3c4d.s3.us-east-1.vpce.amazonaws.com`
+/// - endpoints with path: `http://127.0.0.1:10000/devstoreaccount1`
+///
+/// Scheme will be `https` if not specified. The error of parsing contains
+/// what has been parsed so that users can find out the wrong part easily.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    scheme: String,
+    host: String,
+    port: Option<u16>,
+    path: String,
+}
+
+impl Endpoint {
+    /// Parse given endpoint.
+    pub fn parse(endpoint: &str) -> Result<Endpoint> {
+        let input = endpoint.trim().trim_end_matches('/');
+        if input.is_empty() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty"));
+        }
+
+        let (scheme, rest) = match input.split_once("://") {
+            Some((scheme, rest)) => (scheme.to_ascii_lowercase(), rest),
+            None => ("https".to_string(), input),
+        };
+        if scheme != "http" && scheme != "https" {
+            return Err(new_endpoint_error(
+                endpoint,
+                "endpoint scheme must be http or https",
+                &[("scheme", &scheme)],
+            ));
+        }
+
+        let (authority, path) = match rest.find('/') {
+            Some(idx) => rest.split_at(idx),
+            None => (rest, ""),
+        };
+        if authority.contains('@') {
+            return Err(new_endpoint_error(
+                endpoint,
+                "endpoint must not contain user info",
+                &[("scheme", &scheme), ("authority", authority)],
+            ));
+        }
+
+        let (host, port) = parse_authority(endpoint, &scheme, authority)?;
+        let port = match port {
+            None => None,
+            Some(v) => Some(v.parse::<u16>().map_err(|err| {
+                new_endpoint_error(
+                    endpoint,
+                    "endpoint port is invalid",
+                    &[("scheme", &scheme), ("host", &host), ("port", v)],
+                )
+                .set_source(err)
+            })?),
+        };
+
+        Ok(Endpoint {
+            scheme,
+            host,
+            port,
+            path: path.to_string(),
+        })
+    }
+
+    /// The scheme of endpoint, either `http` or `https`.
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    /// The host of endpoint, IPv6 literals are enclosed in `[]`.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// The port of endpoint if specified.
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    /// The path of endpoint without trailing `/`, could be empty.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The authority of endpoint like `host:port`.
+    pub fn authority(&self) -> String {
+        match self.port {
+            Some(port) => format!("{}:{}", self.host, port),
+            None => self.host.clone(),
+        }
+    }
+
+    /// Check if the host of endpoint is an IPv4 or IPv6 literal.
+    pub fn is_ip(&self) -> bool {
+        self.host.starts_with('[') || self.host.parse::<IpAddr>().is_ok()
+    }
+
+    /// Build a new endpoint with given bucket in given style.
+    ///
+    /// Returns [`ErrorKind::ConfigInvalid`] if virtual host style is used
+    /// with IP literals or bucket can't be used as a host label.
+    pub fn with_bucket(&self, bucket: &str, style: EndpointStyle) -> Result<Endpoint> {
+        let mut ep = self.clone();
+        match style {
+            EndpointStyle::Path => {
+                ep.path = format!("{}/{}", self.path, bucket);
+            }
+            EndpointStyle::VirtualHost => {
+                if self.is_ip() {
+                    return Err(new_endpoint_error(
+                        &self.to_string(),
+                        "virtual host style requires a domain endpoint, please use path style instead",
+                        &[("host", &self.host), ("bucket", bucket)],
+                    ));
+                }
+                // Dotted buckets like `a.b` are still valid host labels.
+                if bucket.split('.').any(|v| v.is_empty()) || !is_valid_host(bucket) {
+                    return Err(new_endpoint_error(
+                        &self.to_string(),
+                        "bucket can't be used in host with virtual host style",
+                        &[("host", &self.host), ("bucket", bucket)],
+                    ));
+                }
+                ep.host = format!("{bucket}.{}", self.host);
+            }
+        }
+        Ok(ep)
+    }
+}
+
+impl Display for Endpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}://{}{}", self.scheme, self.authority(), self.path)
+    }
+}
+
+/// Parse authority into host and the raw port.
+fn parse_authority<'a>(
+    endpoint: &str,
+    scheme: &str,
+    authority: &'a str,
+) -> Result<(String, Option<&'a str>)> {
+    // IPv6 literal like `[::1]:9000`.
+    if let Some(rest) = authority.strip_prefix('[') {
+        let (addr, rest) = rest.split_once(']').ok_or_else(|| {
+            new_endpoint_error(
+                endpoint,
+                "endpoint IPv6 literal is not closed",
+                &[("scheme", scheme), ("authority", authority)],
+            )
+        })?;
+        let host = parse_ipv6(endpoint, scheme, addr)?;
+        let port = match rest {
+            "" => None,
+            _ => Some(rest.strip_prefix(':').ok_or_else(|| {
+                new_endpoint_error(
+                    endpoint,
+                    "endpoint has unexpected content after IPv6 literal",
+                    &[("scheme", scheme), ("host", &host), ("rest", rest)],
+                )
+            })?),
+        };
+        return Ok((host, port));
+    }
+
+    // IPv6 literal without brackets like `::1`, port is not allowed.
+    if authority.matches(':').count() > 1 {
+        let host = parse_ipv6(endpoint, scheme, authority)?;
+        return Ok((host, None));
+    }
+
+    let (host, port) = match authority.split_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (authority, None),
+    };
+    if host.is_empty() || !is_valid_host(host) {
+        return Err(new_endpoint_error(
+            endpoint,
+            "endpoint host is invalid",
+            &[("scheme", scheme), ("host", host)],
+        ));
+    }
+    Ok((host.to_ascii_lowercase(), port))
+}
+
+/// Parse IPv6 address with optional zone and format it as `[addr%25zone]`
+/// which follows RFC 6874.
+fn parse_ipv6(endpoint: &str, scheme: &str, addr: &str) -> Result<String> {
+    let (ip, zone) = match addr.split_once('%') {
+        Some((ip, zone)) => (ip, Some(zone.strip_prefix("25").unwrap_or(zone))),
+        None => (addr, None),
+    };
+    let ip = ip.parse::<Ipv6Addr>().map_err(|err| {
+        new_endpoint_error(
+            endpoint,
+            "endpoint IPv6 literal is invalid",
+            &[("scheme", scheme), ("ipv6", ip)],
+        )
+        .set_source(err)
+    })?;
+
+    match zone {
+        Some(zone) if zone.is_empty() || !is_valid_host(zone) => Err(new_endpoint_error(
+            endpoint,
+            "endpoint IPv6 zone is invalid",
+            &[
+                ("scheme", scheme),
+                ("ipv6", &ip.to_string()),
+                ("zone", zone),
+            ],
+        )),
+        Some(zone) => Ok(format!("[{ip}%25{zone}]")),
+        None => Ok(format!("[{ip}]")),
+    }
+}
+
+fn is_valid_host(host: &str) -> bool {
+    host.bytes()
+        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_'))
+}
+
+fn new_endpoint_error(endpoint: &str, message: &'static str, parsed: &[(&str, &str)]) -> Error {
+    let parsed = parsed
+        .iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Error::new(ErrorKind::ConfigInvalid, message)
+        .with_context("endpoint", endpoint)
+        .with_context("parsed", parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_endpoint() {
+        let cases = vec![
+            ("s3.amazonaws.com", "https", "s3.amazonaws.com", None, ""),
+            (
+                "http://127.0.0.1:9000/",
+                "http",
+                "127.0.0.1",
+                Some(9000),
+                "",
+            ),
+            (
+                "HTTP://Minio.Local:9000",
+                "http",
+                "minio.local",
+                Some(9000),
+                "",
+            ),
+            ("[::1]:9000", "https", "[::1]", Some(9000), ""),
+            ("::1", "https", "[::1]", None, ""),
+            (
+                "http://[fe80::1%eth0]",
+                "http",
+                "[fe80::1%25eth0]",
+                None,
+                "",
+            ),
+            (
+                "http://[fe80::1%25eth0]:10000/devstoreaccount1",
+                "http",
+                "[fe80::1%25eth0]",
+                Some(10000),
+                "/devstoreaccount1",
+            ),
+            (
+                "bucket.vpce-1a2b3c4d-5e6f.s3.us-east-1.vpce.amazonaws.com",
+                "https",
+                "bucket.vpce-1a2b3c4d-5e6f.s3.us-east-1.vpce.amazonaws.com",
+                None,
+                "",
+            ),
+        ];
+
+        for (input, scheme, host, port, path) in cases {
+            let ep = Endpoint::parse(input).expect("endpoint must be valid");
+            assert_eq!(ep.scheme(), scheme, "{input}");
+            assert_eq!(ep.host(), host, "{input}");
+            assert_eq!(ep.port(), port, "{input}");
+            assert_eq!(ep.path(), path, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_endpoint() {
+        for input in [
+            "",
+            "ftp://example.com",
+            "http://user@example.com",
+            "http://example.com:port",
+            "http://[::1",
+            "http://[::1]9000",
+            "http://[::g]",
+            "http://exa mple.com",
+        ] {
+            let err = Endpoint::parse(input).expect_err("endpoint must be invalid");
+            assert_eq!(err.kind(), ErrorKind::ConfigInvalid, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_endpoint_with_bucket() -> Result<()> {
+        let ep = Endpoint::parse("http://127.0.0.1:9000")?;
+        assert_eq!(
+            ep.with_bucket("test", EndpointStyle::Path)?.to_string(),
+            "http://127.0.0.1:9000/test"
+        );
+        assert!(ep.with_bucket("test", EndpointStyle::VirtualHost).is_err());
+
+        let ep = Endpoint::parse("https://s3.us-east-2.amazonaws.com")?;
+        assert_eq!(
+            ep.with_bucket("test", EndpointStyle::VirtualHost)?
+                .to_string(),
+            "https://test.s3.us-east-2.amazonaws.com"
+        );
+        assert_eq!(
+            ep.with_bucket("a.b", EndpointStyle::VirtualHost)?
+                .to_string(),
+            "https://a.b.s3.us-east-2.amazonaws.com"
+        );
+        assert!(ep.with_bucket("a..b", EndpointStyle::VirtualHost).is_err());
+        assert!(ep.with_bucket("", EndpointStyle::VirtualHost).is_err());
+
+        // Bucket is always prepended even if host starts with the same label.
+        let ep = Endpoint::parse("https://test.example.com")?;
+        assert_eq!(
+            ep.with_bucket("test", EndpointStyle::VirtualHost)?
+                .to_string(),
+            "https://test.test.example.com"
+        );
+        Ok(())
+    }
+}
@@ -16,8 +16,12 @@
 // under the License.
 
 use http::response::Parts;
+use http::StatusCode;
 use http::Uri;
 
+use super::parse_retry_after;
+use super::ClientRequestId;
+
 use crate::Error;
 use crate::ErrorKind;
 
@@ -52,10 +56,38 @@ pub fn new_request_sign_error(err: anyhow::Error) -> Error {
 ///
 /// - remove sensitive or useless headers from parts.
 /// - fetch uri if parts extensions contains `Uri`.
+/// - mark `401 Unauthorized` responses as [`ErrorKind::Unauthenticated`].
+/// - mark throttled responses as [`ErrorKind::RateLimited`] with the
+///   parsed `Retry-After`.
 pub fn with_error_response_context(mut err: Error, mut parts: Parts) -> Error {
     if let Some(uri) = parts.extensions.get::<Uri>() {
         err = err.with_context("uri", uri.to_string());
     }
+    err = err.with_context("status", parts.status.as_str());
+    if let Some(id) = parts.extensions.get::<ClientRequestId>() {
+        err = err.with_context("client_request_id", id.id());
+    }
+
+    // 401 always means the credentials are not accepted.
+    if parts.status == StatusCode::UNAUTHORIZED
+        && matches!(
+            err.kind(),
+            ErrorKind::Unexpected | ErrorKind::PermissionDenied
+        )
+    {
+        err = err.with_kind(ErrorKind::Unauthenticated);
+    }
+
+    // Only treat 503 as throttling if service asks us to wait.
+    let retry_after = parse_retry_after(&parts.headers);
+    let throttled = parts.status == StatusCode::TOO_MANY_REQUESTS
+        || (parts.status == StatusCode::SERVICE_UNAVAILABLE && retry_after.is_some());
+    if throttled && err.kind() == ErrorKind::Unexpected {
+        err = err.with_kind(ErrorKind::RateLimited).set_temporary();
+    }
+    if let Some(dur) = retry_after {
+        err = err.with_retry_after(dur);
+    }
 
     // The following headers may contains sensitive information.
     parts.headers.remove("Set-Cookie");
@@ -15,10 +15,13 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::time::Duration;
+
 use base64::engine::general_purpose;
 use base64::Engine;
 use chrono::DateTime;
 use chrono::Utc;
+use flagset::FlagSet;
 use http::header::HeaderName;
 use http::header::CACHE_CONTROL;
 use http::header::CONTENT_DISPOSITION;
@@ -28,6 +31,7 @@ use http::header::CONTENT_TYPE;
 use http::header::ETAG;
 use http::header::LAST_MODIFIED;
 use http::header::LOCATION;
+use http::header::RETRY_AFTER;
 use http::HeaderMap;
 use http::HeaderValue;
 use md5::Digest;
@@ -37,6 +41,7 @@ use crate::EntryMode;
 use crate::Error;
 use crate::ErrorKind;
 use crate::Metadata;
+use crate::Metakey;
 use crate::Result;
 
 /// Parse redirect location from header map
@@ -101,6 +106,22 @@ pub fn parse_content_length(headers: &HeaderMap) -> Result<Option<u64>> {
     }
 }
 
+/// Parse the value of given header as str.
+pub fn parse_header_to_str<'a>(headers: &'a HeaderMap, name: &str) -> Result<Option<&'a str>> {
+    match headers.get(name) {
+        None => Ok(None),
+        Some(v) => Ok(Some(v.to_str().map_err(|e| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "header value is not valid utf-8 string",
+            )
+            .with_operation("http_util::parse_header_to_str")
+            .with_context("header", name)
+            .set_source(e)
+        })?)),
+    }
+}
+
 /// Parse content md5 from header map.
 pub fn parse_content_md5(headers: &HeaderMap) -> Result<Option<&str>> {
     match headers.get(HeaderName::from_static("content-md5")) {
@@ -169,6 +190,34 @@ pub fn parse_last_modified(headers: &HeaderMap) -> Result<Option<DateTime<Utc>>>
     }
 }
 
+/// Parse the time that service asks clients to wait before retrying.
+///
+/// Headers will be checked in order:
+///
+/// - `x-ms-retry-after-ms` and `retry-after-ms`: in milliseconds, returned by azure services.
+/// - `Retry-After`: in seconds or a HTTP date.
+///
+/// Returns `None` if no valid header found, since this is only a hint.
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    for name in ["x-ms-retry-after-ms", "retry-after-ms"] {
+        let ms = headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok());
+        if let Some(ms) = ms {
+            return Some(Duration::from_millis(ms));
+        }
+    }
+
+    let v = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = v.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = parse_datetime_from_rfc2822(v).ok()?;
+    // Date in the past means retry now.
+    Some((at - Utc::now()).to_std().unwrap_or_default())
+}
+
 /// Parse etag from header map.
 pub fn parse_etag(headers: &HeaderMap) -> Result<Option<&str>> {
     match headers.get(ETAG) {
@@ -207,6 +256,24 @@ pub fn parse_content_disposition(headers: &HeaderMap) -> Result<Option<&str>> {
 /// headers. If services have their own logic, they should update the parsed
 /// metadata on demand.
 pub fn parse_into_metadata(path: &str, headers: &HeaderMap) -> Result<Metadata> {
+    parse_into_metadata_with_fields(path, headers, None)
+}
+
+/// parse_into_metadata_with_fields is the same as [`parse_into_metadata`]
+/// but only parses given fields, `None` means all fields.
+///
+/// Services should use this with [`OpStat::fields`] to skip parsing that
+/// users don't need.
+pub fn parse_into_metadata_with_fields(
+    path: &str,
+    headers: &HeaderMap,
+    fields: Option<FlagSet<Metakey>>,
+) -> Result<Metadata> {
+    let need = |field: Metakey| match fields {
+        None => true,
+        Some(fields) => fields.contains(Metakey::Complete) || fields.contains(field),
+    };
+
     let mode = if path.ends_with('/') {
         EntryMode::DIR
     } else {
@@ -214,36 +281,52 @@ pub fn parse_into_metadata(path: &str, headers: &HeaderMap) -> Result<Metadata>
     };
     let mut m = Metadata::new(mode);
 
-    if let Some(v) = parse_cache_control(headers)? {
-        m.set_cache_control(v);
+    if need(Metakey::CacheControl) {
+        if let Some(v) = parse_cache_control(headers)? {
+            m.set_cache_control(v);
+        }
     }
 
-    if let Some(v) = parse_content_length(headers)? {
-        m.set_content_length(v);
+    if need(Metakey::ContentLength) {
+        if let Some(v) = parse_content_length(headers)? {
+            m.set_content_length(v);
+        }
     }
 
-    if let Some(v) = parse_content_type(headers)? {
-        m.set_content_type(v);
+    if need(Metakey::ContentType) {
+        if let Some(v) = parse_content_type(headers)? {
+            m.set_content_type(v);
+        }
     }
 
-    if let Some(v) = parse_content_range(headers)? {
-        m.set_content_range(v);
+    if need(Metakey::ContentRange) {
+        if let Some(v) = parse_content_range(headers)? {
+            m.set_content_range(v);
+        }
     }
 
-    if let Some(v) = parse_etag(headers)? {
-        m.set_etag(v);
+    if need(Metakey::Etag) {
+        if let Some(v) = parse_etag(headers)? {
+            m.set_etag(v);
+        }
     }
 
-    if let Some(v) = parse_content_md5(headers)? {
-        m.set_content_md5(v);
+    if need(Metakey::ContentMd5) {
+        if let Some(v) = parse_content_md5(headers)? {
+            m.set_content_md5(v);
+        }
     }
 
-    if let Some(v) = parse_last_modified(headers)? {
-        m.set_last_modified(v);
+    if need(Metakey::LastModified) {
+        if let Some(v) = parse_last_modified(headers)? {
+            m.set_last_modified(v);
+        }
     }
 
-    if let Some(v) = parse_content_disposition(headers)? {
-        m.set_content_disposition(v);
+    if need(Metakey::ContentDisposition) {
+        if let Some(v) = parse_content_disposition(headers)? {
+            m.set_content_disposition(v);
+        }
     }
 
     Ok(m)
@@ -257,6 +340,25 @@ pub fn format_content_md5(bs: &[u8]) -> String {
     general_purpose::STANDARD.encode(hasher.finalize())
 }
 
+/// format content md5 header by given body.
+///
+/// Returns `None` for stream body whose content is unknown before sending.
+pub fn format_content_md5_of_body(body: &AsyncBody) -> Option<String> {
+    let mut hasher = md5::Md5::new();
+    match body {
+        AsyncBody::Empty => {}
+        AsyncBody::Bytes(bs) => hasher.update(bs),
+        AsyncBody::ChunkedBytes(bs) => {
+            for chunk in oio::WriteBuf::vectored_bytes(bs, bs.len()) {
+                hasher.update(&chunk);
+            }
+        }
+        AsyncBody::Stream(_) => return None,
+    }
+
+    Some(general_purpose::STANDARD.encode(hasher.finalize()))
+}
+
 /// format authorization header by basic auth.
 ///
 /// # Errors
@@ -303,10 +405,40 @@ pub fn build_header_value(v: &str) -> Result<HeaderValue> {
     })
 }
 
+/// Build header value from given secret, the value will be marked as
+/// sensitive so that it won't be printed in `Debug`.
+pub fn build_sensitive_header_value(v: &str) -> Result<HeaderValue> {
+    let mut value = build_header_value(v)?;
+    value.set_sensitive(true);
+    Ok(value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_into_metadata_with_fields() -> Result<()> {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_static("42"));
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+        headers.insert(ETAG, HeaderValue::from_static("\"abc\""));
+
+        let meta = parse_into_metadata_with_fields(
+            "test",
+            &headers,
+            Some(Metakey::Mode | Metakey::ContentLength),
+        )?;
+        assert_eq!(meta.content_length(), 42);
+        assert!(!meta.metakey().contains(Metakey::ContentType));
+        assert!(!meta.metakey().contains(Metakey::Etag));
+
+        let meta = parse_into_metadata("test", &headers)?;
+        assert_eq!(meta.content_type(), Some("text/plain"));
+        assert_eq!(meta.etag(), Some("\"abc\""));
+        Ok(())
+    }
+
     /// Test cases is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_DeleteObjects.html
     #[test]
     fn test_format_content_md5() {
@@ -329,6 +461,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_format_content_md5_of_body() {
+        let expected = format_content_md5(b"Hello, World!");
+
+        let body = AsyncBody::Bytes(bytes::Bytes::from("Hello, World!"));
+        assert_eq!(format_content_md5_of_body(&body), Some(expected.clone()));
+
+        let body = AsyncBody::ChunkedBytes(oio::ChunkedBytes::from_vec(vec![
+            bytes::Bytes::from("Hello, "),
+            bytes::Bytes::from("World!"),
+        ]));
+        assert_eq!(format_content_md5_of_body(&body), Some(expected));
+
+        let body = AsyncBody::Stream(Box::new(()));
+        assert_eq!(format_content_md5_of_body(&body), None);
+    }
+
     /// Test cases is borrowed from
     ///
     /// - RFC2617: https://datatracker.ietf.org/doc/html/rfc2617#section-2
@@ -367,4 +516,25 @@ mod tests {
             assert_eq!(actual, expected)
         }
     }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(120)));
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
+
+        headers.insert("x-ms-retry-after-ms", HeaderValue::from_static("1500"));
+        assert_eq!(
+            parse_retry_after(&headers),
+            Some(Duration::from_millis(1500))
+        );
+    }
 }
@@ -24,29 +24,54 @@
 
 mod client;
 pub use client::HttpClient;
+pub use client::HttpClientBuilder;
+pub use client::HttpClientStats;
+
+#[cfg(feature = "http-tower")]
+mod service;
+
+mod compression;
+pub use compression::with_accept_encoding;
 
 mod body;
 pub use body::AsyncBody;
 pub use body::IncomingAsyncBody;
 
+mod signer;
+pub use signer::RequestSign;
+pub use signer::RequestSigner;
+
 mod header;
 pub use header::build_header_value;
+pub use header::build_sensitive_header_value;
 pub use header::format_authorization_by_basic;
 pub use header::format_authorization_by_bearer;
 pub use header::format_content_md5;
+pub use header::format_content_md5_of_body;
 pub use header::parse_content_disposition;
 pub use header::parse_content_length;
 pub use header::parse_content_md5;
 pub use header::parse_content_range;
 pub use header::parse_content_type;
 pub use header::parse_etag;
+pub use header::parse_header_to_str;
 pub use header::parse_into_metadata;
+pub use header::parse_into_metadata_with_fields;
 pub use header::parse_last_modified;
 pub use header::parse_location;
+pub use header::parse_retry_after;
+
+mod request_id;
+pub use request_id::ClientRequestId;
+
+mod endpoint;
+pub use endpoint::Endpoint;
+pub use endpoint::EndpointStyle;
 
 mod uri;
 pub use uri::percent_decode_path;
 pub use uri::percent_encode_path;
+pub use uri::percent_encode_tags;
 
 mod error;
 pub use error::new_request_build_error;
@@ -0,0 +1,125 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::future::Future;
+
+use http::header::HeaderName;
+use http::HeaderMap;
+use http::HeaderValue;
+
+tokio::task_local! {
+    static CLIENT_REQUEST_ID: ClientRequestId;
+}
+
+/// ClientRequestId is the id that [`HttpClient`][super::HttpClient] will
+/// attach to all requests sent within [`ClientRequestId::scope`].
+///
+/// The id will be sent in the given header (like `x-ms-client-request-id`)
+/// so that it can be found in server side logs, and added into the context
+/// of errors as `client_request_id`.
+///
+/// Users should use [`RequestIdLayer`][crate::layers::RequestIdLayer]
+/// instead of this type directly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientRequestId {
+    header: HeaderName,
+    id: String,
+}
+
+impl ClientRequestId {
+    /// Create a new client request id that will be sent in given header.
+    pub fn new(header: HeaderName, id: impl Into<String>) -> Self {
+        Self {
+            header,
+            id: id.into(),
+        }
+    }
+
+    /// Get the header name of this id.
+    pub fn header(&self) -> &HeaderName {
+        &self.header
+    }
+
+    /// Get the value of this id.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the client request id of current scope.
+    pub fn current() -> Option<Self> {
+        CLIENT_REQUEST_ID.try_with(|v| v.clone()).ok()
+    }
+
+    /// Run given future within the scope of this id.
+    pub async fn scope<F: Future>(self, fut: F) -> F::Output {
+        CLIENT_REQUEST_ID.scope(self, fut).await
+    }
+
+    /// Run given function within the scope of this id.
+    ///
+    /// This is useful while polling readers and writers.
+    pub fn sync_scope<R>(self, f: impl FnOnce() -> R) -> R {
+        CLIENT_REQUEST_ID.sync_scope(self, f)
+    }
+
+    /// Insert the id of current scope into given headers.
+    ///
+    /// Services that sign headers must call this before signing, otherwise
+    /// the header inserted by [`HttpClient`][super::HttpClient] will break
+    /// the signature.
+    pub fn insert_current(headers: &mut HeaderMap) {
+        if let Some(id) = Self::current() {
+            id.insert_into(headers);
+        }
+    }
+
+    /// Insert this id into given headers if it's not set yet.
+    pub(super) fn insert_into(&self, headers: &mut HeaderMap) {
+        if headers.contains_key(&self.header) {
+            return;
+        }
+        if let Ok(v) = HeaderValue::from_str(&self.id) {
+            headers.insert(self.header.clone(), v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_client_request_id_scope() {
+        assert_eq!(ClientRequestId::current(), None);
+
+        let id = ClientRequestId::new(HeaderName::from_static("x-ms-client-request-id"), "abc");
+        let current = id.clone().scope(async { ClientRequestId::current() }).await;
+        assert_eq!(current, Some(id.clone()));
+
+        let mut headers = HeaderMap::new();
+        id.insert_into(&mut headers);
+        assert_eq!(headers["x-ms-client-request-id"], "abc");
+
+        let mut headers = HeaderMap::new();
+        ClientRequestId::insert_current(&mut headers);
+        assert!(headers.is_empty());
+        id.clone()
+            .scope(async { ClientRequestId::insert_current(&mut headers) })
+            .await;
+        assert_eq!(headers["x-ms-client-request-id"], "abc");
+    }
+}
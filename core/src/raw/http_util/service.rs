@@ -0,0 +1,198 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use ::tower::util::BoxService;
+use ::tower::BoxError;
+use ::tower::Layer;
+use ::tower::Service;
+use ::tower::ServiceExt;
+use futures::future::BoxFuture;
+use futures::lock::Mutex;
+use futures::FutureExt;
+use http::Request;
+use http::Response;
+
+use super::AsyncBody;
+use super::HttpClient;
+use super::IncomingAsyncBody;
+use crate::*;
+
+type BoxedService = BoxService<Request<AsyncBody>, Response<IncomingAsyncBody>, BoxError>;
+
+/// SharedService holds the tower service built by [`HttpClient::with_layer`].
+///
+/// All clones of the client share the same service instance, so that
+/// stateful middlewares like `rate_limit` see every request. The lock is
+/// only held while waiting for the service to be ready and dispatching the
+/// request, responses are awaited without it.
+#[derive(Clone)]
+pub(super) struct SharedService(Arc<Mutex<BoxedService>>);
+
+impl SharedService {
+    pub(super) async fn call(
+        &self,
+        req: Request<AsyncBody>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let fut = {
+            let mut svc = self.0.lock().await;
+            svc.ready().await.map_err(format_box_error)?;
+            svc.call(req)
+        };
+        fut.await.map_err(format_box_error)
+    }
+}
+
+impl HttpClient {
+    /// Send all requests of this client through given tower layer, so that
+    /// standard tower middlewares like `load_shed`, `buffer`, `rate_limit`
+    /// and existing observability stacks can be applied to the HTTP layer.
+    ///
+    /// Errors returned by the layer will be converted into
+    /// [`ErrorKind::Unexpected`] with temporary status so that they could be
+    /// retried by `RetryLayer`, unless they are OpenDAL's [`Error`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opendal::raw::HttpClient;
+    /// use tower::ServiceBuilder;
+    ///
+    /// # fn main() -> opendal::Result<()> {
+    /// let client = HttpClient::new()?.with_layer(
+    ///     ServiceBuilder::new()
+    ///         .load_shed()
+    ///         .concurrency_limit(64)
+    ///         .into_inner(),
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_layer<L>(self, layer: L) -> Self
+    where
+        L: Layer<HttpClient>,
+        L::Service:
+            Service<Request<AsyncBody>, Response = Response<IncomingAsyncBody>> + Send + 'static,
+        <L::Service as Service<Request<AsyncBody>>>::Error: Into<BoxError>,
+        <L::Service as Service<Request<AsyncBody>>>::Future: Send + 'static,
+    {
+        // The inner client must send requests directly.
+        let inner = HttpClient {
+            service: None,
+            ..self
+        };
+        let svc = BoxService::new(layer.layer(inner.clone()).map_err(Into::into));
+
+        HttpClient {
+            service: Some(SharedService(Arc::new(Mutex::new(svc)))),
+            ..inner
+        }
+    }
+}
+
+/// HttpClient is a tower service so that it can be used as the innermost
+/// service of tower stacks.
+impl Service<Request<AsyncBody>> for HttpClient {
+    type Response = Response<IncomingAsyncBody>;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<Response<IncomingAsyncBody>>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<AsyncBody>) -> Self::Future {
+        let client = self.clone();
+        async move { client.send(req).await }.boxed()
+    }
+}
+
+fn format_box_error(err: BoxError) -> Error {
+    match err.downcast::<Error>() {
+        Ok(err) => *err,
+        Err(err) => Error::new(ErrorKind::Unexpected, "http service returns error")
+            .with_operation("http_util::Client::send_async")
+            .set_temporary()
+            .set_source(anyhow::anyhow!(err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use ::tower::service_fn;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_with_layer() -> Result<()> {
+        let called = Arc::new(AtomicUsize::new(0));
+        let called_in_layer = called.clone();
+        let layer = ::tower::layer::layer_fn(move |_: HttpClient| {
+            let called = called_in_layer.clone();
+            service_fn(move |_: Request<AsyncBody>| {
+                called.fetch_add(1, Ordering::SeqCst);
+                async {
+                    Err::<Response<IncomingAsyncBody>, _>(Error::new(
+                        ErrorKind::RateLimited,
+                        "overloaded",
+                    ))
+                }
+            })
+        });
+
+        let client = HttpClient::new()?.with_layer(layer);
+        let req = Request::get("http://127.0.0.1:1")
+            .body(AsyncBody::Empty)
+            .expect("request must be valid");
+        let err = client.send(req).await.expect_err("must fail");
+
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        assert_eq!(called.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_with_layer_shares_state() -> Result<()> {
+        let client = HttpClient::new()?.with_layer(::tower::limit::RateLimitLayer::new(
+            1,
+            std::time::Duration::from_secs(3600),
+        ));
+        let new_req = || {
+            Request::get("http://127.0.0.1:1")
+                .body(AsyncBody::Empty)
+                .expect("request must be valid")
+        };
+
+        // The first request consumes the only permit.
+        let _ = client.send(new_req()).await;
+
+        // Clones of client must be limited by the same rate limiter.
+        let res = tokio::time::timeout(
+            std::time::Duration::from_millis(100),
+            client.clone().send(new_req()),
+        )
+        .await;
+        assert!(res.is_err(), "request must be rate limited");
+        Ok(())
+    }
+}
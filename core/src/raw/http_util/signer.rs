@@ -0,0 +1,113 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use flagset::FlagSet;
+use http::Request;
+
+use super::AsyncBody;
+use crate::*;
+
+/// RequestSign signs requests with the credentials and config of a service,
+/// so that applications could send requests that OpenDAL doesn't model
+/// (like unusual sub-resources) without loading credentials again.
+///
+/// Services that support signing will expose their signer via
+/// [`OperatorInfo::signer`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use http::Request;
+/// use opendal::raw::AsyncBody;
+/// use opendal::Operator;
+/// use opendal::Result;
+///
+/// async fn test(op: Operator) -> Result<()> {
+///     let signer = op.info().signer().expect("service must support signing");
+///
+///     let mut req = Request::get("https://bucket.s3.amazonaws.com/?object-lock")
+///         .body(AsyncBody::Empty)
+///         .expect("request must be valid");
+///     signer.sign(&mut req).await?;
+///     Ok(())
+/// }
+/// ```
+#[async_trait]
+pub trait RequestSign: Debug + Send + Sync + 'static {
+    /// Sign the request in headers.
+    async fn sign(&self, req: &mut Request<AsyncBody>) -> Result<()>;
+
+    /// Sign the request in query so that it could be sent by others before
+    /// expired.
+    async fn sign_query(&self, req: &mut Request<AsyncBody>, expire: Duration) -> Result<()> {
+        let _ = (req, expire);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "signer doesn't support signing in query",
+        ))
+    }
+
+    /// Mint a credential that only allows given operations on the path
+    /// before expired, see [`Operator::delegate`].
+    ///
+    /// The path is an absolute path without leading `/`, paths end with
+    /// `/` are prefixes.
+    async fn delegate(
+        &self,
+        path: &str,
+        scope: FlagSet<DelegateScope>,
+        expire: Duration,
+    ) -> Result<DelegatedCredential> {
+        let _ = (path, scope, expire);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "signer doesn't support minting delegated credentials",
+        ))
+    }
+
+    /// Replace the credential used for signing, see
+    /// [`Operator::update_credential`].
+    async fn update_credential(&self, cred: Credential) -> Result<()> {
+        let _ = cred;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "signer doesn't support updating credential",
+        ))
+    }
+
+    /// Replace the api version sent with requests, see
+    /// [`Operator::update_api_version`].
+    fn update_api_version(&self, version: &str) -> Result<()> {
+        let _ = version;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "signer doesn't support updating api version",
+        ))
+    }
+}
+
+/// RequestSigner is the shared [`RequestSign`].
+pub type RequestSigner = Arc<dyn RequestSign>;
@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+
 use percent_encoding::percent_decode_str;
 use percent_encoding::utf8_percent_encode;
 use percent_encoding::AsciiSet;
@@ -48,6 +50,26 @@ pub fn percent_encode_path(path: &str) -> String {
     utf8_percent_encode(path, &PATH_ENCODE_SET).to_string()
 }
 
+/// percent_encode_tags will encode tags into url query format like
+/// `k1=v1&k2=v2`, which is used by `x-amz-tagging` of s3 and `x-ms-tags` of
+/// azblob.
+///
+/// Tags are sorted by key so that the output is stable.
+pub fn percent_encode_tags(tags: &HashMap<String, String>) -> String {
+    let mut tags: Vec<_> = tags.iter().collect();
+    tags.sort();
+    tags.into_iter()
+        .map(|(k, v)| {
+            format!(
+                "{}={}",
+                utf8_percent_encode(k, NON_ALPHANUMERIC),
+                utf8_percent_encode(v, NON_ALPHANUMERIC)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
 /// percent_decode_path will do percent decoding for http decode path.
 ///
 /// If the input is not percent encoded or not valid utf8, return the input.
@@ -91,6 +113,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_percent_encode_tags() {
+        let tags = HashMap::from([
+            ("project".to_string(), "open dal".to_string()),
+            ("env".to_string(), "a&b=c".to_string()),
+        ]);
+
+        assert_eq!(
+            percent_encode_tags(&tags),
+            "env=a%26b%3Dc&project=open%20dal"
+        );
+    }
+
     #[test]
     fn test_percent_decode_path() {
         let cases = vec![
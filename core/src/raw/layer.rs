@@ -179,6 +179,43 @@ pub trait LayeredAccessor: Send + Sync + Debug + Unpin + 'static {
         self.inner().presign(path, args).await
     }
 
+    async fn create_symlink(
+        &self,
+        target: &str,
+        path: &str,
+        args: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        self.inner().create_symlink(target, path, args).await
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        self.inner().set_permissions(path, args).await
+    }
+
+    async fn create_snapshot(
+        &self,
+        path: &str,
+        args: OpCreateSnapshot,
+    ) -> Result<RpCreateSnapshot> {
+        self.inner().create_snapshot(path, args).await
+    }
+
+    async fn lease(&self, path: &str, args: OpLease) -> Result<RpLease> {
+        self.inner().lease(path, args).await
+    }
+
+    async fn list_roots(&self, args: OpListRoots) -> Result<RpListRoots> {
+        self.inner().list_roots(args).await
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        self.inner().concat(path, args).await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.inner().blocking_create_dir(path, args)
     }
@@ -204,6 +241,23 @@ pub trait LayeredAccessor: Send + Sync + Debug + Unpin + 'static {
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)>;
+
+    fn blocking_create_symlink(
+        &self,
+        target: &str,
+        path: &str,
+        args: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        self.inner().blocking_create_symlink(target, path, args)
+    }
+
+    fn blocking_set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        self.inner().blocking_set_permissions(path, args)
+    }
 }
 
 #[async_trait]
@@ -259,6 +313,43 @@ impl<L: LayeredAccessor> Accessor for L {
         (self as &L).presign(path, args).await
     }
 
+    async fn create_symlink(
+        &self,
+        target: &str,
+        path: &str,
+        args: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        (self as &L).create_symlink(target, path, args).await
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        (self as &L).set_permissions(path, args).await
+    }
+
+    async fn create_snapshot(
+        &self,
+        path: &str,
+        args: OpCreateSnapshot,
+    ) -> Result<RpCreateSnapshot> {
+        (self as &L).create_snapshot(path, args).await
+    }
+
+    async fn lease(&self, path: &str, args: OpLease) -> Result<RpLease> {
+        (self as &L).lease(path, args).await
+    }
+
+    async fn list_roots(&self, args: OpListRoots) -> Result<RpListRoots> {
+        (self as &L).list_roots(args).await
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        (self as &L).concat(path, args).await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         (self as &L).blocking_create_dir(path, args)
     }
@@ -290,6 +381,23 @@ impl<L: LayeredAccessor> Accessor for L {
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
         (self as &L).blocking_list(path, args)
     }
+
+    fn blocking_create_symlink(
+        &self,
+        target: &str,
+        path: &str,
+        args: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        (self as &L).blocking_create_symlink(target, path, args)
+    }
+
+    fn blocking_set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        (self as &L).blocking_set_permissions(path, args)
+    }
 }
 
 #[cfg(test)]
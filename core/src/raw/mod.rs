@@ -62,6 +62,9 @@ pub use tokio_util::*;
 mod std_io_util;
 pub use std_io_util::*;
 
+mod credential;
+pub use credential::CredentialCache;
+
 // Expose as a pub mod to avoid confusing.
 pub mod adapters;
 pub mod oio;
@@ -23,3 +23,6 @@ pub use write_buf::WriteBuf;
 
 mod adaptive;
 pub use adaptive::AdaptiveBuf;
+
+mod spill_buf;
+pub use spill_buf::SpillBuf;
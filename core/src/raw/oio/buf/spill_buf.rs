@@ -0,0 +1,308 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fs::File;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::PathBuf;
+
+use bytes::Bytes;
+
+use crate::raw::*;
+use crate::*;
+
+/// The max size of chunk that read from spilled data.
+const SPILL_READ_SIZE: usize = 64 * 1024;
+
+/// SpillBuf is an append-only buffer that keeps data in memory until
+/// `threshold` is reached, the rest will be spilled out.
+///
+/// Spilled data will be written into a file under the temp dir by default,
+/// or into given [`BlockingOperator`] which must support append. Spilled
+/// data will be removed while the buffer is dropped.
+pub struct SpillBuf {
+    threshold: usize,
+    op: Option<BlockingOperator>,
+
+    memory: Vec<Bytes>,
+    memory_size: u64,
+    spill: Option<Spill>,
+}
+
+enum Spill {
+    Local {
+        path: PathBuf,
+        file: File,
+        size: u64,
+    },
+    Operator {
+        op: BlockingOperator,
+        path: String,
+        size: u64,
+    },
+}
+
+impl Drop for Spill {
+    fn drop(&mut self) {
+        match self {
+            Spill::Local { path, .. } => {
+                let _ = std::fs::remove_file(path);
+            }
+            Spill::Operator { op, path, .. } => {
+                let (op, path) = (op.clone(), std::mem::take(path));
+                let cleanup = move || {
+                    if let Err(err) = op.delete(&path) {
+                        log::warn!("spill buf remove {path} failed: {err}");
+                    }
+                };
+                // Deleting via operator may block, don't stall the async
+                // runtime that drops us.
+                match tokio::runtime::Handle::try_current() {
+                    Ok(handle) => {
+                        handle.spawn_blocking(cleanup);
+                    }
+                    Err(_) => cleanup(),
+                }
+            }
+        }
+    }
+}
+
+impl Spill {
+    fn size(&self) -> u64 {
+        match self {
+            Spill::Local { size, .. } | Spill::Operator { size, .. } => *size,
+        }
+    }
+}
+
+impl Debug for SpillBuf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpillBuf")
+            .field("threshold", &self.threshold)
+            .field("memory_size", &self.memory_size)
+            .field("spilled_size", &self.spilled_size())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SpillBuf {
+    /// Create a new spill buffer that keeps at most `threshold` bytes in
+    /// memory.
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            op: None,
+            memory: vec![],
+            memory_size: 0,
+            spill: None,
+        }
+    }
+
+    /// Spill data into given operator instead of local temp dir.
+    pub fn with_operator(mut self, op: BlockingOperator) -> Self {
+        self.op = Some(op);
+        self
+    }
+
+    /// Set the max size of data kept in memory.
+    pub fn set_threshold(&mut self, threshold: usize) {
+        self.threshold = threshold;
+    }
+
+    /// Set the operator that data spilled into.
+    pub fn set_operator(&mut self, op: BlockingOperator) {
+        self.op = Some(op);
+    }
+
+    /// The total size of data in buffer.
+    pub fn len(&self) -> u64 {
+        self.memory_size + self.spilled_size()
+    }
+
+    /// Check if the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The size of data that kept in memory.
+    pub fn memory_size(&self) -> u64 {
+        self.memory_size
+    }
+
+    /// Check if pushing `n` bytes will spill them out.
+    pub fn will_spill(&self, n: usize) -> bool {
+        self.spill.is_some() || self.memory_size + n as u64 > self.threshold as u64
+    }
+
+    /// Remove all data in buffer, spilled data will be removed too.
+    pub fn clear(&mut self) {
+        self.memory.clear();
+        self.memory_size = 0;
+        self.spill = None;
+    }
+
+    /// The size of data that has been spilled.
+    pub fn spilled_size(&self) -> u64 {
+        self.spill.as_ref().map(|s| s.size()).unwrap_or_default()
+    }
+
+    /// Append data into the buffer.
+    pub fn push(&mut self, bs: Bytes) -> Result<()> {
+        if !self.will_spill(bs.len()) {
+            self.memory_size += bs.len() as u64;
+            self.memory.push(bs);
+            return Ok(());
+        }
+
+        if self.spill.is_none() {
+            self.spill = Some(self.new_spill()?);
+        }
+        match self.spill.as_mut().expect("spill must exist") {
+            Spill::Local { file, size, .. } => {
+                // Overwrite data left by a failed write.
+                file.seek(SeekFrom::Start(*size))
+                    .map_err(new_std_io_error)?;
+                file.write_all(&bs).map_err(new_std_io_error)?;
+                *size += bs.len() as u64;
+            }
+            Spill::Operator { op, path, size } => {
+                let n = bs.len() as u64;
+                op.write_with(path, bs).append(true).call()?;
+                *size += n;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a chunk of data starting from `pos`, returns empty bytes if
+    /// `pos` reaches the end.
+    pub fn read_at(&mut self, pos: u64) -> Result<Bytes> {
+        if pos < self.memory_size {
+            let mut start = 0;
+            for bs in &self.memory {
+                let end = start + bs.len() as u64;
+                if pos < end {
+                    return Ok(bs.slice((pos - start) as usize..));
+                }
+                start = end;
+            }
+        }
+
+        let Some(spill) = self.spill.as_mut() else {
+            return Ok(Bytes::new());
+        };
+        let offset = pos - self.memory_size;
+        if offset >= spill.size() {
+            return Ok(Bytes::new());
+        }
+        let n = (spill.size() - offset).min(SPILL_READ_SIZE as u64);
+
+        match spill {
+            Spill::Local { file, .. } => {
+                let mut buf = vec![0; n as usize];
+                file.seek(SeekFrom::Start(offset))
+                    .map_err(new_std_io_error)?;
+                file.read_exact(&mut buf).map_err(new_std_io_error)?;
+                Ok(buf.into())
+            }
+            Spill::Operator { op, path, .. } => {
+                let buf = op.read_with(path).range(offset..offset + n).call()?;
+                Ok(buf.into())
+            }
+        }
+    }
+
+    /// The path of local file that data spilled into.
+    #[cfg(test)]
+    pub(crate) fn spill_path(&self) -> Option<PathBuf> {
+        match &self.spill {
+            Some(Spill::Local { path, .. }) => Some(path.clone()),
+            _ => None,
+        }
+    }
+
+    fn new_spill(&self) -> Result<Spill> {
+        let name = format!("opendal-spill-{}", uuid::Uuid::new_v4());
+        match &self.op {
+            Some(op) => Ok(Spill::Operator {
+                op: op.clone(),
+                path: name,
+                size: 0,
+            }),
+            None => {
+                let path = std::env::temp_dir().join(name);
+                let file = std::fs::OpenOptions::new()
+                    .create_new(true)
+                    .read(true)
+                    .write(true)
+                    .open(&path)
+                    .map_err(new_std_io_error)?;
+                Ok(Spill::Local {
+                    path,
+                    file,
+                    size: 0,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spill_buf() -> Result<()> {
+        let mut buf = SpillBuf::new(4);
+        buf.push(Bytes::from("abc"))?;
+        buf.push(Bytes::from("def"))?;
+        buf.push(Bytes::from("ghi"))?;
+        assert_eq!(buf.len(), 9);
+        assert_eq!(buf.spilled_size(), 6);
+
+        let mut content = vec![];
+        let mut pos = 0;
+        loop {
+            let bs = buf.read_at(pos)?;
+            if bs.is_empty() {
+                break;
+            }
+            pos += bs.len() as u64;
+            content.extend_from_slice(&bs);
+        }
+        assert_eq!(content, b"abcdefghi");
+
+        let path = buf.spill_path().expect("must spill into local file");
+        assert!(path.exists());
+
+        buf.clear();
+        assert!(buf.is_empty());
+        assert!(!path.exists());
+
+        buf.push(Bytes::from("abcdefghi"))?;
+        let path = buf.spill_path().expect("must spill into local file");
+        assert!(path.exists());
+        drop(buf);
+        assert!(!path.exists());
+        Ok(())
+    }
+}
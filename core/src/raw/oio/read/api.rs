@@ -468,14 +468,10 @@ impl Iterator for dyn BlockingRead {
 /// This function is added privately by design and only valid in current
 /// context (i.e. `oio` crate). We don't want to expose this function to
 /// users.
+///
+/// We never return `io::ErrorKind::Interrupted` here: std helpers like
+/// `read_to_end` and `io::copy` will retry interrupted reads forever.
 #[inline]
 fn format_io_error(err: Error) -> io::Error {
-    let kind = match err.kind() {
-        ErrorKind::NotFound => io::ErrorKind::NotFound,
-        ErrorKind::PermissionDenied => io::ErrorKind::PermissionDenied,
-        ErrorKind::InvalidInput => io::ErrorKind::InvalidInput,
-        _ => io::ErrorKind::Interrupted,
-    };
-
-    io::Error::new(kind, err)
+    io::Error::from(err)
 }
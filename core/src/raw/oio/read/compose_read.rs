@@ -0,0 +1,61 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! `compose_read` is used to compose different readers into one type, see
+//! `compose_write` for why we need this.
+
+use std::io::SeekFrom;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Bytes;
+
+use crate::raw::*;
+use crate::*;
+
+/// TwoWaysReader is used to implement [`Read`] based on two ways.
+///
+/// Users can wrap two different readers together.
+pub enum TwoWaysReader<ONE: oio::Read, TWO: oio::Read> {
+    /// The first type for the [`TwoWaysReader`].
+    One(ONE),
+    /// The second type for the [`TwoWaysReader`].
+    Two(TWO),
+}
+
+impl<ONE: oio::Read, TWO: oio::Read> oio::Read for TwoWaysReader<ONE, TWO> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        match self {
+            Self::One(one) => one.poll_read(cx, buf),
+            Self::Two(two) => two.poll_read(cx, buf),
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        match self {
+            Self::One(one) => one.poll_seek(cx, pos),
+            Self::Two(two) => two.poll_seek(cx, pos),
+        }
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        match self {
+            Self::One(one) => one.poll_next(cx),
+            Self::Two(two) => two.poll_next(cx),
+        }
+    }
+}
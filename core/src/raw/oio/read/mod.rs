@@ -48,3 +48,10 @@ pub use std_read::StdReader;
 
 mod lazy_read;
 pub use lazy_read::LazyReader;
+
+mod compose_read;
+pub use compose_read::TwoWaysReader;
+
+mod stitch_read;
+pub use stitch_read::StitchRead;
+pub use stitch_read::StitchReader;
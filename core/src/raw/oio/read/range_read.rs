@@ -38,6 +38,13 @@ use crate::*;
 /// The `seek` operation on `RangeReader` is zero cost and purely in-memory. But calling `seek`
 /// while there is a pending read request will cancel the request and start a new one. This could
 /// add extra cost to the read operation.
+///
+/// # Etag pinning
+///
+/// If the service supports `read_with_if_match`, the etag returned by the first request will be
+/// sent as `If-Match` in all following requests (including the ones sent for retry), so bytes
+/// from a concurrently overwritten object will never be stitched together. Instead, reads will
+/// fail with [`ErrorKind::ConditionNotMatch`], which will not be retried by `RetryLayer`.
 pub struct RangeReader<A: Accessor, R> {
     acc: Arc<A>,
     path: Arc<String>,
@@ -47,6 +54,11 @@ pub struct RangeReader<A: Accessor, R> {
     size: Option<u64>,
     cur: u64,
     state: State<R>,
+
+    /// Whether the etag returned by service should be pinned.
+    pin_etag: bool,
+    /// The etag of the object that has been read.
+    etag: Option<String>,
 }
 
 enum State<R> {
@@ -83,6 +95,7 @@ where
             (None, None) => (Some(0), None),
             v => v,
         };
+        let pin_etag = acc.info().native_capability().read_with_if_match;
 
         RangeReader {
             acc,
@@ -93,6 +106,41 @@ where
             size,
             cur: 0,
             state: State::<R>::Idle,
+
+            pin_etag,
+            etag: None,
+        }
+    }
+
+    /// Build the OpRead for next read request.
+    fn next_op(&self) -> OpRead {
+        let mut op = self.op.clone();
+        // cur != 0 means we have read some data out, we should convert
+        // the op into deterministic to avoid ETag changes.
+        if self.cur != 0 {
+            op = op.into_deterministic();
+        }
+        // Pin the object we have seen if users don't specify one.
+        if op.if_match().is_none() {
+            if let Some(etag) = &self.etag {
+                op = op.with_if_match(etag);
+            }
+        }
+        // Alter OpRead with correct calculated range.
+        op.with_range(self.calculate_range())
+    }
+
+    /// Remember the etag of the object returned by read.
+    fn pin_read_etag(&mut self, rp: &RpRead) {
+        if self.pin_etag && self.etag.is_none() {
+            self.etag = rp.etag().map(|v| v.to_string());
+        }
+    }
+
+    /// Remember the etag of the object returned by stat.
+    fn pin_stat_etag(&mut self, meta: &Metadata) {
+        if self.pin_etag && self.etag.is_none() && meta.contains_metakey(Metakey::Etag) {
+            self.etag = meta.etag().map(|v| v.to_string());
         }
     }
 
@@ -150,25 +198,23 @@ where
     fn read_future(&self) -> BoxFuture<'static, Result<(RpRead, R)>> {
         let acc = self.acc.clone();
         let path = self.path.clone();
-
-        let mut op = self.op.clone();
-        // cur != 0 means we have read some data out, we should convert
-        // the op into deterministic to avoid ETag changes.
-        if self.cur != 0 {
-            op = op.into_deterministic();
-        }
-        // Alter OpRead with correct calculated range.
-        op = op.with_range(self.calculate_range());
-
-        Box::pin(async move { acc.read(&path, op).await })
+        let op = self.next_op();
+        let pinned = self.etag.clone();
+
+        Box::pin(async move {
+            acc.read(&path, op)
+                .await
+                .map_err(|err| with_pinned_etag(err, pinned))
+        })
     }
 
     fn stat_future(&self) -> BoxFuture<'static, Result<RpStat>> {
         let acc = self.acc.clone();
         let path = self.path.clone();
 
+        // Stat the same version or snapshot that we are reading.
+        let mut args = self.op.to_stat();
         // Handle if-match and if-none-match correctly.
-        let mut args = OpStat::default();
         // TODO: stat should support range to check if ETag matches.
         if self.op.range().is_full() {
             if let Some(v) = self.op.if_match() {
@@ -189,27 +235,20 @@ where
     R: oio::BlockingRead,
 {
     fn read_action(&self) -> Result<(RpRead, R)> {
-        let acc = self.acc.clone();
-        let path = self.path.clone();
+        let op = self.next_op();
 
-        let mut op = self.op.clone();
-        // cur != 0 means we have read some data out, we should convert
-        // the op into deterministic to avoid ETag changes.
-        if self.cur != 0 {
-            op = op.into_deterministic();
-        }
-        // Alter OpRead with correct calculated range.
-        op = op.with_range(self.calculate_range());
-
-        acc.blocking_read(&path, op)
+        self.acc
+            .blocking_read(&self.path, op)
+            .map_err(|err| with_pinned_etag(err, self.etag.clone()))
     }
 
     fn stat_action(&self) -> Result<RpStat> {
         let acc = self.acc.clone();
         let path = self.path.clone();
 
+        // Stat the same version or snapshot that we are reading.
+        let mut args = self.op.to_stat();
         // Handle if-match and if-none-match correctly.
-        let mut args = OpStat::default();
         // TODO: stat should support range to check if ETag matches.
         if self.op.range().is_full() {
             if let Some(v) = self.op.if_match() {
@@ -255,7 +294,9 @@ where
                     err
                 })?;
 
-                let length = rp.into_metadata().content_length();
+                let meta = rp.into_metadata();
+                self.pin_stat_etag(&meta);
+                let length = meta.content_length();
                 self.fill_range(length).map_err(|err| {
                     // If stat future returns an error, we should reset
                     // state to Idle so that we can retry it.
@@ -274,6 +315,7 @@ where
                     err
                 })?;
 
+                self.pin_read_etag(&rp);
                 // Set size if read returns size hint.
                 if let Some(size) = rp.size() {
                     if size != 0 && self.size.is_none() {
@@ -338,7 +380,9 @@ where
                     err
                 })?;
 
-                let length = rp.into_metadata().content_length();
+                let meta = rp.into_metadata();
+                self.pin_stat_etag(&meta);
+                let length = meta.content_length();
                 self.fill_range(length)?;
 
                 self.state = State::Idle;
@@ -390,7 +434,9 @@ where
                     err
                 })?;
 
-                let length = rp.into_metadata().content_length();
+                let meta = rp.into_metadata();
+                self.pin_stat_etag(&meta);
+                let length = meta.content_length();
                 self.fill_range(length)?;
 
                 self.state = State::Idle;
@@ -404,6 +450,7 @@ where
                     err
                 })?;
 
+                self.pin_read_etag(&rp);
                 // Set size if read returns size hint.
                 if let Some(size) = rp.size() {
                     if size != 0 && self.size.is_none() {
@@ -449,12 +496,15 @@ where
                 if self.offset.is_none() {
                     let rp = self.stat_action()?;
 
-                    let length = rp.into_metadata().content_length();
+                    let meta = rp.into_metadata();
+                    self.pin_stat_etag(&meta);
+                    let length = meta.content_length();
                     self.fill_range(length)?;
                 }
 
                 let (rp, r) = self.read_action()?;
 
+                self.pin_read_etag(&rp);
                 // Set size if read returns size hint.
                 if let Some(size) = rp.size() {
                     if size != 0 && self.size.is_none() {
@@ -501,7 +551,9 @@ where
                             (size as i64, n)
                         } else {
                             let rp = self.stat_action()?;
-                            let length = rp.into_metadata().content_length();
+                            let meta = rp.into_metadata();
+                            self.pin_stat_etag(&meta);
+                            let length = meta.content_length();
                             self.fill_range(length)?;
 
                             let size = self.size.expect("size must be valid after fill_range");
@@ -560,14 +612,19 @@ where
                         Err(err) => return Some(Err(err)),
                     };
 
-                    let length = rp.into_metadata().content_length();
+                    let meta = rp.into_metadata();
+                    self.pin_stat_etag(&meta);
+                    let length = meta.content_length();
                     if let Err(err) = self.fill_range(length) {
                         return Some(Err(err));
                     }
                 }
 
                 let r = match self.read_action() {
-                    Ok((_, r)) => r,
+                    Ok((rp, r)) => {
+                        self.pin_read_etag(&rp);
+                        r
+                    }
                     Err(err) => return Some(Err(err)),
                 };
                 self.state = State::Read(r);
@@ -597,6 +654,16 @@ where
     }
 }
 
+/// Add the pinned etag into error context if the object has been changed.
+fn with_pinned_etag(err: Error, pinned: Option<String>) -> Error {
+    match pinned {
+        Some(etag) if err.kind() == ErrorKind::ConditionNotMatch => err
+            .with_context("pinned_etag", etag)
+            .with_context("reason", "object has been changed while reading"),
+        _ => err,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::SeekFrom;
@@ -626,11 +693,15 @@ mod tests {
     #[derive(Debug, Clone, Default)]
     struct MockReadService {
         data: Bytes,
+        etag: Arc<std::sync::Mutex<String>>,
     }
 
     impl MockReadService {
         fn new(data: Bytes) -> Self {
-            Self { data }
+            Self {
+                data,
+                etag: Arc::new(std::sync::Mutex::new("v1".to_string())),
+            }
         }
     }
 
@@ -647,6 +718,7 @@ mod tests {
             let mut am = AccessorInfo::default();
             am.set_native_capability(Capability {
                 read: true,
+                read_with_if_match: true,
                 ..Default::default()
             });
 
@@ -654,10 +726,14 @@ mod tests {
         }
 
         async fn read(&self, _: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            let etag = self.etag.lock().unwrap().clone();
+            if matches!(args.if_match(), Some(v) if v != etag) {
+                return Err(Error::new(ErrorKind::ConditionNotMatch, "etag mismatch"));
+            }
             let bs = args.range().apply_on_bytes(self.data.clone());
 
             Ok((
-                RpRead::new(),
+                RpRead::new().with_etag(Some(etag)),
                 MockReader {
                     inner: futures::io::Cursor::new(bs.into()),
                 },
@@ -792,4 +868,31 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_read_pin_etag() -> anyhow::Result<()> {
+        let (bs, _) = gen_bytes();
+        let acc = Arc::new(MockReadService::new(bs.clone()));
+
+        let mut r = Box::new(RangeReader::new(
+            acc.clone(),
+            "x",
+            OpRead::default().with_range(BytesRange::from(..)),
+        )) as oio::Reader;
+
+        let mut buf = vec![0; 1024];
+        r.read_exact(&mut buf).await?;
+        assert_eq!(&bs[..1024], &buf, "read before overwrite");
+
+        // Object has been overwritten, reads after seek must not return data
+        // from the new object.
+        *acc.etag.lock().unwrap() = "v2".to_string();
+        r.seek(SeekFrom::Start(2048)).await?;
+        let err = r.read_exact(&mut buf).await.expect_err("must fail");
+        let err = err.into_inner().expect("must be opendal error");
+        let err = err.downcast::<Error>().expect("must be opendal error");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+        Ok(())
+    }
 }
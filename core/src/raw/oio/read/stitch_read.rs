@@ -0,0 +1,383 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp;
+use std::future::Future;
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Buf;
+use bytes::Bytes;
+use bytes::BytesMut;
+use futures::future::BoxFuture;
+
+use crate::raw::oio::ReadExt;
+use crate::raw::*;
+use crate::*;
+
+/// The default size of every sub-range, 8 MiB.
+const DEFAULT_CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+/// The default retry times of every sub-range.
+const DEFAULT_MAX_RETRIES: usize = 3;
+
+/// StitchRead is used by [`StitchReader`] to send a ranged read request.
+#[async_trait]
+pub trait StitchRead: Send + Sync + Unpin + 'static {
+    /// Send a read request of given range and return the body.
+    ///
+    /// Implementers should check the status of response and return the
+    /// parsed error if the request failed.
+    async fn read_range(&self, range: BytesRange) -> Result<IncomingAsyncBody>;
+}
+
+/// StitchReader splits a large range into sub-ranges and reads them one by
+/// one, every sub-range will be retried independently.
+///
+/// It's designed for flaky CDNs or proxies that stall or drop long-running
+/// transfers: instead of restarting the whole read, only the broken sub-range
+/// will be resumed from the last received byte.
+///
+/// - Use `with_chunk_size` to set the size of sub-ranges.
+/// - Use `with_max_retries` to set the retry times of every sub-range.
+/// - Use `with_first_byte_timeout` to set the deadline of receiving the first
+///   byte of every request, stalled requests will be retried.
+pub struct StitchReader<R: StitchRead> {
+    inner: Arc<R>,
+
+    offset: u64,
+    size: u64,
+    cur: u64,
+
+    chunk_size: u64,
+    max_retries: usize,
+    first_byte_timeout: Option<Duration>,
+
+    state: State,
+}
+
+enum State {
+    Idle,
+    Fetch(BoxFuture<'static, Result<Bytes>>),
+    Read(Bytes),
+}
+
+/// # Safety
+///
+/// We will only take `&mut Self` reference for State.
+unsafe impl Sync for State {}
+
+impl<R: StitchRead> StitchReader<R> {
+    /// Create a new StitchReader that reads `range` of an object with given
+    /// total size.
+    pub fn new(inner: R, range: BytesRange, total_size: u64) -> Self {
+        let (offset, size) = match (range.offset(), range.size()) {
+            (None, None) => (0, total_size),
+            (None, Some(size)) => {
+                let size = cmp::min(size, total_size);
+                (total_size - size, size)
+            }
+            (Some(offset), None) => (offset, total_size.saturating_sub(offset)),
+            (Some(offset), Some(size)) => {
+                (offset, cmp::min(size, total_size.saturating_sub(offset)))
+            }
+        };
+
+        Self {
+            inner: Arc::new(inner),
+            offset,
+            size,
+            cur: 0,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            max_retries: DEFAULT_MAX_RETRIES,
+            first_byte_timeout: None,
+            state: State::Idle,
+        }
+    }
+
+    /// Set the size of every sub-range.
+    ///
+    /// Default to 8 MiB.
+    pub fn with_chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = cmp::max(size, 1) as u64;
+        self
+    }
+
+    /// Set the retry times of every sub-range.
+    ///
+    /// Default to 3.
+    pub fn with_max_retries(mut self, retries: usize) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Set the deadline of receiving the first byte of every request.
+    ///
+    /// Default to no deadline.
+    pub fn with_first_byte_timeout(mut self, timeout: Duration) -> Self {
+        self.first_byte_timeout = Some(timeout);
+        self
+    }
+
+    /// The size of the range that this reader will read.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    fn fetch_future(&self) -> BoxFuture<'static, Result<Bytes>> {
+        let inner = self.inner.clone();
+        let offset = self.offset + self.cur;
+        let size = cmp::min(self.chunk_size, self.size - self.cur);
+        let max_retries = self.max_retries;
+        let timeout = self.first_byte_timeout;
+
+        Box::pin(async move {
+            let mut buf = BytesMut::with_capacity(size as usize);
+            let mut retries = 0;
+            loop {
+                match fetch_chunk(&*inner, offset, size, timeout, &mut buf).await {
+                    Ok(()) => return Ok(buf.freeze()),
+                    Err(err) if err.is_temporary() && retries < max_retries => {
+                        retries += 1;
+                        log::debug!(
+                            "stitch reader retry range {}-{} at {}: {err}",
+                            offset,
+                            offset + size,
+                            buf.len()
+                        );
+                    }
+                    Err(err) => {
+                        return Err(err
+                            .with_context("offset", offset.to_string())
+                            .with_context("size", size.to_string())
+                            .with_context("received", buf.len().to_string()))
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Fetch the rest of a sub-range into buf, returns error if the sub-range
+/// is not fully received.
+async fn fetch_chunk<R: StitchRead>(
+    inner: &R,
+    offset: u64,
+    size: u64,
+    timeout: Option<Duration>,
+    buf: &mut BytesMut,
+) -> Result<()> {
+    let received = buf.len() as u64;
+    if received >= size {
+        return Ok(());
+    }
+    let range = BytesRange::new(Some(offset + received), Some(size - received));
+
+    let first_byte = async {
+        let mut body = inner.read_range(range).await?;
+        let first = body
+            .next()
+            .await
+            .transpose()
+            .map_err(|err| err.set_temporary())?;
+        Ok::<_, Error>((body, first))
+    };
+    let (mut body, mut next) = match timeout {
+        None => first_byte.await?,
+        Some(dur) => tokio::time::timeout(dur, first_byte).await.map_err(|_| {
+            Error::new(ErrorKind::Unexpected, "first byte didn't arrive in time")
+                .with_context("timeout", format!("{dur:?}"))
+                .set_temporary()
+        })??,
+    };
+
+    while let Some(mut bs) = next {
+        let remaining = (size - buf.len() as u64) as usize;
+        if bs.len() > remaining {
+            bs.truncate(remaining);
+        }
+        buf.extend_from_slice(&bs);
+        if buf.len() as u64 >= size {
+            return Ok(());
+        }
+        next = body
+            .next()
+            .await
+            .transpose()
+            .map_err(|err| err.set_temporary())?;
+    }
+
+    Err(Error::new(
+        ErrorKind::Unexpected,
+        "response body ended before range is complete",
+    )
+    .set_temporary())
+}
+
+impl<R: StitchRead> oio::Read for StitchReader<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        loop {
+            match &mut self.state {
+                State::Idle => {
+                    if self.cur >= self.size {
+                        return Poll::Ready(Ok(0));
+                    }
+                    self.state = State::Fetch(self.fetch_future());
+                }
+                State::Fetch(fut) => {
+                    let bs = ready!(Pin::new(fut).poll(cx));
+                    match bs {
+                        Ok(bs) => self.state = State::Read(bs),
+                        Err(err) => {
+                            self.state = State::Idle;
+                            return Poll::Ready(Err(err));
+                        }
+                    }
+                }
+                State::Read(bs) => {
+                    if !bs.has_remaining() {
+                        self.state = State::Idle;
+                        continue;
+                    }
+                    let n = cmp::min(bs.remaining(), buf.len());
+                    buf[..n].copy_from_slice(&bs[..n]);
+                    bs.advance(n);
+                    self.cur += n as u64;
+                    return Poll::Ready(Ok(n));
+                }
+            }
+        }
+    }
+
+    fn poll_seek(&mut self, _: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        let (base, amt) = match pos {
+            SeekFrom::Start(n) => (0, n as i64),
+            SeekFrom::End(n) => (self.size as i64, n),
+            SeekFrom::Current(n) => (self.cur as i64, n),
+        };
+
+        let n = match base.checked_add(amt) {
+            Some(n) if n >= 0 => n as u64,
+            _ => {
+                return Poll::Ready(Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                )))
+            }
+        };
+
+        // Drop the pending or buffered sub-range if we seek out of it.
+        if n != self.cur {
+            self.state = State::Idle;
+            self.cur = n;
+        }
+        Poll::Ready(Ok(self.cur))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        loop {
+            match &mut self.state {
+                State::Idle => {
+                    if self.cur >= self.size {
+                        return Poll::Ready(None);
+                    }
+                    self.state = State::Fetch(self.fetch_future());
+                }
+                State::Fetch(fut) => {
+                    let bs = ready!(Pin::new(fut).poll(cx));
+                    match bs {
+                        Ok(bs) => self.state = State::Read(bs),
+                        Err(err) => {
+                            self.state = State::Idle;
+                            return Poll::Ready(Some(Err(err)));
+                        }
+                    }
+                }
+                State::Read(bs) => {
+                    let bs = std::mem::take(bs);
+                    self.state = State::Idle;
+                    if bs.is_empty() {
+                        continue;
+                    }
+                    self.cur += bs.len() as u64;
+                    return Poll::Ready(Some(Ok(bs)));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use futures::stream;
+
+    use super::*;
+
+    /// MockStitchRead returns a truncated body for the first request.
+    struct MockStitchRead {
+        content: Bytes,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl StitchRead for MockStitchRead {
+        async fn read_range(&self, range: BytesRange) -> Result<IncomingAsyncBody> {
+            let mut bs = range.apply_on_bytes(self.content.clone());
+            if self.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                bs.truncate(bs.len() / 2);
+            }
+            let size = bs.len() as u64;
+            Ok(IncomingAsyncBody::new(
+                Box::new(oio::into_stream(stream::iter(vec![Ok(bs)]))),
+                Some(size),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stitch_reader() -> Result<()> {
+        let content = Bytes::from((0..100u8).collect::<Vec<_>>());
+        let inner = MockStitchRead {
+            content: content.clone(),
+            calls: AtomicUsize::new(0),
+        };
+
+        let mut r = StitchReader::new(inner, BytesRange::from(10..90), 100).with_chunk_size(30);
+        let mut buf = Vec::new();
+        r.read_to_end(&mut buf).await?;
+        assert_eq!(buf, &content[10..90]);
+        // The first truncated sub-range takes one more request.
+        assert_eq!(r.inner.calls.load(Ordering::SeqCst), 4);
+
+        r.seek(SeekFrom::Start(75)).await?;
+        let bs = r.next().await.expect("must have data")?;
+        assert_eq!(bs, &content[85..90]);
+        Ok(())
+    }
+}
@@ -33,6 +33,8 @@ use crate::*;
 pub enum WriteOperation {
     /// Operation for [`Write::write`]
     Write,
+    /// Operation for [`Write::poll_ready`]
+    Ready,
     /// Operation for [`Write::abort`]
     Abort,
     /// Operation for [`Write::close`]
@@ -62,6 +64,7 @@ impl From<WriteOperation> for &'static str {
 
         match v {
             Write => "Writer::write",
+            Ready => "Writer::ready",
             Abort => "Writer::abort",
             Close => "Writer::close",
             BlockingWrite => "BlockingWriter::write",
@@ -86,6 +89,25 @@ pub trait Write: Unpin + Send + Sync {
     /// repeatedly until all bytes has been written.
     fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>>;
 
+    /// Check if the writer is able to accept new data without growing its
+    /// in-flight buffer.
+    ///
+    /// # Behavior
+    ///
+    /// - `Ok(())` means the next `poll_write` will accept data without
+    ///   waiting for in-flight requests.
+    /// - `Pending` means in-flight buffer is full, producers should stop
+    ///   generating data until woken up.
+    /// - `Err(err)` means error happens on in-flight requests.
+    ///
+    /// It's not required to call `poll_ready` before `poll_write`, writers
+    /// will wait for capacity inside `poll_write` instead.
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let _ = cx;
+
+        Poll::Ready(Ok(()))
+    }
+
     /// Close the writer and make sure all data has been flushed.
     fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>>;
 
@@ -121,6 +143,10 @@ impl<T: Write + ?Sized> Write for Box<T> {
         (**self).poll_write(cx, bs)
     }
 
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        (**self).poll_ready(cx)
+    }
+
     fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         (**self).poll_close(cx)
     }
@@ -140,6 +166,11 @@ pub trait WriteExt: Write {
         WriteFuture { writer: self, buf }
     }
 
+    /// Build a future for `poll_ready`.
+    fn ready(&mut self) -> ReadyFuture<Self> {
+        ReadyFuture { writer: self }
+    }
+
     /// Build a future for `poll_close`.
     fn close(&mut self) -> CloseFuture<Self> {
         CloseFuture { writer: self }
@@ -170,6 +201,24 @@ where
     }
 }
 
+/// Make this future `!Unpin` for compatibility with async trait methods.
+#[pin_project(!Unpin)]
+pub struct ReadyFuture<'a, W: Write + Unpin + ?Sized> {
+    writer: &'a mut W,
+}
+
+impl<W> Future for ReadyFuture<'_, W>
+where
+    W: Write + Unpin + ?Sized,
+{
+    type Output = Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.project();
+        Pin::new(this.writer).poll_ready(cx)
+    }
+}
+
 /// Make this future `!Unpin` for compatibility with async trait methods.
 #[pin_project(!Unpin)]
 pub struct AbortFuture<'a, W: Write + Unpin + ?Sized> {
@@ -43,6 +43,14 @@ pub trait AppendObjectWrite: Send + Sync + Unpin + 'static {
 
     /// Append the data to the end of this object.
     async fn append(&self, offset: u64, size: u64, body: AsyncBody) -> Result<()>;
+
+    /// Abort the appending by restoring this object to given offset.
+    ///
+    /// Most services can't truncate an object, so we do nothing by default.
+    async fn abort(&self, offset: u64) -> Result<()> {
+        let _ = offset;
+        Ok(())
+    }
 }
 
 /// AppendObjectWriter will implements [`Write`] based on append object.
@@ -53,6 +61,8 @@ pub trait AppendObjectWrite: Send + Sync + Unpin + 'static {
 pub struct AppendObjectWriter<W: AppendObjectWrite> {
     state: State<W>,
 
+    /// The offset before this writer appending anything.
+    start: Option<u64>,
     offset: Option<u64>,
 }
 
@@ -60,6 +70,7 @@ enum State<W> {
     Idle(Option<W>),
     Offset(BoxFuture<'static, (W, Result<u64>)>),
     Append(BoxFuture<'static, (W, Result<usize>)>),
+    Abort(BoxFuture<'static, (W, Result<()>)>),
 }
 
 /// # Safety
@@ -72,6 +83,7 @@ impl<W: AppendObjectWrite> AppendObjectWriter<W> {
     pub fn new(inner: W) -> Self {
         Self {
             state: State::Idle(Some(inner)),
+            start: None,
             offset: None,
         }
     }
@@ -109,7 +121,9 @@ where
                 State::Offset(fut) => {
                     let (w, offset) = ready!(fut.as_mut().poll(cx));
                     self.state = State::Idle(Some(w));
-                    self.offset = Some(offset?);
+                    let offset = offset?;
+                    self.start = Some(offset);
+                    self.offset = Some(offset);
                 }
                 State::Append(fut) => {
                     let (w, size) = ready!(fut.as_mut().poll(cx));
@@ -120,6 +134,9 @@ where
                     self.offset = self.offset.map(|offset| offset + size as u64);
                     return Poll::Ready(Ok(size));
                 }
+                State::Abort(_) => {
+                    unreachable!("AppendObjectWriter must not go into abort state during write")
+                }
             }
         }
     }
@@ -128,7 +145,46 @@ where
         Poll::Ready(Ok(()))
     }
 
-    fn poll_abort(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
-        Poll::Ready(Ok(()))
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        loop {
+            match &mut self.state {
+                State::Idle(w) => {
+                    // Nothing has been appended, no need to abort.
+                    let Some(start) = self.start else {
+                        return Poll::Ready(Ok(()));
+                    };
+
+                    let w = w.take().expect("writer must be valid");
+                    self.state = State::Abort(Box::pin(async move {
+                        let res = w.abort(start).await;
+
+                        (w, res)
+                    }));
+                }
+                State::Abort(fut) => {
+                    let (w, res) = ready!(fut.as_mut().poll(cx));
+                    self.state = State::Idle(Some(w));
+                    res?;
+
+                    self.start = None;
+                    self.offset = None;
+                    return Poll::Ready(Ok(()));
+                }
+                // The write has been cancelled, wait for it to finish so that
+                // we know where to restore.
+                State::Offset(fut) => {
+                    let (w, offset) = ready!(fut.as_mut().poll(cx));
+                    self.state = State::Idle(Some(w));
+                    if let Ok(offset) = offset {
+                        self.start = Some(offset);
+                        self.offset = Some(offset);
+                    }
+                }
+                State::Append(fut) => {
+                    let (w, _) = ready!(fut.as_mut().poll(cx));
+                    self.state = State::Idle(Some(w));
+                }
+            }
+        }
     }
 }
@@ -72,6 +72,13 @@ impl<ONE: oio::Write, TWO: oio::Write> oio::Write for TwoWaysWriter<ONE, TWO> {
         }
     }
 
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        match self {
+            Self::One(one) => one.poll_ready(cx),
+            Self::Two(two) => two.poll_ready(cx),
+        }
+    }
+
     fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         match self {
             Self::One(one) => one.poll_abort(cx),
@@ -112,6 +119,14 @@ impl<ONE: oio::Write, TWO: oio::Write, THREE: oio::Write> oio::Write
         }
     }
 
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        match self {
+            Self::One(one) => one.poll_ready(cx),
+            Self::Two(two) => two.poll_ready(cx),
+            Self::Three(three) => three.poll_ready(cx),
+        }
+    }
+
     fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         match self {
             Self::One(one) => one.poll_abort(cx),
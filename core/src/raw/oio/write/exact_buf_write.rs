@@ -68,6 +68,14 @@ impl<W: oio::Write> oio::Write for ExactBufWriter<W> {
         Poll::Ready(Ok(written))
     }
 
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        // Only full buffer needs to be flushed before accepting new data.
+        if self.buffer.len() >= self.buffer_size {
+            return self.inner.poll_ready(cx);
+        }
+        Poll::Ready(Ok(()))
+    }
+
     fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         self.buffer.clear();
         self.inner.poll_abort(cx)
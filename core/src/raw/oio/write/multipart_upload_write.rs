@@ -22,6 +22,9 @@ use std::task::Poll;
 
 use async_trait::async_trait;
 use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::FutureExt;
+use futures::StreamExt;
 
 use crate::raw::*;
 use crate::*;
@@ -48,6 +51,16 @@ use crate::*;
 /// ```
 ///
 /// We will use `write_once` instead of starting a new multipart upload.
+///
+/// Parts could be uploaded concurrently if `concurrent` is larger than 1,
+/// `write` will return as soon as there is a free slot so that new data can
+/// be accepted while previous parts are still in flight. In-flight parts are
+/// also polled on every `write` so that uploads overlap with the producer.
+/// Parts are sorted by `part_number` before `complete_part`.
+///
+/// A failed part will be uploaded again in the background, up to 3 attempts
+/// in total. Only the last failure is returned by `write` or `close`, after
+/// that the writer can only be aborted.
 #[async_trait]
 pub trait MultipartUploadWrite: Send + Sync + Unpin + 'static {
     /// write_once is used to write the data to underlying storage at once.
@@ -106,35 +119,137 @@ pub struct MultipartUploadPart {
 /// MultipartUploadWriter will implements [`Write`] based on multipart
 /// uploads.
 pub struct MultipartUploadWriter<W: MultipartUploadWrite> {
-    state: State<W>,
+    state: State,
+    w: Arc<W>,
 
     cache: Option<oio::ChunkedBytes>,
     upload_id: Option<Arc<String>>,
     parts: Vec<MultipartUploadPart>,
+    next_part_number: usize,
+    concurrent: usize,
+    futures: WritePartFutures,
+    /// The part that has failed for [`MAX_PART_ATTEMPTS`] times.
+    failed_part: Option<usize>,
 }
 
-enum State<W> {
-    Idle(Option<W>),
-    Init(BoxFuture<'static, (W, Result<String>)>),
-    Write(BoxFuture<'static, (W, Result<MultipartUploadPart>)>),
-    Close(BoxFuture<'static, (W, Result<()>)>),
-    Abort(BoxFuture<'static, (W, Result<()>)>),
+enum State {
+    Idle,
+    Init(BoxFuture<'static, Result<String>>),
+    Close(BoxFuture<'static, Result<()>>),
+    Abort(BoxFuture<'static, Result<()>>),
 }
 
 /// # Safety
 ///
 /// We will only take `&mut Self` reference for State.
-unsafe impl<S: MultipartUploadWrite> Sync for State<S> {}
+unsafe impl Sync for State {}
+
+/// The max attempts to upload a part before the writer gives up.
+const MAX_PART_ATTEMPTS: usize = 3;
+
+/// The output of a write part future, data and attempts are returned back so
+/// that the part could be uploaded again if failed.
+type WritePartOutput = (usize, oio::ChunkedBytes, usize, Result<MultipartUploadPart>);
+
+struct WritePartFutures(FuturesUnordered<BoxFuture<'static, WritePartOutput>>);
+
+/// # Safety
+///
+/// We will only take `&mut Self` reference for WritePartFutures.
+unsafe impl Sync for WritePartFutures {}
 
 impl<W: MultipartUploadWrite> MultipartUploadWriter<W> {
     /// Create a new MultipartUploadWriter.
-    pub fn new(inner: W) -> Self {
+    ///
+    /// `concurrent` is the max number of parts that could be uploaded at
+    /// the same time, `0` will be treated as `1`.
+    pub fn new(inner: W, concurrent: usize) -> Self {
         Self {
-            state: State::Idle(Some(inner)),
+            state: State::Idle,
+            w: Arc::new(inner),
 
             cache: None,
             upload_id: None,
             parts: Vec::new(),
+            next_part_number: 0,
+            concurrent: concurrent.max(1),
+            futures: WritePartFutures(FuturesUnordered::new()),
+            failed_part: None,
+        }
+    }
+
+    fn write_part(
+        &mut self,
+        upload_id: Arc<String>,
+        part_number: usize,
+        bs: oio::ChunkedBytes,
+        attempt: usize,
+    ) {
+        let w = self.w.clone();
+        self.futures.0.push(
+            async move {
+                let size = bs.len();
+                let part = w
+                    .write_part(
+                        &upload_id,
+                        part_number,
+                        size as u64,
+                        AsyncBody::ChunkedBytes(bs.clone()),
+                    )
+                    .await;
+                (part_number, bs, attempt, part)
+            }
+            .boxed(),
+        );
+    }
+
+    /// Wait for one of the in-flight parts to finish.
+    ///
+    /// Failed part will be scheduled again silently, the error is returned
+    /// only after it has been tried for [`MAX_PART_ATTEMPTS`] times.
+    fn poll_part(&mut self, cx: &mut Context<'_>, upload_id: Arc<String>) -> Poll<Result<()>> {
+        let Some((part_number, bs, attempt, part)) = ready!(self.futures.0.poll_next_unpin(cx))
+        else {
+            return Poll::Ready(Ok(()));
+        };
+        match part {
+            Ok(part) => {
+                self.parts.push(part);
+                Poll::Ready(Ok(()))
+            }
+            Err(err) if attempt + 1 < MAX_PART_ATTEMPTS => {
+                log::debug!("part {part_number} upload failed, retrying: {err}");
+                self.write_part(upload_id, part_number, bs, attempt + 1);
+                Poll::Ready(Ok(()))
+            }
+            Err(err) => {
+                self.failed_part = Some(part_number);
+                Poll::Ready(Err(err
+                    .with_context("part_number", part_number.to_string())
+                    .set_permanent()))
+            }
+        }
+    }
+
+    /// Collect the parts that have finished without waiting for others.
+    fn poll_finished_parts(&mut self, cx: &mut Context<'_>, upload_id: Arc<String>) -> Result<()> {
+        while !self.futures.0.is_empty() {
+            match self.poll_part(cx, upload_id.clone()) {
+                Poll::Ready(res) => res?,
+                Poll::Pending => break,
+            }
+        }
+        Ok(())
+    }
+
+    fn check_failed_part(&self) -> Result<()> {
+        match self.failed_part {
+            Some(part_number) => Err(Error::new(
+                ErrorKind::Unexpected,
+                "part upload failed too many times, writer must be aborted",
+            )
+            .with_context("part_number", part_number.to_string())),
+            None => Ok(()),
         }
     }
 }
@@ -145,29 +260,34 @@ where
     W: MultipartUploadWrite,
 {
     fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        self.check_failed_part()?;
+        if let (true, Some(upload_id)) = (matches!(self.state, State::Idle), self.upload_id.clone())
+        {
+            self.poll_finished_parts(cx, upload_id)?;
+        }
+
         loop {
             match &mut self.state {
-                State::Idle(w) => {
-                    match self.upload_id.as_ref() {
+                State::Idle => {
+                    match self.upload_id.clone() {
                         Some(upload_id) => {
-                            let upload_id = upload_id.clone();
-                            let part_number = self.parts.len();
-
-                            let bs = self.cache.clone().expect("cache must be valid").clone();
-                            let w = w.take().expect("writer must be valid");
-                            self.state = State::Write(Box::pin(async move {
-                                let size = bs.len();
-                                let part = w
-                                    .write_part(
-                                        &upload_id,
-                                        part_number,
-                                        size as u64,
-                                        AsyncBody::ChunkedBytes(bs),
-                                    )
-                                    .await;
-
-                                (w, part)
-                            }));
+                            // Wait for a free slot before uploading the cache.
+                            if self.futures.0.len() >= self.concurrent {
+                                ready!(self.poll_part(cx, upload_id))?;
+                                continue;
+                            }
+
+                            let cache = self.cache.take().expect("cache must be valid");
+                            let part_number = self.next_part_number;
+                            self.next_part_number += 1;
+                            self.write_part(upload_id.clone(), part_number, cache, 0);
+                            // Start the new part right away.
+                            self.poll_finished_parts(cx, upload_id)?;
+
+                            let size = bs.remaining();
+                            let cb = oio::ChunkedBytes::from_vec(bs.vectored_bytes(size));
+                            self.cache = Some(cb);
+                            return Poll::Ready(Ok(size));
                         }
                         None => {
                             // Fill cache with the first write.
@@ -178,30 +298,17 @@ where
                                 return Poll::Ready(Ok(size));
                             }
 
-                            let w = w.take().expect("writer must be valid");
-                            self.state = State::Init(Box::pin(async move {
-                                let upload_id = w.initiate_part().await;
-                                (w, upload_id)
-                            }));
+                            let w = self.w.clone();
+                            self.state =
+                                State::Init(Box::pin(async move { w.initiate_part().await }));
                         }
                     }
                 }
                 State::Init(fut) => {
-                    let (w, upload_id) = ready!(fut.as_mut().poll(cx));
-                    self.state = State::Idle(Some(w));
+                    let upload_id = ready!(fut.as_mut().poll(cx));
+                    self.state = State::Idle;
                     self.upload_id = Some(Arc::new(upload_id?));
                 }
-                State::Write(fut) => {
-                    let (w, part) = ready!(fut.as_mut().poll(cx));
-                    self.state = State::Idle(Some(w));
-                    self.parts.push(part?);
-
-                    // Replace the cache when last write succeeded
-                    let size = bs.remaining();
-                    let cb = oio::ChunkedBytes::from_vec(bs.vectored_bytes(size));
-                    self.cache = Some(cb);
-                    return Poll::Ready(Ok(size));
-                }
                 State::Close(_) => {
                     unreachable!(
                         "MultipartUploadWriter must not go into State::Close during poll_write"
@@ -216,75 +323,76 @@ where
         }
     }
 
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        // Cache will be uploaded by the next write, wait for a free slot for it.
+        self.check_failed_part()?;
+        if let (true, Some(upload_id)) = (matches!(self.state, State::Idle), self.upload_id.clone())
+        {
+            while self.cache.is_some() && self.futures.0.len() >= self.concurrent {
+                ready!(self.poll_part(cx, upload_id.clone()))?;
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
     fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         loop {
             match &mut self.state {
-                State::Idle(w) => {
-                    let w = w.take().expect("writer must be valid");
+                State::Idle => {
+                    let w = self.w.clone();
                     match self.upload_id.clone() {
                         Some(upload_id) => {
-                            let parts = self.parts.clone();
-                            match self.cache.clone() {
-                                Some(bs) => {
-                                    let upload_id = upload_id.clone();
-                                    self.state = State::Write(Box::pin(async move {
-                                        let size = bs.len();
-                                        let part = w
-                                            .write_part(
-                                                &upload_id,
-                                                parts.len(),
-                                                size as u64,
-                                                AsyncBody::ChunkedBytes(bs),
-                                            )
-                                            .await;
-                                        (w, part)
-                                    }));
-                                }
-                                None => {
-                                    self.state = State::Close(Box::pin(async move {
-                                        let res = w.complete_part(&upload_id, &parts).await;
-                                        (w, res)
-                                    }));
-                                }
+                            self.check_failed_part()?;
+                            if self.cache.is_some() && self.futures.0.len() < self.concurrent {
+                                let cache = self.cache.take().expect("cache must be valid");
+                                let part_number = self.next_part_number;
+                                self.next_part_number += 1;
+                                self.write_part(upload_id, part_number, cache, 0);
+                                continue;
                             }
+                            if !self.futures.0.is_empty() {
+                                ready!(self.poll_part(cx, upload_id))?;
+                                continue;
+                            }
+
+                            // Parts could be finished out of order.
+                            let mut parts = self.parts.clone();
+                            parts.sort_by_key(|v| v.part_number);
+                            self.state = State::Close(Box::pin(async move {
+                                w.complete_part(&upload_id, &parts).await
+                            }));
                         }
                         None => match self.cache.clone() {
                             Some(bs) => {
                                 self.state = State::Close(Box::pin(async move {
                                     let size = bs.len();
-                                    let res = w
-                                        .write_once(size as u64, AsyncBody::ChunkedBytes(bs))
-                                        .await;
-                                    (w, res)
+                                    w.write_once(size as u64, AsyncBody::ChunkedBytes(bs)).await
                                 }));
                             }
                             None => {
                                 // Call write_once if there is no data in cache and no upload_id.
                                 self.state = State::Close(Box::pin(async move {
-                                    let res = w.write_once(0, AsyncBody::Empty).await;
-                                    (w, res)
+                                    w.write_once(0, AsyncBody::Empty).await
                                 }));
                             }
                         },
                     }
                 }
                 State::Close(fut) => {
-                    let (w, res) = futures::ready!(fut.as_mut().poll(cx));
-                    self.state = State::Idle(Some(w));
+                    let res = futures::ready!(fut.as_mut().poll(cx));
+                    self.state = State::Idle;
                     // We should check res first before clean up cache.
                     res?;
 
                     self.cache = None;
                     return Poll::Ready(Ok(()));
                 }
-                State::Init(_) => unreachable!(
-                    "MultipartUploadWriter must not go into State::Init during poll_close"
-                ),
-                State::Write(fut) => {
-                    let (w, part) = ready!(fut.as_mut().poll(cx));
-                    self.state = State::Idle(Some(w));
-                    self.parts.push(part?);
-                    self.cache = None;
+                // The write has been cancelled while initiating, resume it.
+                State::Init(fut) => {
+                    let upload_id = ready!(fut.as_mut().poll(cx));
+                    self.state = State::Idle;
+                    self.upload_id = Some(Arc::new(upload_id?));
                 }
                 State::Abort(_) => unreachable!(
                     "MultipartUploadWriter must not go into State::Abort during poll_close"
@@ -296,14 +404,16 @@ where
     fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         loop {
             match &mut self.state {
-                State::Idle(w) => {
-                    let w = w.take().expect("writer must be valid");
+                State::Idle => {
+                    let w = self.w.clone();
+                    // Drop all in-flight parts, they will be purged by abort.
+                    self.futures.0.clear();
                     match self.upload_id.clone() {
                         Some(upload_id) => {
-                            self.state = State::Abort(Box::pin(async move {
-                                let res = w.abort_part(&upload_id).await;
-                                (w, res)
-                            }));
+                            self.state =
+                                State::Abort(Box::pin(
+                                    async move { w.abort_part(&upload_id).await },
+                                ));
                         }
                         None => {
                             self.cache = None;
@@ -312,20 +422,230 @@ where
                     }
                 }
                 State::Abort(fut) => {
-                    let (w, res) = futures::ready!(fut.as_mut().poll(cx));
-                    self.state = State::Idle(Some(w));
+                    let res = futures::ready!(fut.as_mut().poll(cx));
+                    self.state = State::Idle;
                     return Poll::Ready(res);
                 }
-                State::Init(_) => unreachable!(
-                    "MultipartUploadWriter must not go into State::Init during poll_abort"
-                ),
-                State::Write(_) => unreachable!(
-                    "MultipartUploadWriter must not go into State::Write during poll_abort"
-                ),
-                State::Close(_) => unreachable!(
-                    "MultipartUploadWriter must not go into State::Close during poll_abort"
-                ),
+                // The write has been cancelled while initiating, wait for the
+                // upload id so that the upload could be aborted.
+                State::Init(fut) => {
+                    let res = ready!(fut.as_mut().poll(cx));
+                    self.state = State::Idle;
+                    if let Ok(upload_id) = res {
+                        self.upload_id = Some(Arc::new(upload_id));
+                    }
+                }
+                // The close has been cancelled, drop it and abort the upload
+                // instead of committing it.
+                State::Close(_) => {
+                    self.state = State::Idle;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use bytes::Bytes;
+    use pretty_assertions::assert_eq;
+    use rand::thread_rng;
+    use rand::Rng;
+    use rand::RngCore;
+
+    use super::*;
+    use crate::raw::oio::WriteBuf;
+    use crate::raw::oio::WriteExt;
+
+    #[derive(Default)]
+    struct MockWriter {
+        parts: Mutex<HashMap<usize, Bytes>>,
+        content: Mutex<Vec<u8>>,
+        /// The number of `write_part` calls that will fail.
+        failures: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl MultipartUploadWrite for Arc<MockWriter> {
+        async fn write_once(&self, _: u64, body: AsyncBody) -> Result<()> {
+            if let AsyncBody::ChunkedBytes(bs) = body {
+                *self.content.lock().unwrap() = bs.bytes(bs.len()).to_vec();
+            }
+            Ok(())
+        }
+
+        async fn initiate_part(&self) -> Result<String> {
+            Ok("upload".to_string())
+        }
+
+        async fn write_part(
+            &self,
+            _: &str,
+            part_number: usize,
+            _: u64,
+            body: AsyncBody,
+        ) -> Result<MultipartUploadPart> {
+            // Make parts finish out of order.
+            let delay = thread_rng().gen_range(0..10);
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+
+            {
+                let mut failures = self.failures.lock().unwrap();
+                if *failures > 0 {
+                    *failures -= 1;
+                    return Err(Error::new(ErrorKind::Unexpected, "part failed").set_temporary());
+                }
+            }
+
+            let AsyncBody::ChunkedBytes(bs) = body else {
+                unreachable!("body must be chunked bytes")
+            };
+            self.parts
+                .lock()
+                .unwrap()
+                .insert(part_number, bs.bytes(bs.len()));
+            Ok(MultipartUploadPart {
+                part_number,
+                etag: part_number.to_string(),
+            })
+        }
+
+        async fn complete_part(&self, _: &str, parts: &[MultipartUploadPart]) -> Result<()> {
+            let stored = self.parts.lock().unwrap();
+            let mut content = self.content.lock().unwrap();
+            for (idx, part) in parts.iter().enumerate() {
+                assert_eq!(part.part_number, idx, "parts must be completed in order");
+                content.extend_from_slice(&stored[&part.part_number]);
             }
+            Ok(())
+        }
+
+        async fn abort_part(&self, _: &str) -> Result<()> {
+            Ok(())
         }
     }
+
+    #[tokio::test]
+    async fn test_multipart_upload_writer_concurrent() -> Result<()> {
+        let mut rng = thread_rng();
+        let mock = Arc::new(MockWriter::default());
+        let mut w = MultipartUploadWriter::new(mock.clone(), 4);
+
+        let mut expected = vec![];
+        for _ in 0..32 {
+            let mut content = vec![0; rng.gen_range(1..64)];
+            rng.fill_bytes(&mut content);
+            expected.extend_from_slice(&content);
+
+            let mut bs = Bytes::from(content);
+            while !bs.is_empty() {
+                let n = w.write(&bs).await?;
+                bs.advance(n);
+            }
+        }
+        w.close().await?;
+
+        assert_eq!(*mock.content.lock().unwrap(), expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_multipart_upload_writer_ready() -> Result<()> {
+        let mock = Arc::new(MockWriter::default());
+        let mut w = MultipartUploadWriter::new(mock.clone(), 2);
+
+        for i in 0..8u8 {
+            w.ready().await?;
+            // There must be a free slot for the cache after ready.
+            assert!(w.cache.is_none() || w.futures.0.len() < w.concurrent);
+
+            let n = w.write(&Bytes::from(vec![i; 4])).await?;
+            assert_eq!(n, 4);
+        }
+        w.close().await?;
+
+        assert_eq!(mock.content.lock().unwrap().len(), 32);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_multipart_upload_writer_overlap() -> Result<()> {
+        let mock = Arc::new(MockWriter::default());
+        let mut w = MultipartUploadWriter::new(mock.clone(), 4);
+
+        w.write(&Bytes::from(vec![0; 4])).await?;
+        w.write(&Bytes::from(vec![1; 4])).await?;
+        assert_eq!(w.futures.0.len(), 1);
+
+        // The in-flight part finishes while the producer is idle, and will
+        // be collected by the next write without waiting for free slots.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        w.write(&Bytes::from(vec![2; 4])).await?;
+        assert_eq!(w.parts.len(), 1);
+        w.close().await?;
+
+        assert_eq!(mock.content.lock().unwrap().len(), 12);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_multipart_upload_writer_retry_part() -> Result<()> {
+        let mock = Arc::new(MockWriter::default());
+        *mock.failures.lock().unwrap() = MAX_PART_ATTEMPTS - 1;
+        let mut w = MultipartUploadWriter::new(mock.clone(), 1);
+
+        w.write(&Bytes::from(vec![0; 4])).await?;
+        w.write(&Bytes::from(vec![1; 4])).await?;
+        // Failed part is uploaded again without returning errors.
+        w.close().await?;
+        assert_eq!(*mock.failures.lock().unwrap(), 0);
+        assert_eq!(*mock.content.lock().unwrap(), [[0; 4], [1; 4]].concat());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_multipart_upload_writer_retry_part_while_writing() -> Result<()> {
+        let mock = Arc::new(MockWriter::default());
+        *mock.failures.lock().unwrap() = 1;
+        let mut w = MultipartUploadWriter::new(mock.clone(), 2);
+
+        // Retrying part must not fail the writes of other parts.
+        let mut expected = vec![];
+        for i in 0..8u8 {
+            w.write(&Bytes::from(vec![i; 4])).await?;
+            expected.extend_from_slice(&[i; 4]);
+        }
+        w.close().await?;
+
+        assert_eq!(*mock.failures.lock().unwrap(), 0);
+        assert_eq!(*mock.content.lock().unwrap(), expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_multipart_upload_writer_retry_part_exhausted() -> Result<()> {
+        let mock = Arc::new(MockWriter::default());
+        *mock.failures.lock().unwrap() = usize::MAX;
+        let mut w = MultipartUploadWriter::new(mock.clone(), 1);
+
+        w.write(&Bytes::from(vec![0; 4])).await?;
+        w.write(&Bytes::from(vec![1; 4])).await?;
+        // Error is returned only after all attempts are used.
+        let err = w.close().await.expect_err("part must fail");
+        assert!(!err.is_temporary());
+
+        // The writer gives up instead of retrying forever.
+        let err = w.close().await.expect_err("writer must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert_eq!(
+            *mock.failures.lock().unwrap(),
+            usize::MAX - MAX_PART_ATTEMPTS
+        );
+        w.abort().await?;
+        Ok(())
+    }
 }
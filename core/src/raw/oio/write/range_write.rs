@@ -268,14 +268,22 @@ impl<W: RangeWrite> oio::Write for RangeWriter<W> {
                         None => return Poll::Ready(Ok(())),
                     }
                 }
-                State::Init(_) => {
-                    unreachable!("RangeWriter must not go into State::Init during poll_close")
+                // The operation has been cancelled, wait for it to finish so
+                // that the writer and location are available for abort.
+                State::Init(fut) => {
+                    let (w, res) = ready!(fut.poll_unpin(cx));
+                    self.state = State::Idle(Some(w));
+                    if let Ok(location) = res {
+                        self.location = Some(location);
+                    }
                 }
-                State::Write(_) => {
-                    unreachable!("RangeWriter must not go into State::Write during poll_close")
+                State::Write(fut) => {
+                    let (w, _) = ready!(fut.poll_unpin(cx));
+                    self.state = State::Idle(Some(w));
                 }
-                State::Complete(_) => {
-                    unreachable!("RangeWriter must not go into State::Complete during poll_close")
+                State::Complete(fut) => {
+                    let (w, _) = ready!(fut.poll_unpin(cx));
+                    self.state = State::Idle(Some(w));
                 }
                 State::Abort(fut) => {
                     let (w, res) = ready!(fut.poll_unpin(cx));
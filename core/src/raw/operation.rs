@@ -45,6 +45,18 @@ pub enum Operation {
     Batch,
     /// Operation for [`crate::raw::Accessor::presign`]
     Presign,
+    /// Operation for [`crate::raw::Accessor::create_symlink`]
+    CreateSymlink,
+    /// Operation for [`crate::raw::Accessor::set_permissions`]
+    SetPermissions,
+    /// Operation for [`crate::raw::Accessor::create_snapshot`]
+    CreateSnapshot,
+    /// Operation for [`crate::raw::Accessor::lease`]
+    Lease,
+    /// Operation for [`crate::raw::Accessor::list_roots`]
+    ListRoots,
+    /// Operation for [`crate::raw::Accessor::concat`]
+    Concat,
     /// Operation for [`crate::raw::Accessor::blocking_create_dir`]
     BlockingCreateDir,
     /// Operation for [`crate::raw::Accessor::blocking_read`]
@@ -61,6 +73,10 @@ pub enum Operation {
     BlockingDelete,
     /// Operation for [`crate::raw::Accessor::blocking_list`]
     BlockingList,
+    /// Operation for [`crate::raw::Accessor::blocking_create_symlink`]
+    BlockingCreateSymlink,
+    /// Operation for [`crate::raw::Accessor::blocking_set_permissions`]
+    BlockingSetPermissions,
 }
 
 impl Operation {
@@ -90,6 +106,12 @@ impl From<Operation> for &'static str {
             Operation::List => "list",
             Operation::Presign => "presign",
             Operation::Batch => "batch",
+            Operation::CreateSymlink => "create_symlink",
+            Operation::SetPermissions => "set_permissions",
+            Operation::CreateSnapshot => "create_snapshot",
+            Operation::Lease => "lease",
+            Operation::ListRoots => "list_roots",
+            Operation::Concat => "concat",
             Operation::BlockingCreateDir => "blocking_create_dir",
             Operation::BlockingRead => "blocking_read",
             Operation::BlockingWrite => "blocking_write",
@@ -98,6 +120,8 @@ impl From<Operation> for &'static str {
             Operation::BlockingStat => "blocking_stat",
             Operation::BlockingDelete => "blocking_delete",
             Operation::BlockingList => "blocking_list",
+            Operation::BlockingCreateSymlink => "blocking_create_symlink",
+            Operation::BlockingSetPermissions => "blocking_set_permissions",
         }
     }
 }
@@ -19,12 +19,21 @@
 //!
 //! By using ops, users can add more context for operation.
 
+use std::collections::HashMap;
 use std::time::Duration;
 
+use chrono::DateTime;
+use chrono::Utc;
 use flagset::FlagSet;
 
 use crate::raw::*;
+#[cfg(feature = "digest")]
+use crate::DigestAlgorithm;
+#[cfg(feature = "inventory")]
+use crate::Inventory;
+use crate::ListBackpressure;
 use crate::Metakey;
+use crate::Retention;
 
 /// Args for `create` operation.
 ///
@@ -39,12 +48,138 @@ impl OpCreateDir {
     }
 }
 
+/// Args for `create_symlink` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpCreateSymlink {}
+
+impl OpCreateSymlink {
+    /// Create a new `OpCreateSymlink`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Args for `set_permissions` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpSetPermissions {
+    permissions: u32,
+}
+
+impl OpSetPermissions {
+    /// Create a new `OpSetPermissions` with given unix permission bits.
+    pub fn new(permissions: u32) -> Self {
+        Self { permissions }
+    }
+
+    /// Get the unix permission bits from option.
+    pub fn permissions(&self) -> u32 {
+        self.permissions
+    }
+}
+
+/// Args for `create_snapshot` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpCreateSnapshot {}
+
+impl OpCreateSnapshot {
+    /// Create a new `OpCreateSnapshot`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Args for `concat` operation.
+///
+/// The path and sources must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpConcat {
+    sources: Vec<String>,
+}
+
+impl OpConcat {
+    /// Create a new `OpConcat` with given sources in order.
+    pub fn new(sources: Vec<String>) -> Self {
+        Self { sources }
+    }
+
+    /// Get the sources from option.
+    pub fn sources(&self) -> &[String] {
+        &self.sources
+    }
+}
+
+/// Action of `lease` operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LeaseAction {
+    /// Acquire a new lease, `None` means the lease never expires.
+    Acquire(Option<Duration>),
+    /// Renew the lease with given id.
+    Renew(String),
+    /// Release the lease with given id.
+    Release(String),
+    /// Break the current lease no matter who holds it.
+    Break,
+}
+
+/// Args for `lease` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone)]
+pub struct OpLease {
+    action: LeaseAction,
+}
+
+impl OpLease {
+    /// Create a new `OpLease` with given action.
+    pub fn new(action: LeaseAction) -> Self {
+        Self { action }
+    }
+
+    /// Get the action from option.
+    pub fn action(&self) -> &LeaseAction {
+        &self.action
+    }
+}
+
+/// Args for `list_roots` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpListRoots {}
+
+impl OpListRoots {
+    /// Create a new `OpListRoots`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// DeleteSnapshots decides how snapshots of an object will be handled by
+/// `delete` operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeleteSnapshots {
+    /// Delete the object together with all its snapshots.
+    Include,
+    /// Only delete the snapshots and keep the object.
+    Only,
+}
+
 /// Args for `delete` operation.
 ///
 /// The path must be normalized.
 #[derive(Debug, Clone, Default)]
 pub struct OpDelete {
     version: Option<String>,
+    lease_id: Option<String>,
+    snapshots: Option<DeleteSnapshots>,
+    trash: bool,
+    recursive: bool,
+    prefix: bool,
+    must_exist: bool,
 }
 
 impl OpDelete {
@@ -65,6 +200,97 @@ impl OpDelete {
     pub fn version(&self) -> Option<&str> {
         self.version.as_deref()
     }
+
+    /// Change the lease id of this delete operation.
+    pub fn with_lease_id(mut self, lease_id: &str) -> Self {
+        self.lease_id = Some(lease_id.to_string());
+        self
+    }
+
+    /// Get the lease id of this delete operation.
+    pub fn lease_id(&self) -> Option<&str> {
+        self.lease_id.as_deref()
+    }
+
+    /// Change how snapshots of the object will be handled.
+    ///
+    /// Services will refuse to delete an object that has snapshots if
+    /// this is not set.
+    pub fn with_snapshots(mut self, snapshots: DeleteSnapshots) -> Self {
+        self.snapshots = Some(snapshots);
+        self
+    }
+
+    /// Get how snapshots of the object will be handled.
+    pub fn snapshots(&self) -> Option<DeleteSnapshots> {
+        self.snapshots
+    }
+
+    /// Change the trash flag of this delete operation.
+    ///
+    /// If `true`, services will move the path into trash instead of
+    /// removing it permanently.
+    pub fn with_trash(mut self, trash: bool) -> Self {
+        self.trash = trash;
+        self
+    }
+
+    /// Get the trash flag of this delete operation.
+    pub fn trash(&self) -> bool {
+        self.trash
+    }
+
+    /// Change the recursive flag of this delete operation.
+    ///
+    /// If `true`, deleting a dir will remove all entries inside it
+    /// instead of requiring the dir to be empty.
+    pub fn with_recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Get the recursive flag of this delete operation.
+    pub fn recursive(&self) -> bool {
+        self.recursive
+    }
+
+    /// Change the prefix flag of this delete operation.
+    ///
+    /// If `true`, all entries whose path starts with given path will be
+    /// removed.
+    pub fn with_prefix(mut self, prefix: bool) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Get the prefix flag of this delete operation.
+    pub fn prefix(&self) -> bool {
+        self.prefix
+    }
+
+    /// Change the must_exist flag of this delete operation.
+    ///
+    /// If `true`, deleting a path that does not exist will return
+    /// [`ErrorKind::NotFound`] instead of succeeding silently. The check is
+    /// done by `CompleteLayer` via `stat`, it can't be used with `prefix`
+    /// or in batch.
+    pub fn with_must_exist(mut self, must_exist: bool) -> Self {
+        self.must_exist = must_exist;
+        self
+    }
+
+    /// Get the must_exist flag of this delete operation.
+    pub fn must_exist(&self) -> bool {
+        self.must_exist
+    }
+
+    /// Build the stat args to check if the path to delete exists.
+    pub(crate) fn stat_args(&self) -> OpStat {
+        match &self.version {
+            Some(v) => OpStat::new().with_version(v),
+            None => OpStat::new(),
+        }
+    }
 }
 
 /// Args for `list` operation.
@@ -99,6 +325,30 @@ pub struct OpList {
     /// - If this is set to > 1, the list operation will be concurrent,
     ///   and the maximum number of concurrent operations will be determined by this value.
     concurrent: usize,
+    /// The delimiter used to group entries into dirs.
+    ///
+    /// - `None` means using `/` for non-recursive list and no delimiter
+    ///   for recursive list.
+    /// - `Some("")` means listing all entries without grouping.
+    delimiter: Option<String>,
+    /// The max number of entries that could be queued inside lister,
+    /// including the entries waiting for stat.
+    ///
+    /// Default to the same as `concurrent`.
+    stat_queue_size: Option<usize>,
+    /// The backpressure strategy while stat queue is full.
+    stat_backpressure: ListBackpressure,
+    /// Perform stat operations inline instead of spawning tokio tasks.
+    stat_inline: bool,
+    /// Only list entries that modified since given time.
+    modified_since: Option<DateTime<Utc>>,
+    /// List entries from given inventory instead of the service.
+    #[cfg(feature = "inventory")]
+    source: Option<Inventory>,
+    /// Include snapshots of objects in the list result.
+    snapshots: bool,
+    /// Include versions of objects in the list result.
+    versions: bool,
 }
 
 impl Default for OpList {
@@ -107,6 +357,15 @@ impl Default for OpList {
             limit: None,
             start_after: None,
             recursive: false,
+            delimiter: None,
+            stat_queue_size: None,
+            stat_backpressure: ListBackpressure::default(),
+            stat_inline: false,
+            modified_since: None,
+            #[cfg(feature = "inventory")]
+            source: None,
+            snapshots: false,
+            versions: false,
             // By default, we want to know what's the mode of this entry.
             metakey: Metakey::Mode.into(),
             concurrent: 1,
@@ -142,6 +401,17 @@ impl OpList {
         self.start_after.as_deref()
     }
 
+    /// Change the delimiter of this list operation.
+    pub fn with_delimiter(mut self, delimiter: &str) -> Self {
+        self.delimiter = Some(delimiter.into());
+        self
+    }
+
+    /// Get the delimiter of list operation.
+    pub fn delimiter(&self) -> Option<&str> {
+        self.delimiter.as_deref()
+    }
+
     /// The recursive is used to control whether the list operation is recursive.
     ///
     /// - If `false`, list operation will only list the entries under the given path.
@@ -183,6 +453,99 @@ impl OpList {
     pub fn concurrent(&self) -> usize {
         self.concurrent
     }
+
+    /// Change the max number of queued entries of this list operation.
+    ///
+    /// The queue size must not be less than `concurrent`.
+    pub fn with_stat_queue_size(mut self, size: usize) -> Self {
+        self.stat_queue_size = Some(size);
+        self
+    }
+
+    /// Get the max number of queued entries of list operation.
+    pub fn stat_queue_size(&self) -> Option<usize> {
+        self.stat_queue_size
+    }
+
+    /// Change the backpressure strategy of this list operation.
+    pub fn with_stat_backpressure(mut self, backpressure: ListBackpressure) -> Self {
+        self.stat_backpressure = backpressure;
+        self
+    }
+
+    /// Get the backpressure strategy of list operation.
+    pub fn stat_backpressure(&self) -> ListBackpressure {
+        self.stat_backpressure
+    }
+
+    /// Change whether to perform stat operations inline.
+    ///
+    /// If `true`, stat operations will be polled inside lister instead of
+    /// being spawned by `tokio::spawn`.
+    pub fn with_stat_inline(mut self, inline: bool) -> Self {
+        self.stat_inline = inline;
+        self
+    }
+
+    /// Get whether to perform stat operations inline.
+    pub fn stat_inline(&self) -> bool {
+        self.stat_inline
+    }
+
+    /// Change the modified_since of this list operation.
+    ///
+    /// Services that support [`Capability::list_with_modified_since`]
+    /// should only return entries modified since given time.
+    pub fn with_modified_since(mut self, modified_since: DateTime<Utc>) -> Self {
+        self.modified_since = Some(modified_since);
+        self
+    }
+
+    /// Get the modified_since of list operation.
+    pub fn modified_since(&self) -> Option<DateTime<Utc>> {
+        self.modified_since
+    }
+
+    /// Change the source of this list operation.
+    #[cfg(feature = "inventory")]
+    pub fn with_source(mut self, source: Inventory) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Get the source of list operation.
+    #[cfg(feature = "inventory")]
+    pub fn source(&self) -> Option<&Inventory> {
+        self.source.as_ref()
+    }
+
+    /// Change whether snapshots should be included in list result.
+    ///
+    /// Snapshots will be returned as entries with the same path as their
+    /// base objects, and [`Metadata::snapshot`] set.
+    pub fn with_snapshots(mut self, snapshots: bool) -> Self {
+        self.snapshots = snapshots;
+        self
+    }
+
+    /// Get whether snapshots should be included in list result.
+    pub fn snapshots(&self) -> bool {
+        self.snapshots
+    }
+
+    /// Change whether versions should be included in list result.
+    ///
+    /// Versions will be returned as entries with the same path as their
+    /// objects, and [`Metadata::version`] set.
+    pub fn with_versions(mut self, versions: bool) -> Self {
+        self.versions = versions;
+        self
+    }
+
+    /// Get whether versions should be included in list result.
+    pub fn versions(&self) -> bool {
+        self.versions
+    }
 }
 
 /// Args for `presign` operation.
@@ -302,6 +665,9 @@ pub struct OpRead {
     override_cache_control: Option<String>,
     override_content_disposition: Option<String>,
     version: Option<String>,
+    snapshot: Option<String>,
+    lease_id: Option<String>,
+    signer: Option<RequestSigner>,
 }
 
 impl OpRead {
@@ -404,6 +770,59 @@ impl OpRead {
     pub fn version(&self) -> Option<&str> {
         self.version.as_deref()
     }
+
+    /// Set the snapshot id of the option
+    pub fn with_snapshot(mut self, snapshot: &str) -> Self {
+        self.snapshot = Some(snapshot.to_string());
+        self
+    }
+
+    /// Get snapshot id from option
+    pub fn snapshot(&self) -> Option<&str> {
+        self.snapshot.as_deref()
+    }
+
+    /// Build the `OpStat` that targets the same object as this read, used
+    /// by internal stats like fetching the size before reading.
+    pub(crate) fn to_stat(&self) -> OpStat {
+        let mut args = OpStat::new();
+        if let Some(v) = &self.version {
+            args = args.with_version(v);
+        }
+        if let Some(v) = &self.snapshot {
+            args = args.with_snapshot(v);
+        }
+        if let Some(v) = &self.signer {
+            args = args.with_signer(v.clone());
+        }
+        args
+    }
+
+    /// Set the lease id of the option
+    pub fn with_lease_id(mut self, lease_id: &str) -> Self {
+        self.lease_id = Some(lease_id.to_string());
+        self
+    }
+
+    /// Get lease id from option
+    pub fn lease_id(&self) -> Option<&str> {
+        self.lease_id.as_deref()
+    }
+
+    /// Set the signer of option.
+    ///
+    /// The signer will be used to sign requests of this operation instead
+    /// of the credential configured in service, so that one operator can
+    /// act on behalf of many tenants.
+    pub fn with_signer(mut self, signer: RequestSigner) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Get the signer from option.
+    pub fn signer(&self) -> Option<&RequestSigner> {
+        self.signer.as_ref()
+    }
 }
 
 /// Args for `stat` operation.
@@ -412,6 +831,9 @@ pub struct OpStat {
     if_match: Option<String>,
     if_none_match: Option<String>,
     version: Option<String>,
+    snapshot: Option<String>,
+    signer: Option<RequestSigner>,
+    fields: Option<FlagSet<Metakey>>,
 }
 
 impl OpStat {
@@ -452,6 +874,54 @@ impl OpStat {
     pub fn version(&self) -> Option<&str> {
         self.version.as_deref()
     }
+
+    /// Set the snapshot id of the option
+    pub fn with_snapshot(mut self, snapshot: &str) -> Self {
+        self.snapshot = Some(snapshot.to_string());
+        self
+    }
+
+    /// Get snapshot id from option
+    pub fn snapshot(&self) -> Option<&str> {
+        self.snapshot.as_deref()
+    }
+
+    /// Set the signer of option.
+    ///
+    /// The signer will be used to sign requests of this operation instead
+    /// of the credential configured in service, so that one operator can
+    /// act on behalf of many tenants.
+    pub fn with_signer(mut self, signer: RequestSigner) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Get the signer from option.
+    pub fn signer(&self) -> Option<&RequestSigner> {
+        self.signer.as_ref()
+    }
+
+    /// Only fetch given fields of metadata.
+    ///
+    /// Services could skip fetching or parsing fields that are not required,
+    /// but they are still allowed to return more fields than required.
+    pub fn with_fields(mut self, fields: impl Into<FlagSet<Metakey>>) -> Self {
+        self.fields = Some(fields.into());
+        self
+    }
+
+    /// Get the fields from option, `None` means all fields are required.
+    pub fn fields(&self) -> Option<FlagSet<Metakey>> {
+        self.fields
+    }
+
+    /// Check if given field is required by this operation.
+    pub fn need_field(&self, field: Metakey) -> bool {
+        match self.fields {
+            None => true,
+            Some(fields) => fields.contains(Metakey::Complete) || fields.contains(field),
+        }
+    }
 }
 
 /// Args for `write` operation.
@@ -459,10 +929,27 @@ impl OpStat {
 pub struct OpWrite {
     append: bool,
     buffer: Option<usize>,
+    concurrent: usize,
 
     content_type: Option<String>,
     content_disposition: Option<String>,
     cache_control: Option<String>,
+    user_metadata: Option<HashMap<String, String>>,
+    tags: Option<HashMap<String, String>>,
+    storage_class: Option<String>,
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+    lease_id: Option<String>,
+
+    sparse: bool,
+    retention: Option<Retention>,
+    legal_hold: Option<bool>,
+    expire_at: Option<DateTime<Utc>>,
+    signer: Option<RequestSigner>,
+    #[cfg(feature = "digest")]
+    digest: Option<DigestAlgorithm>,
+    compose_sources: Vec<String>,
+    cancel_policy: WriterCancelPolicy,
 }
 
 impl OpWrite {
@@ -512,6 +999,23 @@ impl OpWrite {
         self
     }
 
+    /// Get the concurrent from op.
+    ///
+    /// The concurrent is the max number of parts that could be uploaded
+    /// at the same time, default to 1.
+    pub fn concurrent(&self) -> usize {
+        self.concurrent.max(1)
+    }
+
+    /// Set the concurrent of op.
+    ///
+    /// Services that upload parts independently like s3 will upload
+    /// multiple parts at the same time, others will ignore it.
+    pub fn with_concurrent(mut self, concurrent: usize) -> Self {
+        self.concurrent = concurrent;
+        self
+    }
+
     /// Get the content type from option
     pub fn content_type(&self) -> Option<&str> {
         self.content_type.as_deref()
@@ -544,17 +1048,296 @@ impl OpWrite {
         self.cache_control = Some(cache_control.to_string());
         self
     }
+
+    /// Get the user metadata from option
+    pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
+        self.user_metadata.as_ref()
+    }
+
+    /// Set the user metadata of option
+    pub fn with_user_metadata(mut self, user_metadata: HashMap<String, String>) -> Self {
+        self.user_metadata = Some(user_metadata);
+        self
+    }
+
+    /// Get the tags from option
+    pub fn tags(&self) -> Option<&HashMap<String, String>> {
+        self.tags.as_ref()
+    }
+
+    /// Set the tags of option
+    pub fn with_tags(mut self, tags: HashMap<String, String>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// Get the storage class from option
+    pub fn storage_class(&self) -> Option<&str> {
+        self.storage_class.as_deref()
+    }
+
+    /// Set the storage class of option, a.k.a, access tier in azblob.
+    pub fn with_storage_class(mut self, storage_class: &str) -> Self {
+        self.storage_class = Some(storage_class.to_string());
+        self
+    }
+
+    /// Get the lease id from option
+    pub fn lease_id(&self) -> Option<&str> {
+        self.lease_id.as_deref()
+    }
+
+    /// Set the lease id of option
+    pub fn with_lease_id(mut self, lease_id: &str) -> Self {
+        self.lease_id = Some(lease_id.to_string());
+        self
+    }
+
+    /// Get the If-Match from option
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+
+    /// Set the If-Match of option
+    ///
+    /// The write will fail with [`ErrorKind::ConditionNotMatch`] if the
+    /// etag of existing object doesn't match.
+    pub fn with_if_match(mut self, if_match: &str) -> Self {
+        self.if_match = Some(if_match.to_string());
+        self
+    }
+
+    /// Get the If-None-Match from option
+    pub fn if_none_match(&self) -> Option<&str> {
+        self.if_none_match.as_deref()
+    }
+
+    /// Set the If-None-Match of option
+    ///
+    /// Use `*` to make sure the write only succeeds if the object doesn't
+    /// exist, otherwise [`ErrorKind::ConditionNotMatch`] will be returned.
+    pub fn with_if_none_match(mut self, if_none_match: &str) -> Self {
+        self.if_none_match = Some(if_none_match.to_string());
+        self
+    }
+
+    /// Get the sparse flag from option.
+    pub fn sparse(&self) -> bool {
+        self.sparse
+    }
+
+    /// Set the sparse flag of option.
+    ///
+    /// If sparse is set, services will leave holes for zero ranges instead
+    /// of writing them out.
+    pub fn with_sparse(mut self, sparse: bool) -> Self {
+        self.sparse = sparse;
+        self
+    }
+
+    /// Get the retention from option.
+    pub fn retention(&self) -> Option<Retention> {
+        self.retention
+    }
+
+    /// Set the retention of option.
+    ///
+    /// The written object can't be overwritten or deleted until the
+    /// retention expires.
+    pub fn with_retention(mut self, retention: Retention) -> Self {
+        self.retention = Some(retention);
+        self
+    }
+
+    /// Get the legal hold from option.
+    pub fn legal_hold(&self) -> Option<bool> {
+        self.legal_hold
+    }
+
+    /// Set the legal hold of option.
+    pub fn with_legal_hold(mut self, legal_hold: bool) -> Self {
+        self.legal_hold = Some(legal_hold);
+        self
+    }
+
+    /// Get the expire time from option.
+    pub fn expire_at(&self) -> Option<DateTime<Utc>> {
+        self.expire_at
+    }
+
+    /// Set the expire time of option.
+    ///
+    /// The written object will be removed by service after given time.
+    pub fn with_expire_at(mut self, expire_at: DateTime<Utc>) -> Self {
+        self.expire_at = Some(expire_at);
+        self
+    }
+
+    /// Take the expire time out of option, used by layers that emulate it.
+    pub(crate) fn take_expire_at(&mut self) -> Option<DateTime<Utc>> {
+        self.expire_at.take()
+    }
+
+    /// Set the signer of option.
+    ///
+    /// The signer will be used to sign requests of this operation instead
+    /// of the credential configured in service, so that one operator can
+    /// act on behalf of many tenants.
+    pub fn with_signer(mut self, signer: RequestSigner) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Get the signer from option.
+    pub fn signer(&self) -> Option<&RequestSigner> {
+        self.signer.as_ref()
+    }
+
+    /// Get the digest algorithm from option.
+    #[cfg(feature = "digest")]
+    pub fn digest(&self) -> Option<DigestAlgorithm> {
+        self.digest
+    }
+
+    /// Set the digest algorithm of option.
+    ///
+    /// The content will be digested while writing, and the digest can be
+    /// fetched by [`Writer::digest`](crate::Writer::digest) after close.
+    #[cfg(feature = "digest")]
+    pub fn with_digest(mut self, digest: DigestAlgorithm) -> Self {
+        self.digest = Some(digest);
+        self
+    }
+
+    /// Get the compose sources from option.
+    pub fn compose_sources(&self) -> &[String] {
+        &self.compose_sources
+    }
+
+    /// Set the compose sources of option.
+    ///
+    /// The object will be composed by the service from the content of given
+    /// urls in order without downloading them, written content will be
+    /// placed after them.
+    pub fn with_compose_sources(mut self, sources: Vec<String>) -> Self {
+        self.compose_sources = sources;
+        self
+    }
+
+    /// Get the cancel policy from option.
+    pub fn cancel_policy(&self) -> WriterCancelPolicy {
+        self.cancel_policy
+    }
+
+    /// Set the cancel policy of option.
+    ///
+    /// It's only used by [`Writer`](crate::Writer) and won't be sent to
+    /// services.
+    pub fn with_cancel_policy(mut self, policy: WriterCancelPolicy) -> Self {
+        self.cancel_policy = policy;
+        self
+    }
 }
 
 /// Args for `copy` operation.
 #[derive(Debug, Clone, Default)]
-pub struct OpCopy {}
+pub struct OpCopy {
+    storage_class: Option<String>,
+    content_type: Option<String>,
+    cache_control: Option<String>,
+    user_metadata: Option<HashMap<String, String>>,
+    tags: Option<HashMap<String, String>>,
+    source_url: Option<String>,
+}
 
 impl OpCopy {
     /// Create a new `OpCopy`.
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Get the storage class from option.
+    pub fn storage_class(&self) -> Option<&str> {
+        self.storage_class.as_deref()
+    }
+
+    /// Set the storage class of the copied object.
+    ///
+    /// Services will change the storage class in place if `from` and `to`
+    /// are the same.
+    pub fn with_storage_class(mut self, storage_class: &str) -> Self {
+        self.storage_class = Some(storage_class.to_string());
+        self
+    }
+
+    /// Get the content type from option.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Replace the content type of the copied object.
+    ///
+    /// The content type of source will be preserved if not set.
+    pub fn with_content_type(mut self, content_type: &str) -> Self {
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    /// Get the cache control from option.
+    pub fn cache_control(&self) -> Option<&str> {
+        self.cache_control.as_deref()
+    }
+
+    /// Replace the cache control of the copied object.
+    ///
+    /// The cache control of source will be preserved if not set.
+    pub fn with_cache_control(mut self, cache_control: &str) -> Self {
+        self.cache_control = Some(cache_control.to_string());
+        self
+    }
+
+    /// Get the user metadata from option.
+    pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
+        self.user_metadata.as_ref()
+    }
+
+    /// Replace the user metadata of the copied object.
+    ///
+    /// The user metadata of source will be preserved if not set.
+    pub fn with_user_metadata(mut self, user_metadata: HashMap<String, String>) -> Self {
+        self.user_metadata = Some(user_metadata);
+        self
+    }
+
+    /// Get the tags from option.
+    pub fn tags(&self) -> Option<&HashMap<String, String>> {
+        self.tags.as_ref()
+    }
+
+    /// Replace the tags of the copied object.
+    ///
+    /// The tags of source will be preserved if not set.
+    pub fn with_tags(mut self, tags: HashMap<String, String>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// Get the source url from option.
+    pub fn source_url(&self) -> Option<&str> {
+        self.source_url.as_deref()
+    }
+
+    /// Copy from given url instead of the `from` path, the url is usually
+    /// a presigned url of another service.
+    pub fn with_source_url(mut self, url: &str) -> Self {
+        self.source_url = Some(url.to_string());
+        self
+    }
+
+    /// Check if any of the standard or user metadata will be replaced.
+    pub fn replace_metadata(&self) -> bool {
+        self.content_type.is_some() || self.cache_control.is_some() || self.user_metadata.is_some()
+    }
 }
 
 /// Args for `rename` operation.
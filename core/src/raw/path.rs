@@ -21,20 +21,28 @@ use crate::EntryMode;
 ///
 /// # Rules
 ///
-/// - Input root MUST be the format like `/abc/def/`
+/// - Input root MUST be the format like `/abc/def/`, see [`normalize_root`].
+/// - Input path SHOULD be normalized by [`normalize_path`], leading `/` will
+///   be trimmed.
 /// - Output will be the format like `path/to/root/path`.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::raw::build_abs_path;
+///
+/// assert_eq!(build_abs_path("/root/", "/"), "root/");
+/// assert_eq!(build_abs_path("/root/", "abc/def"), "root/abc/def");
+/// assert_eq!(build_abs_path("/", "abc/"), "abc/");
+/// ```
 pub fn build_abs_path(root: &str, path: &str) -> String {
     debug_assert!(root.starts_with('/'), "root must start with /");
     debug_assert!(root.ends_with('/'), "root must end with /");
 
     let p = root[1..].to_string();
 
-    if path == "/" {
-        p
-    } else {
-        debug_assert!(!path.starts_with('/'), "path must not start with /");
-        p + path
-    }
+    // Make sure path never escapes into an absolute path like `root//path`.
+    p + path.trim_start_matches('/')
 }
 
 /// build_rooted_abs_path will build an absolute path with root.
@@ -93,29 +101,49 @@ pub fn build_rel_path(root: &str, path: &str) -> String {
 /// - All leading / will be trimmed: `///abc` => `abc`
 /// - Internal // will be replaced by /: `abc///def` => `abc/def`
 /// - Empty path will be `/`: `` => `/`
+///
+/// `.` and `..` are kept as is since they could be part of keys in object
+/// storage services, use [`crate::PathPolicy`] to reject them.
+///
+/// Normalization is idempotent: normalizing a normalized path returns it
+/// unchanged. Non-ASCII chars are kept as is.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::raw::normalize_path;
+///
+/// assert_eq!(normalize_path(" //abc//def/ "), "abc/def/");
+/// assert_eq!(normalize_path("abc/../def"), "abc/../def");
+/// assert_eq!(normalize_path(""), "/");
+/// ```
 pub fn normalize_path(path: &str) -> String {
-    // - all whitespace has been trimmed.
-    // - all leading `/` has been trimmed.
-    let path = path.trim().trim_start_matches('/');
+    let path = path.trim();
+    let is_dir = path.ends_with('/');
+
+    let mut segments: Vec<&str> = Vec::new();
+    for seg in path.split('/') {
+        // The first segment must not start with whitespace, otherwise it
+        // will be trimmed while normalizing again.
+        let seg = if segments.is_empty() {
+            seg.trim_start()
+        } else {
+            seg
+        };
+        if !seg.is_empty() {
+            segments.push(seg);
+        }
+    }
 
     // Fast line for empty path.
-    if path.is_empty() {
+    if segments.is_empty() {
         return "/".to_string();
     }
 
-    let has_trailing = path.ends_with('/');
-
-    let mut p = path
-        .split('/')
-        .filter(|v| !v.is_empty())
-        .collect::<Vec<&str>>()
-        .join("/");
-
-    // Append trailing back if input path is endswith `/`.
-    if has_trailing {
+    let mut p = segments.join("/");
+    if is_dir {
         p.push('/');
     }
-
     p
 }
 
@@ -131,22 +159,37 @@ pub fn normalize_path(path: &str) -> String {
 /// - Add trailing `/` if not ends with: `/abc` => `/abc/`
 ///
 /// Finally, we will got path like `/path/to/root/`.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::raw::normalize_root;
+///
+/// assert_eq!(normalize_root("abc//def"), "/abc/def/");
+/// assert_eq!(normalize_root(" /abc/ "), "/abc/");
+/// ```
 pub fn normalize_root(v: &str) -> String {
-    let mut v = v
-        .split('/')
-        .filter(|v| !v.is_empty())
-        .collect::<Vec<&str>>()
-        .join("/");
-    if !v.starts_with('/') {
-        v.insert(0, '/');
+    let v = normalize_path(v);
+    if v == "/" {
+        return v;
     }
-    if !v.ends_with('/') {
-        v.push('/')
-    }
-    v
+
+    format!("/{}/", v.trim_end_matches('/'))
 }
 
-/// Get basename from path.
+/// Get basename from a normalized path.
+///
+/// Basename of dir path keeps its trailing `/`, and basename of root is `/`.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::raw::get_basename;
+///
+/// assert_eq!(get_basename("abc/def.txt"), "def.txt");
+/// assert_eq!(get_basename("abc/def/"), "def/");
+/// assert_eq!(get_basename("/"), "/");
+/// ```
 pub fn get_basename(path: &str) -> &str {
     // Handle root case
     if path == "/" {
@@ -175,7 +218,45 @@ pub fn get_basename(path: &str) -> &str {
     }
 }
 
-/// Get parent from path.
+/// Get extension from a normalized path.
+///
+/// Only files have extensions. Leading `.` of hidden files like `.bashrc`
+/// and trailing `.` like `abc.` are not treated as extensions.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::raw::get_extension;
+///
+/// assert_eq!(get_extension("abc/def.tar.gz"), Some("gz"));
+/// assert_eq!(get_extension("abc/.bashrc"), None);
+/// assert_eq!(get_extension("abc.d/"), None);
+/// ```
+pub fn get_extension(path: &str) -> Option<&str> {
+    if path.ends_with('/') {
+        return None;
+    }
+
+    let name = get_basename(path);
+    match name.rfind('.') {
+        Some(0) | None => None,
+        Some(idx) if idx + 1 == name.len() => None,
+        Some(idx) => Some(&name[idx + 1..]),
+    }
+}
+
+/// Get parent from a normalized path.
+///
+/// Parent is always a dir path, and parent of root is `/`.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::raw::get_parent;
+///
+/// assert_eq!(get_parent("abc/def.txt"), "abc/");
+/// assert_eq!(get_parent("abc/"), "/");
+/// ```
 pub fn get_parent(path: &str) -> &str {
     if path == "/" {
         return "/";
@@ -210,6 +291,24 @@ pub fn get_parent(path: &str) -> &str {
     }
 }
 
+/// Check if given path contains `.` or `..` segments.
+///
+/// Such paths are kept as is by [`normalize_path`], use
+/// [`crate::PathPolicy::with_reject_parent_dir`] to reject them.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::raw::has_dot_segments;
+///
+/// assert!(has_dot_segments("abc/../def"));
+/// assert!(has_dot_segments("abc/."));
+/// assert!(!has_dot_segments("abc/.def/..ghi"));
+/// ```
+pub fn has_dot_segments(path: &str) -> bool {
+    path.split('/').any(|v| v == "." || v == "..")
+}
+
 /// Validate given path is match with given EntryMode.
 pub fn validate_path(path: &str, mode: EntryMode) -> bool {
     debug_assert!(!path.is_empty(), "input path should not be empty");
@@ -223,6 +322,8 @@ pub fn validate_path(path: &str, mode: EntryMode) -> bool {
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -240,6 +341,12 @@ mod tests {
             ("file path contains ///", "abc///def", "abc/def"),
             ("dir path contains ///", "abc///def///", "abc/def/"),
             ("file with whitespace", "abc/def   ", "abc/def"),
+            ("file with leading whitespace after /", "/ abc", "abc"),
+            ("file path contains .", "abc/./def", "abc/./def"),
+            ("file path contains ..", "abc/../def", "abc/../def"),
+            ("path ends with ..", "abc/..", "abc/.."),
+            ("dot file", "abc/.def", "abc/.def"),
+            ("unicode path", "/数据//文件.txt", "数据/文件.txt"),
         ];
 
         for (name, input, expect) in cases {
@@ -258,6 +365,8 @@ mod tests {
             ("abs file path with extra /", "///abc/def", "/abc/def/"),
             ("abs dir path with extra /", "///abc/def/", "/abc/def/"),
             ("dir path contains ///", "abc///def///", "/abc/def/"),
+            ("root with whitespace", " /abc/ ", "/abc/"),
+            ("root contains ..", "/abc/../def", "/abc/../def/"),
         ];
 
         for (name, input, expect) in cases {
@@ -299,6 +408,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_extension() {
+        let cases = vec![
+            ("file", "foo/bar.txt", Some("txt")),
+            ("file with multiple dots", "foo/bar.tar.gz", Some("gz")),
+            ("file without extension", "foo/bar", None),
+            ("hidden file", "foo/.bashrc", None),
+            ("hidden file with extension", "foo/.bashrc.bak", Some("bak")),
+            ("file with trailing dot", "foo/bar.", None),
+            ("dot in parent", "foo.d/bar", None),
+            ("dir", "foo/bar.d/", None),
+            ("dir root", "/", None),
+        ];
+
+        for (name, input, expect) in cases {
+            let actual = get_extension(input);
+            assert_eq!(actual, expect, "{name}")
+        }
+    }
+
     #[test]
     fn test_build_abs_path() {
         let cases = vec![
@@ -309,6 +438,7 @@ mod tests {
             ("input empty with root /", "/", "", ""),
             ("input dir with root /", "/", "def/", "def/"),
             ("input file with root /", "/", "def", "def"),
+            ("input file with leading /", "/abc/", "/def", "abc/def"),
         ];
 
         for (name, root, input, expect) in cases {
@@ -377,4 +507,65 @@ mod tests {
             assert_eq!(actual, expect, "{name}")
         }
     }
+
+    /// Paths built from a small alphabet that is likely to hit edge cases.
+    fn path_strategy() -> impl Strategy<Value = String> {
+        "[ a./é数]{0,24}"
+    }
+
+    proptest! {
+        #[test]
+        fn proptest_normalize_path(path in path_strategy()) {
+            let p = normalize_path(&path);
+
+            prop_assert_eq!(normalize_path(&p), p.clone(), "must be idempotent");
+            prop_assert!(p == "/" || !p.starts_with('/'));
+            prop_assert!(!p.contains("//"));
+            prop_assert!(p == "/" || p.trim() == p, "must not contain outer whitespace");
+            prop_assert!(
+                !has_dot_segments(path.trim()) || has_dot_segments(&p),
+                "dot segments must be kept"
+            );
+            if p != "/" && path.trim().ends_with('/') {
+                prop_assert!(p.ends_with('/'), "dir must be kept as dir");
+            }
+        }
+
+        #[test]
+        fn proptest_normalize_root(root in path_strategy()) {
+            let r = normalize_root(&root);
+
+            prop_assert_eq!(normalize_root(&r), r.clone(), "must be idempotent");
+            prop_assert!(r.starts_with('/') && r.ends_with('/'));
+            prop_assert!(!r.contains("//"));
+        }
+
+        #[test]
+        fn proptest_build_abs_path(root in path_strategy(), path in path_strategy()) {
+            let root = normalize_root(&root);
+            let path = normalize_path(&path);
+            let abs = build_abs_path(&root, &path);
+
+            prop_assert!(!abs.starts_with('/'));
+            prop_assert!(abs.starts_with(&root[1..]));
+            prop_assert!(!abs.contains("//"));
+        }
+
+        #[test]
+        fn proptest_get_basename_and_parent(path in path_strategy()) {
+            let p = normalize_path(&path);
+            let parent = get_parent(&p);
+            let name = get_basename(&p);
+
+            prop_assert!(parent.ends_with('/'));
+            prop_assert!(validate_path(name, if p.ends_with('/') { EntryMode::DIR } else { EntryMode::FILE }));
+            if p == "/" {
+                prop_assert_eq!(name, "/");
+            } else if parent == "/" {
+                prop_assert_eq!(name, p.as_str());
+            } else {
+                prop_assert_eq!(format!("{parent}{name}"), p.clone());
+            }
+        }
+    }
 }
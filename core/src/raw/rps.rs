@@ -17,12 +17,87 @@
 
 use http::Request;
 
+use crate::raw::oio;
 use crate::*;
 
 /// Reply for `create_dir` operation
 #[derive(Debug, Clone, Default)]
 pub struct RpCreateDir {}
 
+/// Reply for `create_symlink` operation
+#[derive(Debug, Clone, Default)]
+pub struct RpCreateSymlink {}
+
+/// Reply for `set_permissions` operation
+#[derive(Debug, Clone, Default)]
+pub struct RpSetPermissions {}
+
+/// Reply for `create_snapshot` operation
+#[derive(Debug, Clone, Default)]
+pub struct RpCreateSnapshot {
+    snapshot: String,
+}
+
+impl RpCreateSnapshot {
+    /// Create a new reply for `create_snapshot`.
+    pub fn new(snapshot: &str) -> Self {
+        Self {
+            snapshot: snapshot.to_string(),
+        }
+    }
+
+    /// Get the id of created snapshot.
+    pub fn snapshot(&self) -> &str {
+        &self.snapshot
+    }
+}
+
+/// Reply for `concat` operation
+#[derive(Debug, Clone, Default)]
+pub struct RpConcat {}
+
+/// Reply for `lease` operation
+#[derive(Debug, Clone, Default)]
+pub struct RpLease {
+    lease_id: Option<String>,
+}
+
+impl RpLease {
+    /// Create a new reply for `lease`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the id of acquired lease.
+    pub fn with_lease_id(mut self, lease_id: &str) -> Self {
+        self.lease_id = Some(lease_id.to_string());
+        self
+    }
+
+    /// Get the id of acquired lease.
+    pub fn lease_id(&self) -> Option<&str> {
+        self.lease_id.as_deref()
+    }
+}
+
+/// Reply for `list_roots` operation
+#[derive(Debug, Clone, Default)]
+pub struct RpListRoots {
+    entries: Vec<oio::Entry>,
+}
+
+impl RpListRoots {
+    /// Create a new reply for `list_roots`.
+    pub fn new(entries: Vec<oio::Entry>) -> Self {
+        Self { entries }
+    }
+
+    /// Consume reply to get the entries of roots.
+    pub fn into_entries(self) -> Vec<oio::Entry> {
+        self.entries
+    }
+}
+
 /// Reply for `delete` operation
 #[derive(Debug, Clone, Default)]
 pub struct RpDelete {}
@@ -107,6 +182,11 @@ pub struct RpRead {
     /// It's ok to leave size as empty, but it's recommended to set size if possible. We will use
     /// this size as hint to do some optimization like avoid an extra stat or read.
     size: Option<u64>,
+    /// Etag is the etag of the object returned by this read operation.
+    ///
+    /// It's used to make sure following range reads of the same reader are
+    /// reading the same object.
+    etag: Option<String>,
 }
 
 impl RpRead {
@@ -128,6 +208,17 @@ impl RpRead {
         self.size = size;
         self
     }
+
+    /// Got the etag of the object returned by this read operation.
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// Set the etag of the object returned by this read operation.
+    pub fn with_etag(mut self, etag: Option<String>) -> Self {
+        self.etag = etag;
+        self
+    }
 }
 
 /// Reply for `batch` operation.
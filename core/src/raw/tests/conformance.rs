@@ -0,0 +1,177 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use bytes::Bytes;
+use futures::TryStreamExt;
+use rand::thread_rng;
+use rand::RngCore;
+
+use crate::*;
+
+/// Run conformance tests against given operator.
+///
+/// This is designed for services implemented outside of OpenDAL: build the
+/// operator via [`Operator::from_accessor`] and make sure it behaves like
+/// built-in services. Only checks allowed by the capability of operator
+/// will be run, all test data will be created under `conformance/`.
+///
+/// The basic checks run first, then the full matrix of
+/// [`testkit`][crate::testkit].
+///
+/// Returns the first failed basic check as [`ErrorKind::Unexpected`] with a
+/// `check` context, or all failed cases of the matrix as context.
+///
+/// # Examples
+///
+/// ```ignore
+/// use opendal::raw::tests::run_conformance_tests;
+/// use opendal::Operator;
+///
+/// #[tokio::test]
+/// async fn test_my_service() -> opendal::Result<()> {
+///     let op = Operator::from_accessor(MyAccessor::default());
+///     run_conformance_tests(&op).await
+/// }
+/// ```
+pub async fn run_conformance_tests(op: &Operator) -> Result<()> {
+    run_basic_checks(op).await?;
+    crate::testkit::run(op).await.into_result()
+}
+
+async fn run_basic_checks(op: &Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    let dir = format!("conformance/{}/", uuid::Uuid::new_v4());
+
+    if cap.stat {
+        let path = format!("{dir}not_exist");
+        match op.stat(&path).await {
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => {
+                return Err(failed("stat_not_exist", "stat must return NotFound").set_source(err))
+            }
+            Ok(_) => return Err(failed("stat_not_exist", "stat must return NotFound")),
+        }
+    }
+
+    if !cap.write {
+        return Ok(());
+    }
+
+    let path = format!("{dir}file");
+    let content = gen_bytes();
+    op.write(&path, content.clone()).await?;
+
+    if cap.stat {
+        let meta = op.stat(&path).await?;
+        ensure(
+            meta.mode() == EntryMode::FILE,
+            "stat_file",
+            "mode must be file",
+        )?;
+        ensure(
+            meta.content_length() == content.len() as u64,
+            "stat_file",
+            "content length must be the same as written",
+        )?;
+    }
+
+    if cap.read {
+        let bs = op.read(&path).await?;
+        ensure(
+            bs == content,
+            "read_full",
+            "content must be the same as written",
+        )?;
+
+        if cap.read_with_range {
+            let bs = op.read_with(&path).range(1..4).await?;
+            ensure(
+                bs == content[1..4],
+                "read_range",
+                "content must match range",
+            )?;
+        }
+    }
+
+    if cap.list {
+        let entries: Vec<Entry> = op.lister(&dir).await?.try_collect().await?;
+        ensure(
+            entries.iter().any(|e| e.path() == path),
+            "list",
+            "written file must be listed",
+        )?;
+    }
+
+    if cap.create_dir {
+        let sub = format!("{dir}sub/");
+        op.create_dir(&sub).await?;
+        if cap.stat {
+            let meta = op.stat(&sub).await?;
+            ensure(
+                meta.mode() == EntryMode::DIR,
+                "create_dir",
+                "mode must be dir",
+            )?;
+        }
+        if cap.delete {
+            op.delete(&sub).await?;
+        }
+    }
+
+    if cap.delete {
+        op.delete(&path).await?;
+        // Delete must be idempotent.
+        op.delete(&path).await?;
+        if cap.stat {
+            ensure(!op.is_exist(&path).await?, "delete", "file must not exist")?;
+        }
+        op.delete(&dir).await?;
+    }
+
+    Ok(())
+}
+
+fn gen_bytes() -> Bytes {
+    let mut content = vec![0; 4096];
+    thread_rng().fill_bytes(&mut content);
+    Bytes::from(content)
+}
+
+fn failed(check: &'static str, message: &'static str) -> Error {
+    Error::new(ErrorKind::Unexpected, message).with_context("check", check)
+}
+
+fn ensure(cond: bool, check: &'static str, message: &'static str) -> Result<()> {
+    if cond {
+        Ok(())
+    } else {
+        Err(failed(check, message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_run_conformance_tests() -> Result<()> {
+        let acc = Memory::default().build()?;
+        let op = Operator::from_accessor(acc);
+        run_conformance_tests(&op).await
+    }
+}
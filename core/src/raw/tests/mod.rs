@@ -25,6 +25,9 @@ mod write;
 pub use write::WriteAction;
 pub use write::WriteChecker;
 
+mod conformance;
+pub use conformance::run_conformance_tests;
+
 mod utils;
 pub use utils::init_test_service;
 pub use utils::TEST_RUNTIME;
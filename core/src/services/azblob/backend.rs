@@ -16,16 +16,23 @@
 // under the License.
 
 use std::collections::HashMap;
+use std::env;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::RwLock;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
+use bytes::Buf;
 use http::header::CONTENT_TYPE;
+use http::HeaderValue;
 use http::StatusCode;
 use log::debug;
+use log::warn;
 use reqsign::AzureStorageConfig;
 use reqsign::AzureStorageLoader;
 use reqsign::AzureStorageSigner;
@@ -37,7 +44,15 @@ use super::error::parse_error;
 use super::lister::AzblobLister;
 use super::writer::AzblobWriter;
 use crate::raw::*;
+use crate::services::azblob::core::parse_access_tier;
+use crate::services::azblob::core::parse_immutability;
+use crate::services::azblob::core::parse_is_current_version;
+use crate::services::azblob::core::parse_lease_id;
+use crate::services::azblob::core::parse_snapshot;
+use crate::services::azblob::core::parse_tag_count;
+use crate::services::azblob::core::parse_version_id;
 use crate::services::azblob::core::AzblobCore;
+use crate::services::azblob::core::BlobTags;
 use crate::services::azblob::writer::AzblobWriters;
 use crate::*;
 
@@ -52,6 +67,21 @@ const KNOWN_AZBLOB_ENDPOINT_SUFFIX: &[&str] = &[
 ];
 
 const AZBLOB_BATCH_LIMIT: usize = 256;
+/// Writes are staged as blocks of 8 MiB by default, small writes will be
+/// buffered instead of being staged as tiny blocks.
+const AZBLOB_DEFAULT_WRITE_BLOCK_SIZE: usize = 8 * 1024 * 1024;
+/// 2022-11-02 is the version supported by Azurite V3 and used by Azure
+/// Portal, we use this version to make sure most our developer happy.
+const AZBLOB_DEFAULT_API_VERSION: &str = "2022-11-02";
+/// The oldest version that supports all features we use, like immutability
+/// policy and legal hold.
+const AZBLOB_MIN_API_VERSION: &str = "2020-10-02";
+/// The newest version that we have verified.
+const AZBLOB_MAX_API_VERSION: &str = "2025-01-05";
+/// The well-known account of Azurite.
+const AZURITE_ACCOUNT_NAME: &str = "devstoreaccount1";
+const AZURITE_ACCOUNT_KEY: &str =
+    "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==";
 /// Azure Storage Blob services support.
 #[derive(Default, Deserialize, Clone)]
 pub struct AzblobConfig {
@@ -71,26 +101,43 @@ pub struct AzblobConfig {
     /// - Azurite: `http://127.0.0.1:10000/devstoreaccount1`
     pub endpoint: Option<String>,
 
+    /// The secondary endpoint of Azblob service backend, read operations
+    /// will fail over to it while the primary endpoint is unavailable.
+    ///
+    /// For RA-GRS accounts, it's like `https://accountname-secondary.blob.core.windows.net`.
+    pub secondary_endpoint: Option<String>,
+
     /// The account name of Azblob service backend.
     pub account_name: Option<String>,
 
     /// The account key of Azblob service backend.
-    pub account_key: Option<String>,
+    pub account_key: Option<Secret>,
 
     /// The encryption key of Azblob service backend.
-    pub encryption_key: Option<String>,
+    pub encryption_key: Option<Secret>,
 
     /// The encryption key sha256 of Azblob service backend.
-    pub encryption_key_sha256: Option<String>,
+    pub encryption_key_sha256: Option<Secret>,
 
     /// The encryption algorithm of Azblob service backend.
     pub encryption_algorithm: Option<String>,
 
     /// The sas token of Azblob service backend.
-    pub sas_token: Option<String>,
+    pub sas_token: Option<Secret>,
 
     /// The maximum batch operations of Azblob service backend.
     pub batch_max_operations: Option<usize>,
+
+    /// The size of blocks staged while writing block blobs, default to
+    /// 8 MiB.
+    pub write_block_size: Option<usize>,
+
+    /// The local emulator that this backend connects to, only `azurite`
+    /// is supported.
+    pub emulator: Option<String>,
+
+    /// The `x-ms-version` sent with every request, default to `2022-11-02`.
+    pub api_version: Option<String>,
 }
 
 impl Debug for AzblobConfig {
@@ -100,6 +147,10 @@ impl Debug for AzblobConfig {
         ds.field("root", &self.root);
         ds.field("container", &self.container);
         ds.field("endpoint", &self.endpoint);
+        ds.field("secondary_endpoint", &self.secondary_endpoint);
+        ds.field("emulator", &self.emulator);
+        ds.field("api_version", &self.api_version);
+        ds.field("write_block_size", &self.write_block_size);
 
         if self.account_name.is_some() {
             ds.field("account_name", &"<redacted>");
@@ -120,6 +171,7 @@ impl Debug for AzblobConfig {
 pub struct AzblobBuilder {
     config: AzblobConfig,
     http_client: Option<HttpClient>,
+    http_client_builder: HttpClientBuilder,
 }
 
 impl Debug for AzblobBuilder {
@@ -166,6 +218,37 @@ impl AzblobBuilder {
         self
     }
 
+    /// Set secondary endpoint of this backend.
+    ///
+    /// Read operations like `read` and `stat` will fail over to the
+    /// secondary endpoint if the primary one returns server errors or can't
+    /// be connected. Write operations will always go to the primary endpoint.
+    ///
+    /// For RA-GRS accounts, it's like `https://accountname-secondary.blob.core.windows.net`.
+    pub fn secondary_endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.config.secondary_endpoint = Some(endpoint.trim_end_matches('/').to_string());
+        }
+
+        self
+    }
+
+    /// Use the preset of given local emulator, only [`Emulator::Azurite`]
+    /// is supported.
+    ///
+    /// The preset fills the following configs if they are not set:
+    ///
+    /// - endpoint: `http://127.0.0.1:10000/devstoreaccount1`
+    /// - account_name: `devstoreaccount1`
+    /// - account_key: the well-known key of Azurite.
+    ///
+    /// Immutability policies, legal hold and batch operations will be
+    /// disabled since they are not supported by Azurite.
+    pub fn emulator(&mut self, emulator: Emulator) -> &mut Self {
+        self.config.emulator = Some(emulator.to_string());
+        self
+    }
+
     /// Set account_name of this backend.
     ///
     /// - If account_name is set, we will take user's input first.
@@ -184,7 +267,7 @@ impl AzblobBuilder {
     /// - If not, we will try to load it from environment.
     pub fn account_key(&mut self, account_key: &str) -> &mut Self {
         if !account_key.is_empty() {
-            self.config.account_key = Some(account_key.to_string());
+            self.config.account_key = Some(account_key.into());
         }
 
         self
@@ -204,7 +287,7 @@ impl AzblobBuilder {
     /// Please use `server_side_encryption_with_*` helpers if even possible.
     pub fn encryption_key(&mut self, v: &str) -> &mut Self {
         if !v.is_empty() {
-            self.config.encryption_key = Some(v.to_string());
+            self.config.encryption_key = Some(v.into());
         }
 
         self
@@ -224,7 +307,7 @@ impl AzblobBuilder {
     /// Please use `server_side_encryption_with_*` helpers if even possible.
     pub fn encryption_key_sha256(&mut self, v: &str) -> &mut Self {
         if !v.is_empty() {
-            self.config.encryption_key_sha256 = Some(v.to_string());
+            self.config.encryption_key_sha256 = Some(v.into());
         }
 
         self
@@ -266,9 +349,12 @@ impl AzblobBuilder {
     pub fn server_side_encryption_with_customer_key(&mut self, key: &[u8]) -> &mut Self {
         // Only AES256 is supported for now
         self.config.encryption_algorithm = Some("AES256".to_string());
-        self.config.encryption_key = Some(BASE64_STANDARD.encode(key));
-        self.config.encryption_key_sha256 =
-            Some(BASE64_STANDARD.encode(Sha256::digest(key).as_slice()));
+        self.config.encryption_key = Some(BASE64_STANDARD.encode(key).into());
+        self.config.encryption_key_sha256 = Some(
+            BASE64_STANDARD
+                .encode(Sha256::digest(key).as_slice())
+                .into(),
+        );
         self
     }
 
@@ -281,7 +367,7 @@ impl AzblobBuilder {
     /// for more info.
     pub fn sas_token(&mut self, sas_token: &str) -> &mut Self {
         if !sas_token.is_empty() {
-            self.config.sas_token = Some(sas_token.to_string());
+            self.config.sas_token = Some(sas_token.into());
         }
 
         self
@@ -298,6 +384,42 @@ impl AzblobBuilder {
         self
     }
 
+    /// Set the max idle connections per host in the pool of http client.
+    ///
+    /// Ignored if [`Self::http_client`] is set.
+    pub fn pool_max_idle_per_host(&mut self, max: usize) -> &mut Self {
+        self.http_client_builder =
+            std::mem::take(&mut self.http_client_builder).pool_max_idle_per_host(max);
+        self
+    }
+
+    /// Set the timeout for idle connections in the pool of http client.
+    ///
+    /// Ignored if [`Self::http_client`] is set.
+    pub fn pool_idle_timeout(&mut self, timeout: std::time::Duration) -> &mut Self {
+        self.http_client_builder =
+            std::mem::take(&mut self.http_client_builder).pool_idle_timeout(timeout);
+        self
+    }
+
+    /// Only use HTTP/2 for connections of http client.
+    ///
+    /// Ignored if [`Self::http_client`] is set.
+    pub fn http2_prior_knowledge(&mut self, enabled: bool) -> &mut Self {
+        self.http_client_builder =
+            std::mem::take(&mut self.http_client_builder).http2_prior_knowledge(enabled);
+        self
+    }
+
+    /// Set the interval of TCP keepalive for connections of http client.
+    ///
+    /// Ignored if [`Self::http_client`] is set.
+    pub fn tcp_keepalive(&mut self, interval: std::time::Duration) -> &mut Self {
+        self.http_client_builder =
+            std::mem::take(&mut self.http_client_builder).tcp_keepalive(interval);
+        self
+    }
+
     /// Set maximum batch operations of this backend.
     pub fn batch_max_operations(&mut self, batch_max_operations: usize) -> &mut Self {
         self.config.batch_max_operations = Some(batch_max_operations);
@@ -305,6 +427,32 @@ impl AzblobBuilder {
         self
     }
 
+    /// Set the size of blocks staged while writing block blobs.
+    ///
+    /// Written data will be buffered and staged as blocks of exactly this
+    /// size except the last one, default to 8 MiB. Data smaller than the
+    /// block size will be uploaded by a single `Put Blob`.
+    pub fn write_block_size(&mut self, size: usize) -> &mut Self {
+        if size > 0 {
+            self.config.write_block_size = Some(size);
+        }
+
+        self
+    }
+
+    /// Set the API version (`x-ms-version`) of this backend.
+    ///
+    /// Newer versions are required by features like the `Cold` access tier.
+    /// The version must be between `2020-10-02` and `2025-01-05`, default to
+    /// `2022-11-02` which is supported by Azurite.
+    pub fn api_version(&mut self, api_version: &str) -> &mut Self {
+        if !api_version.is_empty() {
+            self.config.api_version = Some(api_version.to_string());
+        }
+
+        self
+    }
+
     /// from_connection_string will make a builder from connection string
     ///
     /// connection string looks like:
@@ -389,6 +537,167 @@ impl AzblobBuilder {
 
         Ok(builder)
     }
+
+    /// Create the container configured on this builder, which is useful
+    /// for provisioning tools and tests against Azurite.
+    ///
+    /// Returns [`ErrorKind::AlreadyExists`] if the container exists.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use opendal::services::Azblob;
+    /// use opendal::Emulator;
+    ///
+    /// # async fn example() -> opendal::Result<()> {
+    /// let mut builder = Azblob::default();
+    /// builder.container("test").emulator(Emulator::Azurite);
+    /// builder.create_container().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_container(&self) -> Result<()> {
+        let backend = self.clone().build()?;
+        let resp = backend.core.azblob_create_container().await?;
+
+        match resp.status() {
+            StatusCode::CREATED => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            StatusCode::CONFLICT => {
+                let err = parse_error(resp).await?;
+                Err(
+                    Error::new(ErrorKind::AlreadyExists, "container already exists")
+                        .with_operation("AzblobBuilder::create_container")
+                        .with_context("container", &self.config.container)
+                        .set_source(err),
+                )
+            }
+            _ => Err(parse_error(resp)
+                .await?
+                .with_operation("AzblobBuilder::create_container")),
+        }
+    }
+
+    /// Delete the container configured on this builder with all blobs in it.
+    ///
+    /// Returns [`ErrorKind::NotFound`] if the container doesn't exist.
+    pub async fn delete_container(&self) -> Result<()> {
+        let backend = self.clone().build()?;
+        let resp = backend.core.azblob_delete_container().await?;
+
+        match resp.status() {
+            StatusCode::ACCEPTED => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp)
+                .await?
+                .with_operation("AzblobBuilder::delete_container")),
+        }
+    }
+
+    /// List the names of all containers in the account.
+    ///
+    /// The container configured on this builder is not required.
+    pub async fn list_containers(&self) -> Result<Vec<String>> {
+        let mut builder = self.clone();
+        // Listing containers doesn't belong to any container, use the root
+        // container to pass the validation.
+        if builder.config.container.is_empty() {
+            builder.config.container = "$root".to_string();
+        }
+        let backend = builder.build()?;
+
+        let containers = backend
+            .core
+            .azblob_list_all_containers()
+            .await
+            .map_err(|err| err.with_operation("AzblobBuilder::list_containers"))?;
+        Ok(containers.into_iter().map(|v| v.name).collect())
+    }
+}
+
+impl AzblobBuilder {
+    /// Fill the configs that are not set by users with the preset of Azurite.
+    ///
+    /// ref: <https://learn.microsoft.com/en-us/azure/storage/common/storage-use-azurite#http-connection-strings>
+    fn apply_azurite(&mut self) {
+        if self.config.endpoint.is_none() {
+            self.config.endpoint = Some(Emulator::Azurite.endpoint().to_string());
+        }
+        if self.config.sas_token.is_some() {
+            return;
+        }
+        if self.config.account_name.is_none() {
+            self.config.account_name = Some(AZURITE_ACCOUNT_NAME.to_string());
+        }
+        if self.config.account_key.is_none() {
+            self.config.account_key = Some(AZURITE_ACCOUNT_KEY.into());
+        }
+    }
+
+    /// Check the combinations of configs and report all problems at once.
+    fn validate(&self) -> Result<()> {
+        let cfg = &self.config;
+        let mut errs = ConfigError::new(Scheme::Azblob);
+
+        if cfg.container.is_empty() {
+            errs.push(
+                "container",
+                "container is empty, set it to the name of blob container",
+            );
+        }
+        match &cfg.endpoint {
+            None => errs.push(
+                "endpoint",
+                "endpoint is empty, set it like `https://<account_name>.blob.core.windows.net` \
+                 or use the `azurite` emulator",
+            ),
+            Some(v) => {
+                if let Err(err) = Endpoint::parse(v) {
+                    errs.push("endpoint", format!("endpoint is invalid: {err}"));
+                }
+            }
+        }
+        if cfg.account_key.is_some()
+            && cfg.account_name.is_none()
+            && cfg
+                .endpoint
+                .as_deref()
+                .and_then(infer_storage_name_from_endpoint)
+                .is_none()
+        {
+            errs.push(
+                "account_name",
+                "account_key set but account_name is missing and can't be inferred from endpoint",
+            );
+        }
+        if cfg.encryption_key.is_some() != cfg.encryption_key_sha256.is_some() {
+            errs.push(
+                "encryption_key_sha256",
+                "encryption_key and encryption_key_sha256 must be set together",
+            );
+        }
+        if matches!(cfg.encryption_algorithm.as_deref(), Some(v) if v != "AES256") {
+            errs.push(
+                "encryption_algorithm",
+                "encryption_algorithm value must be AES256",
+            );
+        }
+        if cfg.encryption_key.is_some() && cfg.encryption_algorithm.is_none() {
+            errs.push(
+                "encryption_algorithm",
+                "encryption_key set but encryption_algorithm not provided, set it to AES256",
+            );
+        }
+        if let Some(Err(err)) = cfg.api_version.as_deref().map(check_api_version) {
+            errs.push("api_version", err);
+        }
+
+        errs.finish()
+    }
 }
 
 impl Builder for AzblobBuilder {
@@ -402,6 +711,7 @@ impl Builder for AzblobBuilder {
         AzblobBuilder {
             config,
             http_client: None,
+            http_client_builder: HttpClientBuilder::default(),
         }
     }
 
@@ -411,6 +721,22 @@ impl Builder for AzblobBuilder {
         let root = normalize_root(&self.config.root.take().unwrap_or_default());
         debug!("backend use root {}", root);
 
+        let emulator = match &self.config.emulator {
+            Some(v) => Some(v.parse::<Emulator>().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Azblob)
+            })?),
+            None => None,
+        };
+        match emulator {
+            Some(Emulator::Azurite) => self.apply_azurite(),
+            Some(v) => return Err(v.unsupported(Scheme::Azblob)),
+            None => {}
+        }
+        debug!("backend use emulator {:?}", emulator);
+
+        self.validate()?;
+
         // Handle endpoint, region and container name.
         let container = match self.config.container.is_empty() {
             false => Ok(&self.config.container),
@@ -426,42 +752,72 @@ impl Builder for AzblobBuilder {
                 .with_operation("Builder::build")
                 .with_context("service", Scheme::Azblob)),
         }?;
+        // Validate endpoint early so that users will not get confusing
+        // errors while sending requests.
+        let endpoint = Endpoint::parse(&endpoint)
+            .map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Azblob)
+            })?
+            .to_string();
         debug!("backend use endpoint {}", &container);
 
+        let secondary_endpoint = match &self.config.secondary_endpoint {
+            Some(v) => Some(
+                Endpoint::parse(v)
+                    .map_err(|err| {
+                        err.with_operation("Builder::build")
+                            .with_context("service", Scheme::Azblob)
+                    })?
+                    .to_string(),
+            ),
+            None => None,
+        };
+        if let Some(v) = &secondary_endpoint {
+            debug!("backend use secondary endpoint {}", v);
+        }
+
         let client = if let Some(client) = self.http_client.take() {
             client
         } else {
-            HttpClient::new().map_err(|err| {
+            self.http_client_builder.clone().build().map_err(|err| {
                 err.with_operation("Builder::build")
                     .with_context("service", Scheme::Azblob)
             })?
         };
 
+        let account_name = self
+            .config
+            .account_name
+            .clone()
+            .or_else(|| infer_storage_name_from_endpoint(endpoint.as_str()));
+        // Presign requires a SAS token, an account key or a bearer token to
+        // mint user delegation SAS.
+        let presign = self.config.sas_token.is_some()
+            || self.config.account_key.is_some()
+            || has_bearer_token_source();
         let config_loader = AzureStorageConfig {
-            account_name: self
-                .config
-                .account_name
-                .clone()
-                .or_else(|| infer_storage_name_from_endpoint(endpoint.as_str())),
-            account_key: self.config.account_key.clone(),
-            sas_token: self.config.sas_token.clone(),
+            account_name: account_name.clone(),
+            account_key: self.config.account_key.take().map(Secret::into_secret),
+            sas_token: self.config.sas_token.take().map(Secret::into_secret),
             ..Default::default()
         };
 
         let encryption_key =
             match &self.config.encryption_key {
                 None => None,
-                Some(v) => Some(build_header_value(v).map_err(|err| {
-                    err.with_context("key", "server_side_encryption_customer_key")
-                })?),
+                Some(v) => Some(build_sensitive_header_value(v.expose_secret()).map_err(
+                    |err| err.with_context("key", "server_side_encryption_customer_key"),
+                )?),
             };
 
-        let encryption_key_sha256 = match &self.config.encryption_key_sha256 {
-            None => None,
-            Some(v) => Some(build_header_value(v).map_err(|err| {
-                err.with_context("key", "server_side_encryption_customer_key_sha256")
-            })?),
-        };
+        let encryption_key_sha256 =
+            match &self.config.encryption_key_sha256 {
+                None => None,
+                Some(v) => Some(build_sensitive_header_value(v.expose_secret()).map_err(
+                    |err| err.with_context("key", "server_side_encryption_customer_key_sha256"),
+                )?),
+            };
 
         let encryption_algorithm = match &self.config.encryption_algorithm {
             None => None,
@@ -488,26 +844,94 @@ impl Builder for AzblobBuilder {
             .batch_max_operations
             .unwrap_or(AZBLOB_BATCH_LIMIT);
 
+        let write_block_size = self
+            .config
+            .write_block_size
+            .unwrap_or(AZBLOB_DEFAULT_WRITE_BLOCK_SIZE);
+
+        let api_version = self
+            .config
+            .api_version
+            .as_deref()
+            .unwrap_or(AZBLOB_DEFAULT_API_VERSION);
+        let api_version = HeaderValue::from_str(api_version).map_err(|err| {
+            Error::new(ErrorKind::ConfigInvalid, "api_version is invalid")
+                .with_context("service", Scheme::Azblob)
+                .set_source(err)
+        })?;
+
         debug!("backend build finished: {:?}", &self);
         Ok(AzblobBackend {
             core: Arc::new(AzblobCore {
                 root,
                 endpoint,
+                secondary_endpoint,
                 encryption_key,
                 encryption_key_sha256,
                 encryption_algorithm,
                 container: self.config.container.clone(),
+                account_name,
 
                 client,
                 loader: cred_loader,
+                credential: CredentialCache::default(),
+                credential_override: RwLock::default(),
                 signer,
                 batch_max_operations,
+                api_version: RwLock::new(api_version),
+                primary_unavailable_until: Mutex::default(),
             }),
-            has_sas_token: self.config.sas_token.is_some(),
+            emulator,
+            presign,
+            write_block_size,
         })
     }
 }
 
+/// Check if the environment provides bearer tokens via workload identity or
+/// managed identity.
+fn has_bearer_token_source() -> bool {
+    [
+        "AZURE_FEDERATED_TOKEN_FILE",
+        "AZURE_CLIENT_ID",
+        "IDENTITY_ENDPOINT",
+        "MSI_ENDPOINT",
+    ]
+    .iter()
+    .any(|v| env::var_os(v).is_some())
+}
+
+/// Check if the api version is supported by us.
+pub(super) fn check_api_version(v: &str) -> std::result::Result<(), String> {
+    // Versions are dates like `2022-11-02`, so they can be compared
+    // as strings once the format is checked.
+    if v.len() != 10 || chrono::NaiveDate::parse_from_str(v, "%Y-%m-%d").is_err() {
+        return Err(format!(
+            "api_version {v} is invalid, it must be a date like `2022-11-02`"
+        ));
+    }
+    if v < AZBLOB_MIN_API_VERSION || v > AZBLOB_MAX_API_VERSION {
+        return Err(format!(
+            "api_version {v} is not supported, it must be between \
+             {AZBLOB_MIN_API_VERSION} and {AZBLOB_MAX_API_VERSION}"
+        ));
+    }
+    Ok(())
+}
+
+/// Azblob only accepts a lease duration in whole seconds between 15 and 60,
+/// infinite lease is represented by `None`.
+fn check_lease_duration(duration: Duration) -> Result<()> {
+    if duration.subsec_nanos() != 0 || !(15..=60).contains(&duration.as_secs()) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "lease duration must be whole seconds between 15 and 60",
+        )
+        .with_context("duration", format!("{duration:?}")));
+    }
+    Ok(())
+}
+
 fn infer_storage_name_from_endpoint(endpoint: &str) -> Option<String> {
     let endpoint: &str = endpoint
         .strip_prefix("http://")
@@ -536,7 +960,24 @@ fn infer_storage_name_from_endpoint(endpoint: &str) -> Option<String> {
 #[derive(Debug, Clone)]
 pub struct AzblobBackend {
     core: Arc<AzblobCore>,
-    has_sas_token: bool,
+    emulator: Option<Emulator>,
+    /// Whether the credential is able to presign requests.
+    presign: bool,
+    write_block_size: usize,
+}
+
+impl AzblobBackend {
+    async fn get_tags(&self, path: &str, args: &OpStat) -> Result<HashMap<String, String>> {
+        let resp = self.core.azblob_get_blob_tags(path, args).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: BlobTags =
+            quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+        Ok(output.into_map())
+    }
 }
 
 #[async_trait]
@@ -549,14 +990,24 @@ impl Accessor for AzblobBackend {
     type BlockingLister = ();
 
     fn info(&self) -> AccessorInfo {
+        // Azurite doesn't support immutable storage and blob batch.
+        //
+        // ref: <https://github.com/Azure/Azurite#differences-between-azurite-and-azure-storage>
+        let is_azurite = self.emulator == Some(Emulator::Azurite);
+
         let mut am = AccessorInfo::default();
         am.set_scheme(Scheme::Azblob)
             .set_root(&self.core.root)
             .set_name(&self.core.container)
+            .set_endpoint(&self.core.endpoint)
+            .set_signer(self.core.clone())
             .set_native_capability(Capability {
                 stat: true,
                 stat_with_if_match: true,
                 stat_with_if_none_match: true,
+                stat_root: true,
+                stat_with_snapshot: true,
+                stat_with_version: true,
 
                 read: true,
                 read_can_next: true,
@@ -564,27 +1015,74 @@ impl Accessor for AzblobBackend {
                 read_with_if_match: true,
                 read_with_if_none_match: true,
                 read_with_override_content_disposition: true,
+                read_with_snapshot: true,
+                read_with_version: true,
+                read_with_lease_id: true,
 
                 write: true,
                 write_can_empty: true,
                 write_can_append: true,
+                write_can_multi: true,
                 write_with_cache_control: true,
                 write_with_content_type: true,
+                write_with_if_match: true,
+                write_with_if_none_match: true,
+                write_with_retention: !is_azurite,
+                write_with_legal_hold: !is_azurite,
+                write_with_compose_sources: true,
+                write_with_tags: true,
+                write_with_storage_class: true,
+                write_with_lease_id: true,
+                // The max block size of azblob is 4000 MiB.
+                //
+                // ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/put-block>
+                write_multi_max_size: if cfg!(target_pointer_width = "64") {
+                    Some(4000 * 1024 * 1024)
+                } else {
+                    Some(usize::MAX)
+                },
+                // The max blocks of a blob is 50000.
+                //
+                // ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/put-block-list>
+                write_multi_max_parts: Some(50000),
 
                 delete: true,
+                delete_with_lease_id: true,
+                delete_with_snapshots: true,
+                delete_with_version: true,
+                delete_with_must_exist: true,
                 copy: true,
+                copy_with_storage_class: true,
+                copy_with_content_type: true,
+                copy_with_cache_control: true,
+                copy_with_user_metadata: true,
+                copy_with_tags: true,
+                copy_from_url: true,
+
+                create_snapshot: true,
+                lease: true,
+                concat: true,
 
                 list: true,
                 list_without_recursive: true,
+                list_with_limit: true,
+                // The max page size of Azblob list is 5000.
+                //
+                // ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/list-blobs>
+                list_max_page_size: Some(5000),
                 list_with_recursive: true,
-
-                presign: self.has_sas_token,
-                presign_stat: self.has_sas_token,
-                presign_read: self.has_sas_token,
-                presign_write: self.has_sas_token,
-
-                batch: true,
-                batch_delete: true,
+                list_with_delimiter: true,
+                list_with_snapshots: true,
+                list_with_versions: true,
+                list_roots: true,
+
+                presign: self.presign,
+                presign_stat: self.presign,
+                presign_read: self.presign,
+                presign_write: self.presign,
+
+                batch: !is_azurite,
+                batch_delete: !is_azurite,
                 batch_max_operations: Some(self.core.batch_max_operations),
 
                 ..Default::default()
@@ -601,7 +1099,11 @@ impl Accessor for AzblobBackend {
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
                 let size = parse_content_length(resp.headers())?;
-                Ok((RpRead::new().with_size(size), resp.into_body()))
+                let etag = parse_etag(resp.headers())?.map(|v| v.to_string());
+                Ok((
+                    RpRead::new().with_size(size).with_etag(etag),
+                    resp.into_body(),
+                ))
             }
             StatusCode::RANGE_NOT_SATISFIABLE => Ok((RpRead::new(), IncomingAsyncBody::empty())),
             _ => Err(parse_error(resp).await?),
@@ -609,23 +1111,78 @@ impl Accessor for AzblobBackend {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        if args.append() && !args.compose_sources().is_empty() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "append blob can't be composed from sources",
+            ));
+        }
+        if args.append() && args.storage_class().is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "access tier is only supported by block blob",
+            ));
+        }
+
         let w = AzblobWriter::new(self.core.clone(), args.clone(), path.to_string());
         let w = if args.append() {
             AzblobWriters::Two(oio::AppendObjectWriter::new(w))
         } else {
-            AzblobWriters::One(oio::OneShotWriter::new(w))
+            AzblobWriters::One(oio::ExactBufWriter::new(
+                oio::MultipartUploadWriter::new(w, args.concurrent()),
+                self.write_block_size,
+            ))
         };
 
         Ok((RpWrite::default(), w))
     }
 
-    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.azblob_copy_blob(from, to).await?;
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        // Copy blob onto itself is not allowed, change the tier directly.
+        if let (true, None, Some(tier)) = (from == to, args.source_url(), args.storage_class()) {
+            let resp = self.core.azblob_set_blob_tier(from, tier).await?;
+            return match resp.status() {
+                StatusCode::OK | StatusCode::ACCEPTED => {
+                    resp.into_body().consume().await?;
+                    Ok(RpCopy::default())
+                }
+                _ => Err(parse_error(resp).await?),
+            };
+        }
+
+        let resp = self.core.azblob_copy_blob(from, to, &args).await?;
 
         let status = resp.status();
 
         match status {
             StatusCode::ACCEPTED => {
+                let headers = resp.headers().clone();
+                resp.into_body().consume().await?;
+                self.core.azblob_wait_copy(to, &headers).await?;
+            }
+            _ => return Err(parse_error(resp).await?),
+        }
+
+        // Copy Blob can't replace http headers, set them after copied.
+        if args.content_type().is_none() && args.cache_control().is_none() {
+            return Ok(RpCopy::default());
+        }
+        let resp = self
+            .core
+            .azblob_get_blob_properties(to, &OpStat::default())
+            .await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+        let current = resp.headers().clone();
+        resp.into_body().consume().await?;
+
+        let resp = self
+            .core
+            .azblob_set_blob_properties(to, &current, &args)
+            .await?;
+        match resp.status() {
+            StatusCode::OK => {
                 resp.into_body().consume().await?;
                 Ok(RpCopy::default())
             }
@@ -634,23 +1191,152 @@ impl Accessor for AzblobBackend {
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if path == "/" {
+            let resp = self.core.azblob_get_container_properties().await?;
+
+            return match resp.status() {
+                StatusCode::OK => {
+                    let mut meta = Metadata::new(EntryMode::DIR);
+                    if let Some(v) = parse_last_modified(resp.headers())? {
+                        meta.set_last_modified(v);
+                    }
+                    if let Some(v) = parse_etag(resp.headers())? {
+                        meta.set_etag(v);
+                    }
+                    Ok(RpStat::new(meta))
+                }
+                _ => Err(parse_error(resp).await?),
+            };
+        }
+
         let resp = self.core.azblob_get_blob_properties(path, &args).await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::OK => parse_into_metadata(path, resp.headers()).map(RpStat::new),
+            StatusCode::OK => {
+                let mut meta = parse_into_metadata(path, resp.headers())?;
+                parse_immutability(resp.headers(), &mut meta)?;
+                parse_access_tier(resp.headers(), &mut meta)?;
+                if let Some(v) = args.snapshot() {
+                    meta.set_snapshot(v);
+                }
+                if let Some(v) = parse_version_id(resp.headers())? {
+                    meta.set_version(v);
+                }
+                if let Some(v) = parse_is_current_version(resp.headers())? {
+                    meta.set_is_current(v);
+                }
+                // Tags require an extra request and the `t` permission of
+                // SAS, only fetch them while users asked explicitly.
+                if args
+                    .fields()
+                    .map_or(false, |fields| fields.contains(Metakey::Tags))
+                {
+                    // Only fetch tags while the blob has any.
+                    let tags = if parse_tag_count(resp.headers())? > 0 {
+                        self.get_tags(path, &args).await?
+                    } else {
+                        HashMap::new()
+                    };
+                    meta.set_tags(tags);
+                }
+                Ok(RpStat::new(meta))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        let mut sources = Vec::with_capacity(args.sources().len());
+        for source in args.sources() {
+            sources.push(self.core.azblob_source_url(source).await?);
+        }
+
+        let w = AzblobWriter::new(
+            self.core.clone(),
+            OpWrite::default().with_compose_sources(sources),
+            path.to_string(),
+        );
+        w.compose(0, AsyncBody::Empty).await?;
+
+        Ok(RpConcat::default())
+    }
+
+    async fn create_snapshot(&self, path: &str, _: OpCreateSnapshot) -> Result<RpCreateSnapshot> {
+        let resp = self.core.azblob_create_snapshot(path).await?;
+
+        match resp.status() {
+            StatusCode::CREATED => {
+                let snapshot = parse_snapshot(resp.headers())?
+                    .ok_or_else(|| {
+                        Error::new(ErrorKind::Unexpected, "snapshot id is missing in response")
+                    })?
+                    .to_string();
+                resp.into_body().consume().await?;
+                Ok(RpCreateSnapshot::new(&snapshot))
+            }
             _ => Err(parse_error(resp).await?),
         }
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let resp = self.core.azblob_delete_blob(path).await?;
+    async fn lease(&self, path: &str, args: OpLease) -> Result<RpLease> {
+        if let LeaseAction::Acquire(Some(duration)) = args.action() {
+            check_lease_duration(*duration)?;
+        }
+
+        let resp = self.core.azblob_lease_blob(path, args.action()).await?;
+
+        match (args.action(), resp.status()) {
+            (LeaseAction::Acquire(_), StatusCode::CREATED) => {
+                let lease_id = parse_lease_id(resp.headers())?
+                    .ok_or_else(|| {
+                        Error::new(ErrorKind::Unexpected, "lease id is missing in response")
+                    })?
+                    .to_string();
+                resp.into_body().consume().await?;
+                Ok(RpLease::new().with_lease_id(&lease_id))
+            }
+            (LeaseAction::Renew(_) | LeaseAction::Release(_), StatusCode::OK)
+            | (LeaseAction::Break, StatusCode::ACCEPTED) => {
+                resp.into_body().consume().await?;
+                Ok(RpLease::new())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn list_roots(&self, _: OpListRoots) -> Result<RpListRoots> {
+        let containers = self.core.azblob_list_all_containers().await?;
+
+        let mut entries = Vec::with_capacity(containers.len());
+        for container in containers {
+            let mut meta = Metadata::new(EntryMode::DIR);
+            // Skip the date instead of failing the whole listing.
+            match parse_datetime_from_rfc2822(&container.properties.last_modified) {
+                Ok(v) => meta.set_last_modified(v),
+                Err(err) if !container.properties.last_modified.is_empty() => {
+                    warn!(
+                        "container {} has invalid last modified: {err}",
+                        container.name
+                    )
+                }
+                Err(_) => {}
+            }
+            entries.push(oio::Entry::with(format!("{}/", container.name), meta));
+        }
+
+        Ok(RpListRoots::new(entries))
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let resp = self.core.azblob_delete_blob(path, &args).await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::ACCEPTED | StatusCode::NOT_FOUND => Ok(RpDelete::default()),
+            StatusCode::ACCEPTED => Ok(RpDelete::default()),
+            StatusCode::NOT_FOUND if !args.must_exist() => Ok(RpDelete::default()),
             _ => Err(parse_error(resp).await?),
         }
     }
@@ -660,25 +1346,39 @@ impl Accessor for AzblobBackend {
             self.core.clone(),
             path.to_string(),
             args.recursive(),
+            args.delimiter(),
             args.limit(),
+            args.snapshots(),
+            args.versions(),
         );
 
         Ok((RpList::default(), oio::PageLister::new(l)))
     }
 
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
-        let mut req = match args.operation() {
-            PresignOperation::Stat(v) => self.core.azblob_head_blob_request(path, v)?,
-            PresignOperation::Read(v) => self.core.azblob_get_blob_request(path, v)?,
-            PresignOperation::Write(_) => self.core.azblob_put_blob_request(
-                path,
-                None,
-                &OpWrite::default(),
-                AsyncBody::Empty,
-            )?,
+        let (mut req, scope) = match args.operation() {
+            PresignOperation::Stat(v) => (
+                self.core.azblob_head_blob_request(path, v)?,
+                DelegateScope::Read,
+            ),
+            PresignOperation::Read(v) => (
+                self.core.azblob_get_blob_request(path, v)?,
+                DelegateScope::Read,
+            ),
+            PresignOperation::Write(_) => (
+                self.core.azblob_put_blob_request(
+                    path,
+                    None,
+                    &OpWrite::default(),
+                    AsyncBody::Empty,
+                )?,
+                DelegateScope::Write,
+            ),
         };
 
-        self.core.sign_query(&mut req).await?;
+        self.core
+            .presign(&mut req, path, scope, args.expire())
+            .await?;
 
         let (parts, _) = req.into_parts();
 
@@ -762,9 +1462,24 @@ impl Accessor for AzblobBackend {
 
 #[cfg(test)]
 mod tests {
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::matchers::query_param;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+
     use super::AzblobBuilder;
+    use crate::raw::DeleteSnapshots;
+    use crate::raw::OpRead;
+    use crate::services::azblob::backend::check_lease_duration;
     use crate::services::azblob::backend::infer_storage_name_from_endpoint;
+    use crate::Accessor;
     use crate::Builder;
+    use crate::ConfigError;
+    use crate::Emulator;
+    use crate::ErrorKind;
 
     #[test]
     fn test_infer_storage_name_from_endpoint() {
@@ -803,6 +1518,184 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_builder_secondary_endpoint() {
+        let mut azblob_builder = AzblobBuilder::default();
+        azblob_builder.endpoint("https://storagesample.blob.core.windows.net");
+        azblob_builder.secondary_endpoint("https://storagesample-secondary.blob.core.windows.net/");
+        azblob_builder.container("container");
+        azblob_builder.account_key("account-key");
+        let azblob = azblob_builder
+            .build()
+            .expect("build azblob should be succeeded.");
+
+        let req = azblob
+            .core
+            .azblob_get_blob_request("path/to/file", &OpRead::default())
+            .expect("request must be valid");
+        let req = azblob
+            .core
+            .secondary_request(&req)
+            .expect("request must be valid")
+            .expect("secondary request must exist");
+        assert_eq!(
+            req.uri().to_string(),
+            "https://storagesample-secondary.blob.core.windows.net/container/path/to/file"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_presign_by_shared_key() -> crate::Result<()> {
+        let mut builder = AzblobBuilder::default();
+        builder
+            .endpoint("https://storagesample.blob.core.windows.net")
+            .container("container")
+            .account_name("storagesample")
+            .account_key(super::AZURITE_ACCOUNT_KEY);
+        let op = crate::Operator::new(builder)?.finish();
+
+        let req = op
+            .presign_read("path/to/file", std::time::Duration::from_secs(3600))
+            .await?;
+        assert_eq!(
+            req.uri().path(),
+            "/container/path/to/file",
+            "presigned uri must point to the blob"
+        );
+        let query = req.uri().query().expect("query must exist");
+        assert!(query.contains("sr=b&sp=r&"), "query: {query}");
+        assert!(query.contains("&sig="), "query: {query}");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_presign_by_user_delegation_key() -> crate::Result<()> {
+        use chrono::Utc;
+
+        use super::super::sas::Sas;
+        use crate::DelegateScope;
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/"))
+            .and(query_param("restype", "service"))
+            .and(query_param("comp", "userdelegationkey"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="utf-8"?>
+                <UserDelegationKey>
+                    <SignedOid>oid</SignedOid>
+                    <SignedTid>tid</SignedTid>
+                    <SignedStart>2024-01-01T00:00:00Z</SignedStart>
+                    <SignedExpiry>2024-01-02T00:00:00Z</SignedExpiry>
+                    <SignedService>b</SignedService>
+                    <SignedVersion>2022-11-02</SignedVersion>
+                    <Value>YWNjb3VudC1rZXk=</Value>
+                </UserDelegationKey>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let mut builder = AzblobBuilder::default();
+        builder
+            .endpoint(&server.uri())
+            .container("container")
+            .account_name("devstoreaccount1")
+            .account_key(super::AZURITE_ACCOUNT_KEY);
+        let backend = builder.build()?;
+
+        let start = Utc::now();
+        let expiry = start + chrono::Duration::hours(1);
+        let sas = Sas::new(
+            "container",
+            "path/to/file",
+            DelegateScope::Read.into(),
+            expiry,
+        );
+        let token = backend
+            .core
+            .azblob_sign_by_user_delegation_key(&sas, start, expiry)
+            .await?;
+        for v in ["sr=b", "sp=r", "skoid=oid", "sktid=tid", "sks=b", "&sig="] {
+            assert!(token.contains(v), "{v} not in {token}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_presign_capability() {
+        let mut builder = AzblobBuilder::default();
+        builder
+            .endpoint("https://storagesample.blob.core.windows.net")
+            .container("container")
+            .sas_token("sv=2021-01-01&sig=sig");
+        let backend = builder.build().expect("build must succeed");
+        assert!(backend.info().full_capability().presign_read);
+
+        if super::has_bearer_token_source() {
+            return;
+        }
+        let mut builder = AzblobBuilder::default();
+        builder
+            .endpoint("https://storagesample.blob.core.windows.net")
+            .container("container");
+        let backend = builder.build().expect("build must succeed");
+        assert!(!backend.info().full_capability().presign_read);
+    }
+
+    #[test]
+    fn test_builder_api_version() {
+        let mut azblob_builder = AzblobBuilder::default();
+        azblob_builder.endpoint("https://storagesample.blob.core.windows.net");
+        azblob_builder.container("container");
+        azblob_builder.account_key("account-key");
+        let azblob = azblob_builder
+            .build()
+            .expect("build azblob should be succeeded.");
+        assert_eq!(azblob.core.api_version(), "2022-11-02");
+
+        azblob_builder.api_version("2024-08-04");
+        let azblob = azblob_builder
+            .build()
+            .expect("build azblob should be succeeded.");
+        assert_eq!(azblob.core.api_version(), "2024-08-04");
+
+        for v in ["2019-12-12", "2099-01-01", "2022-1-2", "latest"] {
+            azblob_builder.api_version(v);
+            let err = azblob_builder.build().expect_err("must fail");
+            assert_eq!(err.kind(), ErrorKind::ConfigInvalid, "version {v}");
+        }
+    }
+
+    #[test]
+    fn test_builder_emulator_azurite() {
+        let mut azblob_builder = AzblobBuilder::default();
+        azblob_builder.container("container");
+        azblob_builder.emulator(Emulator::Azurite);
+        let azblob = azblob_builder
+            .build()
+            .expect("build azblob should be succeeded.");
+
+        assert_eq!(
+            azblob.core.endpoint,
+            "http://127.0.0.1:10000/devstoreaccount1"
+        );
+        assert!(!azblob.info().native_capability().batch);
+        assert!(!azblob.info().native_capability().write_with_retention);
+    }
+
+    #[test]
+    fn test_builder_validate() {
+        let mut azblob_builder = AzblobBuilder::default();
+        azblob_builder.account_name("storagesample");
+        azblob_builder.encryption_algorithm("AES128");
+        let err = azblob_builder.build().expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let config_err = ConfigError::from_error(&err).expect("must carry config error");
+        let keys: Vec<_> = config_err.problems().iter().map(|v| v.key()).collect();
+        assert_eq!(keys, vec!["container", "endpoint", "encryption_algorithm"]);
+    }
+
     #[test]
     fn test_no_key_wont_infer_account_name() {
         let mut azblob_builder = AzblobBuilder::default();
@@ -926,4 +1819,873 @@ SharedAccessSignature=sv=2021-01-01&ss=b&srt=c&sp=rwdlaciytfx&se=2022-01-01T11:0
         assert_eq!(builder.config.account_name, None);
         assert_eq!(builder.config.account_key, None);
     }
+
+    fn new_mock_builder(server: &MockServer) -> AzblobBuilder {
+        let mut builder = AzblobBuilder::default();
+        builder
+            .endpoint(&server.uri())
+            .container("container")
+            .account_name("devstoreaccount1")
+            .account_key(super::AZURITE_ACCOUNT_KEY);
+        builder
+    }
+
+    fn new_mock_operator(server: &MockServer) -> crate::Operator {
+        crate::Operator::new(new_mock_builder(server))
+            .expect("build azblob must succeed")
+            .finish()
+    }
+
+    fn new_mock_operator_with_block_size(server: &MockServer, size: usize) -> crate::Operator {
+        let mut builder = new_mock_builder(server);
+        builder.write_block_size(size);
+        crate::Operator::new(builder)
+            .expect("build azblob must succeed")
+            .finish()
+    }
+
+    #[tokio::test]
+    async fn test_delete_keeps_snapshots_by_default() -> crate::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/container/file"))
+            .and(header("x-ms-delete-snapshots", "include"))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // Azblob refuses to delete a blob with snapshots alone.
+        Mock::given(method("DELETE"))
+            .and(path("/container/file"))
+            .respond_with(ResponseTemplate::new(409))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        let err = op.delete("file").await.expect_err("must fail");
+        assert_ne!(err.kind(), ErrorKind::NotFound);
+
+        op.delete_with("file")
+            .snapshots(DeleteSnapshots::Include)
+            .await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_must_exist_by_response() -> crate::Result<()> {
+        let server = MockServer::start().await;
+        // Missing blob is reported by delete, no stat is needed.
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/container/file"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        let err = op
+            .delete_with("file")
+            .must_exist(true)
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        // Idempotent delete is still the default.
+        op.delete("file").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stat_tags_only_if_requested() -> crate::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/container/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "0")
+                    .insert_header("x-ms-tag-count", "1"),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "tags"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="utf-8"?>
+                <Tags><TagSet><Tag><Key>project</Key><Value>opendal</Value></Tag></TagSet></Tags>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        let meta = op.stat("file").await?;
+        assert!(meta.tags().is_none());
+
+        let meta = op.stat_with("file").fields(crate::Metakey::Tags).await?;
+        assert_eq!(
+            meta.tags()
+                .and_then(|v| v.get("project"))
+                .map(String::as_str),
+            Some("opendal")
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_with_waits_for_pending_copy() -> crate::Result<()> {
+        use wiremock::matchers::header_exists;
+
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/container/to"))
+            .and(header_exists("x-ms-copy-source"))
+            .respond_with(ResponseTemplate::new(202).insert_header("x-ms-copy-status", "pending"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // The first poll still sees the copy pending.
+        Mock::given(method("HEAD"))
+            .and(path("/container/to"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "0")
+                    .insert_header("x-ms-copy-status", "pending"),
+            )
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/container/to"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "0")
+                    .insert_header("content-type", "application/octet-stream")
+                    .insert_header("content-language", "en")
+                    .insert_header("x-ms-copy-status", "success"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/container/to"))
+            .and(query_param("comp", "properties"))
+            .and(header("x-ms-blob-content-type", "text/plain"))
+            .and(header("x-ms-blob-content-language", "en"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        op.copy_with("from", "to")
+            .content_type("text/plain")
+            .await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_snapshot() -> crate::Result<()> {
+        let snapshot = "2024-01-01T00:00:00.0000000Z";
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "snapshot"))
+            .respond_with(ResponseTemplate::new(201).insert_header("x-ms-snapshot", snapshot))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // Both stat and read without range will stat the snapshot.
+        Mock::given(method("HEAD"))
+            .and(path("/container/file"))
+            .and(query_param("snapshot", snapshot))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-length", "3"))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/container/file"))
+            .and(query_param("snapshot", snapshot))
+            .respond_with(ResponseTemplate::new(200).set_body_string("old"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        let id = op.create_snapshot("file").await?;
+        assert_eq!(id, snapshot);
+
+        let meta = op.stat_with("file").snapshot(&id).await?;
+        assert_eq!(meta.content_length(), 3);
+        assert_eq!(meta.snapshot(), Some(snapshot));
+
+        let bs = op.read_with("file").snapshot(&id).await?;
+        assert_eq!(bs, b"old");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_client_request_id() -> crate::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/container/file"))
+            .and(header("x-ms-client-request-id", "abc"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-length", "0"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server).layer(
+            crate::layers::RequestIdLayer::default()
+                .with_header(http::HeaderName::from_static("x-ms-client-request-id"))
+                .with_generator(|| "abc".to_string()),
+        );
+
+        op.stat("file").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_versions() -> crate::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/container"))
+            .and(query_param("comp", "list"))
+            .and(query_param("include", "versions"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="utf-8"?>
+                <EnumerationResults ContainerName="container">
+                    <Blobs>
+                        <Blob>
+                            <Name>dir/file</Name>
+                            <VersionId>v1</VersionId>
+                            <Properties>
+                                <Last-Modified>Fri, 01 Dec 2023 08:00:00 GMT</Last-Modified>
+                                <Etag>0x1</Etag>
+                                <Content-Length>3</Content-Length>
+                            </Properties>
+                        </Blob>
+                        <Blob>
+                            <Name>dir/file</Name>
+                            <VersionId>v2</VersionId>
+                            <IsCurrentVersion>true</IsCurrentVersion>
+                            <Properties>
+                                <Last-Modified>Sat, 02 Dec 2023 08:00:00 GMT</Last-Modified>
+                                <Etag>0x2</Etag>
+                                <Content-Length>5</Content-Length>
+                            </Properties>
+                        </Blob>
+                    </Blobs>
+                    <NextMarker />
+                </EnumerationResults>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        let entries = op.list_with("dir/").versions(true).await?;
+        let versions: Vec<_> = entries
+            .iter()
+            .map(|e| (e.path(), e.metadata().version(), e.metadata().is_current()))
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                ("dir/file", Some("v1"), None),
+                ("dir/file", Some("v2"), Some(true))
+            ]
+        );
+        Ok(())
+    }
+
+    /// Mount a HEAD that only matches the version `v1` of `file`.
+    async fn mount_stat_version(server: &MockServer) {
+        Mock::given(method("HEAD"))
+            .and(path("/container/file"))
+            .and(query_param("versionid", "v1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "3")
+                    .insert_header("etag", "\"0x1\"")
+                    .insert_header("x-ms-version-id", "v1")
+                    .insert_header("x-ms-is-current-version", "false"),
+            )
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_stat_version() -> crate::Result<()> {
+        let server = MockServer::start().await;
+        mount_stat_version(&server).await;
+        let op = new_mock_operator(&server);
+
+        let meta = op.stat_with("file").version("v1").await?;
+        assert_eq!(meta.content_length(), 3);
+        assert_eq!(meta.version(), Some("v1"));
+        assert_eq!(meta.is_current(), Some(false));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_version() -> crate::Result<()> {
+        use futures::AsyncSeekExt;
+
+        let server = MockServer::start().await;
+        mount_stat_version(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/container/file"))
+            .and(query_param("versionid", "v1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"0x1\"")
+                    .set_body_string("abc"),
+            )
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        // The size must be fetched from the version instead of current blob.
+        let bs = op.read_with("file").version("v1").await?;
+        assert_eq!(bs, b"abc");
+
+        let mut r = op.reader_with("file").version("v1").await?;
+        let n = r
+            .seek(std::io::SeekFrom::End(0))
+            .await
+            .expect("seek must succeed");
+        assert_eq!(n, 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_version() -> crate::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/container/file"))
+            .and(query_param("versionid", "v1"))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        op.delete_with("file").version("v1").await?;
+        Ok(())
+    }
+
+    /// Returns committed block ids of the last `Put Block List` and the body
+    /// of every staged block by id, sources are staged with an empty body.
+    async fn committed_blocks(
+        server: &MockServer,
+    ) -> (Vec<String>, std::collections::HashMap<String, Vec<u8>>) {
+        let requests = server
+            .received_requests()
+            .await
+            .expect("recording must be enabled");
+
+        let mut blocks = std::collections::HashMap::new();
+        let mut committed = Vec::new();
+        for req in requests {
+            let query: std::collections::HashMap<_, _> =
+                req.url.query_pairs().into_owned().collect();
+            match query.get("comp").map(String::as_str) {
+                Some("block") => {
+                    blocks.insert(query["blockid"].clone(), req.body.clone());
+                }
+                Some("blocklist") => {
+                    let body = String::from_utf8(req.body.clone()).unwrap();
+                    committed = body
+                        .split("<Latest>")
+                        .skip(1)
+                        .map(|v| v.split("</Latest>").next().unwrap().to_string())
+                        .collect();
+                }
+                _ => {}
+            }
+        }
+        (committed, blocks)
+    }
+
+    #[tokio::test]
+    async fn test_write_commits_blocks_in_order() -> crate::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "block"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(3)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "blocklist"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator_with_block_size(&server, 5);
+
+        let mut w = op.writer_with("file").concurrent(3).await?;
+        w.write("Hello, ").await?;
+        w.write("World").await?;
+        w.write("!").await?;
+        w.close().await?;
+
+        let (committed, blocks) = committed_blocks(&server).await;
+        assert_eq!(committed.len(), 3);
+        let content: Vec<u8> = committed.iter().flat_map(|id| blocks[id].clone()).collect();
+        assert_eq!(content, b"Hello, World!");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_buffers_small_chunks_into_blocks() -> crate::Result<()> {
+        const BLOCK_SIZE: usize = 4;
+        const CHUNKS: usize = 10;
+        const CHUNK_SIZE: usize = 3;
+        let total = CHUNKS * CHUNK_SIZE;
+        let expected_blocks = (total + BLOCK_SIZE - 1) / BLOCK_SIZE;
+
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "block"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(expected_blocks as u64)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "blocklist"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator_with_block_size(&server, BLOCK_SIZE);
+
+        let mut w = op.writer_with("file").concurrent(2).await?;
+        let mut expected = vec![];
+        for i in 0..CHUNKS {
+            let chunk = vec![i as u8; CHUNK_SIZE];
+            expected.extend_from_slice(&chunk);
+            w.write(chunk).await?;
+        }
+        w.close().await?;
+
+        let (committed, blocks) = committed_blocks(&server).await;
+        assert_eq!(committed.len(), expected_blocks);
+        // All blocks are exactly the block size except the last one.
+        for id in &committed[..expected_blocks - 1] {
+            assert_eq!(blocks[id].len(), BLOCK_SIZE);
+        }
+        let content: Vec<u8> = committed.iter().flat_map(|id| blocks[id].clone()).collect();
+        assert_eq!(content, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_small_chunks_put_blob_by_default() -> crate::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "block"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(0)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(header("x-ms-blob-type", "BlockBlob"))
+            .and(header("content-length", "13"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        // Data smaller than the default block size is not staged as blocks.
+        let mut w = op.writer("file").await?;
+        w.write("Hello, ").await?;
+        w.write("World").await?;
+        w.write("!").await?;
+        w.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_empty_puts_blob() -> crate::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "block"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(0)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(header("x-ms-blob-type", "BlockBlob"))
+            .and(header("content-length", "0"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        op.write("file", Vec::<u8>::new()).await?;
+
+        let mut w = op.writer("file").await?;
+        w.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_compose_with_staged_blocks() -> crate::Result<()> {
+        let server = MockServer::start().await;
+        let source = format!("{}/container/source?sig=test", server.uri());
+
+        Mock::given(method("HEAD"))
+            .and(path("/container/source"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-length", "1024"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "block"))
+            .and(header("x-ms-copy-source", source.as_str()))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "block"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "blocklist"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator_with_block_size(&server, 7);
+
+        let mut w = op
+            .writer_with("file")
+            .compose_sources(vec![source.clone()])
+            .concurrent(2)
+            .await?;
+        w.write("Hello, ").await?;
+        w.write("World!").await?;
+        w.close().await?;
+
+        let (committed, blocks) = committed_blocks(&server).await;
+        // Sources are committed before written content.
+        assert_eq!(committed.len(), 3);
+        assert!(blocks[&committed[0]].is_empty());
+        let content: Vec<u8> = committed[1..]
+            .iter()
+            .flat_map(|id| blocks[id].clone())
+            .collect();
+        assert_eq!(content, b"Hello, World!");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_compose_splits_large_source_by_range() -> crate::Result<()> {
+        const MIB: u64 = 1024 * 1024;
+
+        let server = MockServer::start().await;
+        let small = format!("{}/container/small?sig=test", server.uri());
+        let large = format!("{}/container/large?sig=test", server.uri());
+
+        Mock::given(method("HEAD"))
+            .and(path("/container/small"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-length", "1024"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/container/large"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", (8000 * MIB + 1).to_string().as_str()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        // Small source is staged in full.
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "block"))
+            .and(header("x-ms-copy-source", small.as_str()))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        for range in [
+            format!("bytes=0-{}", 4000 * MIB - 1),
+            format!("bytes={}-{}", 4000 * MIB, 8000 * MIB - 1),
+            format!("bytes={}-{}", 8000 * MIB, 8000 * MIB),
+        ] {
+            Mock::given(method("PUT"))
+                .and(path("/container/file"))
+                .and(query_param("comp", "block"))
+                .and(header("x-ms-copy-source", large.as_str()))
+                .and(header("x-ms-source-range", range.as_str()))
+                .respond_with(ResponseTemplate::new(201))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "blocklist"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        op.write_with("file", Vec::<u8>::new())
+            .compose_sources(vec![small, large])
+            .await?;
+
+        let (committed, _) = committed_blocks(&server).await;
+        assert_eq!(committed.len(), 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_fails_over_to_secondary() -> crate::Result<()> {
+        let primary = MockServer::start().await;
+        let secondary = MockServer::start().await;
+        // The primary endpoint is skipped after it failed once.
+        Mock::given(method("GET"))
+            .and(path("/container/file"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&primary)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/container/file"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("Hello, World!"))
+            .expect(2)
+            .mount(&secondary)
+            .await;
+
+        let mut builder = AzblobBuilder::default();
+        builder
+            .endpoint(&primary.uri())
+            .secondary_endpoint(&secondary.uri())
+            .container("container")
+            .account_name("devstoreaccount1")
+            .account_key(super::AZURITE_ACCOUNT_KEY);
+        let op = crate::Operator::new(builder)?.finish();
+
+        assert_eq!(op.read("file").await?, b"Hello, World!");
+        assert_eq!(op.read("file").await?, b"Hello, World!");
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_lease_duration() {
+        use std::time::Duration;
+
+        assert!(check_lease_duration(Duration::from_secs(15)).is_ok());
+        assert!(check_lease_duration(Duration::from_secs(60)).is_ok());
+        for d in [
+            Duration::from_secs(14),
+            Duration::from_secs(61),
+            Duration::from_millis(500),
+            Duration::from_millis(30_500),
+        ] {
+            let err = check_lease_duration(d).expect_err("duration must be rejected");
+            assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lease() -> crate::Result<()> {
+        use std::time::Duration;
+
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "lease"))
+            .and(header("x-ms-lease-action", "acquire"))
+            .and(header("x-ms-lease-duration", "30"))
+            .respond_with(ResponseTemplate::new(201).insert_header("x-ms-lease-id", "lease-30"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "lease"))
+            .and(header("x-ms-lease-action", "acquire"))
+            .and(header("x-ms-lease-duration", "-1"))
+            .respond_with(
+                ResponseTemplate::new(201).insert_header("x-ms-lease-id", "lease-infinite"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "lease"))
+            .and(header("x-ms-lease-action", "renew"))
+            .and(header("x-ms-lease-id", "lease-30"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "lease"))
+            .and(header("x-ms-lease-action", "release"))
+            .and(header("x-ms-lease-id", "lease-30"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/container/file"))
+            .and(query_param("comp", "lease"))
+            .and(header("x-ms-lease-action", "break"))
+            .and(header("x-ms-lease-break-period", "0"))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        let id = op
+            .acquire_lease("file", Some(Duration::from_secs(30)))
+            .await?;
+        assert_eq!(id, "lease-30");
+        op.renew_lease("file", &id).await?;
+        op.release_lease("file", &id).await?;
+
+        let id = op.acquire_lease("file", None).await?;
+        assert_eq!(id, "lease-infinite");
+        op.break_lease("file").await?;
+
+        // Invalid durations are rejected before sending requests.
+        let err = op
+            .acquire_lease("file", Some(Duration::from_millis(500)))
+            .await
+            .expect_err("sub-second duration must be rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = op
+            .acquire_lease("file", Some(Duration::from_secs(120)))
+            .await
+            .expect_err("too long duration must be rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_roots() -> crate::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .and(query_param("comp", "list"))
+            .and(query_param("marker", "next"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<EnumerationResults>
+                  <Containers>
+                    <Container><Name>b</Name><Properties><Last-Modified>yesterday</Last-Modified></Properties></Container>
+                  </Containers>
+                  <NextMarker />
+                </EnumerationResults>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .and(query_param("comp", "list"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<EnumerationResults>
+                  <Containers>
+                    <Container><Name>a</Name><Properties><Last-Modified>Sun, 01 Jan 2023 00:00:00 GMT</Last-Modified></Properties></Container>
+                  </Containers>
+                  <NextMarker>next</NextMarker>
+                </EnumerationResults>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        let roots = op.list_roots().await?;
+        let names: Vec<_> = roots.iter().map(|v| v.path()).collect();
+        assert_eq!(names, ["a/", "b/"]);
+        assert!(roots.iter().all(|v| v.metadata().is_dir()));
+        assert_eq!(
+            roots[0].metadata().last_modified().map(|v| v.to_rfc3339()),
+            Some("2023-01-01T00:00:00+00:00".to_string())
+        );
+        // Malformed date is skipped instead of failing the listing.
+        assert_eq!(roots[1].metadata().last_modified(), None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_signer_signs_custom_request() -> crate::Result<()> {
+        use http::Request;
+
+        use crate::raw::AsyncBody;
+
+        let mut builder = AzblobBuilder::default();
+        builder
+            .endpoint("https://storagesample.blob.core.windows.net")
+            .container("container")
+            .account_name("storagesample")
+            .account_key(super::AZURITE_ACCOUNT_KEY);
+        let op = crate::Operator::new(builder)?.finish();
+        let signer = op.info().signer().expect("azblob must expose its signer");
+
+        let mut req = Request::get(
+            "https://storagesample.blob.core.windows.net/container?restype=container&comp=acl",
+        )
+        .body(AsyncBody::Empty)
+        .expect("request must be valid");
+        signer.sign(&mut req).await?;
+        let auth = req
+            .headers()
+            .get(http::header::AUTHORIZATION)
+            .expect("authorization must be set")
+            .to_str()
+            .expect("authorization must be valid");
+        assert!(
+            auth.starts_with("SharedKey storagesample:"),
+            "authorization: {auth}"
+        );
+        assert!(req.headers().contains_key("x-ms-version"));
+
+        let mut req =
+            Request::get("https://storagesample.blob.core.windows.net/container/file?comp=tags")
+                .body(AsyncBody::Empty)
+                .expect("request must be valid");
+        signer
+            .sign_query(&mut req, std::time::Duration::from_secs(3600))
+            .await?;
+        let query = req.uri().query().expect("query must exist");
+        assert!(query.contains("comp=tags"), "query: {query}");
+        assert!(query.contains("&sig="), "query: {query}");
+        Ok(())
+    }
 }
@@ -15,25 +15,47 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Write;
+use std::sync::Mutex;
+use std::sync::RwLock;
 use std::time::Duration;
-
+use std::time::Instant;
+
+use async_trait::async_trait;
+use bytes::Buf;
+use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Utc;
+use flagset::FlagSet;
 use http::header::HeaderName;
+use http::header::CONTENT_DISPOSITION;
+use http::header::CONTENT_ENCODING;
+use http::header::CONTENT_LANGUAGE;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
 use http::header::IF_MATCH;
 use http::header::IF_NONE_MATCH;
+use http::HeaderMap;
 use http::HeaderValue;
 use http::Request;
 use http::Response;
+use http::StatusCode;
+use log::debug;
 use reqsign::AzureStorageCredential;
 use reqsign::AzureStorageLoader;
 use reqsign::AzureStorageSigner;
 use serde::Deserialize;
+use serde::Serialize;
 
+use super::backend::check_api_version;
+use super::error::parse_error;
+use super::sas::format_sas_time;
+use super::sas::Sas;
+use super::sas::UserDelegationKey;
 use crate::raw::*;
 use crate::*;
 
@@ -42,26 +64,66 @@ mod constants {
 
     pub const X_MS_BLOB_TYPE: &str = "x-ms-blob-type";
     pub const X_MS_COPY_SOURCE: &str = "x-ms-copy-source";
+    pub const X_MS_SOURCE_RANGE: &str = "x-ms-source-range";
+    pub const X_MS_COPY_STATUS: &str = "x-ms-copy-status";
+    pub const X_MS_COPY_STATUS_DESCRIPTION: &str = "x-ms-copy-status-description";
     pub const X_MS_BLOB_CACHE_CONTROL: &str = "x-ms-blob-cache-control";
+    pub const X_MS_BLOB_CONTENT_TYPE: &str = "x-ms-blob-content-type";
+    pub const X_MS_BLOB_CONTENT_DISPOSITION: &str = "x-ms-blob-content-disposition";
+    pub const X_MS_BLOB_CONTENT_ENCODING: &str = "x-ms-blob-content-encoding";
+    pub const X_MS_BLOB_CONTENT_LANGUAGE: &str = "x-ms-blob-content-language";
+    pub const X_MS_BLOB_CONTENT_MD5: &str = "x-ms-blob-content-md5";
+    pub const X_MS_META_PREFIX: &str = "x-ms-meta-";
+    pub const X_MS_TAGS: &str = "x-ms-tags";
+    pub const X_MS_TAG_COUNT: &str = "x-ms-tag-count";
+    pub const X_MS_DELETE_SNAPSHOTS: &str = "x-ms-delete-snapshots";
+    pub const X_MS_LEASE_ID: &str = "x-ms-lease-id";
+    pub const X_MS_LEASE_ACTION: &str = "x-ms-lease-action";
+    pub const X_MS_LEASE_DURATION: &str = "x-ms-lease-duration";
+    pub const X_MS_LEASE_BREAK_PERIOD: &str = "x-ms-lease-break-period";
     pub const X_MS_BLOB_CONDITION_APPENDPOS: &str = "x-ms-blob-condition-appendpos";
 
+    pub const X_MS_IMMUTABILITY_POLICY_UNTIL_DATE: &str = "x-ms-immutability-policy-until-date";
+    pub const X_MS_IMMUTABILITY_POLICY_MODE: &str = "x-ms-immutability-policy-mode";
+    pub const X_MS_LEGAL_HOLD: &str = "x-ms-legal-hold";
+    pub const X_MS_ACCESS_TIER: &str = "x-ms-access-tier";
+    pub const X_MS_SNAPSHOT: &str = "x-ms-snapshot";
+    pub const X_MS_VERSION_ID: &str = "x-ms-version-id";
+    pub const X_MS_IS_CURRENT_VERSION: &str = "x-ms-is-current-version";
+
     // Server-side encryption with customer-provided headers
     pub const X_MS_ENCRYPTION_KEY: &str = "x-ms-encryption-key";
     pub const X_MS_ENCRYPTION_KEY_SHA256: &str = "x-ms-encryption-key-sha256";
     pub const X_MS_ENCRYPTION_ALGORITHM: &str = "x-ms-encryption-algorithm";
 }
 
+/// How long reads go to the secondary endpoint directly after the primary
+/// one failed, so that every read won't wait for the broken primary first.
+const FAILOVER_COOLDOWN: Duration = Duration::from_secs(30);
+
 pub struct AzblobCore {
     pub container: String,
+    /// The account name configured by users or inferred from endpoint.
+    pub account_name: Option<String>,
     pub root: String,
     pub endpoint: String,
+    pub secondary_endpoint: Option<String>,
     pub encryption_key: Option<HeaderValue>,
     pub encryption_key_sha256: Option<HeaderValue>,
     pub encryption_algorithm: Option<HeaderValue>,
     pub client: HttpClient,
     pub loader: AzureStorageLoader,
+    pub credential: CredentialCache<AzureStorageCredential>,
+    /// Credential set by users at runtime, which takes precedence over
+    /// the loader.
+    pub credential_override: RwLock<Option<AzureStorageCredential>>,
     pub signer: AzureStorageSigner,
     pub batch_max_operations: usize,
+    /// The `x-ms-version` of all requests, could be updated at runtime.
+    pub api_version: RwLock<HeaderValue>,
+    /// Reads skip the primary endpoint until this instant, which is set
+    /// after the primary endpoint failed.
+    pub primary_unavailable_until: Mutex<Option<Instant>>,
 }
 
 impl Debug for AzblobCore {
@@ -70,59 +132,259 @@ impl Debug for AzblobCore {
             .field("container", &self.container)
             .field("root", &self.root)
             .field("endpoint", &self.endpoint)
+            .field("secondary_endpoint", &self.secondary_endpoint)
             .finish_non_exhaustive()
     }
 }
 
+/// AzblobCore signs requests with shared key or SAS token, see [`RequestSign`].
+#[async_trait]
+impl RequestSign for AzblobCore {
+    async fn sign(&self, req: &mut Request<AsyncBody>) -> Result<()> {
+        AzblobCore::sign(self, req).await
+    }
+
+    async fn sign_query(&self, req: &mut Request<AsyncBody>, expire: Duration) -> Result<()> {
+        let cred = self.load_credential().await?;
+
+        self.signer
+            .sign_query(req, expire, &cred)
+            .map_err(new_request_sign_error)
+    }
+
+    async fn delegate(
+        &self,
+        path: &str,
+        scope: FlagSet<DelegateScope>,
+        expire: Duration,
+    ) -> Result<DelegatedCredential> {
+        self.azblob_delegate(path, scope, expire).await
+    }
+
+    async fn update_credential(&self, cred: Credential) -> Result<()> {
+        let cred = match cred {
+            Credential::AzureSas(token) => {
+                AzureStorageCredential::SharedAccessSignature(token.into_secret())
+            }
+            Credential::AzureSharedKey {
+                account_name,
+                account_key,
+            } => AzureStorageCredential::SharedKey(account_name, account_key.into_secret()),
+        };
+
+        *self
+            .credential_override
+            .write()
+            .expect("lock must be valid") = Some(cred);
+        // Make sure the new credential will be used in next request.
+        self.credential.invalidate();
+        Ok(())
+    }
+
+    fn update_api_version(&self, version: &str) -> Result<()> {
+        check_api_version(version).map_err(|msg| Error::new(ErrorKind::ConfigInvalid, &msg))?;
+        let version = HeaderValue::from_str(version).map_err(|err| {
+            Error::new(ErrorKind::ConfigInvalid, "api_version is invalid").set_source(err)
+        })?;
+
+        *self.api_version.write().expect("lock must be valid") = version;
+        Ok(())
+    }
+}
+
 impl AzblobCore {
     async fn load_credential(&self) -> Result<AzureStorageCredential> {
-        let cred = self
-            .loader
-            .load()
-            .await
-            .map_err(new_request_credential_error)?;
-
-        if let Some(cred) = cred {
-            Ok(cred)
-        } else {
-            Err(Error::new(
-                ErrorKind::ConfigInvalid,
-                "no valid credential found",
-            ))
+        if let Some(cred) = self
+            .credential_override
+            .read()
+            .expect("lock must be valid")
+            .clone()
+        {
+            return Ok(cred);
         }
+
+        self.credential
+            .get_or_load(|| async {
+                let cred = self
+                    .loader
+                    .load()
+                    .await
+                    .map_err(new_request_credential_error)?;
+
+                if let Some(cred) = cred {
+                    Ok(cred)
+                } else {
+                    Err(Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "no valid credential found",
+                    ))
+                }
+            })
+            .await
     }
 
-    pub async fn sign_query<T>(&self, req: &mut Request<T>) -> Result<()> {
+    /// Presign the request of given path by query.
+    ///
+    /// SAS token will be appended directly if configured, otherwise a
+    /// service SAS or user delegation SAS that only allows given scope on
+    /// the path will be minted, so that presign works with OAuth and
+    /// workload identity credentials too.
+    pub async fn presign<T>(
+        &self,
+        req: &mut Request<T>,
+        path: &str,
+        scope: DelegateScope,
+        expire: Duration,
+    ) -> Result<()> {
         let cred = self.load_credential().await?;
+        if let AzureStorageCredential::SharedAccessSignature(_) = cred {
+            return self
+                .signer
+                .sign_query(req, expire, &cred)
+                .map_err(new_request_sign_error);
+        }
 
-        self.signer
-            .sign_query(req, Duration::from_secs(3600), &cred)
-            .map_err(new_request_sign_error)
+        let p = build_abs_path(&self.root, path);
+        let DelegatedCredential::AzureSas { token, .. } =
+            self.azblob_delegate(&p, scope.into(), expire).await?
+        else {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "delegated credential of azblob must be SAS",
+            ));
+        };
+
+        let uri = req.uri().to_string();
+        let sep = if uri.contains('?') { '&' } else { '?' };
+        *req.uri_mut() = format!("{uri}{sep}{token}").parse().map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "presigned uri is invalid").set_source(err)
+        })?;
+        Ok(())
     }
 
     pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        // Request id must be signed too.
+        ClientRequestId::insert_current(req.headers_mut());
+
         let cred = self.load_credential().await?;
         // Insert x-ms-version header for normal requests.
         req.headers_mut().insert(
             HeaderName::from_static(constants::X_MS_VERSION),
-            // 2022-11-02 is the version supported by Azurite V3 and
-            // used by Azure Portal, We use this version to make
-            // sure most our developer happy.
-            //
-            // In the future, we could allow users to configure this value.
-            HeaderValue::from_static("2022-11-02"),
+            self.api_version(),
         );
         self.signer.sign(req, &cred).map_err(new_request_sign_error)
     }
 
     async fn batch_sign<T>(&self, req: &mut Request<T>) -> Result<()> {
         let cred = self.load_credential().await?;
+        // Sub-requests must use the same version as the batch, otherwise
+        // the signer will insert its own.
+        req.headers_mut().insert(
+            HeaderName::from_static(constants::X_MS_VERSION),
+            self.api_version(),
+        );
         self.signer.sign(req, &cred).map_err(new_request_sign_error)
     }
 
-    #[inline]
+    /// Get the `x-ms-version` of current requests.
+    pub fn api_version(&self) -> HeaderValue {
+        self.api_version.read().expect("lock must be valid").clone()
+    }
+
     pub async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
-        self.client.send(req).await
+        let resp = self.client.send(req).await?;
+
+        // Force to reload credential in next request if it has been rejected.
+        if is_credential_rejected(&resp) {
+            self.credential.invalidate();
+        }
+
+        Ok(resp)
+    }
+
+    /// Sign and send a read request, fail over to the secondary endpoint if
+    /// the primary one returns server errors or can't be connected.
+    ///
+    /// Once the primary endpoint failed, reads will be sent to the secondary
+    /// endpoint directly in the following [`FAILOVER_COOLDOWN`], after that
+    /// the primary endpoint will be tried again.
+    ///
+    /// Only requests without body like `GET` and `HEAD` are supported.
+    pub async fn send_read(
+        &self,
+        mut req: Request<AsyncBody>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut secondary = match self.secondary_request(&req)? {
+            Some(req) => req,
+            None => {
+                self.sign(&mut req).await?;
+                return self.send(req).await;
+            }
+        };
+
+        if !self.is_primary_unavailable() {
+            self.sign(&mut req).await?;
+            let result = self.send(req).await;
+            match &result {
+                Ok(resp) if !resp.status().is_server_error() => {
+                    self.set_primary_unavailable(None);
+                    return result;
+                }
+                Err(err) if !err.is_temporary() => return result,
+                _ => {}
+            }
+
+            debug!(
+                "azblob primary endpoint is unavailable, fail over to {}",
+                secondary.uri()
+            );
+            self.set_primary_unavailable(Some(Instant::now() + FAILOVER_COOLDOWN));
+        }
+
+        self.sign(&mut secondary).await?;
+        self.send(secondary).await
+    }
+
+    fn is_primary_unavailable(&self) -> bool {
+        let until = self
+            .primary_unavailable_until
+            .lock()
+            .expect("lock must be valid");
+        matches!(*until, Some(t) if Instant::now() < t)
+    }
+
+    fn set_primary_unavailable(&self, until: Option<Instant>) {
+        *self
+            .primary_unavailable_until
+            .lock()
+            .expect("lock must be valid") = until;
+    }
+
+    /// Build the same request against the secondary endpoint.
+    pub fn secondary_request(
+        &self,
+        req: &Request<AsyncBody>,
+    ) -> Result<Option<Request<AsyncBody>>> {
+        let secondary = match &self.secondary_endpoint {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        let uri = req.uri().to_string();
+        let path = match uri.strip_prefix(&self.endpoint) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        let mut new = Request::builder()
+            .method(req.method().clone())
+            .uri(format!("{secondary}{path}"));
+        for (k, v) in req.headers() {
+            new = new.header(k, v);
+        }
+        let new = new
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        Ok(Some(new))
     }
 
     pub fn insert_sse_headers(&self, mut req: http::request::Builder) -> http::request::Builder {
@@ -157,6 +419,23 @@ impl AzblobCore {
     }
 }
 
+/// Check if the response is caused by an expired or invalid credential.
+///
+/// Azure returns `403 AuthenticationFailed` for signatures that can't be
+/// verified, other `403`s are genuine permission denials which won't be
+/// fixed by reloading credential.
+fn is_credential_rejected<T>(resp: &Response<T>) -> bool {
+    match resp.status() {
+        StatusCode::UNAUTHORIZED => true,
+        StatusCode::FORBIDDEN => resp
+            .headers()
+            .get("x-ms-error-code")
+            .map(|v| v.as_bytes() == b"AuthenticationFailed")
+            .unwrap_or_default(),
+        _ => false,
+    }
+}
+
 impl AzblobCore {
     pub fn azblob_get_blob_request(&self, path: &str, args: &OpRead) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
@@ -175,6 +454,12 @@ impl AzblobCore {
                 percent_encode_path(override_content_disposition)
             ))
         }
+        if let Some(snapshot) = args.snapshot() {
+            query_args.push(format!("snapshot={}", percent_encode_path(snapshot)))
+        }
+        if let Some(version) = args.version() {
+            query_args.push(format!("versionid={}", percent_encode_path(version)))
+        }
 
         if !query_args.is_empty() {
             url.push_str(&format!("?{}", query_args.join("&")));
@@ -185,6 +470,10 @@ impl AzblobCore {
         // Set SSE headers.
         req = self.insert_sse_headers(req);
 
+        if let Some(lease_id) = args.lease_id() {
+            req = req.header(constants::X_MS_LEASE_ID, lease_id);
+        }
+
         let range = args.range();
         if !range.is_full() {
             // azblob doesn't support read with suffix range.
@@ -220,11 +509,9 @@ impl AzblobCore {
         path: &str,
         args: &OpRead,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.azblob_get_blob_request(path, args)?;
-
-        self.sign(&mut req).await?;
+        let req = self.azblob_get_blob_request(path, args)?;
 
-        self.send(req).await
+        self.send_read(req).await
     }
 
     pub fn azblob_put_blob_request(
@@ -248,6 +535,10 @@ impl AzblobCore {
         // Set SSE headers.
         req = self.insert_sse_headers(req);
 
+        if let Some(lease_id) = args.lease_id() {
+            req = req.header(constants::X_MS_LEASE_ID, lease_id);
+        }
+
         if let Some(cache_control) = args.cache_control() {
             req = req.header(constants::X_MS_BLOB_CACHE_CONTROL, cache_control);
         }
@@ -259,11 +550,29 @@ impl AzblobCore {
             req = req.header(CONTENT_TYPE, ty)
         }
 
+        if let Some(tags) = args.tags() {
+            req = req.header(constants::X_MS_TAGS, percent_encode_tags(tags));
+        }
+
+        if let Some(v) = args.storage_class() {
+            req = req.header(constants::X_MS_ACCESS_TIER, v);
+        }
+
+        if let Some(if_match) = args.if_match() {
+            req = req.header(IF_MATCH, if_match);
+        }
+
+        if let Some(if_none_match) = args.if_none_match() {
+            req = req.header(IF_NONE_MATCH, if_none_match);
+        }
+
         req = req.header(
             HeaderName::from_static(constants::X_MS_BLOB_TYPE),
             "BlockBlob",
         );
 
+        req = insert_immutability_headers(req, args);
+
         // Set body
         let req = req.body(body).map_err(new_request_build_error)?;
 
@@ -306,6 +615,10 @@ impl AzblobCore {
         // Set SSE headers.
         req = self.insert_sse_headers(req);
 
+        if let Some(lease_id) = args.lease_id() {
+            req = req.header(constants::X_MS_LEASE_ID, lease_id);
+        }
+
         // The content-length header must be set to zero
         // when creating an appendable blob.
         req = req.header(CONTENT_LENGTH, 0);
@@ -322,6 +635,12 @@ impl AzblobCore {
             req = req.header(constants::X_MS_BLOB_CACHE_CONTROL, cache_control);
         }
 
+        if let Some(tags) = args.tags() {
+            req = req.header(constants::X_MS_TAGS, percent_encode_tags(tags));
+        }
+
+        req = insert_immutability_headers(req, args);
+
         let req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
@@ -345,6 +664,7 @@ impl AzblobCore {
         path: &str,
         position: u64,
         size: u64,
+        lease_id: Option<&str>,
         body: AsyncBody,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
@@ -361,6 +681,10 @@ impl AzblobCore {
         // Set SSE headers.
         req = self.insert_sse_headers(req);
 
+        if let Some(lease_id) = lease_id {
+            req = req.header(constants::X_MS_LEASE_ID, lease_id);
+        }
+
         req = req.header(CONTENT_LENGTH, size);
 
         req = req.header(constants::X_MS_BLOB_CONDITION_APPENDPOS, position);
@@ -370,155 +694,695 @@ impl AzblobCore {
         Ok(req)
     }
 
-    pub fn azblob_head_blob_request(
+    /// Stage a block with given content, the block won't be visible until
+    /// committed by `azblob_put_block_list`.
+    ///
+    /// ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/put-block>
+    pub async fn azblob_put_block(
         &self,
         path: &str,
-        args: &OpStat,
-    ) -> Result<Request<AsyncBody>> {
+        block_id: &str,
+        lease_id: Option<&str>,
+        size: u64,
+        body: AsyncBody,
+    ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!(
-            "{}/{}/{}",
+            "{}/{}/{}?comp=block&blockid={}",
             self.endpoint,
             self.container,
-            percent_encode_path(&p)
+            percent_encode_path(&p),
+            percent_encode_path(block_id)
         );
 
-        let mut req = Request::head(&url);
+        let mut req = Request::put(&url);
 
         // Set SSE headers.
         req = self.insert_sse_headers(req);
 
-        if let Some(if_none_match) = args.if_none_match() {
-            req = req.header(IF_NONE_MATCH, if_none_match);
-        }
-
-        if let Some(if_match) = args.if_match() {
-            req = req.header(IF_MATCH, if_match);
+        if let Some(lease_id) = lease_id {
+            req = req.header(constants::X_MS_LEASE_ID, lease_id);
         }
 
-        let req = req
-            .body(AsyncBody::Empty)
+        let mut req = req
+            .header(CONTENT_LENGTH, size)
+            .body(body)
             .map_err(new_request_build_error)?;
 
-        Ok(req)
+        self.sign(&mut req).await?;
+        self.send(req).await
     }
 
-    pub async fn azblob_get_blob_properties(
+    /// Stage a block whose content will be read from given url by the
+    /// service, so that blobs can be composed without downloading.
+    ///
+    /// The source must be public or authorized by itself, like a url
+    /// carrying SAS token. Only the given `range` of source will be staged
+    /// if it's not full.
+    ///
+    /// ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/put-block-from-url>
+    pub async fn azblob_put_block_from_url(
         &self,
         path: &str,
-        args: &OpStat,
+        block_id: &str,
+        lease_id: Option<&str>,
+        source_url: &str,
+        range: BytesRange,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.azblob_head_blob_request(path, args)?;
-
-        self.sign(&mut req).await?;
-        self.send(req).await
-    }
-
-    pub fn azblob_delete_blob_request(&self, path: &str) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!(
-            "{}/{}/{}",
+            "{}/{}/{}?comp=block&blockid={}",
             self.endpoint,
             self.container,
-            percent_encode_path(&p)
+            percent_encode_path(&p),
+            percent_encode_path(block_id)
         );
 
-        let req = Request::delete(&url);
+        let mut req = Request::put(&url);
 
-        req.header(CONTENT_LENGTH, 0)
-            .body(AsyncBody::Empty)
-            .map_err(new_request_build_error)
-    }
+        // Set SSE headers.
+        req = self.insert_sse_headers(req);
+
+        if let Some(lease_id) = lease_id {
+            req = req.header(constants::X_MS_LEASE_ID, lease_id);
+        }
+
+        if !range.is_full() {
+            req = req.header(constants::X_MS_SOURCE_RANGE, range.to_header());
+        }
 
-    pub async fn azblob_delete_blob(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.azblob_delete_blob_request(path)?;
+        let mut req = req
+            .header(constants::X_MS_COPY_SOURCE, source_url)
+            .header(CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
 
         self.sign(&mut req).await?;
         self.send(req).await
     }
 
-    pub async fn azblob_copy_blob(
+    /// Head the source of `Put Block From URL` to learn its size.
+    ///
+    /// The source url authorizes itself, so the request is not signed.
+    pub async fn azblob_head_source(
         &self,
-        from: &str,
-        to: &str,
+        source_url: &str,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let source = build_abs_path(&self.root, from);
-        let target = build_abs_path(&self.root, to);
+        let req = Request::head(source_url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
 
-        let source = format!(
-            "{}/{}/{}",
-            self.endpoint,
-            self.container,
-            percent_encode_path(&source)
-        );
-        let target = format!(
-            "{}/{}/{}",
+        self.client.send(req).await
+    }
+
+    /// Commit staged blocks in given order as the content of blob.
+    ///
+    /// ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/put-block-list>
+    pub async fn azblob_put_block_list(
+        &self,
+        path: &str,
+        block_ids: &[String],
+        args: &OpWrite,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?comp=blocklist",
             self.endpoint,
             self.container,
-            percent_encode_path(&target)
+            percent_encode_path(&p)
         );
 
-        let mut req = Request::put(&target)
-            .header(constants::X_MS_COPY_SOURCE, source)
-            .header(CONTENT_LENGTH, 0)
-            .body(AsyncBody::Empty)
+        let mut req = Request::put(&url);
+
+        // Set SSE headers.
+        req = self.insert_sse_headers(req);
+
+        if let Some(lease_id) = args.lease_id() {
+            req = req.header(constants::X_MS_LEASE_ID, lease_id);
+        }
+
+        if let Some(cache_control) = args.cache_control() {
+            req = req.header(constants::X_MS_BLOB_CACHE_CONTROL, cache_control);
+        }
+        if let Some(ty) = args.content_type() {
+            req = req.header(constants::X_MS_BLOB_CONTENT_TYPE, ty)
+        }
+        if let Some(tags) = args.tags() {
+            req = req.header(constants::X_MS_TAGS, percent_encode_tags(tags));
+        }
+        if let Some(v) = args.storage_class() {
+            req = req.header(constants::X_MS_ACCESS_TIER, v);
+        }
+        if let Some(if_match) = args.if_match() {
+            req = req.header(IF_MATCH, if_match);
+        }
+        if let Some(if_none_match) = args.if_none_match() {
+            req = req.header(IF_NONE_MATCH, if_none_match);
+        }
+
+        req = insert_immutability_headers(req, args);
+
+        let content = quick_xml::se::to_string(&PutBlockListRequest {
+            latest: block_ids.to_vec(),
+        })
+        .map_err(new_xml_deserialize_error)?;
+
+        let mut req = req
+            .header(CONTENT_LENGTH, content.len())
+            .header(CONTENT_TYPE, "application/xml")
+            .body(AsyncBody::Bytes(Bytes::from(content)))
             .map_err(new_request_build_error)?;
 
         self.sign(&mut req).await?;
         self.send(req).await
     }
 
-    pub async fn azblob_list_blobs(
+    pub fn azblob_head_blob_request(
         &self,
         path: &str,
-        next_marker: &str,
-        delimiter: &str,
-        limit: Option<usize>,
-    ) -> Result<Response<IncomingAsyncBody>> {
+        args: &OpStat,
+    ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         let mut url = format!(
-            "{}/{}?restype=container&comp=list",
-            self.endpoint, self.container
+            "{}/{}/{}",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
         );
-        if !p.is_empty() {
-            write!(url, "&prefix={}", percent_encode_path(&p))
-                .expect("write into string must succeed");
+        let mut query_args = Vec::new();
+        if let Some(snapshot) = args.snapshot() {
+            query_args.push(format!("snapshot={}", percent_encode_path(snapshot)))
         }
-        if let Some(limit) = limit {
-            write!(url, "&maxresults={limit}").expect("write into string must succeed");
+        if let Some(version) = args.version() {
+            query_args.push(format!("versionid={}", percent_encode_path(version)))
         }
-        if !delimiter.is_empty() {
-            write!(url, "&delimiter={delimiter}").expect("write into string must succeed");
+        if !query_args.is_empty() {
+            write!(url, "?{}", query_args.join("&")).expect("write into string must succeed");
         }
-        if !next_marker.is_empty() {
-            write!(url, "&marker={next_marker}").expect("write into string must succeed");
+
+        let mut req = Request::head(&url);
+
+        // Set SSE headers.
+        req = self.insert_sse_headers(req);
+
+        if let Some(if_none_match) = args.if_none_match() {
+            req = req.header(IF_NONE_MATCH, if_none_match);
         }
 
-        let mut req = Request::get(&url)
+        if let Some(if_match) = args.if_match() {
+            req = req.header(IF_MATCH, if_match);
+        }
+
+        let req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
-        self.sign(&mut req).await?;
-        self.send(req).await
+        Ok(req)
     }
 
-    pub async fn azblob_batch_delete(
+    pub async fn azblob_get_blob_properties(
         &self,
-        paths: &[String],
+        path: &str,
+        args: &OpStat,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let url = format!(
-            "{}/{}?restype=container&comp=batch",
-            self.endpoint, self.container
-        );
-
-        let mut multipart = Multipart::new();
+        let req = self.azblob_head_blob_request(path, args)?;
 
-        for (idx, path) in paths.iter().enumerate() {
-            let mut req = self.azblob_delete_blob_request(path)?;
-            self.batch_sign(&mut req).await?;
+        self.send_read(req).await
+    }
+
+    /// Get blob index tags of blob.
+    ///
+    /// ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/get-blob-tags>
+    pub async fn azblob_get_blob_tags(
+        &self,
+        path: &str,
+        args: &OpStat,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!(
+            "{}/{}/{}?comp=tags",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
+        );
+        if let Some(snapshot) = args.snapshot() {
+            write!(url, "&snapshot={}", percent_encode_path(snapshot))
+                .expect("write into string must succeed");
+        }
+        if let Some(version) = args.version() {
+            write!(url, "&versionid={}", percent_encode_path(version))
+                .expect("write into string must succeed");
+        }
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    pub async fn azblob_get_container_properties(&self) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{}/{}?restype=container", self.endpoint, self.container);
+
+        let req = Request::head(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.send_read(req).await
+    }
+
+    /// ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/create-container>
+    pub async fn azblob_create_container(&self) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{}/{}?restype=container", self.endpoint, self.container);
+
+        let mut req = Request::put(&url)
+            .header(CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/delete-container>
+    pub async fn azblob_delete_container(&self) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{}/{}?restype=container", self.endpoint, self.container);
+
+        let mut req = Request::delete(&url)
+            .header(CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/list-containers2>
+    pub async fn azblob_list_containers(
+        &self,
+        marker: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut url = format!("{}/?comp=list", self.endpoint);
+        if !marker.is_empty() {
+            write!(url, "&marker={}", percent_encode_path(marker))
+                .expect("write into string must succeed");
+        }
+
+        let req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.send_read(req).await
+    }
+
+    /// List all containers in the account by following the markers.
+    pub async fn azblob_list_all_containers(&self) -> Result<Vec<Container>> {
+        let mut containers = vec![];
+        let mut marker = String::new();
+        loop {
+            let resp = self.azblob_list_containers(&marker).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let output: ListContainersOutput =
+                quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+            containers.extend(output.containers.container);
+
+            match output.next_marker {
+                Some(v) if !v.is_empty() => marker = v,
+                _ => return Ok(containers),
+            }
+        }
+    }
+
+    pub fn azblob_delete_blob_request(
+        &self,
+        path: &str,
+        args: &OpDelete,
+    ) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!(
+            "{}/{}/{}",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
+        );
+        if let Some(version) = args.version() {
+            write!(url, "?versionid={}", percent_encode_path(version))
+                .expect("write into string must succeed");
+        }
+
+        let mut req = Request::delete(&url);
+
+        if let Some(lease_id) = args.lease_id() {
+            req = req.header(constants::X_MS_LEASE_ID, lease_id);
+        }
+
+        // Blobs with snapshots can't be deleted alone, azblob will return
+        // `409 Conflict` unless users decide how to handle the snapshots.
+        if let Some(snapshots) = args.snapshots() {
+            let v = match snapshots {
+                DeleteSnapshots::Include => "include",
+                DeleteSnapshots::Only => "only",
+            };
+            req = req.header(constants::X_MS_DELETE_SNAPSHOTS, v);
+        }
+
+        req.header(CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)
+    }
+
+    pub async fn azblob_delete_blob(
+        &self,
+        path: &str,
+        args: &OpDelete,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = self.azblob_delete_blob_request(path, args)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    pub async fn azblob_copy_blob(
+        &self,
+        from: &str,
+        to: &str,
+        args: &OpCopy,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let source = match args.source_url() {
+            Some(url) => url.to_string(),
+            None => format!(
+                "{}/{}/{}",
+                self.endpoint,
+                self.container,
+                percent_encode_path(&build_abs_path(&self.root, from))
+            ),
+        };
+        let target = build_abs_path(&self.root, to);
+
+        let target = format!(
+            "{}/{}/{}",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&target)
+        );
+
+        let mut req = Request::put(&target)
+            .header(constants::X_MS_COPY_SOURCE, source)
+            .header(CONTENT_LENGTH, 0);
+        if let Some(v) = args.storage_class() {
+            req = req.header(constants::X_MS_ACCESS_TIER, v);
+        }
+        // Metadata of source will be copied if no metadata specified.
+        if let Some(user_metadata) = args.user_metadata() {
+            for (k, v) in user_metadata {
+                req = req.header(format!("{}{k}", constants::X_MS_META_PREFIX), v);
+            }
+        }
+        if let Some(tags) = args.tags() {
+            req = req.header(constants::X_MS_TAGS, percent_encode_tags(tags));
+        }
+
+        let mut req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Wait until the copy started by Copy Blob has finished.
+    ///
+    /// Copy Blob from urls or other accounts is asynchronous, the response
+    /// could be returned with `x-ms-copy-status: pending` before the data
+    /// lands, so we poll the status of target blob.
+    ///
+    /// ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/copy-blob>
+    pub async fn azblob_wait_copy(&self, path: &str, headers: &HeaderMap) -> Result<()> {
+        let mut status =
+            parse_header_to_str(headers, constants::X_MS_COPY_STATUS)?.map(|v| v.to_string());
+        let mut description = None;
+        let mut delay = Duration::from_millis(100);
+        loop {
+            match status.as_deref() {
+                None | Some("success") => return Ok(()),
+                Some("pending") => {}
+                Some(v) => {
+                    let mut err = Error::new(ErrorKind::Unexpected, "copy blob didn't succeed")
+                        .with_operation("Backend::azblob_wait_copy")
+                        .with_context("copy_status", v);
+                    if let Some(desc) = &description {
+                        err = err.with_context("copy_status_description", desc);
+                    }
+                    return Err(err);
+                }
+            }
+
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(Duration::from_secs(5));
+
+            let resp = self
+                .azblob_get_blob_properties(path, &OpStat::default())
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+            status = parse_header_to_str(resp.headers(), constants::X_MS_COPY_STATUS)?
+                .map(|v| v.to_string());
+            description =
+                parse_header_to_str(resp.headers(), constants::X_MS_COPY_STATUS_DESCRIPTION)?
+                    .map(|v| v.to_string());
+            resp.into_body().consume().await?;
+        }
+    }
+
+    /// Replace the content type and cache control of blob.
+    ///
+    /// Set Blob Properties will clear all http headers not specified, so
+    /// `current` headers of blob are required to preserve them.
+    ///
+    /// ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/set-blob-properties>
+    pub async fn azblob_set_blob_properties(
+        &self,
+        path: &str,
+        current: &HeaderMap,
+        args: &OpCopy,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?comp=properties",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
+        );
+
+        let mut req = Request::put(&url).header(CONTENT_LENGTH, 0);
+
+        let content_type = args.content_type().or(parse_content_type(current)?);
+        if let Some(v) = content_type {
+            req = req.header(constants::X_MS_BLOB_CONTENT_TYPE, v);
+        }
+        let cache_control = args.cache_control().or(parse_cache_control(current)?);
+        if let Some(v) = cache_control {
+            req = req.header(constants::X_MS_BLOB_CACHE_CONTROL, v);
+        }
+        for (name, header) in [
+            (
+                constants::X_MS_BLOB_CONTENT_DISPOSITION,
+                CONTENT_DISPOSITION,
+            ),
+            (constants::X_MS_BLOB_CONTENT_ENCODING, CONTENT_ENCODING),
+            (constants::X_MS_BLOB_CONTENT_LANGUAGE, CONTENT_LANGUAGE),
+            (
+                constants::X_MS_BLOB_CONTENT_MD5,
+                HeaderName::from_static("content-md5"),
+            ),
+        ] {
+            if let Some(v) = current.get(header) {
+                req = req.header(name, v);
+            }
+        }
+
+        let mut req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Change the access tier of blob in place.
+    ///
+    /// ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/set-blob-tier>
+    pub async fn azblob_set_blob_tier(
+        &self,
+        path: &str,
+        tier: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?comp=tier",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
+        );
+
+        let mut req = Request::put(&url)
+            .header(constants::X_MS_ACCESS_TIER, tier)
+            .header(CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Create a read-only snapshot of blob, the id of snapshot will be
+    /// returned in `x-ms-snapshot` header.
+    ///
+    /// ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/snapshot-blob>
+    pub async fn azblob_create_snapshot(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?comp=snapshot",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
+        );
+
+        let mut req = Request::put(&url);
+
+        // Set SSE headers.
+        req = self.insert_sse_headers(req);
+
+        let mut req = req
+            .header(CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Acquire, renew, release or break the lease of blob.
+    ///
+    /// ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/lease-blob>
+    pub async fn azblob_lease_blob(
+        &self,
+        path: &str,
+        action: &LeaseAction,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?comp=lease",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
+        );
+
+        let mut req = Request::put(&url).header(CONTENT_LENGTH, 0);
+        req = match action {
+            LeaseAction::Acquire(duration) => {
+                // -1 means infinite lease.
+                let duration = duration.map(|v| v.as_secs() as i64).unwrap_or(-1);
+                req.header(constants::X_MS_LEASE_ACTION, "acquire")
+                    .header(constants::X_MS_LEASE_DURATION, duration)
+            }
+            LeaseAction::Renew(lease_id) => req
+                .header(constants::X_MS_LEASE_ACTION, "renew")
+                .header(constants::X_MS_LEASE_ID, lease_id),
+            LeaseAction::Release(lease_id) => req
+                .header(constants::X_MS_LEASE_ACTION, "release")
+                .header(constants::X_MS_LEASE_ID, lease_id),
+            // Break the lease immediately.
+            LeaseAction::Break => req
+                .header(constants::X_MS_LEASE_ACTION, "break")
+                .header(constants::X_MS_LEASE_BREAK_PERIOD, 0),
+        };
+
+        let mut req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    pub async fn azblob_list_blobs(
+        &self,
+        path: &str,
+        next_marker: &str,
+        delimiter: &str,
+        limit: Option<usize>,
+        snapshots: bool,
+        versions: bool,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!(
+            "{}/{}?restype=container&comp=list",
+            self.endpoint, self.container
+        );
+        if !p.is_empty() {
+            write!(url, "&prefix={}", percent_encode_path(&p))
+                .expect("write into string must succeed");
+        }
+        if let Some(limit) = limit {
+            write!(url, "&maxresults={limit}").expect("write into string must succeed");
+        }
+        if !delimiter.is_empty() {
+            write!(url, "&delimiter={}", percent_encode_path(delimiter))
+                .expect("write into string must succeed");
+        }
+        if !next_marker.is_empty() {
+            write!(url, "&marker={next_marker}").expect("write into string must succeed");
+        }
+        let include = match (snapshots, versions) {
+            (true, true) => "snapshots,versions",
+            (true, false) => "snapshots",
+            (false, true) => "versions",
+            (false, false) => "",
+        };
+        if !include.is_empty() {
+            write!(url, "&include={include}").expect("write into string must succeed");
+        }
+
+        let mut req = with_accept_encoding(Request::get(&url))
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    pub async fn azblob_batch_delete(
+        &self,
+        paths: &[String],
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!(
+            "{}/{}?restype=container&comp=batch",
+            self.endpoint, self.container
+        );
+
+        let mut multipart = Multipart::new();
+
+        for (idx, path) in paths.iter().enumerate() {
+            let mut req = self.azblob_delete_blob_request(path, &OpDelete::new())?;
+            self.batch_sign(&mut req).await?;
 
             multipart = multipart.part(
                 MixedPart::from_request(req).part_header("content-id".parse().unwrap(), idx.into()),
@@ -531,6 +1395,243 @@ impl AzblobCore {
         self.sign(&mut req).await?;
         self.send(req).await
     }
+
+    /// Mint a SAS token that only allows given scope on the path.
+    ///
+    /// User delegation SAS will be used for bearer token, otherwise service
+    /// SAS signed by account key.
+    pub async fn azblob_delegate(
+        &self,
+        path: &str,
+        scope: FlagSet<DelegateScope>,
+        expire: Duration,
+    ) -> Result<DelegatedCredential> {
+        let now = Utc::now();
+        let expires_at = now
+            + chrono::Duration::from_std(expire).map_err(|err| {
+                Error::new(ErrorKind::InvalidInput, "expire is out of range").set_source(err)
+            })?;
+        let sas = Sas::new(&self.container, path, scope, expires_at)
+            .with_version(self.api_version().to_str().unwrap_or_default());
+
+        let token = match self.load_credential().await? {
+            AzureStorageCredential::SharedKey(account, key) => {
+                sas.sign_by_shared_key(&account, &key)?
+            }
+            AzureStorageCredential::BearerToken(..) => {
+                self.azblob_sign_by_user_delegation_key(&sas, now, expires_at)
+                    .await?
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "delegated SAS requires account key or bearer token",
+                ))
+            }
+        };
+
+        Ok(DelegatedCredential::AzureSas { token, expires_at })
+    }
+
+    /// Build a url of given path that can be read by the service itself,
+    /// which is used as the source of `Put Block From URL`.
+    ///
+    /// The configured SAS token will be used if exists, otherwise a read
+    /// only SAS will be minted.
+    pub async fn azblob_source_url(&self, path: &str) -> Result<String> {
+        let p = build_abs_path(&self.root, path);
+        let url = format!(
+            "{}/{}/{}",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
+        );
+
+        let token = match self.load_credential().await? {
+            AzureStorageCredential::SharedAccessSignature(token) => token,
+            _ => {
+                let cred = self
+                    .azblob_delegate(&p, DelegateScope::Read.into(), Duration::from_secs(3600))
+                    .await?;
+                let DelegatedCredential::AzureSas { token, .. } = cred else {
+                    return Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "delegated credential of azblob must be SAS",
+                    ));
+                };
+                token
+            }
+        };
+
+        Ok(format!("{url}?{}", token.trim_start_matches('?')))
+    }
+
+    /// Sign the SAS by a user delegation key fetched with current
+    /// credential, which is valid between `start` and `expiry`.
+    pub async fn azblob_sign_by_user_delegation_key(
+        &self,
+        sas: &Sas,
+        start: DateTime<Utc>,
+        expiry: DateTime<Utc>,
+    ) -> Result<String> {
+        let key = self
+            .azblob_get_user_delegation_key(&format_sas_time(start), &format_sas_time(expiry))
+            .await?;
+        sas.sign_by_user_delegation_key(self.account_name()?, &key)
+    }
+
+    async fn azblob_get_user_delegation_key(
+        &self,
+        start: &str,
+        expiry: &str,
+    ) -> Result<UserDelegationKey> {
+        let url = format!("{}/?restype=service&comp=userdelegationkey", self.endpoint);
+
+        let content = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?><KeyInfo><Start>{start}</Start><Expiry>{expiry}</Expiry></KeyInfo>"#
+        );
+        let mut req = Request::post(&url)
+            .header(CONTENT_LENGTH, content.len())
+            .header(CONTENT_TYPE, "application/xml")
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        let resp = self.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        quick_xml::de::from_reader(bytes::Buf::reader(bs)).map_err(new_xml_deserialize_error)
+    }
+
+    fn account_name(&self) -> Result<&str> {
+        self.account_name.as_deref().ok_or_else(|| {
+            Error::new(
+                ErrorKind::ConfigInvalid,
+                "account_name is required to sign user delegation SAS",
+            )
+            .with_context("endpoint", &self.endpoint)
+        })
+    }
+}
+
+/// Parse access tier header into storage class of metadata.
+pub fn parse_access_tier(headers: &HeaderMap, meta: &mut Metadata) -> Result<()> {
+    if let Some(v) = parse_header_to_str(headers, constants::X_MS_ACCESS_TIER)? {
+        meta.set_storage_class(v);
+    }
+    Ok(())
+}
+
+pub fn parse_lease_id(headers: &HeaderMap) -> Result<Option<&str>> {
+    parse_header_to_str(headers, constants::X_MS_LEASE_ID)
+}
+
+pub fn parse_tag_count(headers: &HeaderMap) -> Result<usize> {
+    match parse_header_to_str(headers, constants::X_MS_TAG_COUNT)? {
+        Some(v) => v.parse().map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "header value is not valid integer")
+                .with_context("header", constants::X_MS_TAG_COUNT)
+                .set_source(err)
+        }),
+        None => Ok(0),
+    }
+}
+
+pub fn parse_snapshot(headers: &HeaderMap) -> Result<Option<&str>> {
+    parse_header_to_str(headers, constants::X_MS_SNAPSHOT)
+}
+
+pub fn parse_version_id(headers: &HeaderMap) -> Result<Option<&str>> {
+    parse_header_to_str(headers, constants::X_MS_VERSION_ID)
+}
+
+pub fn parse_is_current_version(headers: &HeaderMap) -> Result<Option<bool>> {
+    parse_header_to_str(headers, constants::X_MS_IS_CURRENT_VERSION)
+        .map(|v| v.map(|v| v.eq_ignore_ascii_case("true")))
+}
+
+/// Insert immutability policy and legal hold headers, which requires
+/// version-level immutability support enabled on the container.
+///
+/// ref: <https://learn.microsoft.com/en-us/azure/storage/blobs/immutable-storage-overview>
+pub fn insert_immutability_headers(
+    mut req: http::request::Builder,
+    args: &OpWrite,
+) -> http::request::Builder {
+    if let Some(retention) = args.retention() {
+        let mode = match retention.mode() {
+            RetentionMode::Governance => "Unlocked",
+            RetentionMode::Compliance => "Locked",
+        };
+        req = req
+            .header(constants::X_MS_IMMUTABILITY_POLICY_MODE, mode)
+            .header(
+                constants::X_MS_IMMUTABILITY_POLICY_UNTIL_DATE,
+                retention
+                    .retain_until()
+                    .format("%a, %d %b %Y %H:%M:%S GMT")
+                    .to_string(),
+            );
+    }
+    if let Some(legal_hold) = args.legal_hold() {
+        req = req.header(constants::X_MS_LEGAL_HOLD, legal_hold.to_string());
+    }
+    req
+}
+
+/// Parse immutability policy and legal hold headers into metadata.
+pub fn parse_immutability(headers: &HeaderMap, meta: &mut Metadata) -> Result<()> {
+    let mode = parse_header_to_str(headers, constants::X_MS_IMMUTABILITY_POLICY_MODE)?;
+    let until = parse_header_to_str(headers, constants::X_MS_IMMUTABILITY_POLICY_UNTIL_DATE)?;
+    if let (Some(mode), Some(until)) = (mode, until) {
+        let mode = match mode.to_ascii_lowercase().as_str() {
+            "unlocked" => RetentionMode::Governance,
+            "locked" => RetentionMode::Compliance,
+            // `Mutable` means the policy has been removed.
+            _ => return parse_legal_hold(headers, meta),
+        };
+        meta.set_retention(Retention::new(mode, parse_datetime_from_rfc2822(until)?));
+    }
+
+    parse_legal_hold(headers, meta)
+}
+
+fn parse_legal_hold(headers: &HeaderMap, meta: &mut Metadata) -> Result<()> {
+    if let Some(v) = parse_header_to_str(headers, constants::X_MS_LEGAL_HOLD)? {
+        meta.set_legal_hold(v.eq_ignore_ascii_case("true"));
+    }
+    Ok(())
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListContainersOutput {
+    pub containers: Containers,
+    #[serde(rename = "NextMarker")]
+    pub next_marker: Option<String>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct Containers {
+    pub container: Vec<Container>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct Container {
+    pub name: String,
+    pub properties: ContainerProperties,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ContainerProperties {
+    #[serde(rename = "Last-Modified")]
+    pub last_modified: String,
 }
 
 #[derive(Default, Debug, Deserialize)]
@@ -541,6 +1642,43 @@ pub struct ListBlobsOutput {
     pub next_marker: Option<String>,
 }
 
+/// Output of Get Blob Tags.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct BlobTags {
+    pub tag_set: TagSet,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct TagSet {
+    pub tag: Vec<Tag>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct Tag {
+    pub key: String,
+    pub value: String,
+}
+
+impl BlobTags {
+    pub fn into_map(self) -> HashMap<String, String> {
+        self.tag_set
+            .tag
+            .into_iter()
+            .map(|v| (v.key, v.value))
+            .collect()
+    }
+}
+
+/// Request of Put Block List, blocks will be committed in order.
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename = "BlockList", rename_all = "PascalCase")]
+pub struct PutBlockListRequest {
+    pub latest: Vec<String>,
+}
+
 #[derive(Default, Debug, Deserialize)]
 #[serde(default, rename_all = "PascalCase")]
 pub struct Blobs {
@@ -559,6 +1697,9 @@ pub struct BlobPrefix {
 pub struct Blob {
     pub properties: Properties,
     pub name: String,
+    pub snapshot: Option<String>,
+    pub version_id: Option<String>,
+    pub is_current_version: Option<bool>,
 }
 
 #[derive(Default, Debug, Deserialize)]
@@ -573,6 +1714,7 @@ pub struct Properties {
     #[serde(rename = "Content-Type")]
     pub content_type: String,
     pub etag: String,
+    pub access_tier: Option<String>,
 }
 
 #[cfg(test)]
@@ -583,6 +1725,93 @@ mod tests {
 
     use super::*;
 
+    #[tokio::test]
+    async fn test_update_credential() -> Result<()> {
+        let mut builder = crate::services::Azblob::default();
+        builder
+            .endpoint("https://account.blob.core.windows.net")
+            .container("container")
+            .account_key("account-key");
+        let op = Operator::new(builder)?.finish();
+
+        op.update_credential(Credential::AzureSas("sig=rotated".into()))
+            .await?;
+        let signer = op.info().signer().expect("signer must exist");
+        let mut req = Request::get("https://account.blob.core.windows.net/container/path")
+            .body(AsyncBody::Empty)
+            .expect("request must be valid");
+        signer.sign(&mut req).await?;
+        assert!(req.uri().to_string().contains("sig=rotated"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_api_version() -> Result<()> {
+        let mut builder = crate::services::Azblob::default();
+        builder
+            .endpoint("https://account.blob.core.windows.net")
+            .container("container")
+            .account_key("YWNjb3VudC1rZXk=");
+        let op = Operator::new(builder)?.finish();
+        let signer = op.info().signer().expect("signer must exist");
+
+        op.update_api_version("2024-08-04")?;
+        let mut req = Request::get("https://account.blob.core.windows.net/container/path")
+            .body(AsyncBody::Empty)
+            .expect("request must be valid");
+        signer.sign(&mut req).await?;
+        assert_eq!(req.headers()[constants::X_MS_VERSION], "2024-08-04");
+
+        // Minted SAS follows the api version too.
+        let cred = op
+            .delegate("path", DelegateScope::Read.into(), Duration::from_secs(60))
+            .await?;
+        let DelegatedCredential::AzureSas { token, .. } = cred else {
+            panic!("delegated credential of azblob must be SAS")
+        };
+        assert!(token.starts_with("sv=2024%2D08%2D04&"));
+
+        let err = op
+            .update_api_version("2019-12-12")
+            .expect_err("unsupported version must be rejected");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_list_containers_output() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="https://account.blob.core.windows.net/">
+  <Containers>
+    <Container>
+      <Name>logs</Name>
+      <Properties><Last-Modified>Mon, 11 Sep 2023 08:30:15 GMT</Last-Modified><Etag>0x8D1</Etag></Properties>
+    </Container>
+    <Container>
+      <Name>data</Name>
+      <Properties><Etag>0x8D2</Etag></Properties>
+    </Container>
+  </Containers>
+  <NextMarker>/account/marker</NextMarker>
+</EnumerationResults>"#,
+        );
+
+        let out: ListContainersOutput = de::from_reader(bs.reader()).expect("must success");
+        let names: Vec<_> = out
+            .containers
+            .container
+            .iter()
+            .map(|v| v.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["logs", "data"]);
+        assert_eq!(
+            out.containers.container[0].properties.last_modified,
+            "Mon, 11 Sep 2023 08:30:15 GMT"
+        );
+        assert_eq!(out.next_marker.as_deref(), Some("/account/marker"));
+    }
+
     #[test]
     fn test_parse_xml() {
         let bs = bytes::Bytes::from(
@@ -761,4 +1990,200 @@ mod tests {
 
         de::from_reader(Bytes::from(bs).reader()).expect("must success")
     }
+
+    #[test]
+    fn test_parse_snapshots() {
+        let bs = bytes::Bytes::from(
+            r#"
+            <?xml version="1.0" encoding="utf-8"?>
+            <EnumerationResults ServiceEndpoint="https://test.blob.core.windows.net/" ContainerName="test">
+                <Prefix>backup/db</Prefix>
+                <Blobs>
+                    <Blob>
+                        <Name>backup/db</Name>
+                        <Snapshot>2023-12-01T08:00:00.1234567Z</Snapshot>
+                        <Properties>
+                            <Last-Modified>Fri, 01 Dec 2023 08:00:00 GMT</Last-Modified>
+                            <Etag>0x8DBF24A5E1B2C3D</Etag>
+                            <Content-Length>3</Content-Length>
+                        </Properties>
+                    </Blob>
+                    <Blob>
+                        <Name>backup/db</Name>
+                        <Properties>
+                            <Last-Modified>Sat, 02 Dec 2023 08:00:00 GMT</Last-Modified>
+                            <Etag>0x8DBF24A5E1B2C3E</Etag>
+                            <Content-Length>5</Content-Length>
+                        </Properties>
+                    </Blob>
+                </Blobs>
+                <NextMarker />
+            </EnumerationResults>"#,
+        );
+        let out: ListBlobsOutput = de::from_reader(bs.reader()).expect("must success");
+
+        let snapshots: Vec<_> = out
+            .blobs
+            .blob
+            .iter()
+            .map(|v| (v.name.as_str(), v.snapshot.as_deref()))
+            .collect();
+        assert_eq!(
+            snapshots,
+            vec![
+                ("backup/db", Some("2023-12-01T08:00:00.1234567Z")),
+                ("backup/db", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_versions() {
+        let bs = Bytes::from(
+            r#"
+            <?xml version="1.0" encoding="utf-8"?>
+            <EnumerationResults ServiceEndpoint="https://test.blob.core.windows.net/" ContainerName="test">
+                <Prefix>backup/db</Prefix>
+                <Blobs>
+                    <Blob>
+                        <Name>backup/db</Name>
+                        <VersionId>2023-12-01T08:00:00.1234567Z</VersionId>
+                        <Properties>
+                            <Last-Modified>Fri, 01 Dec 2023 08:00:00 GMT</Last-Modified>
+                            <Etag>0x8DBF24A5E1B2C3D</Etag>
+                            <Content-Length>3</Content-Length>
+                        </Properties>
+                    </Blob>
+                    <Blob>
+                        <Name>backup/db</Name>
+                        <VersionId>2023-12-02T08:00:00.1234567Z</VersionId>
+                        <IsCurrentVersion>true</IsCurrentVersion>
+                        <Properties>
+                            <Last-Modified>Sat, 02 Dec 2023 08:00:00 GMT</Last-Modified>
+                            <Etag>0x8DBF24A5E1B2C3E</Etag>
+                            <Content-Length>5</Content-Length>
+                        </Properties>
+                    </Blob>
+                </Blobs>
+                <NextMarker />
+            </EnumerationResults>"#,
+        );
+        let out: ListBlobsOutput = de::from_reader(bs.reader()).expect("must success");
+
+        let versions: Vec<_> = out
+            .blobs
+            .blob
+            .iter()
+            .map(|v| (v.version_id.as_deref(), v.is_current_version))
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                (Some("2023-12-01T08:00:00.1234567Z"), None),
+                (Some("2023-12-02T08:00:00.1234567Z"), Some(true))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_serialize_put_block_list_request() {
+        let req = PutBlockListRequest {
+            latest: vec!["MDAwMQ==".to_string(), "MDAwMg==".to_string()],
+        };
+
+        let actual = quick_xml::se::to_string(&req).expect("must succeed");
+        assert_eq!(
+            actual,
+            "<BlockList><Latest>MDAwMQ==</Latest><Latest>MDAwMg==</Latest></BlockList>"
+        );
+    }
+
+    #[test]
+    fn test_parse_blob_tags() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <Tags>
+                <TagSet>
+                    <Tag>
+                        <Key>project</Key>
+                        <Value>opendal</Value>
+                    </Tag>
+                    <Tag>
+                        <Key>tier</Key>
+                        <Value>cold</Value>
+                    </Tag>
+                </TagSet>
+            </Tags>"#,
+        );
+        let out: BlobTags = de::from_reader(bs.reader()).expect("must success");
+
+        assert_eq!(
+            out.into_map(),
+            HashMap::from([
+                ("project".to_string(), "opendal".to_string()),
+                ("tier".to_string(), "cold".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_is_credential_rejected() {
+        let build = |status: StatusCode, code: Option<&str>| {
+            let mut resp = Response::builder().status(status);
+            if let Some(code) = code {
+                resp = resp.header("x-ms-error-code", code);
+            }
+            resp.body(()).expect("response must be valid")
+        };
+
+        assert!(is_credential_rejected(&build(
+            StatusCode::UNAUTHORIZED,
+            None
+        )));
+        assert!(is_credential_rejected(&build(
+            StatusCode::FORBIDDEN,
+            Some("AuthenticationFailed")
+        )));
+        assert!(!is_credential_rejected(&build(
+            StatusCode::FORBIDDEN,
+            Some("AuthorizationPermissionMismatch")
+        )));
+        assert!(!is_credential_rejected(&build(StatusCode::FORBIDDEN, None)));
+        assert!(!is_credential_rejected(&build(StatusCode::NOT_FOUND, None)));
+    }
+
+    #[test]
+    fn test_immutability_roundtrip() {
+        let retain_until = DateTime::parse_from_rfc3339("2030-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let args = OpWrite::new()
+            .with_retention(Retention::new(RetentionMode::Compliance, retain_until))
+            .with_legal_hold(true);
+        let req = insert_immutability_headers(Request::put("https://example.com"), &args)
+            .body(())
+            .expect("request must be valid");
+        assert_eq!(
+            req.headers()[constants::X_MS_IMMUTABILITY_POLICY_UNTIL_DATE],
+            "Tue, 01 Jan 2030 00:00:00 GMT"
+        );
+
+        let mut meta = Metadata::new(EntryMode::FILE);
+        parse_immutability(req.headers(), &mut meta).expect("must succeed");
+        let retention = meta.retention().expect("retention must be set");
+        assert_eq!(retention.mode(), RetentionMode::Compliance);
+        assert_eq!(retention.retain_until(), retain_until);
+        assert_eq!(meta.legal_hold(), Some(true));
+
+        // `Mutable` means the policy has been removed.
+        let mut headers = req.headers().clone();
+        headers.insert(
+            constants::X_MS_IMMUTABILITY_POLICY_MODE,
+            HeaderValue::from_static("Mutable"),
+        );
+        let mut meta = Metadata::new(EntryMode::FILE);
+        parse_immutability(&headers, &mut meta).expect("must succeed");
+        assert!(!meta.metakey().contains(Metakey::Retention));
+        assert_eq!(meta.legal_hold(), Some(true));
+    }
 }
@@ -65,7 +65,7 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     let (parts, body) = resp.into_parts();
     let bs = body.bytes().await?;
 
-    let (kind, retryable) = match parts.status {
+    let (mut kind, retryable) = match parts.status {
         StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
         StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
         StatusCode::PRECONDITION_FAILED | StatusCode::NOT_MODIFIED => {
@@ -98,6 +98,15 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
         }
     }
 
+    if let Some(v) = parts
+        .headers
+        .get("x-ms-error-code")
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_azblob_error_code)
+    {
+        kind = v;
+    }
+
     let mut err = Error::new(kind, &message);
 
     err = with_error_response_context(err, parts);
@@ -109,6 +118,19 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     Ok(err)
 }
 
+/// Returns the `ErrorKind` of error codes that can't be told from status.
+///
+/// All possible error code: <https://learn.microsoft.com/en-us/rest/api/storageservices/common-rest-api-error-codes>
+fn parse_azblob_error_code(code: &str) -> Option<ErrorKind> {
+    match code {
+        // Azure returns 403 for both invalid credentials and missing rights.
+        "AuthenticationFailed" | "InvalidAuthenticationInfo" | "NoAuthenticationInformation" => {
+            Some(ErrorKind::Unauthenticated)
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
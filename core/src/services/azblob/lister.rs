@@ -31,19 +31,33 @@ pub struct AzblobLister {
     core: Arc<AzblobCore>,
 
     path: String,
-    delimiter: &'static str,
+    delimiter: String,
     limit: Option<usize>,
+    snapshots: bool,
+    versions: bool,
 }
 
 impl AzblobLister {
-    pub fn new(core: Arc<AzblobCore>, path: String, recursive: bool, limit: Option<usize>) -> Self {
-        let delimiter = if recursive { "" } else { "/" };
+    pub fn new(
+        core: Arc<AzblobCore>,
+        path: String,
+        recursive: bool,
+        delimiter: Option<&str>,
+        limit: Option<usize>,
+        snapshots: bool,
+        versions: bool,
+    ) -> Self {
+        let delimiter = delimiter
+            .unwrap_or(if recursive { "" } else { "/" })
+            .to_string();
 
         Self {
             core,
             path,
             delimiter,
             limit,
+            snapshots,
+            versions,
         }
     }
 }
@@ -53,7 +67,14 @@ impl oio::PageList for AzblobLister {
     async fn next_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
         let resp = self
             .core
-            .azblob_list_blobs(&self.path, &ctx.token, self.delimiter, self.limit)
+            .azblob_list_blobs(
+                &self.path,
+                &ctx.token,
+                &self.delimiter,
+                self.limit,
+                self.snapshots,
+                self.versions,
+            )
             .await?;
 
         if resp.status() != http::StatusCode::OK {
@@ -76,9 +97,11 @@ impl oio::PageList for AzblobLister {
         let prefixes = output.blobs.blob_prefix;
 
         for prefix in prefixes {
+            // Prefixes grouped by custom delimiter don't end with `/`, and
+            // can't be listed as dirs.
             let de = oio::Entry::new(
                 &build_rel_path(&self.core.root, &prefix.name),
-                Metadata::new(EntryMode::DIR),
+                Metadata::new(EntryMode::from_path(&prefix.name)),
             );
 
             ctx.entries.push_back(de)
@@ -87,12 +110,14 @@ impl oio::PageList for AzblobLister {
         for object in output.blobs.blob {
             let path = build_rel_path(&self.core.root, &object.name);
 
-            // azblob could return the dir itself in contents.
-            if path == self.path {
+            // azblob could return the dir itself in contents, but snapshots
+            // and versions of it should be kept.
+            let is_version = self.versions && object.version_id.is_some();
+            if path == self.path && object.snapshot.is_none() && !is_version {
                 continue;
             }
 
-            let meta = Metadata::new(EntryMode::from_path(&path))
+            let mut meta = Metadata::new(EntryMode::from_path(&path))
                 // Keep fit with ETag header.
                 .with_etag(format!("\"{}\"", object.properties.etag.as_str()))
                 .with_content_length(object.properties.content_length)
@@ -101,6 +126,28 @@ impl oio::PageList for AzblobLister {
                 .with_last_modified(parse_datetime_from_rfc2822(
                     object.properties.last_modified.as_str(),
                 )?);
+            if let Some(v) = &object.properties.access_tier {
+                meta.set_storage_class(v);
+            }
+            if self.snapshots {
+                // Mark snapshot as known so that base objects won't be stat again.
+                let bit = meta.metakey();
+                meta = meta.with_metakey(bit | Metakey::Snapshot);
+                if let Some(v) = &object.snapshot {
+                    meta.set_snapshot(v);
+                }
+            }
+            if self.versions {
+                // Mark version as known so that entries won't be stat again.
+                let bit = meta.metakey();
+                meta = meta.with_metakey(bit | Metakey::Version);
+                if let Some(v) = &object.version_id {
+                    meta.set_version(v);
+                }
+                if let Some(v) = object.is_current_version {
+                    meta.set_is_current(v);
+                }
+            }
 
             let de = oio::Entry::with(path, meta);
             ctx.entries.push_back(de);
@@ -22,4 +22,5 @@ pub use backend::AzblobConfig;
 mod core;
 mod error;
 mod lister;
+mod sas;
 mod writer;
@@ -18,6 +18,8 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
 use http::StatusCode;
 
 use super::core::AzblobCore;
@@ -27,8 +29,16 @@ use crate::*;
 
 const X_MS_BLOB_TYPE: &str = "x-ms-blob-type";
 
-pub type AzblobWriters =
-    oio::TwoWaysWriter<oio::OneShotWriter<AzblobWriter>, oio::AppendObjectWriter<AzblobWriter>>;
+/// `Put Block From URL` can stage at most 4000 MiB from the source, larger
+/// sources will be staged as multiple blocks by `x-ms-source-range`.
+///
+/// ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/put-block-from-url#remarks>
+const MAX_BLOCK_FROM_URL_SIZE: u64 = 4000 * 1024 * 1024;
+
+pub type AzblobWriters = oio::TwoWaysWriter<
+    oio::ExactBufWriter<oio::MultipartUploadWriter<AzblobWriter>>,
+    oio::AppendObjectWriter<AzblobWriter>,
+>;
 
 pub struct AzblobWriter {
     core: Arc<AzblobCore>,
@@ -41,18 +51,141 @@ impl AzblobWriter {
     pub fn new(core: Arc<AzblobCore>, op: OpWrite, path: String) -> Self {
         AzblobWriter { core, op, path }
     }
+
+    /// Compose the blob from sources by `Put Block From URL` and commit
+    /// them with written content as the last block.
+    pub async fn compose(&self, size: u64, body: AsyncBody) -> Result<()> {
+        let upload_id = uuid::Uuid::new_v4().to_string();
+        let mut block_ids = self.stage_sources().await?;
+
+        if size > 0 {
+            let block_id = format_block_id(&upload_id, 0);
+            self.put_block(&block_id, size, body).await?;
+            block_ids.push(block_id);
+        }
+
+        self.commit(&block_ids).await
+    }
+
+    /// Stage all compose sources as blocks by `Put Block From URL`.
+    ///
+    /// Sources larger than [`MAX_BLOCK_FROM_URL_SIZE`] are split into
+    /// multiple blocks, each stages a range of the source.
+    async fn stage_sources(&self) -> Result<Vec<String>> {
+        let upload_id = uuid::Uuid::new_v4().to_string();
+        let mut block_ids = Vec::with_capacity(self.op.compose_sources().len());
+
+        for source in self.op.compose_sources() {
+            for range in split_source(self.source_size(source).await?) {
+                let block_id = format_block_id(&upload_id, block_ids.len());
+                let resp = self
+                    .core
+                    .azblob_put_block_from_url(
+                        &self.path,
+                        &block_id,
+                        self.op.lease_id(),
+                        source,
+                        range,
+                    )
+                    .await?;
+                match resp.status() {
+                    StatusCode::CREATED => resp.into_body().consume().await?,
+                    _ => {
+                        return Err(parse_error(resp)
+                            .await?
+                            .with_context("source", source.as_str()))
+                    }
+                }
+                block_ids.push(block_id);
+            }
+        }
+
+        Ok(block_ids)
+    }
+
+    async fn source_size(&self, source: &str) -> Result<u64> {
+        let resp = self.core.azblob_head_source(source).await?;
+        match resp.status() {
+            StatusCode::OK => {
+                let size = parse_content_length(resp.headers())?;
+                resp.into_body().consume().await?;
+                size.ok_or_else(|| {
+                    Error::new(ErrorKind::Unexpected, "content length of source is missing")
+                        .with_context("source", source)
+                })
+            }
+            _ => Err(parse_error(resp).await?.with_context("source", source)),
+        }
+    }
+
+    async fn put_block(&self, block_id: &str, size: u64, body: AsyncBody) -> Result<()> {
+        let resp = self
+            .core
+            .azblob_put_block(&self.path, block_id, self.op.lease_id(), size, body)
+            .await?;
+        match resp.status() {
+            StatusCode::CREATED => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// Commit staged blocks into the blob by `Put Block List`.
+    async fn commit(&self, block_ids: &[String]) -> Result<()> {
+        let resp = self
+            .core
+            .azblob_put_block_list(&self.path, block_ids, &self.op)
+            .await?;
+        match resp.status() {
+            StatusCode::CREATED => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            StatusCode::CONFLICT if self.op.if_none_match() == Some("*") => {
+                let err = parse_error(resp).await?;
+                Err(Error::new(ErrorKind::ConditionNotMatch, "blob already exists").set_source(err))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
+
+/// Split a source of given size into ranges that can be staged by one
+/// `Put Block From URL`, a source fits in one block is staged in full.
+fn split_source(size: u64) -> Vec<BytesRange> {
+    if size <= MAX_BLOCK_FROM_URL_SIZE {
+        return vec![BytesRange::default()];
+    }
+
+    (0..size)
+        .step_by(MAX_BLOCK_FROM_URL_SIZE as usize)
+        .map(|offset| {
+            BytesRange::new(
+                Some(offset),
+                Some(MAX_BLOCK_FROM_URL_SIZE.min(size - offset)),
+            )
+        })
+        .collect()
+}
+
+/// Block ids must be base64 encoded and have the same length in one blob,
+/// so we build them from an uuid and a fixed width index.
+fn format_block_id(upload_id: &str, idx: usize) -> String {
+    BASE64_STANDARD.encode(format!("{upload_id}-{idx:06}"))
 }
 
 #[async_trait]
-impl oio::OneShotWrite for AzblobWriter {
-    async fn write_once(&self, bs: &dyn oio::WriteBuf) -> Result<()> {
-        let bs = oio::ChunkedBytes::from_vec(bs.vectored_bytes(bs.remaining()));
-        let mut req = self.core.azblob_put_blob_request(
-            &self.path,
-            Some(bs.len() as u64),
-            &self.op,
-            AsyncBody::ChunkedBytes(bs),
-        )?;
+impl oio::MultipartUploadWrite for AzblobWriter {
+    async fn write_once(&self, size: u64, body: AsyncBody) -> Result<()> {
+        if !self.op.compose_sources().is_empty() {
+            return self.compose(size, body).await;
+        }
+
+        let mut req = self
+            .core
+            .azblob_put_blob_request(&self.path, Some(size), &self.op, body)?;
 
         self.core.sign(&mut req).await?;
 
@@ -65,9 +198,53 @@ impl oio::OneShotWrite for AzblobWriter {
                 resp.into_body().consume().await?;
                 Ok(())
             }
+            // Azblob returns `409 BlobAlreadyExists` instead of `412` for
+            // `If-None-Match: *`.
+            StatusCode::CONFLICT if self.op.if_none_match() == Some("*") => {
+                let err = parse_error(resp).await?;
+                Err(Error::new(ErrorKind::ConditionNotMatch, "blob already exists").set_source(err))
+            }
             _ => Err(parse_error(resp).await?),
         }
     }
+
+    /// Block blob doesn't need to initiate an upload, we use a random uuid
+    /// as the prefix of block ids instead.
+    async fn initiate_part(&self) -> Result<String> {
+        Ok(uuid::Uuid::new_v4().to_string())
+    }
+
+    async fn write_part(
+        &self,
+        upload_id: &str,
+        part_number: usize,
+        size: u64,
+        body: AsyncBody,
+    ) -> Result<oio::MultipartUploadPart> {
+        let block_id = format_block_id(upload_id, part_number);
+        self.put_block(&block_id, size, body).await?;
+
+        Ok(oio::MultipartUploadPart {
+            part_number,
+            etag: block_id,
+        })
+    }
+
+    async fn complete_part(&self, _: &str, parts: &[oio::MultipartUploadPart]) -> Result<()> {
+        let mut block_ids = self.stage_sources().await?;
+        block_ids.extend(parts.iter().map(|p| p.etag.clone()));
+
+        self.commit(&block_ids).await
+    }
+
+    /// Azblob has no API to discard staged blocks, uncommitted blocks will be
+    /// garbage collected by azblob after a week.
+    ///
+    /// So this is a no-op and [`WriterCancelPolicy::Abort`] won't purge any
+    /// uploaded data on azblob.
+    async fn abort_part(&self, _: &str) -> Result<()> {
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -100,7 +277,7 @@ impl oio::AppendObjectWrite for AzblobWriter {
 
                 self.core.sign(&mut req).await?;
 
-                let resp = self.core.client.send(req).await?;
+                let resp = self.core.send(req).await?;
 
                 let status = resp.status();
                 match status {
@@ -118,9 +295,13 @@ impl oio::AppendObjectWrite for AzblobWriter {
     }
 
     async fn append(&self, offset: u64, size: u64, body: AsyncBody) -> Result<()> {
-        let mut req = self
-            .core
-            .azblob_append_blob_request(&self.path, offset, size, body)?;
+        let mut req = self.core.azblob_append_blob_request(
+            &self.path,
+            offset,
+            size,
+            self.op.lease_id(),
+            body,
+        )?;
 
         self.core.sign(&mut req).await?;
 
@@ -53,7 +53,7 @@ pub struct AzdlsBuilder {
     filesystem: String,
     endpoint: Option<String>,
     account_name: Option<String>,
-    account_key: Option<String>,
+    account_key: Option<Secret>,
     http_client: Option<HttpClient>,
 }
 
@@ -128,7 +128,7 @@ impl AzdlsBuilder {
     /// - If not, we will try to load it from environment.
     pub fn account_key(&mut self, account_key: &str) -> &mut Self {
         if !account_key.is_empty() {
-            self.account_key = Some(account_key.to_string());
+            self.account_key = Some(account_key.into());
         }
 
         self
@@ -187,7 +187,7 @@ impl Builder for AzdlsBuilder {
                 .account_name
                 .clone()
                 .or_else(|| infer_storage_name_from_endpoint(endpoint.as_str())),
-            account_key: self.account_key.clone(),
+            account_key: self.account_key.take().map(Secret::into_secret),
             sas_token: None,
             ..Default::default()
         };
@@ -203,6 +203,7 @@ impl Builder for AzdlsBuilder {
                 endpoint,
                 client,
                 loader: cred_loader,
+                credential: CredentialCache::default(),
                 signer,
             }),
         })
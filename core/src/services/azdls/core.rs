@@ -27,6 +27,7 @@ use http::HeaderName;
 use http::HeaderValue;
 use http::Request;
 use http::Response;
+use http::StatusCode;
 use reqsign::AzureStorageCredential;
 use reqsign::AzureStorageLoader;
 use reqsign::AzureStorageSigner;
@@ -44,6 +45,7 @@ pub struct AzdlsCore {
 
     pub client: HttpClient,
     pub loader: AzureStorageLoader,
+    pub credential: CredentialCache<AzureStorageCredential>,
     pub signer: AzureStorageSigner,
 }
 
@@ -59,23 +61,30 @@ impl Debug for AzdlsCore {
 
 impl AzdlsCore {
     async fn load_credential(&self) -> Result<AzureStorageCredential> {
-        let cred = self
-            .loader
-            .load()
+        self.credential
+            .get_or_load(|| async {
+                let cred = self
+                    .loader
+                    .load()
+                    .await
+                    .map_err(new_request_credential_error)?;
+
+                if let Some(cred) = cred {
+                    Ok(cred)
+                } else {
+                    Err(Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "no valid credential found",
+                    ))
+                }
+            })
             .await
-            .map_err(new_request_credential_error)?;
-
-        if let Some(cred) = cred {
-            Ok(cred)
-        } else {
-            Err(Error::new(
-                ErrorKind::ConfigInvalid,
-                "no valid credential found",
-            ))
-        }
     }
 
     pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        // Request id must be signed too.
+        ClientRequestId::insert_current(req.headers_mut());
+
         let cred = self.load_credential().await?;
         // Insert x-ms-version header for normal requests.
         req.headers_mut().insert(
@@ -90,9 +99,32 @@ impl AzdlsCore {
         self.signer.sign(req, &cred).map_err(new_request_sign_error)
     }
 
-    #[inline]
     pub async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
-        self.client.send(req).await
+        let resp = self.client.send(req).await?;
+
+        // Force to reload credential in next request if it has been rejected.
+        if is_credential_rejected(&resp) {
+            self.credential.invalidate();
+        }
+
+        Ok(resp)
+    }
+}
+
+/// Check if the response is caused by an expired or invalid credential.
+///
+/// Azure returns `403 AuthenticationFailed` for signatures that can't be
+/// verified, other `403`s are genuine permission denials which won't be
+/// fixed by reloading credential.
+fn is_credential_rejected<T>(resp: &Response<T>) -> bool {
+    match resp.status() {
+        StatusCode::UNAUTHORIZED => true,
+        StatusCode::FORBIDDEN => resp
+            .headers()
+            .get("x-ms-error-code")
+            .map(|v| v.as_bytes() == b"AuthenticationFailed")
+            .unwrap_or_default(),
+        _ => false,
     }
 }
 
@@ -250,7 +282,7 @@ impl AzdlsCore {
             .map_err(new_request_build_error)?;
 
         self.sign(&mut req).await?;
-        self.client.send(req).await
+        self.send(req).await
     }
 
     pub async fn azdls_delete(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
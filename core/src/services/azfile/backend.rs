@@ -46,8 +46,8 @@ pub struct AzfileBuilder {
     endpoint: Option<String>,
     account_name: Option<String>,
     share_name: String,
-    account_key: Option<String>,
-    sas_token: Option<String>,
+    account_key: Option<Secret>,
+    sas_token: Option<Secret>,
     http_client: Option<HttpClient>,
 }
 
@@ -109,7 +109,7 @@ impl AzfileBuilder {
     /// - If not, we will try to load it from environment.
     pub fn account_key(&mut self, account_key: &str) -> &mut Self {
         if !account_key.is_empty() {
-            self.account_key = Some(account_key.to_string());
+            self.account_key = Some(account_key.into());
         }
 
         self
@@ -194,8 +194,8 @@ impl Builder for AzfileBuilder {
 
         let config_loader = AzureStorageConfig {
             account_name: Some(account_name),
-            account_key: self.account_key.clone(),
-            sas_token: self.sas_token.clone(),
+            account_key: self.account_key.take().map(Secret::into_secret),
+            sas_token: self.sas_token.take().map(Secret::into_secret),
             ..Default::default()
         };
 
@@ -209,6 +209,7 @@ impl Builder for AzfileBuilder {
                 root,
                 endpoint,
                 loader: cred_loader,
+                credential: CredentialCache::default(),
                 client,
                 signer,
                 share_name: self.share_name.clone(),
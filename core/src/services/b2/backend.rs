@@ -302,12 +302,20 @@ impl Accessor for B2Backend {
                 //
                 // ref: <https://www.backblaze.com/docs/cloud-storage-large-files>
                 write_multi_max_size: Some(5 * 1024 * 1024 * 1024),
+                // The max parts of b2 large file is 10000.
+                //
+                // ref: <https://www.backblaze.com/docs/cloud-storage-large-files>
+                write_multi_max_parts: Some(10000),
 
                 delete: true,
                 copy: true,
 
                 list: true,
                 list_with_limit: true,
+                // The max page size of b2 list is 10000.
+                //
+                // ref: <https://www.backblaze.com/apidocs/b2-list-file-names>
+                list_max_page_size: Some(10000),
                 list_with_start_after: true,
                 list_with_recursive: true,
                 list_without_recursive: true,
@@ -339,9 +347,10 @@ impl Accessor for B2Backend {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let concurrent = args.concurrent();
         let writer = B2Writer::new(self.core.clone(), path, args);
 
-        let w = oio::MultipartUploadWriter::new(writer);
+        let w = oio::MultipartUploadWriter::new(writer, concurrent);
 
         Ok((RpWrite::default(), w))
     }
@@ -43,7 +43,7 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
         404 => (ErrorKind::NotFound, false),
         304 | 412 => (ErrorKind::ConditionNotMatch, false),
         // Service b2 could return 403, show the authorization error
-        401 => (ErrorKind::PermissionDenied, true),
+        401 => (ErrorKind::Unauthenticated, true),
         500 | 502 | 503 | 504 => (ErrorKind::Unexpected, true),
         _ => (ErrorKind::Unexpected, false),
     };
@@ -116,7 +116,7 @@ mod test {
             ),
             (
                 r#"{"status": 401, "code": "bad_auth_token", "message":"The auth token used is not valid. Call b2_authorize_account again to either get a new one, or an error message describing the problem."}"#,
-                ErrorKind::PermissionDenied,
+                ErrorKind::Unauthenticated,
                 StatusCode::UNAUTHORIZED,
             ),
         ];
@@ -42,7 +42,7 @@ pub struct CosBuilder {
     root: Option<String>,
     endpoint: Option<String>,
     secret_id: Option<String>,
-    secret_key: Option<String>,
+    secret_key: Option<Secret>,
     bucket: Option<String>,
     http_client: Option<HttpClient>,
 
@@ -104,7 +104,7 @@ impl CosBuilder {
     /// - If not, we will try to load it from environment.
     pub fn secret_key(&mut self, secret_key: &str) -> &mut Self {
         if !secret_key.is_empty() {
-            self.secret_key = Some(secret_key.to_string());
+            self.secret_key = Some(secret_key.into());
         }
 
         self
@@ -212,7 +212,7 @@ impl Builder for CosBuilder {
             cfg.secret_id = Some(v);
         }
         if let Some(v) = self.secret_key.take() {
-            cfg.secret_key = Some(v);
+            cfg.secret_key = Some(v.into_secret());
         }
 
         let cred_loader = TencentCosCredentialLoader::new(client.client(), cfg);
@@ -253,6 +253,7 @@ impl Accessor for CosBackend {
         am.set_scheme(Scheme::Cos)
             .set_root(&self.core.root)
             .set_name(&self.core.bucket)
+            .set_endpoint(&self.core.endpoint)
             .set_native_capability(Capability {
                 stat: true,
                 stat_with_if_match: true,
@@ -271,6 +272,10 @@ impl Accessor for CosBackend {
                 write_with_content_type: true,
                 write_with_cache_control: true,
                 write_with_content_disposition: true,
+                // COS expires objects by lifecycle rules filtered by tags.
+                //
+                // ref: <https://www.tencentcloud.com/document/product/436/14605>
+                write_with_expire_at: true,
                 // The min multipart size of COS is 1 MiB.
                 //
                 // ref: <https://www.tencentcloud.com/document/product/436/14112>
@@ -283,6 +288,18 @@ impl Accessor for CosBackend {
                 } else {
                     Some(usize::MAX)
                 },
+                // The max parts of COS multipart upload is 10000.
+                //
+                // ref: <https://www.tencentcloud.com/document/product/436/14112>
+                write_multi_max_parts: Some(10000),
+                // The max object size of COS is 48.82 TiB.
+                //
+                // ref: <https://www.tencentcloud.com/document/product/436/14112>
+                write_total_max_size: if cfg!(target_pointer_width = "64") {
+                    Some(10000 * 5 * 1024 * 1024 * 1024)
+                } else {
+                    Some(usize::MAX)
+                },
 
                 delete: true,
                 copy: true,
@@ -310,7 +327,11 @@ impl Accessor for CosBackend {
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
                 let size = parse_content_length(resp.headers())?;
-                Ok((RpRead::new().with_size(size), resp.into_body()))
+                let etag = parse_etag(resp.headers())?.map(|v| v.to_string());
+                Ok((
+                    RpRead::new().with_size(size).with_etag(etag),
+                    resp.into_body(),
+                ))
             }
             StatusCode::RANGE_NOT_SATISFIABLE => Ok((RpRead::new(), IncomingAsyncBody::empty())),
             _ => Err(parse_error(resp).await?),
@@ -323,7 +344,7 @@ impl Accessor for CosBackend {
         let w = if args.append() {
             CosWriters::Two(oio::AppendObjectWriter::new(writer))
         } else {
-            CosWriters::One(oio::MultipartUploadWriter::new(writer))
+            CosWriters::One(oio::MultipartUploadWriter::new(writer, args.concurrent()))
         };
 
         Ok((RpWrite::default(), w))
@@ -20,6 +20,8 @@ use std::fmt::Formatter;
 use std::time::Duration;
 
 use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Utc;
 use http::header::CACHE_CONTROL;
 use http::header::CONTENT_DISPOSITION;
 use http::header::CONTENT_LENGTH;
@@ -37,6 +39,13 @@ use serde::Serialize;
 use crate::raw::*;
 use crate::*;
 
+mod constants {
+    pub const X_COS_TAGGING: &str = "x-cos-tagging";
+
+    /// The tag used by lifecycle rules to expire objects.
+    pub const EXPIRE_DAYS_TAG: &str = "opendal-expire-days";
+}
+
 pub struct CosCore {
     pub bucket: String,
     pub root: String,
@@ -73,6 +82,9 @@ impl CosCore {
     }
 
     pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        // Request id must be signed too.
+        ClientRequestId::insert_current(req.headers_mut());
+
         let cred = if let Some(cred) = self.load_credential().await? {
             cred
         } else {
@@ -98,6 +110,26 @@ impl CosCore {
     pub async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
         self.client.send(req).await
     }
+
+    /// Tag the object with the days before it expires, so that lifecycle
+    /// rules filtered by this tag could remove it.
+    pub fn insert_expire_tagging(
+        &self,
+        mut req: http::request::Builder,
+        expire_at: Option<DateTime<Utc>>,
+    ) -> http::request::Builder {
+        if let Some(expire_at) = expire_at {
+            req = req.header(
+                constants::X_COS_TAGGING,
+                format!(
+                    "{}={}",
+                    constants::EXPIRE_DAYS_TAG,
+                    expire_days_from_now(expire_at)
+                ),
+            );
+        }
+        req
+    }
 }
 
 impl CosCore {
@@ -165,6 +197,7 @@ impl CosCore {
         if let Some(mime) = args.content_type() {
             req = req.header(CONTENT_TYPE, mime)
         }
+        req = self.insert_expire_tagging(req, args.expire_at());
 
         let req = req.body(body).map_err(new_request_build_error)?;
 
@@ -252,6 +285,7 @@ impl CosCore {
         if let Some(cache_control) = args.cache_control() {
             req = req.header(CACHE_CONTROL, cache_control)
         }
+        req = self.insert_expire_tagging(req, args.expire_at());
 
         let req = req.body(body).map_err(new_request_build_error)?;
         Ok(req)
@@ -338,6 +372,7 @@ impl CosCore {
         if let Some(cache_control) = args.cache_control() {
             req = req.header(CACHE_CONTROL, cache_control)
         }
+        req = self.insert_expire_tagging(req, args.expire_at());
 
         let mut req = req
             .body(AsyncBody::Empty)
@@ -78,6 +78,7 @@ impl typed_kv::Adapter for Adapter {
                 set: true,
                 scan: true,
                 delete: true,
+                delete_prefix: true,
             },
         )
     }
@@ -113,6 +114,16 @@ impl typed_kv::Adapter for Adapter {
         Ok(())
     }
 
+    async fn delete_prefix(&self, path: &str) -> Result<()> {
+        self.blocking_delete_prefix(path)
+    }
+
+    fn blocking_delete_prefix(&self, path: &str) -> Result<()> {
+        self.inner.retain(|k, _| !k.starts_with(path));
+
+        Ok(())
+    }
+
     async fn scan(&self, path: &str) -> Result<Vec<String>> {
         self.blocking_scan(path)
     }
@@ -140,4 +151,22 @@ mod tests {
         let b2 = DashmapBuilder::default().build().unwrap();
         assert_ne!(b1.info().name(), b2.info().name())
     }
+
+    #[test]
+    fn test_remove_all_with_prefix() {
+        let op = Operator::new(DashmapBuilder::default())
+            .unwrap()
+            .finish()
+            .blocking();
+        assert!(op.info().full_capability().delete_with_prefix);
+
+        op.write("dir/a", "a").unwrap();
+        op.write("dir/sub/b", "b").unwrap();
+        op.write("dir_sibling", "c").unwrap();
+
+        op.remove_all("dir/").unwrap();
+        assert!(!op.is_exist("dir/a").unwrap());
+        assert!(!op.is_exist("dir/sub/b").unwrap());
+        assert!(op.is_exist("dir_sibling").unwrap());
+    }
 }
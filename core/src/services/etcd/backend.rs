@@ -56,7 +56,7 @@ pub struct EtcdConfig {
     /// the password for authentication
     ///
     /// default is None
-    pub password: Option<String>,
+    pub password: Option<Secret>,
     /// the working directory of the etcd service. Can be "/path/to/dir"
     ///
     /// default is "/"
@@ -144,7 +144,7 @@ impl EtcdBuilder {
     /// default: no password
     pub fn password(&mut self, password: &str) -> &mut Self {
         if !password.is_empty() {
-            self.config.password = Some(password.to_owned());
+            self.config.password = Some(password.into());
         }
         self
     }
@@ -236,7 +236,11 @@ impl Builder for EtcdBuilder {
         if let Some(username) = self.config.username.clone() {
             options = options.with_user(
                 username,
-                self.config.password.clone().unwrap_or("".to_string()),
+                self.config
+                    .password
+                    .take()
+                    .map(Secret::into_secret)
+                    .unwrap_or_default(),
             );
         }
 
@@ -25,6 +25,10 @@ use log::debug;
 use uuid::Uuid;
 
 use super::lister::FsLister;
+use super::sparse;
+use super::trash::FsTrash;
+use super::user_metadata::blocking_get_user_metadata;
+use super::user_metadata::get_user_metadata;
 use super::writer::FsWriter;
 use crate::raw::*;
 use crate::*;
@@ -35,6 +39,9 @@ use crate::*;
 pub struct FsBuilder {
     root: Option<PathBuf>,
     atomic_write_dir: Option<PathBuf>,
+    trash_dir: Option<PathBuf>,
+    enable_cross_device_rename: bool,
+    enable_xattr: bool,
 }
 
 impl FsBuilder {
@@ -65,6 +72,63 @@ impl FsBuilder {
         self
     }
 
+    /// Set trash dir for `delete_with().trash(true)`.
+    ///
+    /// The trash dir follows the FreeDesktop.org Trash specification, set
+    /// it to `~/.local/share/Trash` to use the desktop trash on Linux.
+    /// Trashed entries can be listed and restored via [`FsTrash`].
+    ///
+    /// # Notes
+    ///
+    /// - Trash dir should be on the same filesystem of root, otherwise
+    /// trash will fail with cross-device error.
+    /// - Delete with trash is not supported if trash dir is not set.
+    /// - Like plain delete, only empty dirs could be trashed.
+    pub fn trash_dir(&mut self, dir: &str) -> &mut Self {
+        self.trash_dir = if dir.is_empty() {
+            None
+        } else {
+            Some(PathBuf::from(dir))
+        };
+
+        self
+    }
+
+    /// Enable fallback for cross device rename.
+    ///
+    /// By default, rename between different filesystems will fail with
+    /// the raw `EXDEV` error. After enabled, fs will fall back to copy the
+    /// file into a temp file besides target, fsync it, rename it to target
+    /// and remove the source. Permissions and mtime of source will be
+    /// preserved.
+    ///
+    /// # Notes
+    ///
+    /// Only files are supported, renaming a dir across devices will
+    /// still fail with `Unsupported`.
+    ///
+    /// The fallback is not atomic: source and target could both exist
+    /// if process crashed between rename and remove.
+    pub fn enable_cross_device_rename(&mut self) -> &mut Self {
+        self.enable_cross_device_rename = true;
+        self
+    }
+
+    /// Enable mapping user metadata to xattrs.
+    ///
+    /// After enabled, user metadata passed by `write_with().user_metadata()`
+    /// will be stored as xattrs with prefix `user.opendal.`, and `stat` will
+    /// return them back in [`Metadata::user_metadata`].
+    ///
+    /// # Notes
+    ///
+    /// The underlying filesystem must support user xattrs, otherwise write
+    /// and stat will fail.
+    pub fn enable_xattr(&mut self) -> &mut Self {
+        self.enable_xattr = true;
+        self
+    }
+
     /// OpenDAL requires all input path are normalized to make sure the
     /// behavior is consistent. By enable path check, we can make sure
     /// fs will behave the same as other services.
@@ -87,6 +151,13 @@ impl Builder for FsBuilder {
         map.get("root").map(|v| builder.root(v));
         map.get("atomic_write_dir")
             .map(|v| builder.atomic_write_dir(v));
+        map.get("trash_dir").map(|v| builder.trash_dir(v));
+        map.get("enable_cross_device_rename")
+            .filter(|v| *v == "on" || *v == "true")
+            .map(|_| builder.enable_cross_device_rename());
+        map.get("enable_xattr")
+            .filter(|v| *v == "on" || *v == "true")
+            .map(|_| builder.enable_xattr());
 
         builder
     }
@@ -159,10 +230,15 @@ impl Builder for FsBuilder {
             })
             .unwrap_or(Ok(None))?;
 
+        let trash = self.trash_dir.take().map(FsTrash::new);
+
         debug!("backend build finished: {:?}", &self);
         Ok(FsBackend {
             root,
             atomic_write_dir,
+            trash,
+            enable_cross_device_rename: self.enable_cross_device_rename,
+            enable_xattr: self.enable_xattr,
         })
     }
 }
@@ -172,6 +248,9 @@ impl Builder for FsBuilder {
 pub struct FsBackend {
     root: PathBuf,
     atomic_write_dir: Option<PathBuf>,
+    trash: Option<FsTrash>,
+    enable_cross_device_rename: bool,
+    enable_xattr: bool,
 }
 
 #[inline]
@@ -182,7 +261,145 @@ fn tmp_file_of(path: &str) -> String {
     format!("{name}.{uuid}")
 }
 
+/// Check if the given error is caused by rename across devices.
+///
+/// `io::ErrorKind::CrossesDevices` is not stable in our MSRV, so we have to
+/// check the raw os error here.
+fn is_cross_device_error(err: &std::io::Error) -> bool {
+    #[cfg(unix)]
+    const CROSS_DEVICE_ERROR: i32 = libc::EXDEV;
+    // ERROR_NOT_SAME_DEVICE
+    #[cfg(windows)]
+    const CROSS_DEVICE_ERROR: i32 = 17;
+    #[cfg(not(any(unix, windows)))]
+    const CROSS_DEVICE_ERROR: i32 = -1;
+
+    err.raw_os_error() == Some(CROSS_DEVICE_ERROR)
+}
+
+fn new_cross_device_dir_error() -> Error {
+    Error::new(
+        ErrorKind::Unsupported,
+        "rename dir across devices is not supported",
+    )
+}
+
 impl FsBackend {
+    /// Rename file across devices via copy + fsync + rename + remove.
+    async fn rename_across_device(from: &Path, to: &Path) -> Result<()> {
+        let meta = tokio::fs::metadata(from).await.map_err(new_std_io_error)?;
+        if meta.is_dir() {
+            return Err(new_cross_device_dir_error());
+        }
+        let tmp_path = to.with_file_name(tmp_file_of(&to.to_string_lossy()));
+
+        let res = async {
+            // Permissions of source will be copied too.
+            tokio::fs::copy(from, &tmp_path).await?;
+            filetime::set_file_mtime(
+                &tmp_path,
+                filetime::FileTime::from_last_modification_time(&meta),
+            )?;
+            tokio::fs::OpenOptions::new()
+                .write(true)
+                .open(&tmp_path)
+                .await?
+                .sync_all()
+                .await?;
+            tokio::fs::rename(&tmp_path, to).await
+        }
+        .await;
+        if let Err(err) = res {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(new_std_io_error(err));
+        }
+
+        tokio::fs::remove_file(from).await.map_err(new_std_io_error)
+    }
+
+    /// Rename file across devices via copy + fsync + rename + remove.
+    fn blocking_rename_across_device(from: &Path, to: &Path) -> Result<()> {
+        let meta = std::fs::metadata(from).map_err(new_std_io_error)?;
+        if meta.is_dir() {
+            return Err(new_cross_device_dir_error());
+        }
+        let tmp_path = to.with_file_name(tmp_file_of(&to.to_string_lossy()));
+
+        let res = (|| {
+            // Permissions of source will be copied too.
+            std::fs::copy(from, &tmp_path)?;
+            filetime::set_file_mtime(
+                &tmp_path,
+                filetime::FileTime::from_last_modification_time(&meta),
+            )?;
+            std::fs::OpenOptions::new()
+                .write(true)
+                .open(&tmp_path)?
+                .sync_all()?;
+            std::fs::rename(&tmp_path, to)
+        })();
+        if let Err(err) = res {
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(new_std_io_error(err));
+        }
+
+        std::fs::remove_file(from).map_err(new_std_io_error)
+    }
+
+    fn trash(&self) -> Result<&FsTrash> {
+        self.trash.as_ref().ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unsupported,
+                "delete with trash requires trash_dir to be set",
+            )
+        })
+    }
+
+    /// Convert std fs metadata into opendal metadata.
+    ///
+    /// `meta` should be the metadata of the entry itself (following
+    /// symlinks), `symlink_target` is the raw link content if input path
+    /// is a symlink.
+    fn parse_metadata(
+        meta: &std::fs::Metadata,
+        symlink_target: Option<PathBuf>,
+    ) -> Result<Metadata> {
+        let mode = if meta.is_dir() {
+            EntryMode::DIR
+        } else if meta.is_file() {
+            EntryMode::FILE
+        } else {
+            EntryMode::Unknown
+        };
+        let mut m = Metadata::new(mode)
+            .with_content_length(meta.len())
+            .with_last_modified(
+                meta.modified()
+                    .map(DateTime::from)
+                    .map_err(new_std_io_error)?,
+            );
+
+        // Creation time is not available on all platforms and filesystems.
+        if let Ok(t) = meta.created() {
+            m.set_created(DateTime::from(t));
+        }
+
+        if let Some(target) = symlink_target {
+            m.set_symlink_target(&target.to_string_lossy());
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+
+            m.set_permissions(meta.mode() & 0o7777)
+                .set_uid(meta.uid())
+                .set_gid(meta.gid());
+        }
+
+        Ok(m)
+    }
+
     // Synchronously build write path and ensure the parent dirs created
     fn blocking_ensure_write_abs_path(parent: &Path, path: &str) -> Result<PathBuf> {
         let p = parent.join(path);
@@ -253,6 +470,7 @@ impl Accessor for FsBackend {
             .set_root(&self.root.to_string_lossy())
             .set_native_capability(Capability {
                 stat: true,
+                stat_root: true,
 
                 read: true,
                 read_can_seek: true,
@@ -261,8 +479,12 @@ impl Accessor for FsBackend {
                 write_can_empty: true,
                 write_can_append: true,
                 write_can_multi: true,
+                write_with_sparse: true,
+                write_with_user_metadata: self.enable_xattr,
                 create_dir: true,
                 delete: true,
+                delete_with_trash: self.trash.is_some(),
+                delete_with_must_exist: true,
 
                 list: true,
                 list_without_recursive: true,
@@ -271,6 +493,9 @@ impl Accessor for FsBackend {
                 rename: true,
                 blocking: true,
 
+                create_symlink: cfg!(unix),
+                set_permissions: cfg!(unix),
+
                 ..Default::default()
             });
 
@@ -344,7 +569,15 @@ impl Accessor for FsBackend {
             .await
             .map_err(new_std_io_error)?;
 
-        Ok((RpWrite::new(), FsWriter::new(target_path, tmp_path, f)))
+        // Sparse is not supported in append mode since all writes go to the end.
+        let sparse = op.sparse() && !op.append();
+
+        Ok((
+            RpWrite::new(),
+            FsWriter::new(target_path, tmp_path, f)
+                .with_sparse(sparse)
+                .with_user_metadata(op.user_metadata().cloned()),
+        ))
     }
 
     async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
@@ -355,7 +588,7 @@ impl Accessor for FsBackend {
 
         let to = Self::ensure_write_abs_path(&self.root, to.trim_end_matches('/')).await?;
 
-        tokio::fs::copy(from, to).await.map_err(new_std_io_error)?;
+        sparse::copy(from, to).await?;
 
         Ok(RpCopy::default())
     }
@@ -368,42 +601,106 @@ impl Accessor for FsBackend {
 
         let to = Self::ensure_write_abs_path(&self.root, to.trim_end_matches('/')).await?;
 
-        tokio::fs::rename(from, to)
-            .await
-            .map_err(new_std_io_error)?;
+        match tokio::fs::rename(&from, &to).await {
+            Ok(()) => {}
+            Err(err) if self.enable_cross_device_rename && is_cross_device_error(&err) => {
+                Self::rename_across_device(&from, &to).await?
+            }
+            Err(err) => return Err(new_std_io_error(err)),
+        }
 
         Ok(RpRename::default())
     }
 
+    async fn create_symlink(
+        &self,
+        target: &str,
+        path: &str,
+        _: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        let p = Self::ensure_write_abs_path(&self.root, path).await?;
+
+        #[cfg(unix)]
+        let res = tokio::fs::symlink(target, p)
+            .await
+            .map_err(new_std_io_error);
+        #[cfg(not(unix))]
+        let res = {
+            let _ = (target, p);
+            Err(Error::new(
+                ErrorKind::Unsupported,
+                "create symlink is only supported on unix",
+            ))
+        };
+
+        res.map(|_| RpCreateSymlink::default())
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        let p = self.root.join(path.trim_end_matches('/'));
+
+        #[cfg(unix)]
+        let res = {
+            use std::os::unix::fs::PermissionsExt;
+
+            tokio::fs::set_permissions(p, std::fs::Permissions::from_mode(args.permissions()))
+                .await
+                .map_err(new_std_io_error)
+        };
+        #[cfg(not(unix))]
+        let res = {
+            let _ = (p, args);
+            Err(Error::new(
+                ErrorKind::Unsupported,
+                "set permissions is only supported on unix",
+            ))
+        };
+
+        res.map(|_| RpSetPermissions::default())
+    }
+
     async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
         let p = self.root.join(path.trim_end_matches('/'));
 
-        let meta = tokio::fs::metadata(&p).await.map_err(new_std_io_error)?;
+        let meta = tokio::fs::symlink_metadata(&p)
+            .await
+            .map_err(new_std_io_error)?;
 
-        let mode = if meta.is_dir() {
-            EntryMode::DIR
-        } else if meta.is_file() {
-            EntryMode::FILE
+        let mut m = if meta.is_symlink() {
+            let target = tokio::fs::read_link(&p).await.map_err(new_std_io_error)?;
+            match tokio::fs::metadata(&p).await {
+                Ok(meta) => Self::parse_metadata(&meta, Some(target))?,
+                // Dangling symlink still exists, return the link itself.
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    Self::parse_metadata(&meta, Some(target))?
+                }
+                Err(err) => return Err(new_std_io_error(err)),
+            }
         } else {
-            EntryMode::Unknown
+            Self::parse_metadata(&meta, None)?
         };
-        let m = Metadata::new(mode)
-            .with_content_length(meta.len())
-            .with_last_modified(
-                meta.modified()
-                    .map(DateTime::from)
-                    .map_err(new_std_io_error)?,
-            );
+        if self.enable_xattr {
+            m.set_user_metadata(get_user_metadata(p).await?);
+        }
 
         Ok(RpStat::new(m))
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         let p = self.root.join(path.trim_end_matches('/'));
 
         let meta = tokio::fs::metadata(&p).await;
 
         match meta {
+            Ok(_) if args.trash() => {
+                self.trash()?.trash(&p).await?;
+
+                Ok(RpDelete::default())
+            }
             Ok(meta) => {
                 if meta.is_dir() {
                     tokio::fs::remove_dir(&p).await.map_err(new_std_io_error)?;
@@ -413,7 +710,9 @@ impl Accessor for FsBackend {
 
                 Ok(RpDelete::default())
             }
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(RpDelete::default()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound && !args.must_exist() => {
+                Ok(RpDelete::default())
+            }
             Err(err) => Err(new_std_io_error(err)),
         }
     }
@@ -493,7 +792,15 @@ impl Accessor for FsBackend {
             .open(tmp_path.as_ref().unwrap_or(&target_path))
             .map_err(new_std_io_error)?;
 
-        Ok((RpWrite::new(), FsWriter::new(target_path, tmp_path, f)))
+        // Sparse is not supported in append mode since all writes go to the end.
+        let sparse = op.sparse() && !op.append();
+
+        Ok((
+            RpWrite::new(),
+            FsWriter::new(target_path, tmp_path, f)
+                .with_sparse(sparse)
+                .with_user_metadata(op.user_metadata().cloned()),
+        ))
     }
 
     fn blocking_copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
@@ -504,7 +811,7 @@ impl Accessor for FsBackend {
 
         let to = Self::blocking_ensure_write_abs_path(&self.root, to.trim_end_matches('/'))?;
 
-        std::fs::copy(from, to).map_err(new_std_io_error)?;
+        sparse::blocking_copy(&from, &to)?;
 
         Ok(RpCopy::default())
     }
@@ -517,40 +824,101 @@ impl Accessor for FsBackend {
 
         let to = Self::blocking_ensure_write_abs_path(&self.root, to.trim_end_matches('/'))?;
 
-        std::fs::rename(from, to).map_err(new_std_io_error)?;
+        match std::fs::rename(&from, &to) {
+            Ok(()) => {}
+            Err(err) if self.enable_cross_device_rename && is_cross_device_error(&err) => {
+                Self::blocking_rename_across_device(&from, &to)?
+            }
+            Err(err) => return Err(new_std_io_error(err)),
+        }
 
         Ok(RpRename::default())
     }
 
+    fn blocking_create_symlink(
+        &self,
+        target: &str,
+        path: &str,
+        _: OpCreateSymlink,
+    ) -> Result<RpCreateSymlink> {
+        let p = Self::blocking_ensure_write_abs_path(&self.root, path)?;
+
+        #[cfg(unix)]
+        let res = std::os::unix::fs::symlink(target, p).map_err(new_std_io_error);
+        #[cfg(not(unix))]
+        let res = {
+            let _ = (target, p);
+            Err(Error::new(
+                ErrorKind::Unsupported,
+                "create symlink is only supported on unix",
+            ))
+        };
+
+        res.map(|_| RpCreateSymlink::default())
+    }
+
+    fn blocking_set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        let p = self.root.join(path.trim_end_matches('/'));
+
+        #[cfg(unix)]
+        let res = {
+            use std::os::unix::fs::PermissionsExt;
+
+            std::fs::set_permissions(p, std::fs::Permissions::from_mode(args.permissions()))
+                .map_err(new_std_io_error)
+        };
+        #[cfg(not(unix))]
+        let res = {
+            let _ = (p, args);
+            Err(Error::new(
+                ErrorKind::Unsupported,
+                "set permissions is only supported on unix",
+            ))
+        };
+
+        res.map(|_| RpSetPermissions::default())
+    }
+
     fn blocking_stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
         let p = self.root.join(path.trim_end_matches('/'));
 
-        let meta = std::fs::metadata(p).map_err(new_std_io_error)?;
+        let meta = std::fs::symlink_metadata(&p).map_err(new_std_io_error)?;
 
-        let mode = if meta.is_dir() {
-            EntryMode::DIR
-        } else if meta.is_file() {
-            EntryMode::FILE
+        let mut m = if meta.is_symlink() {
+            let target = std::fs::read_link(&p).map_err(new_std_io_error)?;
+            match std::fs::metadata(&p) {
+                Ok(meta) => Self::parse_metadata(&meta, Some(target))?,
+                // Dangling symlink still exists, return the link itself.
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    Self::parse_metadata(&meta, Some(target))?
+                }
+                Err(err) => return Err(new_std_io_error(err)),
+            }
         } else {
-            EntryMode::Unknown
+            Self::parse_metadata(&meta, None)?
         };
-        let m = Metadata::new(mode)
-            .with_content_length(meta.len())
-            .with_last_modified(
-                meta.modified()
-                    .map(DateTime::from)
-                    .map_err(new_std_io_error)?,
-            );
+        if self.enable_xattr {
+            m.set_user_metadata(blocking_get_user_metadata(&p)?);
+        }
 
         Ok(RpStat::new(m))
     }
 
-    fn blocking_delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         let p = self.root.join(path.trim_end_matches('/'));
 
         let meta = std::fs::metadata(&p);
 
         match meta {
+            Ok(_) if args.trash() => {
+                self.trash()?.blocking_trash(&p)?;
+
+                Ok(RpDelete::default())
+            }
             Ok(meta) => {
                 if meta.is_dir() {
                     std::fs::remove_dir(&p).map_err(new_std_io_error)?;
@@ -560,7 +928,9 @@ impl Accessor for FsBackend {
 
                 Ok(RpDelete::default())
             }
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(RpDelete::default()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound && !args.must_exist() => {
+                Ok(RpDelete::default())
+            }
             Err(err) => Err(new_std_io_error(err)),
         }
     }
@@ -603,4 +973,234 @@ mod tests {
             assert!(tmp_file.starts_with(expected_prefix));
         }
     }
+
+    fn new_operator() -> Result<Operator> {
+        let root = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let mut builder = Fs::default();
+        builder.root(&root.to_string_lossy());
+        Ok(Operator::new(builder)?.finish())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stat_symlink() -> Result<()> {
+        let op = new_operator()?;
+        op.write("file", "hello").await?;
+
+        op.create_symlink("file", "link").await?;
+        let meta = op.stat("link").await?;
+        assert_eq!(meta.mode(), EntryMode::FILE);
+        assert_eq!(meta.content_length(), 5);
+        assert_eq!(meta.symlink_target(), Some("file"));
+
+        // Dangling symlink must be visible instead of NotFound.
+        op.create_symlink("missing", "dangling").await?;
+        let meta = op.stat("dangling").await?;
+        assert_eq!(meta.mode(), EntryMode::Unknown);
+        assert_eq!(meta.symlink_target(), Some("missing"));
+        let meta = op.blocking().stat("dangling")?;
+        assert_eq!(meta.symlink_target(), Some("missing"));
+
+        let err = op
+            .create_symlink("file", "link")
+            .await
+            .expect_err("link must exist");
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_set_permissions() -> Result<()> {
+        let op = new_operator()?;
+        op.write("file", "hello").await?;
+
+        op.set_permissions("file", 0o600).await?;
+        assert_eq!(op.stat("file").await?.permissions(), Some(0o600));
+        op.blocking().set_permissions("file", 0o644)?;
+        assert_eq!(op.stat("file").await?.permissions(), Some(0o644));
+
+        let meta = op.stat("file").await?;
+        assert!(meta.uid().is_some());
+        assert!(meta.gid().is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_cross_device_error() {
+        #[cfg(unix)]
+        assert!(is_cross_device_error(&std::io::Error::from_raw_os_error(
+            libc::EXDEV
+        )));
+        assert!(!is_cross_device_error(&std::io::Error::from(
+            std::io::ErrorKind::NotFound
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_rename_across_device() -> Result<()> {
+        let root = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        std::fs::create_dir_all(root.join("dir")).map_err(new_std_io_error)?;
+        let (from, to) = (root.join("from"), root.join("to"));
+        std::fs::write(&from, "hello").map_err(new_std_io_error)?;
+        let mtime = filetime::FileTime::from_unix_time(1_000_000_000, 0);
+        filetime::set_file_mtime(&from, mtime).map_err(new_std_io_error)?;
+
+        FsBackend::rename_across_device(&from, &to).await?;
+        assert!(!from.exists());
+        assert_eq!(std::fs::read(&to).map_err(new_std_io_error)?, b"hello");
+        let meta = std::fs::metadata(&to).map_err(new_std_io_error)?;
+        assert_eq!(
+            filetime::FileTime::from_last_modification_time(&meta),
+            mtime
+        );
+
+        FsBackend::blocking_rename_across_device(&to, &from)?;
+        assert!(!to.exists());
+        assert_eq!(std::fs::read(&from).map_err(new_std_io_error)?, b"hello");
+
+        // Dirs must not be copied as files.
+        let err = FsBackend::rename_across_device(&root.join("dir"), &root.join("moved"))
+            .await
+            .expect_err("dir must be rejected");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = FsBackend::blocking_rename_across_device(&root.join("dir"), &root.join("moved"))
+            .expect_err("dir must be rejected");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert!(root.join("dir").is_dir());
+        assert!(!root.join("moved").exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_with_user_metadata() -> Result<()> {
+        let root = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let mut builder = Fs::default();
+        builder.root(&root.to_string_lossy()).enable_xattr();
+        let op = Operator::new(builder)?.finish();
+
+        let user_metadata = HashMap::from([("owner".to_string(), "opendal".to_string())]);
+        op.write_with("file", "hello")
+            .user_metadata(user_metadata.clone())
+            .await?;
+        let meta = op.stat("file").await?;
+        assert_eq!(meta.user_metadata(), Some(&user_metadata));
+
+        op.blocking()
+            .write_with("blocking", "hello")
+            .user_metadata(user_metadata.clone())
+            .call()?;
+        let meta = op.blocking().stat("blocking")?;
+        assert_eq!(meta.user_metadata(), Some(&user_metadata));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_must_exist() -> Result<()> {
+        let root = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let mut builder = Fs::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder)?.finish();
+        assert!(op.info().full_capability().delete_with_must_exist);
+
+        op.write("file", "Hello, World!").await?;
+        op.delete_with("file").must_exist(true).await?;
+        // ENOENT is reported by delete itself.
+        let err = op
+            .delete_with("file")
+            .must_exist(true)
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        let err = op
+            .blocking()
+            .delete_with("file")
+            .must_exist(true)
+            .call()
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        // Idempotent delete is still the default.
+        op.delete("file").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_trash_and_restore() -> Result<()> {
+        let root = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let trash_dir = root.join(".trash");
+        let mut builder = Fs::default();
+        builder.root(&root.to_string_lossy());
+        builder.trash_dir(&trash_dir.to_string_lossy());
+        let op = Operator::new(builder)?.finish();
+
+        op.write("file", "Hello, World!").await?;
+        op.write("dir/file", "Hello, World!").await?;
+
+        let err = op
+            .delete_with("dir/")
+            .trash(true)
+            .await
+            .expect_err("trash non-empty dir must fail");
+        assert_eq!(err.kind(), ErrorKind::DirectoryNotEmpty);
+        assert!(op.is_exist("dir/file").await?);
+
+        op.delete_with("file").trash(true).await?;
+        assert!(!op.is_exist("file").await?);
+
+        let trash = FsTrash::new(&trash_dir);
+        let entries = trash.list().await?;
+        assert_eq!(entries.len(), 1);
+        let expected = root.canonicalize().expect("root must exist").join("file");
+        assert_eq!(entries[0].original_path(), expected);
+        assert!(entries[0].deleted_at().is_some());
+
+        op.write("file", "occupied").await?;
+        let err = trash
+            .restore(entries[0].name())
+            .await
+            .expect_err("restore to existing path must fail");
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+
+        op.delete("file").await?;
+        trash.restore(entries[0].name()).await?;
+        assert_eq!(op.read("file").await?, b"Hello, World!");
+        assert!(trash.list().await?.is_empty());
+
+        std::fs::remove_dir_all(root).expect("remove temp dir must succeed");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_keeps_holes() -> Result<()> {
+        let op = new_operator()?;
+        op.create_dir("/").await?;
+        let root = PathBuf::from(op.info().root());
+
+        // A 1MiB file with 4KiB data in the middle.
+        {
+            use std::io::Seek;
+            use std::io::Write;
+
+            let mut f = std::fs::File::create(root.join("sparse")).map_err(new_std_io_error)?;
+            f.set_len(1024 * 1024).map_err(new_std_io_error)?;
+            f.seek(std::io::SeekFrom::Start(512 * 1024))
+                .map_err(new_std_io_error)?;
+            f.write_all(&[1; 4096]).map_err(new_std_io_error)?;
+        }
+
+        op.copy("sparse", "async").await?;
+        op.blocking().copy("sparse", "blocking")?;
+
+        let data_ranges = |name: &str| {
+            let f = std::fs::File::open(root.join(name)).unwrap();
+            sparse::data_ranges(&f, f.metadata().unwrap().len()).unwrap()
+        };
+        let expected = op.read("sparse").await?;
+        for name in ["async", "blocking"] {
+            assert_eq!(op.read(name).await?, expected, "{name}");
+            assert_eq!(data_ranges(name), data_ranges("sparse"), "{name}");
+        }
+        Ok(())
+    }
 }
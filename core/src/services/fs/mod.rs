@@ -19,4 +19,9 @@ mod backend;
 pub use backend::FsBuilder as Fs;
 
 mod lister;
+mod sparse;
+mod trash;
+pub use trash::FsTrash;
+pub use trash::FsTrashEntry;
+mod user_metadata;
 mod writer;
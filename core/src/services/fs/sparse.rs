@@ -0,0 +1,107 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Detect holes of sparse files by `SEEK_DATA`/`SEEK_HOLE` and copy them
+//! without filling the holes.
+
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::ops::Range;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::raw::*;
+use crate::*;
+
+/// Copy `from` into `to` in a blocking thread, holes of `from` are kept.
+pub async fn copy(from: PathBuf, to: PathBuf) -> Result<()> {
+    tokio::task::spawn_blocking(move || blocking_copy(&from, &to))
+        .await
+        .map_err(new_task_join_error)?
+}
+
+/// Copy `from` into `to` with only the data ranges of `from` written, holes
+/// are left by extending `to` to the size of `from`.
+///
+/// Permissions of source will be copied too, the same as `std::fs::copy`.
+pub fn blocking_copy(from: &Path, to: &Path) -> Result<()> {
+    let mut src = File::open(from).map_err(new_std_io_error)?;
+    let meta = src.metadata().map_err(new_std_io_error)?;
+    let ranges = data_ranges(&src, meta.len()).map_err(new_std_io_error)?;
+
+    let mut dst = File::create(to).map_err(new_std_io_error)?;
+    for range in ranges {
+        src.seek(SeekFrom::Start(range.start))
+            .map_err(new_std_io_error)?;
+        dst.seek(SeekFrom::Start(range.start))
+            .map_err(new_std_io_error)?;
+        io::copy(&mut (&mut src).take(range.end - range.start), &mut dst)
+            .map_err(new_std_io_error)?;
+    }
+    dst.set_len(meta.len()).map_err(new_std_io_error)?;
+    dst.set_permissions(meta.permissions())
+        .map_err(new_std_io_error)
+}
+
+/// Returns the ranges of given file that contain data, the gaps between
+/// them are holes which are not allocated on disk.
+///
+/// Filesystems without hole support report the whole file as data.
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+pub fn data_ranges(f: &File, size: u64) -> io::Result<Vec<Range<u64>>> {
+    use std::os::unix::io::AsRawFd;
+
+    let fd = f.as_raw_fd();
+    let mut ranges = Vec::new();
+    let mut offset = 0;
+    while offset < size {
+        // Safety: fd is valid during the lifetime of f.
+        let start = unsafe { libc::lseek(fd, offset as libc::off_t, libc::SEEK_DATA) };
+        if start < 0 {
+            let err = io::Error::last_os_error();
+            // ENXIO means there is no data after offset.
+            if err.raw_os_error() == Some(libc::ENXIO) {
+                break;
+            }
+            return Err(err);
+        }
+        // Safety: fd is valid during the lifetime of f.
+        let end = unsafe { libc::lseek(fd, start, libc::SEEK_HOLE) };
+        if end < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let end = (end as u64).min(size);
+        ranges.push(start as u64..end);
+        offset = end;
+    }
+    Ok(ranges)
+}
+
+/// Returns the ranges of given file that contain data.
+///
+/// Holes can't be detected on this platform, so the whole file is data.
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "freebsd")))]
+pub fn data_ranges(_: &File, size: u64) -> io::Result<Vec<Range<u64>>> {
+    if size == 0 {
+        return Ok(Vec::new());
+    }
+    Ok(vec![0..size])
+}
@@ -0,0 +1,395 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::Local;
+use chrono::NaiveDateTime;
+use chrono::TimeZone;
+use chrono::Utc;
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+use crate::raw::*;
+use crate::*;
+
+const TRASH_INFO_EXT: &str = "trashinfo";
+const TRASH_INFO_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// FsTrash is the trash directory used by `delete_with().trash(true)` on
+/// the fs service.
+///
+/// The layout follows the [FreeDesktop.org Trash specification](https://specifications.freedesktop.org/trash-spec/trashspec-latest.html):
+///
+/// - `{dir}/files/{name}` stores the trashed file or dir.
+/// - `{dir}/info/{name}.trashinfo` stores the original path and deletion date.
+///
+/// So users can set the trash dir to `~/.local/share/Trash` to share the
+/// same trash with desktop environments on Linux.
+#[derive(Debug, Clone)]
+pub struct FsTrash {
+    dir: PathBuf,
+}
+
+/// FsTrashEntry is an entry that lives in [`FsTrash`].
+#[derive(Debug, Clone)]
+pub struct FsTrashEntry {
+    name: String,
+    original_path: PathBuf,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+impl FsTrashEntry {
+    /// Name of this entry inside trash, used by [`FsTrash::restore`].
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Absolute path of this entry before it's moved into trash.
+    pub fn original_path(&self) -> &Path {
+        &self.original_path
+    }
+
+    /// Time when this entry is moved into trash.
+    pub fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+}
+
+impl FsTrash {
+    /// Create a new trash located at given dir.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The dir of this trash.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn files_dir(&self) -> PathBuf {
+        self.dir.join("files")
+    }
+
+    fn info_dir(&self) -> PathBuf {
+        self.dir.join("info")
+    }
+
+    fn info_path(&self, name: &str) -> PathBuf {
+        self.info_dir().join(format!("{name}.{TRASH_INFO_EXT}"))
+    }
+
+    fn build_trash_info(path: &Path) -> String {
+        format!(
+            "[Trash Info]\nPath={}\nDeletionDate={}\n",
+            percent_encode_path(&path.to_string_lossy()),
+            Local::now().format(TRASH_INFO_DATE_FORMAT)
+        )
+    }
+
+    fn parse_trash_info(name: &str, content: &str) -> Result<FsTrashEntry> {
+        let mut original_path = None;
+        let mut deleted_at = None;
+
+        for line in content.lines() {
+            if let Some(v) = line.strip_prefix("Path=") {
+                original_path = Some(PathBuf::from(percent_decode_path(v)));
+            } else if let Some(v) = line.strip_prefix("DeletionDate=") {
+                deleted_at = NaiveDateTime::parse_from_str(v, TRASH_INFO_DATE_FORMAT)
+                    .ok()
+                    .and_then(|v| Local.from_local_datetime(&v).single())
+                    .map(|v| v.with_timezone(&Utc));
+            }
+        }
+
+        let original_path = original_path.ok_or_else(|| {
+            Error::new(ErrorKind::Unexpected, "trash info doesn't contain path")
+                .with_context("name", name)
+        })?;
+
+        Ok(FsTrashEntry {
+            name: name.to_string(),
+            original_path,
+            deleted_at,
+        })
+    }
+
+    /// Pick an unused name for the input path.
+    fn name_of(path: &Path, retry: usize) -> String {
+        let name = path
+            .file_name()
+            .map(|v| v.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        if retry == 0 {
+            name
+        } else {
+            format!("{name}.{}", Uuid::new_v4())
+        }
+    }
+
+    /// Move the file or dir at given absolute path into trash.
+    ///
+    /// Like plain delete, only empty dirs could be trashed.
+    pub(crate) async fn trash(&self, path: &Path) -> Result<()> {
+        let meta = tokio::fs::symlink_metadata(path)
+            .await
+            .map_err(new_std_io_error)?;
+        if meta.is_dir() {
+            let mut rd = tokio::fs::read_dir(path).await.map_err(new_std_io_error)?;
+            if rd.next_entry().await.map_err(new_std_io_error)?.is_some() {
+                return Err(new_dir_not_empty_error(path));
+            }
+        }
+
+        tokio::fs::create_dir_all(self.files_dir())
+            .await
+            .map_err(new_std_io_error)?;
+        tokio::fs::create_dir_all(self.info_dir())
+            .await
+            .map_err(new_std_io_error)?;
+
+        // Reserve the name by creating the trash info file exclusively.
+        let mut retry = 0;
+        let name = loop {
+            let name = Self::name_of(path, retry);
+            match tokio::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(self.info_path(&name))
+                .await
+            {
+                Ok(mut f) => {
+                    f.write_all(Self::build_trash_info(path).as_bytes())
+                        .await
+                        .map_err(new_std_io_error)?;
+                    f.sync_all().await.map_err(new_std_io_error)?;
+                    break name;
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => retry += 1,
+                Err(err) => return Err(new_std_io_error(err)),
+            }
+        };
+
+        if let Err(err) = tokio::fs::rename(path, self.files_dir().join(&name)).await {
+            let _ = tokio::fs::remove_file(self.info_path(&name)).await;
+            return Err(new_std_io_error(err));
+        }
+
+        Ok(())
+    }
+
+    /// Move the file or dir at given absolute path into trash.
+    ///
+    /// Like plain delete, only empty dirs could be trashed.
+    pub(crate) fn blocking_trash(&self, path: &Path) -> Result<()> {
+        let meta = std::fs::symlink_metadata(path).map_err(new_std_io_error)?;
+        if meta.is_dir() {
+            let mut rd = std::fs::read_dir(path).map_err(new_std_io_error)?;
+            if rd.next().transpose().map_err(new_std_io_error)?.is_some() {
+                return Err(new_dir_not_empty_error(path));
+            }
+        }
+
+        std::fs::create_dir_all(self.files_dir()).map_err(new_std_io_error)?;
+        std::fs::create_dir_all(self.info_dir()).map_err(new_std_io_error)?;
+
+        // Reserve the name by creating the trash info file exclusively.
+        let mut retry = 0;
+        let name = loop {
+            let name = Self::name_of(path, retry);
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(self.info_path(&name))
+            {
+                Ok(mut f) => {
+                    f.write_all(Self::build_trash_info(path).as_bytes())
+                        .map_err(new_std_io_error)?;
+                    f.sync_all().map_err(new_std_io_error)?;
+                    break name;
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => retry += 1,
+                Err(err) => return Err(new_std_io_error(err)),
+            }
+        };
+
+        if let Err(err) = std::fs::rename(path, self.files_dir().join(&name)) {
+            let _ = std::fs::remove_file(self.info_path(&name));
+            return Err(new_std_io_error(err));
+        }
+
+        Ok(())
+    }
+
+    /// List all entries in this trash.
+    pub async fn list(&self) -> Result<Vec<FsTrashEntry>> {
+        let mut rd = match tokio::fs::read_dir(self.info_dir()).await {
+            Ok(rd) => rd,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(new_std_io_error(err)),
+        };
+
+        let mut entries = vec![];
+        while let Some(de) = rd.next_entry().await.map_err(new_std_io_error)? {
+            let p = de.path();
+            if p.extension().and_then(|v| v.to_str()) != Some(TRASH_INFO_EXT) {
+                continue;
+            }
+            let name = match p.file_stem() {
+                Some(v) => v.to_string_lossy().to_string(),
+                None => continue,
+            };
+
+            let content = tokio::fs::read_to_string(&p)
+                .await
+                .map_err(new_std_io_error)?;
+            entries.push(Self::parse_trash_info(&name, &content)?);
+        }
+
+        Ok(entries)
+    }
+
+    /// List all entries in this trash.
+    pub fn blocking_list(&self) -> Result<Vec<FsTrashEntry>> {
+        let rd = match std::fs::read_dir(self.info_dir()) {
+            Ok(rd) => rd,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(new_std_io_error(err)),
+        };
+
+        let mut entries = vec![];
+        for de in rd {
+            let p = de.map_err(new_std_io_error)?.path();
+            if p.extension().and_then(|v| v.to_str()) != Some(TRASH_INFO_EXT) {
+                continue;
+            }
+            let name = match p.file_stem() {
+                Some(v) => v.to_string_lossy().to_string(),
+                None => continue,
+            };
+
+            let content = std::fs::read_to_string(&p).map_err(new_std_io_error)?;
+            entries.push(Self::parse_trash_info(&name, &content)?);
+        }
+
+        Ok(entries)
+    }
+
+    /// Restore the entry with given name back to its original path.
+    ///
+    /// Returns `AlreadyExists` error if the original path has been taken.
+    pub async fn restore(&self, name: &str) -> Result<()> {
+        let info_path = self.info_path(name);
+        let content = tokio::fs::read_to_string(&info_path)
+            .await
+            .map_err(new_std_io_error)?;
+        let entry = Self::parse_trash_info(name, &content)?;
+
+        match tokio::fs::symlink_metadata(&entry.original_path).await {
+            Ok(_) => {
+                return Err(Error::new(
+                    ErrorKind::AlreadyExists,
+                    "original path of trashed entry already exists",
+                )
+                .with_context("name", name)
+                .with_context("path", entry.original_path.to_string_lossy()))
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(new_std_io_error(err)),
+        }
+
+        if let Some(parent) = entry.original_path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(new_std_io_error)?;
+        }
+        tokio::fs::rename(self.files_dir().join(name), &entry.original_path)
+            .await
+            .map_err(new_std_io_error)?;
+        tokio::fs::remove_file(info_path)
+            .await
+            .map_err(new_std_io_error)?;
+
+        Ok(())
+    }
+
+    /// Restore the entry with given name back to its original path.
+    ///
+    /// Returns `AlreadyExists` error if the original path has been taken.
+    pub fn blocking_restore(&self, name: &str) -> Result<()> {
+        let info_path = self.info_path(name);
+        let content = std::fs::read_to_string(&info_path).map_err(new_std_io_error)?;
+        let entry = Self::parse_trash_info(name, &content)?;
+
+        match std::fs::symlink_metadata(&entry.original_path) {
+            Ok(_) => {
+                return Err(Error::new(
+                    ErrorKind::AlreadyExists,
+                    "original path of trashed entry already exists",
+                )
+                .with_context("name", name)
+                .with_context("path", entry.original_path.to_string_lossy()))
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(new_std_io_error(err)),
+        }
+
+        if let Some(parent) = entry.original_path.parent() {
+            std::fs::create_dir_all(parent).map_err(new_std_io_error)?;
+        }
+        std::fs::rename(self.files_dir().join(name), &entry.original_path)
+            .map_err(new_std_io_error)?;
+        std::fs::remove_file(info_path).map_err(new_std_io_error)?;
+
+        Ok(())
+    }
+}
+
+fn new_dir_not_empty_error(path: &Path) -> Error {
+    Error::new(
+        ErrorKind::DirectoryNotEmpty,
+        "dir to be trashed is not empty",
+    )
+    .with_context("path", path.to_string_lossy())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_trash_info() {
+        let content =
+            "[Trash Info]\nPath=/tmp/hello%20world.txt\nDeletionDate=2023-10-01T12:30:00\n";
+
+        let entry = FsTrash::parse_trash_info("hello world.txt", content).expect("must succeed");
+        assert_eq!(entry.name(), "hello world.txt");
+        assert_eq!(entry.original_path(), Path::new("/tmp/hello world.txt"));
+        assert!(entry.deleted_at().is_some());
+    }
+
+    #[test]
+    fn test_parse_trash_info_without_path() {
+        let content = "[Trash Info]\nDeletionDate=2023-10-01T12:30:00\n";
+
+        assert!(FsTrash::parse_trash_info("abc", content).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Map user metadata to xattrs with prefix `user.opendal.`.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::raw::*;
+use crate::*;
+
+const XATTR_PREFIX: &str = "user.opendal.";
+
+/// Write user metadata into xattrs of given path in a blocking thread.
+pub async fn set_user_metadata(
+    path: PathBuf,
+    user_metadata: HashMap<String, String>,
+) -> Result<()> {
+    tokio::task::spawn_blocking(move || blocking_set_user_metadata(&path, &user_metadata))
+        .await
+        .map_err(new_task_join_error)?
+}
+
+/// Read user metadata from xattrs of given path in a blocking thread.
+pub async fn get_user_metadata(path: PathBuf) -> Result<HashMap<String, String>> {
+    tokio::task::spawn_blocking(move || blocking_get_user_metadata(&path))
+        .await
+        .map_err(new_task_join_error)?
+}
+
+/// Write user metadata into xattrs of given path.
+pub fn blocking_set_user_metadata(
+    path: &Path,
+    user_metadata: &HashMap<String, String>,
+) -> Result<()> {
+    for (k, v) in user_metadata {
+        xattr::set(path, format!("{XATTR_PREFIX}{k}"), v.as_bytes()).map_err(|err| {
+            new_std_io_error(err)
+                .with_operation("xattr::set")
+                .with_context("key", k)
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Read user metadata from xattrs of given path.
+///
+/// Xattrs that not start with `user.opendal.` or not valid utf-8 will be ignored.
+/// Dangling symlinks have no user metadata.
+pub fn blocking_get_user_metadata(path: &Path) -> Result<HashMap<String, String>> {
+    let mut user_metadata = HashMap::new();
+
+    let names = match xattr::list(path) {
+        Ok(names) => names,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound && path.is_symlink() => {
+            return Ok(user_metadata)
+        }
+        Err(err) => return Err(new_std_io_error(err)),
+    };
+    for name in names {
+        let key = match name.to_str().and_then(|v| v.strip_prefix(XATTR_PREFIX)) {
+            Some(key) => key.to_string(),
+            None => continue,
+        };
+
+        let value = match xattr::get(path, &name).map_err(new_std_io_error)? {
+            Some(value) => value,
+            None => continue,
+        };
+        if let Ok(value) = String::from_utf8(value) {
+            user_metadata.insert(key, value);
+        }
+    }
+
+    Ok(user_metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_user_metadata_roundtrip() -> Result<()> {
+        let path = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        std::fs::write(&path, "hello").map_err(new_std_io_error)?;
+        xattr::set(&path, "user.other", b"ignored").map_err(new_std_io_error)?;
+
+        let user_metadata = HashMap::from([
+            ("location".to_string(), "everywhere".to_string()),
+            ("owner".to_string(), "opendal".to_string()),
+        ]);
+        set_user_metadata(path.clone(), user_metadata.clone()).await?;
+        assert_eq!(get_user_metadata(path.clone()).await?, user_metadata);
+        assert_eq!(blocking_get_user_metadata(&path)?, user_metadata);
+
+        std::fs::remove_file(&path).map_err(new_std_io_error)?;
+        Ok(())
+    }
+}
@@ -15,6 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+use std::io::Seek;
+use std::io::SeekFrom;
 use std::io::Write;
 use std::path::PathBuf;
 use std::pin::Pin;
@@ -25,18 +28,35 @@ use std::task::Poll;
 use async_trait::async_trait;
 use futures::future::BoxFuture;
 use futures::FutureExt;
+use tokio::io::AsyncSeek;
+use tokio::io::AsyncSeekExt;
 use tokio::io::AsyncWrite;
 use tokio::io::AsyncWriteExt;
 
+use super::user_metadata::blocking_set_user_metadata;
+use super::user_metadata::set_user_metadata;
 use crate::raw::*;
 use crate::*;
 
+/// The size of block that we will check for zero ranges in sparse mode.
+///
+/// Most filesystems allocate space in 4KiB blocks, holes smaller than this
+/// can't be kept anyway.
+const SPARSE_BLOCK_SIZE: usize = 4 * 1024;
+
 pub struct FsWriter<F> {
     target_path: PathBuf,
     tmp_path: Option<PathBuf>,
 
     f: Option<F>,
     fut: Option<BoxFuture<'static, Result<()>>>,
+
+    sparse: bool,
+    /// The size of zero ranges that skipped but not seeked yet.
+    hole: u64,
+    seeking: bool,
+
+    user_metadata: Option<HashMap<String, String>>,
 }
 
 impl<F> FsWriter<F> {
@@ -47,8 +67,59 @@ impl<F> FsWriter<F> {
 
             f: Some(f),
             fut: None,
+
+            sparse: false,
+            hole: 0,
+            seeking: false,
+
+            user_metadata: None,
         }
     }
+
+    /// Set user metadata that will be stored as xattrs while closing.
+    pub fn with_user_metadata(mut self, user_metadata: Option<HashMap<String, String>>) -> Self {
+        self.user_metadata = user_metadata;
+        self
+    }
+
+    /// Enable sparse mode that leaves holes for zero ranges.
+    ///
+    /// Sparse mode must not be used with append since writes of file
+    /// opened in append mode always go to the end.
+    pub fn with_sparse(mut self, sparse: bool) -> Self {
+        self.sparse = sparse;
+        self
+    }
+}
+
+/// Split the input buf for sparse write.
+///
+/// Returns `(true, n)` if the first `n` bytes are zero blocks that can be
+/// skipped, otherwise `(false, n)` which means the first `n` bytes should
+/// be written.
+fn split_sparse_chunk(bs: &[u8]) -> (bool, usize) {
+    let is_zero = |b: &[u8]| b.iter().all(|v| *v == 0);
+
+    let mut n = 0;
+    for block in bs.chunks(SPARSE_BLOCK_SIZE) {
+        if block.len() < SPARSE_BLOCK_SIZE || !is_zero(block) {
+            break;
+        }
+        n += block.len();
+    }
+    if n > 0 {
+        return (true, n);
+    }
+
+    // Write until the next zero block.
+    let mut n = 0;
+    for block in bs.chunks(SPARSE_BLOCK_SIZE) {
+        if n > 0 && block.len() == SPARSE_BLOCK_SIZE && is_zero(block) {
+            break;
+        }
+        n += block.len();
+    }
+    (false, n)
 }
 
 /// # Safety
@@ -61,8 +132,33 @@ impl oio::Write for FsWriter<tokio::fs::File> {
     fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
         let f = self.f.as_mut().expect("FsWriter must be initialized");
 
+        if !self.sparse {
+            return Pin::new(f)
+                .poll_write_vectored(cx, &bs.vectored_chunk())
+                .map_err(new_std_io_error);
+        }
+
+        let (is_hole, n) = split_sparse_chunk(bs.chunk());
+        if is_hole {
+            self.hole += n as u64;
+            return Poll::Ready(Ok(n));
+        }
+
+        // Seek over the skipped zero ranges before writing real data.
+        if self.hole > 0 {
+            if !self.seeking {
+                Pin::new(&mut *f)
+                    .start_seek(SeekFrom::Current(self.hole as i64))
+                    .map_err(new_std_io_error)?;
+                self.seeking = true;
+            }
+            ready!(Pin::new(&mut *f).poll_complete(cx)).map_err(new_std_io_error)?;
+            self.seeking = false;
+            self.hole = 0;
+        }
+
         Pin::new(f)
-            .poll_write_vectored(cx, &bs.vectored_chunk())
+            .poll_write(cx, &bs.chunk()[..n])
             .map_err(new_std_io_error)
     }
 
@@ -77,10 +173,22 @@ impl oio::Write for FsWriter<tokio::fs::File> {
             let mut f = self.f.take().expect("FsWriter must be initialized");
             let tmp_path = self.tmp_path.clone();
             let target_path = self.target_path.clone();
+            let hole = self.hole;
+            let user_metadata = self.user_metadata.take();
             self.fut = Some(Box::pin(async move {
                 f.flush().await.map_err(new_std_io_error)?;
+                // Extend the file to keep the trailing holes.
+                if hole > 0 {
+                    let pos = f.stream_position().await.map_err(new_std_io_error)?;
+                    f.set_len(pos + hole).await.map_err(new_std_io_error)?;
+                }
                 f.sync_all().await.map_err(new_std_io_error)?;
 
+                if let Some(user_metadata) = user_metadata {
+                    let path = tmp_path.clone().unwrap_or_else(|| target_path.clone());
+                    set_user_metadata(path, user_metadata).await?;
+                }
+
                 if let Some(tmp_path) = &tmp_path {
                     tokio::fs::rename(tmp_path, &target_path)
                         .await
@@ -122,14 +230,44 @@ impl oio::BlockingWrite for FsWriter<std::fs::File> {
     fn write(&mut self, bs: &dyn oio::WriteBuf) -> Result<usize> {
         let f = self.f.as_mut().expect("FsWriter must be initialized");
 
-        f.write_vectored(&bs.vectored_chunk())
-            .map_err(new_std_io_error)
+        if !self.sparse {
+            return f
+                .write_vectored(&bs.vectored_chunk())
+                .map_err(new_std_io_error);
+        }
+
+        let (is_hole, n) = split_sparse_chunk(bs.chunk());
+        if is_hole {
+            self.hole += n as u64;
+            return Ok(n);
+        }
+
+        // Seek over the skipped zero ranges before writing real data.
+        if self.hole > 0 {
+            f.seek(SeekFrom::Current(self.hole as i64))
+                .map_err(new_std_io_error)?;
+            self.hole = 0;
+        }
+
+        f.write(&bs.chunk()[..n]).map_err(new_std_io_error)
     }
 
     fn close(&mut self) -> Result<()> {
-        if let Some(f) = self.f.take() {
+        if let Some(mut f) = self.f.take() {
+            // Extend the file to keep the trailing holes.
+            if self.hole > 0 {
+                let pos = f.stream_position().map_err(new_std_io_error)?;
+                f.set_len(pos + self.hole).map_err(new_std_io_error)?;
+            }
             f.sync_all().map_err(new_std_io_error)?;
 
+            if let Some(user_metadata) = &self.user_metadata {
+                blocking_set_user_metadata(
+                    self.tmp_path.as_ref().unwrap_or(&self.target_path),
+                    user_metadata,
+                )?;
+            }
+
             if let Some(tmp_path) = &self.tmp_path {
                 std::fs::rename(tmp_path, &self.target_path).map_err(new_std_io_error)?;
             }
@@ -138,3 +276,40 @@ impl oio::BlockingWrite for FsWriter<std::fs::File> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_sparse_chunk() {
+        let cases = vec![
+            ("empty", vec![], (false, 0)),
+            ("small zeros", vec![0; 16], (false, 16)),
+            (
+                "one zero block",
+                vec![0; SPARSE_BLOCK_SIZE],
+                (true, SPARSE_BLOCK_SIZE),
+            ),
+            (
+                "zero blocks with tail",
+                vec![0; 2 * SPARSE_BLOCK_SIZE + 1],
+                (true, 2 * SPARSE_BLOCK_SIZE),
+            ),
+            (
+                "data",
+                vec![1; SPARSE_BLOCK_SIZE + 1],
+                (false, SPARSE_BLOCK_SIZE + 1),
+            ),
+            (
+                "data then zero block",
+                [vec![1; SPARSE_BLOCK_SIZE], vec![0; SPARSE_BLOCK_SIZE]].concat(),
+                (false, SPARSE_BLOCK_SIZE),
+            ),
+        ];
+
+        for (name, input, expected) in cases {
+            assert_eq!(split_sparse_chunk(&input), expected, "{name}");
+        }
+    }
+}
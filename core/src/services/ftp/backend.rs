@@ -59,7 +59,7 @@ pub struct FtpConfig {
     /// user of this backend
     pub user: Option<String>,
     /// password of this backend
-    pub password: Option<String>,
+    pub password: Option<Secret>,
 }
 
 impl Debug for FtpConfig {
@@ -125,7 +125,7 @@ impl FtpBuilder {
         self.config.password = if password.is_empty() {
             None
         } else {
-            Some(password.to_string())
+            Some(password.into())
         };
 
         self
@@ -179,10 +179,7 @@ impl Builder for FtpBuilder {
             Some(v) => v.clone(),
         };
 
-        let password = match &self.config.password {
-            None => "".to_string(),
-            Some(v) => v.clone(),
-        };
+        let password = self.config.password.take().unwrap_or_default();
 
         debug!("ftp backend finished: {:?}", &self);
 
@@ -208,7 +205,7 @@ pub struct Manager {
     endpoint: String,
     root: String,
     user: String,
-    password: String,
+    password: Secret,
     enable_secure: bool,
 }
 
@@ -233,7 +230,9 @@ impl bb8::ManageConnection for Manager {
 
         // login if needed
         if !self.user.is_empty() {
-            ftp_stream.login(&self.user, &self.password).await?;
+            ftp_stream
+                .login(&self.user, self.password.expose_secret())
+                .await?;
         }
 
         // change to the root path
@@ -270,7 +269,7 @@ pub struct FtpBackend {
     endpoint: String,
     root: String,
     user: String,
-    password: String,
+    password: Secret,
     enable_secure: bool,
     pool: OnceCell<bb8::Pool<Manager>>,
 }
@@ -467,7 +466,7 @@ impl FtpBackend {
                         endpoint: self.endpoint.to_string(),
                         root: self.root.to_string(),
                         user: self.user.to_string(),
-                        password: self.password.to_string(),
+                        password: self.password.clone(),
                         enable_secure: self.enable_secure,
                     })
                     .await
@@ -58,14 +58,16 @@ pub struct GcsBuilder {
     service_account: Option<String>,
 
     /// credential string for GCS service
-    credential: Option<String>,
+    credential: Option<Secret>,
     /// credential path for GCS service.
     credential_path: Option<String>,
 
     http_client: Option<HttpClient>,
+    http_client_builder: HttpClientBuilder,
     customed_token_loader: Option<Box<dyn GoogleTokenLoad>>,
     predefined_acl: Option<String>,
     default_storage_class: Option<String>,
+    delegate_service_account: Option<String>,
 }
 
 impl GcsBuilder {
@@ -124,7 +126,7 @@ impl GcsBuilder {
     /// set the base64 hashed credentials string used for OAuth2
     pub fn credential(&mut self, credential: &str) -> &mut Self {
         if !credential.is_empty() {
-            self.credential = Some(credential.to_string())
+            self.credential = Some(credential.into())
         };
         self
     }
@@ -148,6 +150,42 @@ impl GcsBuilder {
         self
     }
 
+    /// Set the max idle connections per host in the pool of http client.
+    ///
+    /// Ignored if [`Self::http_client`] is set.
+    pub fn pool_max_idle_per_host(&mut self, max: usize) -> &mut Self {
+        self.http_client_builder =
+            std::mem::take(&mut self.http_client_builder).pool_max_idle_per_host(max);
+        self
+    }
+
+    /// Set the timeout for idle connections in the pool of http client.
+    ///
+    /// Ignored if [`Self::http_client`] is set.
+    pub fn pool_idle_timeout(&mut self, timeout: std::time::Duration) -> &mut Self {
+        self.http_client_builder =
+            std::mem::take(&mut self.http_client_builder).pool_idle_timeout(timeout);
+        self
+    }
+
+    /// Only use HTTP/2 for connections of http client.
+    ///
+    /// Ignored if [`Self::http_client`] is set.
+    pub fn http2_prior_knowledge(&mut self, enabled: bool) -> &mut Self {
+        self.http_client_builder =
+            std::mem::take(&mut self.http_client_builder).http2_prior_knowledge(enabled);
+        self
+    }
+
+    /// Set the interval of TCP keepalive for connections of http client.
+    ///
+    /// Ignored if [`Self::http_client`] is set.
+    pub fn tcp_keepalive(&mut self, interval: std::time::Duration) -> &mut Self {
+        self.http_client_builder =
+            std::mem::take(&mut self.http_client_builder).tcp_keepalive(interval);
+        self
+    }
+
     /// Specify the customed token loader used by this service.
     pub fn customed_token_loader(&mut self, token_load: Box<dyn GoogleTokenLoad>) -> &mut Self {
         self.customed_token_loader = Some(token_load);
@@ -183,6 +221,18 @@ impl GcsBuilder {
         };
         self
     }
+
+    /// Set the service account email that signs policy documents for
+    /// [`Operator::delegate`](crate::Operator::delegate).
+    ///
+    /// The token of this service must be allowed to call IAM `signBlob`
+    /// for this account.
+    pub fn delegate_service_account(&mut self, email: &str) -> &mut Self {
+        if !email.is_empty() {
+            self.delegate_service_account = Some(email.to_string())
+        };
+        self
+    }
 }
 
 impl Debug for GcsBuilder {
@@ -203,6 +253,34 @@ impl Debug for GcsBuilder {
     }
 }
 
+impl GcsBuilder {
+    /// Check the combinations of configs and report all problems at once.
+    fn validate(&self) -> Result<()> {
+        let mut errs = ConfigError::new(Scheme::Gcs);
+
+        if self.bucket.is_empty() {
+            errs.push("bucket", "bucket is empty, set it to the name of bucket");
+        }
+        if self.credential.is_some() && self.credential_path.is_some() {
+            errs.push(
+                "credential_path",
+                "both credential and credential_path set, only one of them should be provided",
+            );
+        }
+        if self.service_account.is_some()
+            && (self.credential.is_some() || self.credential_path.is_some())
+        {
+            errs.push(
+                "service_account",
+                "service_account is only used with VM metadata, \
+                 unset it while credential or credential_path provided",
+            );
+        }
+
+        errs.finish()
+    }
+}
+
 impl Builder for GcsBuilder {
     const SCHEME: Scheme = Scheme::Gcs;
     type Accessor = GcsBackend;
@@ -218,6 +296,8 @@ impl Builder for GcsBuilder {
         map.get("predefined_acl").map(|v| builder.predefined_acl(v));
         map.get("default_storage_class")
             .map(|v| builder.default_storage_class(v));
+        map.get("delegate_service_account")
+            .map(|v| builder.delegate_service_account(v));
 
         builder
     }
@@ -228,6 +308,8 @@ impl Builder for GcsBuilder {
         let root = normalize_root(&self.root.take().unwrap_or_default());
         debug!("backend use root {}", root);
 
+        self.validate()?;
+
         // Handle endpoint and bucket name
         let bucket = match self.bucket.is_empty() {
             false => Ok(&self.bucket),
@@ -243,7 +325,7 @@ impl Builder for GcsBuilder {
         let client = if let Some(client) = self.http_client.take() {
             client
         } else {
-            HttpClient::new().map_err(|err| {
+            self.http_client_builder.clone().build().map_err(|err| {
                 err.with_operation("Builder::build")
                     .with_context("service", Scheme::Gcs)
             })?
@@ -257,7 +339,7 @@ impl Builder for GcsBuilder {
 
         let mut cred_loader = GoogleCredentialLoader::default();
         if let Some(cred) = &self.credential {
-            cred_loader = cred_loader.with_content(cred);
+            cred_loader = cred_loader.with_content(cred.expose_secret());
         }
         if let Some(cred) = &self.credential_path {
             cred_loader = cred_loader.with_path(cred);
@@ -293,6 +375,7 @@ impl Builder for GcsBuilder {
                 credential_loader: cred_loader,
                 predefined_acl: self.predefined_acl.clone(),
                 default_storage_class: self.default_storage_class.clone(),
+                delegate_service_account: self.delegate_service_account.clone(),
             }),
         };
 
@@ -320,8 +403,11 @@ impl Accessor for GcsBackend {
         am.set_scheme(Scheme::Gcs)
             .set_root(&self.core.root)
             .set_name(&self.core.bucket)
+            .set_endpoint(&self.core.endpoint)
+            .set_signer(self.core.clone())
             .set_native_capability(Capability {
                 stat: true,
+                stat_root: true,
                 stat_with_if_match: true,
                 stat_with_if_none_match: true,
 
@@ -335,6 +421,7 @@ impl Accessor for GcsBackend {
                 write_can_empty: true,
                 write_can_multi: true,
                 write_with_content_type: true,
+                write_with_if_none_match: true,
                 // The buffer size should be a multiple of 256 KiB (256 x 1024 bytes), unless it's the last chunk that completes the upload.
                 // Larger chunk sizes typically make uploads faster, but note that there's a tradeoff between speed and memory usage.
                 // It's recommended that you use at least 8 MiB for the chunk size.
@@ -344,12 +431,18 @@ impl Accessor for GcsBackend {
 
                 delete: true,
                 copy: true,
+                concat: true,
 
                 list: true,
                 list_with_limit: true,
+                // The max page size of GCS list is 1000.
+                //
+                // ref: <https://cloud.google.com/storage/docs/json_api/v1/objects/list>
+                list_max_page_size: Some(1000),
                 list_with_start_after: true,
                 list_without_recursive: true,
                 list_with_recursive: true,
+                list_with_delimiter: true,
 
                 batch: true,
                 batch_max_operations: Some(100),
@@ -368,7 +461,11 @@ impl Accessor for GcsBackend {
 
         if resp.status().is_success() {
             let size = parse_content_length(resp.headers())?;
-            Ok((RpRead::new().with_size(size), resp.into_body()))
+            let etag = parse_etag(resp.headers())?.map(|v| v.to_string());
+            Ok((
+                RpRead::new().with_size(size).with_etag(etag),
+                resp.into_body(),
+            ))
         } else if resp.status() == StatusCode::RANGE_NOT_SATISFIABLE {
             Ok((RpRead::new(), IncomingAsyncBody::empty()))
         } else {
@@ -394,7 +491,32 @@ impl Accessor for GcsBackend {
         }
     }
 
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        self.core.gcs_compose(args.sources(), path).await?;
+
+        Ok(RpConcat::default())
+    }
+
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if path == "/" {
+            let resp = self.core.gcs_get_bucket().await?;
+            if !resp.status().is_success() {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let bucket: GetBucketJsonResponse =
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+            let mut meta = Metadata::new(EntryMode::DIR);
+            // GCS returns location in upper case like `EUROPE-WEST1`, while
+            // regions are in lower case everywhere else.
+            if !bucket.location.is_empty() {
+                meta.set_region(&bucket.location.to_lowercase());
+            }
+            return Ok(RpStat::new(meta));
+        }
+
         let resp = self.core.gcs_get_object_metadata(path, &args).await?;
 
         if !resp.status().is_success() {
@@ -441,6 +563,7 @@ impl Accessor for GcsBackend {
             self.core.clone(),
             path,
             args.recursive(),
+            args.delimiter(),
             args.limit(),
             args.start_after(),
         );
@@ -531,6 +654,15 @@ impl Accessor for GcsBackend {
     }
 }
 
+/// The raw json response returned by [`get`](https://cloud.google.com/storage/docs/json_api/v1/buckets/get)
+/// of bucket.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct GetBucketJsonResponse {
+    /// For example: `"location": "EUROPE-WEST1"`
+    location: String,
+}
+
 /// The raw json response returned by [`get`](https://cloud.google.com/storage/docs/json_api/v1/objects/get)
 #[derive(Debug, Default, Deserialize)]
 #[serde(default, rename_all = "camelCase")]
@@ -559,6 +691,8 @@ struct GetObjectJsonResponse {
 
 #[cfg(test)]
 mod tests {
+    use reqsign::GoogleToken;
+
     use super::*;
 
     #[test]
@@ -592,4 +726,117 @@ mod tests {
         assert_eq!(meta.etag, "CKWasoTgyPkCEAE=");
         assert_eq!(meta.content_type, "image/png");
     }
+
+    #[derive(Debug)]
+    struct StaticTokenLoader;
+
+    #[async_trait]
+    impl GoogleTokenLoad for StaticTokenLoader {
+        async fn load(&self, _: reqwest::Client) -> anyhow::Result<Option<GoogleToken>> {
+            Ok(Some(GoogleToken::new("token", 3600, DEFAULT_GCS_SCOPE)))
+        }
+    }
+
+    fn new_mock_operator(server: &wiremock::MockServer) -> Operator {
+        let mut builder = GcsBuilder::default();
+        builder
+            .endpoint(&server.uri())
+            .bucket("bucket")
+            .customed_token_loader(Box::new(StaticTokenLoader));
+        Operator::new(builder).unwrap().finish()
+    }
+
+    #[tokio::test]
+    async fn test_concat_composes_components() -> Result<()> {
+        use wiremock::matchers::body_string_contains;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::path_regex;
+        use wiremock::Mock;
+        use wiremock::ResponseTemplate;
+
+        let server = wiremock::MockServer::start().await;
+        // 33 sources are composed into 2 components first.
+        Mock::given(method("POST"))
+            .and(path_regex(
+                r"^/storage/v1/b/bucket/o/\.opendal-compose/[0-9a-f-]{36}/compose$",
+            ))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/storage/v1/b/bucket/o/merged/compose"))
+            .and(body_string_contains(COMPOSE_COMPONENT_DIR))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path_regex(
+                r"^/storage/v1/b/bucket/o/\.opendal-compose/[0-9a-f-]{36}$",
+            ))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        let sources: Vec<String> = (0..33).map(|i| format!("part-{i}")).collect();
+        let sources: Vec<&str> = sources.iter().map(String::as_str).collect();
+        op.concat("merged", &sources).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_hides_compose_components() -> Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::ResponseTemplate;
+
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/storage/v1/b/bucket/o"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+                    "prefixes": [".opendal-compose/", "dir/"],
+                    "items": [
+                        {"name": "file", "size": "1", "updated": "2022-08-15T11:33:34.866Z"}
+                    ]
+                }"#,
+            ))
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        let entries = op.list("/").await?;
+        let paths: Vec<&str> = entries.iter().map(|e| e.path()).collect();
+        assert_eq!(paths, vec!["dir/", "file"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_location() -> Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::ResponseTemplate;
+
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/storage/v1/b/bucket"))
+            .and(query_param("fields", "location"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(r#"{"location": "EUROPE-WEST1"}"#),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        assert_eq!(op.location().await?.as_deref(), Some("europe-west1"));
+        Ok(())
+    }
 }
@@ -21,8 +21,14 @@ use std::fmt::Formatter;
 use std::fmt::Write;
 use std::time::Duration;
 
+use async_trait::async_trait;
 use backon::ExponentialBuilder;
 use backon::Retryable;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use chrono::Utc;
+use flagset::FlagSet;
+use futures::future::try_join_all;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_RANGE;
 use http::header::CONTENT_TYPE;
@@ -31,6 +37,8 @@ use http::header::IF_MATCH;
 use http::header::IF_NONE_MATCH;
 use http::Request;
 use http::Response;
+use http::StatusCode;
+use log::warn;
 use once_cell::sync::Lazy;
 use reqsign::GoogleCredential;
 use reqsign::GoogleCredentialLoader;
@@ -40,10 +48,20 @@ use reqsign::GoogleTokenLoader;
 use serde::Deserialize;
 use serde_json::json;
 
+use super::error::parse_error;
 use super::uri::percent_encode_path;
 use crate::raw::*;
 use crate::*;
 
+/// The max sources of GCS compose.
+///
+/// ref: <https://cloud.google.com/storage/docs/composing-objects>
+pub const MAX_COMPOSE_SOURCES: usize = 32;
+
+/// Temporary components of compose are written under this dir of root, and
+/// are hidden from listings.
+pub const COMPOSE_COMPONENT_DIR: &str = ".opendal-compose/";
+
 pub struct GcsCore {
     pub endpoint: String,
     pub bucket: String,
@@ -56,6 +74,7 @@ pub struct GcsCore {
 
     pub predefined_acl: Option<String>,
     pub default_storage_class: Option<String>,
+    pub delegate_service_account: Option<String>,
 }
 
 impl Debug for GcsCore {
@@ -71,6 +90,27 @@ impl Debug for GcsCore {
 static BACKOFF: Lazy<ExponentialBuilder> =
     Lazy::new(|| ExponentialBuilder::default().with_jitter());
 
+/// GcsCore signs requests with OAuth token and V4 signing, see [`RequestSign`].
+#[async_trait]
+impl RequestSign for GcsCore {
+    async fn sign(&self, req: &mut Request<AsyncBody>) -> Result<()> {
+        GcsCore::sign(self, req).await
+    }
+
+    async fn sign_query(&self, req: &mut Request<AsyncBody>, expire: Duration) -> Result<()> {
+        GcsCore::sign_query(self, req, expire).await
+    }
+
+    async fn delegate(
+        &self,
+        path: &str,
+        scope: FlagSet<DelegateScope>,
+        expire: Duration,
+    ) -> Result<DelegatedCredential> {
+        self.gcs_delegate(path, scope, expire).await
+    }
+}
+
 impl GcsCore {
     async fn load_token(&self) -> Result<GoogleToken> {
         let cred = { || self.token_loader.load() }
@@ -249,6 +289,10 @@ impl GcsCore {
             write!(&mut url, "&predefinedAcl={}", acl).unwrap();
         }
 
+        if let Some(v) = gcs_if_generation_match(op)? {
+            write!(&mut url, "&ifGenerationMatch={}", v).unwrap();
+        }
+
         let mut req = Request::post(&url);
 
         req = req.header(CONTENT_LENGTH, size.unwrap_or_default());
@@ -399,6 +443,23 @@ impl GcsCore {
         self.send(req).await
     }
 
+    /// Get metadata of the bucket, only `location` is fetched.
+    ///
+    /// ref: <https://cloud.google.com/storage/docs/json_api/v1/buckets/get>
+    pub async fn gcs_get_bucket(&self) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!(
+            "{}/storage/v1/b/{}?fields=location",
+            self.endpoint, self.bucket
+        );
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
     pub async fn gcs_delete_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
         let mut req = self.gcs_delete_object_request(path)?;
 
@@ -470,6 +531,117 @@ impl GcsCore {
         self.send(req).await
     }
 
+    /// Compose sources into `to` in order.
+    ///
+    /// GCS allows at most [`MAX_COMPOSE_SOURCES`] sources in one compose,
+    /// more sources will be stitched recursively: every batch is composed
+    /// into a temporary component object under [`COMPOSE_COMPONENT_DIR`]
+    /// concurrently, and components are composed again until they fit in
+    /// one request. Temporary components will be removed after compose no
+    /// matter it succeeds or not.
+    ///
+    /// Components left by a crashed process can be collected by a lifecycle
+    /// rule matching the prefix of [`COMPOSE_COMPONENT_DIR`].
+    pub async fn gcs_compose(&self, sources: &[String], to: &str) -> Result<()> {
+        let mut components = Vec::new();
+        let res = self.gcs_compose_inner(sources, to, &mut components).await;
+
+        for path in components {
+            let deleted = match self.gcs_delete_object(&path).await {
+                Ok(resp)
+                    if resp.status().is_success() || resp.status() == StatusCode::NOT_FOUND =>
+                {
+                    resp.into_body().consume().await
+                }
+                Ok(resp) => parse_error(resp).await.and_then(Err),
+                Err(err) => Err(err),
+            };
+            if let Err(err) = deleted {
+                warn!("gcs remove temporary compose component {path} failed: {err}");
+            }
+        }
+
+        res
+    }
+
+    async fn gcs_compose_inner(
+        &self,
+        sources: &[String],
+        to: &str,
+        components: &mut Vec<String>,
+    ) -> Result<()> {
+        let mut sources = sources.to_vec();
+        while sources.len() > MAX_COMPOSE_SOURCES {
+            let level: Vec<String> = sources
+                .chunks(MAX_COMPOSE_SOURCES)
+                .map(|_| format!("{COMPOSE_COMPONENT_DIR}{}", uuid::Uuid::new_v4()))
+                .collect();
+            components.extend(level.iter().cloned());
+
+            try_join_all(
+                sources
+                    .chunks(MAX_COMPOSE_SOURCES)
+                    .zip(level.iter())
+                    .map(|(batch, component)| self.gcs_compose_once(batch, component)),
+            )
+            .await?;
+
+            sources = level;
+        }
+
+        self.gcs_compose_once(&sources, to).await
+    }
+
+    async fn gcs_compose_once(&self, sources: &[String], to: &str) -> Result<()> {
+        let resp = self.gcs_compose_object(sources, to).await?;
+        if resp.status().is_success() {
+            resp.into_body().consume().await
+        } else {
+            Err(parse_error(resp).await?)
+        }
+    }
+
+    /// Compose given sources in order into `to`, sources are paths
+    /// relative to root.
+    ///
+    /// GCS allows at most [`MAX_COMPOSE_SOURCES`] sources in one request,
+    /// and `to` itself could be one of the sources.
+    ///
+    /// ref: <https://cloud.google.com/storage/docs/json_api/v1/objects/compose>
+    pub async fn gcs_compose_object(
+        &self,
+        sources: &[String],
+        to: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let dest = build_abs_path(&self.root, to);
+
+        let req_uri = format!(
+            "{}/storage/v1/b/{}/o/{}/compose",
+            self.endpoint,
+            self.bucket,
+            percent_encode_path(&dest)
+        );
+
+        let source_objects: Vec<_> = sources
+            .iter()
+            .map(|v| json!({ "name": build_abs_path(&self.root, v) }))
+            .collect();
+        let content = json!({
+            "sourceObjects": source_objects,
+            "destination": {},
+        })
+        .to_string();
+
+        let mut req = Request::post(req_uri)
+            .header(CONTENT_TYPE, "application/json")
+            .header(CONTENT_LENGTH, content.len())
+            .body(AsyncBody::Bytes(bytes::Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
     pub async fn gcs_list_objects(
         &self,
         path: &str,
@@ -487,7 +659,8 @@ impl GcsCore {
             percent_encode_path(&p)
         );
         if !delimiter.is_empty() {
-            write!(url, "&delimiter={delimiter}").expect("write into string must succeed");
+            write!(url, "&delimiter={}", percent_encode_path(delimiter))
+                .expect("write into string must succeed");
         }
         if let Some(limit) = limit {
             write!(url, "&maxResults={limit}").expect("write into string must succeed");
@@ -509,7 +682,7 @@ impl GcsCore {
                 .expect("write into string must succeed");
         }
 
-        let mut req = Request::get(&url)
+        let mut req = with_accept_encoding(Request::get(&url))
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
@@ -521,13 +694,18 @@ impl GcsCore {
     pub async fn gcs_initiate_resumable_upload(
         &self,
         path: &str,
+        op: &OpWrite,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
-        let url = format!(
+        let mut url = format!(
             "{}/upload/storage/v1/b/{}/o?uploadType=resumable&name={}",
             self.endpoint, self.bucket, p
         );
 
+        if let Some(v) = gcs_if_generation_match(op)? {
+            write!(&mut url, "&ifGenerationMatch={}", v).unwrap();
+        }
+
         let mut req = Request::post(&url)
             .header(CONTENT_LENGTH, 0)
             .body(AsyncBody::Empty)
@@ -597,6 +775,252 @@ impl GcsCore {
 
         self.send(req).await
     }
+
+    /// Mint a signed policy document that allows uploading objects to the
+    /// path via HTML form posts.
+    ///
+    /// The policy is signed by `delegate_service_account` via IAM `signBlob`.
+    ///
+    /// ref: <https://cloud.google.com/storage/docs/authentication/signatures#policy-document>
+    pub async fn gcs_delegate(
+        &self,
+        path: &str,
+        scope: FlagSet<DelegateScope>,
+        expire: Duration,
+    ) -> Result<DelegatedCredential> {
+        // Policy document is preferred for uploading since clients could
+        // post forms without any SDK, other scopes are downscoped tokens.
+        let email = match &self.delegate_service_account {
+            Some(email) if scope == FlagSet::from(DelegateScope::Write) => email,
+            _ => return self.gcs_downscope(path, scope).await,
+        };
+
+        let now = Utc::now();
+        let expires_at = now
+            + chrono::Duration::from_std(expire).map_err(|err| {
+                Error::new(ErrorKind::InvalidInput, "expire is out of range").set_source(err)
+            })?;
+        let date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let credential = format!(
+            "{email}/{}/auto/storage/goog4_request",
+            now.format("%Y%m%d")
+        );
+
+        // Paths end with `/` are prefixes, clients can upload any objects under them.
+        let (key, key_condition) = if path.is_empty() || path.ends_with('/') {
+            (
+                format!("{path}${{filename}}"),
+                json!(["starts-with", "$key", path]),
+            )
+        } else {
+            (path.to_string(), json!({ "key": path }))
+        };
+        let policy = json!({
+            "conditions": [
+                { "bucket": self.bucket },
+                key_condition,
+                { "x-goog-algorithm": "GOOG4-RSA-SHA256" },
+                { "x-goog-credential": credential },
+                { "x-goog-date": date },
+            ],
+            "expiration": expires_at.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        });
+        let policy = BASE64_STANDARD.encode(policy.to_string());
+
+        let signature = self.gcs_sign_blob(email, policy.as_bytes()).await?;
+        let signature = signature
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+
+        Ok(DelegatedCredential::GcsPolicy {
+            url: format!("{}/{}", self.endpoint, self.bucket),
+            fields: vec![
+                ("key".to_string(), key),
+                ("policy".to_string(), policy),
+                (
+                    "x-goog-algorithm".to_string(),
+                    "GOOG4-RSA-SHA256".to_string(),
+                ),
+                ("x-goog-credential".to_string(), credential),
+                ("x-goog-date".to_string(), date),
+                ("x-goog-signature".to_string(), signature),
+            ],
+            expires_at,
+        })
+    }
+
+    /// Exchange our token for a downscoped one by credential access boundary.
+    ///
+    /// The downscoped token shares the lifetime of our token, which can't
+    /// be extended or shortened.
+    ///
+    /// ref: <https://cloud.google.com/iam/docs/downscoping-short-lived-credentials>
+    async fn gcs_downscope(
+        &self,
+        path: &str,
+        scope: FlagSet<DelegateScope>,
+    ) -> Result<DelegatedCredential> {
+        let boundary = build_access_boundary(&self.bucket, path, scope)?;
+        let token = self.load_token().await?;
+
+        let content = [
+            (
+                "grant_type",
+                "urn:ietf:params:oauth:grant-type:token-exchange",
+            ),
+            (
+                "subject_token_type",
+                "urn:ietf:params:oauth:token-type:access_token",
+            ),
+            (
+                "requested_token_type",
+                "urn:ietf:params:oauth:token-type:access_token",
+            ),
+            ("subject_token", token.access_token()),
+            ("options", boundary.as_str()),
+        ]
+        .iter()
+        .map(|(k, v)| format!("{k}={}", percent_encode_path(v)))
+        .collect::<Vec<_>>()
+        .join("&");
+        let req = Request::post(GCS_STS_ENDPOINT)
+            .header(CONTENT_LENGTH, content.len())
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(AsyncBody::Bytes(bytes::Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        let now = Utc::now();
+        let resp = self.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let out: TokenExchangeResponse =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+        Ok(DelegatedCredential::GcsToken {
+            access_token: out.access_token,
+            expires_at: now + chrono::Duration::seconds(out.expires_in),
+        })
+    }
+
+    /// Sign bytes with the private key of given service account by IAM.
+    ///
+    /// ref: <https://cloud.google.com/iam/docs/reference/credentials/rest/v1/projects.serviceAccounts/signBlob>
+    async fn gcs_sign_blob(&self, email: &str, payload: &[u8]) -> Result<Vec<u8>> {
+        let url = format!(
+            "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts/{}:signBlob",
+            percent_encode_path(email)
+        );
+
+        let content = json!({ "payload": BASE64_STANDARD.encode(payload) }).to_string();
+        let mut req = Request::post(&url)
+            .header(CONTENT_LENGTH, content.len())
+            .header(CONTENT_TYPE, "application/json")
+            .body(AsyncBody::Bytes(bytes::Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        let resp = self.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let out: SignBlobResponse =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+        BASE64_STANDARD.decode(out.signed_blob).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "signed blob is not valid base64").set_source(err)
+        })
+    }
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct SignBlobResponse {
+    signed_blob: String,
+}
+
+const GCS_STS_ENDPOINT: &str = "https://sts.googleapis.com/v1/token";
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct TokenExchangeResponse {
+    access_token: String,
+    expires_in: i64,
+}
+
+/// Build the credential access boundary that only allows given scope on
+/// the path.
+///
+/// Boundaries can only refer to predefined roles, so `List` allows reading
+/// too and `Delete` allows reading and writing too.
+fn build_access_boundary(
+    bucket: &str,
+    path: &str,
+    scope: FlagSet<DelegateScope>,
+) -> Result<String> {
+    let mut roles = vec![];
+    if scope.contains(DelegateScope::Delete) {
+        roles.push("inRole:roles/storage.objectUser");
+    } else {
+        if scope.intersects(DelegateScope::Read | DelegateScope::List) {
+            roles.push("inRole:roles/storage.objectViewer");
+        }
+        if scope.contains(DelegateScope::Write) {
+            roles.push("inRole:roles/storage.objectCreator");
+        }
+    }
+    if roles.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "scope of delegated credential is empty",
+        ));
+    }
+
+    // Literals in CEL must be escaped.
+    let literal = path.replace('\\', "\\\\").replace('\'', "\\'");
+    // Paths end with `/` are prefixes.
+    let object_cond = if path.is_empty() || path.ends_with('/') {
+        format!("resource.name.startsWith('projects/_/buckets/{bucket}/objects/{literal}')")
+    } else {
+        format!("resource.name == 'projects/_/buckets/{bucket}/objects/{literal}'")
+    };
+    let expression = if scope.contains(DelegateScope::List) {
+        format!(
+            "{object_cond} || api.getAttribute('storage.googleapis.com/objectListPrefix', '').startsWith('{literal}')"
+        )
+    } else {
+        object_cond
+    };
+
+    Ok(json!({
+        "accessBoundary": {
+            "accessBoundaryRules": [{
+                "availablePermissions": roles,
+                "availableResource": format!("//storage.googleapis.com/projects/_/buckets/{bucket}"),
+                "availabilityCondition": { "expression": expression },
+            }],
+        },
+    })
+    .to_string())
+}
+
+/// GCS uses generation instead of etag for conditional writes, only
+/// `If-None-Match: *` could be mapped to `ifGenerationMatch=0`.
+///
+/// refer to https://cloud.google.com/storage/docs/request-preconditions for details
+fn gcs_if_generation_match(op: &OpWrite) -> Result<Option<&'static str>> {
+    match op.if_none_match() {
+        None => Ok(None),
+        Some("*") => Ok(Some("0")),
+        Some(v) => Err(Error::new(
+            ErrorKind::Unsupported,
+            "gcs only supports write with if none match `*`",
+        )
+        .with_context("if_none_match", v)),
+    }
 }
 
 /// Response JSON from GCS list objects API.
@@ -771,4 +1195,35 @@ mod tests {
         assert_eq!(output.items[1].updated, "2022-08-15T11:33:34.886Z");
         assert_eq!(output.prefixes, vec!["dir/", "test/"])
     }
+
+    #[test]
+    fn test_build_access_boundary() {
+        let boundary = build_access_boundary(
+            "example",
+            "tenant-'a/",
+            DelegateScope::Read | DelegateScope::List,
+        )
+        .expect("must succeed");
+        let boundary: serde_json::Value = serde_json::from_str(&boundary).expect("must be valid");
+
+        assert_eq!(
+            boundary,
+            json!({
+                "accessBoundary": {
+                    "accessBoundaryRules": [{
+                        "availablePermissions": ["inRole:roles/storage.objectViewer"],
+                        "availableResource": "//storage.googleapis.com/projects/_/buckets/example",
+                        "availabilityCondition": {
+                            "expression": "resource.name.startsWith('projects/_/buckets/example/objects/tenant-\\'a/') \
+                                || api.getAttribute('storage.googleapis.com/objectListPrefix', '').startsWith('tenant-\\'a/')",
+                        },
+                    }],
+                },
+            })
+        );
+
+        let err = build_access_boundary("example", "a", FlagSet::default())
+            .expect_err("empty scope must be rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
 }
@@ -31,7 +31,7 @@ pub struct GcsLister {
     core: Arc<GcsCore>,
 
     path: String,
-    delimiter: &'static str,
+    delimiter: String,
     limit: Option<usize>,
 
     /// Filter results to objects whose names are lexicographically
@@ -45,10 +45,13 @@ impl GcsLister {
         core: Arc<GcsCore>,
         path: &str,
         recursive: bool,
+        delimiter: Option<&str>,
         limit: Option<usize>,
         start_after: Option<&str>,
     ) -> Self {
-        let delimiter = if recursive { "" } else { "/" };
+        let delimiter = delimiter
+            .unwrap_or(if recursive { "" } else { "/" })
+            .to_string();
         Self {
             core,
 
@@ -60,6 +63,14 @@ impl GcsLister {
     }
 }
 
+impl GcsLister {
+    /// Temporary components of compose are hidden unless users are listing
+    /// them explicitly.
+    fn is_compose_component(&self, path: &str) -> bool {
+        path.starts_with(COMPOSE_COMPONENT_DIR) && !self.path.starts_with(COMPOSE_COMPONENT_DIR)
+    }
+}
+
 #[async_trait]
 impl oio::PageList for GcsLister {
     async fn next_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
@@ -68,7 +79,7 @@ impl oio::PageList for GcsLister {
             .gcs_list_objects(
                 &self.path,
                 &ctx.token,
-                self.delimiter,
+                &self.delimiter,
                 self.limit,
                 if ctx.token.is_empty() {
                     self.start_after.clone()
@@ -93,10 +104,14 @@ impl oio::PageList for GcsLister {
         }
 
         for prefix in output.prefixes {
-            let de = oio::Entry::new(
-                &build_rel_path(&self.core.root, &prefix),
-                Metadata::new(EntryMode::DIR),
-            );
+            let path = build_rel_path(&self.core.root, &prefix);
+            if self.is_compose_component(&path) {
+                continue;
+            }
+
+            // Prefixes grouped by custom delimiter don't end with `/`, and
+            // can't be listed as dirs.
+            let de = oio::Entry::new(&path, Metadata::new(EntryMode::from_path(&prefix)));
 
             ctx.entries.push_back(de);
         }
@@ -110,6 +125,9 @@ impl oio::PageList for GcsLister {
             if self.start_after.as_ref() == Some(&path) {
                 continue;
             }
+            if self.is_compose_component(&path) {
+                continue;
+            }
 
             let mut meta = Metadata::new(EntryMode::from_path(&path));
 
@@ -69,7 +69,10 @@ impl oio::RangeWrite for GcsWriter {
     }
 
     async fn initiate_range(&self) -> Result<String> {
-        let resp = self.core.gcs_initiate_resumable_upload(&self.path).await?;
+        let resp = self
+            .core
+            .gcs_initiate_resumable_upload(&self.path, &self.op)
+            .await?;
         let status = resp.status();
 
         match status {
@@ -59,6 +59,7 @@ impl Accessor for GdriveBackend {
 
                 list: true,
                 list_without_recursive: true,
+                list_with_modified_since: true,
 
                 write: true,
 
@@ -203,8 +204,8 @@ impl Accessor for GdriveBackend {
         }
     }
 
-    async fn list(&self, path: &str, _args: OpList) -> Result<(RpList, Self::Lister)> {
-        let l = GdriveLister::new(path.into(), self.core.clone());
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let l = GdriveLister::new(path.into(), self.core.clone(), args.modified_since());
         Ok((RpList::default(), oio::PageLister::new(l)))
     }
 
@@ -311,14 +311,13 @@ impl GdriveCore {
         path: &str,
         page_size: i32,
         next_page_token: &str,
+        modified_since: Option<DateTime<Utc>>,
     ) -> Result<Response<IncomingAsyncBody>> {
         let file_id = self.get_file_id_by_path(path).await;
 
         // when list over a no exist dir, `get_file_id_by_path` will return a NotFound Error, we should return a empty list in this case.
         let q = match file_id {
-            Ok(file_id) => {
-                format!("'{}' in parents and trashed = false", file_id)
-            }
+            Ok(file_id) => format_list_query(&file_id, modified_since),
             Err(e) => match e.kind() {
                 ErrorKind::NotFound => {
                     return Response::builder()
@@ -591,3 +590,42 @@ pub(crate) struct GdriveFileList {
     pub(crate) files: Vec<GdriveFile>,
     pub(crate) next_page_token: Option<String>,
 }
+
+/// Build the query of files in given dir.
+///
+/// Files not modified since `modified_since` are filtered by gdrive, dirs
+/// are always kept so that they can still be walked.
+///
+/// ref: <https://developers.google.com/drive/api/guides/search-files>
+fn format_list_query(file_id: &str, modified_since: Option<DateTime<Utc>>) -> String {
+    let mut q = format!("'{}' in parents and trashed = false", file_id);
+    if let Some(t) = modified_since {
+        q.push_str(&format!(
+            " and (mimeType = 'application/vnd.google-apps.folder' or modifiedTime >= '{}')",
+            t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        ));
+    }
+    q
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_list_query() {
+        assert_eq!(
+            format_list_query("dir", None),
+            "'dir' in parents and trashed = false"
+        );
+
+        let t = DateTime::parse_from_rfc3339("2023-01-01T08:00:00+08:00")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            format_list_query("dir", Some(t)),
+            "'dir' in parents and trashed = false and \
+             (mimeType = 'application/vnd.google-apps.folder' or modifiedTime >= '2023-01-01T00:00:00Z')"
+        );
+    }
+}
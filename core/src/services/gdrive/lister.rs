@@ -18,6 +18,8 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::DateTime;
+use chrono::Utc;
 use http::StatusCode;
 
 use super::core::GdriveCore;
@@ -29,18 +31,26 @@ use crate::*;
 pub struct GdriveLister {
     path: String,
     core: Arc<GdriveCore>,
+    modified_since: Option<DateTime<Utc>>,
 }
 
 impl GdriveLister {
-    pub fn new(path: String, core: Arc<GdriveCore>) -> Self {
-        Self { path, core }
+    pub fn new(path: String, core: Arc<GdriveCore>, modified_since: Option<DateTime<Utc>>) -> Self {
+        Self {
+            path,
+            core,
+            modified_since,
+        }
     }
 }
 
 #[async_trait]
 impl oio::PageList for GdriveLister {
     async fn next_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
-        let resp = self.core.gdrive_list(&self.path, 100, &ctx.token).await?;
+        let resp = self
+            .core
+            .gdrive_list(&self.path, 100, &ctx.token, self.modified_since)
+            .await?;
 
         let bytes = match resp.status() {
             StatusCode::OK => resp.into_body().bytes().await?,
@@ -18,6 +18,7 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use http::header;
@@ -43,11 +44,18 @@ pub struct HttpConfig {
     /// username of this backend
     pub username: Option<String>,
     /// password of this backend
-    pub password: Option<String>,
+    pub password: Option<Secret>,
     /// token of this backend
-    pub token: Option<String>,
+    pub token: Option<Secret>,
     /// root of this backend
     pub root: Option<String>,
+    /// Split large reads into sub-ranges of given size, see
+    /// [`HttpBuilder::read_chunk_size`].
+    pub read_chunk_size: Option<usize>,
+    /// The first byte deadline of every sub-range in milliseconds.
+    pub read_first_byte_timeout_ms: Option<u64>,
+    /// The retry times of every sub-range.
+    pub read_max_retries: Option<usize>,
 }
 
 impl Debug for HttpConfig {
@@ -55,6 +63,12 @@ impl Debug for HttpConfig {
         let mut de = f.debug_struct("HttpConfig");
         de.field("endpoint", &self.endpoint);
         de.field("root", &self.root);
+        de.field("read_chunk_size", &self.read_chunk_size);
+        de.field(
+            "read_first_byte_timeout_ms",
+            &self.read_first_byte_timeout_ms,
+        );
+        de.field("read_max_retries", &self.read_max_retries);
 
         de.finish_non_exhaustive()
     }
@@ -105,7 +119,7 @@ impl HttpBuilder {
     /// default: no password
     pub fn password(&mut self, password: &str) -> &mut Self {
         if !password.is_empty() {
-            self.config.password = Some(password.to_owned());
+            self.config.password = Some(password.into());
         }
         self
     }
@@ -115,7 +129,7 @@ impl HttpBuilder {
     /// default: no access token
     pub fn token(&mut self, token: &str) -> &mut Self {
         if !token.is_empty() {
-            self.config.token = Some(token.to_owned());
+            self.config.token = Some(token.into());
         }
         self
     }
@@ -131,6 +145,39 @@ impl HttpBuilder {
         self
     }
 
+    /// Split large reads into sub-ranges of given size and stitch them in
+    /// order, every sub-range will be retried independently.
+    ///
+    /// This is useful for flaky CDNs that stall or drop long transfers. The
+    /// server must support range requests, and sub-ranges are pinned to the
+    /// `ETag` returned by `HEAD`. Reads fall back to a single request if the
+    /// server doesn't return `Content-Length`.
+    ///
+    /// Default to disabled.
+    pub fn read_chunk_size(&mut self, size: usize) -> &mut Self {
+        if size > 0 {
+            self.config.read_chunk_size = Some(size);
+        }
+        self
+    }
+
+    /// Set the deadline of receiving the first byte of every sub-range,
+    /// stalled sub-ranges will be retried.
+    ///
+    /// Only take effect while `read_chunk_size` is set.
+    pub fn read_first_byte_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.config.read_first_byte_timeout_ms = Some(timeout.as_millis() as u64);
+        self
+    }
+
+    /// Set the retry times of every sub-range.
+    ///
+    /// Only take effect while `read_chunk_size` is set. Default to 3.
+    pub fn read_max_retries(&mut self, retries: usize) -> &mut Self {
+        self.config.read_max_retries = Some(retries);
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -171,6 +218,14 @@ impl Builder for HttpBuilder {
         let root = normalize_root(&self.config.root.take().unwrap_or_default());
         debug!("backend use root {}", root);
 
+        if self.config.read_chunk_size == Some(0) {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "read_chunk_size must be positive")
+                    .with_operation("Builder::build")
+                    .with_context("service", Scheme::Http),
+            );
+        }
+
         let client = if let Some(client) = self.http_client.take() {
             client
         } else {
@@ -184,11 +239,15 @@ impl Builder for HttpBuilder {
         if let Some(username) = &self.config.username {
             auth = Some(format_authorization_by_basic(
                 username,
-                self.config.password.as_deref().unwrap_or_default(),
+                self.config
+                    .password
+                    .as_ref()
+                    .map(|v| v.expose_secret().as_str())
+                    .unwrap_or_default(),
             )?);
         }
         if let Some(token) = &self.config.token {
-            auth = Some(format_authorization_by_bearer(token)?)
+            auth = Some(format_authorization_by_bearer(token.expose_secret())?)
         }
 
         debug!("backend build finished: {:?}", &self);
@@ -197,6 +256,12 @@ impl Builder for HttpBuilder {
             authorization: auth,
             root,
             client,
+            read_chunk_size: self.config.read_chunk_size,
+            read_first_byte_timeout: self
+                .config
+                .read_first_byte_timeout_ms
+                .map(Duration::from_millis),
+            read_max_retries: self.config.read_max_retries,
         })
     }
 }
@@ -209,6 +274,10 @@ pub struct HttpBackend {
     client: HttpClient,
 
     authorization: Option<String>,
+
+    read_chunk_size: Option<usize>,
+    read_first_byte_timeout: Option<Duration>,
+    read_max_retries: Option<usize>,
 }
 
 impl Debug for HttpBackend {
@@ -217,13 +286,14 @@ impl Debug for HttpBackend {
             .field("endpoint", &self.endpoint)
             .field("root", &self.root)
             .field("client", &self.client)
+            .field("read_chunk_size", &self.read_chunk_size)
             .finish()
     }
 }
 
 #[async_trait]
 impl Accessor for HttpBackend {
-    type Reader = IncomingAsyncBody;
+    type Reader = oio::TwoWaysReader<IncomingAsyncBody, oio::StitchReader<HttpRangeRead>>;
     type BlockingReader = ();
     type Writer = ();
     type BlockingWriter = ();
@@ -252,6 +322,12 @@ impl Accessor for HttpBackend {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        if let Some(chunk_size) = self.read_chunk_size {
+            if let Some(res) = self.stitch_read(path, &args, chunk_size).await? {
+                return Ok(res);
+            }
+        }
+
         let resp = self.http_get(path, &args).await?;
 
         let status = resp.status();
@@ -259,9 +335,15 @@ impl Accessor for HttpBackend {
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
                 let size = parse_content_length(resp.headers())?;
-                Ok((RpRead::new().with_size(size), resp.into_body()))
+                Ok((
+                    RpRead::new().with_size(size),
+                    oio::TwoWaysReader::One(resp.into_body()),
+                ))
             }
-            StatusCode::RANGE_NOT_SATISFIABLE => Ok((RpRead::new(), IncomingAsyncBody::empty())),
+            StatusCode::RANGE_NOT_SATISFIABLE => Ok((
+                RpRead::new(),
+                oio::TwoWaysReader::One(IncomingAsyncBody::empty()),
+            )),
             _ => Err(parse_error(resp).await?),
         }
     }
@@ -289,6 +371,64 @@ impl Accessor for HttpBackend {
 }
 
 impl HttpBackend {
+    /// Read the range by sub-ranges, the total size will be fetched by `HEAD`
+    /// first so that we know where to stop.
+    ///
+    /// Sub-ranges are pinned to the `ETag` returned by `HEAD` via `If-Match`
+    /// so that they can't mix different versions of the object. Returns
+    /// `None` if the server doesn't return `Content-Length`, the caller
+    /// should fall back to a plain read.
+    async fn stitch_read(
+        &self,
+        path: &str,
+        args: &OpRead,
+        chunk_size: usize,
+    ) -> Result<Option<(RpRead, <Self as Accessor>::Reader)>> {
+        let mut op = OpStat::new();
+        if let Some(v) = args.if_match() {
+            op = op.with_if_match(v);
+        }
+        if let Some(v) = args.if_none_match() {
+            op = op.with_if_none_match(v);
+        }
+        let resp = self.http_head(path, &op).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+        let Some(total_size) = parse_content_length(resp.headers())? else {
+            return Ok(None);
+        };
+        let mut args = args.clone();
+        if args.if_match().is_none() {
+            if let Some(etag) = parse_etag(resp.headers())? {
+                args = args.with_if_match(etag);
+            }
+        }
+
+        let range = args.range();
+        let mut r = oio::StitchReader::new(
+            HttpRangeRead {
+                backend: self.clone(),
+                path: path.to_string(),
+                args,
+            },
+            range,
+            total_size,
+        )
+        .with_chunk_size(chunk_size);
+        if let Some(v) = self.read_max_retries {
+            r = r.with_max_retries(v);
+        }
+        if let Some(v) = self.read_first_byte_timeout {
+            r = r.with_first_byte_timeout(v);
+        }
+
+        Ok(Some((
+            RpRead::new().with_size(Some(r.size())),
+            oio::TwoWaysReader::Two(r),
+        )))
+    }
+
     async fn http_get(&self, path: &str, args: &OpRead) -> Result<Response<IncomingAsyncBody>> {
         let p = build_rooted_abs_path(&self.root, path);
 
@@ -346,11 +486,38 @@ impl HttpBackend {
     }
 }
 
+/// HttpRangeRead sends sub-range requests for [`oio::StitchReader`].
+pub struct HttpRangeRead {
+    backend: HttpBackend,
+    path: String,
+    args: OpRead,
+}
+
+#[async_trait]
+impl oio::StitchRead for HttpRangeRead {
+    async fn read_range(&self, range: BytesRange) -> Result<IncomingAsyncBody> {
+        let args = self.args.clone().with_range(range);
+        let resp = self.backend.http_get(&self.path, &args).await?;
+
+        match resp.status() {
+            StatusCode::PARTIAL_CONTENT => Ok(resp.into_body()),
+            // Server ignores our range header, we can't stitch the content.
+            StatusCode::OK => Err(Error::new(
+                ErrorKind::Unsupported,
+                "server doesn't support range request",
+            )
+            .with_context("path", &self.path)),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
     use wiremock::matchers::basic_auth;
     use wiremock::matchers::bearer_token;
+    use wiremock::matchers::header;
     use wiremock::matchers::headers;
     use wiremock::matchers::method;
     use wiremock::matchers::path;
@@ -464,6 +631,120 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_read_with_chunk_size() -> Result<()> {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/hello"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-length", "13"))
+            .mount(&mock_server)
+            .await;
+        for (range, body) in [
+            ("bytes=0-5", "Hello,"),
+            ("bytes=6-11", " World"),
+            ("bytes=12-12", "!"),
+        ] {
+            Mock::given(method("GET"))
+                .and(path("/hello"))
+                .and(header("range", range))
+                .respond_with(ResponseTemplate::new(206).set_body_string(body))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+
+        let mut builder = HttpBuilder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.root("/");
+        builder.read_chunk_size(6);
+        let op = Operator::new(builder)?.finish();
+
+        let bs = op.read("hello").await?;
+
+        assert_eq!(bs, b"Hello, World!");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_with_chunk_size_pinned_by_etag() -> Result<()> {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/hello"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "13")
+                    .insert_header("etag", "\"v1\""),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/hello"))
+            .and(header("range", "bytes=0-5"))
+            .and(header("if-match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(206).set_body_string("Hello,"))
+            .mount(&mock_server)
+            .await;
+        // Object has been replaced after the first sub-range.
+        Mock::given(method("GET"))
+            .and(path("/hello"))
+            .and(header("range", "bytes=6-11"))
+            .respond_with(ResponseTemplate::new(412))
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = HttpBuilder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.root("/");
+        builder.read_chunk_size(6);
+        let op = Operator::new(builder)?.finish();
+
+        let err = op.read("hello").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_with_chunk_size_without_content_length() -> Result<()> {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/hello"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/hello"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("Hello, World!"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = HttpBuilder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.root("/");
+        builder.read_chunk_size(6);
+        let op = Operator::new(builder)?.finish();
+
+        let bs = op.read("hello").await?;
+        assert_eq!(bs, b"Hello, World!");
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_chunk_size_zero() {
+        let mut builder = HttpBuilder::from_map(HashMap::from([
+            ("endpoint".to_string(), "http://127.0.0.1".to_string()),
+            ("read_chunk_size".to_string(), "0".to_string()),
+        ]));
+        let err = builder.build().expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
     #[tokio::test]
     async fn test_stat() -> Result<()> {
         let _ = tracing_subscriber::fmt().with_test_writer().try_init();
@@ -154,6 +154,9 @@ impl Builder for MemcachedBuilder {
 /// Backend for memcached services.
 pub type MemcachedBackend = kv::Backend<Adapter>;
 
+/// Expiration larger than this will be treated as unix timestamp by memcached.
+const MAX_RELATIVE_EXPIRATION: u64 = 30 * 24 * 60 * 60;
+
 #[derive(Clone, Debug)]
 pub struct Adapter {
     endpoint: String,
@@ -193,6 +196,7 @@ impl kv::Adapter for Adapter {
             Capability {
                 read: true,
                 write: true,
+                write_with_expire_at: true,
 
                 ..Default::default()
             },
@@ -219,6 +223,19 @@ impl kv::Adapter for Adapter {
         .await
     }
 
+    async fn set_with_ttl(&self, key: &str, value: &[u8], ttl: Duration) -> Result<()> {
+        // Memcached treats expiration larger than 30 days as unix timestamp.
+        let secs = ttl.as_secs().max(1);
+        let expiration = if secs > MAX_RELATIVE_EXPIRATION {
+            (chrono::Utc::now().timestamp() as u64 + secs) as u32
+        } else {
+            secs as u32
+        };
+
+        let mut conn = self.conn().await?;
+        conn.set(&percent_encode_path(key), value, expiration).await
+    }
+
     async fn delete(&self, key: &str) -> Result<()> {
         let mut conn = self.conn().await?;
 
@@ -87,6 +87,7 @@ impl typed_kv::Adapter for Adapter {
                 set: true,
                 delete: true,
                 scan: true,
+                delete_prefix: true,
             },
         )
     }
@@ -122,6 +123,25 @@ impl typed_kv::Adapter for Adapter {
         Ok(())
     }
 
+    async fn delete_prefix(&self, path: &str) -> Result<()> {
+        self.blocking_delete_prefix(path)
+    }
+
+    fn blocking_delete_prefix(&self, path: &str) -> Result<()> {
+        let mut inner = self.inner.lock();
+        let keys: Vec<_> = inner
+            .range(path.to_string()..)
+            .map(|(k, _)| k)
+            .take_while(|k| k.starts_with(path))
+            .cloned()
+            .collect();
+        for key in keys {
+            inner.remove(&key);
+        }
+
+        Ok(())
+    }
+
     async fn scan(&self, path: &str) -> Result<Vec<String>> {
         self.blocking_scan(path)
     }
@@ -160,4 +180,22 @@ mod tests {
         let b2 = MemoryBuilder::default().build().unwrap();
         assert_ne!(b1.info().name(), b2.info().name())
     }
+
+    #[test]
+    fn test_remove_all_with_prefix() {
+        let op = Operator::new(MemoryBuilder::default())
+            .unwrap()
+            .finish()
+            .blocking();
+        assert!(op.info().full_capability().delete_with_prefix);
+
+        op.write("dir/a", "a").unwrap();
+        op.write("dir/sub/b", "b").unwrap();
+        op.write("dir_sibling", "c").unwrap();
+
+        op.remove_all("dir/").unwrap();
+        assert!(!op.is_exist("dir/a").unwrap());
+        assert!(!op.is_exist("dir/sub/b").unwrap());
+        assert!(op.is_exist("dir_sibling").unwrap());
+    }
 }
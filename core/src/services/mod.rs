@@ -57,6 +57,10 @@ pub use self::etcd::EtcdConfig;
 mod fs;
 #[cfg(feature = "services-fs")]
 pub use fs::Fs;
+#[cfg(feature = "services-fs")]
+pub use fs::FsTrash;
+#[cfg(feature = "services-fs")]
+pub use fs::FsTrashEntry;
 
 #[cfg(feature = "services-ftp")]
 mod ftp;
@@ -129,6 +133,11 @@ mod moka;
 #[cfg(feature = "services-moka")]
 pub use self::moka::Moka;
 
+#[cfg(feature = "services-oci")]
+mod oci;
+#[cfg(feature = "services-oci")]
+pub use oci::Oci;
+
 #[cfg(feature = "services-obs")]
 mod obs;
 #[cfg(feature = "services-obs")]
@@ -194,6 +194,7 @@ impl typed_kv::Adapter for Adapter {
                 set: true,
                 delete: true,
                 scan: true,
+                delete_prefix: false,
             },
         )
     }
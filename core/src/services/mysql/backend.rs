@@ -260,6 +260,27 @@ impl kv::Adapter for Adapter {
         }
     }
 
+    async fn size(&self, path: &str) -> Result<Option<u64>> {
+        let query = format!(
+            "SELECT LENGTH(`{}`) FROM `{}` WHERE `{}` = :path LIMIT 1",
+            self.value_field, self.table, self.key_field
+        );
+        let mut conn = self
+            .connection_pool
+            .get_conn()
+            .await
+            .map_err(parse_mysql_error)?;
+        let statement = conn.prep(query).await.map_err(parse_mysql_error)?;
+        conn.exec_first(
+            statement,
+            params! {
+                "path" => path,
+            },
+        )
+        .await
+        .map_err(parse_mysql_error)
+    }
+
     async fn set(&self, path: &str, value: &[u8]) -> Result<()> {
         let query = format!(
             "INSERT INTO `{}` (`{}`, `{}`)
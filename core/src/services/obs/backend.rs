@@ -260,6 +260,7 @@ impl Accessor for ObsBackend {
         am.set_scheme(Scheme::Obs)
             .set_root(&self.core.root)
             .set_name(&self.core.bucket)
+            .set_endpoint(&self.core.endpoint)
             .set_native_capability(Capability {
                 stat: true,
                 stat_with_if_match: true,
@@ -289,6 +290,18 @@ impl Accessor for ObsBackend {
                 } else {
                     Some(usize::MAX)
                 },
+                // The max parts of OBS multipart upload is 10000.
+                //
+                // ref: <https://support.huaweicloud.com/intl/en-us/ugobs-obs/obs_41_0021.html>
+                write_multi_max_parts: Some(10000),
+                // The max object size of OBS is 48.8 TiB.
+                //
+                // ref: <https://support.huaweicloud.com/intl/en-us/ugobs-obs/obs_41_0021.html>
+                write_total_max_size: if cfg!(target_pointer_width = "64") {
+                    Some(10000 * 5 * 1024 * 1024 * 1024)
+                } else {
+                    Some(usize::MAX)
+                },
 
                 delete: true,
                 copy: true,
@@ -337,7 +350,11 @@ impl Accessor for ObsBackend {
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
                 let size = parse_content_length(resp.headers())?;
-                Ok((RpRead::new().with_size(size), resp.into_body()))
+                let etag = parse_etag(resp.headers())?.map(|v| v.to_string());
+                Ok((
+                    RpRead::new().with_size(size).with_etag(etag),
+                    resp.into_body(),
+                ))
             }
             StatusCode::RANGE_NOT_SATISFIABLE => Ok((RpRead::new(), IncomingAsyncBody::empty())),
             _ => Err(parse_error(resp).await?),
@@ -350,7 +367,7 @@ impl Accessor for ObsBackend {
         let w = if args.append() {
             ObsWriters::Two(oio::AppendObjectWriter::new(writer))
         } else {
-            ObsWriters::One(oio::MultipartUploadWriter::new(writer))
+            ObsWriters::One(oio::MultipartUploadWriter::new(writer, args.concurrent()))
         };
 
         Ok((RpWrite::default(), w))
@@ -73,6 +73,9 @@ impl ObsCore {
     }
 
     pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        // Request id must be signed too.
+        ClientRequestId::insert_current(req.headers_mut());
+
         let cred = if let Some(cred) = self.load_credential().await? {
             cred
         } else {
@@ -0,0 +1,459 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+use log::debug;
+
+use super::core::*;
+use super::error::parse_error;
+use super::reader::OciReader;
+use super::writer::OciBlobWriter;
+use super::writer::OciManifestWriter;
+use crate::raw::*;
+use crate::*;
+
+/// [OCI Distribution](https://github.com/opencontainers/distribution-spec) registry support.
+#[doc = include_str!("docs.md")]
+#[derive(Default)]
+pub struct OciBuilder {
+    endpoint: Option<String>,
+    repository: Option<String>,
+    username: Option<String>,
+    password: Option<Secret>,
+
+    http_client: Option<HttpClient>,
+}
+
+impl Debug for OciBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OciBuilder")
+            .field("endpoint", &self.endpoint)
+            .field("repository", &self.repository)
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OciBuilder {
+    /// Set endpoint of this backend.
+    ///
+    /// Endpoint must be full uri, e.g. `https://registry-1.docker.io`.
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        self.endpoint = if endpoint.is_empty() {
+            None
+        } else {
+            Some(endpoint.trim_end_matches('/').to_string())
+        };
+
+        self
+    }
+
+    /// Set repository of this backend, e.g. `library/alpine`.
+    pub fn repository(&mut self, repository: &str) -> &mut Self {
+        self.repository = if repository.is_empty() {
+            None
+        } else {
+            Some(repository.trim_matches('/').to_string())
+        };
+
+        self
+    }
+
+    /// Set username of this backend.
+    ///
+    /// Username and password will be used for basic auth or fetching
+    /// bearer token. Leave them empty to access public repositories
+    /// anonymously.
+    pub fn username(&mut self, username: &str) -> &mut Self {
+        if !username.is_empty() {
+            self.username = Some(username.to_string());
+        }
+        self
+    }
+
+    /// Set password of this backend.
+    pub fn password(&mut self, password: &str) -> &mut Self {
+        if !password.is_empty() {
+            self.password = Some(password.into());
+        }
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+}
+
+impl Builder for OciBuilder {
+    const SCHEME: Scheme = Scheme::Oci;
+    type Accessor = OciBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = OciBuilder::default();
+
+        map.get("endpoint").map(|v| builder.endpoint(v));
+        map.get("repository").map(|v| builder.repository(v));
+        map.get("username").map(|v| builder.username(v));
+        map.get("password").map(|v| builder.password(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        debug!("backend build started: {:?}", &self);
+
+        let endpoint = match self.endpoint.take() {
+            Some(v) => v,
+            None => {
+                return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                    .with_context("service", Scheme::Oci))
+            }
+        };
+        let repository = match self.repository.take() {
+            Some(v) => v,
+            None => {
+                return Err(Error::new(ErrorKind::ConfigInvalid, "repository is empty")
+                    .with_context("service", Scheme::Oci))
+            }
+        };
+
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Oci)
+            })?
+        };
+
+        debug!("backend build finished: {:?}", &self);
+        Ok(OciBackend {
+            core: Arc::new(OciCore::new(
+                endpoint,
+                repository,
+                self.username.take(),
+                self.password.take(),
+                client,
+            )),
+        })
+    }
+}
+
+/// Backend for OCI registry service.
+#[derive(Debug, Clone)]
+pub struct OciBackend {
+    core: Arc<OciCore>,
+}
+
+#[async_trait]
+impl Accessor for OciBackend {
+    type Reader = OciReader;
+    type BlockingReader = ();
+    type Writer = oio::TwoWaysWriter<
+        oio::OneShotWriter<OciManifestWriter>,
+        oio::MultipartUploadWriter<OciBlobWriter>,
+    >;
+    type BlockingWriter = ();
+    type Lister = ();
+    type BlockingLister = ();
+
+    fn info(&self) -> AccessorInfo {
+        let mut am = AccessorInfo::default();
+        am.set_scheme(Scheme::Oci)
+            .set_root("/")
+            .set_name(&self.core.repository)
+            .set_native_capability(Capability {
+                stat: true,
+
+                read: true,
+                read_can_next: true,
+                read_with_range: true,
+
+                write: true,
+                write_can_multi: true,
+                write_with_content_type: true,
+
+                delete: true,
+
+                ..Default::default()
+            });
+        am
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let p = OciPath::parse(path)?;
+        if p == OciPath::Dir {
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                "read a dir is not supported",
+            ));
+        }
+
+        let resp = self.core.oci_get(p, args.range()).await?;
+
+        match resp.status() {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let size = parse_content_length(resp.headers())?;
+
+                // Only the full content could be verified.
+                let digest = if resp.status() == StatusCode::OK && args.range().is_full() {
+                    match p {
+                        OciPath::Blob(digest) => Some(digest.to_string()),
+                        _ => resp
+                            .headers()
+                            .get(DOCKER_CONTENT_DIGEST)
+                            .and_then(|v| v.to_str().ok())
+                            .map(|v| v.to_string()),
+                    }
+                } else {
+                    None
+                };
+
+                Ok((
+                    RpRead::new().with_size(size),
+                    OciReader::new(resp.into_body(), digest),
+                ))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let w = match OciPath::parse(path)? {
+            OciPath::Blob(digest) => oio::TwoWaysWriter::Two(oio::MultipartUploadWriter::new(
+                OciBlobWriter::new(self.core.clone(), digest),
+                1,
+            )),
+            OciPath::Manifest(reference) => oio::TwoWaysWriter::One(oio::OneShotWriter::new(
+                OciManifestWriter::new(self.core.clone(), args, reference),
+            )),
+            OciPath::Dir => {
+                return Err(Error::new(
+                    ErrorKind::IsADirectory,
+                    "write to a dir is not supported",
+                ))
+            }
+        };
+
+        Ok((RpWrite::default(), w))
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        let p = OciPath::parse(path)?;
+        if p == OciPath::Dir {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        let resp = self.core.oci_head(p).await?;
+        match resp.status() {
+            StatusCode::OK => {
+                let mut meta = parse_into_metadata(path, resp.headers())?;
+                if let Some(digest) = resp
+                    .headers()
+                    .get(DOCKER_CONTENT_DIGEST)
+                    .and_then(|v| v.to_str().ok())
+                {
+                    meta.set_etag(digest);
+                }
+                Ok(RpStat::new(meta))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        let p = OciPath::parse(path)?;
+        if p == OciPath::Dir {
+            return Ok(RpDelete::default());
+        }
+
+        let resp = self.core.oci_delete(p).await?;
+        match resp.status() {
+            StatusCode::ACCEPTED | StatusCode::OK | StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                Ok(RpDelete::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use futures::TryStreamExt;
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::matchers::query_param;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+
+    use super::*;
+
+    fn new_mock_operator(server: &MockServer) -> crate::Operator {
+        let mut builder = OciBuilder::default();
+        builder
+            .endpoint(&server.uri())
+            .repository("library/alpine")
+            .username("user")
+            .password("pass");
+        crate::Operator::new(builder).unwrap().finish()
+    }
+
+    async fn read_stream(op: &crate::Operator, path: &str) -> Result<Vec<u8>> {
+        let bs: Vec<Bytes> = op.reader(path).await?.into_stream().try_collect().await?;
+        Ok(bs.concat())
+    }
+
+    #[tokio::test]
+    async fn test_read_manifest_with_token_auth() -> Result<()> {
+        let server = MockServer::start().await;
+        let content = r#"{"schemaVersion":2}"#;
+        let manifest = "/v2/library/alpine/manifests/latest";
+
+        Mock::given(method("GET"))
+            .and(path(manifest))
+            .and(header("authorization", "Bearer token"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(DOCKER_CONTENT_DIGEST, sha256_digest(content.as_bytes()))
+                    .set_body_string(content),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(manifest))
+            .respond_with(ResponseTemplate::new(401).insert_header(
+                "www-authenticate",
+                format!(
+                    r#"Bearer realm="{}/token",service="registry",scope="repository:library/alpine:pull""#,
+                    server.uri()
+                ),
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // The token is fetched with basic auth of username and password.
+        Mock::given(method("GET"))
+            .and(path("/token"))
+            .and(query_param("service", "registry"))
+            .and(query_param("scope", "repository:library/alpine:pull"))
+            .and(header("authorization", "Basic dXNlcjpwYXNz"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"token":"token"}"#))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let op = new_mock_operator(&server);
+        assert_eq!(
+            read_stream(&op, "manifests/latest").await?,
+            content.as_bytes()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_blob_with_mismatched_digest() -> Result<()> {
+        let server = MockServer::start().await;
+        let digest = sha256_digest(b"Hello, World!");
+        Mock::given(method("GET"))
+            .and(path(format!("/v2/library/alpine/blobs/{digest}")))
+            .respond_with(ResponseTemplate::new(200).set_body_string("Hello, Wordl!"))
+            .mount(&server)
+            .await;
+
+        let op = new_mock_operator(&server);
+        let err = read_stream(&op, &format!("blobs/{digest}"))
+            .await
+            .expect_err("digest must be verified");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_blob_by_chunked_upload() -> Result<()> {
+        let server = MockServer::start().await;
+        let uploads = "/v2/library/alpine/blobs/uploads/";
+        let digest = sha256_digest(b"Hello, World!");
+
+        Mock::given(method("POST"))
+            .and(path(uploads))
+            .respond_with(
+                ResponseTemplate::new(202).insert_header("location", format!("{uploads}1")),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        // Registry returns a new location after every chunk.
+        for (from, range, to) in [("1", "0-6", "2"), ("2", "7-12", "3")] {
+            Mock::given(method("PATCH"))
+                .and(path(format!("{uploads}{from}")))
+                .and(header("content-range", range))
+                .respond_with(
+                    ResponseTemplate::new(202).insert_header("location", format!("{uploads}{to}")),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("PUT"))
+            .and(path(format!("{uploads}3")))
+            .and(query_param("digest", digest.as_str()))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let op = new_mock_operator(&server);
+        let mut w = op.writer(&format!("blobs/{digest}")).await?;
+        w.write("Hello, ").await?;
+        w.write("World!").await?;
+        w.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_manifest_with_mismatched_digest() -> Result<()> {
+        let server = MockServer::start().await;
+
+        let op = new_mock_operator(&server);
+        let path = format!("manifests/{}", sha256_digest(b"{}"));
+        let err = op
+            .write(&path, r#"{"schemaVersion":2}"#)
+            .await
+            .expect_err("digest must be verified");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(server.received_requests().await.unwrap().is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,541 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Mutex;
+
+use http::header::ACCEPT;
+use http::header::AUTHORIZATION;
+use http::header::CONTENT_LENGTH;
+use http::header::CONTENT_TYPE;
+use http::header::WWW_AUTHENTICATE;
+use http::HeaderValue;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use serde::Deserialize;
+use sha2::Digest;
+use sha2::Sha256;
+
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The media types of manifests that we accept.
+const MANIFEST_ACCEPT: &str = "application/vnd.oci.image.manifest.v1+json, \
+    application/vnd.oci.image.index.v1+json, \
+    application/vnd.docker.distribution.manifest.v2+json, \
+    application/vnd.docker.distribution.manifest.list.v2+json";
+/// The default media type of manifests that we write.
+pub const DEFAULT_MANIFEST_TYPE: &str = "application/vnd.oci.image.manifest.v1+json";
+/// The header returned by registries which carries the digest of content.
+pub const DOCKER_CONTENT_DIGEST: &str = "docker-content-digest";
+
+/// OciPath is the object that a path points to.
+///
+/// - `blobs/{digest}` points to a blob.
+/// - `manifests/{reference}` points to a manifest by tag or digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OciPath<'a> {
+    Dir,
+    Blob(&'a str),
+    Manifest(&'a str),
+}
+
+impl<'a> OciPath<'a> {
+    pub fn parse(path: &'a str) -> Result<Self> {
+        let path = path.trim_start_matches('/');
+        match path {
+            "" | "blobs/" | "manifests/" => return Ok(OciPath::Dir),
+            _ => {}
+        }
+
+        if let Some(digest) = path.strip_prefix("blobs/") {
+            if digest.contains(':') && !digest.contains('/') {
+                return Ok(OciPath::Blob(digest));
+            }
+        }
+        if let Some(reference) = path.strip_prefix("manifests/") {
+            if !reference.is_empty() && !reference.contains('/') {
+                return Ok(OciPath::Manifest(reference));
+            }
+        }
+
+        Err(Error::new(
+            ErrorKind::NotFound,
+            "path must be blobs/{digest} or manifests/{reference}",
+        )
+        .with_context("path", path))
+    }
+}
+
+/// Calculate the sha256 digest of content.
+pub fn sha256_digest(content: &[u8]) -> String {
+    format!("sha256:{:x}", Sha256::digest(content))
+}
+
+pub struct OciCore {
+    pub endpoint: String,
+    pub repository: String,
+    pub username: Option<String>,
+    pub password: Option<Secret>,
+    pub client: HttpClient,
+
+    /// The authorization that fetched by the token auth flow.
+    authorization: Mutex<Option<String>>,
+}
+
+impl Debug for OciCore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OciCore")
+            .field("endpoint", &self.endpoint)
+            .field("repository", &self.repository)
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OciCore {
+    pub fn new(
+        endpoint: String,
+        repository: String,
+        username: Option<String>,
+        password: Option<Secret>,
+        client: HttpClient,
+    ) -> Self {
+        Self {
+            endpoint,
+            repository,
+            username,
+            password,
+            client,
+            authorization: Mutex::new(None),
+        }
+    }
+
+    fn password(&self) -> &str {
+        self.password
+            .as_ref()
+            .map(|v| v.expose_secret().as_str())
+            .unwrap_or_default()
+    }
+
+    /// Send request with the token auth flow.
+    ///
+    /// Registry will return `401` with a `WWW-Authenticate` challenge if
+    /// we don't have a valid token, we will fetch the token and send the
+    /// request again if the body could be resent.
+    ///
+    /// Reference: <https://distribution.github.io/distribution/spec/auth/token/>
+    pub async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+        let (parts, body) = req.into_parts();
+        let retry = match try_clone_body(&body) {
+            Some(body) => {
+                let mut req = Request::builder()
+                    .method(parts.method.clone())
+                    .uri(parts.uri.clone())
+                    .body(body)
+                    .map_err(new_request_build_error)?;
+                *req.headers_mut() = parts.headers.clone();
+                Some(req)
+            }
+            None => None,
+        };
+
+        let mut req = Request::from_parts(parts, body);
+        self.sign(&mut req)?;
+        let resp = self.client.send(req).await?;
+        if resp.status() != StatusCode::UNAUTHORIZED {
+            return Ok(resp);
+        }
+        let Some(mut req) = retry else {
+            return Ok(resp);
+        };
+        let Some(challenge) = resp
+            .headers()
+            .get(WWW_AUTHENTICATE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string())
+        else {
+            return Ok(resp);
+        };
+        resp.into_body().consume().await?;
+
+        self.authenticate(&challenge).await?;
+        self.sign(&mut req)?;
+        self.client.send(req).await
+    }
+
+    fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        let authorization = self
+            .authorization
+            .lock()
+            .expect("lock must be valid")
+            .clone();
+        if let Some(v) = authorization {
+            let v = HeaderValue::from_str(&v).map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "authorization is not a valid header")
+                    .set_source(err)
+            })?;
+            req.headers_mut().insert(AUTHORIZATION, v);
+        }
+        Ok(())
+    }
+
+    /// Handle the `WWW-Authenticate` challenge and store the authorization.
+    async fn authenticate(&self, challenge: &str) -> Result<()> {
+        let (scheme, params) = challenge.split_once(' ').unwrap_or((challenge, ""));
+
+        let authorization = if scheme.eq_ignore_ascii_case("basic") {
+            let Some(username) = &self.username else {
+                return Err(Error::new(
+                    ErrorKind::PermissionDenied,
+                    "registry requires basic auth but username is not set",
+                ));
+            };
+            format_authorization_by_basic(username, self.password())?
+        } else if scheme.eq_ignore_ascii_case("bearer") {
+            let params = parse_challenge_params(params);
+            let Some(realm) = params.iter().find(|(k, _)| k == "realm").map(|(_, v)| v) else {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "registry returns bearer challenge without realm",
+                )
+                .with_context("challenge", challenge));
+            };
+
+            let query = params
+                .iter()
+                .filter(|(k, _)| k == "service" || k == "scope")
+                .map(|(k, v)| format!("{k}={}", percent_encode_path(v)))
+                .collect::<Vec<_>>()
+                .join("&");
+            let url = if query.is_empty() {
+                realm.to_string()
+            } else {
+                format!("{realm}?{query}")
+            };
+
+            let mut req = Request::get(&url);
+            if let Some(username) = &self.username {
+                req = req.header(
+                    AUTHORIZATION,
+                    format_authorization_by_basic(username, self.password())?,
+                );
+            }
+            let req = req
+                .body(AsyncBody::Empty)
+                .map_err(new_request_build_error)?;
+            let resp = self.client.send(req).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let token: TokenResponse =
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+            match token.token.or(token.access_token) {
+                Some(token) => format!("Bearer {token}"),
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "registry token response doesn't contain token",
+                    ))
+                }
+            }
+        } else {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "registry requires unsupported auth scheme",
+            )
+            .with_context("challenge", challenge));
+        };
+
+        *self.authorization.lock().expect("lock must be valid") = Some(authorization);
+        Ok(())
+    }
+}
+
+// requests
+impl OciCore {
+    pub fn blob_url(&self, digest: &str) -> String {
+        format!("{}/v2/{}/blobs/{}", self.endpoint, self.repository, digest)
+    }
+
+    pub fn manifest_url(&self, reference: &str) -> String {
+        format!(
+            "{}/v2/{}/manifests/{}",
+            self.endpoint, self.repository, reference
+        )
+    }
+
+    /// Build the absolute url of location returned by registry.
+    pub fn location_url(&self, location: &str) -> String {
+        if location.starts_with("http://") || location.starts_with("https://") {
+            location.to_string()
+        } else {
+            format!("{}{}", self.endpoint, location)
+        }
+    }
+
+    pub async fn oci_head(&self, path: OciPath<'_>) -> Result<Response<IncomingAsyncBody>> {
+        let req = match path {
+            OciPath::Blob(digest) => Request::head(self.blob_url(digest)),
+            OciPath::Manifest(reference) => {
+                Request::head(self.manifest_url(reference)).header(ACCEPT, MANIFEST_ACCEPT)
+            }
+            OciPath::Dir => unreachable!("dir can't be sent"),
+        };
+        let req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.send(req).await
+    }
+
+    pub async fn oci_get(
+        &self,
+        path: OciPath<'_>,
+        range: BytesRange,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = match path {
+            OciPath::Blob(digest) => Request::get(self.blob_url(digest)),
+            OciPath::Manifest(reference) => {
+                Request::get(self.manifest_url(reference)).header(ACCEPT, MANIFEST_ACCEPT)
+            }
+            OciPath::Dir => unreachable!("dir can't be sent"),
+        };
+        if !range.is_full() {
+            req = req.header(http::header::RANGE, range.to_header());
+        }
+        let req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.send(req).await
+    }
+
+    pub async fn oci_delete(&self, path: OciPath<'_>) -> Result<Response<IncomingAsyncBody>> {
+        let url = match path {
+            OciPath::Blob(digest) => self.blob_url(digest),
+            OciPath::Manifest(reference) => self.manifest_url(reference),
+            OciPath::Dir => unreachable!("dir can't be sent"),
+        };
+        let req = Request::delete(url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.send(req).await
+    }
+
+    pub async fn oci_put_manifest(
+        &self,
+        reference: &str,
+        content_type: &str,
+        body: bytes::Bytes,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let req = Request::put(self.manifest_url(reference))
+            .header(CONTENT_TYPE, content_type)
+            .header(CONTENT_LENGTH, body.len())
+            .body(AsyncBody::Bytes(body))
+            .map_err(new_request_build_error)?;
+
+        self.send(req).await
+    }
+
+    /// Start a blob upload session and returns its location.
+    pub async fn oci_start_upload(&self) -> Result<String> {
+        let url = format!("{}/v2/{}/blobs/uploads/", self.endpoint, self.repository);
+        let req = Request::post(url)
+            .header(CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        let resp = self.send(req).await?;
+        if resp.status() != StatusCode::ACCEPTED {
+            return Err(parse_error(resp).await?);
+        }
+        let location = self.parse_upload_location(&resp)?;
+        resp.into_body().consume().await?;
+        Ok(location)
+    }
+
+    /// Upload a chunk of blob, returns the location of next request.
+    pub async fn oci_patch_upload(
+        &self,
+        location: &str,
+        offset: u64,
+        size: u64,
+        body: AsyncBody,
+    ) -> Result<String> {
+        let mut req = Request::patch(location)
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .header(CONTENT_LENGTH, size);
+        if size > 0 {
+            req = req.header(
+                http::header::CONTENT_RANGE,
+                format!("{}-{}", offset, offset + size - 1),
+            );
+        }
+        let req = req.body(body).map_err(new_request_build_error)?;
+
+        let resp = self.send(req).await?;
+        if resp.status() != StatusCode::ACCEPTED {
+            return Err(parse_error(resp).await?);
+        }
+        let location = self.parse_upload_location(&resp)?;
+        resp.into_body().consume().await?;
+        Ok(location)
+    }
+
+    /// Finish the blob upload, registry will verify the content by digest.
+    pub async fn oci_finish_upload(
+        &self,
+        location: &str,
+        digest: &str,
+        size: u64,
+        body: AsyncBody,
+    ) -> Result<()> {
+        let sep = if location.contains('?') { '&' } else { '?' };
+        let url = format!("{location}{sep}digest={}", percent_encode_path(digest));
+        let mut req = Request::put(url).header(CONTENT_LENGTH, size);
+        if size > 0 {
+            req = req.header(CONTENT_TYPE, "application/octet-stream");
+        }
+        let req = req.body(body).map_err(new_request_build_error)?;
+
+        let resp = self.send(req).await?;
+        match resp.status() {
+            StatusCode::CREATED => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    pub async fn oci_cancel_upload(&self, location: &str) -> Result<()> {
+        let req = Request::delete(location)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        let resp = self.send(req).await?;
+        match resp.status() {
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    fn parse_upload_location(&self, resp: &Response<IncomingAsyncBody>) -> Result<String> {
+        match parse_location(resp.headers())? {
+            Some(v) => Ok(self.location_url(v)),
+            None => Err(Error::new(
+                ErrorKind::Unexpected,
+                "registry returns upload session without location",
+            )),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    token: Option<String>,
+    access_token: Option<String>,
+}
+
+/// Clone the body if it could be sent again.
+fn try_clone_body(body: &AsyncBody) -> Option<AsyncBody> {
+    match body {
+        AsyncBody::Empty => Some(AsyncBody::Empty),
+        AsyncBody::Bytes(bs) => Some(AsyncBody::Bytes(bs.clone())),
+        AsyncBody::ChunkedBytes(bs) => Some(AsyncBody::ChunkedBytes(bs.clone())),
+        AsyncBody::Stream(_) => None,
+    }
+}
+
+/// Parse params of challenge like `realm="https://auth.docker.io/token",service="registry.docker.io"`.
+fn parse_challenge_params(s: &str) -> Vec<(String, String)> {
+    let mut params = vec![];
+    let mut rest = s.trim();
+    while !rest.is_empty() {
+        let Some((key, value)) = rest.split_once('=') else {
+            break;
+        };
+        let key = key.trim().to_ascii_lowercase();
+
+        let value = value.trim_start();
+        let (value, next) = match value.strip_prefix('"') {
+            Some(v) => match v.find('"') {
+                Some(end) => (&v[..end], &v[end + 1..]),
+                None => (v, ""),
+            },
+            None => match value.find(',') {
+                Some(end) => (&value[..end], &value[end..]),
+                None => (value, ""),
+            },
+        };
+        params.push((key, value.to_string()));
+        rest = next.trim_start().trim_start_matches(',').trim_start();
+    }
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_path() -> Result<()> {
+        assert_eq!(OciPath::parse("/")?, OciPath::Dir);
+        assert_eq!(
+            OciPath::parse("blobs/sha256:abc")?,
+            OciPath::Blob("sha256:abc")
+        );
+        assert_eq!(
+            OciPath::parse("manifests/latest")?,
+            OciPath::Manifest("latest")
+        );
+        assert!(OciPath::parse("blobs/abc").is_err());
+        assert!(OciPath::parse("tags/latest").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_challenge_params() {
+        let params = parse_challenge_params(
+            r#"realm="https://auth.docker.io/token",service="registry.docker.io",scope="repository:library/alpine:pull,push""#,
+        );
+        assert_eq!(
+            params,
+            vec![
+                (
+                    "realm".to_string(),
+                    "https://auth.docker.io/token".to_string()
+                ),
+                ("service".to_string(), "registry.docker.io".to_string()),
+                (
+                    "scope".to_string(),
+                    "repository:library/alpine:pull,push".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,126 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use http::Response;
+use http::StatusCode;
+use serde::Deserialize;
+
+use crate::raw::*;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+/// OciErrors is the error response defined by the distribution spec.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct OciErrors {
+    errors: Vec<OciError>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct OciError {
+    code: String,
+    message: String,
+}
+
+/// Parse error response into Error.
+pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
+    let (parts, body) = resp.into_parts();
+    let bs = body.bytes().await?;
+
+    let (mut kind, retryable) = match parts.status {
+        StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
+        StatusCode::UNAUTHORIZED => (ErrorKind::Unauthenticated, false),
+        StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
+        StatusCode::PRECONDITION_FAILED | StatusCode::NOT_MODIFIED => {
+            (ErrorKind::ConditionNotMatch, false)
+        }
+        StatusCode::INTERNAL_SERVER_ERROR
+        | StatusCode::BAD_GATEWAY
+        | StatusCode::SERVICE_UNAVAILABLE
+        | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+        _ => (ErrorKind::Unexpected, false),
+    };
+
+    let message = match serde_json::from_slice::<OciErrors>(&bs) {
+        Ok(errs) if !errs.errors.is_empty() => {
+            if let Some(v) = errs
+                .errors
+                .iter()
+                .find_map(|e| parse_oci_error_code(&e.code))
+            {
+                kind = v;
+            }
+            errs.errors
+                .iter()
+                .map(|e| format!("{}: {}", e.code, e.message))
+                .collect::<Vec<_>>()
+                .join("; ")
+        }
+        _ => String::from_utf8_lossy(&bs).into_owned(),
+    };
+
+    let mut err = Error::new(kind, &message);
+
+    err = with_error_response_context(err, parts);
+
+    if retryable {
+        err = err.set_temporary();
+    }
+
+    Ok(err)
+}
+
+/// Returns the ErrorKind of error code.
+///
+/// Reference: <https://github.com/opencontainers/distribution-spec/blob/main/spec.md#error-codes>
+fn parse_oci_error_code(code: &str) -> Option<ErrorKind> {
+    match code {
+        "BLOB_UNKNOWN" | "BLOB_UPLOAD_UNKNOWN" | "MANIFEST_UNKNOWN" | "NAME_UNKNOWN" => {
+            Some(ErrorKind::NotFound)
+        }
+        "DENIED" => Some(ErrorKind::PermissionDenied),
+        "UNAUTHORIZED" => Some(ErrorKind::Unauthenticated),
+        "UNSUPPORTED" => Some(ErrorKind::Unsupported),
+        "TOOMANYREQUESTS" => Some(ErrorKind::RateLimited),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error() {
+        let bs = bytes::Bytes::from(
+            r#"{"errors":[{"code":"MANIFEST_UNKNOWN","message":"manifest unknown","detail":{}}]}"#,
+        );
+
+        let out: OciErrors = serde_json::from_slice(&bs).expect("must success");
+        println!("{out:?}");
+
+        assert_eq!(out.errors[0].code, "MANIFEST_UNKNOWN");
+        assert_eq!(out.errors[0].message, "manifest unknown");
+        assert_eq!(
+            parse_oci_error_code(&out.errors[0].code),
+            Some(ErrorKind::NotFound)
+        );
+    }
+}
@@ -0,0 +1,98 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io::SeekFrom;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Bytes;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::raw::*;
+use crate::*;
+
+/// OciReader verifies the content against its digest while reading.
+///
+/// Only `sha256` digests of full content are verified, the error will be
+/// returned at the end of content if mismatched.
+pub struct OciReader {
+    inner: IncomingAsyncBody,
+    /// The expected digest, `None` means no need to verify.
+    digest: Option<String>,
+    hasher: Sha256,
+}
+
+impl OciReader {
+    pub fn new(inner: IncomingAsyncBody, digest: Option<String>) -> Self {
+        let digest = digest.filter(|v| v.starts_with("sha256:"));
+        Self {
+            inner,
+            digest,
+            hasher: Sha256::new(),
+        }
+    }
+
+    fn verify(&mut self) -> Result<()> {
+        let Some(expected) = self.digest.take() else {
+            return Ok(());
+        };
+
+        let actual = format!("sha256:{:x}", self.hasher.finalize_reset());
+        if actual != expected {
+            return Err(
+                Error::new(ErrorKind::Unexpected, "content doesn't match its digest")
+                    .with_context("expected", expected)
+                    .with_context("actual", actual),
+            );
+        }
+        Ok(())
+    }
+}
+
+impl oio::Read for OciReader {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        let n = ready!(self.inner.poll_read(cx, buf))?;
+        if n == 0 && !buf.is_empty() {
+            self.verify()?;
+        } else if self.digest.is_some() {
+            self.hasher.update(&buf[..n]);
+        }
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        self.inner.poll_seek(cx, pos)
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        match ready!(self.inner.poll_next(cx)) {
+            Some(Ok(bs)) => {
+                if self.digest.is_some() {
+                    self.hasher.update(&bs);
+                }
+                Poll::Ready(Some(Ok(bs)))
+            }
+            Some(Err(err)) => Poll::Ready(Some(Err(err))),
+            None => match self.verify() {
+                Ok(()) => Poll::Ready(None),
+                Err(err) => Poll::Ready(Some(Err(err))),
+            },
+        }
+    }
+}
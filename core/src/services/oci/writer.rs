@@ -0,0 +1,177 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use http::StatusCode;
+
+use super::core::*;
+use super::error::parse_error;
+use crate::raw::oio::WriteBuf;
+use crate::raw::*;
+use crate::*;
+
+/// OciManifestWriter puts the manifest at once.
+pub struct OciManifestWriter {
+    core: Arc<OciCore>,
+
+    op: OpWrite,
+    reference: String,
+}
+
+impl OciManifestWriter {
+    pub fn new(core: Arc<OciCore>, op: OpWrite, reference: &str) -> Self {
+        OciManifestWriter {
+            core,
+            op,
+            reference: reference.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl oio::OneShotWrite for OciManifestWriter {
+    async fn write_once(&self, bs: &dyn WriteBuf) -> Result<()> {
+        let bs = bs.bytes(bs.remaining());
+        let digest = sha256_digest(&bs);
+
+        // Manifest referenced by digest must match its content.
+        if self.reference.contains(':') && self.reference != digest {
+            return Err(
+                Error::new(ErrorKind::Unexpected, "content doesn't match its digest")
+                    .with_context("expected", &self.reference)
+                    .with_context("actual", digest),
+            );
+        }
+
+        let content_type = self.op.content_type().unwrap_or(DEFAULT_MANIFEST_TYPE);
+        let resp = self
+            .core
+            .oci_put_manifest(&self.reference, content_type, bs)
+            .await?;
+
+        match resp.status() {
+            StatusCode::CREATED => {
+                // Make sure registry stores the same content as ours.
+                if let Some(actual) = resp
+                    .headers()
+                    .get(DOCKER_CONTENT_DIGEST)
+                    .and_then(|v| v.to_str().ok())
+                {
+                    if actual != digest {
+                        return Err(Error::new(
+                            ErrorKind::Unexpected,
+                            "registry returns mismatched digest",
+                        )
+                        .with_context("expected", digest)
+                        .with_context("actual", actual));
+                    }
+                }
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
+
+/// OciBlobWriter uploads the blob by chunked upload session.
+///
+/// - `upload_id` is the location that the session started at.
+/// - `etag` of part is the upload offset after this part.
+///
+/// Registry may return a new location after every chunk, so we keep the
+/// latest one here.
+pub struct OciBlobWriter {
+    core: Arc<OciCore>,
+
+    digest: String,
+    location: Mutex<String>,
+    offset: AtomicU64,
+}
+
+impl OciBlobWriter {
+    pub fn new(core: Arc<OciCore>, digest: &str) -> Self {
+        OciBlobWriter {
+            core,
+            digest: digest.to_string(),
+            location: Mutex::new(String::new()),
+            offset: AtomicU64::new(0),
+        }
+    }
+
+    fn location(&self) -> String {
+        self.location.lock().expect("lock must be valid").clone()
+    }
+
+    fn set_location(&self, location: String) {
+        *self.location.lock().expect("lock must be valid") = location;
+    }
+}
+
+#[async_trait]
+impl oio::MultipartUploadWrite for OciBlobWriter {
+    async fn write_once(&self, size: u64, body: AsyncBody) -> Result<()> {
+        let location = self.core.oci_start_upload().await?;
+        self.core
+            .oci_finish_upload(&location, &self.digest, size, body)
+            .await
+    }
+
+    async fn initiate_part(&self) -> Result<String> {
+        let location = self.core.oci_start_upload().await?;
+        self.set_location(location.clone());
+        self.offset.store(0, Ordering::SeqCst);
+        Ok(location)
+    }
+
+    async fn write_part(
+        &self,
+        _: &str,
+        part_number: usize,
+        size: u64,
+        body: AsyncBody,
+    ) -> Result<oio::MultipartUploadPart> {
+        let offset = self.offset.load(Ordering::SeqCst);
+        let location = self
+            .core
+            .oci_patch_upload(&self.location(), offset, size, body)
+            .await?;
+        self.set_location(location);
+
+        let offset = offset + size;
+        self.offset.store(offset, Ordering::SeqCst);
+        Ok(oio::MultipartUploadPart {
+            part_number,
+            etag: offset.to_string(),
+        })
+    }
+
+    async fn complete_part(&self, _: &str, _: &[oio::MultipartUploadPart]) -> Result<()> {
+        self.core
+            .oci_finish_upload(&self.location(), &self.digest, 0, AsyncBody::Empty)
+            .await
+    }
+
+    async fn abort_part(&self, _: &str) -> Result<()> {
+        self.core.oci_cancel_upload(&self.location()).await
+    }
+}
@@ -24,7 +24,6 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use bytes::Buf;
 use http::StatusCode;
-use http::Uri;
 use log::debug;
 use reqsign::AliyunConfig;
 use reqsign::AliyunLoader;
@@ -57,7 +56,7 @@ pub struct OssBuilder {
 
     // authenticate options
     access_key_id: Option<String>,
-    access_key_secret: Option<String>,
+    access_key_secret: Option<Secret>,
 
     http_client: Option<HttpClient>,
     /// batch_max_operations
@@ -142,7 +141,7 @@ impl OssBuilder {
     /// - If not, we will try to load it from environment.
     pub fn access_key_secret(&mut self, v: &str) -> &mut Self {
         if !v.is_empty() {
-            self.access_key_secret = Some(v.to_string())
+            self.access_key_secret = Some(v.into())
         }
 
         self
@@ -163,32 +162,10 @@ impl OssBuilder {
     fn parse_endpoint(&self, endpoint: &Option<String>, bucket: &str) -> Result<(String, String)> {
         let (endpoint, host) = match endpoint.clone() {
             Some(ep) => {
-                let uri = ep.parse::<Uri>().map_err(|err| {
-                    Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
-                        .with_context("service", Scheme::Oss)
-                        .with_context("endpoint", &ep)
-                        .set_source(err)
-                })?;
-                let host = uri.host().ok_or_else(|| {
-                    Error::new(ErrorKind::ConfigInvalid, "endpoint host is empty")
-                        .with_context("service", Scheme::Oss)
-                        .with_context("endpoint", &ep)
-                })?;
-                let full_host = format!("{bucket}.{host}");
-                let endpoint = match uri.scheme_str() {
-                    Some(scheme_str) => match scheme_str {
-                        "http" | "https" => format!("{scheme_str}://{full_host}"),
-                        _ => {
-                            return Err(Error::new(
-                                ErrorKind::ConfigInvalid,
-                                "endpoint protocol is invalid",
-                            )
-                            .with_context("service", Scheme::Oss));
-                        }
-                    },
-                    None => format!("https://{full_host}"),
-                };
-                (endpoint, full_host)
+                let ep = Endpoint::parse(&ep)
+                    .and_then(|ep| ep.with_bucket(bucket, EndpointStyle::VirtualHost))
+                    .map_err(|err| err.with_context("service", Scheme::Oss))?;
+                (ep.to_string(), ep.authority())
             }
             None => {
                 return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
@@ -337,7 +314,7 @@ impl Builder for OssBuilder {
         }
 
         if let Some(v) = self.access_key_secret.take() {
-            cfg.access_key_secret = Some(v);
+            cfg.access_key_secret = Some(v.into_secret());
         }
 
         let loader = AliyunLoader::new(client.client(), cfg);
@@ -388,6 +365,7 @@ impl Accessor for OssBackend {
         am.set_scheme(Scheme::Oss)
             .set_root(&self.core.root)
             .set_name(&self.core.bucket)
+            .set_endpoint(&self.core.endpoint)
             .set_native_capability(Capability {
                 stat: true,
                 stat_with_if_match: true,
@@ -406,6 +384,10 @@ impl Accessor for OssBackend {
                 write_with_cache_control: true,
                 write_with_content_type: true,
                 write_with_content_disposition: true,
+                // OSS expires objects by lifecycle rules filtered by tags.
+                //
+                // ref: <https://www.alibabacloud.com/help/en/oss/user-guide/lifecycle-rules-based-on-the-last-modified-time>
+                write_with_expire_at: true,
                 // The min multipart size of OSS is 100 KiB.
                 //
                 // ref: <https://www.alibabacloud.com/help/en/oss/user-guide/multipart-upload-12>
@@ -418,15 +400,32 @@ impl Accessor for OssBackend {
                 } else {
                     Some(usize::MAX)
                 },
+                // The max parts of OSS multipart upload is 10000.
+                //
+                // ref: <https://www.alibabacloud.com/help/en/oss/user-guide/multipart-upload-12>
+                write_multi_max_parts: Some(10000),
+                // The max object size of OSS is 48.8 TiB.
+                //
+                // ref: <https://www.alibabacloud.com/help/en/oss/user-guide/multipart-upload-12>
+                write_total_max_size: if cfg!(target_pointer_width = "64") {
+                    Some(10000 * 5 * 1024 * 1024 * 1024)
+                } else {
+                    Some(usize::MAX)
+                },
 
                 delete: true,
                 copy: true,
 
                 list: true,
                 list_with_limit: true,
+                // The max page size of OSS list is 1000.
+                //
+                // ref: <https://www.alibabacloud.com/help/en/oss/developer-reference/listobjectsv2>
+                list_max_page_size: Some(1000),
                 list_with_start_after: true,
                 list_without_recursive: true,
                 list_with_recursive: true,
+                list_with_delimiter: true,
 
                 presign: true,
                 presign_stat: true,
@@ -438,6 +437,9 @@ impl Accessor for OssBackend {
 
                 ..Default::default()
             });
+        if let Some(region) = parse_region(&self.core.host, &self.core.bucket) {
+            am.set_location(region);
+        }
 
         am
     }
@@ -459,7 +461,11 @@ impl Accessor for OssBackend {
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
                 let size = parse_content_length(resp.headers())?;
-                Ok((RpRead::new().with_size(size), resp.into_body()))
+                let etag = parse_etag(resp.headers())?.map(|v| v.to_string());
+                Ok((
+                    RpRead::new().with_size(size).with_etag(etag),
+                    resp.into_body(),
+                ))
             }
             StatusCode::RANGE_NOT_SATISFIABLE => Ok((RpRead::new(), IncomingAsyncBody::empty())),
             _ => Err(parse_error(resp).await?),
@@ -472,7 +478,7 @@ impl Accessor for OssBackend {
         let w = if args.append() {
             OssWriters::Two(oio::AppendObjectWriter::new(writer))
         } else {
-            OssWriters::One(oio::MultipartUploadWriter::new(writer))
+            OssWriters::One(oio::MultipartUploadWriter::new(writer, args.concurrent()))
         };
 
         Ok((RpWrite::default(), w))
@@ -522,6 +528,7 @@ impl Accessor for OssBackend {
             self.core.clone(),
             path,
             args.recursive(),
+            args.delimiter(),
             args.limit(),
             args.start_after(),
         );
@@ -617,3 +624,36 @@ impl Accessor for OssBackend {
         }
     }
 }
+
+/// Parse region from host like `bucket.oss-cn-hangzhou.aliyuncs.com`.
+fn parse_region<'a>(host: &'a str, bucket: &str) -> Option<&'a str> {
+    let host = host.strip_prefix(bucket)?.strip_prefix('.')?;
+    let region = host.strip_suffix(".aliyuncs.com")?;
+    Some(region.strip_suffix("-internal").unwrap_or(region))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_region() {
+        let cases = vec![
+            (
+                "public",
+                "example.oss-cn-hangzhou.aliyuncs.com",
+                Some("oss-cn-hangzhou"),
+            ),
+            (
+                "internal",
+                "example.oss-cn-hangzhou-internal.aliyuncs.com",
+                Some("oss-cn-hangzhou"),
+            ),
+            ("custom domain", "example.oss.example.com", None),
+        ];
+
+        for (name, host, expected) in cases {
+            assert_eq!(parse_region(host, "example"), expected, "{name}");
+        }
+    }
+}
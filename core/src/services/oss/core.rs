@@ -21,6 +21,8 @@ use std::fmt::Write;
 use std::time::Duration;
 
 use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Utc;
 use http::header::CACHE_CONTROL;
 use http::header::CONTENT_DISPOSITION;
 use http::header::CONTENT_LENGTH;
@@ -47,6 +49,11 @@ mod constants {
     pub const X_OSS_SERVER_SIDE_ENCRYPTION_KEY_ID: &str = "x-oss-server-side-encryption-key-id";
 
     pub const RESPONSE_CONTENT_DISPOSITION: &str = "response-content-disposition";
+
+    pub const X_OSS_TAGGING: &str = "x-oss-tagging";
+
+    /// The tag used by lifecycle rules to expire objects.
+    pub const EXPIRE_DAYS_TAG: &str = "opendal-expire-days";
 }
 
 pub struct OssCore {
@@ -104,6 +111,9 @@ impl OssCore {
     }
 
     pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        // Request id must be signed too.
+        ClientRequestId::insert_current(req.headers_mut());
+
         let cred = if let Some(cred) = self.load_credential().await? {
             cred
         } else {
@@ -154,6 +164,26 @@ impl OssCore {
         }
         req
     }
+
+    /// Tag the object with the days before it expires, so that lifecycle
+    /// rules filtered by this tag could remove it.
+    pub fn insert_expire_tagging(
+        &self,
+        mut req: http::request::Builder,
+        expire_at: Option<DateTime<Utc>>,
+    ) -> http::request::Builder {
+        if let Some(expire_at) = expire_at {
+            req = req.header(
+                constants::X_OSS_TAGGING,
+                format!(
+                    "{}={}",
+                    constants::EXPIRE_DAYS_TAG,
+                    expire_days_from_now(expire_at)
+                ),
+            );
+        }
+        req
+    }
 }
 
 impl OssCore {
@@ -188,6 +218,7 @@ impl OssCore {
 
         // set sse headers
         req = self.insert_sse_headers(req);
+        req = self.insert_expire_tagging(req, args.expire_at());
 
         let req = req.body(body).map_err(new_request_build_error)?;
         Ok(req)
@@ -228,6 +259,7 @@ impl OssCore {
 
         // set sse headers
         req = self.insert_sse_headers(req);
+        req = self.insert_expire_tagging(req, args.expire_at());
 
         let req = req.body(body).map_err(new_request_build_error)?;
         Ok(req)
@@ -335,7 +367,8 @@ impl OssCore {
         let endpoint = self.get_endpoint(false);
         let mut url = format!("{}/?list-type=2", endpoint);
 
-        write!(url, "&delimiter={delimiter}").expect("write into string must succeed");
+        write!(url, "&delimiter={}", percent_encode_path(delimiter))
+            .expect("write into string must succeed");
         // prefix
         if !p.is_empty() {
             write!(url, "&prefix={}", percent_encode_path(&p))
@@ -360,7 +393,7 @@ impl OssCore {
                 .expect("write into string must succeed");
         }
 
-        let req = Request::get(&url)
+        let req = with_accept_encoding(Request::get(&url))
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
         Ok(req)
@@ -508,6 +541,7 @@ impl OssCore {
         content_type: Option<&str>,
         content_disposition: Option<&str>,
         cache_control: Option<&str>,
+        expire_at: Option<DateTime<Utc>>,
         is_presign: bool,
     ) -> Result<Response<IncomingAsyncBody>> {
         let path = build_abs_path(&self.root, path);
@@ -524,6 +558,7 @@ impl OssCore {
             req = req.header(CACHE_CONTROL, cache_control);
         }
         req = self.insert_sse_headers(req);
+        req = self.insert_expire_tagging(req, expire_at);
         let mut req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
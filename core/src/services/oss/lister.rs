@@ -30,7 +30,7 @@ pub struct OssLister {
     core: Arc<OssCore>,
 
     path: String,
-    delimiter: &'static str,
+    delimiter: String,
     limit: Option<usize>,
     /// Filter results to objects whose names are lexicographically
     /// **equal to or after** startOffset
@@ -42,10 +42,13 @@ impl OssLister {
         core: Arc<OssCore>,
         path: &str,
         recursive: bool,
+        delimiter: Option<&str>,
         limit: Option<usize>,
         start_after: Option<&str>,
     ) -> Self {
-        let delimiter = if recursive { "" } else { "/" };
+        let delimiter = delimiter
+            .unwrap_or(if recursive { "" } else { "/" })
+            .to_string();
         Self {
             core,
             path: path.to_string(),
@@ -64,7 +67,7 @@ impl oio::PageList for OssLister {
             .oss_list_object(
                 &self.path,
                 &ctx.token,
-                self.delimiter,
+                &self.delimiter,
                 self.limit,
                 if ctx.token.is_empty() {
                     self.start_after.clone()
@@ -87,9 +90,11 @@ impl oio::PageList for OssLister {
         ctx.token = output.next_continuation_token.unwrap_or_default();
 
         for prefix in output.common_prefixes {
+            // Prefixes grouped by custom delimiter don't end with `/`, and
+            // can't be listed as dirs.
             let de = oio::Entry::new(
                 &build_rel_path(&self.core.root, &prefix.prefix),
-                Metadata::new(EntryMode::DIR),
+                Metadata::new(EntryMode::from_path(&prefix.prefix)),
             );
             ctx.entries.push_back(de);
         }
@@ -75,6 +75,7 @@ impl oio::MultipartUploadWrite for OssWriter {
                 self.op.content_type(),
                 self.op.content_disposition(),
                 self.op.cache_control(),
+                self.op.expire_at(),
                 false,
             )
             .await?;
@@ -302,6 +302,33 @@ impl kv::Adapter for Adapter {
         Ok(Some(value))
     }
 
+    async fn size(&self, path: &str) -> Result<Option<u64>> {
+        let query = format!(
+            "SELECT octet_length({})::BIGINT FROM {} WHERE {} = $1 LIMIT 1",
+            self.value_field, self.table, self.key_field
+        );
+        let connection = self
+            .get_client()
+            .await?
+            .get()
+            .await
+            .map_err(parse_bb8_error)?;
+        let statement = connection
+            .prepare(&query)
+            .await
+            .map_err(parse_postgre_error)?;
+        let rows = connection
+            .query(&statement, &[&path])
+            .await
+            .map_err(parse_postgre_error)?;
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        // Value could be NULL.
+        let size: Option<i64> = rows[0].get(0);
+        Ok(Some(size.unwrap_or_default() as u64))
+    }
+
     async fn set(&self, path: &str, value: &[u8]) -> Result<()> {
         let table = &self.table;
         let key_field = &self.key_field;
@@ -63,7 +63,7 @@ pub struct RedisConfig {
     /// the password for authentication
     ///
     /// default is None
-    password: Option<String>,
+    password: Option<Secret>,
     /// the working directory of the Redis service. Can be "/path/to/dir"
     ///
     /// default is "/"
@@ -158,7 +158,7 @@ impl RedisBuilder {
     /// default: no password
     pub fn password(&mut self, password: &str) -> &mut Self {
         if !password.is_empty() {
-            self.config.password = Some(password.to_owned());
+            self.config.password = Some(password.into());
         }
         self
     }
@@ -220,7 +220,7 @@ impl Builder for RedisBuilder {
                 client_builder = client_builder.username(username.clone());
             }
             if let Some(password) = &self.config.password {
-                client_builder = client_builder.password(password.clone());
+                client_builder = client_builder.password(password.expose_secret().clone());
             }
             let client = client_builder.build()?;
 
@@ -309,7 +309,11 @@ impl RedisBuilder {
         let redis_info = RedisConnectionInfo {
             db: self.config.db,
             username: self.config.username.clone(),
-            password: self.config.password.clone(),
+            password: self
+                .config
+                .password
+                .as_ref()
+                .map(|v| v.expose_secret().clone()),
         };
 
         Ok(ConnectionInfo {
@@ -380,6 +384,7 @@ impl kv::Adapter for Adapter {
             Capability {
                 read: true,
                 write: true,
+                write_with_expire_at: true,
 
                 ..Default::default()
             },
@@ -419,6 +424,17 @@ impl kv::Adapter for Adapter {
         Ok(())
     }
 
+    async fn set_with_ttl(&self, key: &str, value: &[u8], ttl: Duration) -> Result<()> {
+        // Redis will reject zero ttl, make sure we expire the key as soon as possible.
+        let ttl = (ttl.as_millis() as usize).max(1);
+        let conn = self.conn().await?;
+        match conn {
+            RedisConnection::Normal(mut conn) => conn.pset_ex(key, value, ttl).await?,
+            RedisConnection::Cluster(mut conn) => conn.pset_ex(key, value, ttl).await?,
+        }
+        Ok(())
+    }
+
     async fn delete(&self, key: &str) -> Result<()> {
         let conn = self.conn().await?;
         match conn {
@@ -113,6 +113,7 @@ impl kv::Adapter for Adapter {
                 read: true,
                 write: true,
                 list: true,
+                delete_with_prefix: true,
                 blocking: true,
                 ..Default::default()
             },
@@ -159,6 +160,33 @@ impl kv::Adapter for Adapter {
         Ok(self.db.delete(path)?)
     }
 
+    async fn delete_prefix(&self, path: &str) -> Result<()> {
+        let cloned_self = self.clone();
+        let cloned_path = path.to_string();
+
+        task::spawn_blocking(move || cloned_self.blocking_delete_prefix(cloned_path.as_str()))
+            .await
+            .map_err(new_task_join_error)?
+    }
+
+    /// Delete keys by `DeleteRange` which only writes a range tombstone
+    /// instead of deleting keys one by one.
+    fn blocking_delete_prefix(&self, path: &str) -> Result<()> {
+        let mut batch = rocksdb::WriteBatch::default();
+        match prefix_upper_bound(path.as_bytes()) {
+            Some(end) => batch.delete_range(path.as_bytes(), end.as_slice()),
+            // Prefix without upper bound like empty prefix covers all keys
+            // after it, delete them one by one instead.
+            None => {
+                batch.delete(path);
+                for key in self.blocking_scan(path)? {
+                    batch.delete(key);
+                }
+            }
+        }
+        Ok(self.db.write(batch)?)
+    }
+
     async fn scan(&self, path: &str) -> Result<Vec<String>> {
         let cloned_self = self.clone();
         let cloned_path = path.to_string();
@@ -191,6 +219,19 @@ impl kv::Adapter for Adapter {
     }
 }
 
+/// Returns the smallest key that is greater than all keys starting with
+/// given prefix, `None` if there is no such key.
+fn prefix_upper_bound(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut end = prefix.to_vec();
+    while let Some(last) = end.pop() {
+        if last < u8::MAX {
+            end.push(last + 1);
+            return Some(end);
+        }
+    }
+    None
+}
+
 impl From<rocksdb::Error> for Error {
     fn from(e: rocksdb::Error) -> Self {
         Error::new(ErrorKind::Unexpected, "got rocksdb error").set_source(e)
@@ -18,7 +18,6 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
-use std::fmt::Write;
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -44,6 +43,7 @@ use super::error::parse_s3_error_code;
 use super::lister::S3Lister;
 use super::writer::S3Writer;
 use super::writer::S3Writers;
+use crate::raw::oio::MultipartUploadWrite;
 use crate::raw::*;
 use crate::*;
 
@@ -60,6 +60,11 @@ static ENDPOINT_TEMPLATES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new
 
 const DEFAULT_BATCH_MAX_OPERATIONS: usize = 1000;
 
+/// The max size of object that could be copied by a single CopyObject.
+///
+/// ref: <https://docs.aws.amazon.com/AmazonS3/latest/API/API_CopyObject.html>
+const MAX_COPY_OBJECT_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+
 /// Config for Aws S3 and compatible services (including minio, digitalocean space, Tencent Cloud Object Storage(COS) and so on) support.
 #[derive(Default, Deserialize)]
 #[serde(default)]
@@ -107,12 +112,12 @@ pub struct S3Config {
     ///
     /// - If secret_access_key is set, we will take user's input first.
     /// - If not, we will try to load it from environment.
-    pub secret_access_key: Option<String>,
+    pub secret_access_key: Option<Secret>,
     /// security_token (aka, session token) of this backend.
     ///
     /// This token will expire after sometime, it's recommended to set security_token
     /// by hand.
-    pub security_token: Option<String>,
+    pub security_token: Option<Secret>,
     /// role_arn for this backend.
     ///
     /// If `role_arn` is set, we will use already known config as source
@@ -120,6 +125,12 @@ pub struct S3Config {
     pub role_arn: Option<String>,
     /// external_id for this backend.
     pub external_id: Option<String>,
+    /// sts_endpoint for this backend.
+    ///
+    /// STS endpoint is used to mint delegated credentials, the regional
+    /// endpoint like `https://sts.us-east-1.amazonaws.com` will be used
+    /// if not set.
+    pub sts_endpoint: Option<String>,
     /// Disable config load so that opendal will not load config from
     /// environment.
     ///
@@ -138,7 +149,7 @@ pub struct S3Config {
     pub allow_anonymous: bool,
     /// server_side_encryption for this backend.
     ///
-    /// Available values: `AES256`, `aws:kms`.
+    /// Available values: `AES256`, `aws:kms`, `aws:kms:dsse`.
     pub server_side_encryption: Option<String>,
     /// server_side_encryption_aws_kms_key_id for this backend
     ///
@@ -161,7 +172,7 @@ pub struct S3Config {
     ///
     /// base64 encoded key that matches algorithm specified in
     /// `server_side_encryption_customer_algorithm`.
-    pub server_side_encryption_customer_key: Option<String>,
+    pub server_side_encryption_customer_key: Option<Secret>,
     /// Set server_side_encryption_customer_key_md5 for this backend.
     ///
     /// # Value
@@ -196,6 +207,10 @@ pub struct S3Config {
     ///
     /// Please tune this value based on services' document.
     pub batch_max_operations: Option<usize>,
+    /// The local emulator that this backend connects to.
+    ///
+    /// Available values: `minio`, `localstack`.
+    pub emulator: Option<String>,
 }
 
 impl Debug for S3Config {
@@ -221,6 +236,7 @@ pub struct S3Builder {
 
     customed_credential_load: Option<Box<dyn AwsCredentialLoad>>,
     http_client: Option<HttpClient>,
+    http_client_builder: HttpClientBuilder,
 }
 
 impl Debug for S3Builder {
@@ -306,7 +322,7 @@ impl S3Builder {
     /// - If not, we will try to load it from environment.
     pub fn secret_access_key(&mut self, v: &str) -> &mut Self {
         if !v.is_empty() {
-            self.config.secret_access_key = Some(v.to_string())
+            self.config.secret_access_key = Some(v.into())
         }
 
         self
@@ -333,6 +349,20 @@ impl S3Builder {
         self
     }
 
+    /// Set sts_endpoint for this backend.
+    ///
+    /// STS endpoint is used to mint delegated credentials, the regional
+    /// endpoint like `https://sts.us-east-1.amazonaws.com` will be used
+    /// if not set.
+    pub fn sts_endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            // Trim trailing `/` so that we can accept `http://127.0.0.1:9000/`
+            self.config.sts_endpoint = Some(endpoint.trim_end_matches('/').to_string())
+        }
+
+        self
+    }
+
     /// Set default storage_class for this backend.
     ///
     /// Available values:
@@ -355,7 +385,7 @@ impl S3Builder {
 
     /// Set server_side_encryption for this backend.
     ///
-    /// Available values: `AES256`, `aws:kms`.
+    /// Available values: `AES256`, `aws:kms`, `aws:kms:dsse`.
     ///
     /// # Note
     ///
@@ -429,7 +459,7 @@ impl S3Builder {
     /// Please use `server_side_encryption_with_*` helpers if even possible.
     pub fn server_side_encryption_customer_key(&mut self, v: &str) -> &mut Self {
         if !v.is_empty() {
-            self.config.server_side_encryption_customer_key = Some(v.to_string())
+            self.config.server_side_encryption_customer_key = Some(v.into())
         }
 
         self
@@ -500,7 +530,7 @@ impl S3Builder {
         key: &[u8],
     ) -> &mut Self {
         self.config.server_side_encryption_customer_algorithm = Some(algorithm.to_string());
-        self.config.server_side_encryption_customer_key = Some(BASE64_STANDARD.encode(key));
+        self.config.server_side_encryption_customer_key = Some(BASE64_STANDARD.encode(key).into());
         self.config.server_side_encryption_customer_key_md5 =
             Some(BASE64_STANDARD.encode(Md5::digest(key).as_slice()));
         self
@@ -513,7 +543,7 @@ impl S3Builder {
     /// security token's lifetime is short and requires users to refresh in time.
     pub fn security_token(&mut self, token: &str) -> &mut Self {
         if !token.is_empty() {
-            self.config.security_token = Some(token.to_string());
+            self.config.security_token = Some(token.into());
         }
         self
     }
@@ -556,6 +586,53 @@ impl S3Builder {
         self
     }
 
+    /// Use the preset of given local emulator, only [`Emulator::Minio`] and
+    /// [`Emulator::LocalStack`] are supported.
+    ///
+    /// The preset fills the following configs if they are not set:
+    ///
+    /// - endpoint: `http://127.0.0.1:9000` for MinIO, `http://127.0.0.1:4566` for LocalStack.
+    /// - region: `us-east-1`
+    /// - access_key_id and secret_access_key: `minioadmin` for MinIO, `test` for LocalStack.
+    /// - sts_endpoint: the same as endpoint, both emulators serve STS there.
+    ///
+    /// Config loading from env and ec2 metadata will be disabled so that
+    /// real AWS credentials will never be sent to emulators. Path style
+    /// will be used unless virtual host style is enabled explicitly.
+    ///
+    /// Storage class transition by copy will be disabled for MinIO since it
+    /// only accepts `STANDARD` and `REDUCED_REDUNDANCY`.
+    pub fn emulator(&mut self, emulator: Emulator) -> &mut Self {
+        self.config.emulator = Some(emulator.to_string());
+        self
+    }
+
+    /// Fill the configs that are not set by users with the preset of emulator.
+    fn apply_emulator(&mut self, emulator: Emulator) -> Result<()> {
+        let default_key = match emulator {
+            Emulator::Minio => "minioadmin",
+            Emulator::LocalStack => "test",
+            v => return Err(v.unsupported(Scheme::S3)),
+        };
+
+        if self.config.endpoint.is_none() {
+            self.config.endpoint = Some(emulator.endpoint().to_string());
+        }
+        if self.config.region.is_none() {
+            self.config.region = Some("us-east-1".to_string());
+        }
+        if self.config.sts_endpoint.is_none() {
+            self.config.sts_endpoint = self.config.endpoint.clone();
+        }
+        if self.config.access_key_id.is_none() && self.config.secret_access_key.is_none() {
+            self.config.access_key_id = Some(default_key.to_string());
+            self.config.secret_access_key = Some(default_key.into());
+        }
+        self.config.disable_config_load = true;
+        self.config.disable_ec2_metadata = true;
+        Ok(())
+    }
+
     /// Adding a customed credential load for service.
     ///
     /// If customed_credential_load has been set, we will ignore all other
@@ -576,6 +653,110 @@ impl S3Builder {
         self
     }
 
+    /// Set the max idle connections per host in the pool of http client.
+    ///
+    /// Ignored if [`Self::http_client`] is set.
+    pub fn pool_max_idle_per_host(&mut self, max: usize) -> &mut Self {
+        self.http_client_builder =
+            std::mem::take(&mut self.http_client_builder).pool_max_idle_per_host(max);
+        self
+    }
+
+    /// Set the timeout for idle connections in the pool of http client.
+    ///
+    /// Ignored if [`Self::http_client`] is set.
+    pub fn pool_idle_timeout(&mut self, timeout: std::time::Duration) -> &mut Self {
+        self.http_client_builder =
+            std::mem::take(&mut self.http_client_builder).pool_idle_timeout(timeout);
+        self
+    }
+
+    /// Only use HTTP/2 for connections of http client.
+    ///
+    /// Ignored if [`Self::http_client`] is set.
+    pub fn http2_prior_knowledge(&mut self, enabled: bool) -> &mut Self {
+        self.http_client_builder =
+            std::mem::take(&mut self.http_client_builder).http2_prior_knowledge(enabled);
+        self
+    }
+
+    /// Set the interval of TCP keepalive for connections of http client.
+    ///
+    /// Ignored if [`Self::http_client`] is set.
+    pub fn tcp_keepalive(&mut self, interval: std::time::Duration) -> &mut Self {
+        self.http_client_builder =
+            std::mem::take(&mut self.http_client_builder).tcp_keepalive(interval);
+        self
+    }
+
+    /// Check the combinations of configs and report all problems at once.
+    fn validate(&self) -> Result<()> {
+        let cfg = &self.config;
+        let mut errs = ConfigError::new(Scheme::S3);
+
+        if cfg.bucket.is_empty() {
+            errs.push("bucket", "bucket is empty, set it to the name of bucket");
+        } else if !self.is_bucket_valid() {
+            errs.push(
+                "bucket",
+                "bucket contains `.` which can't be used with virtual host style, \
+                 disable enable_virtual_host_style or rename the bucket",
+            );
+        }
+        if cfg.access_key_id.is_some() && cfg.secret_access_key.is_none() {
+            errs.push(
+                "secret_access_key",
+                "access_key_id set but secret_access_key not provided",
+            );
+        }
+        if cfg.secret_access_key.is_some() && cfg.access_key_id.is_none() {
+            errs.push(
+                "access_key_id",
+                "secret_access_key set but access_key_id not provided",
+            );
+        }
+        if cfg.security_token.is_some() && cfg.access_key_id.is_none() {
+            errs.push(
+                "security_token",
+                "security_token set but access_key_id not provided; \
+                 temporary credentials require access_key_id and secret_access_key",
+            );
+        }
+        if cfg.external_id.is_some() && cfg.role_arn.is_none() {
+            errs.push(
+                "role_arn",
+                "external_id set but role_arn not provided, external_id is only used while assuming role",
+            );
+        }
+        if cfg.server_side_encryption_aws_kms_key_id.is_some()
+            && !matches!(
+                cfg.server_side_encryption.as_deref(),
+                Some("aws:kms" | "aws:kms:dsse")
+            )
+        {
+            errs.push(
+                "server_side_encryption",
+                "server_side_encryption_aws_kms_key_id set but server_side_encryption is not \
+                 `aws:kms` or `aws:kms:dsse`",
+            );
+        }
+        let sse_c = [
+            cfg.server_side_encryption_customer_algorithm.is_some(),
+            cfg.server_side_encryption_customer_key.is_some(),
+            cfg.server_side_encryption_customer_key_md5.is_some(),
+        ];
+        if sse_c.iter().any(|v| *v) && !sse_c.iter().all(|v| *v) {
+            errs.push(
+                "server_side_encryption_customer_key",
+                "server_side_encryption_customer_algorithm, server_side_encryption_customer_key \
+                 and server_side_encryption_customer_key_md5 must be set together, \
+                 use server_side_encryption_with_customer_key to set them",
+            );
+        }
+
+        errs.finish()
+    }
+
     /// Check if `bucket` is valid
     /// `bucket` must be not empty and if `enable_virtual_host_style` is true
     /// it couldn't contain dot(.) character
@@ -593,12 +774,37 @@ impl S3Builder {
     }
 
     /// Build endpoint with given region.
-    fn build_endpoint(&self, region: &str) -> String {
+    ///
+    /// Returns [`ErrorKind::ConfigInvalid`] if the endpoint can't be parsed,
+    /// or virtual host style is enabled on an IP endpoint.
+    fn build_endpoint(&self, region: &str) -> Result<String> {
         let bucket = {
             debug_assert!(self.is_bucket_valid(), "bucket must be valid");
 
             self.config.bucket.as_str()
         };
+        let endpoint = self.build_service_endpoint(region);
+
+        // Apply virtual host style.
+        let style = if self.config.enable_virtual_host_style {
+            EndpointStyle::VirtualHost
+        } else {
+            EndpointStyle::Path
+        };
+        let endpoint = Endpoint::parse(&endpoint)
+            .and_then(|ep| ep.with_bucket(bucket, style))
+            .map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::S3)
+            })?;
+
+        Ok(endpoint.to_string())
+    }
+
+    /// Build the endpoint of service without bucket, which is used by
+    /// requests like `ListBuckets`.
+    fn build_service_endpoint(&self, region: &str) -> String {
+        let bucket = self.config.bucket.as_str();
 
         let mut endpoint = match &self.config.endpoint {
             Some(endpoint) => {
@@ -616,22 +822,13 @@ impl S3Builder {
         endpoint = endpoint.replace(&format!("//{bucket}."), "//");
 
         // Update with endpoint templates.
-        endpoint = if let Some(template) = ENDPOINT_TEMPLATES.get(endpoint.as_str()) {
+        if let Some(template) = ENDPOINT_TEMPLATES.get(endpoint.as_str()) {
             template.replace("{region}", region)
         } else {
             // If we don't know where about this endpoint, just leave
             // them as it.
-            endpoint.to_string()
-        };
-
-        // Apply virtual host style.
-        if self.config.enable_virtual_host_style {
-            endpoint = endpoint.replace("//", &format!("//{bucket}."))
-        } else {
-            write!(endpoint, "/{bucket}").expect("write into string must succeed");
-        };
-
-        endpoint
+            endpoint
+        }
     }
 
     /// Set maximum batch operations of this backend.
@@ -769,6 +966,7 @@ impl Builder for S3Builder {
             config,
             customed_credential_load: None,
             http_client: None,
+            http_client_builder: HttpClientBuilder::default(),
         }
     }
 
@@ -778,6 +976,21 @@ impl Builder for S3Builder {
         let root = normalize_root(&self.config.root.clone().unwrap_or_default());
         debug!("backend use root {}", &root);
 
+        let emulator = match self.config.emulator.clone() {
+            Some(v) => {
+                let emulator = v.parse::<Emulator>().map_err(|err| {
+                    err.with_operation("Builder::build")
+                        .with_context("service", Scheme::S3)
+                })?;
+                self.apply_emulator(emulator)?;
+                debug!("backend use emulator {}", emulator);
+                Some(emulator)
+            }
+            None => None,
+        };
+
+        self.validate()?;
+
         // Handle bucket name.
         let bucket = if self.is_bucket_valid() {
             Ok(&self.config.bucket)
@@ -823,9 +1036,9 @@ impl Builder for S3Builder {
         let server_side_encryption_customer_key =
             match &self.config.server_side_encryption_customer_key {
                 None => None,
-                Some(v) => Some(build_header_value(v).map_err(|err| {
-                    err.with_context("key", "server_side_encryption_customer_key")
-                })?),
+                Some(v) => Some(build_sensitive_header_value(v.expose_secret()).map_err(
+                    |err| err.with_context("key", "server_side_encryption_customer_key"),
+                )?),
             };
 
         let server_side_encryption_customer_key_md5 =
@@ -839,7 +1052,7 @@ impl Builder for S3Builder {
         let client = if let Some(client) = self.http_client.take() {
             client
         } else {
-            HttpClient::new().map_err(|err| {
+            self.http_client_builder.clone().build().map_err(|err| {
                 err.with_operation("Builder::build")
                     .with_context("service", Scheme::S3)
             })?
@@ -868,7 +1081,11 @@ impl Builder for S3Builder {
         debug!("backend use region: {region}");
 
         // Building endpoint.
-        let endpoint = self.build_endpoint(&region);
+        let endpoint = self.build_endpoint(&region)?;
+        let service_endpoint = self
+            .build_service_endpoint(&region)
+            .trim_end_matches('/')
+            .to_string();
         debug!("backend use endpoint: {endpoint}");
 
         // Setting all value from user input if available.
@@ -876,10 +1093,10 @@ impl Builder for S3Builder {
             cfg.access_key_id = Some(v)
         }
         if let Some(v) = self.config.secret_access_key.take() {
-            cfg.secret_access_key = Some(v)
+            cfg.secret_access_key = Some(v.into_secret())
         }
         if let Some(v) = self.config.security_token.take() {
-            cfg.session_token = Some(v)
+            cfg.session_token = Some(v.into_secret())
         }
 
         let mut loader: Option<Box<dyn AwsCredentialLoad>> = None;
@@ -889,7 +1106,8 @@ impl Builder for S3Builder {
         }
 
         // If role_arn is set, we must use AssumeRoleLoad.
-        if let Some(role_arn) = self.config.role_arn.take() {
+        let role_arn = self.config.role_arn.take();
+        if let Some(role_arn) = role_arn.clone() {
             // use current env as source credential loader.
             let default_loader = AwsDefaultLoader::new(client.client(), cfg.clone());
 
@@ -931,6 +1149,18 @@ impl Builder for S3Builder {
 
         let signer = AwsV4Signer::new("s3", &region);
 
+        let sts_endpoint = match self.config.sts_endpoint.take() {
+            Some(v) => v,
+            None => {
+                let suffix = if region.starts_with("cn-") {
+                    "amazonaws.com.cn"
+                } else {
+                    "amazonaws.com"
+                };
+                format!("https://sts.{region}.{suffix}")
+            }
+        };
+
         let batch_max_operations = self
             .config
             .batch_max_operations
@@ -940,7 +1170,11 @@ impl Builder for S3Builder {
             core: Arc::new(S3Core {
                 bucket: bucket.to_string(),
                 endpoint,
+                service_endpoint,
                 root,
+                region,
+                role_arn,
+                sts_endpoint,
                 server_side_encryption,
                 server_side_encryption_aws_kms_key_id,
                 server_side_encryption_customer_algorithm,
@@ -953,6 +1187,7 @@ impl Builder for S3Builder {
                 client,
                 batch_max_operations,
             }),
+            emulator,
         })
     }
 }
@@ -961,6 +1196,211 @@ impl Builder for S3Builder {
 #[derive(Debug, Clone)]
 pub struct S3Backend {
     core: Arc<S3Core>,
+    emulator: Option<Emulator>,
+}
+
+/// Split an object of given size into ranges that can be copied by one
+/// UploadPartCopy.
+fn split_copy_source(size: u64) -> Vec<BytesRange> {
+    (0..size)
+        .step_by(MAX_COPY_OBJECT_SIZE as usize)
+        .map(|offset| {
+            let len = MAX_COPY_OBJECT_SIZE.min(size - offset);
+            BytesRange::new(Some(offset), Some(len))
+        })
+        .collect()
+}
+
+impl S3Backend {
+    /// Fetch creation time of the bucket via `ListBuckets`.
+    ///
+    /// HeadBucket doesn't return it, and users may not have permission to
+    /// list buckets, so failures are logged and ignored.
+    async fn bucket_created(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let res = async {
+            let resp = self.core.s3_list_buckets(&self.core.bucket, "").await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let output: ListBucketsOutput =
+                quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+            match output
+                .buckets
+                .bucket
+                .into_iter()
+                .find(|v| v.name == self.core.bucket && !v.creation_date.is_empty())
+            {
+                Some(v) => parse_datetime_from_rfc3339(&v.creation_date).map(Some),
+                None => Ok(None),
+            }
+        }
+        .await;
+
+        res.unwrap_or_else(|err: Error| {
+            debug!("fetch creation time of bucket failed: {err}");
+            None
+        })
+    }
+
+    /// Copy given ranges of sources into `path` via multipart upload, every
+    /// source will be copied as a part by UploadPartCopy.
+    ///
+    /// The upload will be aborted if any of the parts failed.
+    async fn copy_parts(
+        &self,
+        path: &str,
+        args: OpWrite,
+        sources: Vec<(&str, BytesRange)>,
+    ) -> Result<()> {
+        // Reuse the multipart upload of writer.
+        let w = S3Writer::new(self.core.clone(), path, args);
+        let upload_id = w.initiate_part().await?;
+
+        let res = async {
+            let mut parts = Vec::with_capacity(sources.len());
+            for (idx, (source, range)) in sources.into_iter().enumerate() {
+                // AWS S3 requires part number must between [1..=10000]
+                let part_number = idx + 1;
+                let resp = self
+                    .core
+                    .s3_upload_part_copy(path, &upload_id, part_number, source, range)
+                    .await?;
+
+                match resp.status() {
+                    StatusCode::OK => {
+                        let bs = resp.into_body().bytes().await?;
+                        let result: CopyPartResult = quick_xml::de::from_reader(bs.reader())
+                            .map_err(new_xml_deserialize_error)?;
+                        parts.push(oio::MultipartUploadPart {
+                            part_number,
+                            etag: result.etag,
+                        });
+                    }
+                    _ => return Err(parse_error(resp).await?.with_context("source", source)),
+                }
+            }
+            w.complete_part(&upload_id, &parts).await
+        }
+        .await;
+
+        if let Err(err) = res {
+            if let Err(e) = w.abort_part(&upload_id).await {
+                warn!("abort multipart upload {upload_id} of {path} failed: {e}");
+            }
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    async fn object_tags(&self, path: &str) -> Result<HashMap<String, String>> {
+        let resp = self.core.s3_get_object_tagging(path).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: Tagging =
+            quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+        Ok(output
+            .tag_set
+            .tag
+            .into_iter()
+            .map(|v| (v.key, v.value))
+            .collect())
+    }
+
+    /// Transition the storage class of given object in place.
+    ///
+    /// S3 can only change storage class by copying the object onto itself,
+    /// which resets its last modified time. So the original one will be kept
+    /// in user metadata `opendal-last-modified` to make age based rules of
+    /// [`TierMover`] still work after transitions. Objects larger than 5 GiB
+    /// are copied part by part with their tags, and the superseded version
+    /// will be removed on versioned buckets.
+    async fn transition(&self, path: &str, args: OpCopy) -> Result<()> {
+        let resp = self.core.s3_head_object(path, None, None, None).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+        let headers = resp.headers().clone();
+
+        let mut user_metadata = parse_user_metadata(&headers)?;
+        if !user_metadata.contains_key(ORIGINAL_LAST_MODIFIED) {
+            if let Some(v) = parse_last_modified(&headers)? {
+                user_metadata.insert(ORIGINAL_LAST_MODIFIED.to_string(), v.to_rfc3339());
+            }
+        }
+        if let Some(v) = args.user_metadata() {
+            user_metadata.extend(v.clone());
+        }
+
+        let size = parse_content_length(&headers)?.ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "content length is missing in response",
+            )
+            .with_context("path", path)
+        })?;
+        if size <= MAX_COPY_OBJECT_SIZE {
+            let args = args.with_user_metadata(user_metadata);
+            let resp = self
+                .core
+                .s3_copy_object(path, path, &args, Some(&headers))
+                .await?;
+            match resp.status() {
+                StatusCode::OK => resp.into_body().consume().await?,
+                _ => return Err(parse_error(resp).await?),
+            }
+        } else {
+            let mut op = OpWrite::default().with_user_metadata(user_metadata);
+            if let Some(v) = args.storage_class() {
+                op = op.with_storage_class(v);
+            }
+            if let Some(v) = args.content_type().or(parse_content_type(&headers)?) {
+                op = op.with_content_type(v);
+            }
+            if let Some(v) = args.cache_control().or(parse_cache_control(&headers)?) {
+                op = op.with_cache_control(v);
+            }
+            if let Some(v) = parse_content_disposition(&headers)? {
+                op = op.with_content_disposition(v);
+            }
+            // Tags are not copied by UploadPartCopy, carry them while
+            // initiating the upload.
+            if let Some(v) = args.tags() {
+                op = op.with_tags(v.clone());
+            } else if parse_header_to_str(&headers, constants::X_AMZ_TAGGING_COUNT)?
+                .map_or(false, |v| v != "0")
+            {
+                op = op.with_tags(self.object_tags(path).await?);
+            }
+
+            let sources = split_copy_source(size)
+                .into_iter()
+                .map(|range| (path, range))
+                .collect();
+            self.copy_parts(path, op, sources).await?;
+        }
+
+        // The copy has created a new version on versioned buckets, remove the
+        // superseded one so that it will not be billed in the old class.
+        if let Some(version) = parse_header_to_str(&headers, constants::X_AMZ_VERSION_ID)? {
+            if version != "null" {
+                let res = match self.core.s3_delete_object_version(path, version).await {
+                    Ok(resp) if resp.status() == StatusCode::NO_CONTENT => Ok(()),
+                    Ok(resp) => Err(parse_error(resp).await?),
+                    Err(err) => Err(err),
+                };
+                if let Err(err) = res {
+                    warn!("delete superseded version {version} of {path} failed: {err}");
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -973,14 +1413,25 @@ impl Accessor for S3Backend {
     type BlockingLister = ();
 
     fn info(&self) -> AccessorInfo {
+        // MinIO only accepts `STANDARD` and `REDUCED_REDUNDANCY`, objects
+        // are transitioned by ILM instead.
+        //
+        // ref: <https://min.io/docs/minio/linux/administration/object-management/object-lifecycle-management.html>
+        let is_minio = self.emulator == Some(Emulator::Minio);
+
         let mut am = AccessorInfo::default();
         am.set_scheme(Scheme::S3)
             .set_root(&self.core.root)
             .set_name(&self.core.bucket)
+            .set_endpoint(&self.core.endpoint)
+            .set_location(&self.core.region)
+            .set_signer(self.core.clone())
             .set_native_capability(Capability {
                 stat: true,
                 stat_with_if_match: true,
                 stat_with_if_none_match: true,
+                stat_with_signer: true,
+                stat_root: true,
 
                 read: true,
                 read_can_next: true,
@@ -990,12 +1441,18 @@ impl Accessor for S3Backend {
                 read_with_override_cache_control: true,
                 read_with_override_content_disposition: true,
                 read_with_override_content_type: true,
+                read_with_signer: true,
 
                 write: true,
                 write_can_empty: true,
                 write_can_multi: true,
                 write_with_cache_control: true,
                 write_with_content_type: true,
+                write_with_if_match: true,
+                write_with_if_none_match: true,
+                write_with_signer: true,
+                write_with_retention: true,
+                write_with_legal_hold: true,
                 // The min multipart size of S3 is 5 MiB.
                 //
                 // ref: <https://docs.aws.amazon.com/AmazonS3/latest/userguide/qfacts.html>
@@ -1008,14 +1465,38 @@ impl Accessor for S3Backend {
                 } else {
                     Some(usize::MAX)
                 },
+                // The max parts of S3 multipart upload is 10000.
+                //
+                // ref: <https://docs.aws.amazon.com/AmazonS3/latest/userguide/qfacts.html>
+                write_multi_max_parts: Some(10000),
+                // The max object size of S3 is 5 TiB.
+                //
+                // ref: <https://docs.aws.amazon.com/AmazonS3/latest/userguide/qfacts.html>
+                write_total_max_size: if cfg!(target_pointer_width = "64") {
+                    Some(5 * 1024 * 1024 * 1024 * 1024)
+                } else {
+                    Some(usize::MAX)
+                },
 
                 delete: true,
                 copy: true,
+                copy_with_storage_class: !is_minio,
+                copy_with_content_type: true,
+                copy_with_cache_control: true,
+                copy_with_user_metadata: true,
+                copy_with_tags: true,
+                concat: true,
 
                 list: true,
                 list_with_limit: true,
+                // The max page size of S3 list is 1000.
+                //
+                // ref: <https://docs.aws.amazon.com/AmazonS3/latest/API/API_ListObjectsV2.html>
+                list_max_page_size: Some(1000),
+                list_roots: true,
                 list_with_start_after: true,
                 list_with_recursive: true,
+                list_with_delimiter: true,
                 list_without_recursive: true,
 
                 presign: true,
@@ -1040,7 +1521,11 @@ impl Accessor for S3Backend {
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
                 let size = parse_content_length(resp.headers())?;
-                Ok((RpRead::new().with_size(size), resp.into_body()))
+                let etag = parse_etag(resp.headers())?.map(|v| v.to_string());
+                Ok((
+                    RpRead::new().with_size(size).with_etag(etag),
+                    resp.into_body(),
+                ))
             }
             StatusCode::RANGE_NOT_SATISFIABLE => Ok((RpRead::new(), IncomingAsyncBody::empty())),
             _ => Err(parse_error(resp).await?),
@@ -1048,15 +1533,34 @@ impl Accessor for S3Backend {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let concurrent = args.concurrent();
         let writer = S3Writer::new(self.core.clone(), path, args);
 
-        let w = oio::MultipartUploadWriter::new(writer);
+        let w = oio::MultipartUploadWriter::new(writer, concurrent);
 
         Ok((RpWrite::default(), w))
     }
 
-    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.s3_copy_object(from, to).await?;
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        if from == to && args.storage_class().is_some() {
+            self.transition(from, args).await?;
+            return Ok(RpCopy::default());
+        }
+
+        let source_headers = if args.replace_metadata() {
+            let resp = self.core.s3_head_object(from, None, None, None).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+            Some(resp.headers().clone())
+        } else {
+            None
+        };
+
+        let resp = self
+            .core
+            .s3_copy_object(from, to, &args, source_headers.as_ref())
+            .await?;
 
         let status = resp.status();
 
@@ -1072,16 +1576,107 @@ impl Accessor for S3Backend {
         }
     }
 
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        // The max parts of S3 multipart upload is 10000.
+        //
+        // ref: <https://docs.aws.amazon.com/AmazonS3/latest/userguide/qfacts.html>
+        if args.sources().len() > 10000 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "s3 can't concat more than 10000 sources",
+            ));
+        }
+
+        // Sources larger than 5 GiB can't be copied by one UploadPartCopy,
+        // they are split into parts by `x-amz-copy-source-range`.
+        let mut sources = Vec::with_capacity(args.sources().len());
+        for source in args.sources() {
+            let resp = self.core.s3_head_object(source, None, None, None).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?.with_context("source", source));
+            }
+            let size = parse_content_length(resp.headers())?.ok_or_else(|| {
+                Error::new(
+                    ErrorKind::Unexpected,
+                    "content length is missing in response",
+                )
+                .with_context("source", source)
+            })?;
+            resp.into_body().consume().await?;
+
+            if size > MAX_COPY_OBJECT_SIZE {
+                sources.extend(
+                    split_copy_source(size)
+                        .into_iter()
+                        .map(|range| (source.as_str(), range)),
+                );
+            } else {
+                sources.push((source.as_str(), BytesRange::default()));
+            }
+        }
+        if sources.len() > 10000 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "s3 can't concat sources into more than 10000 parts",
+            ));
+        }
+        self.copy_parts(path, OpWrite::default(), sources).await?;
+
+        Ok(RpConcat::default())
+    }
+
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if path == "/" {
+            let resp = self.core.s3_head_bucket().await?;
+
+            return match resp.status() {
+                StatusCode::OK => {
+                    let region =
+                        parse_header_to_str(resp.headers(), constants::X_AMZ_BUCKET_REGION)?
+                            .unwrap_or(&self.core.region);
+                    let mut meta = Metadata::new(EntryMode::DIR).with_region(region.to_string());
+                    if args.need_field(Metakey::Created) {
+                        if let Some(created) = self.bucket_created().await {
+                            meta.set_created(created);
+                        }
+                    }
+                    Ok(RpStat::new(meta))
+                }
+                // Users with permissions on objects only, or buckets behind
+                // gateways that don't implement HeadBucket, still get a dir.
+                StatusCode::NOT_FOUND
+                | StatusCode::FORBIDDEN
+                | StatusCode::MOVED_PERMANENTLY
+                | StatusCode::METHOD_NOT_ALLOWED => {
+                    resp.into_body().consume().await?;
+                    Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
+                }
+                _ => Err(parse_error(resp).await?),
+            };
+        }
+
         let resp = self
             .core
-            .s3_head_object(path, args.if_none_match(), args.if_match())
+            .s3_head_object(path, args.if_none_match(), args.if_match(), args.signer())
             .await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::OK => parse_into_metadata(path, resp.headers()).map(RpStat::new),
+            StatusCode::OK => {
+                let mut meta =
+                    parse_into_metadata_with_fields(path, resp.headers(), args.fields())?;
+                if args.need_field(Metakey::Retention) || args.need_field(Metakey::LegalHold) {
+                    parse_object_lock(resp.headers(), &mut meta)?;
+                }
+                if args.need_field(Metakey::StorageClass) {
+                    parse_storage_class(resp.headers(), &mut meta)?;
+                }
+                if args.need_field(Metakey::UserMetadata) {
+                    meta.set_user_metadata(parse_user_metadata(resp.headers())?);
+                }
+                Ok(RpStat::new(meta))
+            }
             _ => Err(parse_error(resp).await?),
         }
     }
@@ -1106,12 +1701,45 @@ impl Accessor for S3Backend {
             self.core.clone(),
             path,
             args.recursive(),
+            args.delimiter(),
             args.limit(),
             args.start_after(),
         );
         Ok((RpList::default(), oio::PageLister::new(l)))
     }
 
+    async fn list_roots(&self, _: OpListRoots) -> Result<RpListRoots> {
+        let mut entries = vec![];
+        let mut token = String::new();
+        loop {
+            let resp = self.core.s3_list_buckets("", &token).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let output: ListBucketsOutput =
+                quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+            for bucket in output.buckets.bucket {
+                let mut meta = Metadata::new(EntryMode::DIR);
+                // Skip the date instead of failing the whole listing.
+                match parse_datetime_from_rfc3339(&bucket.creation_date) {
+                    Ok(v) => meta.set_created(v),
+                    Err(err) if !bucket.creation_date.is_empty() => {
+                        warn!("bucket {} has invalid creation date: {err}", bucket.name)
+                    }
+                    Err(_) => {}
+                }
+                entries.push(oio::Entry::with(format!("{}/", bucket.name), meta));
+            }
+
+            match output.continuation_token {
+                Some(v) if !v.is_empty() => token = v,
+                _ => return Ok(RpListRoots::new(entries)),
+            }
+        }
+    }
+
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         // We will not send this request out, just for signing.
         let mut req = match args.operation() {
@@ -1173,7 +1801,8 @@ impl Accessor for S3Backend {
                 // set the error kind and mark temporary if retryable
                 let (kind, retryable) =
                     parse_s3_error_code(i.code.as_str()).unwrap_or((ErrorKind::Unexpected, false));
-                let mut err: Error = Error::new(kind, &format!("{i:?}"));
+                let mut err: Error =
+                    Error::new(kind, &format!("{i:?}")).with_context("code", i.code);
                 if retryable {
                     err = err.set_temporary();
                 }
@@ -1213,6 +1842,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_kms_key_id() {
+        for (sse, valid) in [("aws:kms", true), ("aws:kms:dsse", true), ("AES256", false)] {
+            let mut b = S3Builder::default();
+            b.bucket("test");
+            b.server_side_encryption(sse);
+            b.server_side_encryption_aws_kms_key_id("key-id");
+            assert_eq!(b.validate().is_ok(), valid, "{sse}");
+        }
+    }
+
+    #[test]
+    fn test_emulator() {
+        let mut b = S3Builder::default();
+        b.bucket("test");
+        b.access_key_id("access_key");
+        b.secret_access_key("secret_key");
+        b.emulator(Emulator::Minio);
+        let backend = b.build().expect("build must succeed");
+
+        assert_eq!(b.config.endpoint.as_deref(), Some("http://127.0.0.1:9000"));
+        assert_eq!(b.config.region.as_deref(), Some("us-east-1"));
+        assert_eq!(b.config.access_key_id.as_deref(), Some("access_key"));
+        assert_eq!(
+            b.config.sts_endpoint.as_deref(),
+            Some("http://127.0.0.1:9000")
+        );
+        assert!(b.config.disable_config_load);
+        assert!(!backend.info().full_capability().copy_with_storage_class);
+
+        let mut b = S3Builder::default();
+        b.bucket("test");
+        b.emulator(Emulator::LocalStack);
+        let backend = b.build().expect("build must succeed");
+        assert_eq!(b.config.endpoint.as_deref(), Some("http://127.0.0.1:4566"));
+        assert_eq!(b.config.access_key_id.as_deref(), Some("test"));
+        assert!(backend.info().full_capability().copy_with_storage_class);
+
+        let mut b = S3Builder::default();
+        b.bucket("test");
+        b.emulator(Emulator::Azurite);
+        let err = b.build().expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
     #[test]
     fn test_build_endpoint() {
         let _ = tracing_subscriber::fmt().with_test_writer().try_init();
@@ -1231,7 +1905,9 @@ mod tests {
                 b.endpoint(endpoint);
             }
 
-            let endpoint = b.build_endpoint("us-east-2");
+            let endpoint = b
+                .build_endpoint("us-east-2")
+                .expect("endpoint must be valid");
             assert_eq!(endpoint, "https://s3.us-east-2.amazonaws.com/test");
         }
 
@@ -1243,9 +1919,24 @@ mod tests {
                 b.endpoint(endpoint);
             }
 
-            let endpoint = b.build_endpoint("us-east-2");
+            let endpoint = b
+                .build_endpoint("us-east-2")
+                .expect("endpoint must be valid");
             assert_eq!(endpoint, "https://test.s3.us-east-2.amazonaws.com");
         }
+
+        let mut b = S3Builder::default();
+        b.bucket("test");
+        b.endpoint("http://[::1]:9000");
+        let endpoint = b
+            .build_endpoint("us-east-2")
+            .expect("endpoint must be valid");
+        assert_eq!(endpoint, "http://[::1]:9000/test");
+
+        // Virtual host style can't be used with IP endpoints.
+        b.enable_virtual_host_style();
+        let err = b.build_endpoint("us-east-2").expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
     }
 
     #[tokio::test]
@@ -1294,4 +1985,745 @@ mod tests {
             assert_eq!(region.as_deref(), expected, "{}", name);
         }
     }
+
+    #[derive(Debug)]
+    struct TenantSigner;
+
+    #[async_trait]
+    impl RequestSign for TenantSigner {
+        async fn sign(&self, req: &mut http::Request<AsyncBody>) -> Result<()> {
+            req.headers_mut()
+                .insert("x-tenant", http::HeaderValue::from_static("a"));
+            Ok(())
+        }
+    }
+
+    async fn mount_tenant_object(server: &wiremock::MockServer) {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::ResponseTemplate;
+
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/file"))
+            .and(header("x-tenant", "a"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-length", "5"))
+            .expect(1)
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/file"))
+            .and(header("x-tenant", "a"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("hello"))
+            .mount(server)
+            .await;
+    }
+
+    fn new_mock_operator(server: &wiremock::MockServer) -> Operator {
+        let mut builder = S3Builder::default();
+        builder
+            .endpoint(&server.uri())
+            .bucket("bucket")
+            .region("us-east-1")
+            .disable_config_load()
+            .disable_ec2_metadata()
+            .allow_anonymous();
+        Operator::new(builder).unwrap().finish()
+    }
+
+    #[tokio::test]
+    async fn test_delegate_signed_by_sts() -> Result<()> {
+        use wiremock::matchers::body_string_contains;
+        use wiremock::matchers::header_regex;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::ResponseTemplate;
+
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/"))
+            .and(header_regex(
+                "authorization",
+                "^AWS4-HMAC-SHA256 Credential=access_key/[0-9]{8}/us-east-1/sts/aws4_request, \
+                 SignedHeaders=[a-z0-9;-]+, Signature=[0-9a-f]{64}$",
+            ))
+            .and(body_string_contains("Action=GetFederationToken"))
+            .and(body_string_contains("DurationSeconds=3600"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<GetFederationTokenResponse>
+                    <GetFederationTokenResult>
+                        <Credentials>
+                            <AccessKeyId>delegated_key</AccessKeyId>
+                            <SecretAccessKey>delegated_secret</SecretAccessKey>
+                            <SessionToken>token</SessionToken>
+                            <Expiration>2023-01-01T01:00:00Z</Expiration>
+                        </Credentials>
+                    </GetFederationTokenResult>
+                </GetFederationTokenResponse>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder
+            .endpoint(&server.uri())
+            .sts_endpoint(&server.uri())
+            .bucket("bucket")
+            .region("us-east-1")
+            .access_key_id("access_key")
+            .secret_access_key("secret_key")
+            .disable_config_load()
+            .disable_ec2_metadata();
+        let op = Operator::new(builder)?.finish();
+
+        let scope = DelegateScope::Read | DelegateScope::List;
+        let cred = op
+            .delegate("tenant-a/", scope, std::time::Duration::from_secs(3600))
+            .await?;
+        let DelegatedCredential::Aws { access_key_id, .. } = cred else {
+            panic!("s3 must mint aws credential");
+        };
+        assert_eq!(access_key_id, "delegated_key");
+
+        let err = op
+            .delegate("tenant-a/", scope, std::time::Duration::from_secs(60))
+            .await
+            .expect_err("expire shorter than 15 minutes must be rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stat_root() -> Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::ResponseTemplate;
+
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/"))
+            .respond_with(
+                ResponseTemplate::new(200).insert_header("x-amz-bucket-region", "eu-west-1"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .and(query_param("prefix", "bucket"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListAllMyBucketsResult>
+                    <Buckets>
+                        <Bucket><Name>bucket</Name><CreationDate>2023-01-01T00:00:00Z</CreationDate></Bucket>
+                        <Bucket><Name>bucket-2</Name><CreationDate>2023-02-01T00:00:00Z</CreationDate></Bucket>
+                    </Buckets>
+                </ListAllMyBucketsResult>"#,
+            ))
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        let meta = op.stat("/").await?;
+        assert!(meta.is_dir());
+        assert_eq!(meta.region(), Some("eu-west-1"));
+        assert_eq!(
+            meta.created(),
+            Some(parse_datetime_from_rfc3339("2023-01-01T00:00:00Z")?)
+        );
+        // Location is queried from bucket instead of the configured region.
+        assert_eq!(op.location().await?.as_deref(), Some("eu-west-1"));
+
+        // HeadBucket is not allowed anymore, stat on root still returns a dir.
+        for status in [404, 301, 405] {
+            server.reset().await;
+            Mock::given(method("HEAD"))
+                .and(path("/bucket/"))
+                .respond_with(ResponseTemplate::new(status))
+                .mount(&server)
+                .await;
+            let meta = op.stat("/").await?;
+            assert!(meta.is_dir(), "{status}");
+            assert_eq!(meta.region(), None, "{status}");
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_with_custom_delimiter() -> Result<()> {
+        use futures::TryStreamExt;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::ResponseTemplate;
+
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bucket"))
+            .and(query_param("delimiter", "-"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListBucketResult>
+                    <IsTruncated>false</IsTruncated>
+                    <CommonPrefixes><Prefix>logs/a-</Prefix></CommonPrefixes>
+                    <CommonPrefixes><Prefix>logs/b/</Prefix></CommonPrefixes>
+                    <Contents>
+                        <Key>logs/c</Key>
+                        <Size>1</Size>
+                        <LastModified>2023-01-01T00:00:00Z</LastModified>
+                    </Contents>
+                </ListBucketResult>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        let entries: Vec<_> = op
+            .lister_with("logs/")
+            .delimiter("-")
+            .await?
+            .try_collect()
+            .await?;
+        let entries: Vec<_> = entries
+            .iter()
+            .map(|v| (v.path(), v.metadata().mode()))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("logs/a-", EntryMode::FILE),
+                ("logs/b/", EntryMode::DIR),
+                ("logs/c", EntryMode::FILE),
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_with_signer_stats_with_signer() -> Result<()> {
+        let server = wiremock::MockServer::start().await;
+        mount_tenant_object(&server).await;
+        let op = new_mock_operator(&server);
+
+        let signer: RequestSigner = Arc::new(TenantSigner);
+        let bs = op.read_with("file").signer(signer).await?;
+        assert_eq!(bs, b"hello");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reader_with_signer_stats_with_signer() -> Result<()> {
+        use futures::AsyncSeekExt;
+
+        let server = wiremock::MockServer::start().await;
+        mount_tenant_object(&server).await;
+        let op = new_mock_operator(&server);
+
+        let signer: RequestSigner = Arc::new(TenantSigner);
+        let mut r = op.reader_with("file").signer(signer).await?;
+        let pos = r
+            .seek(std::io::SeekFrom::End(0))
+            .await
+            .expect("seek must succeed");
+        assert_eq!(pos, 5);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_with_preserves_metadata() -> Result<()> {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::ResponseTemplate;
+
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/from"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "10")
+                    .insert_header("content-type", "application/octet-stream")
+                    .insert_header("cache-control", "no-cache")
+                    .insert_header("content-encoding", "gzip")
+                    .insert_header("content-language", "en")
+                    .insert_header("expires", "Wed, 01 Jan 2020 00:00:00 GMT")
+                    .insert_header("x-amz-website-redirect-location", "/index.html")
+                    .insert_header("x-amz-meta-owner", "alice"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/to"))
+            .and(header("x-amz-copy-source", "bucket/from"))
+            .and(header("x-amz-metadata-directive", "REPLACE"))
+            .and(header("content-type", "text/plain"))
+            .and(header("cache-control", "no-cache"))
+            .and(header("content-encoding", "gzip"))
+            .and(header("content-language", "en"))
+            .and(header("expires", "Wed, 01 Jan 2020 00:00:00 GMT"))
+            .and(header("x-amz-website-redirect-location", "/index.html"))
+            .and(header("x-amz-meta-owner", "alice"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        op.copy_with("from", "to")
+            .content_type("text/plain")
+            .await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_storage_class() -> Result<()> {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::ResponseTemplate;
+
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "10")
+                    .insert_header("last-modified", "Wed, 01 Jan 2020 00:00:00 GMT")
+                    .insert_header("x-amz-version-id", "v1")
+                    .insert_header("x-amz-meta-owner", "alice"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/file"))
+            .and(header("x-amz-copy-source", "bucket/file"))
+            .and(header("x-amz-storage-class", "GLACIER"))
+            .and(header("x-amz-metadata-directive", "REPLACE"))
+            .and(header("x-amz-meta-owner", "alice"))
+            .and(header(
+                "x-amz-meta-opendal-last-modified",
+                "2020-01-01T00:00:00+00:00",
+            ))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/bucket/file"))
+            .and(query_param("versionId", "v1"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        op.set_storage_class("file", "GLACIER").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_storage_class_larger_than_5gib() -> Result<()> {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::ResponseTemplate;
+
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", (6u64 << 30).to_string())
+                    .insert_header("x-amz-tagging-count", "1")
+                    .insert_header("x-amz-meta-opendal-last-modified", "2020-01-01T00:00:00Z"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/file"))
+            .and(query_param("tagging", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<Tagging><TagSet><Tag><Key>team</Key><Value>infra</Value></Tag></TagSet></Tagging>",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/file"))
+            .and(query_param("uploads", ""))
+            .and(header("x-amz-storage-class", "GLACIER"))
+            .and(header("x-amz-tagging", "team=infra"))
+            .and(header(
+                "x-amz-meta-opendal-last-modified",
+                "2020-01-01T00:00:00Z",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<InitiateMultipartUploadResult><UploadId>u1</UploadId></InitiateMultipartUploadResult>",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        for (part_number, range) in [
+            (1, "bytes=0-5368709119"),
+            (2, "bytes=5368709120-6442450943"),
+        ] {
+            Mock::given(method("PUT"))
+                .and(path("/bucket/file"))
+                .and(query_param("partNumber", part_number.to_string()))
+                .and(query_param("uploadId", "u1"))
+                .and(header("x-amz-copy-source", "bucket/file"))
+                .and(header("x-amz-copy-source-range", range))
+                .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                    "<CopyPartResult><ETag>\"e{part_number}\"</ETag></CopyPartResult>"
+                )))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("POST"))
+            .and(path("/bucket/file"))
+            .and(query_param("uploadId", "u1"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        op.set_storage_class("file", "GLACIER").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tier_mover_with_original_last_modified() -> Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::ResponseTemplate;
+
+        let server = wiremock::MockServer::start().await;
+        // The file has been transitioned to STANDARD_IA, which reset its
+        // last modified time.
+        let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        Mock::given(method("GET"))
+            .and(path("/bucket"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                r#"<ListBucketResult>
+                    <IsTruncated>false</IsTruncated>
+                    <Contents>
+                        <Key>logs/a</Key>
+                        <Size>1</Size>
+                        <LastModified>{now}</LastModified>
+                        <StorageClass>STANDARD_IA</StorageClass>
+                    </Contents>
+                </ListBucketResult>"#
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/logs/a"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "1")
+                    .insert_header("x-amz-storage-class", "STANDARD_IA")
+                    .insert_header("x-amz-meta-opendal-last-modified", "2020-01-01T00:00:00Z"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        let day = std::time::Duration::from_secs(24 * 3600);
+        let report = TierMover::new(op)
+            .with_rule(TierRule::new("STANDARD_IA").older_than(30 * day))
+            .with_rule(TierRule::new("GLACIER").older_than(180 * day))
+            .with_dry_run(true)
+            .run("logs/")
+            .await?;
+        assert_eq!(report.transitions().len(), 1);
+        let t = &report.transitions()[0];
+        assert_eq!(t.from(), Some("STANDARD_IA"));
+        assert_eq!(t.to(), "GLACIER");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_roots() -> Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::ResponseTemplate;
+
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .and(query_param("continuation-token", "next"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListAllMyBucketsResult>
+                  <Buckets>
+                    <Bucket><Name>b</Name><CreationDate>yesterday</CreationDate></Bucket>
+                  </Buckets>
+                </ListAllMyBucketsResult>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListAllMyBucketsResult>
+                  <Buckets>
+                    <Bucket><Name>a</Name><CreationDate>2023-01-01T00:00:00.000Z</CreationDate></Bucket>
+                  </Buckets>
+                  <ContinuationToken>next</ContinuationToken>
+                </ListAllMyBucketsResult>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        let roots = op.list_roots().await?;
+        let names: Vec<_> = roots.iter().map(|v| v.path()).collect();
+        assert_eq!(names, ["a/", "b/"]);
+        assert!(roots.iter().all(|v| v.metadata().is_dir()));
+        assert_eq!(
+            roots[0].metadata().created().map(|v| v.to_rfc3339()),
+            Some("2023-01-01T00:00:00+00:00".to_string())
+        );
+        // Malformed date is skipped instead of failing the listing.
+        assert_eq!(roots[1].metadata().created(), None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_concat_splits_large_source() -> Result<()> {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::ResponseTemplate;
+
+        const GIB: u64 = 1024 * 1024 * 1024;
+
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/small"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-length", "1024"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/large"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", (10 * GIB + 1).to_string().as_str()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/merged"))
+            .and(query_param("uploads", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let parts = [
+            ("1", "/bucket/small", None),
+            (
+                "2",
+                "/bucket/large",
+                Some(format!("bytes=0-{}", 5 * GIB - 1)),
+            ),
+            (
+                "3",
+                "/bucket/large",
+                Some(format!("bytes={}-{}", 5 * GIB, 10 * GIB - 1)),
+            ),
+            (
+                "4",
+                "/bucket/large",
+                Some(format!("bytes={}-{}", 10 * GIB, 10 * GIB)),
+            ),
+        ];
+        for (part_number, source, range) in parts {
+            let mut mock = Mock::given(method("PUT"))
+                .and(path("/bucket/merged"))
+                .and(query_param("partNumber", part_number))
+                .and(header("x-amz-copy-source", source.trim_start_matches('/')));
+            if let Some(range) = range {
+                mock = mock.and(header("x-amz-copy-source-range", range.as_str()));
+            }
+            mock.respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                "<CopyPartResult><ETag>etag-{part_number}</ETag></CopyPartResult>"
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        }
+        Mock::given(method("POST"))
+            .and(path("/bucket/merged"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        op.concat("merged", &["small", "large"]).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_concat_aborts_on_failure() -> Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::ResponseTemplate;
+
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/a"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-length", "1024"))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/merged"))
+            .and(query_param("uploads", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/merged"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+        // Failed abort must not mask the error of copy.
+        Mock::given(method("DELETE"))
+            .and(path("/bucket/merged"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let op = new_mock_operator(&server);
+
+        let err = op
+            .concat("merged", &["a", "a"])
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_http_client_pool_options() -> Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::ResponseTemplate;
+
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/file"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-length", "5"))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder
+            .endpoint(&server.uri())
+            .bucket("bucket")
+            .region("us-east-1")
+            .disable_config_load()
+            .disable_ec2_metadata()
+            .allow_anonymous()
+            .pool_max_idle_per_host(0)
+            .pool_idle_timeout(std::time::Duration::from_secs(1))
+            .tcp_keepalive(std::time::Duration::from_secs(30));
+        let op = Operator::new(builder)?.finish();
+        assert_eq!(op.stat("file").await?.content_length(), 5);
+
+        // Stats are shared with the client passed to builder.
+        let client = HttpClient::builder().pool_max_idle_per_host(1).build()?;
+        let mut builder = S3Builder::default();
+        builder
+            .endpoint(&server.uri())
+            .bucket("bucket")
+            .region("us-east-1")
+            .disable_config_load()
+            .disable_ec2_metadata()
+            .allow_anonymous()
+            .http_client(client.clone());
+        let op = Operator::new(builder)?.finish();
+        op.stat("file").await?;
+
+        let stats = client.stats();
+        assert_eq!(stats.requests_total(), 1);
+        assert_eq!(stats.requests_in_flight(), 0);
+        assert_eq!(stats.errors_total(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_signer_signs_custom_request() -> Result<()> {
+        let mut builder = S3Builder::default();
+        builder
+            .endpoint("http://127.0.0.1:9000")
+            .bucket("bucket")
+            .region("us-east-1")
+            .access_key_id("access_key")
+            .secret_access_key("secret_key")
+            .disable_config_load()
+            .disable_ec2_metadata();
+        let op = Operator::new(builder)?
+            .layer(crate::layers::RetryLayer::new())
+            .finish();
+        let signer = op.info().signer().expect("s3 must expose its signer");
+
+        let mut req = http::Request::get("http://127.0.0.1:9000/bucket?object-lock")
+            .body(AsyncBody::Empty)
+            .expect("request must be valid");
+        signer.sign(&mut req).await?;
+        let auth = req
+            .headers()
+            .get(http::header::AUTHORIZATION)
+            .expect("authorization must be set")
+            .to_str()
+            .expect("authorization must be valid");
+        assert!(
+            auth.starts_with("AWS4-HMAC-SHA256 Credential=access_key/"),
+            "authorization: {auth}"
+        );
+        assert!(req.headers().contains_key("x-amz-date"));
+
+        let mut req = http::Request::get("http://127.0.0.1:9000/bucket/file?tagging")
+            .body(AsyncBody::Empty)
+            .expect("request must be valid");
+        signer
+            .sign_query(&mut req, std::time::Duration::from_secs(3600))
+            .await?;
+        let query = req.uri().query().expect("query must exist");
+        assert!(query.contains("tagging"), "query: {query}");
+        assert!(query.contains("X-Amz-Expires=3600"), "query: {query}");
+        assert!(query.contains("X-Amz-Signature="), "query: {query}");
+        assert!(!req.headers().contains_key(http::header::AUTHORIZATION));
+        Ok(())
+    }
 }
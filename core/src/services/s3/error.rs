@@ -84,6 +84,21 @@ pub fn parse_s3_error_code(code: &str) -> Option<(ErrorKind, bool)> {
         // > A conflicting conditional operation is currently in progress
         // > against this resource. Try again.
         "OperationAborted" => Some((ErrorKind::Unexpected, true)),
+        // > A conflicting conditional operation is currently in progress
+        // > against this resource.
+        //
+        // Returned while conditional writes race with each other, callers
+        // should re-read the state and decide whether to try again.
+        "ConditionalRequestConflict" => Some((ErrorKind::ConditionNotMatch, false)),
+        // > The AWS access key ID you provided does not exist in our records.
+        "InvalidAccessKeyId" => Some((ErrorKind::Unauthenticated, false)),
+        // > The request signature we calculated does not match the signature
+        // > you provided.
+        "SignatureDoesNotMatch" => Some((ErrorKind::Unauthenticated, false)),
+        // > The provided token has expired.
+        "ExpiredToken" | "TokenRefreshRequired" => Some((ErrorKind::Unauthenticated, false)),
+        // > The provided token is malformed or otherwise invalid.
+        "InvalidToken" => Some((ErrorKind::Unauthenticated, false)),
         // > Please reduce your request rate.
         //
         // It's Ok to retry since later on the request rate may get reduced.
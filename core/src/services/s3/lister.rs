@@ -33,7 +33,7 @@ pub struct S3Lister {
     core: Arc<S3Core>,
 
     path: String,
-    delimiter: &'static str,
+    delimiter: String,
     limit: Option<usize>,
 
     /// Amazon S3 starts listing **after** this specified key
@@ -45,10 +45,13 @@ impl S3Lister {
         core: Arc<S3Core>,
         path: &str,
         recursive: bool,
+        delimiter: Option<&str>,
         limit: Option<usize>,
         start_after: Option<&str>,
     ) -> Self {
-        let delimiter = if recursive { "" } else { "/" };
+        let delimiter = delimiter
+            .unwrap_or(if recursive { "" } else { "/" })
+            .to_string();
         Self {
             core,
 
@@ -68,7 +71,7 @@ impl oio::PageList for S3Lister {
             .s3_list_objects(
                 &self.path,
                 &ctx.token,
-                self.delimiter,
+                &self.delimiter,
                 self.limit,
                 // State after should only be set for the first page.
                 if ctx.token.is_empty() {
@@ -103,9 +106,11 @@ impl oio::PageList for S3Lister {
         ctx.token = output.next_continuation_token.clone().unwrap_or_default();
 
         for prefix in output.common_prefixes {
+            // Prefixes grouped by custom delimiter don't end with `/`, and
+            // can't be listed as dirs.
             let de = oio::Entry::new(
                 &build_rel_path(&self.core.root, &prefix.prefix),
-                Metadata::new(EntryMode::DIR),
+                Metadata::new(EntryMode::from_path(&prefix.prefix)),
             );
 
             ctx.entries.push_back(de);
@@ -126,6 +131,9 @@ impl oio::PageList for S3Lister {
                 meta.set_content_md5(etag.trim_matches('"'));
             }
             meta.set_content_length(object.size);
+            if let Some(v) = &object.storage_class {
+                meta.set_storage_class(v);
+            }
 
             // object.last_modified provides more precious time that contains
             // nanosecond, let's trim them.
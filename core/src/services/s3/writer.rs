@@ -51,7 +51,7 @@ impl oio::MultipartUploadWrite for S3Writer {
             .core
             .s3_put_object_request(&self.path, Some(size), &self.op, body)?;
 
-        self.core.sign(&mut req).await?;
+        self.core.sign_with(&mut req, self.op.signer()).await?;
 
         let resp = self.core.send(req).await?;
 
@@ -98,11 +98,16 @@ impl oio::MultipartUploadWrite for S3Writer {
         // AWS S3 requires part number must between [1..=10000]
         let part_number = part_number + 1;
 
-        let mut req =
-            self.core
-                .s3_upload_part_request(&self.path, upload_id, part_number, size, body)?;
+        let mut req = self.core.s3_upload_part_request(
+            &self.path,
+            upload_id,
+            part_number,
+            size,
+            &self.op,
+            body,
+        )?;
 
-        self.core.sign(&mut req).await?;
+        self.core.sign_with(&mut req, self.op.signer()).await?;
 
         let resp = self.core.send(req).await?;
 
@@ -142,7 +147,7 @@ impl oio::MultipartUploadWrite for S3Writer {
 
         let resp = self
             .core
-            .s3_complete_multipart_upload(&self.path, upload_id, parts)
+            .s3_complete_multipart_upload(&self.path, upload_id, &self.op, parts)
             .await?;
 
         let status = resp.status();
@@ -160,7 +165,7 @@ impl oio::MultipartUploadWrite for S3Writer {
     async fn abort_part(&self, upload_id: &str) -> Result<()> {
         let resp = self
             .core
-            .s3_abort_multipart_upload(&self.path, upload_id)
+            .s3_abort_multipart_upload(&self.path, upload_id, &self.op)
             .await?;
         match resp.status() {
             // s3 returns code 204 if abort succeeds.
@@ -46,6 +46,14 @@ impl From<SftpMeta> for Metadata {
             metadata.set_last_modified(modified.as_system_time().into());
         }
 
+        if let Some(uid) = meta.uid() {
+            metadata.set_uid(uid);
+        }
+
+        if let Some(gid) = meta.gid() {
+            metadata.set_gid(gid);
+        }
+
         metadata
     }
 }
@@ -140,6 +140,7 @@ impl kv::Adapter for Adapter {
                 read: true,
                 write: true,
                 list: true,
+                delete_with_prefix: true,
                 blocking: true,
                 ..Default::default()
             },
@@ -194,6 +195,25 @@ impl kv::Adapter for Adapter {
         Ok(())
     }
 
+    async fn delete_prefix(&self, path: &str) -> Result<()> {
+        let cloned_self = self.clone();
+        let cloned_path = path.to_string();
+
+        task::spawn_blocking(move || cloned_self.blocking_delete_prefix(cloned_path.as_str()))
+            .await
+            .map_err(new_task_join_error)?
+    }
+
+    /// Remove all keys with given prefix in one atomic batch.
+    fn blocking_delete_prefix(&self, path: &str) -> Result<()> {
+        let mut batch = sled::Batch::default();
+        for key in self.tree.scan_prefix(path).keys() {
+            batch.remove(key.map_err(parse_error)?);
+        }
+
+        self.tree.apply_batch(batch).map_err(parse_error)
+    }
+
     async fn scan(&self, path: &str) -> Result<Vec<String>> {
         let cloned_self = self.clone();
         let cloned_path = path.to_string();
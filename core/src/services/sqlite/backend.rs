@@ -293,6 +293,31 @@ impl kv::Adapter for Adapter {
         }
     }
 
+    async fn size(&self, path: &str) -> Result<Option<u64>> {
+        let this = self.clone();
+        let path = path.to_string();
+
+        task::spawn_blocking(move || this.blocking_size(&path))
+            .await
+            .map_err(new_task_join_error)?
+    }
+
+    fn blocking_size(&self, path: &str) -> Result<Option<u64>> {
+        let conn = self.pool.get().map_err(parse_r2d2_error)?;
+
+        let query = format!(
+            "SELECT length({}) FROM {} WHERE `{}` = $1 LIMIT 1",
+            self.value_field, self.table, self.key_field
+        );
+        let mut statement = conn.prepare(&query).map_err(parse_rusqlite_error)?;
+        let result = statement.query_row([path], |row| row.get::<_, i64>(0));
+        match result {
+            Ok(v) => Ok(Some(v as u64)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(err) => Err(parse_rusqlite_error(err)),
+        }
+    }
+
     async fn set(&self, path: &str, value: &[u8]) -> Result<()> {
         let this = self.clone();
         let path = path.to_string();
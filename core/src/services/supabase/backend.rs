@@ -19,7 +19,10 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use http::HeaderMap;
+use http::Method;
 use http::StatusCode;
+use http::Uri;
 use log::debug;
 
 use super::core::*;
@@ -38,6 +41,7 @@ pub struct SupabaseBuilder {
     endpoint: Option<String>,
 
     key: Option<String>,
+    enable_resumable_upload: bool,
 
     // todo: optional public, currently true always
     // todo: optional file_size_limit, currently 0
@@ -95,6 +99,16 @@ impl SupabaseBuilder {
         self
     }
 
+    /// Enable resumable upload (tus protocol) while writing in multiple
+    /// chunks.
+    ///
+    /// Supabase requires every chunk to be exactly 6 MiB except the last
+    /// one, please set `buffer` of writer to `6 * 1024 * 1024` as well.
+    pub fn enable_resumable_upload(&mut self) -> &mut Self {
+        self.enable_resumable_upload = true;
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -118,6 +132,9 @@ impl Builder for SupabaseBuilder {
         map.get("bucket").map(|v| builder.bucket(v));
         map.get("endpoint").map(|v| builder.endpoint(v));
         map.get("key").map(|v| builder.key(v));
+        map.get("enable_resumable_upload")
+            .filter(|v| v == &"true")
+            .map(|_| builder.enable_resumable_upload());
 
         builder
     }
@@ -141,7 +158,14 @@ impl Builder for SupabaseBuilder {
 
         let key = self.key.as_ref().map(|k| k.to_owned());
 
-        let core = SupabaseCore::new(&root, bucket, &endpoint, key, http_client);
+        let core = SupabaseCore::new(
+            &root,
+            bucket,
+            &endpoint,
+            key,
+            self.enable_resumable_upload,
+            http_client,
+        );
 
         let core = Arc::new(core);
 
@@ -158,7 +182,10 @@ pub struct SupabaseBackend {
 impl Accessor for SupabaseBackend {
     type Reader = IncomingAsyncBody;
     type BlockingReader = ();
-    type Writer = oio::OneShotWriter<SupabaseWriter>;
+    type Writer = oio::TwoWaysWriter<
+        oio::OneShotWriter<SupabaseWriter>,
+        oio::MultipartUploadWriter<SupabaseWriter>,
+    >;
     type BlockingWriter = ();
     // todo: implement Lister to support list and scan
     type Lister = ();
@@ -175,8 +202,14 @@ impl Accessor for SupabaseBackend {
                 read: true,
 
                 write: true,
+                write_can_multi: self.core.resumable_upload,
+
                 delete: true,
 
+                presign: true,
+                presign_read: true,
+                presign_write: self.core.key.is_some(),
+
                 ..Default::default()
             });
 
@@ -195,10 +228,15 @@ impl Accessor for SupabaseBackend {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        Ok((
-            RpWrite::default(),
-            oio::OneShotWriter::new(SupabaseWriter::new(self.core.clone(), path, args)),
-        ))
+        let w = SupabaseWriter::new(self.core.clone(), path, args);
+
+        let w = if self.core.resumable_upload {
+            oio::TwoWaysWriter::Two(oio::MultipartUploadWriter::new(w, 1))
+        } else {
+            oio::TwoWaysWriter::One(oio::OneShotWriter::new(w))
+        };
+
+        Ok((RpWrite::default(), w))
     }
 
     async fn stat(&self, path: &str, _args: OpStat) -> Result<RpStat> {
@@ -236,4 +274,181 @@ impl Accessor for SupabaseBackend {
             }
         }
     }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        let (method, url) = match args.operation() {
+            PresignOperation::Read(_) => (
+                Method::GET,
+                self.core.supabase_read_url(path, args.expire()).await?,
+            ),
+            PresignOperation::Write(_) => (Method::PUT, self.core.supabase_write_url(path).await?),
+            PresignOperation::Stat(_) => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "supabase doesn't support presign stat",
+                ))
+            }
+        };
+
+        let uri = url.parse::<Uri>().map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "supabase returns invalid url")
+                .with_context("url", url)
+                .set_source(err)
+        })?;
+
+        Ok(RpPresign::new(PresignedRequest::new(
+            method,
+            uri,
+            HeaderMap::new(),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use wiremock::matchers::body_json;
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+
+    use super::*;
+
+    fn new_mock_operator(server: &MockServer, key: Option<&str>) -> crate::Operator {
+        let mut builder = SupabaseBuilder::default();
+        builder
+            .endpoint(&server.uri())
+            .bucket("bucket")
+            .enable_resumable_upload();
+        if let Some(key) = key {
+            builder.key(key);
+        }
+        crate::Operator::new(builder).unwrap().finish()
+    }
+
+    #[tokio::test]
+    async fn test_read_with_range() -> Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/storage/v1/object/authenticated/bucket/file"))
+            .and(header("authorization", "Bearer key"))
+            .and(header("apikey", "key"))
+            .and(header("range", "bytes=7-11"))
+            .respond_with(ResponseTemplate::new(206).set_body_string("World"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let op = new_mock_operator(&server, Some("key"));
+        assert_eq!(op.read_with("file").range(7..12).await?, b"World");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_resumable_upload() -> Result<()> {
+        let server = MockServer::start().await;
+        let location = "/storage/v1/upload/resumable/upload-id";
+        Mock::given(method("POST"))
+            .and(path("/storage/v1/upload/resumable"))
+            .and(header("tus-resumable", "1.0.0"))
+            .and(header("upload-defer-length", "1"))
+            .and(header("apikey", "key"))
+            .respond_with(ResponseTemplate::new(201).insert_header("location", location))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // The last request declares the length to finish the upload.
+        Mock::given(method("PATCH"))
+            .and(path(location))
+            .and(header("upload-offset", "7"))
+            .and(header("upload-length", "7"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // Parts are uploaded in order with the offset of previous parts.
+        for offset in ["0", "4"] {
+            Mock::given(method("PATCH"))
+                .and(path(location))
+                .and(header("upload-offset", offset))
+                .and(header("content-type", "application/offset+octet-stream"))
+                .respond_with(ResponseTemplate::new(204))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let op = new_mock_operator(&server, Some("key"));
+        let mut w = op.writer("file").await?;
+        w.write(vec![1; 4]).await?;
+        w.write(vec![2; 3]).await?;
+        w.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_presign() -> Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/storage/v1/object/sign/bucket/file"))
+            .and(body_json(serde_json::json!({"expiresIn": 3600})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({"signedURL": "/object/sign/bucket/file?token=read"}),
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/storage/v1/object/upload/sign/bucket/file"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({"url": "/object/upload/sign/bucket/file?token=write"}),
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let op = new_mock_operator(&server, Some("key"));
+        let expire = Duration::from_secs(3600);
+
+        let req = op.presign_read("file", expire).await?;
+        assert_eq!(req.method(), &Method::GET);
+        assert_eq!(
+            req.uri().to_string(),
+            format!(
+                "{}/storage/v1/object/sign/bucket/file?token=read",
+                server.uri()
+            )
+        );
+
+        let req = op.presign_write("file", expire).await?;
+        assert_eq!(req.method(), &Method::PUT);
+        assert_eq!(
+            req.uri().to_string(),
+            format!(
+                "{}/storage/v1/object/upload/sign/bucket/file?token=write",
+                server.uri()
+            )
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_presign_public_read_without_key() -> Result<()> {
+        let server = MockServer::start().await;
+        let op = new_mock_operator(&server, None);
+        assert!(!op.info().full_capability().presign_write);
+
+        // Public url is built locally without any request.
+        let req = op.presign_read("file", Duration::from_secs(60)).await?;
+        assert_eq!(
+            req.uri().to_string(),
+            format!("{}/storage/v1/object/public/bucket/file", server.uri())
+        );
+        assert!(server.received_requests().await.unwrap().is_empty());
+        Ok(())
+    }
 }
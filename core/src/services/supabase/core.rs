@@ -16,16 +16,28 @@
 // under the License.
 
 use std::fmt::Debug;
+use std::time::Duration;
 
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
+use http::header::LOCATION;
+use http::header::RANGE;
 use http::HeaderValue;
 use http::Request;
 use http::Response;
+use http::StatusCode;
+use serde::Deserialize;
+use serde::Serialize;
 
+use super::error::parse_error;
 use crate::raw::*;
 use crate::*;
 
+/// The version of tus protocol used by resumable upload.
+const TUS_RESUMABLE: &str = "1.0.0";
+
 pub struct SupabaseCore {
     pub root: String,
     pub bucket: String,
@@ -35,6 +47,8 @@ pub struct SupabaseCore {
     /// If loaded, the read operation will always access the nonpublic resources.
     /// If you want to read the public resources, please do not set the key.
     pub key: Option<String>,
+    /// Use resumable upload (tus protocol) while writing in multiple chunks.
+    pub resumable_upload: bool,
 
     pub http_client: HttpClient,
 }
@@ -55,6 +69,7 @@ impl SupabaseCore {
         bucket: &str,
         endpoint: &str,
         key: Option<String>,
+        resumable_upload: bool,
         client: HttpClient,
     ) -> Self {
         Self {
@@ -62,16 +77,23 @@ impl SupabaseCore {
             bucket: bucket.to_string(),
             endpoint: endpoint.to_string(),
             key,
+            resumable_upload,
             http_client: client,
         }
     }
 
     /// Add authorization header to the request if the key is set. Otherwise leave
     /// the request as-is.
+    ///
+    /// The key will also be sent as `apikey` which is required by the API
+    /// gateway of Supabase.
     pub fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
         if let Some(k) = &self.key {
-            let v = HeaderValue::from_str(&format!("Bearer {}", k)).unwrap();
+            let v = HeaderValue::from_str(&format!("Bearer {}", k))
+                .map_err(|err| new_request_build_error(err.into()))?;
             req.headers_mut().insert(http::header::AUTHORIZATION, v);
+            let v = HeaderValue::from_str(k).map_err(|err| new_request_build_error(err.into()))?;
+            req.headers_mut().insert("apikey", v);
         }
         Ok(())
     }
@@ -126,7 +148,7 @@ impl SupabaseCore {
     pub fn supabase_get_object_public_request(
         &self,
         path: &str,
-        _: BytesRange,
+        range: BytesRange,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
         let url = format!(
@@ -136,7 +158,11 @@ impl SupabaseCore {
             percent_encode_path(&p)
         );
 
-        let req = Request::get(&url);
+        let mut req = Request::get(&url);
+
+        if !range.is_full() {
+            req = req.header(RANGE, range.to_header());
+        }
 
         req.body(AsyncBody::Empty).map_err(new_request_build_error)
     }
@@ -144,7 +170,7 @@ impl SupabaseCore {
     pub fn supabase_get_object_auth_request(
         &self,
         path: &str,
-        _: BytesRange,
+        range: BytesRange,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
         let url = format!(
@@ -154,7 +180,11 @@ impl SupabaseCore {
             percent_encode_path(&p)
         );
 
-        let req = Request::get(&url);
+        let mut req = Request::get(&url);
+
+        if !range.is_full() {
+            req = req.header(RANGE, range.to_header());
+        }
 
         req.body(AsyncBody::Empty).map_err(new_request_build_error)
     }
@@ -217,6 +247,108 @@ impl SupabaseCore {
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)
     }
+
+    pub fn supabase_create_signed_url_request(
+        &self,
+        path: &str,
+        expire: Duration,
+    ) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+        let url = format!(
+            "{}/storage/v1/object/sign/{}/{}",
+            self.endpoint,
+            self.bucket,
+            percent_encode_path(&p)
+        );
+
+        let body = serde_json::to_vec(&SignedUrlRequest {
+            expires_in: expire.as_secs(),
+        })
+        .map_err(new_json_serialize_error)?;
+
+        Request::post(&url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(CONTENT_LENGTH, body.len())
+            .body(AsyncBody::Bytes(body.into()))
+            .map_err(new_request_build_error)
+    }
+
+    pub fn supabase_create_signed_upload_url_request(
+        &self,
+        path: &str,
+    ) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+        let url = format!(
+            "{}/storage/v1/object/upload/sign/{}/{}",
+            self.endpoint,
+            self.bucket,
+            percent_encode_path(&p)
+        );
+
+        Request::post(&url)
+            .header(CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)
+    }
+
+    /// Create a resumable upload with deferred length.
+    ///
+    /// Reference: <https://supabase.com/docs/guides/storage/uploads/resumable-uploads>
+    pub fn supabase_resumable_create_request(
+        &self,
+        path: &str,
+        content_type: Option<&str>,
+    ) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+        let url = format!("{}/storage/v1/upload/resumable", self.endpoint);
+
+        let mut metadata = vec![
+            format!("bucketName {}", BASE64_STANDARD.encode(&self.bucket)),
+            format!("objectName {}", BASE64_STANDARD.encode(&p)),
+        ];
+        if let Some(mime) = content_type {
+            metadata.push(format!("contentType {}", BASE64_STANDARD.encode(mime)));
+        }
+
+        Request::post(&url)
+            .header("Tus-Resumable", TUS_RESUMABLE)
+            .header("Upload-Defer-Length", 1)
+            .header("Upload-Metadata", metadata.join(","))
+            .header("x-upsert", "true")
+            .header(CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)
+    }
+
+    /// Upload data at given offset, `total` should be set for the last
+    /// request to finish the upload.
+    pub fn supabase_resumable_patch_request(
+        &self,
+        location: &str,
+        offset: u64,
+        size: u64,
+        total: Option<u64>,
+        body: AsyncBody,
+    ) -> Result<Request<AsyncBody>> {
+        let mut req = Request::patch(location)
+            .header("Tus-Resumable", TUS_RESUMABLE)
+            .header("Upload-Offset", offset)
+            .header(CONTENT_TYPE, "application/offset+octet-stream")
+            .header(CONTENT_LENGTH, size);
+
+        if let Some(total) = total {
+            req = req.header("Upload-Length", total);
+        }
+
+        req.body(body).map_err(new_request_build_error)
+    }
+
+    pub fn supabase_resumable_delete_request(&self, location: &str) -> Result<Request<AsyncBody>> {
+        Request::delete(location)
+            .header("Tus-Resumable", TUS_RESUMABLE)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)
+    }
 }
 
 // core utils
@@ -268,3 +400,120 @@ impl SupabaseCore {
         self.send(req).await
     }
 }
+
+// resumable upload and presign
+impl SupabaseCore {
+    /// Create a resumable upload and returns its location.
+    pub async fn supabase_resumable_create(
+        &self,
+        path: &str,
+        content_type: Option<&str>,
+    ) -> Result<String> {
+        let mut req = self.supabase_resumable_create_request(path, content_type)?;
+        self.sign(&mut req)?;
+        let resp = self.send(req).await?;
+
+        if resp.status() != StatusCode::CREATED {
+            return Err(parse_error(resp).await?);
+        }
+
+        let location = parse_location(resp.headers())?
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::Unexpected,
+                    "resumable upload created without location",
+                )
+            })?
+            .to_string();
+        resp.into_body().consume().await?;
+
+        // Location could be relative to the endpoint.
+        if location.starts_with("http") {
+            Ok(location)
+        } else {
+            Ok(format!("{}{}", self.endpoint, location))
+        }
+    }
+
+    pub async fn supabase_resumable_patch(
+        &self,
+        location: &str,
+        offset: u64,
+        size: u64,
+        total: Option<u64>,
+        body: AsyncBody,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = self.supabase_resumable_patch_request(location, offset, size, total, body)?;
+        self.sign(&mut req)?;
+        self.send(req).await
+    }
+
+    pub async fn supabase_resumable_delete(
+        &self,
+        location: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = self.supabase_resumable_delete_request(location)?;
+        self.sign(&mut req)?;
+        self.send(req).await
+    }
+
+    /// Build the url that can read the object without authorization.
+    ///
+    /// Public url will be returned if no key is set, otherwise a signed url
+    /// will be created.
+    pub async fn supabase_read_url(&self, path: &str, expire: Duration) -> Result<String> {
+        if self.key.is_none() {
+            let p = build_abs_path(&self.root, path);
+            return Ok(format!(
+                "{}/storage/v1/object/public/{}/{}",
+                self.endpoint,
+                self.bucket,
+                percent_encode_path(&p)
+            ));
+        }
+
+        let mut req = self.supabase_create_signed_url_request(path, expire)?;
+        self.sign(&mut req)?;
+        let resp = self.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let signed: SignedUrlResponse =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+        Ok(format!("{}/storage/v1{}", self.endpoint, signed.signed_url))
+    }
+
+    /// Build the url that can upload the object without authorization.
+    pub async fn supabase_write_url(&self, path: &str) -> Result<String> {
+        let mut req = self.supabase_create_signed_upload_url_request(path)?;
+        self.sign(&mut req)?;
+        let resp = self.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let signed: SignedUploadUrlResponse =
+            serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+        Ok(format!("{}/storage/v1{}", self.endpoint, signed.url))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SignedUrlRequest {
+    expires_in: u64,
+}
+
+#[derive(Deserialize)]
+struct SignedUrlResponse {
+    #[serde(rename = "signedURL")]
+    signed_url: String,
+}
+
+#[derive(Deserialize)]
+struct SignedUploadUrlResponse {
+    url: String,
+}
@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -31,6 +33,9 @@ pub struct SupabaseWriter {
 
     op: OpWrite,
     path: String,
+
+    /// The offset of resumable upload.
+    offset: AtomicU64,
 }
 
 impl SupabaseWriter {
@@ -39,20 +44,16 @@ impl SupabaseWriter {
             core,
             op,
             path: path.to_string(),
+            offset: AtomicU64::new(0),
         }
     }
-}
-
-#[async_trait]
-impl oio::OneShotWrite for SupabaseWriter {
-    async fn write_once(&self, bs: &dyn WriteBuf) -> Result<()> {
-        let bs = oio::ChunkedBytes::from_vec(bs.vectored_bytes(bs.remaining()));
 
+    async fn upload(&self, size: u64, body: AsyncBody) -> Result<()> {
         let mut req = self.core.supabase_upload_object_request(
             &self.path,
-            Some(bs.len()),
+            Some(size as usize),
             self.op.content_type(),
-            AsyncBody::ChunkedBytes(bs),
+            body,
         )?;
 
         self.core.sign(&mut req)?;
@@ -68,3 +69,91 @@ impl oio::OneShotWrite for SupabaseWriter {
         }
     }
 }
+
+#[async_trait]
+impl oio::OneShotWrite for SupabaseWriter {
+    async fn write_once(&self, bs: &dyn WriteBuf) -> Result<()> {
+        let bs = oio::ChunkedBytes::from_vec(bs.vectored_bytes(bs.remaining()));
+
+        self.upload(bs.len() as u64, AsyncBody::ChunkedBytes(bs))
+            .await
+    }
+}
+
+/// Supabase's resumable upload is based on [tus](https://tus.io/protocols/resumable-upload),
+/// parts must be uploaded in order.
+///
+/// - `upload_id` is the location of the upload.
+/// - `etag` of part is the upload offset after this part.
+#[async_trait]
+impl oio::MultipartUploadWrite for SupabaseWriter {
+    async fn write_once(&self, size: u64, body: AsyncBody) -> Result<()> {
+        self.upload(size, body).await
+    }
+
+    async fn initiate_part(&self) -> Result<String> {
+        let location = self
+            .core
+            .supabase_resumable_create(&self.path, self.op.content_type())
+            .await?;
+        self.offset.store(0, Ordering::SeqCst);
+        Ok(location)
+    }
+
+    async fn write_part(
+        &self,
+        upload_id: &str,
+        part_number: usize,
+        size: u64,
+        body: AsyncBody,
+    ) -> Result<oio::MultipartUploadPart> {
+        let offset = self.offset.load(Ordering::SeqCst);
+        let resp = self
+            .core
+            .supabase_resumable_patch(upload_id, offset, size, None, body)
+            .await?;
+
+        match resp.status() {
+            StatusCode::NO_CONTENT => {
+                resp.into_body().consume().await?;
+                let offset = offset + size;
+                self.offset.store(offset, Ordering::SeqCst);
+
+                Ok(oio::MultipartUploadPart {
+                    part_number,
+                    etag: offset.to_string(),
+                })
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn complete_part(&self, upload_id: &str, _: &[oio::MultipartUploadPart]) -> Result<()> {
+        // Declare the length of upload to finish it.
+        let total = self.offset.load(Ordering::SeqCst);
+        let resp = self
+            .core
+            .supabase_resumable_patch(upload_id, total, 0, Some(total), AsyncBody::Empty)
+            .await?;
+
+        match resp.status() {
+            StatusCode::NO_CONTENT => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn abort_part(&self, upload_id: &str) -> Result<()> {
+        let resp = self.core.supabase_resumable_delete(upload_id).await?;
+
+        match resp.status() {
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
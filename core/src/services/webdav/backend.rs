@@ -43,8 +43,8 @@ use crate::*;
 pub struct WebdavBuilder {
     endpoint: Option<String>,
     username: Option<String>,
-    password: Option<String>,
-    token: Option<String>,
+    password: Option<Secret>,
+    token: Option<Secret>,
     root: Option<String>,
     http_client: Option<HttpClient>,
 }
@@ -88,7 +88,7 @@ impl WebdavBuilder {
     /// default: no password
     pub fn password(&mut self, password: &str) -> &mut Self {
         if !password.is_empty() {
-            self.password = Some(password.to_owned());
+            self.password = Some(password.into());
         }
         self
     }
@@ -98,7 +98,7 @@ impl WebdavBuilder {
     /// default: no access token
     pub fn token(&mut self, token: &str) -> &mut Self {
         if !token.is_empty() {
-            self.token = Some(token.to_owned());
+            self.token = Some(token.into());
         }
         self
     }
@@ -178,11 +178,14 @@ impl Builder for WebdavBuilder {
         if let Some(username) = &self.username {
             auth = Some(format_authorization_by_basic(
                 username,
-                self.password.as_deref().unwrap_or_default(),
+                self.password
+                    .as_ref()
+                    .map(|v| v.expose_secret().as_str())
+                    .unwrap_or_default(),
             )?);
         }
         if let Some(token) = &self.token {
-            auth = Some(format_authorization_by_bearer(token)?)
+            auth = Some(format_authorization_by_bearer(token.expose_secret())?)
         }
 
         debug!("backend build finished: {:?}", &self);
@@ -17,14 +17,22 @@
 
 use core::fmt::Debug;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::RwLock;
+use std::time::Duration;
 
 use async_trait::async_trait;
+use chrono::DateTime;
+use chrono::Utc;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
+use http::Method;
 use http::Request;
 use http::Response;
 use http::StatusCode;
+use http::Uri;
 use log::debug;
+use tokio::sync::Mutex;
 use tokio::sync::OnceCell;
 
 use super::error::parse_error;
@@ -33,6 +41,9 @@ use super::lister::WebhdfsLister;
 use super::message::BooleanResp;
 use super::message::FileStatusType;
 use super::message::FileStatusWrapper;
+use super::message::LocationResp;
+use super::message::LongResp;
+use super::message::TokenWrapper;
 use super::writer::WebhdfsWriter;
 use crate::raw::*;
 use crate::*;
@@ -46,7 +57,13 @@ pub struct WebhdfsBuilder {
     root: Option<String>,
     endpoint: Option<String>,
     delegation: Option<String>,
+    user: Option<String>,
+    fetch_delegation_token: bool,
+    datanode_endpoint: Option<String>,
     disable_list_batch: bool,
+    read_chunk_size: Option<usize>,
+    read_first_byte_timeout: Option<Duration>,
+    read_max_retries: Option<usize>,
 }
 
 impl Debug for WebhdfsBuilder {
@@ -54,6 +71,9 @@ impl Debug for WebhdfsBuilder {
         f.debug_struct("Builder")
             .field("root", &self.root)
             .field("endpoint", &self.endpoint)
+            .field("user", &self.user)
+            .field("datanode_endpoint", &self.datanode_endpoint)
+            .field("read_chunk_size", &self.read_chunk_size)
             .finish_non_exhaustive()
     }
 }
@@ -105,6 +125,47 @@ impl WebhdfsBuilder {
         self
     }
 
+    /// Set the user name of this backend
+    ///
+    /// # Note
+    ///
+    /// The user name will be sent as `user.name` for simple authentication
+    /// if no delegation token is used, and will be the renewer of fetched
+    /// delegation tokens.
+    pub fn user(&mut self, user: &str) -> &mut Self {
+        if !user.is_empty() {
+            self.user = Some(user.to_string());
+        }
+        self
+    }
+
+    /// Fetch delegation token via `GETDELEGATIONTOKEN` and renew it
+    /// automatically before expiring
+    ///
+    /// # Note
+    ///
+    /// The token will be fetched by the first request. A new token will be
+    /// fetched if the current one can't be renewed anymore.
+    pub fn fetch_delegation_token(&mut self) -> &mut Self {
+        self.fetch_delegation_token = true;
+        self
+    }
+
+    /// Set the endpoint that datanodes are reachable through
+    ///
+    /// # Note
+    ///
+    /// Namenode redirects `OPEN`, `CREATE` and `APPEND` to datanodes by `307`,
+    /// but the datanode address may not be reachable while behind proxies.
+    /// If set, we will ask namenode for the location with `noredirect=true`
+    /// and send the request to this endpoint instead.
+    pub fn datanode_endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.datanode_endpoint = Some(endpoint.trim_end_matches('/').to_string());
+        }
+        self
+    }
+
     /// Disable batch listing
     ///
     /// # Note
@@ -115,6 +176,39 @@ impl WebhdfsBuilder {
         self.disable_list_batch = true;
         self
     }
+
+    /// Split large reads into sub-ranges of given size
+    ///
+    /// # Note
+    ///
+    /// Sub-ranges will be read in order and retried independently, which is
+    /// useful while datanodes are behind flaky proxies. Disabled by default.
+    pub fn read_chunk_size(&mut self, size: usize) -> &mut Self {
+        if size > 0 {
+            self.read_chunk_size = Some(size);
+        }
+        self
+    }
+
+    /// Set the deadline of receiving the first byte of every sub-range
+    ///
+    /// # Note
+    ///
+    /// Only take effect while `read_chunk_size` is set.
+    pub fn read_first_byte_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.read_first_byte_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the retry times of every sub-range
+    ///
+    /// # Note
+    ///
+    /// Only take effect while `read_chunk_size` is set, default to 3.
+    pub fn read_max_retries(&mut self, retries: usize) -> &mut Self {
+        self.read_max_retries = Some(retries);
+        self
+    }
 }
 
 impl Builder for WebhdfsBuilder {
@@ -127,9 +221,24 @@ impl Builder for WebhdfsBuilder {
         map.get("root").map(|v| builder.root(v));
         map.get("endpoint").map(|v| builder.endpoint(v));
         map.get("delegation").map(|v| builder.delegation(v));
+        map.get("user").map(|v| builder.user(v));
+        map.get("fetch_delegation_token")
+            .filter(|v| v == &"true")
+            .map(|_| builder.fetch_delegation_token());
+        map.get("datanode_endpoint")
+            .map(|v| builder.datanode_endpoint(v));
         map.get("disable_list_batch")
             .filter(|v| v == &"true")
             .map(|_| builder.disable_list_batch());
+        map.get("read_chunk_size")
+            .and_then(|v| v.parse().ok())
+            .map(|v| builder.read_chunk_size(v));
+        map.get("read_first_byte_timeout_ms")
+            .and_then(|v| v.parse().ok())
+            .map(|v| builder.read_first_byte_timeout(Duration::from_millis(v)));
+        map.get("read_max_retries")
+            .and_then(|v| v.parse().ok())
+            .map(|v| builder.read_max_retries(v));
 
         builder
     }
@@ -160,33 +269,90 @@ impl Builder for WebhdfsBuilder {
         };
         debug!("backend use endpoint {}", endpoint);
 
-        let auth = self
-            .delegation
-            .take()
-            .map(|dt| format!("delegation_token={dt}"));
+        let delegation = self.delegation.take().map(|token| DelegationToken {
+            token,
+            expires_at: None,
+            fetched: false,
+        });
 
         let client = HttpClient::new()?;
 
         let backend = WebhdfsBackend {
             root,
             endpoint,
-            auth,
+            user: self.user.take(),
+            delegation: Arc::new(RwLock::new(delegation)),
+            renew_lock: Arc::new(Mutex::new(())),
+            fetch_delegation_token: self.fetch_delegation_token,
+            datanode_endpoint: self.datanode_endpoint.take(),
             client,
             root_checker: OnceCell::new(),
             disable_list_batch: self.disable_list_batch,
+            read_chunk_size: self.read_chunk_size,
+            read_first_byte_timeout: self.read_first_byte_timeout,
+            read_max_retries: self.read_max_retries,
         };
 
         Ok(backend)
     }
 }
 
+/// DelegationToken is the delegation token used by backend.
+#[derive(Clone)]
+struct DelegationToken {
+    token: String,
+    /// The expire time returned by renew, `None` means unknown and the
+    /// token will not be renewed.
+    expires_at: Option<DateTime<Utc>>,
+    /// Tokens set by users are sent as `delegation_token` for compatibility,
+    /// fetched ones are sent as `delegation` which is defined by WebHDFS.
+    fetched: bool,
+}
+
+impl Debug for DelegationToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DelegationToken")
+            .field("token", &"<redacted>")
+            .field("expires_at", &self.expires_at)
+            .field("fetched", &self.fetched)
+            .finish()
+    }
+}
+
+impl DelegationToken {
+    fn query(&self) -> String {
+        if self.fetched {
+            format!("delegation={}", self.token)
+        } else {
+            format!("delegation_token={}", self.token)
+        }
+    }
+
+    fn need_renew(&self) -> bool {
+        match self.expires_at {
+            // Renew the token 5 minutes before it expires.
+            Some(t) => t - chrono::Duration::minutes(5) <= Utc::now(),
+            None => false,
+        }
+    }
+}
+
 /// Backend for WebHDFS service
 #[derive(Debug, Clone)]
 pub struct WebhdfsBackend {
     root: String,
     endpoint: String,
-    auth: Option<String>,
+    user: Option<String>,
+    delegation: Arc<RwLock<Option<DelegationToken>>>,
+    /// Make sure only one request is fetching or renewing the token, other
+    /// requests can still use the current token.
+    renew_lock: Arc<Mutex<()>>,
+    fetch_delegation_token: bool,
+    datanode_endpoint: Option<String>,
     root_checker: OnceCell<()>,
+    read_chunk_size: Option<usize>,
+    read_first_byte_timeout: Option<Duration>,
+    read_max_retries: Option<usize>,
 
     pub disable_list_batch: bool,
     pub client: HttpClient,
@@ -196,7 +362,7 @@ impl WebhdfsBackend {
     /// create object or make a directory
     ///
     /// TODO: we should split it into mkdir and create
-    pub fn webhdfs_create_object_request(
+    pub async fn webhdfs_create_object_request(
         &self,
         path: &str,
         size: Option<usize>,
@@ -215,17 +381,20 @@ impl WebhdfsBackend {
             percent_encode_path(&p),
             op,
         );
-        if let Some(auth) = &self.auth {
-            url += format!("&{auth}").as_str();
+        if let Some(auth) = self.auth().await? {
+            url += &format!("&{auth}");
         }
 
-        let mut req = Request::put(&url);
-
         // mkdir does not redirect
         if path.ends_with('/') {
-            return req.body(AsyncBody::Empty).map_err(new_request_build_error);
+            return Request::put(&url)
+                .body(AsyncBody::Empty)
+                .map_err(new_request_build_error);
         }
 
+        let url = self.datanode_url(Method::PUT, url).await?;
+        let mut req = Request::put(&url);
+
         if let Some(size) = size {
             req = req.header(CONTENT_LENGTH, size.to_string());
         }
@@ -247,7 +416,7 @@ impl WebhdfsBackend {
             self.endpoint,
             percent_encode_path(&p),
         );
-        if let Some(auth) = &self.auth {
+        if let Some(auth) = self.auth().await? {
             url += &format!("&{auth}");
         }
 
@@ -268,6 +437,7 @@ impl WebhdfsBackend {
             }
         }
 
+        let url = self.datanode_url(Method::GET, url).await?;
         let req = Request::get(&url)
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
@@ -285,8 +455,8 @@ impl WebhdfsBackend {
             self.endpoint,
             percent_encode_path(&p),
         );
-        if let Some(auth) = &self.auth {
-            url += format!("&{auth}").as_str();
+        if let Some(auth) = self.auth().await? {
+            url += &format!("&{auth}");
         }
 
         let req = Request::get(&url)
@@ -310,8 +480,8 @@ impl WebhdfsBackend {
         if !start_after.is_empty() {
             url += format!("&startAfter={}", start_after).as_str();
         }
-        if let Some(auth) = &self.auth {
-            url += format!("&{auth}").as_str();
+        if let Some(auth) = self.auth().await? {
+            url += &format!("&{auth}");
         }
 
         let req = Request::get(&url)
@@ -320,6 +490,52 @@ impl WebhdfsBackend {
         self.client.send(req).await
     }
 
+    pub async fn webhdfs_append_request(
+        &self,
+        path: &str,
+        size: u64,
+        body: AsyncBody,
+    ) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+        let mut url = format!(
+            "{}/webhdfs/v1/{}?op=APPEND",
+            self.endpoint,
+            percent_encode_path(&p),
+        );
+        if let Some(auth) = self.auth().await? {
+            url += &format!("&{auth}");
+        }
+
+        let url = self.datanode_url(Method::POST, url).await?;
+        Request::post(&url)
+            .header(CONTENT_LENGTH, size.to_string())
+            .body(body)
+            .map_err(new_request_build_error)
+    }
+
+    pub async fn webhdfs_truncate(
+        &self,
+        path: &str,
+        size: u64,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+        let mut url = format!(
+            "{}/webhdfs/v1/{}?op=TRUNCATE&newlength={}",
+            self.endpoint,
+            percent_encode_path(&p),
+            size
+        );
+        if let Some(auth) = self.auth().await? {
+            url += &format!("&{auth}");
+        }
+
+        let req = Request::post(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+
     async fn webhdfs_read_file(
         &self,
         path: &str,
@@ -329,7 +545,7 @@ impl WebhdfsBackend {
         self.client.send(req).await
     }
 
-    async fn webhdfs_get_file_status(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+    pub async fn webhdfs_get_file_status(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
         let mut url = format!(
             "{}/webhdfs/v1/{}?op=GETFILESTATUS",
@@ -337,8 +553,8 @@ impl WebhdfsBackend {
             percent_encode_path(&p),
         );
 
-        if let Some(auth) = &self.auth {
-            url += format!("&{auth}").as_str();
+        if let Some(auth) = self.auth().await? {
+            url += &format!("&{auth}");
         }
 
         let req = Request::get(&url)
@@ -355,8 +571,8 @@ impl WebhdfsBackend {
             self.endpoint,
             percent_encode_path(&p),
         );
-        if let Some(auth) = &self.auth {
-            url += format!("&{auth}").as_str();
+        if let Some(auth) = self.auth().await? {
+            url += &format!("&{auth}");
         }
 
         let req = Request::delete(&url)
@@ -366,6 +582,155 @@ impl WebhdfsBackend {
         self.client.send(req).await
     }
 
+    /// Build the auth query of requests.
+    ///
+    /// Delegation token will be fetched or renewed here if
+    /// `fetch_delegation_token` is enabled.
+    async fn auth(&self) -> Result<Option<String>> {
+        if self.fetch_delegation_token
+            && self.current_delegation().map_or(true, |dt| dt.need_renew())
+        {
+            let _guard = self.renew_lock.lock().await;
+            // The token could have been refreshed while we are waiting.
+            match self.current_delegation() {
+                None => {
+                    let dt = self.webhdfs_get_delegation_token().await?;
+                    self.set_delegation(dt);
+                }
+                Some(mut dt) if dt.need_renew() => {
+                    match self.webhdfs_renew_delegation_token(&dt.token).await {
+                        Ok(expires_at) => dt.expires_at = Some(expires_at),
+                        // Token can't be renewed after its max lifetime, fetch a new one.
+                        Err(err) => {
+                            debug!("webhdfs renew delegation token failed: {err}");
+                            dt = self.webhdfs_get_delegation_token().await?;
+                        }
+                    }
+                    self.set_delegation(dt);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if let Some(dt) = self.current_delegation() {
+            return Ok(Some(dt.query()));
+        }
+        Ok(self
+            .user
+            .as_ref()
+            .map(|user| format!("user.name={}", percent_encode_path(user))))
+    }
+
+    fn current_delegation(&self) -> Option<DelegationToken> {
+        self.delegation.read().expect("lock must be valid").clone()
+    }
+
+    fn set_delegation(&self, dt: DelegationToken) {
+        *self.delegation.write().expect("lock must be valid") = Some(dt);
+    }
+
+    async fn webhdfs_get_delegation_token(&self) -> Result<DelegationToken> {
+        let mut url = format!("{}/webhdfs/v1/?op=GETDELEGATIONTOKEN", self.endpoint);
+        if let Some(user) = &self.user {
+            let user = percent_encode_path(user);
+            url += &format!("&user.name={user}&renewer={user}");
+        }
+
+        let req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        let resp = self.client.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let token = serde_json::from_slice::<TokenWrapper>(&bs)
+            .map_err(new_json_deserialize_error)?
+            .token
+            .url_string;
+
+        // Renew the token at once to know when it will expire.
+        let expires_at = self.webhdfs_renew_delegation_token(&token).await.ok();
+        Ok(DelegationToken {
+            token,
+            expires_at,
+            fetched: true,
+        })
+    }
+
+    async fn webhdfs_renew_delegation_token(&self, token: &str) -> Result<DateTime<Utc>> {
+        let mut url = format!(
+            "{}/webhdfs/v1/?op=RENEWDELEGATIONTOKEN&token={}",
+            self.endpoint, token
+        );
+        if let Some(user) = &self.user {
+            url += &format!("&user.name={}", percent_encode_path(user));
+        }
+
+        let req = Request::put(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        let resp = self.client.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let expires_at = serde_json::from_slice::<LongResp>(&bs)
+            .map_err(new_json_deserialize_error)?
+            .long;
+        parse_datetime_from_from_timestamp_millis(expires_at)
+    }
+
+    /// Resolve the url that data should be sent to or read from.
+    ///
+    /// If `datanode_endpoint` is not set, the namenode url will be returned
+    /// directly and the `307` redirection will be followed by http client.
+    async fn datanode_url(&self, method: Method, url: String) -> Result<String> {
+        let Some(endpoint) = &self.datanode_endpoint else {
+            return Ok(url);
+        };
+
+        let req = Request::builder()
+            .method(method)
+            .uri(format!("{url}&noredirect=true"))
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        let resp = self.client.send(req).await?;
+
+        let location = match resp.status() {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                serde_json::from_slice::<LocationResp>(&bs)
+                    .map_err(new_json_deserialize_error)?
+                    .location
+            }
+            // Some proxies may not respect `noredirect`.
+            StatusCode::TEMPORARY_REDIRECT => match parse_location(resp.headers())? {
+                Some(v) => v.to_string(),
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "webhdfs redirect without location",
+                    ))
+                }
+            },
+            _ => return Err(parse_error(resp).await?),
+        };
+
+        let uri = location.parse::<Uri>().map_err(|err| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "webhdfs datanode location is invalid",
+            )
+            .with_context("location", &location)
+            .set_source(err)
+        })?;
+        let path_and_query = uri.path_and_query().map(|v| v.as_str()).unwrap_or("/");
+        Ok(format!("{endpoint}{path_and_query}"))
+    }
+
     async fn check_root(&self) -> Result<()> {
         let resp = self.webhdfs_get_file_status("/").await?;
         match resp.status() {
@@ -394,9 +759,12 @@ impl WebhdfsBackend {
 
 #[async_trait]
 impl Accessor for WebhdfsBackend {
-    type Reader = IncomingAsyncBody;
+    type Reader = oio::TwoWaysReader<IncomingAsyncBody, oio::StitchReader<WebhdfsRangeRead>>;
     type BlockingReader = ();
-    type Writer = oio::OneShotWriter<WebhdfsWriter>;
+    type Writer = oio::TwoWaysWriter<
+        oio::OneShotWriter<WebhdfsWriter>,
+        oio::AppendObjectWriter<WebhdfsWriter>,
+    >;
     type BlockingWriter = ();
     type Lister = oio::PageLister<WebhdfsLister>;
     type BlockingLister = ();
@@ -413,6 +781,7 @@ impl Accessor for WebhdfsBackend {
                 read_with_range: true,
 
                 write: true,
+                write_can_append: true,
                 create_dir: true,
                 delete: true,
 
@@ -426,12 +795,9 @@ impl Accessor for WebhdfsBackend {
 
     /// Create a file or directory
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
-        let req = self.webhdfs_create_object_request(
-            path,
-            Some(0),
-            &OpWrite::default(),
-            AsyncBody::Empty,
-        )?;
+        let req = self
+            .webhdfs_create_object_request(path, Some(0), &OpWrite::default(), AsyncBody::Empty)
+            .await?;
 
         let resp = self.client.send(req).await?;
 
@@ -463,11 +829,41 @@ impl Accessor for WebhdfsBackend {
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         let range = args.range();
+        if let Some(chunk_size) = self.read_chunk_size {
+            let total_size = self
+                .stat(path, OpStat::new())
+                .await?
+                .into_metadata()
+                .content_length();
+            let mut r = oio::StitchReader::new(
+                WebhdfsRangeRead {
+                    backend: self.clone(),
+                    path: path.to_string(),
+                },
+                range,
+                total_size,
+            )
+            .with_chunk_size(chunk_size);
+            if let Some(v) = self.read_max_retries {
+                r = r.with_max_retries(v);
+            }
+            if let Some(v) = self.read_first_byte_timeout {
+                r = r.with_first_byte_timeout(v);
+            }
+            return Ok((
+                RpRead::new().with_size(Some(r.size())),
+                oio::TwoWaysReader::Two(r),
+            ));
+        }
+
         let resp = self.webhdfs_read_file(path, range).await?;
         match resp.status() {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
                 let size = parse_content_length(resp.headers())?;
-                Ok((RpRead::new().with_size(size), resp.into_body()))
+                Ok((
+                    RpRead::new().with_size(size),
+                    oio::TwoWaysReader::One(resp.into_body()),
+                ))
             }
             // WebHDFS will returns 403 when range is outside of the end.
             StatusCode::FORBIDDEN => {
@@ -475,21 +871,32 @@ impl Accessor for WebhdfsBackend {
                 let bs = body.bytes().await?;
                 let s = String::from_utf8_lossy(&bs);
                 if s.contains("out of the range") {
-                    Ok((RpRead::new(), IncomingAsyncBody::empty()))
+                    Ok((
+                        RpRead::new(),
+                        oio::TwoWaysReader::One(IncomingAsyncBody::empty()),
+                    ))
                 } else {
                     Err(parse_error_msg(parts, &s)?)
                 }
             }
-            StatusCode::RANGE_NOT_SATISFIABLE => Ok((RpRead::new(), IncomingAsyncBody::empty())),
+            StatusCode::RANGE_NOT_SATISFIABLE => Ok((
+                RpRead::new(),
+                oio::TwoWaysReader::One(IncomingAsyncBody::empty()),
+            )),
             _ => Err(parse_error(resp).await?),
         }
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        Ok((
-            RpWrite::default(),
-            oio::OneShotWriter::new(WebhdfsWriter::new(self.clone(), args, path.to_string())),
-        ))
+        let w = WebhdfsWriter::new(self.clone(), args.clone(), path.to_string());
+
+        let w = if args.append() {
+            oio::TwoWaysWriter::Two(oio::AppendObjectWriter::new(w))
+        } else {
+            oio::TwoWaysWriter::One(oio::OneShotWriter::new(w))
+        };
+
+        Ok((RpWrite::default(), w))
     }
 
     async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
@@ -549,3 +956,102 @@ impl Accessor for WebhdfsBackend {
         Ok((RpList::default(), oio::PageLister::new(l)))
     }
 }
+
+/// WebhdfsRangeRead sends sub-range `OPEN` requests for [`oio::StitchReader`].
+pub struct WebhdfsRangeRead {
+    backend: WebhdfsBackend,
+    path: String,
+}
+
+#[async_trait]
+impl oio::StitchRead for WebhdfsRangeRead {
+    async fn read_range(&self, range: BytesRange) -> Result<IncomingAsyncBody> {
+        let resp = self.backend.webhdfs_read_file(&self.path, range).await?;
+
+        match resp.status() {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => Ok(resp.into_body()),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::method;
+    use wiremock::matchers::query_param;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+
+    use super::*;
+
+    const DIR_STATUS: &str = r#"{"FileStatus": {
+        "length": 0,
+        "modificationTime": 1320173277227,
+        "pathSuffix": "",
+        "type": "DIRECTORY"
+    }}"#;
+
+    #[tokio::test]
+    async fn test_delegation_token_compat() -> Result<()> {
+        let server = MockServer::start().await;
+        // Check root and stat.
+        Mock::given(method("GET"))
+            .and(query_param("op", "GETFILESTATUS"))
+            .and(query_param("delegation_token", "abc"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(DIR_STATUS))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let mut builder = WebhdfsBuilder::default();
+        builder.endpoint(&server.uri()).delegation("abc");
+        let op = Operator::new(builder)?.finish();
+
+        op.stat("dir/").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_delegation_token() -> Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("op", "GETDELEGATIONTOKEN"))
+            .and(query_param("renewer", "hdfs"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(r#"{"Token": {"urlString": "xyz"}}"#),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        let expires_at = (Utc::now() + chrono::Duration::hours(1)).timestamp_millis();
+        Mock::given(method("PUT"))
+            .and(query_param("op", "RENEWDELEGATIONTOKEN"))
+            .and(query_param("token", "xyz"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(format!(r#"{{"long": {expires_at}}}"#)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("op", "GETFILESTATUS"))
+            .and(query_param("delegation", "xyz"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(DIR_STATUS))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let mut builder = WebhdfsBuilder::default();
+        builder
+            .endpoint(&server.uri())
+            .user("hdfs")
+            .fetch_delegation_token();
+        let op = Operator::new(builder)?.finish();
+
+        // Token will be fetched once and reused by later requests.
+        op.stat("dir/").await?;
+        op.stat("dir/").await?;
+        Ok(())
+    }
+}
@@ -24,6 +24,29 @@ pub(super) struct BooleanResp {
     pub boolean: bool,
 }
 
+#[derive(Debug, Deserialize)]
+pub(super) struct LongResp {
+    pub long: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub(super) struct LocationResp {
+    pub location: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub(super) struct TokenWrapper {
+    pub token: Token,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct Token {
+    pub url_string: String,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub(super) struct FileStatusWrapper {
@@ -17,9 +17,12 @@
 
 use async_trait::async_trait;
 use http::StatusCode;
+use log::debug;
 
 use super::backend::WebhdfsBackend;
 use super::error::parse_error;
+use super::message::BooleanResp;
+use super::message::FileStatusWrapper;
 use crate::raw::oio::WriteBuf;
 use crate::raw::*;
 use crate::*;
@@ -43,12 +46,15 @@ impl oio::OneShotWrite for WebhdfsWriter {
     async fn write_once(&self, bs: &dyn WriteBuf) -> Result<()> {
         let bs = bs.bytes(bs.remaining());
 
-        let req = self.backend.webhdfs_create_object_request(
-            &self.path,
-            Some(bs.len()),
-            &self.op,
-            AsyncBody::Bytes(bs),
-        )?;
+        let req = self
+            .backend
+            .webhdfs_create_object_request(
+                &self.path,
+                Some(bs.len()),
+                &self.op,
+                AsyncBody::Bytes(bs),
+            )
+            .await?;
 
         let resp = self.backend.client.send(req).await?;
 
@@ -62,3 +68,78 @@ impl oio::OneShotWrite for WebhdfsWriter {
         }
     }
 }
+
+#[async_trait]
+impl oio::AppendObjectWrite for WebhdfsWriter {
+    async fn offset(&self) -> Result<u64> {
+        let resp = self.backend.webhdfs_get_file_status(&self.path).await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let file_status = serde_json::from_slice::<FileStatusWrapper>(&bs)
+                    .map_err(new_json_deserialize_error)?
+                    .file_status;
+
+                Ok(file_status.length)
+            }
+            // WebHDFS can't append to a file that not exists, create it first.
+            StatusCode::NOT_FOUND => {
+                let req = self
+                    .backend
+                    .webhdfs_create_object_request(&self.path, Some(0), &self.op, AsyncBody::Empty)
+                    .await?;
+                let resp = self.backend.client.send(req).await?;
+
+                match resp.status() {
+                    StatusCode::CREATED | StatusCode::OK => {
+                        resp.into_body().consume().await?;
+                        Ok(0)
+                    }
+                    _ => Err(parse_error(resp).await?),
+                }
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn append(&self, _: u64, size: u64, body: AsyncBody) -> Result<()> {
+        let req = self
+            .backend
+            .webhdfs_append_request(&self.path, size, body)
+            .await?;
+
+        let resp = self.backend.client.send(req).await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// Truncate the file back to the offset before appending.
+    async fn abort(&self, offset: u64) -> Result<()> {
+        let resp = self.backend.webhdfs_truncate(&self.path, offset).await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let resp = serde_json::from_slice::<BooleanResp>(&bs)
+                    .map_err(new_json_deserialize_error)?;
+                // `false` means the last block is under recovery and the file
+                // will be truncated once the recovery is finished.
+                if !resp.boolean {
+                    debug!("webhdfs truncate {} is in progress", self.path);
+                }
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
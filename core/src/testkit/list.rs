@@ -0,0 +1,59 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use futures::TryStreamExt;
+
+use super::*;
+
+pub(super) fn cases() -> Vec<TestCase> {
+    vec![
+        TestCase::new(
+            "list_dir",
+            |cap| cap.write && cap.list && cap.delete,
+            |op| Box::pin(test_list_dir(op)),
+        ),
+        TestCase::new(
+            "list_not_exist",
+            |cap| cap.list,
+            |op| Box::pin(test_list_not_exist(op)),
+        ),
+    ]
+}
+
+/// Written file should be listed in its parent dir.
+async fn test_list_dir(op: Operator) -> Result<()> {
+    let dir = format!("{}/", gen_path());
+    let path = format!("{dir}file");
+    op.write(&path, gen_bytes(op.info().full_capability()))
+        .await?;
+
+    let entries: Vec<Entry> = op.lister(&dir).await?.try_collect().await?;
+    ensure(
+        entries.iter().any(|e| e.path() == path),
+        "written file must be listed",
+    )?;
+
+    op.delete(&path).await
+}
+
+/// List a not exist dir should return empty.
+async fn test_list_not_exist(op: Operator) -> Result<()> {
+    let dir = format!("{}/", gen_path());
+
+    let entries: Vec<Entry> = op.lister(&dir).await?.try_collect().await?;
+    ensure(entries.is_empty(), "not exist dir must be empty")
+}
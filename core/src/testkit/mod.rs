@@ -0,0 +1,199 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Conformance test kit for services and layers implemented outside of
+//! OpenDAL.
+//!
+//! The kit contains a matrix of read, write, list and presign cases. Only
+//! cases allowed by the capability of operator will be run, all data will
+//! be created under `testkit/`.
+//!
+//! Use [`testkit!`][crate::testkit!] to generate a test for an operator:
+//!
+//! ```ignore
+//! opendal::testkit!(test_my_service, {
+//!     opendal::Operator::from_accessor(MyAccessor::default())
+//! });
+//! ```
+//!
+//! Or call [`run`] directly to inspect the [`TestReport`].
+
+mod list;
+mod presign;
+mod read;
+mod write;
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use rand::thread_rng;
+use rand::Rng;
+use rand::RngCore;
+
+use crate::*;
+
+/// Generate a test that runs the whole conformance matrix against the
+/// operator built by given expr.
+///
+/// The test will panic with all failed cases.
+#[macro_export]
+macro_rules! testkit {
+    ($name:ident, $op:expr) => {
+        #[test]
+        fn $name() {
+            let op: $crate::Operator = $op;
+            let report = $crate::raw::tests::TEST_RUNTIME.block_on($crate::testkit::run(&op));
+            if let Err(err) = report.into_result() {
+                panic!("{}", err);
+            }
+        }
+    };
+}
+
+/// TestCase is a single case of the conformance matrix.
+#[derive(Clone, Copy)]
+pub struct TestCase {
+    name: &'static str,
+    required: fn(&Capability) -> bool,
+    f: fn(Operator) -> BoxFuture<'static, Result<()>>,
+}
+
+impl Debug for TestCase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TestCase")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl TestCase {
+    fn new(
+        name: &'static str,
+        required: fn(&Capability) -> bool,
+        f: fn(Operator) -> BoxFuture<'static, Result<()>>,
+    ) -> Self {
+        Self { name, required, f }
+    }
+
+    /// Get the name of this case.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Check if this case can be run with given capability.
+    pub fn is_supported(&self, cap: &Capability) -> bool {
+        (self.required)(cap)
+    }
+
+    /// Run this case against given operator.
+    pub async fn run(&self, op: &Operator) -> Result<()> {
+        (self.f)(op.clone()).await
+    }
+}
+
+/// TestReport is the result of [`run`].
+#[derive(Debug, Default)]
+pub struct TestReport {
+    /// Names of passed cases.
+    pub passed: Vec<&'static str>,
+    /// Names of cases skipped for missing capability.
+    pub skipped: Vec<&'static str>,
+    /// Failed cases and their errors.
+    pub failed: Vec<(&'static str, Error)>,
+}
+
+impl TestReport {
+    /// Convert the report into an error if any case failed.
+    pub fn into_result(self) -> Result<()> {
+        if self.failed.is_empty() {
+            return Ok(());
+        }
+
+        let mut err = Error::new(
+            ErrorKind::Unexpected,
+            &format!("{} conformance cases failed", self.failed.len()),
+        );
+        for (name, e) in self.failed {
+            err = err.with_context(name, e.to_string());
+        }
+        Err(err)
+    }
+}
+
+/// Get all cases of the conformance matrix.
+pub fn cases() -> Vec<TestCase> {
+    let mut cases = Vec::new();
+    cases.extend(read::cases());
+    cases.extend(write::cases());
+    cases.extend(list::cases());
+    cases.extend(presign::cases());
+    cases
+}
+
+/// Run all cases supported by the operator and collect the report.
+pub async fn run(op: &Operator) -> TestReport {
+    let cap = op.info().full_capability();
+
+    let mut report = TestReport::default();
+    for case in cases() {
+        if !case.is_supported(&cap) {
+            report.skipped.push(case.name);
+            continue;
+        }
+        match case.run(op).await {
+            Ok(()) => report.passed.push(case.name),
+            Err(err) => report.failed.push((case.name, err)),
+        }
+    }
+    report
+}
+
+fn gen_path() -> String {
+    format!("testkit/{}", uuid::Uuid::new_v4())
+}
+
+fn gen_bytes(cap: Capability) -> Bytes {
+    let max_size = cap.write_total_max_size.unwrap_or(64 * 1024).min(64 * 1024);
+    let mut rng = thread_rng();
+    let mut content = vec![0; rng.gen_range(4..=max_size.max(4))];
+    rng.fill_bytes(&mut content);
+    Bytes::from(content)
+}
+
+fn ensure(cond: bool, message: &'static str) -> Result<()> {
+    if cond {
+        Ok(())
+    } else {
+        Err(Error::new(ErrorKind::Unexpected, message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_run_memory() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        let report = run(&op).await;
+        assert!(!report.passed.is_empty());
+        report.into_result()
+    }
+}
@@ -0,0 +1,116 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::time::Duration;
+
+use http::header;
+
+use super::*;
+use crate::raw::*;
+
+pub(super) fn cases() -> Vec<TestCase> {
+    vec![
+        TestCase::new(
+            "presign_read",
+            |cap| cap.write && cap.presign_read && cap.delete,
+            |op| Box::pin(test_presign_read(op)),
+        ),
+        TestCase::new(
+            "presign_write",
+            |cap| cap.stat && cap.presign_write && cap.delete,
+            |op| Box::pin(test_presign_write(op)),
+        ),
+        TestCase::new(
+            "presign_stat",
+            |cap| cap.write && cap.presign_stat && cap.delete,
+            |op| Box::pin(test_presign_stat(op)),
+        ),
+    ]
+}
+
+/// Presigned read request should return the written content.
+async fn test_presign_read(op: Operator) -> Result<()> {
+    let path = gen_path();
+    let content = gen_bytes(op.info().full_capability());
+    op.write(&path, content.clone()).await?;
+
+    let req = op.presign_read(&path, Duration::from_secs(3600)).await?;
+    let resp = send(req, None).await?;
+    let bs = resp.bytes().await.map_err(new_reqwest_error)?;
+    ensure(bs == content, "content must be the same as written")?;
+
+    op.delete(&path).await
+}
+
+/// Presigned write request should create the file.
+async fn test_presign_write(op: Operator) -> Result<()> {
+    let path = gen_path();
+    let content = gen_bytes(op.info().full_capability());
+
+    let req = op.presign_write(&path, Duration::from_secs(3600)).await?;
+    send(req, Some(content.clone())).await?;
+
+    let meta = op.stat(&path).await?;
+    ensure(
+        meta.content_length() == content.len() as u64,
+        "content length must be the same as written",
+    )?;
+
+    op.delete(&path).await
+}
+
+/// Presigned stat request should return the content length.
+async fn test_presign_stat(op: Operator) -> Result<()> {
+    let path = gen_path();
+    let content = gen_bytes(op.info().full_capability());
+    op.write(&path, content.clone()).await?;
+
+    let req = op.presign_stat(&path, Duration::from_secs(3600)).await?;
+    let resp = send(req, None).await?;
+    let size = parse_content_length(resp.headers())?;
+    ensure(
+        size == Some(content.len() as u64),
+        "content length must be the same as written",
+    )?;
+
+    op.delete(&path).await
+}
+
+async fn send(req: PresignedRequest, body: Option<Bytes>) -> Result<reqwest::Response> {
+    let mut builder = reqwest::Client::new().request(req.method().clone(), req.uri().to_string());
+    for (k, v) in req.header() {
+        builder = builder.header(k, v);
+    }
+    if let Some(body) = body {
+        builder = builder
+            .header(header::CONTENT_LENGTH, body.len())
+            .body(body);
+    }
+
+    let resp = builder.send().await.map_err(new_reqwest_error)?;
+    if !resp.status().is_success() {
+        return Err(
+            Error::new(ErrorKind::Unexpected, "presigned request must succeed")
+                .with_context("status", resp.status().to_string()),
+        );
+    }
+    Ok(resp)
+}
+
+fn new_reqwest_error(err: reqwest::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, "send presigned request failed").set_source(err)
+}
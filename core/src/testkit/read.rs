@@ -0,0 +1,116 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use super::*;
+
+pub(super) fn cases() -> Vec<TestCase> {
+    vec![
+        TestCase::new(
+            "stat_not_exist",
+            |cap| cap.stat,
+            |op| Box::pin(test_stat_not_exist(op)),
+        ),
+        TestCase::new(
+            "stat_file",
+            |cap| cap.write && cap.stat && cap.delete,
+            |op| Box::pin(test_stat_file(op)),
+        ),
+        TestCase::new(
+            "read_full",
+            |cap| cap.write && cap.read && cap.delete,
+            |op| Box::pin(test_read_full(op)),
+        ),
+        TestCase::new(
+            "read_range",
+            |cap| cap.write && cap.read && cap.read_with_range && cap.delete,
+            |op| Box::pin(test_read_range(op)),
+        ),
+        TestCase::new(
+            "read_not_exist",
+            |cap| cap.read,
+            |op| Box::pin(test_read_not_exist(op)),
+        ),
+    ]
+}
+
+/// Stat a not exist file should return NotFound.
+async fn test_stat_not_exist(op: Operator) -> Result<()> {
+    match op.stat(&gen_path()).await {
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+        Err(err) => {
+            Err(Error::new(ErrorKind::Unexpected, "stat must return NotFound").set_source(err))
+        }
+        Ok(_) => Err(Error::new(
+            ErrorKind::Unexpected,
+            "stat must return NotFound",
+        )),
+    }
+}
+
+/// Stat a written file should return its mode and size.
+async fn test_stat_file(op: Operator) -> Result<()> {
+    let path = gen_path();
+    let content = gen_bytes(op.info().full_capability());
+    op.write(&path, content.clone()).await?;
+
+    let meta = op.stat(&path).await?;
+    ensure(meta.mode() == EntryMode::FILE, "mode must be file")?;
+    ensure(
+        meta.content_length() == content.len() as u64,
+        "content length must be the same as written",
+    )?;
+
+    op.delete(&path).await
+}
+
+/// Read a written file should return the same content.
+async fn test_read_full(op: Operator) -> Result<()> {
+    let path = gen_path();
+    let content = gen_bytes(op.info().full_capability());
+    op.write(&path, content.clone()).await?;
+
+    let bs = op.read(&path).await?;
+    ensure(bs == content, "content must be the same as written")?;
+
+    op.delete(&path).await
+}
+
+/// Read a range of written file should return the content in range.
+async fn test_read_range(op: Operator) -> Result<()> {
+    let path = gen_path();
+    let content = gen_bytes(op.info().full_capability());
+    op.write(&path, content.clone()).await?;
+
+    let bs = op.read_with(&path).range(1..4).await?;
+    ensure(bs == content[1..4], "content must match range")?;
+
+    op.delete(&path).await
+}
+
+/// Read a not exist file should return NotFound.
+async fn test_read_not_exist(op: Operator) -> Result<()> {
+    match op.read(&gen_path()).await {
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+        Err(err) => {
+            Err(Error::new(ErrorKind::Unexpected, "read must return NotFound").set_source(err))
+        }
+        Ok(_) => Err(Error::new(
+            ErrorKind::Unexpected,
+            "read must return NotFound",
+        )),
+    }
+}
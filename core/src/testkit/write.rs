@@ -0,0 +1,68 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use super::*;
+
+pub(super) fn cases() -> Vec<TestCase> {
+    vec![
+        TestCase::new(
+            "write_and_delete",
+            |cap| cap.write && cap.delete && cap.stat,
+            |op| Box::pin(test_write_and_delete(op)),
+        ),
+        TestCase::new(
+            "delete_not_exist",
+            |cap| cap.delete,
+            |op| Box::pin(test_delete_not_exist(op)),
+        ),
+        TestCase::new(
+            "create_dir",
+            |cap| cap.create_dir && cap.stat && cap.delete,
+            |op| Box::pin(test_create_dir(op)),
+        ),
+    ]
+}
+
+/// Written file should not exist after deleted.
+async fn test_write_and_delete(op: Operator) -> Result<()> {
+    let path = gen_path();
+    op.write(&path, gen_bytes(op.info().full_capability()))
+        .await?;
+    ensure(op.is_exist(&path).await?, "file must exist after written")?;
+
+    op.delete(&path).await?;
+    ensure(
+        !op.is_exist(&path).await?,
+        "file must not exist after deleted",
+    )
+}
+
+/// Delete a not exist file should succeed.
+async fn test_delete_not_exist(op: Operator) -> Result<()> {
+    op.delete(&gen_path()).await
+}
+
+/// Created dir should be stat as dir.
+async fn test_create_dir(op: Operator) -> Result<()> {
+    let path = format!("{}/", gen_path());
+    op.create_dir(&path).await?;
+
+    let meta = op.stat(&path).await?;
+    ensure(meta.mode() == EntryMode::DIR, "mode must be dir")?;
+
+    op.delete(&path).await
+}
@@ -0,0 +1,56 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Transfer files between operators.
+//!
+//! [`copy`] picks the cheapest strategy that both operators support:
+//!
+//! - [`TransferStrategy::ServerSide`]: both operators point to the same
+//!   bucket or container, the file is copied by the service directly.
+//! - [`TransferStrategy::CopyFromUrl`]: source supports
+//!   [`Capability::presign_read`](crate::Capability::presign_read) and
+//!   destination supports
+//!   [`Capability::copy_from_url`](crate::Capability::copy_from_url), the
+//!   destination service will fetch the file from a presigned url.
+//! - [`TransferStrategy::Streamed`]: the file is read from source in chunks
+//!   concurrently and written into destination.
+//!
+//! # Examples
+//!
+//! ```
+//! use opendal::transfer;
+//! use opendal::transfer::Transfer;
+//! use opendal::Operator;
+//! use opendal::Result;
+//!
+//! async fn test(src: Operator, dst: Operator) -> Result<()> {
+//!     let strategy = transfer::copy(&src, "path/to/file", &dst, "path/to/file").await?;
+//!     println!("file is copied via {strategy:?}");
+//!
+//!     Transfer::new(src, dst)
+//!         .with_concurrent(8)
+//!         .with_chunk_size(16 * 1024 * 1024)
+//!         .copy("path/to/large_file", "path/to/large_file")
+//!         .await?;
+//!     Ok(())
+//! }
+//! ```
+
+mod planner;
+pub use planner::copy;
+pub use planner::Transfer;
+pub use planner::TransferStrategy;
@@ -0,0 +1,343 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::stream;
+use futures::StreamExt;
+use futures::TryStreamExt;
+
+use crate::raw::*;
+use crate::*;
+
+const DEFAULT_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+const DEFAULT_PRESIGN_EXPIRE: Duration = Duration::from_secs(3600);
+
+/// TransferStrategy is the way that [`Transfer`] copies a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TransferStrategy {
+    /// Copied by the service directly via [`Operator::copy`].
+    ServerSide,
+    /// Copied by the destination service from a presigned url of source.
+    CopyFromUrl,
+    /// Read from source and written into destination.
+    Streamed,
+}
+
+/// Copy a file from `src` of `src_op` to `dst` of `dst_op` with the best
+/// strategy, returns the strategy that has been used.
+///
+/// This is a shortcut of [`Transfer::copy`] with default options.
+pub async fn copy(
+    src_op: &Operator,
+    src: &str,
+    dst_op: &Operator,
+    dst: &str,
+) -> Result<TransferStrategy> {
+    Transfer::new(src_op.clone(), dst_op.clone())
+        .copy(src, dst)
+        .await
+}
+
+/// Transfer copies files between two operators.
+#[derive(Debug, Clone)]
+pub struct Transfer {
+    src: Operator,
+    dst: Operator,
+    concurrent: usize,
+    chunk_size: usize,
+    presign_expire: Duration,
+}
+
+impl Transfer {
+    /// Create a transfer from `src` operator to `dst` operator.
+    pub fn new(src: Operator, dst: Operator) -> Self {
+        Self {
+            src,
+            dst,
+            concurrent: 4,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            presign_expire: DEFAULT_PRESIGN_EXPIRE,
+        }
+    }
+
+    /// Set the number of chunks that will be read concurrently in
+    /// [`TransferStrategy::Streamed`].
+    ///
+    /// Default to `4`.
+    pub fn with_concurrent(mut self, concurrent: usize) -> Self {
+        self.concurrent = concurrent.max(1);
+        self
+    }
+
+    /// Set the size of chunks in [`TransferStrategy::Streamed`].
+    ///
+    /// Default to 8 MiB.
+    pub fn with_chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = size.max(1);
+        self
+    }
+
+    /// Set the expire duration of the presigned url in
+    /// [`TransferStrategy::CopyFromUrl`].
+    ///
+    /// Default to 1 hour.
+    pub fn with_presign_expire(mut self, expire: Duration) -> Self {
+        self.presign_expire = expire;
+        self
+    }
+
+    /// Plan the strategy that will be used to copy `src`.
+    pub fn plan(&self, src: &str) -> TransferStrategy {
+        if self.server_side_path(src).is_some() {
+            return TransferStrategy::ServerSide;
+        }
+
+        let src_cap = self.src.info().full_capability();
+        let dst_cap = self.dst.info().full_capability();
+        if src_cap.presign_read && dst_cap.copy_from_url {
+            return TransferStrategy::CopyFromUrl;
+        }
+        TransferStrategy::Streamed
+    }
+
+    /// Copy `src` of source operator to `dst` of destination operator,
+    /// returns the strategy that has been used.
+    pub async fn copy(&self, src: &str, dst: &str) -> Result<TransferStrategy> {
+        let strategy = self.plan(src);
+        match strategy {
+            TransferStrategy::ServerSide => {
+                let from = self
+                    .server_side_path(src)
+                    .expect("server side path must be valid");
+                self.dst.copy(&from, dst).await?;
+            }
+            TransferStrategy::CopyFromUrl => {
+                let req = self.src.presign_read(src, self.presign_expire).await?;
+                self.dst
+                    .inner()
+                    .copy(
+                        &self.src.normalize(src)?,
+                        &self.dst.normalize(dst)?,
+                        OpCopy::new().with_source_url(&req.uri().to_string()),
+                    )
+                    .await?;
+            }
+            TransferStrategy::Streamed => self.copy_streamed(src, dst).await?,
+        }
+        Ok(strategy)
+    }
+
+    /// Build the path of `src` relative to the root of destination if
+    /// both operators point to the same bucket or container of the same
+    /// endpoint.
+    fn server_side_path(&self, src: &str) -> Option<String> {
+        let (src_info, dst_info) = (self.src.info(), self.dst.info());
+        if !dst_info.full_capability().copy {
+            return None;
+        }
+
+        // Services like memory don't have a name, they are only the same
+        // while the operators are cloned from the same one.
+        let same_service = Arc::ptr_eq(self.src.inner(), self.dst.inner())
+            || (src_info.scheme() == dst_info.scheme()
+                && !src_info.name().is_empty()
+                && src_info.name() == dst_info.name()
+                && src_info.endpoint() == dst_info.endpoint());
+        if !same_service {
+            return None;
+        }
+
+        let src = self.src.normalize(src).ok()?;
+        let abs = build_abs_path(&normalize_root(src_info.root()), &src);
+        let dst_root = build_abs_path(&normalize_root(dst_info.root()), "");
+        abs.strip_prefix(&dst_root).map(|v| v.to_string())
+    }
+
+    async fn copy_streamed(&self, src: &str, dst: &str) -> Result<()> {
+        let size = self.src.stat(src).await?.content_length();
+        let chunk_size = self.chunk_size as u64;
+
+        let mut chunks = stream::iter((0..size).step_by(self.chunk_size))
+            .map(|offset| {
+                let range = offset..(offset + chunk_size).min(size);
+                self.src.read_with(src).range(range)
+            })
+            .buffered(self.concurrent);
+
+        let mut w = self.dst.writer(dst).await?;
+        loop {
+            let res = match chunks.try_next().await {
+                Ok(Some(bs)) => w.write(bs).await,
+                Ok(None) => break,
+                Err(err) => Err(err),
+            };
+            if let Err(err) = res {
+                let _ = w.abort().await;
+                return Err(err.with_operation("Transfer::copy"));
+            }
+        }
+        w.close().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_transfer_server_side() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        op.write("src", "Hello, World!").await?;
+
+        let strategy = copy(&op, "src", &op.clone(), "dst").await?;
+        assert_eq!(strategy, TransferStrategy::ServerSide);
+        assert_eq!(op.read("dst").await?, b"Hello, World!");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transfer_streamed() -> Result<()> {
+        let src = Operator::new(Memory::default())?.finish();
+        let dst = Operator::new(Memory::default())?.finish();
+        let content: Vec<u8> = (0..100u8).collect();
+        src.write("src", content.clone()).await?;
+
+        let strategy = Transfer::new(src, dst.clone())
+            .with_chunk_size(7)
+            .with_concurrent(3)
+            .copy("src", "dst")
+            .await?;
+        assert_eq!(strategy, TransferStrategy::Streamed);
+        assert_eq!(dst.read("dst").await?, content);
+        Ok(())
+    }
+
+    #[cfg(all(feature = "services-azblob", feature = "services-s3"))]
+    mod copy_from_url {
+        use wiremock::matchers::header_exists;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use super::*;
+        use crate::services::Azblob;
+        use crate::services::S3;
+
+        fn new_azblob(endpoint: &str) -> Operator {
+            let mut builder = Azblob::default();
+            builder
+                .emulator(Emulator::Azurite)
+                .endpoint(endpoint)
+                .container("container");
+            Operator::new(builder).expect("must init").finish()
+        }
+
+        fn new_s3(endpoint: &str) -> Operator {
+            let mut builder = S3::default();
+            builder
+                .endpoint(endpoint)
+                .bucket("container")
+                .region("us-east-1")
+                .access_key_id("access_key_id")
+                .secret_access_key("secret_access_key");
+            Operator::new(builder).expect("must init").finish()
+        }
+
+        #[test]
+        fn test_plan_different_endpoints() {
+            let src = new_azblob("http://127.0.0.1:10000/account1");
+            let dst = new_azblob("http://127.0.0.1:10000/account2");
+            assert_ne!(
+                Transfer::new(src, dst).plan("file"),
+                TransferStrategy::ServerSide
+            );
+
+            let src = new_azblob("http://127.0.0.1:10000/account1");
+            let dst = new_azblob("http://127.0.0.1:10000/account1");
+            assert_eq!(
+                Transfer::new(src, dst).plan("file"),
+                TransferStrategy::ServerSide
+            );
+        }
+
+        #[tokio::test]
+        async fn test_transfer_copy_from_url() -> Result<()> {
+            let server = MockServer::start().await;
+            Mock::given(method("PUT"))
+                .and(path("/container/dst"))
+                .and(header_exists("x-ms-copy-source"))
+                .respond_with(
+                    ResponseTemplate::new(202).insert_header("x-ms-copy-status", "pending"),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("HEAD"))
+                .and(path("/container/dst"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("content-length", "0")
+                        .insert_header("x-ms-copy-status", "success"),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let src = new_s3("https://s3.amazonaws.com");
+            let dst = new_azblob(&server.uri());
+            let strategy = Transfer::new(src, dst).copy("src", "dst").await?;
+            assert_eq!(strategy, TransferStrategy::CopyFromUrl);
+            Ok(())
+        }
+
+        #[tokio::test]
+        async fn test_transfer_copy_from_url_failed() {
+            let server = MockServer::start().await;
+            Mock::given(method("PUT"))
+                .and(path("/container/dst"))
+                .respond_with(
+                    ResponseTemplate::new(202).insert_header("x-ms-copy-status", "pending"),
+                )
+                .mount(&server)
+                .await;
+            Mock::given(method("HEAD"))
+                .and(path("/container/dst"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("content-length", "0")
+                        .insert_header("x-ms-copy-status", "failed")
+                        .insert_header("x-ms-copy-status-description", "source not found"),
+                )
+                .mount(&server)
+                .await;
+
+            let src = new_s3("https://s3.amazonaws.com");
+            let dst = new_azblob(&server.uri());
+            let err = Transfer::new(src, dst)
+                .copy("src", "dst")
+                .await
+                .expect_err("must fail");
+            assert_eq!(err.kind(), ErrorKind::Unexpected);
+        }
+    }
+}
@@ -0,0 +1,228 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use arrow_array::builder::StringBuilder;
+use arrow_array::builder::TimestampMicrosecondBuilder;
+use arrow_array::builder::UInt64Builder;
+use arrow_array::ArrayRef;
+use arrow_array::RecordBatch;
+use arrow_schema::DataType;
+use arrow_schema::Field;
+use arrow_schema::Schema;
+use arrow_schema::SchemaRef;
+use arrow_schema::TimeUnit;
+use futures::Stream;
+use futures::StreamExt;
+
+use crate::*;
+
+/// Build the arrow schema of entries.
+fn entry_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("path", DataType::Utf8, false),
+        Field::new("size", DataType::UInt64, true),
+        Field::new(
+            "mtime",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            true,
+        ),
+        Field::new("etag", DataType::Utf8, true),
+        Field::new("content_type", DataType::Utf8, true),
+    ]))
+}
+
+/// Convert entries into a record batch.
+///
+/// Metadata that is not known (not included in metakey or not returned by
+/// service) will be `null`, dirs always have `null` size.
+fn entries_to_record_batch(entries: &[Entry]) -> Result<RecordBatch> {
+    let mut path = StringBuilder::with_capacity(entries.len(), entries.len() * 32);
+    let mut size = UInt64Builder::with_capacity(entries.len());
+    let mut mtime = TimestampMicrosecondBuilder::with_capacity(entries.len()).with_timezone("UTC");
+    let mut etag = StringBuilder::new();
+    let mut content_type = StringBuilder::new();
+
+    for entry in entries {
+        let meta = entry.metadata();
+        path.append_value(entry.path());
+
+        if meta.is_file() && meta.contains_metakey(Metakey::ContentLength) {
+            size.append_value(meta.content_length());
+        } else {
+            size.append_null();
+        }
+        if meta.contains_metakey(Metakey::LastModified) {
+            mtime.append_option(meta.last_modified().map(|v| v.timestamp_micros()));
+        } else {
+            mtime.append_null();
+        }
+        if meta.contains_metakey(Metakey::Etag) {
+            etag.append_option(meta.etag());
+        } else {
+            etag.append_null();
+        }
+        if meta.contains_metakey(Metakey::ContentType) {
+            content_type.append_option(meta.content_type());
+        } else {
+            content_type.append_null();
+        }
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(path.finish()),
+        Arc::new(size.finish()),
+        Arc::new(mtime.finish()),
+        Arc::new(etag.finish()),
+        Arc::new(content_type.finish()),
+    ];
+    RecordBatch::try_new(entry_schema(), columns).map_err(|err| {
+        Error::new(ErrorKind::Unexpected, "build arrow record batch failed").set_source(err)
+    })
+}
+
+impl Lister {
+    /// The arrow schema of record batches returned by [`Lister::into_arrow`].
+    ///
+    /// | column         | type                     | nullable |
+    /// |----------------|--------------------------|----------|
+    /// | `path`         | `Utf8`                   | false    |
+    /// | `size`         | `UInt64`                 | true     |
+    /// | `mtime`        | `Timestamp(µs, "UTC")`   | true     |
+    /// | `etag`         | `Utf8`                   | true     |
+    /// | `content_type` | `Utf8`                   | true     |
+    pub fn arrow_schema() -> SchemaRef {
+        entry_schema()
+    }
+
+    /// Convert this lister into a stream of arrow record batches, every
+    /// batch contains at most `batch_size` entries.
+    ///
+    /// Only metadata included in the metakey of lister will be filled,
+    /// others will be `null`. Please specify metakey like the following
+    /// to get all columns:
+    ///
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use opendal::Metakey;
+    /// use opendal::Operator;
+    /// use opendal::Result;
+    ///
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let mut batches = op
+    ///         .lister_with("dir/")
+    ///         .metakey(
+    ///             Metakey::ContentLength
+    ///                 | Metakey::LastModified
+    ///                 | Metakey::Etag
+    ///                 | Metakey::ContentType,
+    ///         )
+    ///         .await?
+    ///         .into_arrow(1024);
+    ///
+    ///     while let Some(batch) = batches.try_next().await? {
+    ///         println!("got {} entries", batch.num_rows());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn into_arrow(
+        self,
+        batch_size: usize,
+    ) -> impl Stream<Item = Result<RecordBatch>> + Send + Unpin {
+        self.chunks(batch_size.max(1)).map(|entries| {
+            let entries = entries.into_iter().collect::<Result<Vec<_>>>()?;
+            entries_to_record_batch(&entries)
+        })
+    }
+}
+
+impl BlockingLister {
+    /// The arrow schema of record batches returned by
+    /// [`BlockingLister::into_arrow`].
+    ///
+    /// Read [`Lister::arrow_schema`] for more details.
+    pub fn arrow_schema() -> SchemaRef {
+        entry_schema()
+    }
+
+    /// Convert this lister into an iterator of arrow record batches, every
+    /// batch contains at most `batch_size` entries.
+    ///
+    /// Read [`Lister::into_arrow`] for more details.
+    pub fn into_arrow(self, batch_size: usize) -> impl Iterator<Item = Result<RecordBatch>> {
+        let batch_size = batch_size.max(1);
+        let mut lister = self.peekable();
+        std::iter::from_fn(move || {
+            lister.peek()?;
+            let entries = lister.by_ref().take(batch_size).collect::<Result<Vec<_>>>();
+            Some(entries.and_then(|entries| entries_to_record_batch(&entries)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::UInt64Type;
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_into_arrow() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        op.write("dir/a", "Hello").await?;
+        op.write("dir/b", "World!").await?;
+        op.create_dir("dir/c/").await?;
+
+        let batches: Vec<RecordBatch> = op
+            .lister_with("dir/")
+            .metakey(Metakey::ContentLength)
+            .await?
+            .into_arrow(2)
+            .try_collect()
+            .await?;
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].schema(), Lister::arrow_schema());
+
+        let mut sizes: Vec<_> = batches
+            .iter()
+            .flat_map(|b| {
+                b.column(1)
+                    .as_primitive::<UInt64Type>()
+                    .iter()
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        sizes.sort();
+        assert_eq!(sizes, vec![None, Some(5), Some(6)]);
+
+        let batches: Vec<_> = op
+            .blocking()
+            .lister("dir/")?
+            .into_arrow(10)
+            .collect::<Result<_>>()?;
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].num_rows(), 3);
+        // Etag is not in metakey.
+        assert_eq!(batches[0].column(3).null_count(), 3);
+        Ok(())
+    }
+}
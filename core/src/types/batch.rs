@@ -0,0 +1,220 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+use crate::raw::*;
+use crate::*;
+
+/// BatchEntry is the outcome of a single path in batch operations like
+/// [`Operator::remove_via`].
+///
+/// `T` is the reply returned by service for this path on success, for
+/// example [`RpDelete`] for [`Operator::remove_via`].
+#[derive(Debug)]
+pub struct BatchEntry<T> {
+    path: String,
+    result: Result<T>,
+}
+
+impl<T> BatchEntry<T> {
+    /// Path of this entry.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Check if the operation on this entry succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    /// Reply returned for this entry, if succeeded.
+    pub fn reply(&self) -> Option<&T> {
+        self.result.as_ref().ok()
+    }
+
+    /// Error returned for this entry, if any.
+    pub fn error(&self) -> Option<&Error> {
+        self.result.as_ref().err()
+    }
+
+    /// The error code returned by service for this entry, if any.
+    ///
+    /// Only services that report per-item error codes in their batch
+    /// responses (like `s3`) will have this set.
+    pub fn code(&self) -> Option<&str> {
+        self.error().and_then(|err| err.context_value("code"))
+    }
+
+    /// Consume this entry to get the result.
+    pub fn into_result(self) -> Result<T> {
+        self.result
+    }
+}
+
+/// BatchResults contains the outcomes of batch operations like
+/// [`Operator::remove_via`].
+///
+/// Entries are kept in the same order as the input.
+#[derive(Debug)]
+pub struct BatchResults<T> {
+    entries: Vec<BatchEntry<T>>,
+    failures: usize,
+}
+
+impl<T> Default for BatchResults<T> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            failures: 0,
+        }
+    }
+}
+
+impl<T> BatchResults<T> {
+    /// Get all entries.
+    pub fn entries(&self) -> &[BatchEntry<T>] {
+        &self.entries
+    }
+
+    /// Consume self to get all entries.
+    pub fn into_entries(self) -> Vec<BatchEntry<T>> {
+        self.entries
+    }
+
+    /// The number of failed entries.
+    pub fn failures(&self) -> usize {
+        self.failures
+    }
+
+    /// Check if all entries succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.failures == 0
+    }
+
+    /// Push a new outcome.
+    ///
+    /// Returns the error of this outcome if the number of failures
+    /// exceeds `max_failures`, callers should abort the whole batch.
+    pub(crate) fn push(
+        &mut self,
+        path: String,
+        result: Result<T>,
+        max_failures: usize,
+    ) -> Result<()> {
+        let result = match result {
+            Err(err) if self.failures >= max_failures => {
+                return Err(err
+                    .with_context("path", path)
+                    .with_context("failures", (self.failures + 1).to_string()));
+            }
+            Err(err) => {
+                self.failures += 1;
+                Err(err)
+            }
+            Ok(v) => Ok(v),
+        };
+        self.entries.push(BatchEntry { path, result });
+
+        Ok(())
+    }
+}
+
+impl BatchResults<RpDelete> {
+    /// Push the reply of a batch call whose input paths are `paths`.
+    ///
+    /// Services could return results in any order, we will reorder them
+    /// to match the input.
+    pub(crate) fn push_batch(
+        &mut self,
+        paths: Vec<String>,
+        rp: RpBatch,
+        max_failures: usize,
+    ) -> Result<()> {
+        let mut replies: HashMap<String, VecDeque<Result<RpDelete>>> = HashMap::new();
+        for (path, res) in rp.into_results() {
+            let res = res.map(|v| match v {
+                BatchedReply::Delete(v) => v,
+            });
+            replies.entry(path).or_default().push_back(res);
+        }
+
+        for path in paths {
+            let res = replies
+                .get_mut(&path)
+                .and_then(|v| v.pop_front())
+                .unwrap_or_else(|| {
+                    Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "batch reply doesn't contain result of this path",
+                    ))
+                });
+            self.push(path, res, max_failures)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_batch_reorder() {
+        let mut results = BatchResults::default();
+        let rp = RpBatch::new(vec![
+            ("b".to_string(), Ok(RpDelete::default().into())),
+            (
+                "c".to_string(),
+                Err(Error::new(ErrorKind::PermissionDenied, "denied")
+                    .with_context("code", "AccessDenied")),
+            ),
+            ("a".to_string(), Ok(RpDelete::default().into())),
+        ]);
+
+        results
+            .push_batch(
+                vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                rp,
+                1,
+            )
+            .expect("must not abort");
+
+        let paths: Vec<_> = results.entries().iter().map(|v| v.path()).collect();
+        assert_eq!(paths, vec!["a", "b", "c"]);
+        assert_eq!(results.failures(), 1);
+        assert!(results.entries()[0].reply().is_some());
+        assert!(results.entries()[2].reply().is_none());
+        assert_eq!(results.entries()[2].code(), Some("AccessDenied"));
+    }
+
+    #[test]
+    fn test_push_abort() {
+        let mut results: BatchResults<RpDelete> = BatchResults::default();
+        let rp = RpBatch::new(vec![(
+            "a".to_string(),
+            Err(Error::new(ErrorKind::Unexpected, "failed")),
+        )]);
+
+        let err = results
+            .push_batch(vec!["a".to_string()], rp, 0)
+            .expect_err("must abort");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+}
@@ -0,0 +1,212 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use tokio::sync::OwnedSemaphorePermit;
+use tokio::sync::Semaphore;
+
+/// The unit of semaphore permits, so that a single permit could cover
+/// buffers larger than `u32::MAX`.
+const PERMIT_UNIT: usize = 1024;
+
+/// BufferPool accounts memory used by buffers of readers and writers, and
+/// bounds the total usage if capacity is set.
+///
+/// BufferPool doesn't own or recycle the buffers themselves: they are still
+/// allocated by readers, writers and services. The pool only decides how
+/// many bytes could be held at the same time, so that memory usage across
+/// streams is bounded and observable.
+///
+/// BufferPool is cheap to clone, all clones share the same accounting.
+/// Attach it to operators via
+/// [`BufferPoolLayer`](crate::layers::BufferPoolLayer).
+///
+/// # Examples
+///
+/// ```
+/// use opendal::BufferPool;
+///
+/// let pool = BufferPool::with_capacity(256 * 1024 * 1024);
+/// println!("used: {}, peak: {}", pool.used(), pool.peak());
+/// ```
+#[derive(Clone, Default)]
+pub struct BufferPool {
+    inner: Arc<BufferPoolInner>,
+}
+
+#[derive(Default)]
+struct BufferPoolInner {
+    capacity: Option<usize>,
+    semaphore: Option<Arc<Semaphore>>,
+    used: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl Debug for BufferPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BufferPool")
+            .field("capacity", &self.inner.capacity)
+            .field("used", &self.used())
+            .field("peak", &self.peak())
+            .finish()
+    }
+}
+
+impl BufferPool {
+    /// Create a buffer pool without capacity, only accounting is performed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a buffer pool that uses at most `capacity` bytes.
+    ///
+    /// Buffers larger than capacity will be treated as capacity, so that
+    /// they could still be served once the pool is idle.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let permits = to_units(capacity).clamp(1, Semaphore::MAX_PERMITS);
+        Self {
+            inner: Arc::new(BufferPoolInner {
+                capacity: Some(capacity),
+                semaphore: Some(Arc::new(Semaphore::new(permits))),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// The capacity of this pool.
+    pub fn capacity(&self) -> Option<usize> {
+        self.inner.capacity
+    }
+
+    /// The bytes currently used.
+    pub fn used(&self) -> usize {
+        self.inner.used.load(Ordering::Relaxed)
+    }
+
+    /// The max bytes used at the same time.
+    pub fn peak(&self) -> usize {
+        self.inner.peak.load(Ordering::Relaxed)
+    }
+
+    /// Acquire `size` bytes from the pool, waiting until enough bytes
+    /// are released if capacity is reached.
+    pub async fn acquire(&self, size: usize) -> BufferPermit {
+        let permit = match &self.inner.semaphore {
+            Some(semaphore) => Some(
+                semaphore
+                    .clone()
+                    .acquire_many_owned(self.permits(size))
+                    .await
+                    .expect("semaphore must be valid"),
+            ),
+            None => None,
+        };
+        self.new_permit(size, permit)
+    }
+
+    /// Try to acquire `size` bytes from the pool, returns `None` if
+    /// capacity is reached.
+    pub fn try_acquire(&self, size: usize) -> Option<BufferPermit> {
+        let permit = match &self.inner.semaphore {
+            Some(semaphore) => Some(
+                semaphore
+                    .clone()
+                    .try_acquire_many_owned(self.permits(size))
+                    .ok()?,
+            ),
+            None => None,
+        };
+        Some(self.new_permit(size, permit))
+    }
+
+    fn permits(&self, size: usize) -> u32 {
+        let size = self.inner.capacity.map_or(size, |cap| size.min(cap));
+        to_units(size).min(u32::MAX as usize) as u32
+    }
+
+    fn new_permit(&self, size: usize, permit: Option<OwnedSemaphorePermit>) -> BufferPermit {
+        let used = self.inner.used.fetch_add(size, Ordering::Relaxed) + size;
+        self.inner.peak.fetch_max(used, Ordering::Relaxed);
+
+        BufferPermit {
+            pool: self.clone(),
+            size,
+            _permit: permit,
+        }
+    }
+}
+
+fn to_units(size: usize) -> usize {
+    size / PERMIT_UNIT + usize::from(size % PERMIT_UNIT != 0)
+}
+
+/// BufferPermit holds bytes acquired from [`BufferPool`] and releases them
+/// while dropped.
+pub struct BufferPermit {
+    pool: BufferPool,
+    size: usize,
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl Debug for BufferPermit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BufferPermit")
+            .field("size", &self.size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BufferPermit {
+    /// The bytes held by this permit.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl Drop for BufferPermit {
+    fn drop(&mut self) {
+        self.pool.inner.used.fetch_sub(self.size, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_buffer_pool() {
+        let pool = BufferPool::with_capacity(4096);
+
+        let a = pool.acquire(3000).await;
+        assert_eq!(pool.used(), 3000);
+        assert!(pool.try_acquire(2048).is_none());
+
+        // Buffers larger than capacity could be served while idle.
+        drop(a);
+        let b = pool.try_acquire(8192).expect("must be served");
+        assert_eq!(pool.used(), 8192);
+        drop(b);
+
+        assert_eq!(pool.used(), 0);
+        assert_eq!(pool.peak(), 8192);
+    }
+}
@@ -61,6 +61,15 @@ pub struct Capability {
     pub stat_with_if_match: bool,
     /// If operator supports stat with if none match.
     pub stat_with_if_none_match: bool,
+    /// If operator supports stat with per-operation signer.
+    pub stat_with_signer: bool,
+    /// If operator supports stat a snapshot of object.
+    pub stat_with_snapshot: bool,
+    /// If operator supports stat a version of object.
+    pub stat_with_version: bool,
+    /// If operator supports stat on root to fetch backend-level metadata
+    /// like creation time and region of the bucket.
+    pub stat_root: bool,
 
     /// If operator supports read.
     pub read: bool,
@@ -80,6 +89,14 @@ pub struct Capability {
     pub read_with_override_content_disposition: bool,
     /// if operator supports read with override content type.
     pub read_with_override_content_type: bool,
+    /// If operator supports read with per-operation signer.
+    pub read_with_signer: bool,
+    /// If operator supports read a snapshot of object.
+    pub read_with_snapshot: bool,
+    /// If operator supports read a version of object.
+    pub read_with_version: bool,
+    /// If operator supports read with lease id.
+    pub read_with_lease_id: bool,
 
     /// If operator supports write.
     pub write: bool,
@@ -95,6 +112,31 @@ pub struct Capability {
     pub write_with_content_disposition: bool,
     /// If operator supports write with cache control.
     pub write_with_cache_control: bool,
+    /// If operator supports write with sparse, a.k.a, leave holes for zero ranges.
+    pub write_with_sparse: bool,
+    /// If operator supports write with user metadata.
+    pub write_with_user_metadata: bool,
+    /// If operator supports write with tags.
+    pub write_with_tags: bool,
+    /// If operator supports write with storage class.
+    pub write_with_storage_class: bool,
+    /// If operator supports write with lease id.
+    pub write_with_lease_id: bool,
+    /// If operator supports write with if match.
+    pub write_with_if_match: bool,
+    /// If operator supports write with if none match.
+    pub write_with_if_none_match: bool,
+    /// If operator supports write with per-operation signer.
+    pub write_with_signer: bool,
+    /// If operator supports write with retention, a.k.a, WORM.
+    pub write_with_retention: bool,
+    /// If operator supports write with legal hold.
+    pub write_with_legal_hold: bool,
+    /// If operator supports write with expire time.
+    pub write_with_expire_at: bool,
+    /// If operator supports write with compose sources, a.k.a, composing
+    /// the object from other urls at server side.
+    pub write_with_compose_sources: bool,
     /// write_multi_max_size is the max size that services support in write_multi.
     ///
     /// For example, AWS S3 supports 5GiB as max in write_multi.
@@ -111,19 +153,66 @@ pub struct Capability {
     ///
     /// For example, Cloudflare D1 supports 1MB as max in write_total.
     pub write_total_max_size: Option<usize>,
+    /// write_multi_max_parts is the max parts that services support in write_multi.
+    ///
+    /// For example, AWS S3 supports 10000 parts as max in write_multi.
+    pub write_multi_max_parts: Option<usize>,
 
     /// If operator supports create dir.
     pub create_dir: bool,
 
     /// If operator supports delete.
     pub delete: bool,
+    /// If operator supports delete with trash.
+    pub delete_with_trash: bool,
+    /// If operator supports delete with recursive.
+    pub delete_with_recursive: bool,
+    /// If operator supports delete all entries with given prefix.
+    pub delete_with_prefix: bool,
+    /// If operator supports delete with lease id.
+    pub delete_with_lease_id: bool,
+    /// If operator supports delete with snapshots of object.
+    pub delete_with_snapshots: bool,
+    /// If operator supports delete a version of object.
+    pub delete_with_version: bool,
+    /// If operator reports deleting a missing path as `NotFound` while
+    /// `must_exist` is set.
+    ///
+    /// Otherwise `must_exist` will be checked via `stat` before delete.
+    pub delete_with_must_exist: bool,
 
     /// If operator supports copy.
     pub copy: bool,
+    /// If operator supports copy with storage class, which could be used to
+    /// change the storage class of an object in place.
+    pub copy_with_storage_class: bool,
+    /// If operator supports copy with replacing content type.
+    pub copy_with_content_type: bool,
+    /// If operator supports copy with replacing cache control.
+    pub copy_with_cache_control: bool,
+    /// If operator supports copy with replacing user metadata.
+    pub copy_with_user_metadata: bool,
+    /// If operator supports copy with replacing tags.
+    pub copy_with_tags: bool,
+    /// If operator supports copy from a url, which could be a presigned
+    /// url of another service.
+    pub copy_from_url: bool,
+
+    /// If operator supports concat objects at server side.
+    pub concat: bool,
 
     /// If operator supports rename.
     pub rename: bool,
 
+    /// If operator supports create symlink.
+    pub create_symlink: bool,
+    /// If operator supports set permissions.
+    pub set_permissions: bool,
+    /// If operator supports create snapshot of object.
+    pub create_snapshot: bool,
+    /// If operator supports acquire, renew, release and break lease of object.
+    pub lease: bool,
+
     /// If operator supports list.
     pub list: bool,
     /// If backend supports list with limit.
@@ -134,6 +223,23 @@ pub struct Capability {
     pub list_with_recursive: bool,
     /// If backend supports list without recursive.
     pub list_without_recursive: bool,
+    /// If backend supports list with delimiter.
+    pub list_with_delimiter: bool,
+    /// If backend supports filtering entries by last modified time while
+    /// listing.
+    ///
+    /// Lister will filter entries by itself if not supported.
+    pub list_with_modified_since: bool,
+    /// If backend supports list with snapshots of objects included.
+    pub list_with_snapshots: bool,
+    /// If backend supports list with versions of objects included.
+    pub list_with_versions: bool,
+    /// The max entries that backend returns in one page of list.
+    ///
+    /// For example, AWS S3 returns 1000 entries as max in one page.
+    pub list_max_page_size: Option<usize>,
+    /// If operator supports list all buckets or containers in the account.
+    pub list_roots: bool,
 
     /// If operator supports presign.
     pub presign: bool,
@@ -153,6 +259,43 @@ pub struct Capability {
 
     /// If operator supports blocking.
     pub blocking: bool,
+
+    /// If operator stats the path again after read or list failed to
+    /// return `IsADirectory` or `NotADirectory` for misused paths.
+    ///
+    /// This costs an extra request on every `NotFound` read and every
+    /// empty list, enable it via `EmulateLayer::with_path_misuse`.
+    pub path_misuse_check: bool,
+}
+
+impl Capability {
+    /// Pick the chunk size for writing `total` bytes in write_multi.
+    ///
+    /// The chunk size will be the smallest size that can write `total`
+    /// bytes within `write_multi_max_parts`, but at least
+    /// `write_multi_min_size` and `default` and at most
+    /// `write_multi_max_size`, aligned to `write_multi_align_size`.
+    ///
+    /// Returns `None` if the service doesn't support write_multi.
+    pub fn write_multi_chunk_size(&self, total: u64, default: usize) -> Option<usize> {
+        if !self.write_can_multi {
+            return None;
+        }
+
+        let mut size = default.max(self.write_multi_min_size.unwrap_or_default());
+        if let Some(parts) = self.write_multi_max_parts {
+            let parts = parts.max(1) as u64;
+            let required = (total + parts - 1) / parts;
+            size = size.max(usize::try_from(required).unwrap_or(usize::MAX));
+        }
+        if let Some(align) = self.write_multi_align_size.filter(|v| *v > 0) {
+            size = size.saturating_add(align - 1) / align * align;
+        }
+        if let Some(max) = self.write_multi_max_size {
+            size = size.min(max);
+        }
+        Some(size)
+    }
 }
 
 impl Debug for Capability {
@@ -177,12 +320,30 @@ impl Debug for Capability {
         if self.copy {
             s.push("Copy");
         }
+        if self.concat {
+            s.push("Concat");
+        }
         if self.rename {
             s.push("Rename");
         }
+        if self.create_symlink {
+            s.push("CreateSymlink");
+        }
+        if self.set_permissions {
+            s.push("SetPermissions");
+        }
+        if self.create_snapshot {
+            s.push("CreateSnapshot");
+        }
+        if self.lease {
+            s.push("Lease");
+        }
         if self.list {
             s.push("List");
         }
+        if self.list_roots {
+            s.push("ListRoots");
+        }
         if self.presign {
             s.push("Presign");
         }
@@ -196,3 +357,47 @@ impl Debug for Capability {
         write!(f, "{{ {} }}", s.join(" | "))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_multi_chunk_size() {
+        let cap = Capability {
+            write_can_multi: true,
+            write_multi_min_size: Some(5 * 1024 * 1024),
+            write_multi_max_size: Some(5 * 1024 * 1024 * 1024),
+            write_multi_max_parts: Some(10000),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            cap.write_multi_chunk_size(1024, 8 * 1024 * 1024),
+            Some(8 * 1024 * 1024)
+        );
+        assert_eq!(
+            cap.write_multi_chunk_size(1024, 1024),
+            Some(5 * 1024 * 1024)
+        );
+        // 1 TiB needs chunks larger than 100 MiB to fit in 10000 parts.
+        assert_eq!(
+            cap.write_multi_chunk_size(1024 * 1024 * 1024 * 1024, 8 * 1024 * 1024),
+            Some(109951163)
+        );
+        assert_eq!(
+            Capability::default().write_multi_chunk_size(1024, 1024),
+            None
+        );
+
+        let cap = Capability {
+            write_can_multi: true,
+            write_multi_align_size: Some(256 * 1024),
+            ..Default::default()
+        };
+        assert_eq!(
+            cap.write_multi_chunk_size(1024, 1000 * 1024),
+            Some(1024 * 1024)
+        );
+    }
+}
@@ -0,0 +1,238 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use bytes::Bytes;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::*;
+
+/// The length of hex encoded sha256 digest.
+const DIGEST_LEN: usize = 64;
+
+/// CasDigest is the hex encoded sha256 digest of content stored in [`Cas`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CasDigest(String);
+
+impl CasDigest {
+    /// Calculate the digest of given content.
+    pub fn of(content: &[u8]) -> Self {
+        Self(format!("{:x}", Sha256::digest(content)))
+    }
+
+    /// Get the hex encoded digest.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for CasDigest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for CasDigest {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.len() != DIGEST_LEN || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(
+                Error::new(ErrorKind::InvalidInput, "digest is not a valid sha256 hex")
+                    .with_context("digest", s),
+            );
+        }
+        Ok(Self(s.to_ascii_lowercase()))
+    }
+}
+
+/// Cas is a content-addressable storage facade on top of [`Operator`],
+/// created by [`Operator::cas`].
+///
+/// Content is addressed by its sha256 digest and stored at a sharded path
+/// like `cas/ab/cd/abcd...` so that no dir grows too large.
+///
+/// - `put` will skip the write if the content already exists, disable it
+///   via [`Cas::with_check_exists`] if `stat` is more expensive than `write`
+///   in your service.
+/// - `get` will verify the content against its digest.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::Operator;
+/// use opendal::Result;
+///
+/// async fn test(op: Operator) -> Result<()> {
+///     let cas = op.cas();
+///
+///     let digest = cas.put("Hello, World!").await?;
+///     let content = cas.get(&digest).await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cas {
+    op: Operator,
+    root: String,
+    shard_depth: usize,
+    shard_width: usize,
+    check_exists: bool,
+}
+
+impl Cas {
+    pub(crate) fn new(op: Operator) -> Self {
+        Self {
+            op,
+            root: "cas/".to_string(),
+            shard_depth: 2,
+            shard_width: 2,
+            check_exists: true,
+        }
+    }
+
+    /// Set the dir that all content will be stored in.
+    ///
+    /// Default to `cas/`.
+    pub fn with_root(mut self, root: &str) -> Self {
+        self.root = match root.trim_matches('/') {
+            "" => "".to_string(),
+            v => format!("{v}/"),
+        };
+        self
+    }
+
+    /// Set the layout of shards, the first `depth * width` chars of digest
+    /// will be used as `depth` levels of dirs.
+    ///
+    /// Default to `2` levels with `2` chars for each.
+    pub fn with_shards(mut self, depth: usize, width: usize) -> Self {
+        self.shard_depth = depth;
+        self.shard_width = width;
+        self
+    }
+
+    /// Check if the content exists before writing it.
+    ///
+    /// Default to `true`.
+    pub fn with_check_exists(mut self, v: bool) -> Self {
+        self.check_exists = v;
+        self
+    }
+
+    /// Get the path that content of given digest will be stored at.
+    pub fn path(&self, digest: &CasDigest) -> String {
+        let digest = digest.as_str();
+        let width = self.shard_width.max(1);
+        let depth = self.shard_depth.min(DIGEST_LEN / width);
+
+        let mut path = self.root.clone();
+        for i in 0..depth {
+            path.push_str(&digest[i * width..(i + 1) * width]);
+            path.push('/');
+        }
+        path.push_str(digest);
+        path
+    }
+
+    /// Store the content and return its digest.
+    pub async fn put(&self, content: impl Into<Bytes>) -> Result<CasDigest> {
+        let content = content.into();
+        let digest = CasDigest::of(&content);
+        let path = self.path(&digest);
+
+        if self.check_exists && self.op.is_exist(&path).await? {
+            return Ok(digest);
+        }
+        self.op.write(&path, content).await?;
+        Ok(digest)
+    }
+
+    /// Get the content of given digest.
+    ///
+    /// Returns [`ErrorKind::Unexpected`] if the stored content doesn't
+    /// match the digest.
+    pub async fn get(&self, digest: &CasDigest) -> Result<Vec<u8>> {
+        let path = self.path(digest);
+        let content = self.op.read(&path).await?;
+
+        let actual = CasDigest::of(&content);
+        if &actual != digest {
+            return Err(
+                Error::new(ErrorKind::Unexpected, "content doesn't match its digest")
+                    .with_context("path", path)
+                    .with_context("actual", actual.0),
+            );
+        }
+        Ok(content)
+    }
+
+    /// Check if the content of given digest exists.
+    pub async fn contains(&self, digest: &CasDigest) -> Result<bool> {
+        self.op.is_exist(&self.path(digest)).await
+    }
+
+    /// Delete the content of given digest.
+    pub async fn delete(&self, digest: &CasDigest) -> Result<()> {
+        self.op.delete(&self.path(digest)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_cas() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        let cas = op.cas();
+
+        let digest = cas.put("Hello, World!").await?;
+        assert_eq!(
+            digest.as_str(),
+            "dffd6021bb2bd5b0af676290809ec3a53191dd81c7f70a4b28688a362182986f"
+        );
+        assert_eq!(
+            cas.path(&digest),
+            "cas/df/fd/dffd6021bb2bd5b0af676290809ec3a53191dd81c7f70a4b28688a362182986f"
+        );
+        assert_eq!(cas.get(&digest).await?, b"Hello, World!");
+
+        // Corrupted content must be detected.
+        op.write(&cas.path(&digest), "Hello, OpenDAL!").await?;
+        let err = cas.get(&digest).await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        cas.delete(&digest).await?;
+        assert!(!cas.contains(&digest).await?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cas_digest_from_str() {
+        assert!("abc".parse::<CasDigest>().is_err());
+        let digest: CasDigest = "DFFD6021BB2BD5B0AF676290809EC3A53191DD81C7F70A4B28688A362182986F"
+            .parse()
+            .expect("must be valid");
+        assert_eq!(digest, CasDigest::of(b"Hello, World!"));
+    }
+}
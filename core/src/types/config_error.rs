@@ -0,0 +1,154 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::*;
+
+/// ConfigError lists every problem found while validating the config of a
+/// service builder, so that users can fix them all at once instead of one
+/// by one.
+///
+/// Builders return it as the source of an [`ErrorKind::ConfigInvalid`]
+/// error, use [`ConfigError::from_error`] to get it back.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::services::Azblob;
+/// use opendal::ConfigError;
+/// use opendal::Operator;
+///
+/// let mut builder = Azblob::default();
+/// builder.account_name("test");
+///
+/// let err = Operator::new(builder).expect_err("must fail");
+/// if let Some(config_err) = ConfigError::from_error(&err) {
+///     for problem in config_err.problems() {
+///         println!("{}: {}", problem.key(), problem.message());
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConfigError {
+    scheme: Scheme,
+    problems: Vec<ConfigProblem>,
+}
+
+impl ConfigError {
+    pub(crate) fn new(scheme: Scheme) -> Self {
+        Self {
+            scheme,
+            problems: vec![],
+        }
+    }
+
+    /// Record a problem of given config key.
+    pub(crate) fn push(&mut self, key: &'static str, message: impl Into<String>) {
+        self.problems.push(ConfigProblem {
+            key,
+            message: message.into(),
+        })
+    }
+
+    /// Convert into an error if any problem has been recorded.
+    pub(crate) fn finish(self) -> Result<()> {
+        if self.problems.is_empty() {
+            return Ok(());
+        }
+
+        let scheme = self.scheme;
+        Err(Error::new(
+            ErrorKind::ConfigInvalid,
+            &format!("config has {} problem(s)", self.problems.len()),
+        )
+        .with_operation("Builder::build")
+        .with_context("service", scheme)
+        .set_source(self))
+    }
+
+    /// Get the config error carried by given error.
+    pub fn from_error(err: &Error) -> Option<&ConfigError> {
+        std::error::Error::source(err)?.downcast_ref::<ConfigError>()
+    }
+
+    /// The scheme of service that reports the problems.
+    pub fn scheme(&self) -> Scheme {
+        self.scheme
+    }
+
+    /// All problems found in config.
+    pub fn problems(&self) -> &[ConfigProblem] {
+        &self.problems
+    }
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid config of {}:", self.scheme)?;
+        for problem in &self.problems {
+            write!(f, " [{}] {};", problem.key, problem.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// ConfigProblem is a problem of config found by [`ConfigError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    key: &'static str,
+    message: String,
+}
+
+impl ConfigProblem {
+    /// The config key that has the problem.
+    pub fn key(&self) -> &str {
+        self.key
+    }
+
+    /// The message describing the problem and how to fix it.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_error() {
+        assert!(ConfigError::new(Scheme::S3).finish().is_ok());
+
+        let mut errs = ConfigError::new(Scheme::S3);
+        errs.push("bucket", "bucket is empty");
+        errs.push(
+            "secret_access_key",
+            "access_key_id set but secret_access_key not provided",
+        );
+        let err = errs.finish().expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let config_err = ConfigError::from_error(&err).expect("must carry config error");
+        assert_eq!(config_err.scheme(), Scheme::S3);
+        assert_eq!(config_err.problems().len(), 2);
+        assert_eq!(config_err.problems()[0].key(), "bucket");
+    }
+}
@@ -0,0 +1,132 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use chrono::DateTime;
+use chrono::Utc;
+use flagset::flags;
+
+flags! {
+    /// DelegateScope is the operations that a [`DelegatedCredential`]
+    /// minted by [`Operator::delegate`] allows.
+    ///
+    /// [`Operator::delegate`]: crate::Operator::delegate
+    pub enum DelegateScope: u8 {
+        /// Allow reading objects under the path.
+        Read,
+        /// Allow writing objects under the path.
+        Write,
+        /// Allow listing objects under the path.
+        List,
+        /// Allow deleting objects under the path.
+        Delete,
+    }
+}
+
+/// DelegatedCredential is a narrowly-scoped, time-limited credential
+/// minted by [`Operator::delegate`] that servers can hand to their clients
+/// for direct access.
+///
+/// [`Operator::delegate`]: crate::Operator::delegate
+#[derive(Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DelegatedCredential {
+    /// Temporary AWS credential returned by STS, which carries a session
+    /// policy that only allows given operations under the path.
+    Aws {
+        /// The access key id.
+        access_key_id: String,
+        /// The secret access key.
+        secret_access_key: String,
+        /// The session token.
+        session_token: String,
+        /// The time that this credential will be expired.
+        expires_at: DateTime<Utc>,
+    },
+    /// Azure SAS token which should be appended to the query of
+    /// request urls.
+    AzureSas {
+        /// The SAS token without leading `?`.
+        token: String,
+        /// The time that this token will be expired.
+        expires_at: DateTime<Utc>,
+    },
+    /// GCS signed policy document which allows clients to upload objects
+    /// under the path via HTML form posts.
+    GcsPolicy {
+        /// The url that the form should be posted to.
+        url: String,
+        /// The fields that should be included in the form.
+        fields: Vec<(String, String)>,
+        /// The time that this policy will be expired.
+        expires_at: DateTime<Utc>,
+    },
+    /// GCS OAuth access token downscoped by credential access boundary,
+    /// which should be sent as `Authorization: Bearer {access_token}`.
+    GcsToken {
+        /// The downscoped access token.
+        access_token: String,
+        /// The time that this token will be expired.
+        expires_at: DateTime<Utc>,
+    },
+}
+
+impl DelegatedCredential {
+    /// The time that this credential will be expired.
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        match self {
+            DelegatedCredential::Aws { expires_at, .. } => *expires_at,
+            DelegatedCredential::AzureSas { expires_at, .. } => *expires_at,
+            DelegatedCredential::GcsPolicy { expires_at, .. } => *expires_at,
+            DelegatedCredential::GcsToken { expires_at, .. } => *expires_at,
+        }
+    }
+}
+
+/// Secrets are redacted so that credentials could be logged safely.
+impl Debug for DelegatedCredential {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DelegatedCredential::Aws {
+                access_key_id,
+                expires_at,
+                ..
+            } => f
+                .debug_struct("Aws")
+                .field("access_key_id", access_key_id)
+                .field("expires_at", expires_at)
+                .finish_non_exhaustive(),
+            DelegatedCredential::AzureSas { expires_at, .. } => f
+                .debug_struct("AzureSas")
+                .field("expires_at", expires_at)
+                .finish_non_exhaustive(),
+            DelegatedCredential::GcsPolicy {
+                url, expires_at, ..
+            } => f
+                .debug_struct("GcsPolicy")
+                .field("url", url)
+                .field("expires_at", expires_at)
+                .finish_non_exhaustive(),
+            DelegatedCredential::GcsToken { expires_at, .. } => f
+                .debug_struct("GcsToken")
+                .field("expires_at", expires_at)
+                .finish_non_exhaustive(),
+        }
+    }
+}
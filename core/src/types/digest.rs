@@ -0,0 +1,269 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use md5::Md5;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::raw::*;
+use crate::*;
+
+/// Algorithms that can be used to digest the content while writing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DigestAlgorithm {
+    /// MD5, the same as `Content-MD5`.
+    Md5,
+    /// SHA-256.
+    Sha256,
+    /// CRC32C (Castagnoli), the same as the checksum used by GCS.
+    Crc32c,
+}
+
+/// ContentDigest is the digest of all content written by a writer, returned
+/// by [`Writer::digest`] after close.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContentDigest {
+    algorithm: DigestAlgorithm,
+    value: Vec<u8>,
+}
+
+impl ContentDigest {
+    /// The algorithm of this digest.
+    pub fn algorithm(&self) -> DigestAlgorithm {
+        self.algorithm
+    }
+
+    /// The raw bytes of this digest, crc32c is in big endian.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.value
+    }
+
+    /// Format digest in lowercase hex.
+    pub fn to_hex(&self) -> String {
+        self.value.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    /// Format digest in base64, which is used by headers like `Content-MD5`.
+    pub fn to_base64(&self) -> String {
+        general_purpose::STANDARD.encode(&self.value)
+    }
+}
+
+impl Display for ContentDigest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+enum Hasher {
+    Md5(Md5),
+    Sha256(Sha256),
+    Crc32c(u32),
+}
+
+impl Hasher {
+    fn new(algorithm: DigestAlgorithm) -> Self {
+        match algorithm {
+            DigestAlgorithm::Md5 => Hasher::Md5(Md5::new()),
+            DigestAlgorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            DigestAlgorithm::Crc32c => Hasher::Crc32c(0),
+        }
+    }
+
+    fn update(&mut self, bs: &[u8]) {
+        match self {
+            Hasher::Md5(h) => h.update(bs),
+            Hasher::Sha256(h) => h.update(bs),
+            Hasher::Crc32c(v) => *v = crc32c::crc32c_append(*v, bs),
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        match self {
+            Hasher::Md5(h) => h.finalize().to_vec(),
+            Hasher::Sha256(h) => h.finalize().to_vec(),
+            Hasher::Crc32c(v) => v.to_be_bytes().to_vec(),
+        }
+    }
+}
+
+/// DigestState is shared between [`DigestWriter`] and the writer returned
+/// to users.
+enum DigestState {
+    Hashing(Hasher),
+    Done(ContentDigest),
+    /// The writer has been aborted or failed to close.
+    Failed,
+}
+
+/// DigestHandle is used to fetch the digest after writer closed.
+#[derive(Clone)]
+pub(crate) struct DigestHandle {
+    algorithm: DigestAlgorithm,
+    state: Arc<Mutex<DigestState>>,
+}
+
+impl DigestHandle {
+    /// Get the digest, returns `None` if the writer is not closed successfully.
+    pub(crate) fn digest(&self) -> Option<ContentDigest> {
+        match &*self.state.lock().expect("lock must be valid") {
+            DigestState::Done(v) => Some(v.clone()),
+            _ => None,
+        }
+    }
+
+    fn update(&self, bs: &dyn oio::WriteBuf, n: usize) {
+        if let DigestState::Hashing(h) = &mut *self.state.lock().expect("lock must be valid") {
+            for bs in bs.vectored_bytes(n) {
+                h.update(&bs);
+            }
+        }
+    }
+
+    fn finish(&self, succeed: bool) {
+        let mut state = self.state.lock().expect("lock must be valid");
+        if !matches!(&*state, DigestState::Hashing(_)) {
+            return;
+        }
+        let DigestState::Hashing(h) = std::mem::replace(&mut *state, DigestState::Failed) else {
+            unreachable!("state must be hashing")
+        };
+        if succeed {
+            *state = DigestState::Done(ContentDigest {
+                algorithm: self.algorithm,
+                value: h.finish(),
+            });
+        }
+    }
+}
+
+/// DigestWriter digests all content that accepted by inner writer.
+pub(crate) struct DigestWriter<W> {
+    inner: W,
+    handle: DigestHandle,
+}
+
+impl<W> DigestWriter<W> {
+    pub(crate) fn new(inner: W, algorithm: DigestAlgorithm) -> (Self, DigestHandle) {
+        let handle = DigestHandle {
+            algorithm,
+            state: Arc::new(Mutex::new(DigestState::Hashing(Hasher::new(algorithm)))),
+        };
+        (
+            Self {
+                inner,
+                handle: handle.clone(),
+            },
+            handle,
+        )
+    }
+}
+
+impl<W: oio::Write> oio::Write for DigestWriter<W> {
+    fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        let n = ready!(self.inner.poll_write(cx, bs))?;
+        self.handle.update(bs, n);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let res = ready!(self.inner.poll_close(cx));
+        self.handle.finish(res.is_ok());
+        Poll::Ready(res)
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let res = ready!(self.inner.poll_abort(cx));
+        self.handle.finish(false);
+        Poll::Ready(res)
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for DigestWriter<W> {
+    fn write(&mut self, bs: &dyn oio::WriteBuf) -> Result<usize> {
+        let n = self.inner.write(bs)?;
+        self.handle.update(bs, n);
+        Ok(n)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        let res = self.inner.close();
+        self.handle.finish(res.is_ok());
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_writer_digest() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+
+        let cases = [
+            (DigestAlgorithm::Md5, "65a8e27d8879283831b664bd8b7f0ad4"),
+            (
+                DigestAlgorithm::Sha256,
+                "dffd6021bb2bd5b0af676290809ec3a53191dd81c7f70a4b28688a362182986f",
+            ),
+            (DigestAlgorithm::Crc32c, "4d551068"),
+        ];
+        for (algorithm, expected) in cases {
+            let mut w = op.writer_with("test").digest(algorithm).await?;
+            w.write("Hello, ").await?;
+            assert!(w.digest().is_none());
+            w.write("World!").await?;
+            w.close().await?;
+
+            let digest = w.digest().expect("digest must exist");
+            assert_eq!(digest.algorithm(), algorithm);
+            assert_eq!(digest.to_hex(), expected, "{algorithm:?}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_blocking_writer_digest() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish().blocking();
+
+        let mut w = op.writer_with("test").digest(DigestAlgorithm::Md5).call()?;
+        w.write("Hello, World!")?;
+        w.close()?;
+        assert_eq!(
+            w.digest().map(|v| v.to_base64()),
+            Some("ZajifYh5KDgxtmS9i38K1A==".to_string())
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use crate::*;
+
+/// Local emulators of cloud storage services that can be used as the preset
+/// of service builders, for example `AzblobBuilder::emulator`.
+///
+/// Presets will fill the endpoint, credentials and other configs that are
+/// not set by users, and disable the capabilities that emulators don't
+/// support.
+///
+/// # Notes
+///
+/// Emulator is `non_exhaustive`, new variant COULD be added at any time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Emulator {
+    /// [Azurite](https://github.com/Azure/Azurite): emulator of Azure Storage,
+    /// used with `azblob`.
+    Azurite,
+    /// [LocalStack](https://github.com/localstack/localstack): emulator of
+    /// AWS services, used with `s3`.
+    LocalStack,
+    /// [MinIO](https://github.com/minio/minio): S3 compatible object storage,
+    /// used with `s3`.
+    Minio,
+}
+
+impl Emulator {
+    /// The default endpoint of emulator while running locally.
+    pub fn endpoint(&self) -> &'static str {
+        match self {
+            Emulator::Azurite => "http://127.0.0.1:10000/devstoreaccount1",
+            Emulator::LocalStack => "http://127.0.0.1:4566",
+            Emulator::Minio => "http://127.0.0.1:9000",
+        }
+    }
+
+    /// Convert self into static str.
+    pub fn into_static(self) -> &'static str {
+        self.into()
+    }
+
+    /// Build the error for emulators that are not supported by given service.
+    pub(crate) fn unsupported(self, scheme: Scheme) -> Error {
+        Error::new(
+            ErrorKind::ConfigInvalid,
+            "emulator is not supported by this service",
+        )
+        .with_operation("Builder::build")
+        .with_context("service", scheme)
+        .with_context("emulator", self.into_static())
+    }
+}
+
+impl Display for Emulator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.into_static())
+    }
+}
+
+impl FromStr for Emulator {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "azurite" => Ok(Emulator::Azurite),
+            "localstack" => Ok(Emulator::LocalStack),
+            "minio" => Ok(Emulator::Minio),
+            _ => Err(Error::new(ErrorKind::ConfigInvalid, "emulator is unknown")
+                .with_context("emulator", s)),
+        }
+    }
+}
+
+impl From<Emulator> for &'static str {
+    fn from(v: Emulator) -> Self {
+        match v {
+            Emulator::Azurite => "azurite",
+            Emulator::LocalStack => "localstack",
+            Emulator::Minio => "minio",
+        }
+    }
+}
@@ -96,6 +96,45 @@ impl Entry {
         get_basename(&self.path)
     }
 
+    /// Name of entry for display, which is [`Entry::name`] without the
+    /// trailing `/` of dirs.
+    ///
+    /// The display name of root is `/`.
+    pub fn display_name(&self) -> &str {
+        match self.name() {
+            "/" => "/",
+            name => name.trim_end_matches('/'),
+        }
+    }
+
+    /// Parent of entry, which always ends with `/`.
+    ///
+    /// The parent of root and entries under root is `/`.
+    pub fn parent(&self) -> &str {
+        get_parent(&self.path)
+    }
+
+    /// Extension of entry without the leading `.`.
+    ///
+    /// Returns `None` for dirs, hidden files like `.bashrc` and files
+    /// without extension.
+    pub fn extension(&self) -> Option<&str> {
+        get_extension(&self.path)
+    }
+
+    /// Percent-decoded form of [`Entry::path`].
+    ///
+    /// Some services return keys that are percent-encoded. The path will
+    /// be returned as is if it's not a valid percent-encoded utf-8 string.
+    pub fn decoded_path(&self) -> String {
+        percent_decode_path(&self.path)
+    }
+
+    /// Percent-decoded form of [`Entry::display_name`].
+    pub fn decoded_display_name(&self) -> String {
+        percent_decode_path(self.display_name())
+    }
+
     /// Fetch metadata of this entry.
     ///
     /// # Notes
@@ -190,3 +229,31 @@ impl Entry {
         (self.path, self.metadata)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_path_helpers() {
+        let entry = Entry::new(
+            "foo/bar%20baz.tar.gz".to_string(),
+            Metadata::new(EntryMode::FILE),
+        );
+        assert_eq!(entry.display_name(), "bar%20baz.tar.gz");
+        assert_eq!(entry.parent(), "foo/");
+        assert_eq!(entry.extension(), Some("gz"));
+        assert_eq!(entry.decoded_path(), "foo/bar baz.tar.gz");
+        assert_eq!(entry.decoded_display_name(), "bar baz.tar.gz");
+
+        let entry = Entry::new("foo/bar.d/".to_string(), Metadata::new(EntryMode::DIR));
+        assert_eq!(entry.name(), "bar.d/");
+        assert_eq!(entry.display_name(), "bar.d");
+        assert_eq!(entry.parent(), "foo/");
+        assert_eq!(entry.extension(), None);
+
+        let entry = Entry::new("/".to_string(), Metadata::new(EntryMode::DIR));
+        assert_eq!(entry.display_name(), "/");
+        assert_eq!(entry.parent(), "/");
+    }
+}
@@ -42,6 +42,7 @@ use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::io;
+use std::time::Duration;
 
 /// Result that is a wrapper of `Result<T, opendal::Error>`
 pub type Result<T> = std::result::Result<T, Error>;
@@ -61,14 +62,24 @@ pub enum ErrorKind {
     /// The given path is not found.
     NotFound,
     /// The given path doesn't have enough permission for this operation
+    ///
+    /// The identity is valid but lacks rights, refreshing credentials won't help.
     PermissionDenied,
+    /// The credentials are missing, invalid or expired.
+    ///
+    /// Applications could refresh their credentials and try again.
+    Unauthenticated,
     /// The given path is a directory.
     IsADirectory,
     /// The given path is not a directory.
     NotADirectory,
+    /// The given directory is not empty.
+    DirectoryNotEmpty,
     /// The given path already exists thus we failed to the specified operation on it.
     AlreadyExists,
     /// Requests that sent to this path is over the limit, please slow down.
+    ///
+    /// The time that service asks to wait could be fetched via [`Error::retry_after`].
     RateLimited,
     /// The given file paths are same.
     IsSameFile,
@@ -82,6 +93,10 @@ pub enum ErrorKind {
     ///
     /// As OpenDAL cannot handle the `condition not match` error, it will always return this error to users.
     /// So users could to handle this error by themselves.
+    ///
+    /// This error is never retried by `RetryLayer` even if it's temporary, since the condition
+    /// is not going to match without users re-reading the state. Use [`Error::is_not_modified`]
+    /// to tell 304 Not Modified apart from 412 Precondition Failed.
     ConditionNotMatch,
     /// The content is truncated.
     ///
@@ -128,8 +143,10 @@ impl From<ErrorKind> for &'static str {
             ErrorKind::ConfigInvalid => "ConfigInvalid",
             ErrorKind::NotFound => "NotFound",
             ErrorKind::PermissionDenied => "PermissionDenied",
+            ErrorKind::Unauthenticated => "Unauthenticated",
             ErrorKind::IsADirectory => "IsADirectory",
             ErrorKind::NotADirectory => "NotADirectory",
+            ErrorKind::DirectoryNotEmpty => "DirectoryNotEmpty",
             ErrorKind::AlreadyExists => "AlreadyExists",
             ErrorKind::RateLimited => "RateLimited",
             ErrorKind::IsSameFile => "IsSameFile",
@@ -241,6 +258,7 @@ pub struct Error {
     operation: &'static str,
     context: Vec<(&'static str, String)>,
     source: Option<anyhow::Error>,
+    retry_after: Option<Duration>,
     backtrace: Backtrace,
 }
 
@@ -333,6 +351,7 @@ impl Error {
             operation: "",
             context: Vec::default(),
             source: None,
+            retry_after: None,
             // `Backtrace::capture()` will check if backtrace has been enabled
             // internally. It's zero cost if backtrace is disabled.
             backtrace: Backtrace::capture(),
@@ -411,14 +430,58 @@ impl Error {
     pub fn is_temporary(&self) -> bool {
         self.status == ErrorStatus::Temporary
     }
+
+    /// Set the time that service asks to wait before retrying.
+    ///
+    /// `RetryLayer` will wait at least this long before the next retry.
+    pub fn with_retry_after(mut self, dur: Duration) -> Self {
+        self.retry_after = Some(dur);
+        self
+    }
+
+    /// Get the time that service asks to wait before retrying, usually
+    /// parsed from `Retry-After` like headers of [`ErrorKind::RateLimited`]
+    /// errors.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after
+    }
+
+    /// Replace the kind of error, used while we know more about the error
+    /// than the service mapping.
+    pub(crate) fn with_kind(mut self, kind: ErrorKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Check if this error is a [`ErrorKind::ConditionNotMatch`] caused by
+    /// `304 Not Modified`, which means the content is still the same as
+    /// the one users have.
+    ///
+    /// Returns `false` for `412 Precondition Failed` and errors that don't
+    /// come from a HTTP response.
+    pub fn is_not_modified(&self) -> bool {
+        self.kind == ErrorKind::ConditionNotMatch && self.context_value("status") == Some("304")
+    }
+
+    /// Get the value of the first context with given key.
+    pub(crate) fn context_value(&self, key: &str) -> Option<&str> {
+        self.context
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.as_str())
+    }
 }
 
 impl From<Error> for io::Error {
     fn from(err: Error) -> Self {
         let kind = match err.kind() {
             ErrorKind::NotFound => io::ErrorKind::NotFound,
-            ErrorKind::PermissionDenied => io::ErrorKind::PermissionDenied,
+            ErrorKind::PermissionDenied | ErrorKind::Unauthenticated => {
+                io::ErrorKind::PermissionDenied
+            }
             ErrorKind::InvalidInput => io::ErrorKind::InvalidInput,
+            ErrorKind::AlreadyExists => io::ErrorKind::AlreadyExists,
+            ErrorKind::Unsupported => io::ErrorKind::Unsupported,
             _ => io::ErrorKind::Other,
         };
 
@@ -444,6 +507,7 @@ mod tests {
             ("called", "send_async".to_string()),
         ],
         source: Some(anyhow!("networking error")),
+        retry_after: None,
         backtrace: Backtrace::disabled(),
     });
 
@@ -473,4 +537,13 @@ Source:
 "#
         )
     }
+
+    #[test]
+    fn test_error_is_not_modified() {
+        let err =
+            Error::new(ErrorKind::ConditionNotMatch, "not modified").with_context("status", "304");
+        assert!(err.is_not_modified());
+        let err = Error::new(ErrorKind::ConditionNotMatch, "failed").with_context("status", "412");
+        assert!(!err.is_not_modified());
+    }
 }
@@ -0,0 +1,137 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use chrono::DateTime;
+use chrono::Utc;
+
+use crate::*;
+
+/// ExpiryManifest removes objects that written with `expire_at` emulated by
+/// [`EmulateLayer::with_expire`](crate::layers::EmulateLayer::with_expire).
+///
+/// Every emulated object has a record named like
+/// `{dir}{expire_timestamp:020}-{uuid}` whose content is the path of object
+/// followed by the [`Metadata::fingerprint`] of the written version, so
+/// records could be scanned in the order of expire time.
+///
+/// Objects that have been overwritten after the record was created will be
+/// kept, only their records are removed.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::ExpiryManifest;
+/// use opendal::Operator;
+/// use opendal::Result;
+///
+/// async fn test(op: Operator) -> Result<()> {
+///     let removed = ExpiryManifest::new(op, ".expiry/").gc().await?;
+///     println!("{removed} objects expired");
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExpiryManifest {
+    op: Operator,
+    dir: String,
+}
+
+impl ExpiryManifest {
+    /// Create a manifest that stores records under given dir.
+    ///
+    /// The dir must be the same as the one passed to `EmulateLayer`.
+    pub fn new(op: Operator, dir: &str) -> Self {
+        Self {
+            op,
+            dir: Self::normalize_dir(dir),
+        }
+    }
+
+    /// Remove all expired objects and their records, returns the number of
+    /// records that have been handled.
+    pub async fn gc(&self) -> Result<usize> {
+        let entries = match self.op.list(&self.dir).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(0),
+            Err(err) => return Err(err),
+        };
+
+        let now = Utc::now().timestamp();
+        let mut removed = 0;
+        for entry in entries {
+            let Some(expire_at) = Self::parse_record(entry.name()) else {
+                continue;
+            };
+            if expire_at > now {
+                continue;
+            }
+
+            let content = self.op.read(entry.path()).await?;
+            let content = String::from_utf8(content).map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "expiry record is not valid utf-8")
+                    .with_context("record", entry.path())
+                    .set_source(err)
+            })?;
+            let (path, fingerprint) = match content.split_once('\n') {
+                Some((path, fingerprint)) => (path, Some(fingerprint)),
+                None => (content.as_str(), None),
+            };
+
+            let expired = match fingerprint {
+                Some(fingerprint) => match self.op.stat(path).await {
+                    Ok(meta) => meta.fingerprint().as_deref() == Some(fingerprint),
+                    Err(err) if err.kind() == ErrorKind::NotFound => false,
+                    Err(err) => return Err(err),
+                },
+                None => true,
+            };
+            if expired {
+                self.op.delete(path).await?;
+            }
+            self.op.delete(entry.path()).await?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    pub(crate) fn normalize_dir(dir: &str) -> String {
+        format!("{}/", dir.trim_end_matches('/'))
+    }
+
+    /// Build a new record path for object expires at given time.
+    pub(crate) fn record_path(dir: &str, expire_at: DateTime<Utc>) -> String {
+        format!(
+            "{}{:020}-{}",
+            dir,
+            expire_at.timestamp().max(0),
+            uuid::Uuid::new_v4()
+        )
+    }
+
+    /// Build the content of record for the written version of path.
+    pub(crate) fn record_content(path: &str, meta: &Metadata) -> String {
+        match meta.fingerprint() {
+            Some(fingerprint) => format!("{path}\n{fingerprint}"),
+            None => path.to_string(),
+        }
+    }
+
+    /// Parse the expire timestamp from record name.
+    fn parse_record(name: &str) -> Option<i64> {
+        name.split_once('-')?.0.parse().ok()
+    }
+}
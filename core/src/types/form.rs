@@ -0,0 +1,631 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::pin::Pin;
+use std::task::Poll;
+
+use bytes::Buf;
+use bytes::Bytes;
+use bytes::BytesMut;
+use futures::Stream;
+use futures::StreamExt;
+
+use crate::*;
+
+/// The max size of a non-file field.
+const MAX_FIELD_SIZE: usize = 64 * 1024;
+/// The max size of the headers of a part.
+const MAX_HEADERS_SIZE: usize = 8 * 1024;
+
+/// FormUpload streams the file parts of a `multipart/form-data` body into
+/// [`Writer`]s directly without temp files, which is useful for web upload
+/// endpoints.
+///
+/// - File parts will be written to `{dir}{file_name}`, the `Content-Type` of
+///   part will be used as the content type of the file.
+/// - Other parts will be returned as text fields, at most 64 KiB for each.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bytes::Bytes;
+/// use futures::stream;
+/// use futures::StreamExt;
+/// use opendal::FormUpload;
+/// use opendal::Operator;
+/// use opendal::Result;
+///
+/// async fn test(op: Operator, content_type: &str, body: Vec<Bytes>) -> Result<()> {
+///     let form = FormUpload::new(op)
+///         .with_dir("uploads/")
+///         .with_max_file_size(16 * 1024 * 1024)
+///         .with_max_files(4)
+///         .upload(content_type, stream::iter(body).map(Ok::<_, std::io::Error>))
+///         .await?;
+///
+///     for file in form.files() {
+///         println!("{} is uploaded to {}", file.file_name(), file.path());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct FormUpload {
+    op: Operator,
+    dir: String,
+    max_file_size: Option<u64>,
+    max_files: Option<usize>,
+}
+
+impl FormUpload {
+    /// Create a new form upload that writes files into given operator.
+    pub fn new(op: Operator) -> Self {
+        Self {
+            op,
+            dir: "/".to_string(),
+            max_file_size: None,
+            max_files: None,
+        }
+    }
+
+    /// Set the dir that files will be written to.
+    ///
+    /// Default to `/`.
+    pub fn with_dir(mut self, dir: &str) -> Self {
+        self.dir = format!("{}/", dir.trim_end_matches('/'));
+        self
+    }
+
+    /// Set the max size of every file, upload will fail and the file will
+    /// be aborted if exceeded.
+    pub fn with_max_file_size(mut self, size: u64) -> Self {
+        self.max_file_size = Some(size);
+        self
+    }
+
+    /// Set the max number of files in a form.
+    pub fn with_max_files(mut self, count: usize) -> Self {
+        self.max_files = Some(count);
+        self
+    }
+
+    /// Upload given form body.
+    ///
+    /// `content_type` is the value of `Content-Type` header of the request,
+    /// which contains the boundary of form.
+    pub async fn upload<S, E>(&self, content_type: &str, body: S) -> Result<UploadedForm>
+    where
+        S: Stream<Item = std::result::Result<Bytes, E>> + Send,
+        E: Into<anyhow::Error>,
+    {
+        let boundary = parse_boundary(content_type)?;
+        let mut parser = FormParser::new(&boundary);
+        let mut body = Box::pin(body);
+        let mut form = UploadedForm::default();
+
+        loop {
+            let headers = match next_part(&mut parser, &mut body).await? {
+                Some(headers) => headers,
+                None => return Ok(form),
+            };
+
+            match headers.file_name {
+                Some(file_name) => {
+                    if matches!(self.max_files, Some(max) if form.files.len() >= max) {
+                        return Err(
+                            Error::new(ErrorKind::InvalidInput, "too many files in form")
+                                .with_context("max_files", form.files.len().to_string()),
+                        );
+                    }
+                    let file = self
+                        .upload_file(
+                            &mut parser,
+                            &mut body,
+                            headers.name,
+                            file_name,
+                            headers.content_type,
+                        )
+                        .await?;
+                    form.files.push(file);
+                }
+                None => {
+                    let mut value = BytesMut::new();
+                    while let Some(bs) = next_chunk(&mut parser, &mut body).await? {
+                        value.extend_from_slice(&bs);
+                        if value.len() > MAX_FIELD_SIZE {
+                            return Err(Error::new(
+                                ErrorKind::InvalidInput,
+                                "form field is too large",
+                            )
+                            .with_context("field", headers.name));
+                        }
+                    }
+                    form.fields
+                        .push((headers.name, String::from_utf8_lossy(&value).to_string()));
+                }
+            }
+        }
+    }
+
+    async fn upload_file<S, E>(
+        &self,
+        parser: &mut FormParser,
+        body: &mut Pin<Box<S>>,
+        field_name: String,
+        file_name: String,
+        content_type: Option<String>,
+    ) -> Result<UploadedFile>
+    where
+        S: Stream<Item = std::result::Result<Bytes, E>> + Send,
+        E: Into<anyhow::Error>,
+    {
+        let name = sanitize_file_name(&file_name)?;
+        let path = format!("{}{}", self.dir, name);
+
+        let mut fw = self.op.writer_with(&path);
+        if let Some(v) = content_type.as_deref() {
+            fw = fw.content_type(v);
+        }
+        let mut w = fw.await?;
+
+        let mut size = 0;
+        loop {
+            let bs = match next_chunk(parser, body).await {
+                Ok(Some(bs)) => bs,
+                Ok(None) => break,
+                Err(err) => {
+                    let _ = w.abort().await;
+                    return Err(err);
+                }
+            };
+
+            size += bs.len() as u64;
+            if matches!(self.max_file_size, Some(max) if size > max) {
+                let _ = w.abort().await;
+                return Err(Error::new(ErrorKind::InvalidInput, "file is too large")
+                    .with_context("path", &path)
+                    .with_context(
+                        "max_file_size",
+                        self.max_file_size.unwrap_or_default().to_string(),
+                    ));
+            }
+            if let Err(err) = w.write(bs).await {
+                let _ = w.abort().await;
+                return Err(err);
+            }
+        }
+        w.close().await?;
+
+        Ok(UploadedFile {
+            field_name,
+            file_name,
+            path,
+            content_type,
+            size,
+        })
+    }
+}
+
+/// Read the headers of next part, returns `None` if the form is ended.
+async fn next_part<S, E>(
+    parser: &mut FormParser,
+    body: &mut Pin<Box<S>>,
+) -> Result<Option<PartHeaders>>
+where
+    S: Stream<Item = std::result::Result<Bytes, E>> + Send,
+    E: Into<anyhow::Error>,
+{
+    loop {
+        match parser.next_part()? {
+            Poll::Ready(v) => return Ok(v),
+            Poll::Pending => fill(parser, body).await?,
+        }
+    }
+}
+
+/// Read next chunk of current part, returns `None` if the part is ended.
+async fn next_chunk<S, E>(parser: &mut FormParser, body: &mut Pin<Box<S>>) -> Result<Option<Bytes>>
+where
+    S: Stream<Item = std::result::Result<Bytes, E>> + Send,
+    E: Into<anyhow::Error>,
+{
+    loop {
+        match parser.next_chunk() {
+            Poll::Ready(v) => return Ok(v),
+            Poll::Pending => fill(parser, body).await?,
+        }
+    }
+}
+
+/// Read more data from body into parser.
+async fn fill<S, E>(parser: &mut FormParser, body: &mut Pin<Box<S>>) -> Result<()>
+where
+    S: Stream<Item = std::result::Result<Bytes, E>> + Send,
+    E: Into<anyhow::Error>,
+{
+    match body.next().await {
+        Some(Ok(bs)) => {
+            parser.buf.extend_from_slice(&bs);
+            Ok(())
+        }
+        Some(Err(err)) => Err(Error::new(ErrorKind::Unexpected, "read form body").set_source(err)),
+        None => Err(Error::new(
+            ErrorKind::ContentIncomplete,
+            "form body ended unexpectedly",
+        )),
+    }
+}
+
+/// UploadedForm is the result of [`FormUpload::upload`].
+#[derive(Debug, Default)]
+pub struct UploadedForm {
+    files: Vec<UploadedFile>,
+    fields: Vec<(String, String)>,
+}
+
+impl UploadedForm {
+    /// Files that have been uploaded, in the same order as the form.
+    pub fn files(&self) -> &[UploadedFile] {
+        &self.files
+    }
+
+    /// Text fields of the form as `(name, value)`.
+    pub fn fields(&self) -> &[(String, String)] {
+        &self.fields
+    }
+
+    /// Get the value of given text field.
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// UploadedFile is a file uploaded by [`FormUpload`].
+#[derive(Debug, Clone)]
+pub struct UploadedFile {
+    field_name: String,
+    file_name: String,
+    path: String,
+    content_type: Option<String>,
+    size: u64,
+}
+
+impl UploadedFile {
+    /// Name of the form field.
+    pub fn field_name(&self) -> &str {
+        &self.field_name
+    }
+
+    /// File name provided by client.
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    /// Path that the file has been written to.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Content type provided by client.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Size of the file.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParserState {
+    /// Waiting for the first boundary.
+    Preamble,
+    /// Reading headers of a part.
+    Headers,
+    /// Reading content of a part.
+    Body,
+    /// The closing boundary has been read.
+    End,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct PartHeaders {
+    name: String,
+    file_name: Option<String>,
+    content_type: Option<String>,
+}
+
+/// FormParser is a push based parser of `multipart/form-data`.
+///
+/// Callers should push data into `buf` while `Poll::Pending` returned.
+struct FormParser {
+    buf: BytesMut,
+    /// `\r\n--{boundary}`
+    delimiter: Vec<u8>,
+    state: ParserState,
+}
+
+impl FormParser {
+    fn new(boundary: &str) -> Self {
+        Self {
+            buf: BytesMut::new(),
+            delimiter: format!("\r\n--{boundary}").into_bytes(),
+            state: ParserState::Preamble,
+        }
+    }
+
+    fn next_part(&mut self) -> Result<Poll<Option<PartHeaders>>> {
+        loop {
+            match self.state {
+                ParserState::Preamble => {
+                    // The first boundary doesn't have the leading CRLF.
+                    let first = &self.delimiter[2..];
+                    match find(&self.buf, first) {
+                        // Need to know what follows the boundary.
+                        Some(idx) if self.buf.len() < idx + first.len() + 2 => {
+                            self.buf.advance(idx);
+                            return Ok(Poll::Pending);
+                        }
+                        Some(idx) => {
+                            self.buf.advance(idx + first.len());
+                            self.skip_boundary_suffix();
+                        }
+                        None => {
+                            // Preamble is ignored, only keep the tail which
+                            // could be a part of boundary.
+                            let keep = first.len() - 1;
+                            if self.buf.len() > keep {
+                                self.buf.advance(self.buf.len() - keep);
+                            }
+                            return Ok(Poll::Pending);
+                        }
+                    }
+                }
+                ParserState::Headers => {
+                    let idx = match find(&self.buf, b"\r\n\r\n") {
+                        Some(idx) => idx,
+                        None if self.buf.len() > MAX_HEADERS_SIZE => {
+                            return Err(Error::new(
+                                ErrorKind::InvalidInput,
+                                "headers of form part is too large",
+                            ))
+                        }
+                        None => return Ok(Poll::Pending),
+                    };
+                    let headers = parse_part_headers(&self.buf[..idx])?;
+                    self.buf.advance(idx + 4);
+                    self.state = ParserState::Body;
+                    return Ok(Poll::Ready(Some(headers)));
+                }
+                // Skip the remaining content of current part.
+                ParserState::Body => match self.next_chunk() {
+                    Poll::Ready(_) => continue,
+                    Poll::Pending => return Ok(Poll::Pending),
+                },
+                ParserState::End => return Ok(Poll::Ready(None)),
+            }
+        }
+    }
+
+    fn next_chunk(&mut self) -> Poll<Option<Bytes>> {
+        if self.state != ParserState::Body {
+            return Poll::Ready(None);
+        }
+
+        match find(&self.buf, &self.delimiter) {
+            // Need to know what follows the boundary before ending the part.
+            Some(0) if self.buf.len() < self.delimiter.len() + 2 => Poll::Pending,
+            Some(0) => {
+                self.buf.advance(self.delimiter.len());
+                self.skip_boundary_suffix();
+                Poll::Ready(None)
+            }
+            Some(idx) => Poll::Ready(Some(self.buf.split_to(idx).freeze())),
+            None => {
+                // Keep the tail which could be a part of delimiter.
+                let keep = self.delimiter.len() - 1;
+                if self.buf.len() <= keep {
+                    return Poll::Pending;
+                }
+                let n = self.buf.len() - keep;
+                Poll::Ready(Some(self.buf.split_to(n).freeze()))
+            }
+        }
+    }
+
+    /// Skip the `\r\n` or `--` after boundary, callers must make sure
+    /// there are at least 2 bytes in buffer.
+    fn skip_boundary_suffix(&mut self) {
+        self.state = if &self.buf[..2] == b"--" {
+            ParserState::End
+        } else {
+            ParserState::Headers
+        };
+        self.buf.advance(2);
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn parse_boundary(content_type: &str) -> Result<String> {
+    let mut params = content_type.split(';');
+    let mime = params.next().unwrap_or_default().trim();
+    if !mime.eq_ignore_ascii_case("multipart/form-data") {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "content type is not multipart/form-data",
+        )
+        .with_context("content_type", content_type));
+    }
+
+    params
+        .filter_map(|v| v.trim().split_once('='))
+        .find(|(k, _)| k.eq_ignore_ascii_case("boundary"))
+        .map(|(_, v)| v.trim_matches('"').to_string())
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| {
+            Error::new(ErrorKind::InvalidInput, "boundary is missing")
+                .with_context("content_type", content_type)
+        })
+}
+
+fn parse_part_headers(bs: &[u8]) -> Result<PartHeaders> {
+    let s = std::str::from_utf8(bs).map_err(|err| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            "headers of form part is not valid utf-8",
+        )
+        .set_source(err)
+    })?;
+
+    let mut headers = PartHeaders::default();
+    let mut has_disposition = false;
+    for line in s.split("\r\n") {
+        let (key, value) = match line.split_once(':') {
+            Some(v) => v,
+            None => continue,
+        };
+        let value = value.trim();
+
+        if key.trim().eq_ignore_ascii_case("content-disposition") {
+            has_disposition = true;
+            for param in value.split(';').skip(1) {
+                match param.trim().split_once('=') {
+                    Some(("name", v)) => headers.name = v.trim_matches('"').to_string(),
+                    Some(("filename", v)) => {
+                        headers.file_name = Some(v.trim_matches('"').to_string())
+                    }
+                    _ => {}
+                }
+            }
+        } else if key.trim().eq_ignore_ascii_case("content-type") {
+            headers.content_type = Some(value.to_string());
+        }
+    }
+
+    if !has_disposition {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "form part doesn't have content-disposition",
+        ));
+    }
+    Ok(headers)
+}
+
+/// Only keep the base name of file provided by client so that files can't
+/// be written outside the dir.
+fn sanitize_file_name(name: &str) -> Result<&str> {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    if name.is_empty() || name == "." || name == ".." {
+        return Err(Error::new(ErrorKind::InvalidInput, "file name is invalid")
+            .with_context("file_name", name));
+    }
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+    use crate::services::Memory;
+
+    const BODY: &str = "--boundary\r\n\
+        Content-Disposition: form-data; name=\"title\"\r\n\
+        \r\n\
+        hello\r\n\
+        --boundary\r\n\
+        Content-Disposition: form-data; name=\"file\"; filename=\"../a.txt\"\r\n\
+        Content-Type: text/plain\r\n\
+        \r\n\
+        Hello, World!\r\n\
+        --boundary--\r\n";
+
+    #[tokio::test]
+    async fn test_form_upload() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+
+        // Split body into small chunks to make sure boundaries across
+        // chunks are handled.
+        let chunks: Vec<_> = BODY
+            .as_bytes()
+            .chunks(3)
+            .map(|v| Ok::<_, std::io::Error>(Bytes::copy_from_slice(v)))
+            .collect();
+        let form = FormUpload::new(op.clone())
+            .with_dir("uploads")
+            .upload(
+                "multipart/form-data; boundary=boundary",
+                stream::iter(chunks),
+            )
+            .await?;
+
+        assert_eq!(form.field("title"), Some("hello"));
+        assert_eq!(form.files().len(), 1);
+        let file = &form.files()[0];
+        assert_eq!(file.path(), "uploads/a.txt");
+        assert_eq!(file.size(), 13);
+        assert_eq!(file.content_type(), Some("text/plain"));
+
+        let meta = op.stat("uploads/a.txt").await?;
+        assert_eq!(meta.content_type(), Some("text/plain"));
+        assert_eq!(op.read("uploads/a.txt").await?, b"Hello, World!");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_form_upload_too_large() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+
+        let err = FormUpload::new(op)
+            .with_max_file_size(5)
+            .upload(
+                "multipart/form-data; boundary=\"boundary\"",
+                stream::iter(vec![Ok::<_, std::io::Error>(Bytes::from(BODY))]),
+            )
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parser_preamble_is_bounded() -> Result<()> {
+        let mut parser = FormParser::new("boundary");
+        for _ in 0..1024 {
+            parser.buf.extend_from_slice(&[b'-'; 1024]);
+            assert!(parser.next_part()?.is_pending());
+            assert!(parser.buf.len() < parser.delimiter.len());
+        }
+
+        parser.buf.extend_from_slice(BODY.as_bytes());
+        let headers = match parser.next_part()? {
+            Poll::Ready(Some(headers)) => headers,
+            _ => panic!("headers must be ready"),
+        };
+        assert_eq!(headers.name, "title");
+        Ok(())
+    }
+}
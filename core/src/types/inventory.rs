@@ -0,0 +1,790 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use flate2::write::GzDecoder;
+use serde::Deserialize;
+
+use crate::raw::oio::BlockingRead;
+use crate::raw::oio::ReadExt;
+use crate::raw::*;
+use crate::*;
+
+/// InventoryFormat is the format of inventory reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InventoryFormat {
+    /// [S3 Inventory](https://docs.aws.amazon.com/AmazonS3/latest/userguide/storage-inventory.html)
+    /// in CSV format.
+    S3,
+    /// [Azure Blob Inventory](https://learn.microsoft.com/en-us/azure/storage/blobs/blob-inventory)
+    /// in CSV format.
+    Azblob,
+}
+
+/// Inventory is a native inventory report that can be used as the source of
+/// [`Operator::lister_with`] instead of live list calls, so that namespaces
+/// with billions of objects can be enumerated cheaply.
+///
+/// `op` is the operator that contains the inventory report (e.g. the
+/// destination bucket) and `manifest` is the path to its `manifest.json`.
+///
+/// Keys in the inventory are matched against the root of the operator being
+/// listed. Entries returned from inventory are a snapshot at the time the
+/// report generated, and they carry all metadata available in the report
+/// so no extra `stat` will be sent.
+///
+/// # Examples
+///
+/// ```
+/// use futures::TryStreamExt;
+/// use opendal::Inventory;
+/// use opendal::Operator;
+/// use opendal::Result;
+///
+/// async fn test(op: Operator, inventory_op: Operator) -> Result<()> {
+///     let inventory = Inventory::s3(
+///         inventory_op,
+///         "bucket/config-id/2023-01-01T00-00Z/manifest.json",
+///     );
+///     let mut lister = op
+///         .lister_with("path/to/dir/")
+///         .recursive(true)
+///         .source(inventory)
+///         .await?;
+///     while let Some(entry) = lister.try_next().await? {
+///         println!("{}", entry.path());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Inventory {
+    op: Operator,
+    manifest: String,
+    format: InventoryFormat,
+}
+
+impl Inventory {
+    /// Create an inventory from given manifest in given format.
+    pub fn new(op: Operator, manifest: &str, format: InventoryFormat) -> Self {
+        Self {
+            op,
+            manifest: manifest.to_string(),
+            format,
+        }
+    }
+
+    /// Create an inventory from the `manifest.json` of S3 Inventory.
+    pub fn s3(op: Operator, manifest: &str) -> Self {
+        Self::new(op, manifest, InventoryFormat::S3)
+    }
+
+    /// Create an inventory from the `<rule>-manifest.json` of Azure Blob
+    /// Inventory.
+    pub fn azblob(op: Operator, manifest: &str) -> Self {
+        Self::new(op, manifest, InventoryFormat::Azblob)
+    }
+
+    /// Get the format of this inventory.
+    pub fn format(&self) -> InventoryFormat {
+        self.format
+    }
+
+    /// Create a lister that lists entries under `path` from this inventory.
+    pub(crate) async fn lister(
+        &self,
+        root: &str,
+        path: &str,
+        args: &OpList,
+    ) -> Result<oio::Lister> {
+        let bs = self.op.read(&self.manifest).await?;
+        let lister = self.new_lister(&bs, root, path, args)?;
+        Ok(Box::new(oio::PageLister::new(InventoryPageLister {
+            lister,
+            current: Mutex::new(None),
+        })))
+    }
+
+    /// Create a blocking lister that lists entries under `path` from this
+    /// inventory, the operator of inventory must support blocking.
+    pub(crate) fn blocking_lister(
+        &self,
+        root: &str,
+        path: &str,
+        args: &OpList,
+    ) -> Result<oio::BlockingLister> {
+        let bs = self.op.blocking().read(&self.manifest)?;
+        let lister = self.new_lister(&bs, root, path, args)?;
+        Ok(Box::new(BlockingInventoryLister {
+            lister,
+            idx: 0,
+            current: None,
+            entries: VecDeque::new(),
+        }))
+    }
+
+    fn new_lister(
+        &self,
+        bs: &[u8],
+        root: &str,
+        path: &str,
+        args: &OpList,
+    ) -> Result<InventoryLister> {
+        let (files, schema) = match self.format {
+            InventoryFormat::S3 => {
+                let manifest: S3Manifest =
+                    serde_json::from_slice(bs).map_err(|e| self.manifest_error(e))?;
+                if !manifest.file_format.eq_ignore_ascii_case("csv") {
+                    return Err(self.unsupported_format(&manifest.file_format));
+                }
+                let schema = manifest
+                    .file_schema
+                    .split(',')
+                    .map(|v| v.trim().to_string())
+                    .collect();
+                let files = manifest.files.into_iter().map(|v| v.key).collect();
+                (files, Some(schema))
+            }
+            InventoryFormat::Azblob => {
+                let manifest: AzblobManifest =
+                    serde_json::from_slice(bs).map_err(|e| self.manifest_error(e))?;
+                if !manifest.rule_definition.format.eq_ignore_ascii_case("csv") {
+                    return Err(self.unsupported_format(&manifest.rule_definition.format));
+                }
+                let files = manifest.files.into_iter().map(|v| v.blob).collect();
+                // Azure Blob Inventory reports carry the schema as header.
+                (files, None)
+            }
+        };
+
+        Ok(InventoryLister {
+            op: self.op.clone(),
+            format: self.format,
+            files,
+            schema,
+            root: root.trim_start_matches('/').to_string(),
+            path: if path == "/" {
+                "".to_string()
+            } else {
+                path.to_string()
+            },
+            recursive: args.recursive(),
+            start_after: args.start_after().map(|v| v.to_string()),
+            dirs: Mutex::new(HashSet::new()),
+        })
+    }
+
+    fn manifest_error(&self, err: serde_json::Error) -> Error {
+        Error::new(ErrorKind::Unexpected, "inventory manifest is invalid")
+            .with_context("manifest", &self.manifest)
+            .set_source(err)
+    }
+
+    fn unsupported_format(&self, format: &str) -> Error {
+        Error::new(
+            ErrorKind::Unsupported,
+            "only inventory reports in CSV format are supported",
+        )
+        .with_context("manifest", &self.manifest)
+        .with_context("format", format)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct S3Manifest {
+    file_format: String,
+    file_schema: String,
+    files: Vec<S3ManifestFile>,
+}
+
+#[derive(Deserialize)]
+struct S3ManifestFile {
+    key: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzblobManifest {
+    files: Vec<AzblobManifestFile>,
+    rule_definition: AzblobRuleDefinition,
+}
+
+#[derive(Deserialize)]
+struct AzblobManifestFile {
+    blob: String,
+}
+
+#[derive(Deserialize)]
+struct AzblobRuleDefinition {
+    format: String,
+}
+
+/// InventoryLister converts rows of inventory data files into entries.
+struct InventoryLister {
+    op: Operator,
+    format: InventoryFormat,
+    files: Vec<String>,
+    schema: Option<Vec<String>>,
+    root: String,
+    path: String,
+    recursive: bool,
+    start_after: Option<String>,
+    /// Dirs that have been returned in non-recursive list.
+    dirs: Mutex<HashSet<String>>,
+}
+
+/// InventoryPageLister reads one chunk of an inventory data file per page,
+/// so that large data files will not be loaded into memory at once.
+struct InventoryPageLister {
+    lister: InventoryLister,
+    /// The data file being read, it will be taken out while fetching page.
+    current: Mutex<Option<(Reader, InventoryFileDecoder)>>,
+}
+
+#[async_trait]
+impl oio::PageList for InventoryPageLister {
+    async fn next_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
+        let idx: usize = ctx.token.parse().unwrap_or(0);
+        let current = self.current.lock().expect("lock must be valid").take();
+        let (mut r, mut decoder) = match current {
+            Some(v) => v,
+            None => {
+                let Some(file) = self.lister.files.get(idx) else {
+                    ctx.done = true;
+                    return Ok(());
+                };
+                let r = self.lister.op.reader(file).await?;
+                (r, InventoryFileDecoder::new(file))
+            }
+        };
+
+        match r.next().await {
+            Some(bs) => {
+                decoder.feed(&self.lister, &bs?, &mut ctx.entries)?;
+                *self.current.lock().expect("lock must be valid") = Some((r, decoder));
+            }
+            None => {
+                decoder.finish(&self.lister, &mut ctx.entries)?;
+                ctx.token = (idx + 1).to_string();
+                ctx.done = idx + 1 >= self.lister.files.len();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// BlockingInventoryLister is the blocking version of
+/// [`InventoryPageLister`].
+struct BlockingInventoryLister {
+    lister: InventoryLister,
+    idx: usize,
+    current: Option<(BlockingReader, InventoryFileDecoder)>,
+    entries: VecDeque<oio::Entry>,
+}
+
+impl oio::BlockingList for BlockingInventoryLister {
+    fn next(&mut self) -> Result<Option<oio::Entry>> {
+        loop {
+            if let Some(entry) = self.entries.pop_front() {
+                return Ok(Some(entry));
+            }
+
+            let (r, decoder) = match self.current.as_mut() {
+                Some(v) => v,
+                None => {
+                    let Some(file) = self.lister.files.get(self.idx) else {
+                        return Ok(None);
+                    };
+                    let r = self.lister.op.blocking().reader(file)?;
+                    self.current.insert((r, InventoryFileDecoder::new(file)))
+                }
+            };
+            match BlockingRead::next(r) {
+                Some(bs) => decoder.feed(&self.lister, &bs?, &mut self.entries)?,
+                None => {
+                    decoder.finish(&self.lister, &mut self.entries)?;
+                    self.current = None;
+                    self.idx += 1;
+                }
+            }
+        }
+    }
+}
+
+/// InventoryFileDecoder decodes an inventory data file chunk by chunk.
+///
+/// Files ending with `.gz` will be decompressed, rows crossing chunks will
+/// be kept until they are complete.
+struct InventoryFileDecoder {
+    file: String,
+    gzip: Option<GzDecoder<Vec<u8>>>,
+    /// Bytes of incomplete utf-8 chars at the end of the last chunk.
+    pending: Vec<u8>,
+    csv: CsvParser,
+    /// The schema read from the header of data file.
+    schema: Option<Vec<String>>,
+}
+
+impl InventoryFileDecoder {
+    fn new(file: &str) -> Self {
+        Self {
+            file: file.to_string(),
+            gzip: file.ends_with(".gz").then(|| GzDecoder::new(Vec::new())),
+            pending: Vec::new(),
+            csv: CsvParser::default(),
+            schema: None,
+        }
+    }
+
+    fn feed(
+        &mut self,
+        lister: &InventoryLister,
+        bs: &[u8],
+        entries: &mut VecDeque<oio::Entry>,
+    ) -> Result<()> {
+        match self.gzip.as_mut() {
+            Some(gzip) => {
+                gzip.write_all(bs)
+                    .map_err(|e| new_decompress_error(&self.file, e))?;
+                let buf = std::mem::take(gzip.get_mut());
+                self.pending.extend_from_slice(&buf);
+            }
+            None => self.pending.extend_from_slice(bs),
+        }
+
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(s) => s.len(),
+            // Incomplete char at the end, wait for next chunk.
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => return Err(self.utf8_error(e)),
+        };
+        let rest = self.pending.split_off(valid);
+        let content = std::str::from_utf8(&self.pending).expect("content must be valid utf-8");
+        let mut records = vec![];
+        self.csv.feed(content, &mut records);
+        self.pending = rest;
+
+        self.push_records(lister, records, entries)
+    }
+
+    fn finish(
+        &mut self,
+        lister: &InventoryLister,
+        entries: &mut VecDeque<oio::Entry>,
+    ) -> Result<()> {
+        // Flush the rest of gzip stream and feed it as plain content.
+        if let Some(mut gzip) = self.gzip.take() {
+            gzip.try_finish()
+                .map_err(|e| new_decompress_error(&self.file, e))?;
+            let buf = std::mem::take(gzip.get_mut());
+            self.feed(lister, &buf, entries)?;
+        }
+
+        if !self.pending.is_empty() {
+            let err = std::str::from_utf8(&self.pending).expect_err("must be incomplete");
+            return Err(self.utf8_error(err));
+        }
+        let mut records = vec![];
+        self.csv.finish(&mut records);
+        self.push_records(lister, records, entries)
+    }
+
+    fn push_records(
+        &mut self,
+        lister: &InventoryLister,
+        records: Vec<Vec<String>>,
+        entries: &mut VecDeque<oio::Entry>,
+    ) -> Result<()> {
+        for record in records {
+            let schema = match (&lister.schema, &self.schema) {
+                (Some(schema), _) | (None, Some(schema)) => schema,
+                (None, None) => {
+                    self.schema = Some(record);
+                    continue;
+                }
+            };
+            let row: HashMap<&str, &str> = schema
+                .iter()
+                .map(|v| v.as_str())
+                .zip(record.iter().map(|v| v.as_str()))
+                .collect();
+            if let Some(entry) = lister.parse_row(&row)? {
+                entries.push_back(entry);
+            }
+        }
+        Ok(())
+    }
+
+    fn utf8_error(&self, err: std::str::Utf8Error) -> Error {
+        Error::new(ErrorKind::Unexpected, "inventory file is not valid utf-8")
+            .with_context("file", &self.file)
+            .set_source(err)
+    }
+}
+
+impl InventoryLister {
+    /// Convert a row of inventory into entry, returns `None` if it should
+    /// be skipped.
+    fn parse_row(&self, row: &HashMap<&str, &str>) -> Result<Option<oio::Entry>> {
+        let (key, size, last_modified, etag, storage_class) = match self.format {
+            InventoryFormat::S3 => {
+                // Skip non-current versions and delete markers.
+                if row.get("IsLatest") == Some(&"false")
+                    || row.get("IsDeleteMarker") == Some(&"true")
+                {
+                    return Ok(None);
+                }
+                (
+                    percent_decode_path(row.get("Key").copied().unwrap_or_default()),
+                    row.get("Size"),
+                    row.get("LastModifiedDate"),
+                    row.get("ETag"),
+                    row.get("StorageClass"),
+                )
+            }
+            InventoryFormat::Azblob => (
+                row.get("Name").copied().unwrap_or_default().to_string(),
+                row.get("Content-Length"),
+                row.get("Last-Modified"),
+                row.get("Etag"),
+                row.get("AccessTier"),
+            ),
+        };
+
+        let Some(rel) = key.strip_prefix(&self.root) else {
+            return Ok(None);
+        };
+        let Some(rest) = rel.strip_prefix(&self.path) else {
+            return Ok(None);
+        };
+        if rest.is_empty() {
+            return Ok(None);
+        }
+
+        // Group entries into dirs for non-recursive list.
+        if !self.recursive {
+            if let Some(idx) = rest.find('/') {
+                let dir = format!("{}{}", self.path, &rest[..=idx]);
+                if !self.is_after(&dir)
+                    || !self
+                        .dirs
+                        .lock()
+                        .expect("lock must be valid")
+                        .insert(dir.clone())
+                {
+                    return Ok(None);
+                }
+                let meta = Metadata::new(EntryMode::DIR).with_metakey(Metakey::Complete);
+                return Ok(Some(oio::Entry::new(&dir, meta)));
+            }
+        }
+        if !self.is_after(rel) {
+            return Ok(None);
+        }
+
+        let mode = if rel.ends_with('/') {
+            EntryMode::DIR
+        } else {
+            EntryMode::FILE
+        };
+        let mut meta = Metadata::new(mode);
+        if meta.is_file() {
+            if let Some(v) = size.filter(|v| !v.is_empty()) {
+                meta.set_content_length(v.parse().map_err(|e| {
+                    Error::new(ErrorKind::Unexpected, "inventory size is invalid")
+                        .with_context("key", &key)
+                        .set_source(e)
+                })?);
+            }
+            if let Some(v) = last_modified.filter(|v| !v.is_empty()) {
+                meta.set_last_modified(
+                    parse_datetime_from_rfc3339(v).or_else(|_| parse_datetime_from_rfc2822(v))?,
+                );
+            }
+            if let Some(v) = etag.filter(|v| !v.is_empty()) {
+                meta.set_etag(v);
+            }
+            if let Some(v) = storage_class.filter(|v| !v.is_empty()) {
+                meta.set_storage_class(v);
+            }
+        }
+        // Inventory carries all metadata we can get, don't stat them again.
+        let meta = meta.with_metakey(Metakey::Complete);
+        Ok(Some(oio::Entry::new(rel, meta)))
+    }
+
+    fn is_after(&self, path: &str) -> bool {
+        match &self.start_after {
+            Some(v) => path > v.as_str(),
+            None => true,
+        }
+    }
+}
+
+fn new_decompress_error(file: &str, err: std::io::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, "decompress inventory file failed")
+        .with_context("file", file)
+        .set_source(err)
+}
+
+/// Parse CSV content into records, quoted fields could contain `,`, `"`
+/// (escaped as `""`) and line breaks.
+#[cfg(test)]
+fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut parser = CsvParser::default();
+    parser.feed(content, &mut records);
+    parser.finish(&mut records);
+    records
+}
+
+/// CsvParser parses CSV content that could be split at any char.
+///
+/// Quoted fields could contain `,`, `"` (escaped as `""`) and line breaks.
+#[derive(Default)]
+struct CsvParser {
+    record: Vec<String>,
+    field: String,
+    quoted: bool,
+    /// A `"` has been met in quoted field, it's either the end of quote or
+    /// an escaped `"` depending on the next char.
+    quote_pending: bool,
+}
+
+impl CsvParser {
+    /// Feed content and push complete records into `records`.
+    fn feed(&mut self, content: &str, records: &mut Vec<Vec<String>>) {
+        for c in content.chars() {
+            if self.quote_pending {
+                self.quote_pending = false;
+                if c == '"' {
+                    self.field.push('"');
+                    continue;
+                }
+                self.quoted = false;
+            }
+
+            match c {
+                '"' if self.quoted => self.quote_pending = true,
+                '"' => self.quoted = true,
+                ',' if !self.quoted => self.record.push(std::mem::take(&mut self.field)),
+                '\r' if !self.quoted => {}
+                '\n' if !self.quoted => {
+                    self.record.push(std::mem::take(&mut self.field));
+                    records.push(std::mem::take(&mut self.record));
+                }
+                c => self.field.push(c),
+            }
+        }
+    }
+
+    /// Push the last record without trailing line break.
+    fn finish(&mut self, records: &mut Vec<Vec<String>>) {
+        if !self.field.is_empty() || !self.record.is_empty() {
+            self.record.push(std::mem::take(&mut self.field));
+            records.push(std::mem::take(&mut self.record));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services::Memory;
+
+    #[test]
+    fn test_parse_csv() {
+        let records = parse_csv("a,\"b,\"\"c\"\"\"\r\n\"d\ne\",\n");
+        assert_eq!(
+            records,
+            vec![
+                vec!["a".to_string(), "b,\"c\"".to_string()],
+                vec!["d\ne".to_string(), "".to_string()],
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_from_s3_inventory() -> Result<()> {
+        let inventory_op = Operator::new(Memory::default())?.finish();
+        inventory_op
+            .write(
+                "manifest.json",
+                r#"{
+                    "sourceBucket": "example",
+                    "fileFormat": "CSV",
+                    "fileSchema": "Bucket, Key, Size, LastModifiedDate, ETag, StorageClass",
+                    "files": [{"key": "data/1.csv"}, {"key": "data/2.csv"}]
+                }"#,
+            )
+            .await?;
+        inventory_op
+            .write(
+                "data/1.csv",
+                "\"example\",\"dir/a%20b.txt\",\"3\",\"2023-01-01T00:00:00.000Z\",\"etag-a\",\"STANDARD\"\n\
+                 \"example\",\"dir/sub/c.txt\",\"5\",\"2023-01-01T00:00:00.000Z\",\"etag-c\",\"GLACIER\"\n",
+            )
+            .await?;
+        inventory_op
+            .write(
+                "data/2.csv",
+                "\"example\",\"dir/sub/d.txt\",\"7\",\"2023-01-01T00:00:00.000Z\",\"etag-d\",\"STANDARD\"\n\
+                 \"example\",\"other.txt\",\"1\",\"2023-01-01T00:00:00.000Z\",\"etag-o\",\"STANDARD\"\n",
+            )
+            .await?;
+        let inventory = Inventory::s3(inventory_op, "manifest.json");
+
+        // The operator being listed is empty, all entries come from inventory.
+        let op = Operator::new(Memory::default())?.finish();
+        let entries: Vec<Entry> = op
+            .lister_with("dir/")
+            .source(inventory.clone())
+            .await?
+            .try_collect()
+            .await?;
+        let paths: Vec<&str> = entries.iter().map(|e| e.path()).collect();
+        assert_eq!(paths, vec!["dir/a b.txt", "dir/sub/"]);
+        assert_eq!(entries[0].metadata().content_length(), 3);
+        assert_eq!(entries[0].metadata().etag(), Some("etag-a"));
+
+        let entries: Vec<Entry> = op
+            .lister_with("dir/")
+            .recursive(true)
+            .metakey(Metakey::StorageClass)
+            .source(inventory)
+            .await?
+            .try_collect()
+            .await?;
+        let paths: Vec<&str> = entries.iter().map(|e| e.path()).collect();
+        assert_eq!(paths, vec!["dir/a b.txt", "dir/sub/c.txt", "dir/sub/d.txt"]);
+        assert_eq!(entries[1].metadata().storage_class(), Some("GLACIER"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_from_azblob_inventory() -> Result<()> {
+        let inventory_op = Operator::new(Memory::default())?.finish();
+        inventory_op
+            .write(
+                "rule-manifest.json",
+                r#"{
+                    "destinationContainer": "inventory",
+                    "files": [{"blob": "2023/01/01/rule.csv", "size": 100}],
+                    "ruleDefinition": {"format": "csv", "objectType": "blob"}
+                }"#,
+            )
+            .await?;
+        inventory_op
+            .write(
+                "2023/01/01/rule.csv",
+                "Name,Content-Length,Last-Modified,AccessTier\n\
+                 a.txt,3,2023-01-01T00:00:00.0000000Z,Hot\n\
+                 b/c.txt,5,2023-01-01T00:00:00.0000000Z,Cool\n",
+            )
+            .await?;
+        let inventory = Inventory::azblob(inventory_op, "rule-manifest.json");
+
+        let op = Operator::new(Memory::default())?.finish();
+        let entries: Vec<Entry> = op
+            .lister_with("/")
+            .start_after("a.txt")
+            .source(inventory)
+            .await?
+            .try_collect()
+            .await?;
+        let paths: Vec<&str> = entries.iter().map(|e| e.path()).collect();
+        assert_eq!(paths, vec!["b/"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_gzip_in_chunks() -> Result<()> {
+        let content = "Name,Content-Length\n\"a,\"\"é\"\"\",3\nb/c.txt,5";
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gzip.write_all(content.as_bytes())
+            .expect("write must succeed");
+        let bs = gzip.finish().expect("finish must succeed");
+
+        let lister = InventoryLister {
+            op: Operator::new(Memory::default())?.finish(),
+            format: InventoryFormat::Azblob,
+            files: vec![],
+            schema: None,
+            root: "".to_string(),
+            path: "".to_string(),
+            recursive: true,
+            start_after: None,
+            dirs: Mutex::new(HashSet::new()),
+        };
+        // Feed one byte per chunk so that chars and rows are split.
+        let mut decoder = InventoryFileDecoder::new("rule.csv.gz");
+        let mut entries = VecDeque::new();
+        for b in bs.chunks(1) {
+            decoder.feed(&lister, b, &mut entries)?;
+        }
+        decoder.finish(&lister, &mut entries)?;
+
+        let entries: Vec<(String, u64)> = entries
+            .into_iter()
+            .map(|e| {
+                let e = e.into_entry();
+                (e.path().to_string(), e.metadata().content_length())
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            vec![("a,\"é\"".to_string(), 3), ("b/c.txt".to_string(), 5),]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_blocking_list_from_inventory() -> Result<()> {
+        let inventory_op = Operator::new(Memory::default())?.finish();
+        let blocking_inventory_op = inventory_op.blocking();
+        blocking_inventory_op.write(
+            "rule-manifest.json",
+            r#"{
+                "files": [{"blob": "rule.csv"}],
+                "ruleDefinition": {"format": "csv"}
+            }"#,
+        )?;
+        blocking_inventory_op.write("rule.csv", "Name,Content-Length\na.txt,3\nb/c.txt,5\n")?;
+        let inventory = Inventory::azblob(inventory_op, "rule-manifest.json");
+
+        let op = Operator::new(Memory::default())?.finish().blocking();
+        let entries: Vec<Entry> = op
+            .lister_with("/")
+            .recursive(true)
+            .source(inventory)
+            .call()?
+            .collect::<Result<_>>()?;
+        let paths: Vec<&str> = entries.iter().map(|e| e.path()).collect();
+        assert_eq!(paths, vec!["a.txt", "b/c.txt"]);
+        Ok(())
+    }
+}
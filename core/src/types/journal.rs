@@ -0,0 +1,447 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::time::Duration;
+use std::time::Instant;
+
+use bytes::Buf;
+use bytes::BufMut;
+use bytes::Bytes;
+use bytes::BytesMut;
+
+use crate::*;
+
+/// The size of record header which is the length of record in u32 BE.
+const RECORD_HEADER_SIZE: usize = 4;
+const SEGMENT_SUFFIX: &str = ".log";
+const DEFAULT_SEGMENT_SIZE: u64 = 64 * 1024 * 1024;
+
+/// FlushPolicy controls when records appended to [`JournalWriter`] will be
+/// sent to the service.
+///
+/// Records are durable only after they have been flushed. Every flush is
+/// an append write which is persisted once it returns, for example `fs`
+/// will fsync the segment, so the flush policy is also the fsync policy:
+/// use [`FlushPolicy::Bytes`] or [`FlushPolicy::Interval`] to sync records
+/// in groups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlushPolicy {
+    /// Flush after every record, the safest but slowest.
+    EveryRecord,
+    /// Flush once buffered records reach given bytes.
+    Bytes(usize),
+    /// Flush on append if the last flush happened before given interval.
+    Interval(Duration),
+    /// Only flush while [`JournalWriter::flush`] or [`JournalWriter::close`]
+    /// is called.
+    Manual,
+}
+
+/// JournalPosition is the position of a record in [`Journal`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JournalPosition {
+    /// The id of segment.
+    pub segment: u64,
+    /// The offset in segment.
+    pub offset: u64,
+}
+
+/// Journal is an append-only log built on the append capability of
+/// services like `fs` and `azblob` (AppendBlob).
+///
+/// Records are stored in segments named like `{dir}00000000000000000001.log`,
+/// a new segment will be started once the current one reaches
+/// `segment_size`. Every record is prefixed with its length, so readers can
+/// tail segments while they are being written.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::FlushPolicy;
+/// use opendal::Journal;
+/// use opendal::JournalPosition;
+/// use opendal::Operator;
+/// use opendal::Result;
+///
+/// async fn test(op: Operator) -> Result<()> {
+///     let journal = Journal::new(op, "wal/").with_flush_policy(FlushPolicy::Bytes(4096));
+///
+///     let mut w = journal.writer().await?;
+///     w.append("hello").await?;
+///     w.close().await?;
+///
+///     let mut r = journal.reader(JournalPosition::default());
+///     while let Some(record) = r.next().await? {
+///         println!("{:?}", record);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Journal {
+    op: Operator,
+    dir: String,
+    segment_size: u64,
+    flush_policy: FlushPolicy,
+}
+
+impl Journal {
+    /// Create a journal that stores segments under given dir.
+    pub fn new(op: Operator, dir: &str) -> Self {
+        Self {
+            op,
+            dir: format!("{}/", dir.trim_end_matches('/')),
+            segment_size: DEFAULT_SEGMENT_SIZE,
+            flush_policy: FlushPolicy::EveryRecord,
+        }
+    }
+
+    /// Set the max size of segments.
+    ///
+    /// Default to 64 MiB.
+    pub fn with_segment_size(mut self, size: u64) -> Self {
+        self.segment_size = size;
+        self
+    }
+
+    /// Set the flush policy of writers.
+    ///
+    /// Default to [`FlushPolicy::EveryRecord`].
+    pub fn with_flush_policy(mut self, policy: FlushPolicy) -> Self {
+        self.flush_policy = policy;
+        self
+    }
+
+    /// List the ids of all segments in order.
+    pub async fn segments(&self) -> Result<Vec<u64>> {
+        let entries = match self.op.list(&self.dir).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err),
+        };
+
+        let mut ids: Vec<u64> = entries
+            .iter()
+            .filter_map(|e| e.name().strip_suffix(SEGMENT_SUFFIX)?.parse().ok())
+            .collect();
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    /// Create a writer that appends records into a new segment.
+    ///
+    /// Only one writer should exist for a journal at the same time.
+    pub async fn writer(&self) -> Result<JournalWriter> {
+        if !self.op.info().full_capability().write_can_append {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "journal requires the service to support append",
+            )
+            .with_context("service", self.op.info().scheme().into_static()));
+        }
+
+        let segment = self.segments().await?.last().map(|v| v + 1).unwrap_or(1);
+        Ok(JournalWriter {
+            journal: self.clone(),
+            segment,
+            written: 0,
+            buf: BytesMut::new(),
+            flushed_at: Instant::now(),
+            uncertain: false,
+        })
+    }
+
+    /// Create a reader that starts reading from given position.
+    pub fn reader(&self, start: JournalPosition) -> JournalReader {
+        JournalReader {
+            journal: self.clone(),
+            pos: start,
+            buf: Bytes::new(),
+        }
+    }
+
+    fn segment_path(&self, segment: u64) -> String {
+        format!("{}{:020}{}", self.dir, segment, SEGMENT_SUFFIX)
+    }
+}
+
+/// JournalWriter appends records into [`Journal`].
+#[derive(Debug)]
+pub struct JournalWriter {
+    journal: Journal,
+    segment: u64,
+    /// The size that has been flushed to current segment.
+    written: u64,
+    buf: BytesMut,
+    flushed_at: Instant,
+    /// Last flush failed, part of the records could have been persisted.
+    uncertain: bool,
+}
+
+impl JournalWriter {
+    /// Append a record and returns its position.
+    ///
+    /// The record may not be durable until flushed, see [`FlushPolicy`].
+    pub async fn append(&mut self, record: impl Into<Bytes>) -> Result<JournalPosition> {
+        let record = record.into();
+        let size = (RECORD_HEADER_SIZE + record.len()) as u64;
+        let len = u32::try_from(record.len()).map_err(|_| {
+            Error::new(ErrorKind::InvalidInput, "record is too large")
+                .with_context("size", record.len().to_string())
+        })?;
+
+        // Rotate segment if current one is going to be full.
+        let current = self.written + self.buf.len() as u64;
+        if current > 0 && current + size > self.journal.segment_size {
+            self.flush().await?;
+            self.segment += 1;
+            self.written = 0;
+        }
+
+        let pos = JournalPosition {
+            segment: self.segment,
+            offset: self.written + self.buf.len() as u64,
+        };
+        self.buf.put_u32(len);
+        self.buf.put(record);
+
+        let should_flush = match self.journal.flush_policy {
+            FlushPolicy::EveryRecord => true,
+            FlushPolicy::Bytes(n) => self.buf.len() >= n,
+            FlushPolicy::Interval(d) => self.flushed_at.elapsed() >= d,
+            FlushPolicy::Manual => false,
+        };
+        if should_flush {
+            self.flush().await?;
+        }
+        Ok(pos)
+    }
+
+    /// Flush all buffered records into service.
+    pub async fn flush(&mut self) -> Result<()> {
+        self.flushed_at = Instant::now();
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+
+        if self.uncertain {
+            self.recover().await?;
+            if self.buf.is_empty() {
+                return Ok(());
+            }
+        }
+
+        let bs = self.buf.split().freeze();
+        let size = bs.len() as u64;
+        let path = self.journal.segment_path(self.segment);
+        if let Err(err) = self
+            .journal
+            .op
+            .write_with(&path, bs.clone())
+            .append(true)
+            .await
+        {
+            // Put records back so that they could be flushed again.
+            let mut buf = BytesMut::from(bs.as_ref());
+            buf.extend_from_slice(&self.buf);
+            self.buf = buf;
+            self.uncertain = true;
+            return Err(err);
+        }
+        self.written += size;
+        Ok(())
+    }
+
+    /// Drop buffered data that has been persisted by a failed flush, so that
+    /// records won't be appended twice.
+    async fn recover(&mut self) -> Result<()> {
+        let path = self.journal.segment_path(self.segment);
+        let size = match self.journal.op.stat(&path).await {
+            Ok(meta) => meta.content_length(),
+            Err(err) if err.kind() == ErrorKind::NotFound => 0,
+            Err(err) => return Err(err),
+        };
+
+        let persisted = size.saturating_sub(self.written).min(self.buf.len() as u64);
+        self.buf.advance(persisted as usize);
+        self.written += persisted;
+        self.uncertain = false;
+        Ok(())
+    }
+
+    /// Flush all buffered records and close the writer.
+    pub async fn close(&mut self) -> Result<()> {
+        self.flush().await
+    }
+}
+
+/// JournalReader reads records from [`Journal`] in order.
+///
+/// Reader will move to the next segment once current segment is fully read
+/// and a newer segment exists, so it can be used to tail a journal.
+#[derive(Debug)]
+pub struct JournalReader {
+    journal: Journal,
+    pos: JournalPosition,
+    /// Data that has been read from `pos` but not returned yet.
+    buf: Bytes,
+}
+
+impl JournalReader {
+    /// The position of next record.
+    pub fn position(&self) -> JournalPosition {
+        self.pos
+    }
+
+    /// Read next record, returns `None` if no more records available for
+    /// now, callers can call it again later to tail the journal.
+    pub async fn next(&mut self) -> Result<Option<Bytes>> {
+        loop {
+            if let Some(record) = self.next_buffered() {
+                return Ok(Some(record));
+            }
+            if self.fill().await? {
+                continue;
+            }
+
+            // Current segment has been fully read, move to the next one.
+            let next = self
+                .journal
+                .segments()
+                .await?
+                .into_iter()
+                .find(|id| *id > self.pos.segment);
+            match next {
+                Some(segment) => {
+                    // Make sure records appended before rotation are read.
+                    if self.fill().await? {
+                        continue;
+                    }
+                    self.pos = JournalPosition { segment, offset: 0 };
+                    self.buf = Bytes::new();
+                }
+                None => return Ok(None),
+            }
+        }
+    }
+
+    fn next_buffered(&mut self) -> Option<Bytes> {
+        if self.buf.len() < RECORD_HEADER_SIZE {
+            return None;
+        }
+        let len = (&self.buf[..RECORD_HEADER_SIZE]).get_u32() as usize;
+        if self.buf.len() < RECORD_HEADER_SIZE + len {
+            return None;
+        }
+
+        self.buf.advance(RECORD_HEADER_SIZE);
+        let record = self.buf.split_to(len);
+        self.pos.offset += (RECORD_HEADER_SIZE + len) as u64;
+        Some(record)
+    }
+
+    /// Read new data of current segment, returns `false` if nothing new.
+    async fn fill(&mut self) -> Result<bool> {
+        let path = self.journal.segment_path(self.pos.segment);
+        let start = self.pos.offset + self.buf.len() as u64;
+
+        let size = match self.journal.op.stat(&path).await {
+            Ok(meta) => meta.content_length(),
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(false),
+            Err(err) => return Err(err),
+        };
+        if size <= start {
+            return Ok(false);
+        }
+
+        let bs = self.journal.op.read_with(&path).range(start..size).await?;
+        let mut buf = BytesMut::from(self.buf.as_ref());
+        buf.extend_from_slice(&bs);
+        self.buf = buf.freeze();
+        Ok(true)
+    }
+}
+
+#[cfg(all(test, feature = "services-fs"))]
+mod tests {
+    use super::*;
+    use crate::services::Fs;
+
+    #[tokio::test]
+    async fn test_journal() -> Result<()> {
+        let root = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let mut builder = Fs::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder)?.finish();
+
+        // Every segment can hold two `record-x` at most.
+        let journal = Journal::new(op.clone(), "wal")
+            .with_segment_size(24)
+            .with_flush_policy(FlushPolicy::Manual);
+
+        let mut w = journal.writer().await?;
+        let mut r = journal.reader(JournalPosition::default());
+        for i in 0..3 {
+            w.append(format!("record-{i}")).await?;
+        }
+        assert_eq!(r.next().await?.as_deref(), Some(b"record-0".as_ref()));
+        assert_eq!(r.next().await?.as_deref(), Some(b"record-1".as_ref()));
+        assert_eq!(r.next().await?, None);
+
+        w.close().await?;
+        assert_eq!(journal.segments().await?, vec![1, 2]);
+        assert_eq!(r.next().await?.as_deref(), Some(b"record-2".as_ref()));
+        assert_eq!(
+            r.position(),
+            JournalPosition {
+                segment: 2,
+                offset: 12
+            }
+        );
+
+        op.remove_all("/").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_journal_flush_after_failure() -> Result<()> {
+        let root = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let mut builder = Fs::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder)?.finish();
+
+        let journal = Journal::new(op.clone(), "wal").with_flush_policy(FlushPolicy::Manual);
+        let mut w = journal.writer().await?;
+        w.append("record-0").await?;
+        w.append("record-1").await?;
+
+        // Emulate a flush that failed after the first record was persisted.
+        let path = journal.segment_path(w.segment);
+        let persisted = w.buf[..RECORD_HEADER_SIZE + 8].to_vec();
+        op.write_with(&path, persisted).append(true).await?;
+        w.uncertain = true;
+
+        w.close().await?;
+        let mut r = journal.reader(JournalPosition::default());
+        assert_eq!(r.next().await?.as_deref(), Some(b"record-0".as_ref()));
+        assert_eq!(r.next().await?.as_deref(), Some(b"record-1".as_ref()));
+        assert_eq!(r.next().await?, None);
+
+        op.remove_all("/").await?;
+        Ok(())
+    }
+}
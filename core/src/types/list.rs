@@ -22,7 +22,10 @@ use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
 
+use chrono::DateTime;
+use chrono::Utc;
 use flagset::FlagSet;
+use futures::future::BoxFuture;
 use futures::FutureExt;
 use futures::Stream;
 use tokio::task::JoinHandle;
@@ -43,10 +46,64 @@ pub struct Lister {
     lister: Option<oio::Lister>,
     /// required_metakey is the metakey required by users.
     required_metakey: FlagSet<Metakey>,
+    /// modified_since is used to filter entries if service doesn't
+    /// support it.
+    modified_since: Option<DateTime<Utc>>,
 
     /// tasks is used to store tasks that are run in concurrent.
     tasks: VecDeque<StatTask>,
+    /// The max number of stat tasks that are running.
+    concurrent: usize,
+    /// The max number of tasks that could be queued.
+    queue_size: usize,
+    /// The number of stat tasks that are running.
+    stating: usize,
+    backpressure: ListBackpressure,
+    inline: bool,
     errored: bool,
+    summary: ListSummary,
+}
+
+/// ListBackpressure decides how [`Lister`] returns entries while there are
+/// stat tasks queued.
+///
+/// In both strategies, lister will stop fetching more entries while the
+/// stat queue is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ListBackpressure {
+    /// Wait for the oldest task, entries are returned in the listing order.
+    ///
+    /// A slow stat will block all following entries.
+    #[default]
+    Ordered,
+    /// Wait for any task, entries are returned in the completion order.
+    Unordered,
+}
+
+/// ListSummary records how entries of a list have been served.
+///
+/// Entries that don't contain the required metakey will be filled by an
+/// extra `stat` call, which could be expensive for large dirs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ListSummary {
+    entries_from_list: usize,
+    stat_fallbacks: usize,
+}
+
+impl ListSummary {
+    /// The number of entries served from listing directly.
+    pub fn entries_from_list(&self) -> usize {
+        self.entries_from_list
+    }
+
+    /// The number of extra `stat` calls issued for entries that don't
+    /// contain the required metakey.
+    ///
+    /// Entries whose `stat` failed are counted as well.
+    pub fn stat_fallbacks(&self) -> usize {
+        self.stat_fallbacks
+    }
 }
 
 /// StatTask is used to store the task that is run in concurrent.
@@ -85,8 +142,8 @@ pub struct Lister {
 /// use opendal::Result;
 /// use opendal::Entry;
 ///
-/// assert_eq!(264, size_of::<(String, Result<opendal::raw::RpStat>)>());
-/// assert_eq!(264, size_of::<Option<Entry>>());
+/// assert_eq!(360, size_of::<(String, Result<opendal::raw::RpStat>)>());
+/// assert_eq!(360, size_of::<Option<Entry>>());
 /// ```
 ///
 /// So let's ignore this lint:
@@ -94,6 +151,8 @@ pub struct Lister {
 enum StatTask {
     /// Stating is used to store the join handle of spawned task.
     Stating(JoinHandle<(String, Result<RpStat>)>),
+    /// InlineStating is used to store the stat future that polled by lister.
+    InlineStating(BoxFuture<'static, (String, Result<RpStat>)>),
     /// Known is used to store the entry that already contains the required metakey.
     Known(Option<Entry>),
 }
@@ -106,20 +165,67 @@ unsafe impl Sync for Lister {}
 impl Lister {
     /// Create a new lister.
     pub(crate) async fn create(acc: FusedAccessor, path: &str, args: OpList) -> Result<Self> {
-        let required_metakey = args.metakey();
+        let (required_metakey, modified_since) = filter_modified_since(&acc, &args);
         let concurrent = cmp::max(1, args.concurrent());
+        let queue_size = args.stat_queue_size().unwrap_or(concurrent);
+        if queue_size < concurrent {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "stat queue size must not be less than concurrent",
+            )
+            .with_operation(Operation::List)
+            .with_context("concurrent", concurrent.to_string())
+            .with_context("stat_queue_size", queue_size.to_string()));
+        }
+        let backpressure = args.stat_backpressure();
+        let inline = args.stat_inline();
 
+        #[cfg(feature = "inventory")]
+        let lister = match args.source() {
+            Some(inventory) => inventory.lister(acc.info().root(), path, &args).await?,
+            None => acc.list(path, args).await?.1,
+        };
+        #[cfg(not(feature = "inventory"))]
         let (_, lister) = acc.list(path, args).await?;
 
         Ok(Self {
             acc,
             lister: Some(lister),
             required_metakey,
-
-            tasks: VecDeque::with_capacity(concurrent),
+            modified_since,
+
+            tasks: VecDeque::with_capacity(queue_size),
+            concurrent,
+            queue_size,
+            stating: 0,
+            backpressure,
+            inline,
             errored: false,
+            summary: ListSummary::default(),
         })
     }
+
+    /// Poll given task, returns the entry if it's ready.
+    fn poll_task(task: &mut StatTask, cx: &mut Context<'_>) -> Poll<Result<Entry>> {
+        let (path, rp) = match task {
+            StatTask::Stating(handle) => {
+                ready!(handle.poll_unpin(cx)).map_err(new_task_join_error)?
+            }
+            StatTask::InlineStating(fut) => ready!(fut.poll_unpin(cx)),
+            StatTask::Known(entry) => {
+                return Poll::Ready(Ok(entry.take().expect("entry must be valid")))
+            }
+        };
+
+        Poll::Ready(rp.map(|rp| Entry::new(path, rp.into_metadata())))
+    }
+
+    /// Get the summary of this list.
+    ///
+    /// The summary will be complete after lister returns `None`.
+    pub fn summary(&self) -> ListSummary {
+        self.summary
+    }
 }
 
 impl Stream for Lister {
@@ -131,68 +237,89 @@ impl Stream for Lister {
             return Poll::Ready(None);
         }
 
-        // Trying to pull more tasks if there are more space.
-        if self.tasks.len() < self.tasks.capacity() {
-            if let Some(lister) = self.lister.as_mut() {
-                match lister.poll_next(cx) {
-                    Poll::Pending => {}
-                    Poll::Ready(Ok(Some(oe))) => {
-                        let (path, metadata) = oe.into_entry().into_parts();
-                        if metadata.contains_metakey(self.required_metakey) {
-                            self.tasks
-                                .push_back(StatTask::Known(Some(Entry::new(path, metadata))));
-                        } else {
-                            let acc = self.acc.clone();
-                            let fut = async move {
-                                let res = acc.stat(&path, OpStat::default()).await;
-                                (path, res)
-                            };
-                            self.tasks.push_back(StatTask::Stating(tokio::spawn(fut)));
+        loop {
+            // Trying to pull more tasks if there are more space.
+            if self.tasks.len() < self.queue_size && self.stating < self.concurrent {
+                if let Some(lister) = self.lister.as_mut() {
+                    match lister.poll_next(cx) {
+                        Poll::Pending => {}
+                        Poll::Ready(Ok(Some(oe))) => {
+                            let (path, metadata) = oe.into_entry().into_parts();
+                            if metadata.contains_metakey(self.required_metakey) {
+                                self.summary.entries_from_list += 1;
+                                self.tasks
+                                    .push_back(StatTask::Known(Some(Entry::new(path, metadata))));
+                            } else {
+                                self.summary.stat_fallbacks += 1;
+                                self.stating += 1;
+                                let acc = self.acc.clone();
+                                let fut = async move {
+                                    let res = acc.stat(&path, OpStat::default()).await;
+                                    (path, res)
+                                };
+                                let task = if self.inline {
+                                    StatTask::InlineStating(Box::pin(fut))
+                                } else {
+                                    StatTask::Stating(tokio::spawn(fut))
+                                };
+                                self.tasks.push_back(task);
+                            }
                         }
-                    }
-                    Poll::Ready(Ok(None)) => {
-                        self.lister = None;
-                    }
-                    Poll::Ready(Err(err)) => {
+                        Poll::Ready(Ok(None)) => {
+                            self.lister = None;
+                        }
+                        Poll::Ready(Err(err)) => {
+                            self.errored = true;
+                            return Poll::Ready(Some(Err(err)));
+                        }
+                    };
+                }
+            }
+
+            let ready = match self.backpressure {
+                ListBackpressure::Ordered => match self.tasks.front_mut() {
+                    Some(task) => Self::poll_task(task, cx).map(|res| (0, res)),
+                    None => Poll::Pending,
+                },
+                ListBackpressure::Unordered => self
+                    .tasks
+                    .iter_mut()
+                    .enumerate()
+                    .find_map(|(idx, task)| match Self::poll_task(task, cx) {
+                        Poll::Ready(res) => Some(Poll::Ready((idx, res))),
+                        Poll::Pending => None,
+                    })
+                    .unwrap_or(Poll::Pending),
+            };
+
+            if let Poll::Ready((idx, res)) = ready {
+                // Make sure this task has been removed after it's ready.
+                let task = self.tasks.remove(idx).expect("task must be valid");
+                if !matches!(task, StatTask::Known(_)) {
+                    self.stating -= 1;
+                }
+
+                return match res {
+                    // Skip entries that are not modified and try next one.
+                    Ok(entry) if !is_modified_since(&entry, self.modified_since) => continue,
+                    Ok(entry) => Poll::Ready(Some(Ok(entry))),
+                    Err(err) => {
                         self.errored = true;
-                        return Poll::Ready(Some(Err(err)));
+                        Poll::Ready(Some(Err(err)))
                     }
                 };
             }
-        }
-
-        if let Some(handle) = self.tasks.front_mut() {
-            return match handle {
-                StatTask::Stating(handle) => {
-                    let (path, rp) = ready!(handle.poll_unpin(cx)).map_err(new_task_join_error)?;
 
-                    // Make sure this task has been popped after it's ready.
-                    self.tasks.pop_front();
+            if !self.tasks.is_empty() {
+                return Poll::Pending;
+            }
 
-                    match rp {
-                        Ok(rp) => {
-                            let metadata = rp.into_metadata();
-                            Poll::Ready(Some(Ok(Entry::new(path, metadata))))
-                        }
-                        Err(err) => {
-                            self.errored = true;
-                            Poll::Ready(Some(Err(err)))
-                        }
-                    }
-                }
-                StatTask::Known(entry) => {
-                    let entry = entry.take().expect("entry must be valid");
-                    self.tasks.pop_front();
-                    Poll::Ready(Some(Ok(entry)))
-                }
+            return if self.lister.is_none() {
+                Poll::Ready(None)
+            } else {
+                Poll::Pending
             };
         }
-
-        if self.lister.is_none() {
-            Poll::Ready(None)
-        } else {
-            Poll::Pending
-        }
     }
 }
 
@@ -207,9 +334,13 @@ pub struct BlockingLister {
     acc: FusedAccessor,
     /// required_metakey is the metakey required by users.
     required_metakey: FlagSet<Metakey>,
+    /// modified_since is used to filter entries if service doesn't
+    /// support it.
+    modified_since: Option<DateTime<Utc>>,
 
     lister: oio::BlockingLister,
     errored: bool,
+    summary: ListSummary,
 }
 
 /// # Safety
@@ -220,17 +351,33 @@ unsafe impl Sync for BlockingLister {}
 impl BlockingLister {
     /// Create a new lister.
     pub(crate) fn create(acc: FusedAccessor, path: &str, args: OpList) -> Result<Self> {
-        let required_metakey = args.metakey();
+        let (required_metakey, modified_since) = filter_modified_since(&acc, &args);
+
+        #[cfg(feature = "inventory")]
+        let lister = match args.source() {
+            Some(inventory) => inventory.blocking_lister(acc.info().root(), path, &args)?,
+            None => acc.blocking_list(path, args)?.1,
+        };
+        #[cfg(not(feature = "inventory"))]
         let (_, lister) = acc.blocking_list(path, args)?;
 
         Ok(Self {
             acc,
             required_metakey,
+            modified_since,
 
             lister,
             errored: false,
+            summary: ListSummary::default(),
         })
     }
+
+    /// Get the summary of this list.
+    ///
+    /// The summary will be complete after lister returns `None`.
+    pub fn summary(&self) -> ListSummary {
+        self.summary
+    }
 }
 
 /// TODO: we can implement next_chunk.
@@ -238,6 +385,21 @@ impl Iterator for BlockingLister {
     type Item = Result<Entry>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let entry = match self.next_entry()? {
+                Ok(entry) => entry,
+                Err(err) => return Some(Err(err)),
+            };
+            // Skip entries that are not modified and try next one.
+            if is_modified_since(&entry, self.modified_since) {
+                return Some(Ok(entry));
+            }
+        }
+    }
+}
+
+impl BlockingLister {
+    fn next_entry(&mut self) -> Option<Result<Entry>> {
         // Returns `None` if we have errored.
         if self.errored {
             return None;
@@ -254,9 +416,12 @@ impl Iterator for BlockingLister {
 
         let (path, metadata) = entry.into_entry().into_parts();
         if metadata.contains_metakey(self.required_metakey) {
+            self.summary.entries_from_list += 1;
             return Some(Ok(Entry::new(path, metadata)));
         }
 
+        self.summary.stat_fallbacks += 1;
+
         let metadata = match self.acc.blocking_stat(&path, OpStat::default()) {
             Ok(rp) => rp.into_metadata(),
             Err(err) => {
@@ -268,13 +433,55 @@ impl Iterator for BlockingLister {
     }
 }
 
+/// Decide whether lister needs to filter entries by `modified_since`
+/// itself, returns the required metakey and the time to filter.
+///
+/// `last_modified` will be required if the service doesn't support
+/// filtering.
+fn filter_modified_since(
+    acc: &FusedAccessor,
+    args: &OpList,
+) -> (FlagSet<Metakey>, Option<DateTime<Utc>>) {
+    let metakey = args.metakey();
+    // Entries from inventory must be filtered by lister itself.
+    #[cfg(feature = "inventory")]
+    if let (Some(t), Some(_)) = (args.modified_since(), args.source()) {
+        return (metakey | Metakey::LastModified, Some(t));
+    }
+    match args.modified_since() {
+        Some(t) if !acc.info().full_capability().list_with_modified_since => {
+            (metakey | Metakey::LastModified, Some(t))
+        }
+        _ => (metakey, None),
+    }
+}
+
+/// Check if the entry has been modified since given time.
+///
+/// Dirs and entries without `last_modified` will always be kept since we
+/// can't tell whether they have been modified.
+fn is_modified_since(entry: &Entry, since: Option<DateTime<Utc>>) -> bool {
+    let Some(since) = since else {
+        return true;
+    };
+    if entry.metadata().is_dir() {
+        return true;
+    }
+    match entry.metadata().last_modified() {
+        Some(t) => t >= since,
+        None => true,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use futures::future;
     use futures::StreamExt;
+    use futures::TryStreamExt;
 
     use super::*;
     use crate::services::Azblob;
+    use crate::services::Memory;
 
     /// Inspired by <https://gist.github.com/kyle-mccarthy/1e6ae89cc34495d731b91ebf5eb5a3d9>
     ///
@@ -308,4 +515,131 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_list_modified_since() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        op.write("dir/old", "old").await?;
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        let since = Utc::now();
+        op.write("dir/new", "new").await?;
+
+        let entries = op.list_with("dir/").modified_since(since).await?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path(), "dir/new");
+
+        let entries = op
+            .blocking()
+            .list_with("dir/")
+            .modified_since(since)
+            .call()?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path(), "dir/new");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_summary() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        op.write("dir/a", "a").await?;
+        op.write("dir/b", "b").await?;
+
+        let mut lister = op.lister("dir/").await?;
+        let mut count = 0;
+        while lister.try_next().await?.is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 2);
+        assert_eq!(lister.summary().entries_from_list(), 2);
+        assert_eq!(lister.summary().stat_fallbacks(), 0);
+
+        // Memory doesn't return content length in list.
+        let mut lister = op
+            .lister_with("dir/")
+            .metakey(Metakey::ContentLength)
+            .await?;
+        while lister.try_next().await?.is_some() {}
+        assert_eq!(lister.summary().entries_from_list(), 0);
+        assert_eq!(lister.summary().stat_fallbacks(), 2);
+
+        let mut lister = op
+            .blocking()
+            .lister_with("dir/")
+            .metakey(Metakey::ContentLength)
+            .call()?;
+        for entry in lister.by_ref() {
+            entry?;
+        }
+        assert_eq!(lister.summary().entries_from_list(), 0);
+        assert_eq!(lister.summary().stat_fallbacks(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_stat_queue_size_less_than_concurrent() {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+
+        let err = op
+            .lister_with("dir/")
+            .concurrent(4)
+            .stat_queue_size(2)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[tokio::test]
+    async fn test_list_stat_inline() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        for i in 0..16 {
+            op.write(&format!("dir/{i:02}"), vec![0; i]).await?;
+        }
+        let expected: Vec<_> = op
+            .list("dir/")
+            .await?
+            .into_iter()
+            .map(|e| e.path().to_string())
+            .collect();
+
+        // Stat tasks are polled by lister without spawning, and entries
+        // are still returned in listing order.
+        let entries: Vec<Entry> = op
+            .lister_with("dir/")
+            .metakey(Metakey::ContentLength)
+            .concurrent(4)
+            .stat_queue_size(8)
+            .stat_inline(true)
+            .await?
+            .try_collect()
+            .await?;
+        let paths: Vec<_> = entries.iter().map(|e| e.path().to_string()).collect();
+        assert_eq!(paths, expected);
+        for entry in entries {
+            let size: u64 = entry.name().parse().unwrap();
+            assert_eq!(entry.metadata().content_length(), size);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_stat_unordered() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        for i in 0..16 {
+            op.write(&format!("dir/{i:02}"), vec![0; i]).await?;
+        }
+
+        let mut paths: Vec<_> = op
+            .lister_with("dir/")
+            .metakey(Metakey::ContentLength)
+            .concurrent(4)
+            .stat_backpressure(ListBackpressure::Unordered)
+            .await?
+            .map_ok(|e| e.path().to_string())
+            .try_collect()
+            .await?;
+        paths.sort();
+        let expected: Vec<_> = (0..16).map(|i| format!("dir/{i:02}")).collect();
+        assert_eq!(paths, expected);
+        Ok(())
+    }
 }
@@ -0,0 +1,433 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::*;
+
+const MANIFEST_DIR: &str = "_manifests/";
+const STAGING_DIR: &str = "_staging/";
+const MANIFEST_SUFFIX: &str = ".json";
+
+/// Manifest is a committed snapshot of [`ManifestLog`].
+///
+/// It maps logical paths to the physical paths that hold their content.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    version: u64,
+    entries: BTreeMap<String, String>,
+}
+
+impl Manifest {
+    /// The version of this manifest, starts from `1`.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Get the physical path of given logical path.
+    pub fn get(&self, path: &str) -> Option<&str> {
+        self.entries.get(path).map(|v| v.as_str())
+    }
+
+    /// Iterate all logical paths along with their physical paths.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+/// ManifestLog commits a set of objects atomically on plain object
+/// storages by two-phase manifest.
+///
+/// 1. Objects of a [`ManifestTransaction`] are written to a staging prefix.
+/// 2. [`ManifestTransaction::commit`] writes the next manifest with
+///    `If-None-Match: *`, only one of the concurrent transactions based on
+///    the same version could succeed.
+///
+/// Staged objects will be removed if the commit failed, and readers only
+/// see objects referenced by committed manifests.
+///
+/// Requires [`Capability::write_with_if_none_match`].
+///
+/// # Examples
+///
+/// ```
+/// use opendal::ManifestLog;
+/// use opendal::Operator;
+/// use opendal::Result;
+///
+/// async fn test(op: Operator) -> Result<()> {
+///     let log = ManifestLog::new(op, "tables/events");
+///
+///     let mut txn = log.begin().await?;
+///     txn.put("data/part-0", "Hello").await?;
+///     txn.put("data/part-1", "World").await?;
+///     let manifest = txn.commit().await?;
+///
+///     let content = log.read(&manifest, "data/part-0").await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ManifestLog {
+    op: Operator,
+    root: String,
+}
+
+impl ManifestLog {
+    /// Create a manifest log under given root.
+    pub fn new(op: Operator, root: &str) -> Self {
+        Self {
+            op,
+            root: format!("{}/", root.trim_end_matches('/')),
+        }
+    }
+
+    /// Load the latest committed manifest.
+    pub async fn latest(&self) -> Result<Option<Manifest>> {
+        let dir = format!("{}{}", self.root, MANIFEST_DIR);
+        let entries = match self.op.list(&dir).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let latest = entries
+            .iter()
+            .filter_map(|e| e.name().strip_suffix(MANIFEST_SUFFIX)?.parse::<u64>().ok())
+            .max();
+        match latest {
+            Some(version) => self.load(version).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Load the manifest of given version.
+    pub async fn load(&self, version: u64) -> Result<Manifest> {
+        let path = self.manifest_path(version);
+        let bs = self.op.read(&path).await?;
+        serde_json::from_slice(&bs).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "manifest is invalid")
+                .with_context("path", path)
+                .set_source(err)
+        })
+    }
+
+    /// Read the content of given logical path in manifest.
+    pub async fn read(&self, manifest: &Manifest, path: &str) -> Result<Vec<u8>> {
+        match manifest.get(path) {
+            Some(physical) => self.op.read(physical).await,
+            None => Err(
+                Error::new(ErrorKind::NotFound, "path is not found in manifest")
+                    .with_context("path", path)
+                    .with_context("version", manifest.version.to_string()),
+            ),
+        }
+    }
+
+    /// Begin a transaction based on the latest manifest.
+    pub async fn begin(&self) -> Result<ManifestTransaction> {
+        let base = self.latest().await?.unwrap_or_default();
+        let id = uuid::Uuid::new_v4().to_string();
+
+        Ok(ManifestTransaction {
+            log: self.clone(),
+            staging: format!("{}{}{}/", self.root, STAGING_DIR, id),
+            base,
+            puts: BTreeMap::new(),
+            deletes: vec![],
+            orphans: vec![],
+        })
+    }
+
+    fn manifest_path(&self, version: u64) -> String {
+        format!(
+            "{}{}{:020}{}",
+            self.root, MANIFEST_DIR, version, MANIFEST_SUFFIX
+        )
+    }
+}
+
+/// ManifestTransaction stages objects and commits them atomically, created
+/// by [`ManifestLog::begin`].
+///
+/// Staged objects will be removed if put or commit failed. Dropping a
+/// transaction without commit leaves staged objects behind, please call
+/// [`ManifestTransaction::abort`] to remove them.
+#[derive(Debug)]
+pub struct ManifestTransaction {
+    log: ManifestLog,
+    staging: String,
+    base: Manifest,
+    /// Logical paths to staged physical paths.
+    puts: BTreeMap<String, String>,
+    deletes: Vec<String>,
+    /// Staged physical paths that are no longer referenced.
+    orphans: Vec<String>,
+}
+
+impl ManifestTransaction {
+    /// The version that this transaction is based on, `0` means empty.
+    pub fn base_version(&self) -> u64 {
+        self.base.version
+    }
+
+    /// Stage the content of given logical path.
+    pub async fn put(&mut self, path: &str, bs: impl Into<Bytes>) -> Result<()> {
+        let physical = format!("{}{}", self.staging, path.trim_start_matches('/'));
+        if let Err(err) = self.log.op.write(&physical, bs).await {
+            // The object could be partially written, remove it in best effort.
+            let _ = self.log.op.delete(&physical).await;
+            return Err(err);
+        }
+        self.orphans.retain(|v| v != &physical);
+        self.puts.insert(path.to_string(), physical);
+        Ok(())
+    }
+
+    /// Remove given logical path from the next manifest.
+    pub fn delete(&mut self, path: &str) {
+        if let Some(physical) = self.puts.remove(path) {
+            self.orphans.push(physical);
+        }
+        self.deletes.push(path.to_string());
+    }
+
+    /// Commit all staged changes as the next manifest.
+    ///
+    /// Returns [`ErrorKind::ConditionNotMatch`] if another transaction has
+    /// committed first, staged objects will be removed in this case.
+    pub async fn commit(self) -> Result<Manifest> {
+        let mut manifest = Manifest {
+            version: self.base.version + 1,
+            entries: self.base.entries.clone(),
+        };
+        for path in &self.deletes {
+            manifest.entries.remove(path);
+        }
+        manifest.entries.extend(self.puts.clone());
+
+        let res = match serde_json::to_vec(&manifest) {
+            Ok(bs) => {
+                self.log
+                    .op
+                    .write_with(&self.log.manifest_path(manifest.version), bs)
+                    .if_none_match("*")
+                    .content_type("application/json")
+                    .await
+            }
+            Err(err) => {
+                Err(Error::new(ErrorKind::Unexpected, "serialize manifest").set_source(err))
+            }
+        };
+
+        match res {
+            Ok(()) => {
+                // Orphans are not referenced by any manifest, it's safe
+                // to leave them behind if removing failed.
+                for path in &self.orphans {
+                    let _ = self.log.op.delete(path).await;
+                }
+                Ok(manifest)
+            }
+            Err(err) => {
+                // Rollback is best effort, the original error is more
+                // important to callers.
+                let _ = self.abort().await;
+                Err(err
+                    .with_operation("ManifestTransaction::commit")
+                    .with_context("version", manifest.version.to_string()))
+            }
+        }
+    }
+
+    /// Abort the transaction and remove all staged objects.
+    pub async fn abort(self) -> Result<()> {
+        self.log.op.remove_all(&self.staging).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::raw::*;
+    use crate::services::Memory;
+
+    /// Emulate `If-None-Match: *` on top of memory for testing, it's not
+    /// atomic but enough for sequential transactions.
+    #[derive(Debug)]
+    struct ConditionalLayer;
+
+    impl<A: Accessor> Layer<A> for ConditionalLayer {
+        type LayeredAccessor = ConditionalAccessor<A>;
+
+        fn layer(&self, inner: A) -> Self::LayeredAccessor {
+            ConditionalAccessor { inner }
+        }
+    }
+
+    #[derive(Debug)]
+    struct ConditionalAccessor<A: Accessor> {
+        inner: A,
+    }
+
+    #[async_trait]
+    impl<A: Accessor> LayeredAccessor for ConditionalAccessor<A> {
+        type Inner = A;
+        type Reader = A::Reader;
+        type BlockingReader = A::BlockingReader;
+        type Writer = A::Writer;
+        type BlockingWriter = A::BlockingWriter;
+        type Lister = A::Lister;
+        type BlockingLister = A::BlockingLister;
+
+        fn inner(&self) -> &Self::Inner {
+            &self.inner
+        }
+
+        fn metadata(&self) -> AccessorInfo {
+            let mut info = self.inner.info();
+            info.full_capability_mut().write_with_if_none_match = true;
+            info
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            self.inner.read(path, args).await
+        }
+
+        async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            if args.if_none_match() == Some("*")
+                && self.inner.stat(path, OpStat::new()).await.is_ok()
+            {
+                return Err(Error::new(
+                    ErrorKind::ConditionNotMatch,
+                    "object already exists",
+                ));
+            }
+            self.inner.write(path, OpWrite::new()).await
+        }
+
+        async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+            self.inner.list(path, args).await
+        }
+
+        fn blocking_read(
+            &self,
+            path: &str,
+            args: OpRead,
+        ) -> Result<(RpRead, Self::BlockingReader)> {
+            self.inner.blocking_read(path, args)
+        }
+
+        fn blocking_write(
+            &self,
+            path: &str,
+            args: OpWrite,
+        ) -> Result<(RpWrite, Self::BlockingWriter)> {
+            self.inner.blocking_write(path, args)
+        }
+
+        fn blocking_list(
+            &self,
+            path: &str,
+            args: OpList,
+        ) -> Result<(RpList, Self::BlockingLister)> {
+            self.inner.blocking_list(path, args)
+        }
+    }
+
+    fn new_operator() -> Result<Operator> {
+        Ok(Operator::new(Memory::default())?
+            .layer(ConditionalLayer)
+            .finish())
+    }
+
+    #[tokio::test]
+    async fn test_commit() -> Result<()> {
+        let op = new_operator()?;
+        let log = ManifestLog::new(op.clone(), "tables/t1");
+
+        let mut txn = log.begin().await?;
+        txn.put("data/part-0", "Hello").await?;
+        txn.put("data/part-1", "World").await?;
+        let manifest = txn.commit().await?;
+        assert_eq!(manifest.version(), 1);
+        assert_eq!(log.read(&manifest, "data/part-0").await?, b"Hello");
+
+        let mut txn = log.begin().await?;
+        assert_eq!(txn.base_version(), 1);
+        txn.put("data/part-2", "!").await?;
+        let orphan = txn.puts["data/part-2"].clone();
+        txn.delete("data/part-2");
+        txn.delete("data/part-0");
+        let manifest = txn.commit().await?;
+        assert_eq!(manifest.version(), 2);
+        assert_eq!(manifest.get("data/part-0"), None);
+        assert_eq!(log.read(&manifest, "data/part-1").await?, b"World");
+        assert!(!op.is_exist(&orphan).await?);
+
+        assert_eq!(log.latest().await?, Some(manifest));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_commit_conflict() -> Result<()> {
+        let op = new_operator()?;
+        let log = ManifestLog::new(op.clone(), "tables/t1");
+
+        let mut a = log.begin().await?;
+        let mut b = log.begin().await?;
+        a.put("data/part-0", "a").await?;
+        b.put("data/part-0", "b").await?;
+        let staged = b.puts["data/part-0"].clone();
+
+        let manifest = a.commit().await?;
+        let err = b.commit().await.expect_err("must conflict");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        assert!(!op.is_exist(&staged).await?);
+
+        assert_eq!(log.latest().await?, Some(manifest.clone()));
+        assert_eq!(log.read(&manifest, "data/part-0").await?, b"a");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_commit_rollback() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        let log = ManifestLog::new(op.clone(), "tables/t1");
+
+        let mut txn = log.begin().await?;
+        assert_eq!(txn.base_version(), 0);
+        txn.put("data/part-0", "Hello").await?;
+        let staged = txn.puts["data/part-0"].clone();
+        assert!(staged.starts_with("tables/t1/_staging/"));
+        assert!(op.is_exist(&staged).await?);
+
+        // Memory doesn't support conditional writes, commit must fail and
+        // roll back staged objects.
+        let err = txn.commit().await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert!(!op.is_exist(&staged).await?);
+        assert_eq!(log.latest().await?, None);
+        Ok(())
+    }
+}
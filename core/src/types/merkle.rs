@@ -0,0 +1,329 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use futures::TryStreamExt;
+use md5::Digest;
+use md5::Md5;
+
+use crate::*;
+
+/// MerkleDiff is the difference between two [`MerkleTree`]s.
+///
+/// Paths are relative to the prefix of trees.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MerkleDiff {
+    /// The file only exists in the other tree.
+    Added(String),
+    /// The file only exists in this tree.
+    Removed(String),
+    /// The file exists in both trees but the content differs.
+    Changed(String),
+}
+
+#[derive(Debug, Clone)]
+enum MerkleNode {
+    File {
+        hash: String,
+    },
+    Dir {
+        hash: String,
+        children: BTreeMap<String, MerkleNode>,
+    },
+}
+
+impl MerkleNode {
+    fn empty_dir() -> Self {
+        MerkleNode::Dir {
+            hash: String::new(),
+            children: BTreeMap::new(),
+        }
+    }
+
+    fn hash(&self) -> &str {
+        match self {
+            MerkleNode::File { hash } => hash,
+            MerkleNode::Dir { hash, .. } => hash,
+        }
+    }
+
+    fn insert(&mut self, path: &str, hash: String) {
+        let MerkleNode::Dir { children, .. } = self else {
+            unreachable!("files must not have children")
+        };
+        match path.split_once('/') {
+            Some((name, rest)) => {
+                let child = children
+                    .entry(name.to_string())
+                    .or_insert_with(MerkleNode::empty_dir);
+                // A file and a dir could share the same name on some
+                // services, the dir wins.
+                if matches!(child, MerkleNode::File { .. }) {
+                    *child = MerkleNode::empty_dir();
+                }
+                child.insert(rest, hash)
+            }
+            None => {
+                children
+                    .entry(path.to_string())
+                    .or_insert(MerkleNode::File { hash });
+            }
+        }
+    }
+
+    /// Calculate hashes of all dirs from bottom up.
+    fn seal(&mut self) {
+        if let MerkleNode::Dir { hash, children } = self {
+            let mut hasher = Md5::new();
+            for (name, child) in children.iter_mut() {
+                child.seal();
+                hasher.update(name.as_bytes());
+                hasher.update(b"\0");
+                hasher.update(child.hash().as_bytes());
+                hasher.update(b"\n");
+            }
+            *hash = format!("{:x}", hasher.finalize());
+        }
+    }
+
+    fn files(&self, path: String, out: &mut Vec<String>) {
+        match self {
+            MerkleNode::File { .. } => out.push(path),
+            MerkleNode::Dir { children, .. } => {
+                for (name, child) in children {
+                    child.files(join_path(&path, name), out);
+                }
+            }
+        }
+    }
+
+    fn diff(&self, other: &MerkleNode, path: String, out: &mut Vec<MerkleDiff>) {
+        if self.hash() == other.hash() {
+            return;
+        }
+
+        match (self, other) {
+            (MerkleNode::File { .. }, MerkleNode::File { .. }) => {
+                out.push(MerkleDiff::Changed(path))
+            }
+            (MerkleNode::Dir { children: a, .. }, MerkleNode::Dir { children: b, .. }) => {
+                for (name, child) in a {
+                    let p = join_path(&path, name);
+                    match b.get(name) {
+                        Some(other) => child.diff(other, p, out),
+                        None => child.collect(p, out, MerkleDiff::Removed),
+                    }
+                }
+                for (name, child) in b {
+                    if !a.contains_key(name) {
+                        child.collect(join_path(&path, name), out, MerkleDiff::Added);
+                    }
+                }
+            }
+            _ => {
+                self.collect(path.clone(), out, MerkleDiff::Removed);
+                other.collect(path, out, MerkleDiff::Added);
+            }
+        }
+    }
+
+    fn collect(&self, path: String, out: &mut Vec<MerkleDiff>, f: fn(String) -> MerkleDiff) {
+        let mut files = vec![];
+        self.files(path, &mut files);
+        out.extend(files.into_iter().map(f));
+    }
+}
+
+/// MerkleTree is the merkle hash over all files under a prefix, which can
+/// be used to verify and diff prefixes after sync or migration.
+///
+/// File hashes are the md5 of content. The checksum from metadata will be
+/// used if available, otherwise the content will be read and hashed. Dir
+/// hashes are calculated from names and hashes of their children, so
+/// identical sub trees can be skipped while diffing.
+///
+/// MerkleTree is designed for verification, not for security.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::MerkleTree;
+/// use opendal::Operator;
+/// use opendal::Result;
+///
+/// async fn test(src: Operator, dst: Operator) -> Result<()> {
+///     let a = MerkleTree::build(&src, "data/").await?;
+///     let b = MerkleTree::build(&dst, "backup/data/").await?;
+///
+///     if a.root_hash() != b.root_hash() {
+///         for diff in a.diff(&b) {
+///             println!("{:?}", diff);
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    root: MerkleNode,
+}
+
+impl MerkleTree {
+    /// Build the merkle tree of all files under given prefix.
+    pub async fn build(op: &Operator, prefix: &str) -> Result<Self> {
+        let mut lister = op
+            .lister_with(prefix)
+            .recursive(true)
+            .metakey(Metakey::Mode | Metakey::ContentMd5)
+            .await?;
+
+        let mut root = MerkleNode::empty_dir();
+        while let Some(entry) = lister.try_next().await? {
+            if !entry.metadata().is_file() {
+                continue;
+            }
+            let path = entry.path();
+            let rel = path.strip_prefix(prefix).unwrap_or(path);
+            if rel.is_empty() {
+                continue;
+            }
+
+            let hash = match entry.metadata().content_md5().and_then(normalize_md5) {
+                Some(v) => v,
+                None => hash_file(op, path).await?,
+            };
+            root.insert(rel, hash);
+        }
+        root.seal();
+
+        Ok(Self { root })
+    }
+
+    /// The hash of the whole tree.
+    pub fn root_hash(&self) -> &str {
+        self.root.hash()
+    }
+
+    /// Diff with the other tree, files only existing in `other` will be
+    /// reported as [`MerkleDiff::Added`].
+    pub fn diff(&self, other: &MerkleTree) -> Vec<MerkleDiff> {
+        let mut out = vec![];
+        self.root.diff(&other.root, String::new(), &mut out);
+        out
+    }
+}
+
+/// Hash the content of file chunk by chunk, so that large files won't be
+/// loaded into memory.
+async fn hash_file(op: &Operator, path: &str) -> Result<String> {
+    let mut stream = op.reader(path).await?.into_stream();
+    let mut hasher = Md5::new();
+    while let Some(bs) = stream.try_next().await? {
+        hasher.update(&bs);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn join_path(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{parent}/{name}")
+    }
+}
+
+/// Services return md5 in hex (s3 etag) or base64 (gcs), normalize them
+/// into lower hex. Values like multipart etags are not md5 and ignored.
+fn normalize_md5(v: &str) -> Option<String> {
+    let v = v.trim_matches('"');
+    if v.len() == 32 && v.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Some(v.to_ascii_lowercase());
+    }
+
+    match BASE64_STANDARD.decode(v) {
+        Ok(bs) if bs.len() == 16 => Some(bs.iter().map(|b| format!("{b:02x}")).collect()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[test]
+    fn test_normalize_md5() {
+        let hex = "65a8e27d8879283831b664bd8b7f0ad4";
+        assert_eq!(normalize_md5(hex).as_deref(), Some(hex));
+        assert_eq!(
+            normalize_md5("ZajifYh5KDgxtmS9i38K1A==").as_deref(),
+            Some(hex)
+        );
+        assert_eq!(normalize_md5("\"abc-2\""), None);
+    }
+
+    #[tokio::test]
+    async fn test_hash_file() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        let content = "Hello, World!".repeat(64 * 1024);
+        op.write("large", content.clone()).await?;
+
+        assert_eq!(
+            hash_file(&op, "large").await?,
+            format!("{:x}", Md5::digest(content.as_bytes()))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_merkle_diff() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        for (path, content) in [
+            ("a/x", "x"),
+            ("a/sub/y", "y"),
+            ("a/sub/z", "z"),
+            ("b/x", "x"),
+            ("b/sub/y", "y"),
+            ("b/sub/z", "z"),
+        ] {
+            op.write(path, content).await?;
+        }
+
+        let a = MerkleTree::build(&op, "a/").await?;
+        let b = MerkleTree::build(&op, "b/").await?;
+        assert_eq!(a.root_hash(), b.root_hash());
+        assert!(a.diff(&b).is_empty());
+
+        op.write("b/sub/y", "changed").await?;
+        op.delete("b/sub/z").await?;
+        op.write("b/w", "w").await?;
+        let b = MerkleTree::build(&op, "b/").await?;
+        assert_ne!(a.root_hash(), b.root_hash());
+        assert_eq!(
+            a.diff(&b),
+            vec![
+                MerkleDiff::Changed("sub/y".to_string()),
+                MerkleDiff::Removed("sub/z".to_string()),
+                MerkleDiff::Added("w".to_string()),
+            ]
+        );
+        Ok(())
+    }
+}
@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+
 use chrono::prelude::*;
 use flagset::flags;
 use flagset::FlagSet;
@@ -45,6 +47,23 @@ pub struct Metadata {
     etag: Option<String>,
     last_modified: Option<DateTime<Utc>>,
     version: Option<String>,
+    is_current: Option<bool>,
+
+    symlink_target: Option<String>,
+    permissions: Option<u32>,
+    uid: Option<u32>,
+    gid: Option<u32>,
+
+    user_metadata: Option<HashMap<String, String>>,
+    tags: Option<HashMap<String, String>>,
+
+    retention: Option<Retention>,
+    legal_hold: Option<bool>,
+    storage_class: Option<String>,
+
+    created: Option<DateTime<Utc>>,
+    region: Option<String>,
+    snapshot: Option<String>,
 }
 
 impl Metadata {
@@ -71,6 +90,23 @@ impl Metadata {
             etag: None,
             content_disposition: None,
             version: None,
+            is_current: None,
+
+            symlink_target: None,
+            permissions: None,
+            uid: None,
+            gid: None,
+
+            user_metadata: None,
+            tags: None,
+
+            retention: None,
+            legal_hold: None,
+            storage_class: None,
+
+            created: None,
+            region: None,
+            snapshot: None,
         }
     }
 
@@ -317,6 +353,9 @@ impl Metadata {
     ///
     /// OpenDAL parse the raw value into [`DateTime`] for convenient.
     ///
+    /// Services backed by local filesystems like `fs` keep the full
+    /// nanosecond precision returned by the OS.
+    ///
     /// # Panics
     ///
     /// This value is only available when calling on result of `stat` or `list` with
@@ -510,6 +549,445 @@ impl Metadata {
         self.metakey |= Metakey::Version;
         self
     }
+
+    /// Whether this entry is the current version of its path, returns `None`
+    /// if it's unknown.
+    ///
+    /// Listing with versions returns an entry for every version of the same
+    /// path, use this to tell which one is current.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::Version`], otherwise it will panic.
+    pub fn is_current(&self) -> Option<bool> {
+        debug_assert!(
+            self.metakey.contains(Metakey::Version) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: is_current, maybe a bug"
+        );
+
+        self.is_current
+    }
+
+    /// Set whether this entry is the current version of its path.
+    pub fn with_is_current(mut self, v: bool) -> Self {
+        self.is_current = Some(v);
+        self.metakey |= Metakey::Version;
+        self
+    }
+
+    /// Set whether this entry is the current version of its path.
+    pub fn set_is_current(&mut self, v: bool) -> &mut Self {
+        self.is_current = Some(v);
+        self.metakey |= Metakey::Version;
+        self
+    }
+
+    /// Fingerprint is a string that identifies the content of this entry,
+    /// which will change once the content changed.
+    ///
+    /// It's built from the first available field in order of `version`,
+    /// `etag`, `content_md5` and `content_length` with `last_modified`.
+    /// Returns `None` if none of them are available.
+    ///
+    /// Use [`OperatorInfo::cache_key`] to build a key that is unique across
+    /// services.
+    pub fn fingerprint(&self) -> Option<String> {
+        if let Some(v) = &self.version {
+            return Some(format!("version:{v}"));
+        }
+        if let Some(v) = &self.etag {
+            return Some(format!("etag:{v}"));
+        }
+        if let Some(v) = &self.content_md5 {
+            return Some(format!("md5:{v}"));
+        }
+        match (self.content_length, self.last_modified) {
+            (Some(size), Some(t)) => Some(format!("size:{size};mtime:{}", t.timestamp_micros())),
+            _ => None,
+        }
+    }
+
+    /// Symlink target of this entry.
+    ///
+    /// Only available when this entry is a symbolic link on services like
+    /// `fs`. The returned target is the raw content of the link and is not
+    /// resolved or normalized by OpenDAL.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::SymlinkTarget`], otherwise it will panic.
+    pub fn symlink_target(&self) -> Option<&str> {
+        debug_assert!(
+            self.metakey.contains(Metakey::SymlinkTarget)
+                || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: symlink_target, maybe a bug"
+        );
+
+        self.symlink_target.as_deref()
+    }
+
+    /// Set symlink target of this entry.
+    pub fn with_symlink_target(mut self, v: String) -> Self {
+        self.symlink_target = Some(v);
+        self.metakey |= Metakey::SymlinkTarget;
+        self
+    }
+
+    /// Set symlink target of this entry.
+    pub fn set_symlink_target(&mut self, v: &str) -> &mut Self {
+        self.symlink_target = Some(v.to_string());
+        self.metakey |= Metakey::SymlinkTarget;
+        self
+    }
+
+    /// Unix permission bits of this entry, like `0o644`.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::Permissions`], otherwise it will panic.
+    pub fn permissions(&self) -> Option<u32> {
+        debug_assert!(
+            self.metakey.contains(Metakey::Permissions) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: permissions, maybe a bug"
+        );
+
+        self.permissions
+    }
+
+    /// Set unix permission bits of this entry.
+    pub fn with_permissions(mut self, v: u32) -> Self {
+        self.permissions = Some(v);
+        self.metakey |= Metakey::Permissions;
+        self
+    }
+
+    /// Set unix permission bits of this entry.
+    pub fn set_permissions(&mut self, v: u32) -> &mut Self {
+        self.permissions = Some(v);
+        self.metakey |= Metakey::Permissions;
+        self
+    }
+
+    /// Numeric user id of the owner of this entry.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::Owner`], otherwise it will panic.
+    pub fn uid(&self) -> Option<u32> {
+        debug_assert!(
+            self.metakey.contains(Metakey::Owner) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: uid, maybe a bug"
+        );
+
+        self.uid
+    }
+
+    /// Set numeric user id of the owner of this entry.
+    pub fn with_uid(mut self, v: u32) -> Self {
+        self.uid = Some(v);
+        self.metakey |= Metakey::Owner;
+        self
+    }
+
+    /// Set numeric user id of the owner of this entry.
+    pub fn set_uid(&mut self, v: u32) -> &mut Self {
+        self.uid = Some(v);
+        self.metakey |= Metakey::Owner;
+        self
+    }
+
+    /// Numeric group id of the owner of this entry.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::Owner`], otherwise it will panic.
+    pub fn gid(&self) -> Option<u32> {
+        debug_assert!(
+            self.metakey.contains(Metakey::Owner) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: gid, maybe a bug"
+        );
+
+        self.gid
+    }
+
+    /// Set numeric group id of the owner of this entry.
+    pub fn with_gid(mut self, v: u32) -> Self {
+        self.gid = Some(v);
+        self.metakey |= Metakey::Owner;
+        self
+    }
+
+    /// Set numeric group id of the owner of this entry.
+    pub fn set_gid(&mut self, v: u32) -> &mut Self {
+        self.gid = Some(v);
+        self.metakey |= Metakey::Owner;
+        self
+    }
+
+    /// User defined metadata of this entry.
+    ///
+    /// User metadata is the custom key-value pairs attached to this entry,
+    /// like `x-amz-meta-*` in AWS S3 or `user.opendal.*` xattrs in fs.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::UserMetadata`], otherwise it will panic.
+    pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
+        debug_assert!(
+            self.metakey.contains(Metakey::UserMetadata)
+                || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: user_metadata, maybe a bug"
+        );
+
+        self.user_metadata.as_ref()
+    }
+
+    /// Set user defined metadata of this entry.
+    pub fn with_user_metadata(mut self, v: HashMap<String, String>) -> Self {
+        self.user_metadata = Some(v);
+        self.metakey |= Metakey::UserMetadata;
+        self
+    }
+
+    /// Set user defined metadata of this entry.
+    pub fn set_user_metadata(&mut self, v: HashMap<String, String>) -> &mut Self {
+        self.user_metadata = Some(v);
+        self.metakey |= Metakey::UserMetadata;
+        self
+    }
+
+    /// Tags of this entry.
+    ///
+    /// Tags are the key-value pairs that can be indexed and filtered by
+    /// service, like blob index tags in Azure Blob.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::Tags`], otherwise it will panic.
+    pub fn tags(&self) -> Option<&HashMap<String, String>> {
+        debug_assert!(
+            self.metakey.contains(Metakey::Tags) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: tags, maybe a bug"
+        );
+
+        self.tags.as_ref()
+    }
+
+    /// Set tags of this entry.
+    pub fn with_tags(mut self, v: HashMap<String, String>) -> Self {
+        self.tags = Some(v);
+        self.metakey |= Metakey::Tags;
+        self
+    }
+
+    /// Set tags of this entry.
+    pub fn set_tags(&mut self, v: HashMap<String, String>) -> &mut Self {
+        self.tags = Some(v);
+        self.metakey |= Metakey::Tags;
+        self
+    }
+
+    /// Retention of this entry.
+    ///
+    /// `None` means no retention is set or the service doesn't support it.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::Retention`], otherwise it will panic.
+    pub fn retention(&self) -> Option<Retention> {
+        debug_assert!(
+            self.metakey.contains(Metakey::Retention) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: retention, maybe a bug"
+        );
+
+        self.retention
+    }
+
+    /// Set retention of this entry.
+    pub fn with_retention(mut self, v: Retention) -> Self {
+        self.retention = Some(v);
+        self.metakey |= Metakey::Retention;
+        self
+    }
+
+    /// Set retention of this entry.
+    pub fn set_retention(&mut self, v: Retention) -> &mut Self {
+        self.retention = Some(v);
+        self.metakey |= Metakey::Retention;
+        self
+    }
+
+    /// Legal hold status of this entry.
+    ///
+    /// Entries under legal hold can't be overwritten or deleted until the
+    /// hold is removed, no matter what retention is.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::LegalHold`], otherwise it will panic.
+    pub fn legal_hold(&self) -> Option<bool> {
+        debug_assert!(
+            self.metakey.contains(Metakey::LegalHold) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: legal_hold, maybe a bug"
+        );
+
+        self.legal_hold
+    }
+
+    /// Set legal hold status of this entry.
+    pub fn with_legal_hold(mut self, v: bool) -> Self {
+        self.legal_hold = Some(v);
+        self.metakey |= Metakey::LegalHold;
+        self
+    }
+
+    /// Set legal hold status of this entry.
+    pub fn set_legal_hold(&mut self, v: bool) -> &mut Self {
+        self.legal_hold = Some(v);
+        self.metakey |= Metakey::LegalHold;
+        self
+    }
+
+    /// Storage class (a.k.a. access tier) of this entry, like `STANDARD_IA`
+    /// of s3 or `Cool` of azblob.
+    ///
+    /// `None` means the service doesn't support storage classes.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::StorageClass`], otherwise it will panic.
+    pub fn storage_class(&self) -> Option<&str> {
+        debug_assert!(
+            self.metakey.contains(Metakey::StorageClass)
+                || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: storage_class, maybe a bug"
+        );
+
+        self.storage_class.as_deref()
+    }
+
+    /// Set storage class of this entry.
+    pub fn with_storage_class(mut self, v: String) -> Self {
+        self.storage_class = Some(v);
+        self.metakey |= Metakey::StorageClass;
+        self
+    }
+
+    /// Set storage class of this entry.
+    pub fn set_storage_class(&mut self, v: &str) -> &mut Self {
+        self.storage_class = Some(v.to_string());
+        self.metakey |= Metakey::StorageClass;
+        self
+    }
+
+    /// Creation time of this entry.
+    ///
+    /// For the root dir, this is the creation time of the bucket or
+    /// container if the service exposes it.
+    ///
+    /// `None` means the service doesn't provide creation time.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::Created`], otherwise it will panic.
+    pub fn created(&self) -> Option<DateTime<Utc>> {
+        debug_assert!(
+            self.metakey.contains(Metakey::Created) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: created, maybe a bug"
+        );
+
+        self.created
+    }
+
+    /// Set creation time of this entry.
+    pub fn with_created(mut self, v: DateTime<Utc>) -> Self {
+        self.created = Some(v);
+        self.metakey |= Metakey::Created;
+        self
+    }
+
+    /// Set creation time of this entry.
+    pub fn set_created(&mut self, v: DateTime<Utc>) -> &mut Self {
+        self.created = Some(v);
+        self.metakey |= Metakey::Created;
+        self
+    }
+
+    /// Region of the bucket or container, only returned while `stat` on the
+    /// root dir.
+    ///
+    /// `None` means the service doesn't have regions or doesn't expose it.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::Region`], otherwise it will panic.
+    pub fn region(&self) -> Option<&str> {
+        debug_assert!(
+            self.metakey.contains(Metakey::Region) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: region, maybe a bug"
+        );
+
+        self.region.as_deref()
+    }
+
+    /// Set region of the bucket or container.
+    pub fn with_region(mut self, v: String) -> Self {
+        self.region = Some(v);
+        self.metakey |= Metakey::Region;
+        self
+    }
+
+    /// Set region of the bucket or container.
+    pub fn set_region(&mut self, v: &str) -> &mut Self {
+        self.region = Some(v.to_string());
+        self.metakey |= Metakey::Region;
+        self
+    }
+
+    /// Snapshot id of this entry, only returned while listing with
+    /// snapshots included. Entries without snapshot id are the base objects.
+    ///
+    /// For azblob, the id is the timestamp returned while creating the
+    /// snapshot, like `2023-12-01T08:00:00.1234567Z`.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::Snapshot`], otherwise it will panic.
+    pub fn snapshot(&self) -> Option<&str> {
+        debug_assert!(
+            self.metakey.contains(Metakey::Snapshot) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: snapshot, maybe a bug"
+        );
+
+        self.snapshot.as_deref()
+    }
+
+    /// Set snapshot id of this entry.
+    pub fn with_snapshot(mut self, v: String) -> Self {
+        self.snapshot = Some(v);
+        self.metakey |= Metakey::Snapshot;
+        self
+    }
+
+    /// Set snapshot id of this entry.
+    pub fn set_snapshot(&mut self, v: &str) -> &mut Self {
+        self.snapshot = Some(v.to_string());
+        self.metakey |= Metakey::Snapshot;
+        self
+    }
 }
 
 flags! {
@@ -551,5 +1029,27 @@ flags! {
         LastModified,
         /// Key for version.
         Version,
+        /// Key for symlink target.
+        SymlinkTarget,
+        /// Key for unix permissions.
+        Permissions,
+        /// Key for owner, including both uid and gid.
+        Owner,
+        /// Key for user defined metadata.
+        UserMetadata,
+        /// Key for retention.
+        Retention,
+        /// Key for legal hold.
+        LegalHold,
+        /// Key for storage class.
+        StorageClass,
+        /// Key for creation time.
+        Created,
+        /// Key for region.
+        Region,
+        /// Key for snapshot.
+        Snapshot,
+        /// Key for tags.
+        Tags,
     }
 }
@@ -28,15 +28,123 @@ pub use metadata::Metakey;
 mod reader;
 pub use reader::BlockingReader;
 pub use reader::Reader;
+pub use reader::ReaderStream;
+
+#[cfg(feature = "digest")]
+mod digest;
+#[cfg(feature = "digest")]
+pub use digest::ContentDigest;
+#[cfg(feature = "digest")]
+pub use digest::DigestAlgorithm;
+#[cfg(feature = "digest")]
+pub(crate) use digest::DigestHandle;
+#[cfg(feature = "digest")]
+pub(crate) use digest::DigestWriter;
 
 mod writer;
 pub use writer::BlockingWriter;
 pub use writer::Writer;
+pub use writer::WriterCancelPolicy;
+pub use writer::WriterSink;
 
 mod list;
 pub use list::BlockingLister;
+pub use list::ListBackpressure;
+pub use list::ListSummary;
 pub use list::Lister;
 
+#[cfg(feature = "arrow")]
+mod arrow;
+
+#[cfg(feature = "parquet")]
+mod parquet;
+#[cfg(feature = "parquet")]
+pub use self::parquet::ParquetReader;
+#[cfg(feature = "parquet")]
+pub use self::parquet::ParquetRowGroup;
+
+#[cfg(feature = "object-store")]
+mod object_store;
+#[cfg(feature = "object-store")]
+pub use self::object_store::OpendalStore;
+
+#[cfg(feature = "cas")]
+mod cas;
+#[cfg(feature = "cas")]
+pub use cas::Cas;
+#[cfg(feature = "cas")]
+pub use cas::CasDigest;
+
+#[cfg(feature = "inventory")]
+mod inventory;
+#[cfg(feature = "inventory")]
+pub use inventory::Inventory;
+#[cfg(feature = "inventory")]
+pub use inventory::InventoryFormat;
+
+mod form;
+pub use form::FormUpload;
+pub use form::UploadedFile;
+pub use form::UploadedForm;
+
+mod journal;
+pub use journal::FlushPolicy;
+pub use journal::Journal;
+pub use journal::JournalPosition;
+pub use journal::JournalReader;
+pub use journal::JournalWriter;
+
+mod manifest;
+pub use manifest::Manifest;
+pub use manifest::ManifestLog;
+pub use manifest::ManifestTransaction;
+
+mod merkle;
+pub use merkle::MerkleDiff;
+pub use merkle::MerkleTree;
+
+mod buffer_pool;
+pub use buffer_pool::BufferPermit;
+pub use buffer_pool::BufferPool;
+
+mod retention;
+pub use retention::Retention;
+pub use retention::RetentionMode;
+
+mod expiry;
+pub use expiry::ExpiryManifest;
+
+mod union;
+pub use union::UnionOperator;
+
+mod credential;
+pub use credential::Credential;
+
+mod delegate;
+pub use delegate::DelegateScope;
+pub use delegate::DelegatedCredential;
+
+mod batch;
+pub use batch::BatchEntry;
+pub use batch::BatchResults;
+
+mod stats;
+pub use stats::CostStats;
+pub use stats::OperationStats;
+pub use stats::OperatorStats;
+pub use stats::RequestClass;
+pub(crate) use stats::StatsCollector;
+
+mod tiering;
+pub use tiering::TierMover;
+pub use tiering::TierReport;
+pub use tiering::TierRule;
+pub use tiering::TierTransition;
+pub(crate) use tiering::ORIGINAL_LAST_MODIFIED;
+
+mod path_policy;
+pub use path_policy::PathPolicy;
+
 mod operator;
 pub use operator::operator_functions;
 pub use operator::operator_futures;
@@ -56,5 +164,15 @@ pub use error::Result;
 mod scheme;
 pub use scheme::Scheme;
 
+mod emulator;
+pub use emulator::Emulator;
+
+mod secret;
+pub use secret::Secret;
+
+mod config_error;
+pub use config_error::ConfigError;
+pub use config_error::ConfigProblem;
+
 mod capability;
 pub use capability::Capability;
@@ -0,0 +1,394 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::ops::Range;
+
+use ::object_store::path::Path;
+use ::object_store::GetOptions;
+use ::object_store::GetResult;
+use ::object_store::ListResult;
+use ::object_store::MultipartId;
+use ::object_store::ObjectMeta;
+use ::object_store::ObjectStore;
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use tokio::io::AsyncWrite;
+
+use crate::*;
+
+/// OpendalStore implements [`ObjectStore`] on top of an [`Operator`].
+///
+/// So that users of `object_store` like `arrow`, `datafusion` and `delta-rs`
+/// can use all services supported by OpenDAL directly.
+///
+/// # Notes
+///
+/// - `put_multipart` is backed by [`Writer`], which can't be aborted via
+///   `abort_multipart` later, drop the returned writer without `shutdown`
+///   instead.
+/// - `get_opts` only supports the `range` option.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use object_store::ObjectStore;
+/// use opendal::services;
+/// use opendal::OpendalStore;
+/// use opendal::Operator;
+///
+/// let op = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .finish();
+/// let _: Arc<dyn ObjectStore> = Arc::new(OpendalStore::new(op));
+/// ```
+#[derive(Clone)]
+pub struct OpendalStore {
+    inner: Operator,
+}
+
+impl OpendalStore {
+    /// Create a new store on top of given operator.
+    pub fn new(op: Operator) -> Self {
+        Self { inner: op }
+    }
+
+    /// Get the inner operator.
+    pub fn operator(&self) -> &Operator {
+        &self.inner
+    }
+}
+
+impl Debug for OpendalStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpendalStore")
+            .field("info", &self.inner.info())
+            .finish()
+    }
+}
+
+impl Display for OpendalStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let info = self.inner.info();
+        write!(
+            f,
+            "OpenDAL({}, bucket={}, root={})",
+            info.scheme(),
+            info.name(),
+            info.root()
+        )
+    }
+}
+
+#[async_trait]
+impl ObjectStore for OpendalStore {
+    async fn put(&self, location: &Path, bytes: Bytes) -> ::object_store::Result<()> {
+        self.inner
+            .write(location.as_ref(), bytes)
+            .await
+            .map_err(|err| format_object_store_error(err, location.as_ref()))
+    }
+
+    async fn put_multipart(
+        &self,
+        location: &Path,
+    ) -> ::object_store::Result<(MultipartId, Box<dyn AsyncWrite + Unpin + Send>)> {
+        let w = self
+            .inner
+            .writer(location.as_ref())
+            .await
+            .map_err(|err| format_object_store_error(err, location.as_ref()))?;
+
+        Ok((MultipartId::default(), Box::new(w)))
+    }
+
+    async fn abort_multipart(
+        &self,
+        location: &Path,
+        _: &MultipartId,
+    ) -> ::object_store::Result<()> {
+        Err(format_object_store_error(
+            Error::new(
+                ErrorKind::Unsupported,
+                "abort_multipart is not supported, drop the writer instead",
+            ),
+            location.as_ref(),
+        ))
+    }
+
+    async fn get(&self, location: &Path) -> ::object_store::Result<GetResult> {
+        let r = self
+            .inner
+            .reader(location.as_ref())
+            .await
+            .map_err(|err| format_object_store_error(err, location.as_ref()))?;
+
+        Ok(GetResult::Stream(r.map_err(format_io_error).boxed()))
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> ::object_store::Result<GetResult> {
+        let GetOptions {
+            if_match,
+            if_none_match,
+            if_modified_since,
+            if_unmodified_since,
+            range,
+        } = options;
+        if if_match.is_some()
+            || if_none_match.is_some()
+            || if_modified_since.is_some()
+            || if_unmodified_since.is_some()
+        {
+            return Err(format_object_store_error(
+                Error::new(
+                    ErrorKind::Unsupported,
+                    "get_opts with conditions is not supported",
+                ),
+                location.as_ref(),
+            ));
+        }
+
+        let range = match range {
+            Some(range) => range,
+            None => return self.get(location).await,
+        };
+
+        let r = self
+            .inner
+            .reader_with(location.as_ref())
+            .range(range.start as u64..range.end as u64)
+            .await
+            .map_err(|err| format_object_store_error(err, location.as_ref()))?;
+
+        Ok(GetResult::Stream(r.map_err(format_io_error).boxed()))
+    }
+
+    async fn get_range(
+        &self,
+        location: &Path,
+        range: Range<usize>,
+    ) -> ::object_store::Result<Bytes> {
+        let bs = self
+            .inner
+            .read_with(location.as_ref())
+            .range(range.start as u64..range.end as u64)
+            .await
+            .map_err(|err| format_object_store_error(err, location.as_ref()))?;
+
+        Ok(Bytes::from(bs))
+    }
+
+    async fn head(&self, location: &Path) -> ::object_store::Result<ObjectMeta> {
+        let meta = self
+            .inner
+            .stat(location.as_ref())
+            .await
+            .map_err(|err| format_object_store_error(err, location.as_ref()))?;
+
+        Ok(format_object_meta(location.as_ref(), &meta))
+    }
+
+    async fn delete(&self, location: &Path) -> ::object_store::Result<()> {
+        self.inner
+            .delete(location.as_ref())
+            .await
+            .map_err(|err| format_object_store_error(err, location.as_ref()))
+    }
+
+    async fn list(
+        &self,
+        prefix: Option<&Path>,
+    ) -> ::object_store::Result<BoxStream<'_, ::object_store::Result<ObjectMeta>>> {
+        let path = format_dir_path(prefix);
+
+        let lister = self
+            .inner
+            .lister_with(&path)
+            .recursive(true)
+            .metakey(Metakey::ContentLength | Metakey::LastModified | Metakey::Etag)
+            .await
+            .map_err(|err| format_object_store_error(err, &path))?;
+
+        let stream = lister
+            .try_filter(|entry| futures::future::ready(entry.metadata().is_file()))
+            .map(move |res| {
+                res.map(|entry| format_object_meta(entry.path(), entry.metadata()))
+                    .map_err(|err| format_object_store_error(err, &path))
+            });
+
+        Ok(stream.boxed())
+    }
+
+    async fn list_with_delimiter(
+        &self,
+        prefix: Option<&Path>,
+    ) -> ::object_store::Result<ListResult> {
+        let path = format_dir_path(prefix);
+
+        let entries = self
+            .inner
+            .list_with(&path)
+            .metakey(Metakey::ContentLength | Metakey::LastModified | Metakey::Etag)
+            .await
+            .map_err(|err| format_object_store_error(err, &path))?;
+
+        let mut common_prefixes = Vec::new();
+        let mut objects = Vec::new();
+        for entry in entries {
+            let meta = entry.metadata();
+            if meta.is_dir() {
+                // Skip the dir itself which could be returned by some services.
+                if entry.path() != path {
+                    common_prefixes.push(Path::from(entry.path()));
+                }
+            } else {
+                objects.push(format_object_meta(entry.path(), meta));
+            }
+        }
+
+        Ok(ListResult {
+            common_prefixes,
+            objects,
+        })
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> ::object_store::Result<()> {
+        self.inner
+            .copy(from.as_ref(), to.as_ref())
+            .await
+            .map_err(|err| format_object_store_error(err, from.as_ref()))
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> ::object_store::Result<()> {
+        self.inner
+            .rename(from.as_ref(), to.as_ref())
+            .await
+            .map_err(|err| format_object_store_error(err, from.as_ref()))
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, _: &Path) -> ::object_store::Result<()> {
+        Err(format_object_store_error(
+            Error::new(
+                ErrorKind::Unsupported,
+                "copy_if_not_exists is not supported",
+            ),
+            from.as_ref(),
+        ))
+    }
+}
+
+/// Build the dir path used by OpenDAL from object_store's prefix.
+fn format_dir_path(prefix: Option<&Path>) -> String {
+    match prefix {
+        None => "/".to_string(),
+        Some(p) if p.as_ref().is_empty() => "/".to_string(),
+        Some(p) => format!("{}/", p.as_ref()),
+    }
+}
+
+fn format_object_meta(path: &str, meta: &Metadata) -> ObjectMeta {
+    ObjectMeta {
+        location: Path::from(path),
+        last_modified: meta.last_modified().unwrap_or_default(),
+        size: meta.content_length() as usize,
+        e_tag: meta.etag().map(|v| v.to_string()),
+    }
+}
+
+fn format_object_store_error(err: Error, path: &str) -> ::object_store::Error {
+    match err.kind() {
+        ErrorKind::NotFound => ::object_store::Error::NotFound {
+            path: path.to_string(),
+            source: Box::new(err),
+        },
+        ErrorKind::AlreadyExists => ::object_store::Error::AlreadyExists {
+            path: path.to_string(),
+            source: Box::new(err),
+        },
+        ErrorKind::ConditionNotMatch => ::object_store::Error::Precondition {
+            path: path.to_string(),
+            source: Box::new(err),
+        },
+        ErrorKind::Unsupported => ::object_store::Error::NotSupported {
+            source: Box::new(err),
+        },
+        kind => ::object_store::Error::Generic {
+            store: kind.into_static(),
+            source: Box::new(err),
+        },
+    }
+}
+
+fn format_io_error(err: std::io::Error) -> ::object_store::Error {
+    ::object_store::Error::Generic {
+        store: "opendal",
+        source: Box::new(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_opendal_store() -> ::object_store::Result<()> {
+        let op = Operator::new(Memory::default())
+            .expect("must init")
+            .finish();
+        let store = OpendalStore::new(op);
+
+        let path = Path::from("data/test.txt");
+        store
+            .put(&path, Bytes::from_static(b"Hello, World!"))
+            .await?;
+
+        let meta = store.head(&path).await?;
+        assert_eq!(meta.size, 13);
+
+        let bs = store.get_range(&path, 0..5).await?;
+        assert_eq!(bs.as_ref(), b"Hello");
+
+        let bs = store.get(&path).await?.bytes().await?;
+        assert_eq!(bs.as_ref(), b"Hello, World!");
+
+        let objects: Vec<_> = store.list(None).await?.try_collect().await?;
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].location, path);
+
+        let res = store.list_with_delimiter(None).await?;
+        assert_eq!(res.common_prefixes, vec![Path::from("data")]);
+
+        store.delete(&path).await?;
+        assert!(matches!(
+            store.head(&path).await,
+            Err(::object_store::Error::NotFound { .. })
+        ));
+        Ok(())
+    }
+}
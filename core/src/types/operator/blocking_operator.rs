@@ -14,9 +14,11 @@
 // KIND, either express or implied.  See the License for the
 // specific language governing permissions and limitations
 // under the License.
+use std::sync::Arc;
 
 use bytes::Bytes;
 
+use super::misuse::*;
 use super::operator_functions::*;
 use crate::raw::oio::BlockingRead;
 use crate::raw::oio::WriteBuf;
@@ -87,6 +89,8 @@ pub struct BlockingOperator {
     accessor: FusedAccessor,
 
     limit: usize,
+
+    policy: Option<Arc<PathPolicy>>,
 }
 
 impl BlockingOperator {
@@ -104,7 +108,11 @@ impl BlockingOperator {
             .full_capability()
             .batch_max_operations
             .unwrap_or(1000);
-        Self { accessor, limit }
+        Self {
+            accessor,
+            limit,
+            policy: None,
+        }
     }
 
     /// Get current operator's limit
@@ -121,6 +129,28 @@ impl BlockingOperator {
         op
     }
 
+    /// Get current operator's path policy.
+    pub fn path_policy(&self) -> Option<&PathPolicy> {
+        self.policy.as_deref()
+    }
+
+    /// Specify the path policy that all input paths must pass.
+    ///
+    /// Default: no policy
+    pub fn with_path_policy(&self, policy: PathPolicy) -> Self {
+        let mut op = self.clone();
+        op.policy = Some(Arc::new(policy));
+        op
+    }
+
+    /// Check input path against path policy and normalize it.
+    fn normalize(&self, path: &str) -> Result<String> {
+        match &self.policy {
+            Some(policy) => Ok(normalize_path(&policy.check(path)?)),
+            None => Ok(normalize_path(path)),
+        }
+    }
+
     /// Get information of underlying accessor.
     ///
     /// # Examples
@@ -138,6 +168,24 @@ impl BlockingOperator {
     pub fn info(&self) -> OperatorInfo {
         OperatorInfo::new(self.accessor.info())
     }
+
+    /// Get a snapshot of the I/O statistics of this operator.
+    ///
+    /// Read [`Operator::stats`] for more details.
+    pub fn stats(&self) -> OperatorStats {
+        self.accessor
+            .info()
+            .stats()
+            .map(|v| v.snapshot())
+            .unwrap_or_default()
+    }
+
+    /// Reset the I/O statistics of this operator.
+    pub fn reset_stats(&self) {
+        if let Some(stats) = self.accessor.info().stats() {
+            stats.reset()
+        }
+    }
 }
 
 /// # Operator blocking API.
@@ -262,7 +310,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn stat_with(&self, path: &str) -> FunctionStat {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         FunctionStat(OperatorFunction::new(
             self.inner().clone(),
@@ -326,7 +374,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn create_dir(&self, path: &str) -> Result<()> {
-        let path = normalize_path(path);
+        let path = self.normalize(path)?;
 
         if !validate_path(&path, EntryMode::DIR) {
             return Err(Error::new(
@@ -382,7 +430,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn read_with(&self, path: &str) -> FunctionRead {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         FunctionRead(OperatorFunction::new(
             self.inner().clone(),
@@ -398,23 +446,32 @@ impl BlockingOperator {
                     );
                 }
 
+                let signer = args.signer().cloned();
                 let range = args.range();
                 let size_hint = match range.size() {
                     Some(v) => v,
                     None => {
-                        let mut size = inner
-                            .blocking_stat(&path, OpStat::default())?
-                            .into_metadata()
-                            .content_length();
-                        size -= range.offset().unwrap_or(0);
-                        size
+                        let meta = inner
+                            .blocking_stat(&path, args.to_stat())
+                            .map_err(|err| {
+                                blocking_check_read_error(&inner, &path, signer.as_ref(), err)
+                            })?
+                            .into_metadata();
+                        if meta.is_dir() {
+                            return Err(new_is_a_directory_error(&inner, &path));
+                        }
+                        meta.content_length() - range.offset().unwrap_or(0)
                     }
                 };
 
-                let (_, mut s) = inner.blocking_read(&path, args)?;
+                let (_, mut s) = inner.blocking_read(&path, args).map_err(|err| {
+                    blocking_check_read_error(&inner, &path, signer.as_ref(), err)
+                })?;
 
                 let mut buf = Vec::with_capacity(size_hint as usize);
-                s.read_to_end(&mut buf)?;
+                s.read_to_end(&mut buf).map_err(|err| {
+                    blocking_check_read_error(&inner, &path, signer.as_ref(), err)
+                })?;
 
                 Ok(buf)
             },
@@ -453,7 +510,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn reader_with(&self, path: &str) -> FunctionReader {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         FunctionReader(OperatorFunction::new(
             self.inner().clone(),
@@ -469,7 +526,9 @@ impl BlockingOperator {
                     );
                 }
 
+                let signer = args.signer().cloned();
                 BlockingReader::create(inner.clone(), &path, args)
+                    .map_err(|err| blocking_check_read_error(&inner, &path, signer.as_ref(), err))
             },
         ))
     }
@@ -519,7 +578,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn copy(&self, from: &str, to: &str) -> Result<()> {
-        let from = normalize_path(from);
+        let from = self.normalize(from)?;
 
         if !validate_path(&from, EntryMode::FILE) {
             return Err(
@@ -530,7 +589,7 @@ impl BlockingOperator {
             );
         }
 
-        let to = normalize_path(to);
+        let to = self.normalize(to)?;
 
         if !validate_path(&to, EntryMode::FILE) {
             return Err(
@@ -576,7 +635,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn rename(&self, from: &str, to: &str) -> Result<()> {
-        let from = normalize_path(from);
+        let from = self.normalize(from)?;
 
         if !validate_path(&from, EntryMode::FILE) {
             return Err(
@@ -587,7 +646,7 @@ impl BlockingOperator {
             );
         }
 
-        let to = normalize_path(to);
+        let to = self.normalize(to)?;
 
         if !validate_path(&to, EntryMode::FILE) {
             return Err(
@@ -613,6 +672,67 @@ impl BlockingOperator {
         Ok(())
     }
 
+    /// Create a symlink at `path` which points to `target`.
+    ///
+    /// # Notes
+    ///
+    /// - `path` must be a file path.
+    /// - `target` is stored as is, it will not be normalized or checked.
+    /// - Require [`Capability::create_symlink`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::BlockingOperator;
+    ///
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// op.create_symlink("path/to/file", "path/to/link")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_symlink(&self, target: &str, path: &str) -> Result<()> {
+        let path = self.normalize(path)?;
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(Error::new(ErrorKind::IsADirectory, "path is a directory")
+                .with_operation("BlockingOperator::create_symlink")
+                .with_context("service", self.info().scheme())
+                .with_context("path", path));
+        }
+
+        self.inner()
+            .blocking_create_symlink(target, &path, OpCreateSymlink::new())?;
+
+        Ok(())
+    }
+
+    /// Set unix permission bits like `0o644` of given path.
+    ///
+    /// # Notes
+    ///
+    /// - Require [`Capability::set_permissions`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::BlockingOperator;
+    ///
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// op.set_permissions("path/to/file", 0o644)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_permissions(&self, path: &str, permissions: u32) -> Result<()> {
+        let path = self.normalize(path)?;
+
+        self.inner()
+            .blocking_set_permissions(&path, OpSetPermissions::new(permissions))?;
+
+        Ok(())
+    }
+
     /// Write data with option described in OpenDAL [RFC-0661][`crate::docs::rfcs::rfc_0661_path_in_accessor`]
     ///
     /// # Notes
@@ -636,7 +756,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn write_with(&self, path: &str, bs: impl Into<Bytes>) -> FunctionWrite {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let bs = bs.into();
 
@@ -711,7 +831,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn writer_with(&self, path: &str) -> FunctionWriter {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         FunctionWriter(OperatorFunction::new(
             self.inner().clone(),
@@ -778,7 +898,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn delete_with(&self, path: &str) -> FunctionDelete {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         FunctionDelete(OperatorFunction::new(
             self.inner().clone(),
@@ -870,6 +990,22 @@ impl BlockingOperator {
             return self.delete(path);
         }
 
+        // Let underlying layers handle the whole dir if they can.
+        if self.info().full_capability().delete_with_recursive {
+            return self.delete_with(path).recursive(true).call();
+        }
+
+        // Push down to services that can delete by prefix server side.
+        if self.info().full_capability().delete_with_prefix {
+            // Make sure we won't remove siblings like `abc_def` for `abc`.
+            let prefix = if path.ends_with('/') {
+                path.to_string()
+            } else {
+                format!("{path}/")
+            };
+            return self.delete_with(&prefix).prefix(true).call();
+        }
+
         let obs = self.lister_with(path).recursive(true).call()?;
 
         for v in obs {
@@ -1017,7 +1153,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn list_with(&self, path: &str) -> FunctionList {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         FunctionList(OperatorFunction::new(
             self.inner().clone(),
@@ -1034,9 +1170,17 @@ impl BlockingOperator {
                     .with_context("path", &path));
                 }
 
-                let lister = BlockingLister::create(inner, &path, args)?;
+                let res: Result<Vec<Entry>> =
+                    BlockingLister::create(inner.clone(), &path, args).and_then(|l| l.collect());
 
-                lister.collect()
+                match res {
+                    Ok(entries) if entries.is_empty() => {
+                        blocking_check_list_file(&inner, &path)?;
+                        Ok(entries)
+                    }
+                    Ok(entries) => Ok(entries),
+                    Err(err) => Err(blocking_check_list_error(&inner, &path, err)),
+                }
             },
         ))
     }
@@ -1191,7 +1335,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn lister_with(&self, path: &str) -> FunctionLister {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         FunctionLister(OperatorFunction::new(
             self.inner().clone(),
@@ -1208,7 +1352,8 @@ impl BlockingOperator {
                     .with_context("path", &path));
                 }
 
-                BlockingLister::create(inner, &path, args)
+                BlockingLister::create(inner.clone(), &path, args)
+                    .map_err(|err| blocking_check_list_error(&inner, &path, err))
             },
         ))
     }
@@ -1216,6 +1361,10 @@ impl BlockingOperator {
 
 impl From<BlockingOperator> for Operator {
     fn from(v: BlockingOperator) -> Self {
-        Operator::from_inner(v.accessor).with_limit(v.limit)
+        let op = Operator::from_inner(v.accessor).with_limit(v.limit);
+        match v.policy {
+            Some(policy) => op.with_path_policy(policy.as_ref().clone()),
+            None => op,
+        }
     }
 }
@@ -80,6 +80,32 @@ impl Operator {
         Ok(OperatorBuilder::new(acc))
     }
 
+    /// Create a new operator from an [`Accessor`] implemented outside of
+    /// OpenDAL.
+    ///
+    /// This is the entry for out-of-tree services: implement
+    /// [`Accessor`][crate::raw::Accessor] with [`Scheme::Custom`] in its
+    /// [`AccessorInfo`][crate::raw::AccessorInfo], and OpenDAL will wrap it
+    /// with the same required layers as built-in services.
+    ///
+    /// Implementations can be verified with
+    /// [`run_conformance_tests`][crate::raw::tests::run_conformance_tests]
+    /// under the `tests` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opendal::raw::Accessor;
+    /// use opendal::Operator;
+    ///
+    /// fn build(acc: impl Accessor) -> Operator {
+    ///     Operator::from_accessor(acc)
+    /// }
+    /// ```
+    pub fn from_accessor<A: Accessor>(acc: A) -> Operator {
+        OperatorBuilder::new(acc).finish()
+    }
+
     /// Create a new operator from given map.
     ///
     /// # Notes
@@ -211,6 +237,8 @@ impl Operator {
             Scheme::Mysql => Self::from_map::<services::Mysql>(map)?.finish(),
             #[cfg(feature = "services-obs")]
             Scheme::Obs => Self::from_map::<services::Obs>(map)?.finish(),
+            #[cfg(feature = "services-oci")]
+            Scheme::Oci => Self::from_map::<services::Oci>(map)?.finish(),
             #[cfg(feature = "services-onedrive")]
             Scheme::Onedrive => Self::from_map::<services::Onedrive>(map)?.finish(),
             #[cfg(feature = "services-postgresql")]
@@ -354,6 +382,7 @@ impl<A: Accessor> OperatorBuilder<A> {
         OperatorBuilder { accessor }
             .layer(ErrorContextLayer)
             .layer(CompleteLayer)
+            .layer(StatsLayer)
     }
 
     /// Create a new layer with static dispatch.
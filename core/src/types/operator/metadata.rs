@@ -15,6 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use md5::Digest;
+use md5::Md5;
+
 use crate::raw::*;
 use crate::*;
 
@@ -47,6 +50,41 @@ impl OperatorInfo {
         self.0.name()
     }
 
+    /// Endpoint that requests are sent to, returns `None` if the backend
+    /// doesn't send requests to an endpoint.
+    pub fn endpoint(&self) -> Option<&str> {
+        self.0.endpoint()
+    }
+
+    /// Location of the data at rest, like the region of bucket, returns
+    /// `None` if it's unknown.
+    ///
+    /// Applications enforcing data residency rules can assert the location
+    /// at startup:
+    ///
+    /// ```
+    /// use opendal::Operator;
+    /// use opendal::Result;
+    ///
+    /// fn check_residency(op: &Operator) -> Result<()> {
+    ///     let info = op.info();
+    ///     assert!(matches!(info.location(), Some(v) if v.starts_with("eu-")));
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// Use [`Operator::location`] to query the location from service.
+    ///
+    /// The location is reported by service. For example, `s3` reports the
+    /// region that requests are signed for, which must match the region of
+    /// bucket for requests to succeed. S3 compatible services might use a
+    /// placeholder region like `us-east-1` or `auto` instead.
+    pub fn location(&self) -> Option<&str> {
+        self.0.location()
+    }
+
     /// Get [`Full Capability`] of operator.
     pub fn full_capability(&self) -> Capability {
         self.0.full_capability()
@@ -56,4 +94,69 @@ impl OperatorInfo {
     pub fn native_capability(&self) -> Capability {
         self.0.native_capability()
     }
+
+    /// Get the request signer of underlying service, returns `None` if the
+    /// service doesn't support signing.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. [`RequestSigner`] could be
+    /// changed during minor updates.
+    pub fn signer(&self) -> Option<RequestSigner> {
+        self.0.signer()
+    }
+
+    /// Build a stable cache key for the content of path described by given
+    /// metadata.
+    ///
+    /// The key is the hex encoded md5 of scheme, name, root, path and
+    /// [`Metadata::fingerprint`], so caches in different processes will
+    /// agree on the same key, and the key is evenly distributed for
+    /// consistent hashing.
+    ///
+    /// Returns `None` if the metadata doesn't have a fingerprint.
+    pub fn cache_key(&self, path: &str, meta: &Metadata) -> Option<String> {
+        let fingerprint = meta.fingerprint()?;
+        let path = normalize_path(path);
+
+        let mut hasher = Md5::new();
+        for part in [
+            self.scheme().into_static(),
+            self.name(),
+            self.root(),
+            path.as_str(),
+            fingerprint.as_str(),
+        ] {
+            hasher.update(part.as_bytes());
+            // Separate parts so that `ab` + `c` differs from `a` + `bc`.
+            hasher.update([0]);
+        }
+        Some(format!("{:x}", hasher.finalize()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[test]
+    fn test_cache_key() -> Result<()> {
+        let info = Operator::new(Memory::default())?.finish().info();
+        let meta = Metadata::new(EntryMode::FILE).with_etag("\"abc\"".to_string());
+
+        let key = info
+            .cache_key("path/to/file", &meta)
+            .expect("must have key");
+        assert_eq!(key.len(), 32);
+        assert_eq!(info.cache_key("/path/to/file", &meta), Some(key.clone()));
+
+        let changed = Metadata::new(EntryMode::FILE).with_etag("\"def\"".to_string());
+        assert_ne!(info.cache_key("path/to/file", &changed), Some(key));
+        assert_eq!(
+            info.cache_key("path/to/file", &Metadata::new(EntryMode::FILE)),
+            None
+        );
+        Ok(())
+    }
 }
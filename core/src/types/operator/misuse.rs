@@ -0,0 +1,146 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Services report misusing a dir as a file (or the reverse) differently,
+//! like `NotFound` for object storages and `Unexpected` for fs. Helpers in
+//! this mod stat the path again after operations failed, so that users
+//! get [`ErrorKind::IsADirectory`] or [`ErrorKind::NotADirectory`].
+//!
+//! The extra stat is only sent while `path_misuse_check` is enabled in
+//! capability, see [`crate::layers::EmulateLayer::with_path_misuse`].
+
+use crate::raw::*;
+use crate::*;
+
+/// Only these kinds could be caused by misusing paths.
+fn maybe_misused(err: &Error) -> bool {
+    matches!(err.kind(), ErrorKind::NotFound | ErrorKind::Unexpected)
+}
+
+/// Returns true if users asked to check misused paths.
+fn check_enabled(acc: &FusedAccessor) -> bool {
+    acc.info().full_capability().path_misuse_check
+}
+
+pub(super) fn new_is_a_directory_error(acc: &FusedAccessor, path: &str) -> Error {
+    Error::new(ErrorKind::IsADirectory, "read path is a directory")
+        .with_operation(Operation::Read)
+        .with_context("service", acc.info().scheme().into_static())
+        .with_context("path", path)
+}
+
+fn new_not_a_directory_error(acc: &FusedAccessor, path: &str) -> Error {
+    Error::new(ErrorKind::NotADirectory, "list path is a file")
+        .with_operation(Operation::List)
+        .with_context("service", acc.info().scheme().into_static())
+        .with_context("path", path)
+}
+
+/// Build the args to stat the dir of a failed read, requests must be signed
+/// by the same signer as read.
+fn read_dir_stat(signer: Option<&RequestSigner>) -> OpStat {
+    match signer {
+        Some(signer) => OpStat::new().with_signer(signer.clone()),
+        None => OpStat::new(),
+    }
+}
+
+/// Convert the error of reading path into `IsADirectory` if path is a dir.
+pub(super) async fn check_read_error(
+    acc: &FusedAccessor,
+    path: &str,
+    signer: Option<&RequestSigner>,
+    err: Error,
+) -> Error {
+    if !maybe_misused(&err) || !check_enabled(acc) {
+        return err;
+    }
+
+    match acc.stat(&format!("{path}/"), read_dir_stat(signer)).await {
+        Ok(rp) if rp.into_metadata().is_dir() => new_is_a_directory_error(acc, path),
+        _ => err,
+    }
+}
+
+/// The blocking version of [`check_read_error`].
+pub(super) fn blocking_check_read_error(
+    acc: &FusedAccessor,
+    path: &str,
+    signer: Option<&RequestSigner>,
+    err: Error,
+) -> Error {
+    if !maybe_misused(&err) || !check_enabled(acc) {
+        return err;
+    }
+
+    match acc.blocking_stat(&format!("{path}/"), read_dir_stat(signer)) {
+        Ok(rp) if rp.into_metadata().is_dir() => new_is_a_directory_error(acc, path),
+        _ => err,
+    }
+}
+
+/// Returns `NotADirectory` if the dir to list is a file.
+///
+/// Should be called while listing failed or returned nothing.
+pub(super) async fn check_list_file(acc: &FusedAccessor, path: &str) -> Result<()> {
+    let file = path.trim_end_matches('/');
+    if file.is_empty() || !check_enabled(acc) {
+        return Ok(());
+    }
+
+    match acc.stat(file, OpStat::new()).await {
+        Ok(rp) if rp.into_metadata().is_file() => Err(new_not_a_directory_error(acc, path)),
+        _ => Ok(()),
+    }
+}
+
+/// The blocking version of [`check_list_file`].
+pub(super) fn blocking_check_list_file(acc: &FusedAccessor, path: &str) -> Result<()> {
+    let file = path.trim_end_matches('/');
+    if file.is_empty() || !check_enabled(acc) {
+        return Ok(());
+    }
+
+    match acc.blocking_stat(file, OpStat::new()) {
+        Ok(rp) if rp.into_metadata().is_file() => Err(new_not_a_directory_error(acc, path)),
+        _ => Ok(()),
+    }
+}
+
+/// Convert the error of listing path into `NotADirectory` if path is a file.
+pub(super) async fn check_list_error(acc: &FusedAccessor, path: &str, err: Error) -> Error {
+    if !maybe_misused(&err) {
+        return err;
+    }
+
+    match check_list_file(acc, path).await {
+        Err(e) => e,
+        Ok(()) => err,
+    }
+}
+
+/// The blocking version of [`check_list_error`].
+pub(super) fn blocking_check_list_error(acc: &FusedAccessor, path: &str, err: Error) -> Error {
+    if !maybe_misused(&err) {
+        return err;
+    }
+
+    match blocking_check_list_file(acc, path) {
+        Err(e) => e,
+        Ok(()) => err,
+    }
+}
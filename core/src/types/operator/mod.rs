@@ -30,5 +30,7 @@ pub use builder::OperatorBuilder;
 mod metadata;
 pub use metadata::OperatorInfo;
 
+mod misuse;
+
 pub mod operator_functions;
 pub mod operator_futures;
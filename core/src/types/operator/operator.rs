@@ -15,15 +15,20 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashSet;
+use std::sync::Arc;
 use std::time::Duration;
+use std::time::Instant;
 
 use bytes::Buf;
 use bytes::Bytes;
+use flagset::FlagSet;
 use futures::stream;
 use futures::Stream;
 use futures::StreamExt;
 use futures::TryStreamExt;
 
+use super::misuse::*;
 use super::BlockingOperator;
 use crate::operator_futures::*;
 use crate::raw::oio::ReadExt;
@@ -31,6 +36,10 @@ use crate::raw::oio::WriteExt;
 use crate::raw::*;
 use crate::*;
 
+/// The min and max delay between polls of [`Operator::wait_visible`].
+const WAIT_VISIBLE_MIN_DELAY: Duration = Duration::from_millis(50);
+const WAIT_VISIBLE_MAX_DELAY: Duration = Duration::from_secs(1);
+
 /// Operator is the entry for all public async APIs.
 ///
 /// Developer should manipulate the data from storage service through Operator only by right.
@@ -69,6 +78,9 @@ pub struct Operator {
 
     // limit is usually the maximum size of data that operator will handle in one operation
     limit: usize,
+
+    // policy will be applied on all input paths before normalization
+    policy: Option<Arc<PathPolicy>>,
 }
 
 /// # Operator basic API.
@@ -83,7 +95,11 @@ impl Operator {
             .full_capability()
             .batch_max_operations
             .unwrap_or(1000);
-        Self { accessor, limit }
+        Self {
+            accessor,
+            limit,
+            policy: None,
+        }
     }
 
     pub(super) fn into_inner(self) -> FusedAccessor {
@@ -105,6 +121,28 @@ impl Operator {
         op
     }
 
+    /// Get current operator's path policy.
+    pub fn path_policy(&self) -> Option<&PathPolicy> {
+        self.policy.as_deref()
+    }
+
+    /// Specify the path policy that all input paths must pass.
+    ///
+    /// Default: no policy
+    pub fn with_path_policy(&self, policy: PathPolicy) -> Self {
+        let mut op = self.clone();
+        op.policy = Some(Arc::new(policy));
+        op
+    }
+
+    /// Check input path against path policy and normalize it.
+    pub(crate) fn normalize(&self, path: &str) -> Result<String> {
+        match &self.policy {
+            Some(policy) => Ok(normalize_path(&policy.check(path)?)),
+            None => Ok(normalize_path(path)),
+        }
+    }
+
     /// Get information of underlying accessor.
     ///
     /// # Examples
@@ -124,16 +162,91 @@ impl Operator {
         OperatorInfo::new(self.accessor.info())
     }
 
+    /// Get a snapshot of the I/O statistics of this operator.
+    ///
+    /// Statistics are shared by all operators cloned from the same
+    /// [`OperatorBuilder`], including the [`BlockingOperator`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.write("test", "Hello, World!").await?;
+    ///
+    /// let stats = op.stats();
+    /// println!("written {} bytes", stats.written_bytes());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stats(&self) -> OperatorStats {
+        self.accessor
+            .info()
+            .stats()
+            .map(|v| v.snapshot())
+            .unwrap_or_default()
+    }
+
+    /// Reset the I/O statistics of this operator.
+    pub fn reset_stats(&self) {
+        if let Some(stats) = self.accessor.info().stats() {
+            stats.reset()
+        }
+    }
+
     /// Create a new blocking operator.
     ///
     /// This operation is nearly no cost.
     pub fn blocking(&self) -> BlockingOperator {
-        BlockingOperator::from_inner(self.accessor.clone()).with_limit(self.limit)
+        let op = BlockingOperator::from_inner(self.accessor.clone()).with_limit(self.limit);
+        match &self.policy {
+            Some(policy) => op.with_path_policy(policy.as_ref().clone()),
+            None => op,
+        }
+    }
+
+    /// Create a content-addressable storage facade on top of this operator.
+    ///
+    /// This operation is nearly no cost.
+    #[cfg(feature = "cas")]
+    pub fn cas(&self) -> Cas {
+        Cas::new(self.clone())
     }
 }
 
 /// Operator async API.
 impl Operator {
+    /// Query the location of data at rest, like the region of bucket.
+    ///
+    /// The location is queried from service by `stat` on root if supported,
+    /// otherwise [`OperatorInfo::location`] from config will be returned.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let location = op.location().await?;
+    /// assert!(matches!(location.as_deref(), Some(v) if v.starts_with("eu")));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn location(&self) -> Result<Option<String>> {
+        if self.info().full_capability().stat_root {
+            let meta = self.stat("/").await?;
+            if meta.metakey().contains(Metakey::Region) {
+                if let Some(v) = meta.region() {
+                    return Ok(Some(v.to_string()));
+                }
+            }
+        }
+
+        Ok(self.info().location().map(String::from))
+    }
+
     /// Check if this operator can work correctly.
     ///
     /// We will send a `list` request to path and return any errors we met.
@@ -192,6 +305,12 @@ impl Operator {
     /// when `test/abc` exists since the service won't have the concept of dir. There is nothing
     /// we can do about this.
     ///
+    /// ## Stat on root
+    ///
+    /// `stat("/")` always returns metadata with dir mode on all services. Services that
+    /// support [`Capability::stat_root`] will also return backend-level metadata like
+    /// [`Metadata::created`] and [`Metadata::region`] of the bucket when available.
+    ///
     /// # Examples
     ///
     /// ## Check if file exists
@@ -280,8 +399,29 @@ impl Operator {
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// ## Only fetch required fields
+    ///
+    /// Services like `postgresql` and `s3` can skip fetching or parsing fields that are not
+    /// required, which reduces the cost of high-QPS stat workloads.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use opendal::Metakey;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let meta = op
+    ///     .stat_with("test")
+    ///     .fields(Metakey::ContentLength | Metakey::Etag)
+    ///     .await?;
+    /// println!("{} {:?}", meta.content_length(), meta.etag());
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn stat_with(&self, path: &str) -> FutureStat {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FutureStat(OperatorFuture::new(
             self.inner().clone(),
@@ -327,6 +467,117 @@ impl Operator {
         }
     }
 
+    /// Wait until the object at given path is visible, returns its metadata.
+    ///
+    /// This is a read-your-writes barrier for eventually-consistent services
+    /// and caches: `stat` will be polled with backoff until the object
+    /// exists and, if `etag` is given, its etag matches.
+    ///
+    /// Returns a temporary [`ErrorKind::Unexpected`] error if the object is
+    /// still not visible after `timeout`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// #[tokio::main]
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     op.write("test", "Hello, World!").await?;
+    ///     let meta = op.stat("test").await?;
+    ///
+    ///     op.wait_visible("test", meta.etag(), Duration::from_secs(10))
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn wait_visible(
+        &self,
+        path: &str,
+        etag: Option<&str>,
+        timeout: Duration,
+    ) -> Result<Metadata> {
+        let start = Instant::now();
+        let mut delay = WAIT_VISIBLE_MIN_DELAY;
+        loop {
+            match self.stat(path).await {
+                Ok(meta) if etag.is_none() || meta.etag() == etag => return Ok(meta),
+                Ok(_) => {}
+                Err(err) if err.kind() == ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                let mut err = Error::new(ErrorKind::Unexpected, "object is not visible in time")
+                    .with_operation("Operator::wait_visible")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", path)
+                    .with_context("timeout", timeout.as_secs_f64().to_string())
+                    .set_temporary();
+                if let Some(etag) = etag {
+                    err = err.with_context("etag", etag);
+                }
+                return Err(err);
+            }
+            tokio::time::sleep(delay.min(timeout - elapsed)).await;
+            delay = (delay * 2).min(WAIT_VISIBLE_MAX_DELAY);
+        }
+    }
+
+    /// Wait until the object at given path is not visible anymore.
+    ///
+    /// This is the counterpart of [`Operator::wait_visible`] for deletions:
+    /// `stat` will be polled with backoff until it returns
+    /// [`ErrorKind::NotFound`].
+    ///
+    /// Returns a temporary [`ErrorKind::Unexpected`] error if the object is
+    /// still visible after `timeout`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// #[tokio::main]
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     op.delete("test").await?;
+    ///     op.wait_deleted("test", Duration::from_secs(10)).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn wait_deleted(&self, path: &str, timeout: Duration) -> Result<()> {
+        let start = Instant::now();
+        let mut delay = WAIT_VISIBLE_MIN_DELAY;
+        loop {
+            match self.stat(path).await {
+                Ok(_) => {}
+                Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+                Err(err) => return Err(err),
+            }
+
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                return Err(
+                    Error::new(ErrorKind::Unexpected, "object is still visible in time")
+                        .with_operation("Operator::wait_deleted")
+                        .with_context("service", self.info().scheme().into_static())
+                        .with_context("path", path)
+                        .with_context("timeout", timeout.as_secs_f64().to_string())
+                        .set_temporary(),
+                );
+            }
+            tokio::time::sleep(delay.min(timeout - elapsed)).await;
+            delay = (delay * 2).min(WAIT_VISIBLE_MAX_DELAY);
+        }
+    }
+
     /// Create a dir at given path.
     ///
     /// # Notes
@@ -353,7 +604,7 @@ impl Operator {
     /// # }
     /// ```
     pub async fn create_dir(&self, path: &str) -> Result<()> {
-        let path = normalize_path(path);
+        let path = self.normalize(path)?;
 
         if !validate_path(&path, EntryMode::DIR) {
             return Err(Error::new(
@@ -410,7 +661,7 @@ impl Operator {
     /// # }
     /// ```
     pub fn read_with(&self, path: &str) -> FutureRead {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FutureRead(OperatorFuture::new(
             self.inner().clone(),
@@ -428,25 +679,44 @@ impl Operator {
                         .with_context("path", &path));
                     }
 
+                    let signer = args.signer().cloned();
                     let range = args.range();
                     let size_hint = match range.size() {
                         Some(v) => v,
                         None => {
-                            let mut size = inner
-                                .stat(&path, OpStat::default())
-                                .await?
-                                .into_metadata()
-                                .content_length();
-                            size -= range.offset().unwrap_or(0);
-                            size
+                            let meta = match inner.stat(&path, args.to_stat()).await {
+                                Ok(rp) => rp.into_metadata(),
+                                Err(err) => {
+                                    return Err(check_read_error(
+                                        &inner,
+                                        &path,
+                                        signer.as_ref(),
+                                        err,
+                                    )
+                                    .await)
+                                }
+                            };
+                            if meta.is_dir() {
+                                return Err(new_is_a_directory_error(&inner, &path));
+                            }
+                            meta.content_length() - range.offset().unwrap_or(0)
                         }
                     };
 
-                    let (_, mut s) = inner.read(&path, args).await?;
-                    let mut buf = Vec::with_capacity(size_hint as usize);
-                    s.read_to_end(&mut buf).await?;
+                    let res = async {
+                        let (_, mut s) = inner.read(&path, args).await?;
+                        let mut buf = Vec::with_capacity(size_hint as usize);
+                        s.read_to_end(&mut buf).await?;
+                        Ok(buf)
+                    }
+                    .await;
 
-                    Ok(buf)
+                    match res {
+                        Ok(buf) => Ok(buf),
+                        Err(err) => {
+                            Err(check_read_error(&inner, &path, signer.as_ref(), err).await)
+                        }
+                    }
                 };
 
                 Box::pin(fut)
@@ -491,7 +761,7 @@ impl Operator {
     /// # }
     /// ```
     pub fn reader_with(&self, path: &str) -> FutureReader {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FutureReader(OperatorFuture::new(
             self.inner().clone(),
@@ -509,7 +779,13 @@ impl Operator {
                         .with_context("path", path));
                     }
 
-                    Reader::create(inner.clone(), &path, args).await
+                    let signer = args.signer().cloned();
+                    match Reader::create(inner.clone(), &path, args).await {
+                        Ok(r) => Ok(r),
+                        Err(err) => {
+                            Err(check_read_error(&inner, &path, signer.as_ref(), err).await)
+                        }
+                    }
                 };
 
                 Box::pin(fut)
@@ -566,23 +842,206 @@ impl Operator {
     /// # }
     /// ```
     pub async fn copy(&self, from: &str, to: &str) -> Result<()> {
-        let from = normalize_path(from);
+        self.copy_with(from, to).await
+    }
+
+    /// Copy a file from `from` to `to` with extra options.
+    ///
+    /// # Notes
+    ///
+    /// Standard metadata, user metadata and tags of `from` will be preserved
+    /// unless they are replaced via options. Services that can't replace
+    /// them will return [`ErrorKind::Unsupported`] instead of ignoring them
+    /// silently.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.copy_with("path/to/file", "path/to/file2")
+    ///     .content_type("text/plain")
+    ///     .cache_control("no-cache")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn copy_with(&self, from: &str, to: &str) -> FutureCopy {
+        let (from, to) = match (self.normalize(from), self.normalize(to)) {
+            (Ok(from), Ok(to)) => (Ok(from), to),
+            (Err(err), _) | (_, Err(err)) => (Err(err), String::new()),
+        };
+
+        let fut = FutureCopy(OperatorFuture::new(
+            self.inner().clone(),
+            from,
+            (OpCopy::new(), to),
+            |inner, from, (args, to)| {
+                let fut = async move {
+                    if !validate_path(&from, EntryMode::FILE) {
+                        return Err(Error::new(
+                            ErrorKind::IsADirectory,
+                            "from path is a directory",
+                        )
+                        .with_operation("Operator::copy")
+                        .with_context("service", inner.info().scheme())
+                        .with_context("from", from));
+                    }
+
+                    if !validate_path(&to, EntryMode::FILE) {
+                        return Err(
+                            Error::new(ErrorKind::IsADirectory, "to path is a directory")
+                                .with_operation("Operator::copy")
+                                .with_context("service", inner.info().scheme())
+                                .with_context("to", to),
+                        );
+                    }
+
+                    if from == to {
+                        return Err(Error::new(
+                            ErrorKind::IsSameFile,
+                            "from and to paths are same",
+                        )
+                        .with_operation("Operator::copy")
+                        .with_context("service", inner.info().scheme())
+                        .with_context("from", from)
+                        .with_context("to", to));
+                    }
+
+                    inner.copy(&from, &to, args).await?;
+                    Ok(())
+                };
+
+                Box::pin(fut)
+            },
+        ));
+
+        fut
+    }
+
+    /// Concat `sources` in order into `path`.
+    ///
+    /// # Notes
+    ///
+    /// - `path` and `sources` must be files.
+    /// - `path` will be overwritten if it exists, `sources` are kept
+    ///   untouched.
+    /// - Services with [`Capability::concat`] concat at server side without
+    ///   downloading, like `UploadPartCopy` on S3, `compose` on GCS and
+    ///   `Put Block From URL` on Azure Blob. Other services fallback to
+    ///   stream the content of `sources` into `path`.
+    /// - S3 requires every source except the last one to be at least 5MiB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.concat("logs/merged", &["logs/shard-0", "logs/shard-1"])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn concat(&self, path: &str, sources: &[&str]) -> Result<()> {
+        let path = self.normalize(path)?;
+        let sources = sources
+            .iter()
+            .map(|v| self.normalize(v))
+            .collect::<Result<Vec<_>>>()?;
+
+        for p in sources.iter().chain([&path]) {
+            if !validate_path(p, EntryMode::FILE) {
+                return Err(
+                    Error::new(ErrorKind::IsADirectory, "concat path is a directory")
+                        .with_operation("Operator::concat")
+                        .with_context("service", self.info().scheme().into_static())
+                        .with_context("path", p),
+                );
+            }
+        }
+        if sources.is_empty() {
+            return Err(
+                Error::new(ErrorKind::InvalidInput, "concat sources are empty")
+                    .with_operation("Operator::concat")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", &path),
+            );
+        }
+        if sources.contains(&path) {
+            return Err(
+                Error::new(ErrorKind::IsSameFile, "concat path is one of the sources")
+                    .with_operation("Operator::concat")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", &path),
+            );
+        }
+
+        if self.info().full_capability().concat {
+            self.inner().concat(&path, OpConcat::new(sources)).await?;
+            return Ok(());
+        }
+
+        let mut w = self.writer(&path).await?;
+        for src in &sources {
+            let res = match self.reader(src).await {
+                Ok(r) => w.copy(r).await.map(|_| ()),
+                Err(err) => Err(err),
+            };
+            if let Err(err) = res {
+                // Keep the original error, the abort failure is only logged.
+                if let Err(e) = w.abort().await {
+                    log::warn!("abort concat into {path} failed: {e}");
+                }
+                return Err(err.with_context("source", src));
+            }
+        }
+        w.close().await
+    }
+
+    /// Rename a file from `from` to `to`.
+    ///
+    /// # Notes
+    ///
+    /// - `from` and `to` must be a file.
+    /// - `to` will be overwritten if it exists.
+    /// - If `from` and `to` are the same, an `IsSameFile` error will occur.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.rename("path/to/file", "path/to/file2").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rename(&self, from: &str, to: &str) -> Result<()> {
+        let from = self.normalize(from)?;
 
         if !validate_path(&from, EntryMode::FILE) {
             return Err(
                 Error::new(ErrorKind::IsADirectory, "from path is a directory")
-                    .with_operation("Operator::copy")
+                    .with_operation("Operator::move_")
                     .with_context("service", self.info().scheme())
                     .with_context("from", from),
             );
         }
 
-        let to = normalize_path(to);
+        let to = self.normalize(to)?;
 
         if !validate_path(&to, EntryMode::FILE) {
             return Err(
                 Error::new(ErrorKind::IsADirectory, "to path is a directory")
-                    .with_operation("Operator::copy")
+                    .with_operation("Operator::move_")
                     .with_context("service", self.info().scheme())
                     .with_context("to", to),
             );
@@ -591,25 +1050,25 @@ impl Operator {
         if from == to {
             return Err(
                 Error::new(ErrorKind::IsSameFile, "from and to paths are same")
-                    .with_operation("Operator::copy")
+                    .with_operation("Operator::move_")
                     .with_context("service", self.info().scheme())
                     .with_context("from", from)
                     .with_context("to", to),
             );
         }
 
-        self.inner().copy(&from, &to, OpCopy::new()).await?;
+        self.inner().rename(&from, &to, OpRename::new()).await?;
 
         Ok(())
     }
 
-    /// Rename a file from `from` to `to`.
+    /// Create a symlink at `path` which points to `target`.
     ///
     /// # Notes
     ///
-    /// - `from` and `to` must be a file.
-    /// - `to` will be overwritten if it exists.
-    /// - If `from` and `to` are the same, an `IsSameFile` error will occur.
+    /// - `path` must be a file path.
+    /// - `target` is stored as is, it will not be normalized or checked.
+    /// - Require [`Capability::create_symlink`].
     ///
     /// # Examples
     ///
@@ -619,46 +1078,301 @@ impl Operator {
     ///
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// op.rename("path/to/file", "path/to/file2").await?;
+    /// op.create_symlink("path/to/file", "path/to/link").await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn rename(&self, from: &str, to: &str) -> Result<()> {
-        let from = normalize_path(from);
+    pub async fn create_symlink(&self, target: &str, path: &str) -> Result<()> {
+        let path = self.normalize(path)?;
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(Error::new(ErrorKind::IsADirectory, "path is a directory")
+                .with_operation("Operator::create_symlink")
+                .with_context("service", self.info().scheme())
+                .with_context("path", path));
+        }
+
+        self.inner()
+            .create_symlink(target, &path, OpCreateSymlink::new())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Change the storage class (a.k.a. access tier) of given file in place,
+    /// like `STANDARD_IA` of s3 or `Cool` of azblob.
+    ///
+    /// Services implement this by their native tiering APIs, for example,
+    /// s3 will copy the object onto itself and azblob will call
+    /// `Set Blob Tier`. Refer to [`TierMover`] to transition files in bulk.
+    ///
+    /// # Notes
+    ///
+    /// - Require [`Capability::copy_with_storage_class`].
+    /// - Archived objects may need to be restored before transitioning.
+    /// - S3 resets the last modified time of transitioned objects, the
+    ///   original one is kept in user metadata `opendal-last-modified`, and
+    ///   the superseded version will be removed on versioned buckets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.set_storage_class("path/to/file", "STANDARD_IA").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_storage_class(&self, path: &str, storage_class: &str) -> Result<()> {
+        let path = self.normalize(path)?;
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(Error::new(ErrorKind::IsADirectory, "path is a directory")
+                .with_operation("Operator::set_storage_class")
+                .with_context("service", self.info().scheme())
+                .with_context("path", path));
+        }
+
+        self.inner()
+            .copy(
+                &path,
+                &path,
+                OpCopy::new().with_storage_class(storage_class),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Set unix permission bits like `0o644` of given path.
+    ///
+    /// # Notes
+    ///
+    /// - Require [`Capability::set_permissions`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.set_permissions("path/to/file", 0o644).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_permissions(&self, path: &str, permissions: u32) -> Result<()> {
+        let path = self.normalize(path)?;
+
+        self.inner()
+            .set_permissions(&path, OpSetPermissions::new(permissions))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Create a read-only snapshot of given file and return the id of
+    /// snapshot.
+    ///
+    /// The snapshot can be read or stat via `read_with(path).snapshot(id)`
+    /// and `stat_with(path).snapshot(id)`.
+    ///
+    /// # Notes
+    ///
+    /// - Require [`Capability::create_snapshot`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let id = op.create_snapshot("path/to/file").await?;
+    /// let bs = op.read_with("path/to/file").snapshot(&id).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_snapshot(&self, path: &str) -> Result<String> {
+        let path = self.normalize(path)?;
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "snapshot path is a directory")
+                    .with_operation("Operator::create_snapshot")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", &path),
+            );
+        }
+
+        let rp = self
+            .inner()
+            .create_snapshot(&path, OpCreateSnapshot::new())
+            .await?;
+
+        Ok(rp.snapshot().to_string())
+    }
+
+    /// List all snapshots of given file.
+    ///
+    /// Every returned entry has the same path as the file, with
+    /// [`Metadata::snapshot`] set to the id of snapshot.
+    ///
+    /// # Notes
+    ///
+    /// - Require [`Capability::list_with_snapshots`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// for entry in op.list_snapshots("path/to/file").await? {
+    ///     let id = entry.metadata().snapshot().expect("must have snapshot id");
+    ///     let meta = op.stat_with(entry.path()).snapshot(id).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_snapshots(&self, path: &str) -> Result<Vec<Entry>> {
+        let path = self.normalize(path)?;
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "snapshot path is a directory")
+                    .with_operation("Operator::list_snapshots")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", &path),
+            );
+        }
+
+        // List with the file path as prefix so that only the file itself
+        // and its snapshots will be returned.
+        let args = OpList::new()
+            .with_recursive(true)
+            .with_snapshots(true)
+            .with_metakey(Metakey::Mode | Metakey::Snapshot);
+        let entries: Vec<Entry> = Lister::create(self.inner().clone(), &path, args)
+            .await?
+            .try_collect()
+            .await?;
+
+        Ok(entries
+            .into_iter()
+            .filter(|e| e.path() == path && e.metadata().snapshot().is_some())
+            .collect())
+    }
+
+    /// Acquire a lease of given file and return the id of lease.
+    ///
+    /// While the lease is active, writes and deletes without the lease id
+    /// will be rejected, which could be used to build a distributed lock.
+    /// `None` duration means the lease never expires until released.
+    ///
+    /// # Notes
+    ///
+    /// - Require [`Capability::lease`].
+    /// - Services could have their own limitation on duration, for example,
+    ///   azblob only allows whole seconds between 15 and 60, other durations
+    ///   will be rejected with [`ErrorKind::InvalidInput`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let id = op
+    ///     .acquire_lease("path/to/lock", Some(Duration::from_secs(30)))
+    ///     .await?;
+    /// op.write_with("path/to/lock", "owned").lease_id(&id).await?;
+    /// op.release_lease("path/to/lock", &id).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn acquire_lease(&self, path: &str, duration: Option<Duration>) -> Result<String> {
+        let rp = self
+            .lease(
+                path,
+                LeaseAction::Acquire(duration),
+                "Operator::acquire_lease",
+            )
+            .await?;
+
+        rp.lease_id().map(|v| v.to_string()).ok_or_else(|| {
+            Error::new(ErrorKind::Unexpected, "service doesn't return lease id")
+                .with_operation("Operator::acquire_lease")
+                .with_context("service", self.info().scheme().into_static())
+                .with_context("path", path)
+        })
+    }
 
-        if !validate_path(&from, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "from path is a directory")
-                    .with_operation("Operator::move_")
-                    .with_context("service", self.info().scheme())
-                    .with_context("from", from),
-            );
-        }
+    /// Renew the lease of given file to reset its duration.
+    ///
+    /// # Notes
+    ///
+    /// - Require [`Capability::lease`].
+    pub async fn renew_lease(&self, path: &str, lease_id: &str) -> Result<()> {
+        self.lease(
+            path,
+            LeaseAction::Renew(lease_id.to_string()),
+            "Operator::renew_lease",
+        )
+        .await?;
+        Ok(())
+    }
 
-        let to = normalize_path(to);
+    /// Release the lease of given file so that others could acquire it.
+    ///
+    /// # Notes
+    ///
+    /// - Require [`Capability::lease`].
+    pub async fn release_lease(&self, path: &str, lease_id: &str) -> Result<()> {
+        self.lease(
+            path,
+            LeaseAction::Release(lease_id.to_string()),
+            "Operator::release_lease",
+        )
+        .await?;
+        Ok(())
+    }
 
-        if !validate_path(&to, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "to path is a directory")
-                    .with_operation("Operator::move_")
-                    .with_context("service", self.info().scheme())
-                    .with_context("to", to),
-            );
-        }
+    /// Break the lease of given file without knowing its id, useful to
+    /// recover from crashed holders.
+    ///
+    /// # Notes
+    ///
+    /// - Require [`Capability::lease`].
+    pub async fn break_lease(&self, path: &str) -> Result<()> {
+        self.lease(path, LeaseAction::Break, "Operator::break_lease")
+            .await?;
+        Ok(())
+    }
 
-        if from == to {
+    async fn lease(&self, path: &str, action: LeaseAction, op: &'static str) -> Result<RpLease> {
+        let path = self.normalize(path)?;
+
+        if !validate_path(&path, EntryMode::FILE) {
             return Err(
-                Error::new(ErrorKind::IsSameFile, "from and to paths are same")
-                    .with_operation("Operator::move_")
-                    .with_context("service", self.info().scheme())
-                    .with_context("from", from)
-                    .with_context("to", to),
+                Error::new(ErrorKind::IsADirectory, "lease path is a directory")
+                    .with_operation(op)
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", &path),
             );
         }
 
-        self.inner().rename(&from, &to, OpRename::new()).await?;
-
-        Ok(())
+        self.inner().lease(&path, OpLease::new(action)).await
     }
 
     /// Write multiple bytes into path.
@@ -713,7 +1427,7 @@ impl Operator {
     /// # }
     /// ```
     pub fn writer_with(&self, path: &str) -> FutureWriter {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FutureWriter(OperatorFuture::new(
             self.inner().clone(),
@@ -764,7 +1478,7 @@ impl Operator {
     /// # }
     /// ```
     pub fn write_with(&self, path: &str, bs: impl Into<Bytes>) -> FutureWrite {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
         let bs = bs.into();
 
         let fut = FutureWrite(OperatorFuture::new(
@@ -825,7 +1539,8 @@ impl Operator {
     ///
     /// # Notes
     ///
-    /// - Deleting a file that does not exist won't return errors.
+    /// - Deleting a file that does not exist won't return errors unless
+    ///   `must_exist` is set.
     ///
     /// # Examples
     ///
@@ -836,12 +1551,12 @@ impl Operator {
     ///
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// op.delete_with("test").await?;
+    /// op.delete_with("test").must_exist(true).await?;
     /// # Ok(())
     /// # }
     /// ```
     pub fn delete_with(&self, path: &str) -> FutureDelete {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FutureDelete(OperatorFuture::new(
             self.inner().clone(),
@@ -881,7 +1596,9 @@ impl Operator {
     /// # }
     /// ```
     pub async fn remove(&self, paths: Vec<String>) -> Result<()> {
-        self.remove_via(stream::iter(paths)).await
+        self.remove_via(stream::iter(paths)).await?;
+
+        Ok(())
     }
 
     /// remove will remove files via the given paths.
@@ -895,6 +1612,11 @@ impl Operator {
     /// If underlying services support delete in batch, we will use batch
     /// delete instead.
     ///
+    /// The returned [`BatchResults`] contains the outcome of every path in
+    /// the same order as input. By default, the whole operation will be
+    /// aborted on the first failure, use [`FutureRemoveVia::max_failures`]
+    /// to tolerate more.
+    ///
     /// # Examples
     ///
     /// ```
@@ -906,41 +1628,20 @@ impl Operator {
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
     /// let stream = stream::iter(vec!["abc".to_string(), "def".to_string()]);
-    /// op.remove_via(stream).await?;
+    /// let results = op.remove_via(stream).max_failures(10).await?;
+    /// for entry in results.entries() {
+    ///     if let Some(err) = entry.error() {
+    ///         println!("failed to remove {}: {err}", entry.path());
+    ///     }
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn remove_via(&self, input: impl Stream<Item = String> + Unpin) -> Result<()> {
-        let input = input.map(|v| normalize_path(&v));
-
-        if self.info().full_capability().batch {
-            let mut input = input
-                .map(|v| (v, OpDelete::default().into()))
-                .chunks(self.limit());
-
-            while let Some(batches) = input.next().await {
-                let results = self
-                    .inner()
-                    .batch(OpBatch::new(batches))
-                    .await?
-                    .into_results();
-
-                // TODO: return error here directly seems not a good idea?
-                for (_, result) in results {
-                    let _ = result?;
-                }
-            }
-        } else {
-            input
-                .map(Ok)
-                .try_for_each_concurrent(self.limit, |path| async move {
-                    let _ = self.inner().delete(&path, OpDelete::default()).await?;
-                    Ok::<(), Error>(())
-                })
-                .await?;
-        }
-
-        Ok(())
+    pub fn remove_via<S>(&self, input: S) -> FutureRemoveVia<S>
+    where
+        S: Stream<Item = String> + Unpin,
+    {
+        FutureRemoveVia::new(self.clone(), input)
     }
 
     /// Remove the path and all nested dirs and files recursively.
@@ -979,6 +1680,22 @@ impl Operator {
             return self.delete(path).await;
         }
 
+        // Let underlying layers handle the whole dir if they can.
+        if self.info().full_capability().delete_with_recursive {
+            return self.delete_with(path).recursive(true).await;
+        }
+
+        // Push down to services that can delete by prefix server side.
+        if self.info().full_capability().delete_with_prefix {
+            // Make sure we won't remove siblings like `abc_def` for `abc`.
+            let prefix = if path.ends_with('/') {
+                path.to_string()
+            } else {
+                format!("{path}/")
+            };
+            return self.delete_with(&prefix).prefix(true).await;
+        }
+
         let obs = self.lister_with(path).recursive(true).await?;
 
         if self.info().full_capability().batch {
@@ -1013,6 +1730,181 @@ impl Operator {
         Ok(())
     }
 
+    /// Rename all files under `from` dir to `to` dir.
+    ///
+    /// Files will be moved concurrently via native `rename` or `copy` +
+    /// `delete` if `rename` is not supported.
+    ///
+    /// # Notes
+    ///
+    /// - `from` and `to` must be dirs and must not contain each other.
+    /// - The returned [`BatchResults`] contains the outcome of every source
+    ///   path. By default, the whole operation will be aborted on the first
+    ///   failure, use [`FutureRenameAll::max_failures`] to tolerate more.
+    /// - Use [`FutureRenameAll::checkpoint`] to record moved paths in a dir,
+    ///   so that an interrupted rename could be resumed by calling it again
+    ///   with the same checkpoint. The checkpoint will be removed once all
+    ///   files have been moved without failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let results = op
+    ///     .rename_all("path/to/src/", "path/to/dst/")
+    ///     .checkpoint(".rename_all.checkpoint/")
+    ///     .max_failures(100)
+    ///     .await?;
+    /// for entry in results.entries() {
+    ///     if let Some(err) = entry.error() {
+    ///         println!("failed to move {}: {err}", entry.path());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rename_all(&self, from: &str, to: &str) -> FutureRenameAll {
+        FutureRenameAll::new(self.clone(), from, to)
+    }
+
+    /// Move all files under `from` to `to`, abort if failures exceed
+    /// `max_failures`.
+    pub(super) async fn rename_dir(
+        &self,
+        from: &str,
+        to: &str,
+        checkpoint: Option<&str>,
+        max_failures: usize,
+    ) -> Result<BatchResults<RpRename>> {
+        // Make sure we won't move siblings like `abc_def` for `abc`.
+        let from = self.normalize(&format!("{from}/"))?;
+        let to = self.normalize(&format!("{to}/"))?;
+        // Root normalizes to `/` which never prefixes other paths, but it
+        // contains everything.
+        if from == "/" || to == "/" || from.starts_with(&to) || to.starts_with(&from) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "from and to paths must not contain each other",
+            )
+            .with_operation("Operator::rename_all")
+            .with_context("service", self.info().scheme())
+            .with_context("from", from)
+            .with_context("to", to));
+        }
+
+        let cap = self.info().full_capability();
+        if !cap.rename && !(cap.copy && cap.delete) {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "service doesn't support rename or copy with delete",
+            )
+            .with_operation("Operator::rename_all")
+            .with_context("service", self.info().scheme()));
+        }
+
+        // Checkpoint is a dir of segments, every segment contains paths
+        // moved by one batch separated by `\n`. Segments are never rewritten,
+        // so that recording won't grow with the number of moved files.
+        let checkpoint = match checkpoint {
+            Some(path) => Some(self.normalize(&format!("{path}/"))?),
+            None => None,
+        };
+        let mut moved = HashSet::new();
+        let mut next_segment = 0;
+        if let Some(dir) = &checkpoint {
+            let mut entries = self.lister(dir).await?;
+            while let Some(entry) = entries.try_next().await? {
+                if entry.metadata().mode() != EntryMode::FILE {
+                    continue;
+                }
+                let bs = self.read(entry.path()).await?;
+                let content = String::from_utf8(bs).map_err(|err| {
+                    Error::new(ErrorKind::Unexpected, "checkpoint is not valid utf-8")
+                        .with_operation("Operator::rename_all")
+                        .with_context("checkpoint", entry.path())
+                        .set_source(err)
+                })?;
+                moved.extend(content.lines().map(|v| v.to_string()));
+                if let Ok(n) = entry.name().parse::<u64>() {
+                    next_segment = next_segment.max(n + 1);
+                }
+            }
+        }
+
+        let mut lister = self
+            .lister_with(&from)
+            .recursive(true)
+            .await?
+            .try_filter(|entry| {
+                let skip = entry.metadata().mode() != EntryMode::FILE
+                    || moved.contains(entry.path())
+                    || checkpoint
+                        .as_deref()
+                        .map_or(false, |dir| entry.path().starts_with(dir));
+                futures::future::ready(!skip)
+            })
+            .try_chunks(self.limit());
+
+        let mut results = BatchResults::default();
+        while let Some(entries) = lister.next().await {
+            let entries = entries.map_err(|err| err.1)?;
+
+            let outcomes: Vec<(String, Result<RpRename>)> = stream::iter(entries)
+                .map(|entry| {
+                    let src = entry.path().to_string();
+                    let dst = format!("{to}{}", src.strip_prefix(&from).unwrap_or(&src));
+                    async move {
+                        let res = if cap.rename {
+                            self.inner().rename(&src, &dst, OpRename::new()).await
+                        } else {
+                            match self.inner().copy(&src, &dst, OpCopy::new()).await {
+                                Ok(_) => self
+                                    .inner()
+                                    .delete(&src, OpDelete::new())
+                                    .await
+                                    .map(|_| RpRename::default()),
+                                Err(err) => Err(err),
+                            }
+                        };
+                        (src, res)
+                    }
+                })
+                .buffered(self.limit())
+                .collect()
+                .await;
+
+            let mut done = String::new();
+            for (path, res) in outcomes {
+                if res.is_ok() {
+                    done.push_str(&path);
+                    done.push('\n');
+                }
+                results.push(path, res, max_failures)?;
+            }
+
+            if let Some(dir) = &checkpoint {
+                if done.is_empty() {
+                    continue;
+                }
+                // Keep segments sorted by name in the order of writing.
+                self.write(&format!("{dir}{next_segment:020}"), done)
+                    .await?;
+                next_segment += 1;
+            }
+        }
+
+        if let Some(dir) = &checkpoint {
+            if results.is_ok() {
+                self.remove_all(dir).await?;
+            }
+        }
+        Ok(results)
+    }
+
     /// List entries within a given directory.
     ///
     /// # Notes
@@ -1145,7 +2037,7 @@ impl Operator {
     /// # }
     /// ```
     pub fn list_with(&self, path: &str) -> FutureList {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FutureList(OperatorFuture::new(
             self.inner().clone(),
@@ -1163,9 +2055,22 @@ impl Operator {
                         .with_context("path", &path));
                     }
 
-                    let lister = Lister::create(inner, &path, args).await?;
+                    let res: Result<Vec<Entry>> = async {
+                        Lister::create(inner.clone(), &path, args)
+                            .await?
+                            .try_collect()
+                            .await
+                    }
+                    .await;
 
-                    lister.try_collect().await
+                    match res {
+                        Ok(entries) if entries.is_empty() => {
+                            check_list_file(&inner, &path).await?;
+                            Ok(entries)
+                        }
+                        Ok(entries) => Ok(entries),
+                        Err(err) => Err(check_list_error(&inner, &path, err).await),
+                    }
                 };
                 Box::pin(fut)
             },
@@ -1173,6 +2078,38 @@ impl Operator {
         fut
     }
 
+    /// List all roots like buckets or containers in the account of this
+    /// operator, the bucket or container configured is not required to
+    /// exist.
+    ///
+    /// Entries are returned as dirs like `bucket/`, with [`Metadata::created`]
+    /// or [`Metadata::last_modified`] set if the service provides them.
+    ///
+    /// Require [`Capability::list_roots`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// for entry in op.list_roots().await? {
+    ///     println!("found bucket {}", entry.name());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_roots(&self) -> Result<Vec<Entry>> {
+        let rp = self.inner().list_roots(OpListRoots::new()).await?;
+
+        Ok(rp
+            .into_entries()
+            .into_iter()
+            .map(|v| v.into_entry())
+            .collect())
+    }
+
     /// List entries within a given directory as a stream.
     ///
     /// This function will create a new handle to list entries.
@@ -1323,7 +2260,7 @@ impl Operator {
     /// # }
     /// ```
     pub fn lister_with(&self, path: &str) -> FutureLister {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FutureLister(OperatorFuture::new(
             self.inner().clone(),
@@ -1341,7 +2278,10 @@ impl Operator {
                         .with_context("path", &path));
                     }
 
-                    Lister::create(inner, &path, args).await
+                    match Lister::create(inner.clone(), &path, args).await {
+                        Ok(l) => Ok(l),
+                        Err(err) => Err(check_list_error(&inner, &path, err).await),
+                    }
                 };
                 Box::pin(fut)
             },
@@ -1374,7 +2314,7 @@ impl Operator {
     /// # }
     /// ```
     pub async fn presign_stat(&self, path: &str, expire: Duration) -> Result<PresignedRequest> {
-        let path = normalize_path(path);
+        let path = self.normalize(path)?;
 
         let op = OpPresign::new(OpStat::new(), expire);
 
@@ -1409,7 +2349,7 @@ impl Operator {
     /// curl "https://s3.amazonaws.com/examplebucket/test.txt?X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential=access_key_id/20130721/us-east-1/s3/aws4_request&X-Amz-Date=20130721T201207Z&X-Amz-Expires=86400&X-Amz-SignedHeaders=host&X-Amz-Signature=<signature-value>" -O /tmp/test.txt
     /// ```
     pub async fn presign_read(&self, path: &str, expire: Duration) -> Result<PresignedRequest> {
-        let path = normalize_path(path);
+        let path = self.normalize(path)?;
 
         let op = OpPresign::new(OpRead::new(), expire);
 
@@ -1439,7 +2379,7 @@ impl Operator {
     /// # }
     /// ```
     pub fn presign_read_with(&self, path: &str, expire: Duration) -> FuturePresignRead {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FuturePresignRead(OperatorFuture::new(
             self.inner().clone(),
@@ -1512,7 +2452,7 @@ impl Operator {
     /// # }
     /// ```
     pub fn presign_write_with(&self, path: &str, expire: Duration) -> FuturePresignWrite {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FuturePresignWrite(OperatorFuture::new(
             self.inner().clone(),
@@ -1529,4 +2469,456 @@ impl Operator {
         ));
         fut
     }
+
+    /// Mint a narrowly-scoped, time-limited credential that only allows
+    /// given operations on the path, so that servers can grant clients
+    /// direct access to the service safely.
+    ///
+    /// Path ends with `/` will be treated as a prefix. Only services that
+    /// expose a signer via [`OperatorInfo::signer`] support this, and they
+    /// could reject scopes they can't express:
+    ///
+    /// - `s3`: STS `AssumeRole` if `role_arn` is set, otherwise
+    ///   `GetFederationToken`, with a session policy.
+    /// - `azblob`: user delegation SAS for bearer token, otherwise service SAS.
+    /// - `gcs`: signed policy document for [`DelegateScope::Write`] if
+    ///   `delegate_service_account` is set, otherwise a token downscoped by
+    ///   credential access boundary which expires with the service's token.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use opendal::DelegateScope;
+    /// use opendal::Operator;
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let cred = op
+    ///         .delegate(
+    ///             "tenant-a/",
+    ///             DelegateScope::Read | DelegateScope::List,
+    ///             Duration::from_secs(3600),
+    ///         )
+    ///         .await?;
+    ///     println!("credential expires at {}", cred.expires_at());
+    ///
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub async fn delegate(
+        &self,
+        path: &str,
+        scope: impl Into<FlagSet<DelegateScope>>,
+        expire: Duration,
+    ) -> Result<DelegatedCredential> {
+        let path = self.normalize(path)?;
+        let info = self.info();
+
+        let Some(signer) = info.signer() else {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "service doesn't support minting delegated credentials",
+            )
+            .with_operation("Operator::delegate")
+            .with_context("service", info.scheme()));
+        };
+
+        let abs_path = build_abs_path(info.root(), &path);
+        signer
+            .delegate(&abs_path, scope.into(), expire)
+            .await
+            .map_err(|err| {
+                err.with_operation("Operator::delegate")
+                    .with_context("service", info.scheme())
+                    .with_context("path", &path)
+            })
+    }
+
+    /// Replace the credential of this operator at runtime, requests sent
+    /// after this call will be signed by the new credential.
+    ///
+    /// This is useful for credentials that rotate regularly like SAS tokens,
+    /// the operator and its connection pool don't need to be rebuilt.
+    ///
+    /// Only services that expose a signer via [`OperatorInfo::signer`]
+    /// support this:
+    ///
+    /// - `azblob`: [`Credential::AzureSas`] and [`Credential::AzureSharedKey`].
+    ///
+    /// # Notes
+    ///
+    /// Capabilities are decided while building the operator, for example
+    /// `azblob` built without SAS token won't support presign even if a SAS
+    /// token is set later.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use opendal::Credential;
+    /// use opendal::Operator;
+    ///
+    /// async fn test(op: Operator, token: String) -> Result<()> {
+    ///     op.update_credential(Credential::AzureSas(token.into()))
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn update_credential(&self, cred: Credential) -> Result<()> {
+        let info = self.info();
+
+        let Some(signer) = info.signer() else {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "service doesn't support updating credential",
+            )
+            .with_operation("Operator::update_credential")
+            .with_context("service", info.scheme()));
+        };
+
+        signer.update_credential(cred).await.map_err(|err| {
+            err.with_operation("Operator::update_credential")
+                .with_context("service", info.scheme())
+        })
+    }
+
+    /// Override the api version of this operator's requests at runtime.
+    ///
+    /// This is useful to opt into newer api versions for some operators
+    /// without rebuilding them, the version set by builder will be replaced.
+    ///
+    /// Only services that expose a signer via [`OperatorInfo::signer`]
+    /// support this:
+    ///
+    /// - `azblob`: `x-ms-version` of requests and the version of minted SAS.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// fn test(op: Operator) -> Result<()> {
+    ///     op.update_api_version("2024-08-04")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn update_api_version(&self, version: &str) -> Result<()> {
+        let info = self.info();
+
+        let Some(signer) = info.signer() else {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "service doesn't support updating api version",
+            )
+            .with_operation("Operator::update_api_version")
+            .with_context("service", info.scheme()));
+        };
+
+        signer.update_api_version(version).map_err(|err| {
+            err.with_operation("Operator::update_api_version")
+                .with_context("service", info.scheme())
+                .with_context("api_version", version)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_rename_all_resume() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        for path in ["src/a", "src/b", "src/dir/c", "src_sibling"] {
+            op.write(path, path).await?;
+        }
+        // `src/a` and `src/b` have been moved by two batches before
+        // interruption.
+        op.rename("src/a", "dst/a").await?;
+        op.rename("src/b", "dst/b").await?;
+        op.write("checkpoint/00000000000000000000", "src/a\n")
+            .await?;
+        op.write("checkpoint/00000000000000000001", "src/b\n")
+            .await?;
+
+        let results = op.rename_all("src", "dst").checkpoint("checkpoint").await?;
+        assert!(results.is_ok());
+        assert_eq!(results.entries().len(), 1);
+
+        assert_eq!(op.read("dst/a").await?, b"src/a");
+        assert_eq!(op.read("dst/b").await?, b"src/b");
+        assert_eq!(op.read("dst/dir/c").await?, b"src/dir/c");
+        assert!(!op.is_exist("src/b").await?);
+        assert!(op.is_exist("src_sibling").await?);
+        assert!(!op.is_exist("checkpoint/00000000000000000000").await?);
+        assert!(!op.is_exist("checkpoint/00000000000000000001").await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rename_all_root() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        op.write("src/a", "Hello, World!").await?;
+
+        for (from, to) in [("/", "dst/"), ("", "dst"), ("src/", "/")] {
+            let err = op.rename_all(from, to).await.expect_err("must fail");
+            assert_eq!(err.kind(), ErrorKind::InvalidInput, "{from} -> {to}");
+        }
+        assert!(op.is_exist("src/a").await?);
+        assert!(!op.is_exist("dst/src/a").await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_remove_via_fallback() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish().with_limit(2);
+        assert!(!op.info().full_capability().batch);
+        for path in ["a", "b", "c"] {
+            op.write(path, path).await?;
+        }
+
+        let paths = ["c", "a", "not_exist", "b"].map(|v| v.to_string());
+        let results = op.remove_via(stream::iter(paths)).await?;
+        assert!(results.is_ok());
+        let paths: Vec<_> = results.entries().iter().map(|v| v.path()).collect();
+        assert_eq!(paths, vec!["c", "a", "not_exist", "b"]);
+        assert!(results.entries().iter().all(|v| v.reply().is_some()));
+        for path in ["a", "b", "c"] {
+            assert!(!op.is_exist(path).await?);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "services-fs")]
+    #[tokio::test]
+    async fn test_remove_via_fallback_max_failures() -> Result<()> {
+        let root = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let mut builder = crate::services::Fs::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder)?.finish();
+        // Remove a non-empty dir will fail.
+        for path in ["a", "b", "c", "dir/d"] {
+            op.write(path, path).await?;
+        }
+        let paths = || ["a", "dir/", "b"].map(|v| v.to_string());
+
+        let err = op
+            .remove_via(stream::iter(paths()))
+            .await
+            .expect_err("must abort");
+        assert_eq!(err.context_value("path"), Some("dir/"));
+
+        let results = op.remove_via(stream::iter(paths())).max_failures(1).await?;
+        assert_eq!(results.failures(), 1);
+        assert_eq!(results.entries().len(), 3);
+        assert!(results.entries()[0].is_ok());
+        assert!(results.entries()[1].error().is_some());
+        assert!(results.entries()[2].is_ok());
+        assert!(!op.is_exist("b").await?);
+        assert!(op.is_exist("c").await?);
+
+        std::fs::remove_dir_all(root).expect("remove temp dir must succeed");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dot_segments() -> Result<()> {
+        // Dot segments are part of keys by default.
+        let op = Operator::new(Memory::default())?.finish();
+        op.write("abc/def", "Hello, World!").await?;
+        op.write("abc/../def", "Hello, World!").await?;
+        assert_eq!(op.read("abc/../def").await?, b"Hello, World!");
+        op.remove_all("abc/..").await?;
+        assert!(op.is_exist("abc/def").await?);
+        op.delete("abc/../def").await?;
+        assert!(!op.is_exist("abc/../def").await?);
+
+        // Path policy rejects them.
+        let op = op.with_path_policy(PathPolicy::default().with_reject_parent_dir(true));
+        for path in ["abc/..", "abc/./def", "../abc/def"] {
+            let err = op.remove_all(path).await.expect_err("must fail");
+            assert_eq!(err.kind(), ErrorKind::InvalidInput, "{path}");
+        }
+        assert!(op.is_exist("abc/def").await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_concat_fallback() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        op.write("shard-0", "Hello, ").await?;
+        op.write("shard-1", "World!").await?;
+
+        op.concat("merged", &["shard-0", "shard-1"]).await?;
+        assert_eq!(op.read("merged").await?, b"Hello, World!");
+        assert!(op.is_exist("shard-0").await?);
+
+        let err = op
+            .concat("shard-0", &["shard-0", "shard-1"])
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::IsSameFile);
+
+        // The error of source is returned and nothing is written.
+        let err = op
+            .concat("broken", &["shard-0", "not_exist"])
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert!(!op.is_exist("broken").await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_wait_visible() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        let timeout = Duration::from_millis(100);
+
+        op.write("test", "Hello, World!").await?;
+        let meta = op.wait_visible("test", None, timeout).await?;
+        assert_eq!(meta.content_length(), 13);
+
+        let err = op
+            .wait_visible("not_exist", None, timeout)
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_temporary());
+
+        op.delete("test").await?;
+        op.wait_deleted("test", timeout).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_must_exist() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        op.write("test", "Hello, World!").await?;
+
+        op.delete_with("test").must_exist(true).await?;
+        let err = op
+            .delete_with("test")
+            .must_exist(true)
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        // Idempotent delete is still the default.
+        op.delete("test").await?;
+
+        // Raw API must be checked too.
+        let err = op
+            .inner()
+            .delete("test", OpDelete::new().with_must_exist(true))
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        let err = op
+            .blocking()
+            .delete_with("test")
+            .must_exist(true)
+            .call()
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        // Dirs are checked by their children.
+        op.write("dir/file", "Hello, World!").await?;
+        op.delete_with("dir/").must_exist(true).await?;
+        let err = op
+            .delete_with("not_exist/")
+            .must_exist(true)
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let err = op
+            .delete_with("di")
+            .prefix(true)
+            .must_exist(true)
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(op.is_exist("dir/file").await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_path_misuse() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .layer(crate::layers::EmulateLayer::default().with_path_misuse(true))
+            .finish();
+        assert!(op.info().full_capability().path_misuse_check);
+        op.create_dir("dir/").await?;
+        op.write("dir/file", "hello").await?;
+
+        let err = op.read("dir").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::IsADirectory);
+        let err = op.list("dir/file/").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotADirectory);
+
+        let err = op.read("not_exist").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let op = op.blocking();
+        let err = op.read("dir").expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::IsADirectory);
+        let err = op.list("dir/file/").expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotADirectory);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_path_misuse_disabled_by_default() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        assert!(!op.info().full_capability().path_misuse_check);
+        op.create_dir("dir/").await?;
+        op.write("dir/file", "hello").await?;
+
+        // Without the extra stat, the native error of service is returned.
+        let err = op.read("dir").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert!(op.list("dir/file/").await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_path_policy() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .finish()
+            .with_path_policy(PathPolicy::strict());
+
+        op.write("tenant/cafe\u{301}", "hello").await?;
+        assert_eq!(op.read("tenant/caf\u{e9}").await?, b"hello");
+
+        let err = op.read("tenant/../other").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = op.delete("tenant\\file").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = op.blocking().stat("a\nb").expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_stats() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        op.write("test", "Hello, World!").await?;
+        assert_eq!(op.read("test").await?, b"Hello, World!");
+        let _ = op.stat("not_exist").await;
+
+        let stats = op.stats();
+        assert_eq!(stats.written_bytes(), 13);
+        assert_eq!(stats.read_bytes(), 13);
+        assert_eq!(stats.operation(Operation::Write).count(), 1);
+        assert_eq!(stats.operation(Operation::Stat).errors(), 1);
+
+        op.reset_stats();
+        let stats = op.blocking().stats();
+        assert_eq!(stats.written_bytes(), 0);
+        assert_eq!(stats.operation(Operation::Write), OperationStats::default());
+        Ok(())
+    }
 }
@@ -22,6 +22,8 @@
 use std::ops::RangeBounds;
 
 use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Utc;
 use flagset::FlagSet;
 
 use crate::raw::*;
@@ -32,7 +34,8 @@ use crate::*;
 /// The function will consume all the input to generate a result.
 pub(crate) struct OperatorFunction<T, R> {
     inner: FusedAccessor,
-    path: String,
+    /// The error will be returned while calling if the path is invalid.
+    path: Result<String>,
     args: T,
     f: fn(FusedAccessor, String, T) -> Result<R>,
 }
@@ -40,7 +43,7 @@ pub(crate) struct OperatorFunction<T, R> {
 impl<T, R> OperatorFunction<T, R> {
     pub fn new(
         inner: FusedAccessor,
-        path: String,
+        path: Result<String>,
         args: T,
         f: fn(FusedAccessor, String, T) -> Result<R>,
     ) -> Self {
@@ -62,7 +65,7 @@ impl<T, R> OperatorFunction<T, R> {
     }
 
     fn call(self) -> Result<R> {
-        (self.f)(self.inner, self.path, self.args)
+        (self.f)(self.inner, self.path?, self.args)
     }
 }
 
@@ -125,6 +128,44 @@ impl FunctionWrite {
         self
     }
 
+    /// Leave holes for zero ranges instead of writing them out.
+    ///
+    /// Require [`Capability::write_with_sparse`].
+    pub fn sparse(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_sparse(v), bs));
+        self
+    }
+
+    /// Set the user metadata of option
+    ///
+    /// Require [`Capability::write_with_user_metadata`].
+    pub fn user_metadata(mut self, v: impl IntoIterator<Item = (String, String)>) -> Self {
+        let v = v.into_iter().collect();
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_user_metadata(v), bs));
+        self
+    }
+
+    /// Only write if the etag of existing object matches.
+    ///
+    /// Require [`Capability::write_with_if_match`].
+    pub fn if_match(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_if_match(v), bs));
+        self
+    }
+
+    /// Only write if the etag of existing object doesn't match, use `*` to
+    /// write only if the object doesn't exist.
+    ///
+    /// Require [`Capability::write_with_if_none_match`].
+    pub fn if_none_match(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_if_none_match(v), bs));
+        self
+    }
+
     /// Call the function to consume all the input and generate a
     /// result.
     pub fn call(self) -> Result<()> {
@@ -185,6 +226,49 @@ impl FunctionWriter {
         self
     }
 
+    /// Leave holes for zero ranges instead of writing them out.
+    ///
+    /// Require [`Capability::write_with_sparse`].
+    pub fn sparse(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_sparse(v));
+        self
+    }
+
+    /// Set the user metadata of option
+    ///
+    /// Require [`Capability::write_with_user_metadata`].
+    pub fn user_metadata(mut self, v: impl IntoIterator<Item = (String, String)>) -> Self {
+        let v = v.into_iter().collect();
+        self.0 = self.0.map_args(|args| args.with_user_metadata(v));
+        self
+    }
+
+    /// Only write if the etag of existing object matches.
+    ///
+    /// Require [`Capability::write_with_if_match`].
+    pub fn if_match(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_if_match(v));
+        self
+    }
+
+    /// Only write if the etag of existing object doesn't match, use `*` to
+    /// write only if the object doesn't exist.
+    ///
+    /// Require [`Capability::write_with_if_none_match`].
+    pub fn if_none_match(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_if_none_match(v));
+        self
+    }
+
+    /// Digest the content with given algorithm while writing, the digest
+    /// can be fetched by `digest()` of writer after close so that callers
+    /// don't need to read the object again.
+    #[cfg(feature = "digest")]
+    pub fn digest(mut self, v: DigestAlgorithm) -> Self {
+        self.0 = self.0.map_args(|args| args.with_digest(v));
+        self
+    }
+
     /// Call the function to consume all the input and generate a
     /// result.
     pub fn call(self) -> Result<BlockingWriter> {
@@ -204,6 +288,41 @@ impl FunctionDelete {
         self
     }
 
+    /// Move the path into trash instead of removing it permanently.
+    ///
+    /// Require [`Capability::delete_with_trash`].
+    pub fn trash(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_trash(v));
+        self
+    }
+
+    /// Remove all entries inside the dir instead of requiring it to be empty.
+    ///
+    /// Require [`Capability::delete_with_recursive`].
+    pub fn recursive(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_recursive(v));
+        self
+    }
+
+    /// Remove all entries whose path starts with given path.
+    ///
+    /// Require [`Capability::delete_with_prefix`].
+    pub fn prefix(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_prefix(v));
+        self
+    }
+
+    /// Return [`ErrorKind::NotFound`] if the path does not exist instead of
+    /// succeeding silently.
+    ///
+    /// The existence is checked via `stat` before deleting, so the path
+    /// could still be removed by others between them. Returns
+    /// [`ErrorKind::InvalidInput`] if used with `prefix`.
+    pub fn must_exist(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_must_exist(v));
+        self
+    }
+
     /// Call the function to consume all the input and generate a
     /// result.
     pub fn call(self) -> Result<()> {
@@ -226,6 +345,19 @@ impl FunctionList {
         self
     }
 
+    /// The delimiter used by underlying service to group entries into dirs.
+    ///
+    /// By default, `/` is used for non-recursive list and no delimiter is
+    /// used for recursive list. Set to `""` to list all entries flatly.
+    /// Entries grouped by other delimiters will be returned as files since
+    /// their paths don't end with `/`.
+    ///
+    /// Require [`Capability::list_with_delimiter`].
+    pub fn delimiter(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_delimiter(v));
+        self
+    }
+
     /// The start_after passes to underlying service to specify the specified key
     /// to start listing from.
     pub fn start_after(mut self, v: &str) -> Self {
@@ -257,6 +389,29 @@ impl FunctionList {
         self
     }
 
+    /// Only list entries that have been modified since given time, useful
+    /// for incremental jobs that don't want to process unchanged entries.
+    ///
+    /// If service doesn't support [`Capability::list_with_modified_since`],
+    /// lister will fetch `last_modified` of entries and filter them by
+    /// itself. Entries modified exactly at given time will be kept, dirs
+    /// and entries without `last_modified` will always be kept.
+    pub fn modified_since(mut self, v: DateTime<Utc>) -> Self {
+        self.0 = self.0.map_args(|args| args.with_modified_since(v));
+        self
+    }
+
+    /// List entries from given inventory report instead of sending list
+    /// requests to the service.
+    ///
+    /// The operator of inventory must support blocking operations. See
+    /// [`Inventory`] for more details.
+    #[cfg(feature = "inventory")]
+    pub fn source(mut self, v: Inventory) -> Self {
+        self.0 = self.0.map_args(|args| args.with_source(v));
+        self
+    }
+
     /// Call the function to consume all the input and generate a
     /// result.
     pub fn call(self) -> Result<Vec<Entry>> {
@@ -279,6 +434,19 @@ impl FunctionLister {
         self
     }
 
+    /// The delimiter used by underlying service to group entries into dirs.
+    ///
+    /// By default, `/` is used for non-recursive list and no delimiter is
+    /// used for recursive list. Set to `""` to list all entries flatly.
+    /// Entries grouped by other delimiters will be returned as files since
+    /// their paths don't end with `/`.
+    ///
+    /// Require [`Capability::list_with_delimiter`].
+    pub fn delimiter(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_delimiter(v));
+        self
+    }
+
     /// The start_after passes to underlying service to specify the specified key
     /// to start listing from.
     pub fn start_after(mut self, v: &str) -> Self {
@@ -310,6 +478,29 @@ impl FunctionLister {
         self
     }
 
+    /// Only list entries that have been modified since given time, useful
+    /// for incremental jobs that don't want to process unchanged entries.
+    ///
+    /// If service doesn't support [`Capability::list_with_modified_since`],
+    /// lister will fetch `last_modified` of entries and filter them by
+    /// itself. Entries modified exactly at given time will be kept, dirs
+    /// and entries without `last_modified` will always be kept.
+    pub fn modified_since(mut self, v: DateTime<Utc>) -> Self {
+        self.0 = self.0.map_args(|args| args.with_modified_since(v));
+        self
+    }
+
+    /// List entries from given inventory report instead of sending list
+    /// requests to the service.
+    ///
+    /// The operator of inventory must support blocking operations. See
+    /// [`Inventory`] for more details.
+    #[cfg(feature = "inventory")]
+    pub fn source(mut self, v: Inventory) -> Self {
+        self.0 = self.0.map_args(|args| args.with_source(v));
+        self
+    }
+
     /// Call the function to consume all the input and generate a
     /// result.
     pub fn call(self) -> Result<BlockingLister> {
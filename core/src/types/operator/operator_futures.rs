@@ -22,15 +22,21 @@
 use std::mem;
 use std::ops::RangeBounds;
 use std::pin::Pin;
+use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
 use std::time::Duration;
 
 use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Utc;
 use flagset::FlagSet;
 use futures::future::BoxFuture;
+use futures::stream::FuturesOrdered;
 use futures::Future;
 use futures::FutureExt;
+use futures::Stream;
+use futures::StreamExt;
 
 use crate::raw::*;
 use crate::*;
@@ -52,6 +58,9 @@ pub(crate) enum OperatorFuture<T, F> {
     ),
     /// Polling state, waiting for the future to be ready
     Poll(BoxFuture<'static, Result<F>>),
+    /// Failed state, the input is invalid and the error will be returned
+    /// while polling.
+    Failed(Error),
     /// Empty state, the future has been polled and completed or
     /// something is broken during state switch.
     Empty,
@@ -60,11 +69,14 @@ pub(crate) enum OperatorFuture<T, F> {
 impl<T, F> OperatorFuture<T, F> {
     pub fn new(
         inner: FusedAccessor,
-        path: String,
+        path: Result<String>,
         args: T,
         f: fn(FusedAccessor, String, T) -> BoxFuture<'static, Result<F>>,
     ) -> Self {
-        OperatorFuture::Idle(inner, path, args, f)
+        match path {
+            Ok(path) => OperatorFuture::Idle(inner, path, args, f),
+            Err(err) => OperatorFuture::Failed(err),
+        }
     }
 
     fn map_args(self, f: impl FnOnce(T) -> T) -> Self {
@@ -72,6 +84,7 @@ impl<T, F> OperatorFuture<T, F> {
             OperatorFuture::Idle(inner, path, args, func) => {
                 OperatorFuture::Idle(inner, path, f(args), func)
             }
+            OperatorFuture::Failed(err) => OperatorFuture::Failed(err),
             _ => unreachable!("future has been polled and should not be changed again"),
         }
     }
@@ -107,6 +120,7 @@ where
                 Poll::Pending => OperatorFuture::Poll(fut),
                 Poll::Ready(v) => return Poll::Ready(v),
             },
+            OperatorFuture::Failed(err) => return Poll::Ready(Err(err)),
             OperatorFuture::Empty => {
                 panic!("future polled after completion");
             }
@@ -134,10 +148,39 @@ impl FutureStat {
     }
 
     /// Set the version for this operation.
+    ///
+    /// Require [`Capability::stat_with_version`].
     pub fn version(mut self, v: &str) -> Self {
         self.0 = self.0.map_args(|args| args.with_version(v));
         self
     }
+
+    /// Operate on the snapshot with given id instead of the base object.
+    ///
+    /// Require [`Capability::stat_with_snapshot`].
+    pub fn snapshot(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_snapshot(v));
+        self
+    }
+
+    /// Sign requests of this operation by given signer instead of the
+    /// credential configured in service.
+    ///
+    /// Require [`Capability::stat_with_signer`].
+    pub fn signer(mut self, v: RequestSigner) -> Self {
+        self.0 = self.0.map_args(|args| args.with_signer(v));
+        self
+    }
+
+    /// Only fetch given fields of metadata, services could skip fetching
+    /// or parsing other fields to reduce the cost of stat.
+    ///
+    /// [`Metakey::Mode`] is always available, other fields that are not
+    /// required should not be visited.
+    pub fn fields(mut self, v: impl Into<FlagSet<Metakey>>) -> Self {
+        self.0 = self.0.map_args(|args| args.with_fields(v));
+        self
+    }
 }
 
 impl Future for FutureStat {
@@ -295,10 +338,37 @@ impl FutureRead {
     }
 
     /// Set the version for this operation.
+    ///
+    /// Require [`Capability::read_with_version`].
     pub fn version(mut self, v: &str) -> Self {
         self.0 = self.0.map_args(|args| args.with_version(v));
         self
     }
+
+    /// Operate on the snapshot with given id instead of the base object.
+    ///
+    /// Require [`Capability::read_with_snapshot`].
+    pub fn snapshot(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_snapshot(v));
+        self
+    }
+
+    /// Read with given lease id, required if the object has an active lease.
+    ///
+    /// Require [`Capability::read_with_lease_id`].
+    pub fn lease_id(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_lease_id(v));
+        self
+    }
+
+    /// Sign requests of this operation by given signer instead of the
+    /// credential configured in service.
+    ///
+    /// Require [`Capability::read_with_signer`].
+    pub fn signer(mut self, v: RequestSigner) -> Self {
+        self.0 = self.0.map_args(|args| args.with_signer(v));
+        self
+    }
 }
 
 impl Future for FutureRead {
@@ -356,6 +426,39 @@ impl FutureReader {
         self.0 = self.0.map_args(|args| args.with_if_none_match(v));
         self
     }
+
+    /// Sign requests of this operation by given signer instead of the
+    /// credential configured in service.
+    ///
+    /// Require [`Capability::read_with_signer`].
+    pub fn signer(mut self, v: RequestSigner) -> Self {
+        self.0 = self.0.map_args(|args| args.with_signer(v));
+        self
+    }
+
+    /// Read the snapshot with given id instead of the base object.
+    ///
+    /// Require [`Capability::read_with_snapshot`].
+    pub fn snapshot(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_snapshot(v));
+        self
+    }
+
+    /// Read the version with given id instead of the current one.
+    ///
+    /// Require [`Capability::read_with_version`].
+    pub fn version(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_version(v));
+        self
+    }
+
+    /// Read with given lease id, required if the object has an active lease.
+    ///
+    /// Require [`Capability::read_with_lease_id`].
+    pub fn lease_id(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_lease_id(v));
+        self
+    }
 }
 
 impl Future for FutureReader {
@@ -420,6 +523,117 @@ impl FutureWrite {
             .map_args(|(args, bs)| (args.with_cache_control(v), bs));
         self
     }
+
+    /// Leave holes for zero ranges instead of writing them out.
+    ///
+    /// Require [`Capability::write_with_sparse`].
+    pub fn sparse(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_sparse(v), bs));
+        self
+    }
+
+    /// Set the user metadata of option
+    ///
+    /// Require [`Capability::write_with_user_metadata`].
+    pub fn user_metadata(mut self, v: impl IntoIterator<Item = (String, String)>) -> Self {
+        let v = v.into_iter().collect();
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_user_metadata(v), bs));
+        self
+    }
+
+    /// Set the tags of option
+    ///
+    /// Require [`Capability::write_with_tags`].
+    pub fn tags(mut self, v: impl IntoIterator<Item = (String, String)>) -> Self {
+        let v = v.into_iter().collect();
+        self.0 = self.0.map_args(|(args, bs)| (args.with_tags(v), bs));
+        self
+    }
+
+    /// Set the storage class of option, a.k.a, access tier in azblob.
+    ///
+    /// Require [`Capability::write_with_storage_class`].
+    pub fn storage_class(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_storage_class(v), bs));
+        self
+    }
+
+    /// Write with given lease id, required if the object has an active lease.
+    ///
+    /// Require [`Capability::write_with_lease_id`].
+    pub fn lease_id(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_lease_id(v), bs));
+        self
+    }
+
+    /// Only write if the etag of existing object matches.
+    ///
+    /// Require [`Capability::write_with_if_match`].
+    pub fn if_match(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_if_match(v), bs));
+        self
+    }
+
+    /// Only write if the etag of existing object doesn't match, use `*` to
+    /// write only if the object doesn't exist.
+    ///
+    /// Require [`Capability::write_with_if_none_match`].
+    pub fn if_none_match(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_if_none_match(v), bs));
+        self
+    }
+
+    /// Sign requests of this operation by given signer instead of the
+    /// credential configured in service.
+    ///
+    /// Require [`Capability::write_with_signer`].
+    pub fn signer(mut self, v: RequestSigner) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_signer(v), bs));
+        self
+    }
+
+    /// Protect the written object from being overwritten or deleted until
+    /// the retention expires.
+    ///
+    /// Require [`Capability::write_with_retention`].
+    pub fn retention(mut self, v: Retention) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_retention(v), bs));
+        self
+    }
+
+    /// Place or remove legal hold on the written object.
+    ///
+    /// Require [`Capability::write_with_legal_hold`].
+    pub fn legal_hold(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_legal_hold(v), bs));
+        self
+    }
+
+    /// Remove the written object after given time.
+    ///
+    /// Require [`Capability::write_with_expire_at`], which could be
+    /// emulated by [`EmulateLayer::with_expire`](crate::layers::EmulateLayer::with_expire).
+    pub fn expire_at(mut self, v: DateTime<Utc>) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_expire_at(v), bs));
+        self
+    }
+
+    /// Compose the object from the content of given urls in order at server
+    /// side, written content will be placed after them.
+    ///
+    /// Require [`Capability::write_with_compose_sources`].
+    pub fn compose_sources(mut self, v: Vec<String>) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_compose_sources(v), bs));
+        self
+    }
 }
 
 impl Future for FutureWrite {
@@ -461,6 +675,7 @@ impl FutureWriter {
     ///
     /// - AWS S3 requires the part size to be in [5MiB, 5GiB].
     /// - GCS requires the part size to be aligned with 256 KiB.
+    /// - Azblob uses buffer size as the block size, which must be less than 4000 MiB.
     ///
     /// The services will alter the buffer size to meet their requirements.
     pub fn buffer(mut self, v: usize) -> Self {
@@ -468,6 +683,20 @@ impl FutureWriter {
         self
     }
 
+    /// Set the max number of parts that could be uploaded concurrently.
+    ///
+    /// New data will be accepted while previous parts are still in flight,
+    /// so the memory usage could be up to `concurrent` times of buffer size.
+    ///
+    /// ## NOTE
+    ///
+    /// Only services that upload parts independently like s3, oss, cos, obs,
+    /// b2 and azblob support concurrent write, others will ignore this option.
+    pub fn concurrent(mut self, v: usize) -> Self {
+        self.0 = self.0.map_args(|args| args.with_concurrent(v));
+        self
+    }
+
     /// Set the content type of option
     pub fn content_type(mut self, v: &str) -> Self {
         self.0 = self.0.map_args(|args| args.with_content_type(v));
@@ -485,6 +714,128 @@ impl FutureWriter {
         self.0 = self.0.map_args(|args| args.with_cache_control(v));
         self
     }
+
+    /// Leave holes for zero ranges instead of writing them out.
+    ///
+    /// Require [`Capability::write_with_sparse`].
+    pub fn sparse(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_sparse(v));
+        self
+    }
+
+    /// Set the user metadata of option
+    ///
+    /// Require [`Capability::write_with_user_metadata`].
+    pub fn user_metadata(mut self, v: impl IntoIterator<Item = (String, String)>) -> Self {
+        let v = v.into_iter().collect();
+        self.0 = self.0.map_args(|args| args.with_user_metadata(v));
+        self
+    }
+
+    /// Set the tags of option
+    ///
+    /// Require [`Capability::write_with_tags`].
+    pub fn tags(mut self, v: impl IntoIterator<Item = (String, String)>) -> Self {
+        let v = v.into_iter().collect();
+        self.0 = self.0.map_args(|args| args.with_tags(v));
+        self
+    }
+
+    /// Set the storage class of option, a.k.a, access tier in azblob.
+    ///
+    /// Require [`Capability::write_with_storage_class`].
+    pub fn storage_class(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_storage_class(v));
+        self
+    }
+
+    /// Write with given lease id, required if the object has an active lease.
+    ///
+    /// Require [`Capability::write_with_lease_id`].
+    pub fn lease_id(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_lease_id(v));
+        self
+    }
+
+    /// Only write if the etag of existing object matches.
+    ///
+    /// Require [`Capability::write_with_if_match`].
+    pub fn if_match(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_if_match(v));
+        self
+    }
+
+    /// Only write if the etag of existing object doesn't match, use `*` to
+    /// write only if the object doesn't exist.
+    ///
+    /// Require [`Capability::write_with_if_none_match`].
+    pub fn if_none_match(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_if_none_match(v));
+        self
+    }
+
+    /// Sign requests of this operation by given signer instead of the
+    /// credential configured in service.
+    ///
+    /// Require [`Capability::write_with_signer`].
+    pub fn signer(mut self, v: RequestSigner) -> Self {
+        self.0 = self.0.map_args(|args| args.with_signer(v));
+        self
+    }
+
+    /// Protect the written object from being overwritten or deleted until
+    /// the retention expires.
+    ///
+    /// Require [`Capability::write_with_retention`].
+    pub fn retention(mut self, v: Retention) -> Self {
+        self.0 = self.0.map_args(|args| args.with_retention(v));
+        self
+    }
+
+    /// Place or remove legal hold on the written object.
+    ///
+    /// Require [`Capability::write_with_legal_hold`].
+    pub fn legal_hold(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_legal_hold(v));
+        self
+    }
+
+    /// Remove the written object after given time.
+    ///
+    /// Require [`Capability::write_with_expire_at`], which could be
+    /// emulated by [`EmulateLayer::with_expire`](crate::layers::EmulateLayer::with_expire).
+    pub fn expire_at(mut self, v: DateTime<Utc>) -> Self {
+        self.0 = self.0.map_args(|args| args.with_expire_at(v));
+        self
+    }
+
+    /// Compose the object from the content of given urls in order at server
+    /// side, written content will be placed after them.
+    ///
+    /// Require [`Capability::write_with_compose_sources`].
+    pub fn compose_sources(mut self, v: Vec<String>) -> Self {
+        self.0 = self.0.map_args(|args| args.with_compose_sources(v));
+        self
+    }
+
+    /// Digest the content with given algorithm while writing, the digest
+    /// can be fetched by `digest()` of writer after close so that callers
+    /// don't need to read the object again.
+    #[cfg(feature = "digest")]
+    pub fn digest(mut self, v: DigestAlgorithm) -> Self {
+        self.0 = self.0.map_args(|args| args.with_digest(v));
+        self
+    }
+
+    /// Set the behavior of writer when it's dropped without `close` or
+    /// `abort`, or its in-flight operation is cancelled.
+    ///
+    /// The default policy is [`WriterCancelPolicy::Ignore`], see
+    /// [`WriterCancelPolicy`] for more details.
+    pub fn cancel_policy(mut self, v: WriterCancelPolicy) -> Self {
+        self.0 = self.0.map_args(|args| args.with_cancel_policy(v));
+        self
+    }
 }
 
 impl Future for FutureWriter {
@@ -502,10 +853,64 @@ pub struct FutureDelete(pub(crate) OperatorFuture<OpDelete, ()>);
 
 impl FutureDelete {
     /// Change the version of this delete operation.
+    ///
+    /// Require [`Capability::delete_with_version`].
     pub fn version(mut self, v: &str) -> Self {
         self.0 = self.0.map_args(|args| args.with_version(v));
         self
     }
+
+    /// Delete with given lease id, required if the object has an active lease.
+    ///
+    /// Require [`Capability::delete_with_lease_id`].
+    pub fn lease_id(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_lease_id(v));
+        self
+    }
+
+    /// Decide how snapshots of the object will be handled, deleting an
+    /// object that has snapshots will fail if not set.
+    ///
+    /// Require [`Capability::delete_with_snapshots`].
+    pub fn snapshots(mut self, v: DeleteSnapshots) -> Self {
+        self.0 = self.0.map_args(|args| args.with_snapshots(v));
+        self
+    }
+
+    /// Move the path into trash instead of removing it permanently.
+    ///
+    /// Require [`Capability::delete_with_trash`].
+    pub fn trash(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_trash(v));
+        self
+    }
+
+    /// Remove all entries inside the dir instead of requiring it to be empty.
+    ///
+    /// Require [`Capability::delete_with_recursive`].
+    pub fn recursive(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_recursive(v));
+        self
+    }
+
+    /// Remove all entries whose path starts with given path.
+    ///
+    /// Require [`Capability::delete_with_prefix`].
+    pub fn prefix(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_prefix(v));
+        self
+    }
+
+    /// Return [`ErrorKind::NotFound`] if the path does not exist instead of
+    /// succeeding silently.
+    ///
+    /// The existence is checked via `stat` before deleting, so the path
+    /// could still be removed by others between them. Returns
+    /// [`ErrorKind::InvalidInput`] if used with `prefix`.
+    pub fn must_exist(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_must_exist(v));
+        self
+    }
 }
 
 impl Future for FutureDelete {
@@ -516,6 +921,71 @@ impl Future for FutureDelete {
     }
 }
 
+/// Future that generated by [`Operator::copy_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FutureCopy(pub(crate) OperatorFuture<(OpCopy, String), ()>);
+
+impl FutureCopy {
+    /// Set the storage class of the copied object.
+    ///
+    /// Require [`Capability::copy_with_storage_class`].
+    pub fn storage_class(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, to)| (args.with_storage_class(v), to));
+        self
+    }
+
+    /// Replace the content type of the copied object.
+    ///
+    /// Require [`Capability::copy_with_content_type`].
+    pub fn content_type(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, to)| (args.with_content_type(v), to));
+        self
+    }
+
+    /// Replace the cache control of the copied object.
+    ///
+    /// Require [`Capability::copy_with_cache_control`].
+    pub fn cache_control(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, to)| (args.with_cache_control(v), to));
+        self
+    }
+
+    /// Replace the user metadata of the copied object.
+    ///
+    /// Require [`Capability::copy_with_user_metadata`].
+    pub fn user_metadata(mut self, v: impl IntoIterator<Item = (String, String)>) -> Self {
+        let v = v.into_iter().collect();
+        self.0 = self
+            .0
+            .map_args(|(args, to)| (args.with_user_metadata(v), to));
+        self
+    }
+
+    /// Replace the tags of the copied object.
+    ///
+    /// Require [`Capability::copy_with_tags`].
+    pub fn tags(mut self, v: impl IntoIterator<Item = (String, String)>) -> Self {
+        let v = v.into_iter().collect();
+        self.0 = self.0.map_args(|(args, to)| (args.with_tags(v), to));
+        self
+    }
+}
+
+impl Future for FutureCopy {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_unpin(cx)
+    }
+}
+
 /// Future that generated by [`Operator::list_with`].
 ///
 /// Users can add more options by public functions provided by this struct.
@@ -531,6 +1001,19 @@ impl FutureList {
         self
     }
 
+    /// The delimiter used by underlying service to group entries into dirs.
+    ///
+    /// By default, `/` is used for non-recursive list and no delimiter is
+    /// used for recursive list. Set to `""` to list all entries flatly.
+    /// Entries grouped by other delimiters will be returned as files since
+    /// their paths don't end with `/`.
+    ///
+    /// Require [`Capability::list_with_delimiter`].
+    pub fn delimiter(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_delimiter(v));
+        self
+    }
+
     /// The start_after passes to underlying service to specify the specified key
     /// to start listing from.
     pub fn start_after(mut self, v: &str) -> Self {
@@ -561,6 +1044,48 @@ impl FutureList {
         self.0 = self.0.map_args(|args| args.with_metakey(v));
         self
     }
+
+    /// Only list entries that have been modified since given time, useful
+    /// for incremental jobs that don't want to process unchanged entries.
+    ///
+    /// If service doesn't support [`Capability::list_with_modified_since`],
+    /// lister will fetch `last_modified` of entries and filter them by
+    /// itself. Entries modified exactly at given time will be kept, dirs
+    /// and entries without `last_modified` will always be kept.
+    pub fn modified_since(mut self, v: DateTime<Utc>) -> Self {
+        self.0 = self.0.map_args(|args| args.with_modified_since(v));
+        self
+    }
+
+    /// Include snapshots of objects in the result, snapshots will be
+    /// returned with the same path as their base objects and
+    /// [`Metadata::snapshot`] set.
+    ///
+    /// Require [`Capability::list_with_snapshots`].
+    pub fn snapshots(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_snapshots(v));
+        self
+    }
+
+    /// Include versions of objects in the result, versions will be
+    /// returned with the same path as their objects and
+    /// [`Metadata::version`] set.
+    ///
+    /// Require [`Capability::list_with_versions`].
+    pub fn versions(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_versions(v));
+        self
+    }
+
+    /// List entries from given inventory report instead of sending list
+    /// requests to the service.
+    ///
+    /// See [`Inventory`] for more details.
+    #[cfg(feature = "inventory")]
+    pub fn source(mut self, v: Inventory) -> Self {
+        self.0 = self.0.map_args(|args| args.with_source(v));
+        self
+    }
 }
 
 impl Future for FutureList {
@@ -586,6 +1111,19 @@ impl FutureLister {
         self
     }
 
+    /// The delimiter used by underlying service to group entries into dirs.
+    ///
+    /// By default, `/` is used for non-recursive list and no delimiter is
+    /// used for recursive list. Set to `""` to list all entries flatly.
+    /// Entries grouped by other delimiters will be returned as files since
+    /// their paths don't end with `/`.
+    ///
+    /// Require [`Capability::list_with_delimiter`].
+    pub fn delimiter(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_delimiter(v));
+        self
+    }
+
     /// The start_after passes to underlying service to specify the specified key
     /// to start listing from.
     pub fn start_after(mut self, v: &str) -> Self {
@@ -617,6 +1155,38 @@ impl FutureLister {
         self
     }
 
+    /// Only list entries that have been modified since given time, useful
+    /// for incremental jobs that don't want to process unchanged entries.
+    ///
+    /// If service doesn't support [`Capability::list_with_modified_since`],
+    /// lister will fetch `last_modified` of entries and filter them by
+    /// itself. Entries modified exactly at given time will be kept, dirs
+    /// and entries without `last_modified` will always be kept.
+    pub fn modified_since(mut self, v: DateTime<Utc>) -> Self {
+        self.0 = self.0.map_args(|args| args.with_modified_since(v));
+        self
+    }
+
+    /// Include snapshots of objects in the result, snapshots will be
+    /// returned with the same path as their base objects and
+    /// [`Metadata::snapshot`] set.
+    ///
+    /// Require [`Capability::list_with_snapshots`].
+    pub fn snapshots(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_snapshots(v));
+        self
+    }
+
+    /// Include versions of objects in the result, versions will be
+    /// returned with the same path as their objects and
+    /// [`Metadata::version`] set.
+    ///
+    /// Require [`Capability::list_with_versions`].
+    pub fn versions(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_versions(v));
+        self
+    }
+
     /// Concurrent is used to control the number of concurrent stat requests.
     ///
     /// If concurrent is set to <=1, the lister will perform stat requests sequentially.
@@ -626,6 +1196,46 @@ impl FutureLister {
         self.0 = self.0.map_args(|args| args.with_concurrent(v));
         self
     }
+
+    /// The max number of entries that could be queued inside lister,
+    /// including the entries waiting for stat.
+    ///
+    /// Lister will stop fetching more entries while the queue is full.
+    /// The queue size must not be less than `concurrent`, default to
+    /// the same as `concurrent`.
+    pub fn stat_queue_size(mut self, v: usize) -> Self {
+        self.0 = self.0.map_args(|args| args.with_stat_queue_size(v));
+        self
+    }
+
+    /// The backpressure strategy while stat queue is full.
+    ///
+    /// The default strategy is [`ListBackpressure::Ordered`].
+    pub fn stat_backpressure(mut self, v: ListBackpressure) -> Self {
+        self.0 = self.0.map_args(|args| args.with_stat_backpressure(v));
+        self
+    }
+
+    /// Perform stat operations inline instead of spawning tokio tasks.
+    ///
+    /// This is useful for environments where `tokio::spawn` is not
+    /// desirable like single-thread runtime or wasm. Stat operations
+    /// will still be concurrent but only make progress while polling
+    /// the lister.
+    pub fn stat_inline(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_stat_inline(v));
+        self
+    }
+
+    /// List entries from given inventory report instead of sending list
+    /// requests to the service.
+    ///
+    /// See [`Inventory`] for more details.
+    #[cfg(feature = "inventory")]
+    pub fn source(mut self, v: Inventory) -> Self {
+        self.0 = self.0.map_args(|args| args.with_source(v));
+        self
+    }
 }
 
 impl Future for FutureLister {
@@ -635,3 +1245,214 @@ impl Future for FutureLister {
         self.0.poll_unpin(cx)
     }
 }
+
+/// Future that generated by [`Operator::remove_via`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FutureRemoveVia<S> {
+    op: Operator,
+    /// The input stream, `None` means it has been exhausted.
+    input: Option<S>,
+    max_failures: usize,
+    results: BatchResults<RpDelete>,
+    /// Paths collected for the next batch call.
+    paths: Vec<String>,
+    /// The in-flight batch call and its input paths.
+    batch: Option<(Vec<String>, BoxFuture<'static, Result<RpBatch>>)>,
+    /// The in-flight delete calls if service doesn't support batch.
+    deletes: FuturesOrdered<BoxFuture<'static, (String, Result<RpDelete>)>>,
+}
+
+impl<S> FutureRemoveVia<S> {
+    pub(crate) fn new(op: Operator, input: S) -> Self {
+        Self {
+            op,
+            input: Some(input),
+            max_failures: 0,
+            results: BatchResults::default(),
+            paths: Vec::new(),
+            batch: None,
+            deletes: FuturesOrdered::new(),
+        }
+    }
+
+    /// Set the max number of failures allowed before aborting.
+    ///
+    /// Failed paths will be recorded in [`BatchResults`] until the number
+    /// of failures exceeds this value, then the error of the last failure
+    /// will be returned directly.
+    ///
+    /// Default to `0` which means abort on the first failure.
+    pub fn max_failures(mut self, v: usize) -> Self {
+        self.max_failures = v;
+        self
+    }
+}
+
+impl<S> FutureRemoveVia<S>
+where
+    S: Stream<Item = String> + Unpin,
+{
+    /// Poll next path from input, returns `Ready(None)` if input has been
+    /// exhausted.
+    fn poll_path(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<String>>> {
+        let Some(input) = self.input.as_mut() else {
+            return Poll::Ready(None);
+        };
+
+        match ready!(input.poll_next_unpin(cx)) {
+            Some(path) => Poll::Ready(Some(self.op.normalize(&path))),
+            None => {
+                self.input = None;
+                Poll::Ready(None)
+            }
+        }
+    }
+
+    /// Delete paths by chunks via batch calls.
+    fn poll_batch(&mut self, cx: &mut Context<'_>) -> Poll<Result<BatchResults<RpDelete>>> {
+        loop {
+            if let Some((_, fut)) = self.batch.as_mut() {
+                let rp = ready!(fut.poll_unpin(cx));
+                let (paths, _) = self.batch.take().expect("batch must be in-flight");
+                self.results.push_batch(paths, rp?, self.max_failures)?;
+            }
+
+            while self.paths.len() < self.op.limit() {
+                match ready!(self.poll_path(cx)) {
+                    Some(path) => self.paths.push(path?),
+                    None => break,
+                }
+            }
+            if self.paths.is_empty() {
+                return Poll::Ready(Ok(mem::take(&mut self.results)));
+            }
+
+            let paths = mem::take(&mut self.paths);
+            let ops = paths
+                .iter()
+                .map(|v| (v.clone(), OpDelete::default().into()))
+                .collect();
+            let acc = self.op.inner().clone();
+            let fut = async move { acc.batch(OpBatch::new(ops)).await }.boxed();
+            self.batch = Some((paths, fut));
+        }
+    }
+
+    /// Delete paths one by one concurrently, results are kept in the
+    /// same order as input.
+    fn poll_delete(&mut self, cx: &mut Context<'_>) -> Poll<Result<BatchResults<RpDelete>>> {
+        loop {
+            while self.deletes.len() < self.op.limit() {
+                let path = match self.poll_path(cx) {
+                    Poll::Ready(Some(path)) => path?,
+                    Poll::Ready(None) | Poll::Pending => break,
+                };
+                let acc = self.op.inner().clone();
+                self.deletes.push_back(
+                    async move {
+                        let res = acc.delete(&path, OpDelete::default()).await;
+                        (path, res)
+                    }
+                    .boxed(),
+                );
+            }
+
+            match ready!(self.deletes.poll_next_unpin(cx)) {
+                Some((path, res)) => self.results.push(path, res, self.max_failures)?,
+                // No in-flight deletes, wait for more input.
+                None if self.input.is_some() => return Poll::Pending,
+                None => return Poll::Ready(Ok(mem::take(&mut self.results))),
+            }
+        }
+    }
+}
+
+impl<S> Future for FutureRemoveVia<S>
+where
+    S: Stream<Item = String> + Unpin,
+{
+    type Output = Result<BatchResults<RpDelete>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.op.info().full_capability().batch {
+            self.poll_batch(cx)
+        } else {
+            self.poll_delete(cx)
+        }
+    }
+}
+
+/// Future that generated by [`Operator::rename_all`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FutureRenameAll {
+    op: Operator,
+    from: String,
+    to: String,
+    checkpoint: Option<String>,
+    max_failures: usize,
+    fut: Option<BoxFuture<'static, Result<BatchResults<RpRename>>>>,
+}
+
+impl FutureRenameAll {
+    pub(crate) fn new(op: Operator, from: &str, to: &str) -> Self {
+        Self {
+            op,
+            from: from.to_string(),
+            to: to.to_string(),
+            checkpoint: None,
+            max_failures: 0,
+            fut: None,
+        }
+    }
+
+    /// Record moved paths in the checkpoint dir at given path.
+    ///
+    /// Every batch of moved paths will be written as a new file under this
+    /// dir, so recording costs stay linear to the number of files. Paths
+    /// recorded in an existing checkpoint will be skipped, so that an
+    /// interrupted rename could be resumed.
+    pub fn checkpoint(mut self, path: &str) -> Self {
+        self.checkpoint = Some(path.to_string());
+        self
+    }
+
+    /// Set the max number of failures allowed before aborting.
+    ///
+    /// Failed paths will be recorded in [`BatchResults`] until the number
+    /// of failures exceeds this value, then the error of the last failure
+    /// will be returned directly.
+    ///
+    /// Default to `0` which means abort on the first failure.
+    pub fn max_failures(mut self, v: usize) -> Self {
+        self.max_failures = v;
+        self
+    }
+}
+
+impl Future for FutureRenameAll {
+    type Output = Result<BatchResults<RpRename>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.fut.is_none() {
+            let op = self.op.clone();
+            let from = mem::take(&mut self.from);
+            let to = mem::take(&mut self.to);
+            let checkpoint = self.checkpoint.take();
+            let max_failures = self.max_failures;
+            self.fut = Some(
+                async move {
+                    op.rename_dir(&from, &to, checkpoint.as_deref(), max_failures)
+                        .await
+                }
+                .boxed(),
+            );
+        }
+
+        self.fut
+            .as_mut()
+            .expect("future must be initialized")
+            .poll_unpin(cx)
+    }
+}
@@ -0,0 +1,371 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io::SeekFrom;
+use std::ops::Range;
+use std::sync::Arc;
+
+use ::parquet::file::footer::decode_footer;
+use ::parquet::file::footer::decode_metadata;
+use ::parquet::file::metadata::ParquetMetaData;
+use bytes::Bytes;
+use bytes::BytesMut;
+
+use crate::raw::oio::ReadExt;
+use crate::*;
+
+/// The size of parquet footer: 4 bytes metadata length and `PAR1`.
+const FOOTER_SIZE: usize = 8;
+/// The size of tail that will be fetched while opening.
+const DEFAULT_FOOTER_PREFETCH: usize = 64 * 1024;
+/// Ranges whose gap is smaller than this will be fetched together.
+const DEFAULT_COALESCE: u64 = 1024 * 1024;
+
+/// ParquetReader reads parquet files in row-group granularity.
+///
+/// While opening, the footer and metadata will be fetched by one tail
+/// read (via `seek` from the end, which will be emulated if services don't
+/// support suffix range). Then column chunks of row groups can be fetched
+/// by [`ParquetReader::read_row_group`], adjacent chunks will be
+/// coalesced into one ranged read to reduce requests.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::Operator;
+/// use opendal::ParquetReader;
+/// use opendal::Result;
+///
+/// async fn test(op: Operator) -> Result<()> {
+///     let r = op.reader("data.parquet").await?;
+///     let mut pr = ParquetReader::new(r).await?;
+///
+///     for idx in 0..pr.metadata().num_row_groups() {
+///         // Only fetch the first and third column.
+///         let rg = pr.read_row_group(idx, &[0, 2]).await?;
+///         let chunk = rg.column(0).expect("column must be fetched");
+///         println!("column chunk has {} bytes", chunk.len());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct ParquetReader {
+    reader: Reader,
+    size: u64,
+    metadata: Arc<ParquetMetaData>,
+    coalesce: u64,
+}
+
+impl ParquetReader {
+    /// Open a parquet file by fetching its footer.
+    pub async fn new(reader: Reader) -> Result<Self> {
+        Self::with_footer_prefetch(reader, DEFAULT_FOOTER_PREFETCH).await
+    }
+
+    /// Open a parquet file by fetching its footer, `prefetch` bytes will
+    /// be fetched from the end of file in the first read.
+    ///
+    /// Metadata larger than `prefetch` requires one more read.
+    pub async fn with_footer_prefetch(mut reader: Reader, prefetch: usize) -> Result<Self> {
+        let size = reader.seek(SeekFrom::End(0)).await?;
+        if size < FOOTER_SIZE as u64 {
+            return Err(new_parquet_error("file is too small to be parquet"));
+        }
+
+        let prefetch = (prefetch.max(FOOTER_SIZE) as u64).min(size);
+        let tail = read_range(&mut reader, size - prefetch..size).await?;
+
+        let mut footer = [0; FOOTER_SIZE];
+        footer.copy_from_slice(&tail[tail.len() - FOOTER_SIZE..]);
+        let metadata_len = decode_footer(&footer).map_err(new_parquet_source_error)?;
+        let total = match metadata_len.checked_add(FOOTER_SIZE) {
+            Some(total) if total as u64 <= size => total as u64,
+            _ => return Err(new_parquet_error("metadata length exceeds file size")),
+        };
+
+        let metadata = if total <= prefetch {
+            let start = tail.len() - total as usize;
+            decode_metadata(&tail[start..tail.len() - FOOTER_SIZE])
+        } else {
+            let bs = read_range(&mut reader, size - total..size - FOOTER_SIZE as u64).await?;
+            decode_metadata(&bs)
+        }
+        .map_err(new_parquet_source_error)?;
+
+        Ok(Self {
+            reader,
+            size,
+            metadata: Arc::new(metadata),
+            coalesce: DEFAULT_COALESCE,
+        })
+    }
+
+    /// Set the max gap between column chunks that will be fetched in one
+    /// read.
+    ///
+    /// Default to 1 MiB, set to `0` to only merge adjacent chunks.
+    pub fn with_coalesce(mut self, gap: u64) -> Self {
+        self.coalesce = gap;
+        self
+    }
+
+    /// Get the metadata of this parquet file.
+    pub fn metadata(&self) -> &Arc<ParquetMetaData> {
+        &self.metadata
+    }
+
+    /// Fetch column chunks of given row group.
+    ///
+    /// `columns` are the index of columns to fetch, all columns will be
+    /// fetched if it's empty.
+    ///
+    /// Byte ranges of column chunks come from the file metadata and will be
+    /// rejected if they don't fit in the file.
+    pub async fn read_row_group(
+        &mut self,
+        row_group: usize,
+        columns: &[usize],
+    ) -> Result<ParquetRowGroup> {
+        if row_group >= self.metadata.num_row_groups() {
+            return Err(new_parquet_error("row group is out of range")
+                .with_context("row_group", row_group.to_string()));
+        }
+        let rg = self.metadata.row_group(row_group);
+
+        let columns: Vec<usize> = if columns.is_empty() {
+            (0..rg.num_columns()).collect()
+        } else {
+            columns.to_vec()
+        };
+        let mut ranges = Vec::with_capacity(columns.len());
+        for &idx in &columns {
+            if idx >= rg.num_columns() {
+                return Err(new_parquet_error("column is out of range")
+                    .with_context("column", idx.to_string()));
+            }
+            let (start, len) = rg.column(idx).byte_range();
+            match start.checked_add(len) {
+                Some(end) if end <= self.size => ranges.push(start..end),
+                _ => {
+                    return Err(new_parquet_error("column chunk exceeds file size")
+                        .with_context("column", idx.to_string())
+                        .with_context("start", start.to_string())
+                        .with_context("length", len.to_string())
+                        .with_context("size", self.size.to_string()))
+                }
+            }
+        }
+
+        let mut fetched: Vec<(Range<u64>, Bytes)> = vec![];
+        for range in coalesce_ranges(&ranges, self.coalesce) {
+            let bs = read_range(&mut self.reader, range.clone()).await?;
+            fetched.push((range, bs));
+        }
+
+        let chunks = columns
+            .into_iter()
+            .zip(ranges)
+            .map(|(idx, range)| {
+                let (fr, bs) = fetched
+                    .iter()
+                    .find(|(fr, _)| fr.start <= range.start && range.end <= fr.end)
+                    .expect("range must be fetched");
+                let offset = (range.start - fr.start) as usize;
+                let len = (range.end - range.start) as usize;
+                (idx, bs.slice(offset..offset + len))
+            })
+            .collect();
+
+        Ok(ParquetRowGroup { row_group, chunks })
+    }
+}
+
+/// ParquetRowGroup is the column chunks fetched by
+/// [`ParquetReader::read_row_group`].
+#[derive(Debug, Clone)]
+pub struct ParquetRowGroup {
+    row_group: usize,
+    chunks: Vec<(usize, Bytes)>,
+}
+
+impl ParquetRowGroup {
+    /// The index of this row group.
+    pub fn row_group(&self) -> usize {
+        self.row_group
+    }
+
+    /// Get the bytes of given column chunk, returns `None` if not fetched.
+    pub fn column(&self, idx: usize) -> Option<&Bytes> {
+        self.chunks
+            .iter()
+            .find(|(i, _)| *i == idx)
+            .map(|(_, bs)| bs)
+    }
+
+    /// Iterate over all fetched column chunks in the order of request.
+    pub fn columns(&self) -> impl Iterator<Item = (usize, &Bytes)> {
+        self.chunks.iter().map(|(i, bs)| (*i, bs))
+    }
+}
+
+/// Merge ranges whose gap is not larger than `coalesce`.
+fn coalesce_ranges(ranges: &[Range<u64>], coalesce: u64) -> Vec<Range<u64>> {
+    let mut ranges = ranges.to_vec();
+    ranges.sort_by_key(|r| r.start);
+
+    let mut merged: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end.saturating_add(coalesce) => {
+                last.end = last.end.max(range.end);
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Read exactly the given range from reader.
+async fn read_range(reader: &mut Reader, range: Range<u64>) -> Result<Bytes> {
+    reader.seek(SeekFrom::Start(range.start)).await?;
+
+    let size = (range.end - range.start) as usize;
+    let mut buf = BytesMut::zeroed(size);
+    let mut read = 0;
+    while read < size {
+        let n = reader.read(&mut buf[read..]).await?;
+        if n == 0 {
+            return Err(
+                Error::new(ErrorKind::ContentIncomplete, "reader got too little data")
+                    .with_context("expect", size.to_string())
+                    .with_context("actual", read.to_string()),
+            );
+        }
+        read += n;
+    }
+    Ok(buf.freeze())
+}
+
+fn new_parquet_error(message: &str) -> Error {
+    Error::new(ErrorKind::Unexpected, message).with_context("format", "parquet")
+}
+
+fn new_parquet_source_error(err: ::parquet::errors::ParquetError) -> Error {
+    new_parquet_error("parse parquet metadata failed").set_source(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[test]
+    fn test_coalesce_ranges() {
+        let cases = vec![
+            ("empty", vec![], 0, vec![]),
+            ("adjacent", vec![0..10, 10..20], 0, vec![0..20]),
+            ("gap", vec![0..10, 15..20], 4, vec![0..10, 15..20]),
+            ("small gap", vec![15..20, 0..10], 5, vec![0..20]),
+            ("overlap", vec![0..10, 5..8, 9..12], 0, vec![0..12]),
+        ];
+
+        for (name, input, coalesce, expected) in cases {
+            assert_eq!(coalesce_ranges(&input, coalesce), expected, "{name}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_parquet() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        op.write("small", "PAR1").await?;
+        op.write("invalid", "Hello, World!").await?;
+
+        for path in ["small", "invalid"] {
+            let r = op.reader(path).await?;
+            let err = ParquetReader::new(r).await.err().expect("must fail");
+            assert_eq!(err.kind(), ErrorKind::Unexpected, "{path}");
+        }
+        Ok(())
+    }
+
+    /// Build a parquet file with given row groups of `(a: INT32, b: INT64)`.
+    fn build_parquet(row_groups: &[(Vec<i32>, Vec<i64>)]) -> Vec<u8> {
+        use ::parquet::column::writer::ColumnWriter;
+        use ::parquet::file::properties::WriterProperties;
+        use ::parquet::file::writer::SerializedFileWriter;
+        use ::parquet::schema::parser::parse_message_type;
+
+        let schema = parse_message_type("message test { REQUIRED INT32 a; REQUIRED INT64 b; }")
+            .expect("schema must be valid");
+        let props = WriterProperties::builder().build();
+
+        let mut buf = vec![];
+        let mut w = SerializedFileWriter::new(&mut buf, Arc::new(schema), Arc::new(props))
+            .expect("writer must be created");
+        for (a, b) in row_groups {
+            let mut rg = w.next_row_group().expect("row group must be created");
+            while let Some(mut col) = rg.next_column().expect("column must be valid") {
+                match col.untyped() {
+                    ColumnWriter::Int32ColumnWriter(cw) => cw.write_batch(a, None, None),
+                    ColumnWriter::Int64ColumnWriter(cw) => cw.write_batch(b, None, None),
+                    _ => unreachable!("only int32 and int64 columns are used"),
+                }
+                .expect("write batch must succeed");
+                col.close().expect("column must be closed");
+            }
+            rg.close().expect("row group must be closed");
+        }
+        w.close().expect("writer must be closed");
+        buf
+    }
+
+    #[tokio::test]
+    async fn test_read_row_group() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        let content = build_parquet(&[
+            (vec![1, 2, 3], vec![10, 20, 30]),
+            (vec![4, 5], vec![40, 50]),
+        ]);
+        op.write("data.parquet", content.clone()).await?;
+
+        // Use a small prefetch to fetch metadata by another read.
+        let r = op.reader("data.parquet").await?;
+        let mut pr = ParquetReader::with_footer_prefetch(r, 16)
+            .await?
+            .with_coalesce(0);
+        assert_eq!(pr.metadata().num_row_groups(), 2);
+        assert_eq!(pr.metadata().row_group(1).num_rows(), 2);
+
+        let rg = pr.read_row_group(1, &[1]).await?;
+        assert_eq!(rg.row_group(), 1);
+        assert!(rg.column(0).is_none());
+        let (start, len) = pr.metadata().row_group(1).column(1).byte_range();
+        assert_eq!(
+            rg.column(1).expect("column must be fetched").as_ref(),
+            &content[start as usize..(start + len) as usize]
+        );
+
+        let rg = pr.read_row_group(0, &[]).await?;
+        assert_eq!(rg.columns().map(|(idx, _)| idx).collect::<Vec<_>>(), [0, 1]);
+
+        let err = pr.read_row_group(2, &[]).await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        let err = pr.read_row_group(0, &[2]).await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        Ok(())
+    }
+}
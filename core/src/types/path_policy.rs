@@ -0,0 +1,185 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::borrow::Cow;
+
+#[cfg(feature = "path-nfc")]
+use unicode_normalization::is_nfc;
+#[cfg(feature = "path-nfc")]
+use unicode_normalization::UnicodeNormalization;
+
+use crate::*;
+
+/// PathPolicy validates and sanitizes paths at the [`Operator`] boundary,
+/// set via [`Operator::with_path_policy`].
+///
+/// Paths are checked before normalization, a rejected path will return
+/// [`ErrorKind::InvalidInput`] with `reason` in context without reaching
+/// the service.
+///
+/// The default policy accepts everything. Use [`PathPolicy::strict`] for
+/// servers that build paths from untrusted input.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::services::Memory;
+/// use opendal::Operator;
+/// use opendal::PathPolicy;
+///
+/// # fn main() -> opendal::Result<()> {
+/// let op = Operator::new(Memory::default())?
+///     .finish()
+///     .with_path_policy(PathPolicy::strict().with_max_length(255));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathPolicy {
+    reject_parent_dir: bool,
+    reject_control_chars: bool,
+    reject_backslash: bool,
+    max_length: Option<usize>,
+    #[cfg(feature = "path-nfc")]
+    normalize_nfc: bool,
+}
+
+impl PathPolicy {
+    /// Create a policy that rejects `.` and `..`, control characters and
+    /// backslashes, limits paths to 1024 bytes and normalizes them to NFC
+    /// if feature `path-nfc` is enabled.
+    pub fn strict() -> Self {
+        Self {
+            reject_parent_dir: true,
+            reject_control_chars: true,
+            reject_backslash: true,
+            max_length: Some(1024),
+            #[cfg(feature = "path-nfc")]
+            normalize_nfc: true,
+        }
+    }
+
+    /// Reject paths that contain `.` or `..` segments, which are kept as is
+    /// by normalization instead of being resolved.
+    pub fn with_reject_parent_dir(mut self, v: bool) -> Self {
+        self.reject_parent_dir = v;
+        self
+    }
+
+    /// Reject paths that contain control characters like `\0` and `\n`.
+    pub fn with_reject_control_chars(mut self, v: bool) -> Self {
+        self.reject_control_chars = v;
+        self
+    }
+
+    /// Reject paths that contain `\`, which are separators on windows.
+    pub fn with_reject_backslash(mut self, v: bool) -> Self {
+        self.reject_backslash = v;
+        self
+    }
+
+    /// Reject paths longer than given bytes.
+    pub fn with_max_length(mut self, v: usize) -> Self {
+        self.max_length = Some(v);
+        self
+    }
+
+    /// Normalize paths to unicode NFC so that the same name in different
+    /// forms maps to the same key.
+    ///
+    /// This function is only available with feature `path-nfc`.
+    #[cfg(feature = "path-nfc")]
+    pub fn with_normalize_nfc(mut self, v: bool) -> Self {
+        self.normalize_nfc = v;
+        self
+    }
+
+    /// Check given path, returns the sanitized path if it's accepted.
+    pub fn check<'a>(&self, path: &'a str) -> Result<Cow<'a, str>> {
+        if self.reject_parent_dir
+            && path
+                .split(['/', '\\'])
+                .any(|v| v.trim() == ".." || v.trim() == ".")
+        {
+            return Err(new_rejected_error(path, "path contains `.` or `..`"));
+        }
+        if self.reject_control_chars && path.chars().any(char::is_control) {
+            return Err(new_rejected_error(path, "path contains control characters"));
+        }
+        if self.reject_backslash && path.contains('\\') {
+            return Err(new_rejected_error(path, "path contains `\\`"));
+        }
+
+        #[cfg(feature = "path-nfc")]
+        let path = if self.normalize_nfc && !is_nfc(path) {
+            Cow::Owned(path.nfc().collect())
+        } else {
+            Cow::Borrowed(path)
+        };
+        #[cfg(not(feature = "path-nfc"))]
+        let path = Cow::Borrowed(path);
+
+        if let Some(max) = self.max_length {
+            if path.len() > max {
+                return Err(new_rejected_error(&path, "path is too long")
+                    .with_context("max_length", max.to_string()));
+            }
+        }
+        Ok(path)
+    }
+}
+
+fn new_rejected_error(path: &str, reason: &'static str) -> Error {
+    Error::new(ErrorKind::InvalidInput, "path is rejected by path policy")
+        .with_context("path", path.escape_debug().to_string())
+        .with_context("reason", reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_policy() {
+        let policy = PathPolicy::strict().with_max_length(16);
+
+        let cases = vec![
+            ("normal path", "abc/def", true),
+            ("dot file", "abc/.def", true),
+            ("current dir", "abc/./def", false),
+            ("parent dir", "abc/../def", false),
+            ("parent dir with backslash", "abc\\..", false),
+            ("control chars", "abc\0def", false),
+            ("newline", "abc\ndef", false),
+            ("backslash", "abc\\def", false),
+            ("too long", "abcdefghijklmnopq", false),
+        ];
+        for (name, path, accepted) in cases {
+            let res = policy.check(path);
+            assert_eq!(res.is_ok(), accepted, "{name}");
+            if let Err(err) = res {
+                assert_eq!(err.kind(), ErrorKind::InvalidInput, "{name}");
+            }
+        }
+
+        // `e` + combining acute accent will be normalized into `é`.
+        #[cfg(feature = "path-nfc")]
+        assert_eq!(policy.check("cafe\u{301}").unwrap(), "caf\u{e9}");
+        // Default policy accepts everything.
+        assert_eq!(PathPolicy::default().check("../a\0").unwrap(), "../a\0");
+    }
+}
@@ -74,6 +74,29 @@ impl Reader {
             seek_state: SeekState::Init,
         })
     }
+
+    /// Convert reader into a [`Stream`] of `Result<Bytes>`.
+    ///
+    /// Unlike the `Stream` implemented by reader itself, errors returned
+    /// by this stream are OpenDAL's [`Error`] so users can check the
+    /// [`ErrorKind`] directly.
+    pub fn into_stream(self) -> ReaderStream {
+        ReaderStream { inner: self.inner }
+    }
+}
+
+/// ReaderStream is a [`Stream`] of `Result<Bytes>` returned by
+/// [`Reader::into_stream`].
+pub struct ReaderStream {
+    inner: oio::Reader,
+}
+
+impl Stream for ReaderStream {
+    type Item = Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        oio::Read::poll_next(&mut self.inner, cx)
+    }
 }
 
 impl oio::Read for Reader {
@@ -171,12 +194,16 @@ impl Stream for Reader {
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         Pin::new(&mut self.inner)
             .poll_next(cx)
-            .map_err(|err| io::Error::new(io::ErrorKind::Interrupted, err))
+            .map_err(io::Error::from)
     }
 }
 
 /// BlockingReader is designed to read data from given path in an blocking
 /// manner.
+///
+/// BlockingReader implements [`io::Read`] and [`io::Seek`], so it can be
+/// used directly with crates that expect std traits like `zip`, `tar`
+/// and `csv`.
 pub struct BlockingReader {
     pub(crate) inner: oio::BlockingReader,
 }
@@ -232,9 +259,7 @@ impl Iterator for BlockingReader {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.inner
-            .next()
-            .map(|v| v.map_err(|err| io::Error::new(io::ErrorKind::Interrupted, err)))
+        self.inner.next().map(|v| v.map_err(io::Error::from))
     }
 }
 
@@ -246,6 +271,10 @@ mod tests {
     use tokio::io::AsyncReadExt;
     use tokio::io::AsyncSeekExt;
 
+    use futures::StreamExt;
+    use futures::TryStreamExt;
+
+    use super::*;
     use crate::services;
     use crate::Operator;
 
@@ -306,4 +335,133 @@ mod tests {
             .expect("read to end must succeed");
         assert_eq!(buf, content);
     }
+
+    #[test]
+    fn test_blocking_reader_std_io() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .finish()
+            .blocking();
+        let path = "test_file";
+
+        let content = gen_random_bytes();
+        let mut w = op.writer(path).unwrap();
+        io::copy(&mut content.as_slice(), &mut w).expect("copy must succeed");
+        w.close().expect("close must succeed");
+
+        let mut r = op.reader(path).unwrap();
+        let mut buf = Vec::new();
+        io::Read::read_to_end(&mut r, &mut buf).expect("read to end must succeed");
+        assert_eq!(buf, content);
+
+        let pos = io::Seek::seek(&mut r, io::SeekFrom::Start(1)).unwrap();
+        assert_eq!(pos, 1);
+        let mut buf = Vec::new();
+        io::Read::read_to_end(&mut r, &mut buf).expect("read to end must succeed");
+        assert_eq!(buf, content[1..]);
+    }
+
+    #[tokio::test]
+    async fn test_reader_into_stream_and_writer_into_sink() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        let content = gen_random_bytes();
+        let chunks: Vec<_> = content
+            .chunks(4096)
+            .map(|v| Ok(Bytes::copy_from_slice(v)))
+            .collect();
+        let w = op.writer(path).await.unwrap();
+        futures::stream::iter(chunks)
+            .forward(w.into_sink())
+            .await
+            .expect("forward must succeed");
+
+        let stream = op.reader(path).await.unwrap().into_stream();
+        let buf: Vec<Bytes> = stream.try_collect().await.expect("read must succeed");
+        assert_eq!(buf.concat(), content);
+    }
+
+    #[tokio::test]
+    async fn test_reader_stream_error_kind() {
+        let mut r = Reader {
+            inner: Box::new(()),
+            seek_state: SeekState::Init,
+        };
+
+        let err = StreamExt::next(&mut r).await.unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn test_blocking_reader_iterator_error_kind() {
+        let mut r = BlockingReader {
+            inner: Box::new(()),
+        };
+
+        let err = Iterator::next(&mut r).unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+
+    /// A reader that returns the first chunk and then fails.
+    struct BrokenReader {
+        polled: bool,
+    }
+
+    impl oio::Read for BrokenReader {
+        fn poll_read(&mut self, _: &mut Context<'_>, _: &mut [u8]) -> Poll<Result<usize>> {
+            unimplemented!("only poll_next is used")
+        }
+
+        fn poll_seek(&mut self, _: &mut Context<'_>, _: io::SeekFrom) -> Poll<Result<u64>> {
+            unimplemented!("only poll_next is used")
+        }
+
+        fn poll_next(&mut self, _: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+            if self.polled {
+                return Poll::Ready(Some(Err(Error::new(
+                    ErrorKind::ContentIncomplete,
+                    "connection reset",
+                ))));
+            }
+            self.polled = true;
+            Poll::Ready(Some(Ok(Bytes::from("Hello, "))))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reader_into_stream_keeps_error_kind() {
+        let r = Reader {
+            inner: Box::new(BrokenReader { polled: false }),
+            seek_state: SeekState::Init,
+        };
+        let mut stream = r.into_stream();
+
+        let bs = stream
+            .next()
+            .await
+            .unwrap()
+            .expect("first chunk must succeed");
+        assert_eq!(bs, "Hello, ");
+        let err = stream.next().await.unwrap().expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::ContentIncomplete);
+    }
+
+    #[tokio::test]
+    async fn test_reader_into_stream_with_range() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+        op.write(path, "Hello, World!")
+            .await
+            .expect("write must succeed");
+
+        let stream = op
+            .reader_with(path)
+            .range(7..12)
+            .await
+            .unwrap()
+            .into_stream();
+        let buf: Vec<Bytes> = stream.try_collect().await.expect("read must succeed");
+        assert_eq!(buf.concat(), b"World");
+    }
 }
@@ -0,0 +1,57 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use chrono::DateTime;
+use chrono::Utc;
+
+/// RetentionMode decides who can shorten or remove a [`Retention`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetentionMode {
+    /// Users with special permissions can shorten or remove the retention.
+    ///
+    /// Maps to `GOVERNANCE` in S3 and `Unlocked` in Azure.
+    Governance,
+    /// Nobody can shorten or remove the retention before it expires.
+    ///
+    /// Maps to `COMPLIANCE` in S3 and `Locked` in Azure.
+    Compliance,
+}
+
+/// Retention protects an object from being overwritten or deleted until
+/// given time, a.k.a, WORM (write once, read many).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Retention {
+    mode: RetentionMode,
+    retain_until: DateTime<Utc>,
+}
+
+impl Retention {
+    /// Create a new retention.
+    pub fn new(mode: RetentionMode, retain_until: DateTime<Utc>) -> Self {
+        Self { mode, retain_until }
+    }
+
+    /// Get the mode of retention.
+    pub fn mode(&self) -> RetentionMode {
+        self.mode
+    }
+
+    /// Get the time that retention expires.
+    pub fn retain_until(&self) -> DateTime<Utc> {
+        self.retain_until
+    }
+}
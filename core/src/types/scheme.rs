@@ -83,6 +83,8 @@ pub enum Scheme {
     MiniMoka,
     /// [moka][crate::services::Moka]: moka backend support.
     Moka,
+    /// [oci][crate::services::Oci]: OCI registry services.
+    Oci,
     /// [obs][crate::services::Obs]: Huawei Cloud OBS services.
     Obs,
     /// [onedrive][crate::services::Onedrive]: Microsoft OneDrive services.
@@ -219,6 +221,8 @@ impl Scheme {
             Scheme::Mysql,
             #[cfg(feature = "services-obs")]
             Scheme::Obs,
+            #[cfg(feature = "services-oci")]
+            Scheme::Oci,
             #[cfg(feature = "services-onedrive")]
             Scheme::Onedrive,
             #[cfg(feature = "services-postgresql")]
@@ -314,6 +318,7 @@ impl FromStr for Scheme {
             "mini_moka" => Ok(Scheme::MiniMoka),
             "moka" => Ok(Scheme::Moka),
             "obs" => Ok(Scheme::Obs),
+            "oci" => Ok(Scheme::Oci),
             "onedrive" => Ok(Scheme::Onedrive),
             "persy" => Ok(Scheme::Persy),
             "postgresql" => Ok(Scheme::Postgresql),
@@ -367,6 +372,7 @@ impl From<Scheme> for &'static str {
             Scheme::MiniMoka => "mini_moka",
             Scheme::Moka => "moka",
             Scheme::Obs => "obs",
+            Scheme::Oci => "oci",
             Scheme::Onedrive => "onedrive",
             Scheme::Persy => "persy",
             Scheme::Postgresql => "postgresql",
@@ -0,0 +1,416 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::raw::*;
+
+/// OperatorStats is a snapshot of the I/O statistics of an
+/// [`Operator`][crate::Operator], returned by
+/// [`Operator::stats`][crate::Operator::stats].
+///
+/// Statistics are collected by OpenDAL itself, so they are available even
+/// if applications don't run metrics systems like Prometheus. It's useful
+/// to print an I/O summary at the end of a job:
+///
+/// ```
+/// use opendal::Operator;
+///
+/// fn report(op: &Operator) {
+///     println!("{}", op.stats());
+/// }
+/// ```
+///
+/// # Notes
+///
+/// Operations are recorded at the service level, which means every retry
+/// will be counted. The duration of `read`, `write` and `list` only covers
+/// the call itself, not the time spent on the returned reader, writer or
+/// lister.
+#[derive(Debug, Clone, Default)]
+pub struct OperatorStats {
+    operations: HashMap<Operation, OperationStats>,
+    read_bytes: u64,
+    written_bytes: u64,
+    elapsed: Duration,
+    cost: CostStats,
+}
+
+impl OperatorStats {
+    /// Get the statistics of given operation.
+    pub fn operation(&self, op: Operation) -> OperationStats {
+        self.operations.get(&op).copied().unwrap_or_default()
+    }
+
+    /// Iterate over all operations that have been called.
+    pub fn operations(&self) -> impl Iterator<Item = (Operation, OperationStats)> + '_ {
+        self.operations.iter().map(|(k, v)| (*k, *v))
+    }
+
+    /// Get the total bytes read from readers.
+    pub fn read_bytes(&self) -> u64 {
+        self.read_bytes
+    }
+
+    /// Get the total bytes written into writers.
+    pub fn written_bytes(&self) -> u64 {
+        self.written_bytes
+    }
+
+    /// Get the time elapsed since statistics started or were reset last.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Get the estimated cost of requests and egress.
+    ///
+    /// Cost is only estimated while [`CostLayer`][crate::layers::CostLayer]
+    /// is used, otherwise it will be zero.
+    pub fn cost(&self) -> CostStats {
+        self.cost
+    }
+}
+
+impl Display for OperatorStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "elapsed={:?} read_bytes={} written_bytes={}",
+            self.elapsed, self.read_bytes, self.written_bytes
+        )?;
+        if self.cost != CostStats::default() {
+            write!(
+                f,
+                " class_a_requests={} class_b_requests={} egress_bytes={} estimated_cost={:.6}",
+                self.cost.class_a_requests,
+                self.cost.class_b_requests,
+                self.cost.egress_bytes,
+                self.cost.estimated_cost
+            )?;
+        }
+
+        let mut ops: Vec<_> = self.operations().collect();
+        ops.sort_by_key(|(op, _)| op.into_static());
+        for (op, stats) in ops {
+            write!(
+                f,
+                "\n{}: count={} errors={} duration={:?}",
+                op, stats.count, stats.errors, stats.duration
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// OperationStats is the statistics of one operation in [`OperatorStats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OperationStats {
+    count: u64,
+    errors: u64,
+    duration: Duration,
+}
+
+impl OperationStats {
+    /// Get the count of calls.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Get the count of calls that returned errors.
+    pub fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    /// Get the total duration of all calls.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Get the average duration of calls.
+    pub fn average(&self) -> Duration {
+        match self.count {
+            0 => Duration::ZERO,
+            n => self.duration / n as u32,
+        }
+    }
+}
+
+/// RequestClass is the pricing class of requests used by
+/// [`CostModel`][crate::layers::CostModel].
+///
+/// Most services charge requests in two classes, like `Class A` and
+/// `Class B` of GCS, `PUT` and `GET` of S3, `Write` and `Read` of Azblob.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RequestClass {
+    /// Requests that change or list data, like `write`, `copy` and `list`.
+    A,
+    /// Requests that read data, like `read` and `stat`.
+    B,
+    /// Requests that are free, like `delete`.
+    Free,
+}
+
+/// CostStats is the estimated cost in [`OperatorStats`], collected by
+/// [`CostLayer`][crate::layers::CostLayer].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CostStats {
+    class_a_requests: u64,
+    class_b_requests: u64,
+    egress_bytes: u64,
+    estimated_cost: f64,
+}
+
+impl CostStats {
+    /// Get the count of class A requests, like `write` and `list`.
+    pub fn class_a_requests(&self) -> u64 {
+        self.class_a_requests
+    }
+
+    /// Get the count of class B requests, like `read` and `stat`.
+    pub fn class_b_requests(&self) -> u64 {
+        self.class_b_requests
+    }
+
+    /// Get the bytes read out of the service.
+    pub fn egress_bytes(&self) -> u64 {
+        self.egress_bytes
+    }
+
+    /// Get the estimated cost in the currency of given cost model.
+    pub fn estimated_cost(&self) -> f64 {
+        self.estimated_cost
+    }
+}
+
+/// All operations, the index of operation is its discriminant.
+const OPERATIONS: [Operation; 27] = [
+    Operation::Info,
+    Operation::CreateDir,
+    Operation::Read,
+    Operation::Write,
+    Operation::Copy,
+    Operation::Rename,
+    Operation::Stat,
+    Operation::Delete,
+    Operation::List,
+    Operation::Batch,
+    Operation::Presign,
+    Operation::CreateSymlink,
+    Operation::SetPermissions,
+    Operation::CreateSnapshot,
+    Operation::Lease,
+    Operation::ListRoots,
+    Operation::Concat,
+    Operation::BlockingCreateDir,
+    Operation::BlockingRead,
+    Operation::BlockingWrite,
+    Operation::BlockingCopy,
+    Operation::BlockingRename,
+    Operation::BlockingStat,
+    Operation::BlockingDelete,
+    Operation::BlockingList,
+    Operation::BlockingCreateSymlink,
+    Operation::BlockingSetPermissions,
+];
+
+/// StatsCollector collects statistics for [`OperatorStats`].
+///
+/// It's used by every operator, so all counters are atomics to avoid
+/// contention between concurrent operations.
+#[derive(Debug)]
+pub(crate) struct StatsCollector {
+    operations: [OperationCounter; OPERATIONS.len()],
+    read_bytes: AtomicU64,
+    written_bytes: AtomicU64,
+    /// The time statistics started in nanos since `created_at`.
+    created_at: Instant,
+    started_at: AtomicU64,
+
+    class_a_requests: AtomicU64,
+    class_b_requests: AtomicU64,
+    egress_bytes: AtomicU64,
+    /// The bits of estimated cost in `f64`.
+    estimated_cost: AtomicU64,
+}
+
+#[derive(Debug, Default)]
+struct OperationCounter {
+    count: AtomicU64,
+    errors: AtomicU64,
+    /// The total duration in nanos.
+    duration: AtomicU64,
+}
+
+impl Default for StatsCollector {
+    fn default() -> Self {
+        Self {
+            operations: Default::default(),
+            read_bytes: AtomicU64::new(0),
+            written_bytes: AtomicU64::new(0),
+            created_at: Instant::now(),
+            started_at: AtomicU64::new(0),
+            class_a_requests: AtomicU64::new(0),
+            class_b_requests: AtomicU64::new(0),
+            egress_bytes: AtomicU64::new(0),
+            estimated_cost: AtomicU64::new(0f64.to_bits()),
+        }
+    }
+}
+
+impl StatsCollector {
+    /// Record a call of given operation which started at `start`.
+    pub(crate) fn record(&self, op: Operation, start: Instant, ok: bool) {
+        let duration = start.elapsed().as_nanos() as u64;
+
+        let counter = &self.operations[op as usize];
+        counter.count.fetch_add(1, Ordering::Relaxed);
+        counter.duration.fetch_add(duration, Ordering::Relaxed);
+        if !ok {
+            counter.errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn add_read_bytes(&self, n: usize) {
+        self.read_bytes.fetch_add(n as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_written_bytes(&self, n: usize) {
+        self.written_bytes.fetch_add(n as u64, Ordering::Relaxed);
+    }
+
+    /// Record a request of given class and its estimated cost.
+    pub(crate) fn add_request_cost(&self, class: RequestClass, cost: f64) {
+        match class {
+            RequestClass::A => self.class_a_requests.fetch_add(1, Ordering::Relaxed),
+            RequestClass::B => self.class_b_requests.fetch_add(1, Ordering::Relaxed),
+            RequestClass::Free => 0,
+        };
+        self.add_estimated_cost(cost);
+    }
+
+    /// Record bytes read out of the service and their estimated cost.
+    pub(crate) fn add_egress_cost(&self, n: usize, cost: f64) {
+        self.egress_bytes.fetch_add(n as u64, Ordering::Relaxed);
+        self.add_estimated_cost(cost);
+    }
+
+    fn add_estimated_cost(&self, cost: f64) {
+        if cost == 0.0 {
+            return;
+        }
+        let _ = self
+            .estimated_cost
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |v| {
+                Some((f64::from_bits(v) + cost).to_bits())
+            });
+    }
+
+    pub(crate) fn snapshot(&self) -> OperatorStats {
+        let mut operations = HashMap::new();
+        for (op, counter) in OPERATIONS.iter().zip(&self.operations) {
+            let count = counter.count.load(Ordering::Relaxed);
+            if count == 0 {
+                continue;
+            }
+            operations.insert(
+                *op,
+                OperationStats {
+                    count,
+                    errors: counter.errors.load(Ordering::Relaxed),
+                    duration: Duration::from_nanos(counter.duration.load(Ordering::Relaxed)),
+                },
+            );
+        }
+
+        let started_at = Duration::from_nanos(self.started_at.load(Ordering::Relaxed));
+        OperatorStats {
+            operations,
+            read_bytes: self.read_bytes.load(Ordering::Relaxed),
+            written_bytes: self.written_bytes.load(Ordering::Relaxed),
+            elapsed: self.created_at.elapsed().saturating_sub(started_at),
+            cost: CostStats {
+                class_a_requests: self.class_a_requests.load(Ordering::Relaxed),
+                class_b_requests: self.class_b_requests.load(Ordering::Relaxed),
+                egress_bytes: self.egress_bytes.load(Ordering::Relaxed),
+                estimated_cost: f64::from_bits(self.estimated_cost.load(Ordering::Relaxed)),
+            },
+        }
+    }
+
+    pub(crate) fn reset(&self) {
+        for counter in &self.operations {
+            counter.count.store(0, Ordering::Relaxed);
+            counter.errors.store(0, Ordering::Relaxed);
+            counter.duration.store(0, Ordering::Relaxed);
+        }
+        self.read_bytes.store(0, Ordering::Relaxed);
+        self.written_bytes.store(0, Ordering::Relaxed);
+        self.started_at.store(
+            self.created_at.elapsed().as_nanos() as u64,
+            Ordering::Relaxed,
+        );
+        self.class_a_requests.store(0, Ordering::Relaxed);
+        self.class_b_requests.store(0, Ordering::Relaxed);
+        self.egress_bytes.store(0, Ordering::Relaxed);
+        self.estimated_cost.store(0f64.to_bits(), Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operations() {
+        for (idx, op) in OPERATIONS.iter().enumerate() {
+            assert_eq!(*op as usize, idx, "{op}");
+        }
+        assert_eq!(
+            OPERATIONS.len(),
+            Operation::BlockingSetPermissions as usize + 1
+        );
+    }
+
+    #[test]
+    fn test_stats_collector() {
+        let stats = StatsCollector::default();
+        stats.record(Operation::Read, Instant::now(), true);
+        stats.record(Operation::Read, Instant::now(), false);
+        stats.add_request_cost(RequestClass::A, 0.5);
+        stats.add_egress_cost(10, 0.25);
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.operation(Operation::Read).count(), 2);
+        assert_eq!(snapshot.operation(Operation::Read).errors(), 1);
+        assert_eq!(snapshot.operations().count(), 1);
+        assert_eq!(snapshot.cost().class_a_requests(), 1);
+        assert_eq!(snapshot.cost().egress_bytes(), 10);
+        assert_eq!(snapshot.cost().estimated_cost(), 0.75);
+
+        stats.reset();
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.operations().count(), 0);
+        assert_eq!(snapshot.cost(), CostStats::default());
+    }
+}
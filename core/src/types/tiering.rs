@@ -0,0 +1,462 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+use futures::TryStreamExt;
+
+use crate::*;
+
+/// The user metadata key to keep the original last modified time of files
+/// whose last modified time will be reset by transitions, like s3.
+pub(crate) const ORIGINAL_LAST_MODIFIED: &str = "opendal-last-modified";
+
+/// TierRule decides which storage class files should be transitioned to.
+///
+/// All conditions of a rule must be matched, a rule without conditions
+/// matches all files.
+#[derive(Debug, Clone)]
+pub struct TierRule {
+    storage_class: String,
+    older_than: Option<Duration>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    tags: Vec<(String, String)>,
+}
+
+impl TierRule {
+    /// Create a rule that transitions files to given storage class, like
+    /// `STANDARD_IA` of s3 or `Cool` of azblob.
+    pub fn new(storage_class: &str) -> Self {
+        Self {
+            storage_class: storage_class.to_string(),
+            older_than: None,
+            min_size: None,
+            max_size: None,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Only match files that last modified before given age.
+    pub fn older_than(mut self, age: Duration) -> Self {
+        self.older_than = Some(age);
+        self
+    }
+
+    /// Only match files whose size is larger than or equal to given size.
+    pub fn min_size(mut self, size: u64) -> Self {
+        self.min_size = Some(size);
+        self
+    }
+
+    /// Only match files whose size is smaller than or equal to given size.
+    pub fn max_size(mut self, size: u64) -> Self {
+        self.max_size = Some(size);
+        self
+    }
+
+    /// Only match files that have given tag.
+    ///
+    /// Can be called multiple times, all tags must be matched.
+    pub fn with_tag(mut self, key: &str, value: &str) -> Self {
+        self.tags.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Check if rule matches the size and age of file, tags are ignored.
+    fn matches_stat(
+        &self,
+        size: u64,
+        last_modified: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        if matches!(self.min_size, Some(v) if size < v) {
+            return false;
+        }
+        if matches!(self.max_size, Some(v) if size > v) {
+            return false;
+        }
+        if let Some(age) = self.older_than {
+            let elapsed = match last_modified {
+                Some(v) => (now - v).to_std().unwrap_or_default(),
+                // We don't know how old the file is, skip it to be safe.
+                None => return false,
+            };
+            if elapsed < age {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn matches_tags(&self, meta: &Metadata) -> bool {
+        if self.tags.is_empty() {
+            return true;
+        }
+        let tags = if meta.contains_metakey(Metakey::Tags) {
+            meta.tags()
+        } else {
+            None
+        };
+        let Some(tags) = tags else {
+            return false;
+        };
+        self.tags.iter().all(|(k, v)| tags.get(k) == Some(v))
+    }
+}
+
+/// The last modified time used to calculate the age of file, prefer the
+/// original one kept by previous transitions.
+fn age_base(meta: &Metadata) -> Option<DateTime<Utc>> {
+    let original = if meta.contains_metakey(Metakey::UserMetadata) {
+        meta.user_metadata()
+            .and_then(|v| v.get(ORIGINAL_LAST_MODIFIED))
+            .and_then(|v| DateTime::parse_from_rfc3339(v).ok())
+            .map(|v| v.with_timezone(&Utc))
+    } else {
+        None
+    };
+    original.or_else(|| meta.last_modified())
+}
+
+/// TierMover scans files under a dir and transitions them between storage
+/// classes (a.k.a. access tiers) based on given rules, like `Hot → Cool →
+/// Archive` of azblob or `STANDARD → STANDARD_IA → GLACIER` of s3.
+///
+/// - Rules are evaluated in order and the last matched rule wins, so rules
+///   can be listed from hot to cold.
+/// - Files are transitioned by [`Operator::set_storage_class`] which uses
+///   native tiering APIs of services.
+/// - Ages are calculated from the original last modified time if it was
+///   kept by previous transitions, so rules could be chained on s3.
+/// - Tags are matched against [`Metadata::tags`] instead of user metadata.
+/// - Failed transitions will be recorded in [`TierReport`] instead of
+///   stopping the whole run.
+/// - Dry run reports the transitions without applying them.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use opendal::Operator;
+/// use opendal::Result;
+/// use opendal::TierMover;
+/// use opendal::TierRule;
+///
+/// async fn test(op: Operator) -> Result<()> {
+///     let day = Duration::from_secs(24 * 3600);
+///     let report = TierMover::new(op)
+///         .with_rule(TierRule::new("STANDARD_IA").older_than(30 * day))
+///         .with_rule(TierRule::new("GLACIER").older_than(180 * day))
+///         .with_dry_run(true)
+///         .run("logs/")
+///         .await?;
+///
+///     for t in report.transitions() {
+///         println!("{}: {:?} -> {}", t.path(), t.from(), t.to());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TierMover {
+    op: Operator,
+    rules: Vec<TierRule>,
+    dry_run: bool,
+}
+
+impl TierMover {
+    /// Create a new tier mover on given operator.
+    pub fn new(op: Operator) -> Self {
+        Self {
+            op,
+            rules: Vec::new(),
+            dry_run: false,
+        }
+    }
+
+    /// Add a rule, the last matched rule wins.
+    pub fn with_rule(mut self, rule: TierRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Only report the transitions without applying them.
+    ///
+    /// Default to `false`.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Scan all files under given dir recursively and transition them.
+    pub async fn run(&self, path: &str) -> Result<TierReport> {
+        let now = Utc::now();
+        let mut report = TierReport::default();
+
+        let mut lister = self
+            .op
+            .lister_with(path)
+            .recursive(true)
+            .metakey(Metakey::Mode | Metakey::ContentLength | Metakey::LastModified)
+            .await?;
+        while let Some(entry) = lister.try_next().await? {
+            if !entry.metadata().is_file() {
+                continue;
+            }
+            report.scanned += 1;
+
+            if let Some(t) = self.plan(entry.path(), entry.metadata(), now).await? {
+                report.transitions.push(self.apply(t).await);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Decide the transition of given file, returns `None` if no rule
+    /// matched or file is already in the target storage class.
+    async fn plan(
+        &self,
+        path: &str,
+        listed: &Metadata,
+        now: DateTime<Utc>,
+    ) -> Result<Option<TierTransition>> {
+        // Files in the class of our rules could have been transitioned
+        // before, and their last modified time listed may have been reset.
+        // Fetch the full metadata to read the original one.
+        let mut stat = None;
+        if self.maybe_transitioned(listed) {
+            stat = Some(self.stat(path).await?);
+        }
+
+        let meta = stat.as_ref().unwrap_or(listed);
+        let size = meta.content_length();
+        let last_modified = age_base(meta);
+
+        let candidates: Vec<&TierRule> = self
+            .rules
+            .iter()
+            .filter(|r| r.matches_stat(size, last_modified, now))
+            .collect();
+        if candidates.is_empty() {
+            return Ok(None);
+        }
+
+        // Fetch the full metadata only if list doesn't return enough info.
+        let need_tags = candidates.iter().any(|r| !r.tags.is_empty());
+        if stat.is_none() && (need_tags || !listed.contains_metakey(Metakey::StorageClass)) {
+            stat = Some(self.stat(path).await?);
+        }
+        let meta = stat.as_ref().unwrap_or(listed);
+
+        let Some(rule) = candidates.into_iter().rev().find(|r| r.matches_tags(meta)) else {
+            return Ok(None);
+        };
+        let from = if meta.contains_metakey(Metakey::StorageClass) {
+            meta.storage_class().map(|v| v.to_string())
+        } else {
+            None
+        };
+        if matches!(&from, Some(v) if v.eq_ignore_ascii_case(&rule.storage_class)) {
+            return Ok(None);
+        }
+
+        Ok(Some(TierTransition {
+            path: path.to_string(),
+            size,
+            from,
+            to: rule.storage_class.clone(),
+            applied: false,
+            error: None,
+        }))
+    }
+
+    /// Check if the file is in the storage class of any rule with age
+    /// condition, which means it may have been transitioned before.
+    fn maybe_transitioned(&self, meta: &Metadata) -> bool {
+        if !meta.contains_metakey(Metakey::StorageClass) {
+            return false;
+        }
+        let Some(class) = meta.storage_class() else {
+            return false;
+        };
+        self.rules
+            .iter()
+            .any(|r| r.older_than.is_some() && r.storage_class.eq_ignore_ascii_case(class))
+    }
+
+    /// Stat the file with tags if any rule requires them.
+    async fn stat(&self, path: &str) -> Result<Metadata> {
+        if self.rules.iter().any(|r| !r.tags.is_empty()) {
+            self.op
+                .stat_with(path)
+                .fields(Metakey::Complete | Metakey::Tags)
+                .await
+        } else {
+            self.op.stat(path).await
+        }
+    }
+
+    async fn apply(&self, mut t: TierTransition) -> TierTransition {
+        if self.dry_run {
+            return t;
+        }
+
+        match self.op.set_storage_class(&t.path, &t.to).await {
+            Ok(()) => t.applied = true,
+            Err(err) => t.error = Some(err),
+        }
+        t
+    }
+}
+
+/// TierReport is the result of [`TierMover::run`].
+#[derive(Debug, Default)]
+pub struct TierReport {
+    scanned: usize,
+    transitions: Vec<TierTransition>,
+}
+
+impl TierReport {
+    /// The number of files that have been scanned.
+    pub fn scanned(&self) -> usize {
+        self.scanned
+    }
+
+    /// All planned transitions, including failed ones.
+    pub fn transitions(&self) -> &[TierTransition] {
+        &self.transitions
+    }
+
+    /// Transitions that failed to apply.
+    pub fn failed(&self) -> impl Iterator<Item = &TierTransition> {
+        self.transitions.iter().filter(|t| t.error.is_some())
+    }
+}
+
+/// TierTransition is a transition of a file planned by [`TierMover`].
+#[derive(Debug)]
+pub struct TierTransition {
+    path: String,
+    size: u64,
+    from: Option<String>,
+    to: String,
+    applied: bool,
+    error: Option<Error>,
+}
+
+impl TierTransition {
+    /// Path of the file.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Size of the file.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Current storage class of the file, `None` if unknown.
+    pub fn from(&self) -> Option<&str> {
+        self.from.as_deref()
+    }
+
+    /// Target storage class of the file.
+    pub fn to(&self) -> &str {
+        &self.to
+    }
+
+    /// Check if the transition has been applied, always `false` in dry run.
+    pub fn is_applied(&self) -> bool {
+        self.applied
+    }
+
+    /// The error of applying this transition.
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::services::Memory;
+
+    #[test]
+    fn test_tier_rule_matches_tags() {
+        let rule = TierRule::new("Cool").with_tag("team", "infra");
+        let tags = HashMap::from([("team".to_string(), "infra".to_string())]);
+
+        let meta = Metadata::new(EntryMode::FILE).with_tags(tags.clone());
+        assert!(rule.matches_tags(&meta));
+
+        // User metadata is not tags.
+        let meta = Metadata::new(EntryMode::FILE).with_user_metadata(tags);
+        assert!(!rule.matches_tags(&meta));
+    }
+
+    #[test]
+    fn test_age_base_prefers_original_last_modified() {
+        let original = DateTime::parse_from_rfc3339("2020-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut meta = Metadata::new(EntryMode::FILE).with_last_modified(Utc::now());
+        assert_ne!(age_base(&meta), Some(original));
+
+        meta.set_user_metadata(HashMap::from([(
+            ORIGINAL_LAST_MODIFIED.to_string(),
+            original.to_rfc3339(),
+        )]));
+        assert_eq!(age_base(&meta), Some(original));
+    }
+
+    #[tokio::test]
+    async fn test_tier_mover() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        op.write("data/small", vec![0; 10]).await?;
+        op.write("data/large", vec![0; 1024]).await?;
+
+        let mover = TierMover::new(op)
+            .with_rule(TierRule::new("Cool").min_size(100))
+            .with_rule(TierRule::new("Archive").min_size(1000))
+            .with_rule(TierRule::new("Cold").older_than(Duration::from_secs(3600)));
+
+        let report = mover.clone().with_dry_run(true).run("data/").await?;
+        assert_eq!(report.scanned(), 2);
+        assert_eq!(report.transitions().len(), 1);
+        let t = &report.transitions()[0];
+        assert_eq!(t.path(), "data/large");
+        assert_eq!(t.to(), "Archive");
+        assert!(!t.is_applied());
+
+        // Memory doesn't support storage class.
+        let report = mover.run("data/").await?;
+        let failed: Vec<_> = report.failed().collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(
+            failed[0].error().map(|e| e.kind()),
+            Some(ErrorKind::Unsupported)
+        );
+        Ok(())
+    }
+}
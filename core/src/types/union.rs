@@ -0,0 +1,208 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashSet;
+
+use bytes::Bytes;
+
+use crate::*;
+
+/// UnionOperator overlays several operators like overlayfs, which is
+/// useful for layered datasets like a base snapshot with deltas.
+///
+/// - `stat` and `read` resolve in the order of layers, the first layer
+///   that contains the path wins.
+/// - `list` merges entries of all layers, entries in upper layers will
+///   shadow the ones with the same path in lower layers.
+/// - `write` and `delete` only go to the top layer.
+///
+/// # Notes
+///
+/// Deleting a path only removes it from the top layer, the same path in
+/// lower layers will be visible again.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::Operator;
+/// use opendal::Result;
+/// use opendal::UnionOperator;
+///
+/// async fn test(delta: Operator, base: Operator) -> Result<()> {
+///     let op = UnionOperator::new(delta).with_lower(base);
+///
+///     op.write("path/to/file", "Hello, World!").await?;
+///     let entries = op.list("path/to/").await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct UnionOperator {
+    /// Layers from top to bottom.
+    layers: Vec<Operator>,
+}
+
+impl UnionOperator {
+    /// Create a union operator whose top layer is given operator.
+    pub fn new(top: Operator) -> Self {
+        Self { layers: vec![top] }
+    }
+
+    /// Add a layer below all existing layers.
+    pub fn with_lower(mut self, op: Operator) -> Self {
+        self.layers.push(op);
+        self
+    }
+
+    /// Get the top layer that all writes go to.
+    pub fn top(&self) -> &Operator {
+        &self.layers[0]
+    }
+
+    /// Get all layers from top to bottom.
+    pub fn layers(&self) -> &[Operator] {
+        &self.layers
+    }
+
+    /// Get the metadata of path from the first layer that contains it.
+    pub async fn stat(&self, path: &str) -> Result<Metadata> {
+        self.resolve(path).await.map(|(_, meta)| meta)
+    }
+
+    /// Check if the path exists in any layer.
+    pub async fn is_exist(&self, path: &str) -> Result<bool> {
+        match self.resolve(path).await {
+            Ok(_) => Ok(true),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Read the whole path from the first layer that contains it.
+    pub async fn read(&self, path: &str) -> Result<Vec<u8>> {
+        let (op, _) = self.resolve(path).await?;
+        op.read(path).await
+    }
+
+    /// Create a reader of path from the first layer that contains it.
+    pub async fn reader(&self, path: &str) -> Result<Reader> {
+        let (op, _) = self.resolve(path).await?;
+        op.reader(path).await
+    }
+
+    /// List entries of dir in all layers.
+    ///
+    /// Returns `NotFound` if the dir doesn't exist in any layer.
+    pub async fn list(&self, path: &str) -> Result<Vec<Entry>> {
+        let mut seen = HashSet::new();
+        let mut entries = vec![];
+        let mut found = false;
+
+        for op in &self.layers {
+            let layer = match op.list(path).await {
+                Ok(layer) => layer,
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            };
+            found = true;
+
+            for entry in layer {
+                if seen.insert(entry.path().to_string()) {
+                    entries.push(entry);
+                }
+            }
+        }
+
+        if !found {
+            return Err(
+                Error::new(ErrorKind::NotFound, "path not found in any layer")
+                    .with_operation("UnionOperator::list")
+                    .with_context("path", path),
+            );
+        }
+        Ok(entries)
+    }
+
+    /// Write bytes into path of the top layer.
+    pub async fn write(&self, path: &str, bs: impl Into<Bytes>) -> Result<()> {
+        self.top().write(path, bs).await
+    }
+
+    /// Create a writer of path in the top layer.
+    pub async fn writer(&self, path: &str) -> Result<Writer> {
+        self.top().writer(path).await
+    }
+
+    /// Delete path from the top layer.
+    pub async fn delete(&self, path: &str) -> Result<()> {
+        self.top().delete(path).await
+    }
+
+    /// Find the first layer that contains path.
+    async fn resolve(&self, path: &str) -> Result<(&Operator, Metadata)> {
+        for op in &self.layers {
+            match op.stat(path).await {
+                Ok(meta) => return Ok((op, meta)),
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(
+            Error::new(ErrorKind::NotFound, "path not found in any layer")
+                .with_operation("UnionOperator::stat")
+                .with_context("path", path),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_union() -> Result<()> {
+        let base = Operator::new(Memory::default())?.finish();
+        let delta = Operator::new(Memory::default())?.finish();
+        base.write("data/a", "base-a").await?;
+        base.write("data/b", "base-b").await?;
+
+        let op = UnionOperator::new(delta.clone()).with_lower(base.clone());
+        op.write("data/b", "delta-b").await?;
+        op.write("data/c", "delta-c").await?;
+
+        assert_eq!(op.read("data/a").await?, b"base-a");
+        assert_eq!(op.read("data/b").await?, b"delta-b");
+        assert_eq!(base.read("data/b").await?, b"base-b");
+
+        let mut paths: Vec<_> = op
+            .list("data/")
+            .await?
+            .into_iter()
+            .filter(|v| v.metadata().is_file())
+            .map(|v| v.path().to_string())
+            .collect();
+        paths.sort();
+        assert_eq!(paths, vec!["data/a", "data/b", "data/c"]);
+
+        op.delete("data/b").await?;
+        assert_eq!(op.read("data/b").await?, b"base-b");
+        assert!(!op.is_exist("data/d").await?);
+        Ok(())
+    }
+}
@@ -16,12 +16,15 @@
 // under the License.
 
 use std::io;
+use std::mem;
 use std::pin::Pin;
+use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
 
 use bytes::Bytes;
 use futures::AsyncWrite;
+use futures::Sink;
 use futures::TryStreamExt;
 
 use crate::raw::oio::Write;
@@ -36,7 +39,9 @@ use crate::*;
 /// ## Notes
 ///
 /// Please make sure either `close` or `abort` has been called before
-/// dropping the writer otherwise the data could be lost.
+/// dropping the writer otherwise the data could be lost. Data that has been
+/// uploaded like multipart parts will be left in services unless
+/// [`WriterCancelPolicy::Abort`] is set.
 ///
 /// ## Usage
 ///
@@ -73,6 +78,39 @@ use crate::*;
 ///   creating writer with `append` enabled.
 pub struct Writer {
     inner: oio::Writer,
+    #[cfg(feature = "digest")]
+    digest: Option<DigestHandle>,
+
+    cancel_policy: WriterCancelPolicy,
+    /// An operation has been started but not finished yet, it will be left
+    /// as `true` if the operation future has been dropped.
+    in_flight: bool,
+    /// The writer has been closed or aborted.
+    finished: bool,
+}
+
+/// WriterCancelPolicy decides how [`Writer`] behaves while it's cancelled,
+/// either dropped without `close` or `abort`, or an in-flight `write`,
+/// `close` or `abort` future dropped like in `tokio::select!`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WriterCancelPolicy {
+    /// Do nothing, data that has been uploaded could be leaked in services
+    /// and following operations will continue from an unknown state.
+    #[default]
+    Ignore,
+    /// Mark the writer as poisoned once an in-flight operation has been
+    /// cancelled, all following operations will return
+    /// [`ErrorKind::Unexpected`] instead of committing partial content.
+    ///
+    /// Users can still call [`Writer::abort`] to clean up.
+    Poison,
+    /// Poison the writer like [`WriterCancelPolicy::Poison`], and abort it
+    /// in a background task while it's dropped without `close` or `abort`.
+    ///
+    /// The background task will be spawned in current tokio runtime, nothing
+    /// will happen if there is no runtime.
+    Abort,
 }
 
 impl Writer {
@@ -84,20 +122,124 @@ impl Writer {
     /// We don't want to expose those details to users so keep this function
     /// in crate only.
     pub(crate) async fn create(acc: FusedAccessor, path: &str, op: OpWrite) -> Result<Self> {
+        #[cfg(feature = "digest")]
+        let digest = op.digest();
+        let cancel_policy = op.cancel_policy();
         let (_, w) = acc.write(path, op).await?;
 
-        Ok(Writer { inner: w })
+        #[cfg(feature = "digest")]
+        let (w, digest): (oio::Writer, _) = match digest {
+            Some(algorithm) => {
+                let (w, handle) = DigestWriter::new(w, algorithm);
+                (Box::new(w), Some(handle))
+            }
+            None => (w, None),
+        };
+
+        Ok(Writer {
+            inner: w,
+            #[cfg(feature = "digest")]
+            digest,
+            cancel_policy,
+            in_flight: false,
+            finished: false,
+        })
+    }
+
+    /// Mark an operation as started.
+    ///
+    /// Returns error if a previous operation has been cancelled and the
+    /// writer has been poisoned.
+    fn start(&mut self, op: &'static str) -> Result<()> {
+        if self.in_flight && self.cancel_policy != WriterCancelPolicy::Ignore {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "writer is poisoned since a previous operation has been cancelled",
+            )
+            .with_operation(op));
+        }
+        self.in_flight = true;
+        Ok(())
+    }
+
+    /// Get the digest of all written content.
+    ///
+    /// Returns `None` if digest is not enabled by `writer_with().digest()`,
+    /// or the writer is not closed successfully yet.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use opendal::DigestAlgorithm;
+    /// use opendal::Operator;
+    ///
+    /// async fn digest_example(op: Operator) -> Result<()> {
+    ///     let mut w = op
+    ///         .writer_with("path/to/file")
+    ///         .digest(DigestAlgorithm::Sha256)
+    ///         .await?;
+    ///     w.write("Hello, World!").await?;
+    ///     w.close().await?;
+    ///
+    ///     let digest = w.digest().expect("digest must exist");
+    ///     println!("sha256 of file is {}", digest.to_hex());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "digest")]
+    pub fn digest(&self) -> Option<ContentDigest> {
+        self.digest.as_ref().and_then(|v| v.digest())
+    }
+
+    /// Wait until the writer is able to accept new data without growing
+    /// its in-flight buffer.
+    ///
+    /// Writers with concurrent uploads enabled will keep accepting data
+    /// while parts are in flight, producers can call `ready` before
+    /// generating more data to apply backpressure under slow networks.
+    /// Calling `write` without `ready` is still valid, it will wait inside.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// async fn ready_example(op: Operator) -> Result<()> {
+    ///     let mut w = op.writer_with("path/to/file").concurrent(4).await?;
+    ///     for _ in 0..16 {
+    ///         w.ready().await?;
+    ///         w.write(vec![0; 8 * 1024 * 1024]).await?;
+    ///     }
+    ///     w.close().await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn ready(&mut self) -> Result<()> {
+        self.inner.ready().await
+    }
+
+    /// Poll version of [`Writer::ready`].
+    pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx)
     }
 
     /// Write into inner writer.
     pub async fn write(&mut self, bs: impl Into<Bytes>) -> Result<()> {
+        self.start("Writer::write")?;
         let mut bs = bs.into();
-        while bs.remaining() > 0 {
-            let n = self.inner.write(&bs).await?;
-            bs.advance(n);
+        let res = async {
+            while bs.remaining() > 0 {
+                let n = self.inner.write(&bs).await?;
+                bs.advance(n);
+            }
+            Ok::<(), Error>(())
         }
+        .await;
 
-        Ok(())
+        self.in_flight = false;
+        res
     }
 
     /// Sink into writer.
@@ -134,17 +276,24 @@ impl Writer {
         S: futures::Stream<Item = Result<T>>,
         T: Into<Bytes>,
     {
+        self.start("Writer::sink")?;
         let mut sink_from = Box::pin(sink_from);
         let mut written = 0;
-        while let Some(bs) = sink_from.try_next().await? {
-            let mut bs = bs.into();
-            while bs.remaining() > 0 {
-                let n = self.inner.write(&bs).await?;
-                bs.advance(n);
-                written += n as u64;
+        let res = async {
+            while let Some(bs) = sink_from.try_next().await? {
+                let mut bs = bs.into();
+                while bs.remaining() > 0 {
+                    let n = self.inner.write(&bs).await?;
+                    bs.advance(n);
+                    written += n as u64;
+                }
             }
+            Ok::<u64, Error>(written)
         }
-        Ok(written)
+        .await;
+
+        self.in_flight = false;
+        res
     }
 
     /// Copy into writer.
@@ -195,7 +344,12 @@ impl Writer {
     /// Abort should only be called when the writer is not closed or
     /// aborted, otherwise an unexpected error could be returned.
     pub async fn abort(&mut self) -> Result<()> {
-        self.inner.abort().await
+        // Abort is always allowed so that poisoned writers can be cleaned up.
+        self.in_flight = true;
+        let res = self.inner.abort().await;
+        self.in_flight = false;
+        self.finished = res.is_ok();
+        res
     }
 
     /// Close the writer and make sure all data have been committed.
@@ -205,7 +359,110 @@ impl Writer {
     /// Close should only be called when the writer is not closed or
     /// aborted, otherwise an unexpected error could be returned.
     pub async fn close(&mut self) -> Result<()> {
-        self.inner.close().await
+        self.start("Writer::close")?;
+        let res = self.inner.close().await;
+        self.in_flight = false;
+        self.finished = res.is_ok();
+        res
+    }
+
+    /// Convert writer into a [`Sink`] of [`Bytes`].
+    ///
+    /// - `poll_flush` will make sure all sent bytes have been written.
+    /// - `poll_close` will flush and then close the writer, so users don't
+    ///   need to call [`Writer::close`] again.
+    ///
+    /// This makes it possible to use writer in codec pipelines like
+    /// `FramedWrite` or `StreamExt::forward`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use bytes::Bytes;
+    /// use futures::stream;
+    /// use futures::StreamExt;
+    /// use opendal::Operator;
+    ///
+    /// async fn into_sink_example(op: Operator) -> Result<()> {
+    ///     let w = op.writer("path/to/file").await?;
+    ///     let stream = stream::iter(vec![Bytes::from("hello"), Bytes::from("world")]).map(Ok);
+    ///     stream.forward(w.into_sink()).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn into_sink(mut self) -> WriterSink {
+        // The writer has been moved into sink, don't abort it while dropping.
+        self.finished = true;
+        WriterSink {
+            inner: mem::replace(&mut self.inner, Box::new(())),
+            buf: Bytes::new(),
+        }
+    }
+}
+
+impl Drop for Writer {
+    fn drop(&mut self) {
+        if self.finished || self.cancel_policy != WriterCancelPolicy::Abort {
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            log::warn!("writer is dropped without close or abort, but no runtime to abort it");
+            return;
+        };
+
+        let mut inner = mem::replace(&mut self.inner, Box::new(()));
+        handle.spawn(async move {
+            if let Err(err) = inner.abort().await {
+                log::warn!("abort dropped writer failed: {err}");
+            }
+        });
+    }
+}
+
+/// WriterSink is a [`Sink`] of [`Bytes`] returned by [`Writer::into_sink`].
+pub struct WriterSink {
+    inner: oio::Writer,
+    /// The bytes that have been sent but not written yet.
+    buf: Bytes,
+}
+
+impl WriterSink {
+    fn poll_write_buf(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        while self.buf.remaining() > 0 {
+            let n = ready!(self.inner.poll_write(cx, &self.buf))?;
+            self.buf.advance(n);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Sink<Bytes> for WriterSink {
+    type Error = Error;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        ready!(self.poll_write_buf(cx))?;
+        self.inner.poll_ready(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: Bytes) -> Result<()> {
+        debug_assert!(
+            self.buf.is_empty(),
+            "start_send must be called after poll_ready returns ready"
+        );
+
+        self.buf = item;
+        Ok(())
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.poll_write_buf(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        ready!(self.poll_write_buf(cx))?;
+        self.inner.poll_close(cx)
     }
 }
 
@@ -226,9 +483,10 @@ impl AsyncWrite for Writer {
     }
 
     fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
-        self.inner
-            .poll_close(cx)
-            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+        ready!(self.inner.poll_close(cx))
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        self.finished = true;
+        Poll::Ready(Ok(()))
     }
 }
 
@@ -248,16 +506,27 @@ impl tokio::io::AsyncWrite for Writer {
     }
 
     fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
-        self.inner
-            .poll_close(cx)
-            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+        ready!(self.inner.poll_close(cx))
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        self.finished = true;
+        Poll::Ready(Ok(()))
     }
 }
 
 /// BlockingWriter is designed to write data into given path in an blocking
 /// manner.
+///
+/// BlockingWriter implements [`io::Write`], so it can be used directly with
+/// crates that expect std traits like `tar` and `csv`.
+///
+/// ## Notes
+///
+/// `io::Write::flush` doesn't commit the data, please make sure
+/// [`BlockingWriter::close`] has been called after all data written.
 pub struct BlockingWriter {
     pub(crate) inner: oio::BlockingWriter,
+    #[cfg(feature = "digest")]
+    digest: Option<DigestHandle>,
 }
 
 impl BlockingWriter {
@@ -269,9 +538,33 @@ impl BlockingWriter {
     /// We don't want to expose those details to users so keep this function
     /// in crate only.
     pub(crate) fn create(acc: FusedAccessor, path: &str, op: OpWrite) -> Result<Self> {
+        #[cfg(feature = "digest")]
+        let digest = op.digest();
         let (_, w) = acc.blocking_write(path, op)?;
 
-        Ok(BlockingWriter { inner: w })
+        #[cfg(feature = "digest")]
+        let (w, digest): (oio::BlockingWriter, _) = match digest {
+            Some(algorithm) => {
+                let (w, handle) = DigestWriter::new(w, algorithm);
+                (Box::new(w), Some(handle))
+            }
+            None => (w, None),
+        };
+
+        Ok(BlockingWriter {
+            inner: w,
+            #[cfg(feature = "digest")]
+            digest,
+        })
+    }
+
+    /// Get the digest of all written content.
+    ///
+    /// Returns `None` if digest is not enabled by `writer_with().digest()`,
+    /// or the writer is not closed successfully yet.
+    #[cfg(feature = "digest")]
+    pub fn digest(&self) -> Option<ContentDigest> {
+        self.digest.as_ref().and_then(|v| v.digest())
     }
 
     /// Write into inner writer.
@@ -302,3 +595,189 @@ impl io::Write for BlockingWriter {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use futures::FutureExt;
+    use futures::SinkExt;
+
+    use super::*;
+    use crate::raw::oio::MultipartUploadPart;
+    use crate::raw::oio::MultipartUploadWrite;
+    use crate::raw::oio::MultipartUploadWriter;
+
+    #[derive(Default)]
+    struct MockUpload {
+        completed: AtomicBool,
+        aborted: AtomicBool,
+    }
+
+    #[async_trait]
+    impl MultipartUploadWrite for Arc<MockUpload> {
+        async fn write_once(&self, _: u64, _: AsyncBody) -> Result<()> {
+            self.completed.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn initiate_part(&self) -> Result<String> {
+            // Yield once so that the write could be cancelled while initiating.
+            tokio::task::yield_now().await;
+            Ok("upload".to_string())
+        }
+
+        async fn write_part(
+            &self,
+            _: &str,
+            part_number: usize,
+            _: u64,
+            _: AsyncBody,
+        ) -> Result<MultipartUploadPart> {
+            Ok(MultipartUploadPart {
+                part_number,
+                etag: part_number.to_string(),
+            })
+        }
+
+        async fn complete_part(&self, _: &str, _: &[MultipartUploadPart]) -> Result<()> {
+            self.completed.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn abort_part(&self, _: &str) -> Result<()> {
+            self.aborted.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn new_writer(upload: Arc<MockUpload>, cancel_policy: WriterCancelPolicy) -> Writer {
+        Writer {
+            inner: Box::new(MultipartUploadWriter::new(upload, 1)),
+            #[cfg(feature = "digest")]
+            digest: None,
+            cancel_policy,
+            in_flight: false,
+            finished: false,
+        }
+    }
+
+    /// Write twice with the second write cancelled while initiating upload.
+    async fn cancel_write(w: &mut Writer) -> Result<()> {
+        w.write("Hello, ").await?;
+        assert!(w.write("World!").now_or_never().is_none());
+        Ok(())
+    }
+
+    /// Give background tasks a chance to run.
+    async fn yield_many() {
+        for _ in 0..16 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_write_poisons_writer() -> Result<()> {
+        let upload = Arc::new(MockUpload::default());
+        let mut w = new_writer(upload.clone(), WriterCancelPolicy::Poison);
+        cancel_write(&mut w).await?;
+
+        let err = w.write("!").await.expect_err("must be poisoned");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        let err = w.close().await.expect_err("must be poisoned");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(!upload.completed.load(Ordering::SeqCst));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_abort_after_poison() -> Result<()> {
+        let upload = Arc::new(MockUpload::default());
+        let mut w = new_writer(upload.clone(), WriterCancelPolicy::Poison);
+        cancel_write(&mut w).await?;
+
+        // The cancelled upload must be aborted instead of panicking.
+        w.abort().await?;
+        assert!(upload.aborted.load(Ordering::SeqCst));
+        assert!(!upload.completed.load(Ordering::SeqCst));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_write_ignored() -> Result<()> {
+        let upload = Arc::new(MockUpload::default());
+        let mut w = new_writer(upload.clone(), WriterCancelPolicy::Ignore);
+        cancel_write(&mut w).await?;
+
+        w.close().await?;
+        assert!(upload.completed.load(Ordering::SeqCst));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_drop_without_close() -> Result<()> {
+        for (policy, aborted) in [
+            (WriterCancelPolicy::Ignore, false),
+            (WriterCancelPolicy::Poison, false),
+            (WriterCancelPolicy::Abort, true),
+        ] {
+            let upload = Arc::new(MockUpload::default());
+            let mut w = new_writer(upload.clone(), policy);
+            w.write("Hello, ").await?;
+            w.write("World!").await?;
+            drop(w);
+
+            yield_many().await;
+            assert_eq!(upload.aborted.load(Ordering::SeqCst), aborted, "{policy:?}");
+            assert!(!upload.completed.load(Ordering::SeqCst), "{policy:?}");
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_drop_cancelled_write() -> Result<()> {
+        let upload = Arc::new(MockUpload::default());
+        let mut w = new_writer(upload.clone(), WriterCancelPolicy::Abort);
+        cancel_write(&mut w).await?;
+        drop(w);
+
+        // The background abort must wait for the in-flight initiate.
+        yield_many().await;
+        assert!(upload.aborted.load(Ordering::SeqCst));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_drop_after_close() -> Result<()> {
+        let upload = Arc::new(MockUpload::default());
+        let mut w = new_writer(upload.clone(), WriterCancelPolicy::Abort);
+        w.write("Hello, World!").await?;
+        w.close().await?;
+        drop(w);
+
+        yield_many().await;
+        assert!(upload.completed.load(Ordering::SeqCst));
+        assert!(!upload.aborted.load(Ordering::SeqCst));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_writer_into_sink_flush_and_close() -> Result<()> {
+        let upload = Arc::new(MockUpload::default());
+        let mut sink = new_writer(upload.clone(), WriterCancelPolicy::Poison).into_sink();
+
+        sink.send(Bytes::from("Hello, ")).await?;
+        sink.feed(Bytes::from("World!")).await?;
+        sink.flush().await?;
+        // Flush only makes sure bytes are written, upload is not completed.
+        assert!(!upload.completed.load(Ordering::SeqCst));
+
+        sink.close().await?;
+        assert!(upload.completed.load(Ordering::SeqCst));
+        assert!(!upload.aborted.load(Ordering::SeqCst));
+        Ok(())
+    }
+}